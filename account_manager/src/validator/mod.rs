@@ -4,6 +4,7 @@ pub mod import;
 pub mod list;
 pub mod modify;
 pub mod recover;
+pub mod signing_audit_log;
 pub mod slashing_protection;
 
 use crate::{VALIDATOR_DIR_FLAG, VALIDATOR_DIR_FLAG_ALIAS};
@@ -48,6 +49,7 @@ pub fn cli_app() -> Command {
         .subcommand(list::cli_app())
         .subcommand(recover::cli_app())
         .subcommand(slashing_protection::cli_app())
+        .subcommand(signing_audit_log::cli_app())
         .subcommand(exit::cli_app())
 }
 
@@ -69,6 +71,9 @@ pub fn cli_run<E: EthSpec>(matches: &ArgMatches, env: Environment<E>) -> Result<
         Some((slashing_protection::CMD, matches)) => {
             slashing_protection::cli_run(matches, env, validator_base_dir)
         }
+        Some((signing_audit_log::CMD, matches)) => {
+            signing_audit_log::cli_run(matches, validator_base_dir)
+        }
         Some((exit::CMD, matches)) => exit::cli_run(matches, env),
         Some((unknown, _)) => Err(format!(
             "{} does not have a {} command. See --help",
@@ -0,0 +1,96 @@
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use signing_audit_log::{verify, SIGNING_AUDIT_LOG_DIR};
+use std::path::PathBuf;
+use std::str::FromStr;
+use types::Hash256;
+
+pub const CMD: &str = "signing-audit-log";
+pub const VERIFY_CMD: &str = "verify";
+
+pub const ANCHOR_HASH_FLAG: &str = "anchor-hash";
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about("Inspect the signing audit log written by the validator client")
+        .display_order(0)
+        .subcommand(
+            Command::new(VERIFY_CMD)
+                .about(
+                    "Check that no records in the signing audit log have been modified, \
+                     re-ordered or removed",
+                )
+                .arg(
+                    Arg::new(ANCHOR_HASH_FLAG)
+                        .long(ANCHOR_HASH_FLAG)
+                        .action(ArgAction::Set)
+                        .value_name("HASH")
+                        .help(
+                            "The 0x-prefixed hash of a previously observed record, which must \
+                             still be present in the log. Providing the hash of a recent head \
+                             detects removal of records from the end of the log",
+                        )
+                        .display_order(0),
+                ),
+        )
+}
+
+pub fn cli_run(matches: &ArgMatches, validator_base_dir: PathBuf) -> Result<(), String> {
+    let log_dir = validator_base_dir.join(SIGNING_AUDIT_LOG_DIR);
+
+    match matches.subcommand() {
+        Some((VERIFY_CMD, matches)) => {
+            let anchor = clap_utils::parse_optional::<String>(matches, ANCHOR_HASH_FLAG)?
+                .map(|hash| {
+                    Hash256::from_str(hash.trim_start_matches("0x"))
+                        .map_err(|e| format!("Invalid --{} value: {:?}", ANCHOR_HASH_FLAG, e))
+                })
+                .transpose()?;
+
+            if !log_dir.exists() {
+                return Err(format!(
+                    "No signing audit log exists at: {}",
+                    log_dir.display()
+                ));
+            }
+
+            let summary = verify(&log_dir, anchor).map_err(|e| {
+                format!(
+                    "Signing audit log at {} failed verification: {:?}",
+                    log_dir.display(),
+                    e
+                )
+            })?;
+
+            eprintln!("Signing audit log verified successfully:");
+            eprintln!("- files: {}", summary.files);
+            eprintln!("- records: {}", summary.record_count);
+            if let Some(first_index) = summary.first_index {
+                eprintln!("- first index: {}", first_index);
+            }
+            if let Some(head) = summary.head {
+                eprintln!("- head index: {}", head.index);
+                eprintln!("- head hash: {:?}", head.hash);
+            }
+            if summary.pruned {
+                eprintln!(
+                    "Older records have been pruned, the oldest remaining record could not be \
+                     checked against its predecessor."
+                );
+            }
+            if anchor.is_none() {
+                eprintln!(
+                    "No --{} was provided, removal of the latest records cannot be detected. \
+                     Store the head hash above for use in future verifications.",
+                    ANCHOR_HASH_FLAG
+                );
+            }
+
+            Ok(())
+        }
+        Some((unknown, _)) => Err(format!(
+            "{} does not have a {} command. See --help",
+            CMD, unknown
+        )),
+        _ => Err(format!("No command provided for {}. See --help", CMD)),
+    }
+}
@@ -58,6 +58,7 @@ use crate::observed_operations::{ObservationOutcome, ObservedOperations};
 use crate::observed_slashable::ObservedSlashable;
 use crate::persisted_beacon_chain::{PersistedBeaconChain, DUMMY_CANONICAL_HEAD_BLOCK_ROOT};
 use crate::persisted_fork_choice::PersistedForkChoice;
+use crate::persisted_naive_aggregation_pools::PersistedNaiveAggregationPools;
 use crate::pre_finalization_cache::PreFinalizationBlockCache;
 use crate::shuffling_cache::{BlockShufflingIds, ShufflingCache};
 use crate::sync_committee_verification::{
@@ -136,6 +137,8 @@ pub const BEACON_CHAIN_DB_KEY: Hash256 = Hash256::zero();
 pub const OP_POOL_DB_KEY: Hash256 = Hash256::zero();
 pub const ETH1_CACHE_DB_KEY: Hash256 = Hash256::zero();
 pub const FORK_CHOICE_DB_KEY: Hash256 = Hash256::zero();
+/// Shares a column with the op pool, so must be distinct from `OP_POOL_DB_KEY`.
+pub const NAIVE_AGGREGATION_POOLS_DB_KEY: Hash256 = Hash256::repeat_byte(0x01);
 
 /// Defines how old a block can be before it's no longer a candidate for the early attester cache.
 const EARLY_ATTESTER_CACHE_HISTORIC_SLOTS: u64 = 4;
@@ -700,6 +703,20 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(())
     }
 
+    /// Persists `self.naive_aggregation_pool` and `self.naive_sync_aggregation_pool` to disk.
+    pub fn persist_naive_aggregation_pools(&self) -> Result<(), Error> {
+        let _timer = metrics::start_timer(&metrics::PERSIST_NAIVE_AGGREGATION_POOLS);
+
+        let persisted = PersistedNaiveAggregationPools::from_pools(
+            &self.naive_aggregation_pool.read(),
+            &self.naive_sync_aggregation_pool.read(),
+        );
+        self.store
+            .put_item(&NAIVE_AGGREGATION_POOLS_DB_KEY, &persisted)?;
+
+        Ok(())
+    }
+
     /// Persists `self.eth1_chain` and its caches to disk.
     pub fn persist_eth1_cache(&self) -> Result<(), Error> {
         let _timer = metrics::start_timer(&metrics::PERSIST_ETH1_CACHE);
@@ -1228,6 +1245,27 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
+    /// Returns the data column at the given root and index, if any. The data availability cache
+    /// is checked first, followed by the database.
+    ///
+    /// ## Errors
+    ///
+    /// May return a database error.
+    pub fn get_data_column_checking_all_caches(
+        &self,
+        block_root: Hash256,
+        index: ColumnIndex,
+    ) -> Result<Option<Arc<DataColumnSidecar<T::EthSpec>>>, Error> {
+        if let Some(data_column) = self
+            .data_availability_checker
+            .get_data_column(&DataColumnIdentifier { block_root, index })?
+        {
+            return Ok(Some(data_column));
+        }
+
+        Ok(self.store.get_data_column(&block_root, &index)?)
+    }
+
     pub fn get_blinded_block(
         &self,
         block_root: &Hash256,
@@ -2984,6 +3022,29 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self.remove_notified_custody_columns(&block_root, r)
     }
 
+    /// Cache the custody columns received over RPC in the data availability checker, and import
+    /// the block if it becomes available.
+    pub async fn process_rpc_custody_columns(
+        self: &Arc<Self>,
+        block_root: Hash256,
+        custody_columns: DataColumnSidecarList<T::EthSpec>,
+    ) -> Result<AvailabilityProcessingStatus, BlockError<T::EthSpec>> {
+        // If this block has already been imported to forkchoice it must have been available, so
+        // we don't need to process its columns again.
+        if self
+            .canonical_head
+            .fork_choice_read_lock()
+            .contains_block(&block_root)
+        {
+            return Err(BlockError::BlockIsAlreadyKnown(block_root));
+        }
+
+        let r = self
+            .check_rpc_custody_columns_availability_and_import(block_root, custody_columns)
+            .await;
+        self.remove_notified_custody_columns(&block_root, r)
+    }
+
     /// Cache the blobs in the processing cache, process it, then evict it from the cache if it was
     /// imported or errors.
     pub async fn process_rpc_blobs(
@@ -3356,6 +3417,57 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self.process_availability(slot, availability).await
     }
 
+    /// Checks if the provided custody columns can make any cached blocks available, and imports
+    /// immediately if so, otherwise caches the columns in the data availability checker.
+    async fn check_rpc_custody_columns_availability_and_import(
+        self: &Arc<Self>,
+        block_root: Hash256,
+        custody_columns: DataColumnSidecarList<T::EthSpec>,
+    ) -> Result<AvailabilityProcessingStatus, BlockError<T::EthSpec>> {
+        let Ok(slot) = custody_columns
+            .iter()
+            .map(|c| c.slot())
+            .unique()
+            .exactly_one()
+        else {
+            return Err(BlockError::InternalError(
+                "Columns for the same block should have matching slot".to_string(),
+            ));
+        };
+
+        // Need to scope this to ensure the lock is dropped before calling `process_availability`
+        // Even an explicit drop is not enough to convince the borrow checker.
+        {
+            let mut slashable_cache = self.observed_slashable.write();
+            for header in custody_columns
+                .iter()
+                .map(|c| c.signed_block_header.clone())
+                .unique()
+            {
+                if verify_header_signature::<T, BlockError<T::EthSpec>>(self, &header).is_ok() {
+                    slashable_cache
+                        .observe_slashable(
+                            header.message.slot,
+                            header.message.proposer_index,
+                            block_root,
+                        )
+                        .map_err(|e| BlockError::BeaconChainError(e.into()))?;
+                    if let Some(slasher) = self.slasher.as_ref() {
+                        slasher.accept_block_header(header);
+                    }
+                }
+            }
+        }
+        let epoch = slot.epoch(T::EthSpec::slots_per_epoch());
+        let availability = self.data_availability_checker.put_rpc_custody_columns(
+            block_root,
+            epoch,
+            custody_columns,
+        )?;
+
+        self.process_availability(slot, availability).await
+    }
+
     /// Imports a fully available block. Otherwise, returns `AvailabilityProcessingStatus::MissingComponents`
     ///
     /// An error is returned if the block was unable to be imported. It may be partially imported
@@ -3627,7 +3739,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         // If the write fails, revert fork choice to the version from disk, else we can
         // end up with blocks in fork choice that are missing from disk.
         // See https://github.com/sigp/lighthouse/issues/2028
-        let (_, signed_block, blobs) = signed_block.deconstruct();
+        let (_, signed_block, blobs, data_columns) = signed_block.deconstruct();
         let block = signed_block.message();
         ops.extend(
             confirmed_state_roots
@@ -3648,6 +3760,17 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
         }
 
+        if let Some(data_columns) = data_columns {
+            if !data_columns.is_empty() {
+                debug!(
+                    self.log, "Writing data_columns to store";
+                    "block_root" => %block_root,
+                    "count" => data_columns.len(),
+                );
+                ops.push(StoreOp::PutDataColumns(block_root, data_columns));
+            }
+        }
+
         let txn_lock = self.store.hot_db.begin_rw_transaction();
 
         if let Err(e) = self.store.do_atomically_with_block_and_blobs_cache(ops) {
@@ -4221,6 +4344,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn produce_block_with_verification(
         self: &Arc<Self>,
         randao_reveal: Signature,
@@ -4228,6 +4352,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         validator_graffiti: Option<Graffiti>,
         verification: ProduceBlockVerification,
         builder_boost_factor: Option<u64>,
+        builder_min_bid_gwei: Option<u64>,
         block_production_version: BlockProductionVersion,
     ) -> Result<BeaconBlockResponseWrapper<T::EthSpec>, BlockProductionError> {
         metrics::inc_counter(&metrics::BLOCK_PRODUCTION_REQUESTS);
@@ -4257,6 +4382,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             validator_graffiti,
             verification,
             builder_boost_factor,
+            builder_min_bid_gwei,
             block_production_version,
         )
         .await
@@ -4816,6 +4942,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         validator_graffiti: Option<Graffiti>,
         verification: ProduceBlockVerification,
         builder_boost_factor: Option<u64>,
+        builder_min_bid_gwei: Option<u64>,
         block_production_version: BlockProductionVersion,
     ) -> Result<BeaconBlockResponseWrapper<T::EthSpec>, BlockProductionError> {
         // Part 1/3 (blocking)
@@ -4837,6 +4964,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                         randao_reveal,
                         graffiti,
                         builder_boost_factor,
+                        builder_min_bid_gwei,
                         block_production_version,
                     )
                 },
@@ -4935,6 +5063,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         randao_reveal: Signature,
         graffiti: Graffiti,
         builder_boost_factor: Option<u64>,
+        builder_min_bid_gwei: Option<u64>,
         block_production_version: BlockProductionVersion,
     ) -> Result<PartialBeaconBlock<T::EthSpec>, BlockProductionError> {
         let eth1_chain = self
@@ -4984,6 +5113,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             chain_health: self
                 .is_healthy(&parent_root)
                 .map_err(BlockProductionError::BeaconChain)?,
+            min_bid_gwei: builder_min_bid_gwei,
         };
 
         // If required, start the process of loading an execution payload from the EL early. This
@@ -5007,8 +5137,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
         };
 
-        let (mut proposer_slashings, mut attester_slashings, mut voluntary_exits) =
-            self.op_pool.get_slashings_and_exits(&state, &self.spec);
+        // The slashings and attestations share the time allowed for packing the block.
+        let packing_deadline = self.op_pool.packing_deadline();
+        let (mut proposer_slashings, mut attester_slashings, mut voluntary_exits) = self
+            .op_pool
+            .get_slashings_and_exits_before(&state, packing_deadline, &self.spec);
 
         let eth1_data = eth1_chain.eth1_data_for_block_production(&state, &self.spec)?;
 
@@ -5058,10 +5191,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         let mut attestations = self
             .op_pool
-            .get_attestations(
+            .get_attestations_before(
                 &state,
                 prev_attestation_filter,
                 curr_attestation_filter,
+                packing_deadline,
                 &self.spec,
             )
             .map_err(BlockProductionError::OpPoolError)?;
@@ -6255,6 +6389,28 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 return;
             }
 
+            // Periodically persist the op pools, so that they survive an unclean shutdown.
+            if let Some(epochs) = self.config.op_pool_persistence_epochs {
+                if slot.as_u64() % (epochs * T::EthSpec::slots_per_epoch()) == 0 {
+                    let chain = self.clone();
+                    self.task_executor.clone().spawn_blocking(
+                        move || {
+                            if let Err(e) = chain
+                                .persist_op_pool()
+                                .and_then(|()| chain.persist_naive_aggregation_pools())
+                            {
+                                error!(
+                                    chain.log,
+                                    "Failed to persist op pools";
+                                    "error" => ?e,
+                                );
+                            }
+                        },
+                        "persist_op_pools",
+                    );
+                }
+            }
+
             // Run fork choice and signal to any waiting task that it has completed.
             self.recompute_head_at_current_slot().await;
 
@@ -6797,6 +6953,19 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
+    /// Gets the best `LightClientUpdate` for each of `count` sync committee periods from
+    /// `start_period`.
+    ///
+    /// The result is contiguous, stopping at the first period for which no update is available.
+    pub fn get_light_client_updates(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Vec<LightClientUpdate<T::EthSpec>>, Error> {
+        self.light_client_server_cache
+            .get_light_client_updates(&self.store, start_period, count)
+    }
+
     pub fn metrics(&self) -> BeaconChainMetrics {
         BeaconChainMetrics {
             reqresp_pre_import_cache_len: self.reqresp_pre_import_cache.read().len(),
@@ -6809,6 +6978,7 @@ impl<T: BeaconChainTypes> Drop for BeaconChain<T> {
         let drop = || -> Result<(), Error> {
             self.persist_head_and_fork_choice()?;
             self.persist_op_pool()?;
+            self.persist_naive_aggregation_pools()?;
             self.persist_eth1_cache()
         };
 
@@ -10,19 +10,23 @@ use state_processing::ConsensusContext;
 use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 use types::blob_sidecar::{BlobIdentifier, BlobSidecarError, FixedBlobSidecarList};
+use types::data_column_sidecar::DataColumnSidecarList;
 use types::{
     BeaconBlockRef, BeaconState, BlindedPayload, BlobSidecarList, Epoch, EthSpec, Hash256,
     SignedBeaconBlock, SignedBeaconBlockHeader, Slot,
 };
 
-/// A block that has been received over RPC. It has 2 internal variants:
+/// A block that has been received over RPC. It has 3 internal variants:
 ///
 /// 1. `BlockAndBlobs`: A fully available post deneb block with all the blobs available. This variant
 ///    is only constructed after making consistency checks between blocks and blobs.
 ///    Hence, it is fully self contained w.r.t verification. i.e. this block has all the required
 ///    data to get verified and imported into fork choice.
 ///
-/// 2. `Block`: This can be a fully available pre-deneb block **or** a post-deneb block that may or may
+/// 2. `BlockAndCustodyColumns`: The PeerDAS equivalent of `BlockAndBlobs`. It contains the block
+///    and all the data columns this node is required to custody for it.
+///
+/// 3. `Block`: This can be a fully available pre-deneb block **or** a post-deneb block that may or may
 ///    not require blobs to be considered fully available.
 ///
 /// Note: We make a distinction over blocks received over gossip because
@@ -50,6 +54,7 @@ impl<E: EthSpec> RpcBlock<E> {
         match &self.block {
             RpcBlockInner::Block(block) => block,
             RpcBlockInner::BlockAndBlobs(block, _) => block,
+            RpcBlockInner::BlockAndCustodyColumns(block, _) => block,
         }
     }
 
@@ -57,6 +62,7 @@ impl<E: EthSpec> RpcBlock<E> {
         match &self.block {
             RpcBlockInner::Block(block) => block.clone(),
             RpcBlockInner::BlockAndBlobs(block, _) => block.clone(),
+            RpcBlockInner::BlockAndCustodyColumns(block, _) => block.clone(),
         }
     }
 
@@ -64,6 +70,15 @@ impl<E: EthSpec> RpcBlock<E> {
         match &self.block {
             RpcBlockInner::Block(_) => None,
             RpcBlockInner::BlockAndBlobs(_, blobs) => Some(blobs),
+            RpcBlockInner::BlockAndCustodyColumns(_, _) => None,
+        }
+    }
+
+    pub fn custody_columns(&self) -> Option<&DataColumnSidecarList<E>> {
+        match &self.block {
+            RpcBlockInner::Block(_) => None,
+            RpcBlockInner::BlockAndBlobs(_, _) => None,
+            RpcBlockInner::BlockAndCustodyColumns(_, data_columns) => Some(data_columns),
         }
     }
 }
@@ -79,6 +94,9 @@ enum RpcBlockInner<E: EthSpec> {
     /// This variant is used with parent lookups and by-range responses. It should have all blobs
     /// ordered, all block roots matching, and the correct number of blobs for this block.
     BlockAndBlobs(Arc<SignedBeaconBlock<E>>, BlobSidecarList<E>),
+    /// This variant is used with parent lookups and by-range responses post PeerDAS. It should
+    /// have all of the custody columns of this node, with all block roots matching.
+    BlockAndCustodyColumns(Arc<SignedBeaconBlock<E>>, DataColumnSidecarList<E>),
 }
 
 impl<E: EthSpec> RpcBlock<E> {
@@ -153,25 +171,82 @@ impl<E: EthSpec> RpcBlock<E> {
         Self::new(Some(block_root), block, blobs)
     }
 
+    /// Constructs a new `BlockAndCustodyColumns` variant after checking that every column
+    /// belongs to the provided block. An empty list of columns is viewed the same as a block
+    /// without blobs.
+    pub fn new_with_custody_columns(
+        block_root: Option<Hash256>,
+        block: Arc<SignedBeaconBlock<E>>,
+        custody_columns: DataColumnSidecarList<E>,
+    ) -> Result<Self, AvailabilityCheckError> {
+        let block_root = block_root.unwrap_or_else(|| get_block_root(&block));
+
+        if let Some(data_column) = custody_columns
+            .iter()
+            .find(|data_column| data_column.block_root() != block_root)
+        {
+            return Err(AvailabilityCheckError::DataColumnBlockRootMismatch {
+                block_root,
+                data_column_block_root: data_column.block_root(),
+            });
+        }
+
+        // Range sync only pairs columns with blocks by root, so tie each column to the block's
+        // commitments here rather than trusting the serving peer.
+        let block_commitments = block.message().body().blob_kzg_commitments().ok();
+        for data_column in custody_columns.iter() {
+            if block_commitments != Some(&data_column.kzg_commitments) {
+                return Err(AvailabilityCheckError::DataColumnKzgCommitmentsMismatch(
+                    data_column.index,
+                ));
+            }
+            if !data_column.verify_inclusion_proof() {
+                return Err(AvailabilityCheckError::DataColumnInvalidInclusionProof(
+                    data_column.index,
+                ));
+            }
+        }
+
+        let inner = if custody_columns.is_empty() {
+            RpcBlockInner::Block(block)
+        } else {
+            RpcBlockInner::BlockAndCustodyColumns(block, custody_columns)
+        };
+        Ok(Self {
+            block_root,
+            block: inner,
+        })
+    }
+
     pub fn deconstruct(
         self,
     ) -> (
         Hash256,
         Arc<SignedBeaconBlock<E>>,
         Option<BlobSidecarList<E>>,
+        Option<DataColumnSidecarList<E>>,
     ) {
         let block_root = self.block_root();
         match self.block {
-            RpcBlockInner::Block(block) => (block_root, block, None),
-            RpcBlockInner::BlockAndBlobs(block, blobs) => (block_root, block, Some(blobs)),
+            RpcBlockInner::Block(block) => (block_root, block, None, None),
+            RpcBlockInner::BlockAndBlobs(block, blobs) => (block_root, block, Some(blobs), None),
+            RpcBlockInner::BlockAndCustodyColumns(block, data_columns) => {
+                (block_root, block, None, Some(data_columns))
+            }
         }
     }
     pub fn n_blobs(&self) -> usize {
         match &self.block {
-            RpcBlockInner::Block(_) => 0,
+            RpcBlockInner::Block(_) | RpcBlockInner::BlockAndCustodyColumns(_, _) => 0,
             RpcBlockInner::BlockAndBlobs(_, blobs) => blobs.len(),
         }
     }
+    pub fn n_data_columns(&self) -> usize {
+        match &self.block {
+            RpcBlockInner::Block(_) | RpcBlockInner::BlockAndBlobs(_, _) => 0,
+            RpcBlockInner::BlockAndCustodyColumns(_, data_columns) => data_columns.len(),
+        }
+    }
 }
 
 /// A block that has gone through all pre-deneb block processing checks including block processing
@@ -517,12 +592,15 @@ impl<E: EthSpec> AsBlock<E> for AvailableBlock<E> {
     }
 
     fn into_rpc_block(self) -> RpcBlock<E> {
-        let (block_root, block, blobs_opt) = self.deconstruct();
+        let (block_root, block, blobs_opt, data_columns_opt) = self.deconstruct();
         // Circumvent the constructor here, because an Available block will have already had
         // consistency checks performed.
-        let inner = match blobs_opt {
-            None => RpcBlockInner::Block(block),
-            Some(blobs) => RpcBlockInner::BlockAndBlobs(block, blobs),
+        let inner = match (blobs_opt, data_columns_opt) {
+            (None, None) => RpcBlockInner::Block(block),
+            (Some(blobs), _) => RpcBlockInner::BlockAndBlobs(block, blobs),
+            (None, Some(data_columns)) => {
+                RpcBlockInner::BlockAndCustodyColumns(block, data_columns)
+            }
         };
         RpcBlock {
             block_root,
@@ -554,12 +632,14 @@ impl<E: EthSpec> AsBlock<E> for RpcBlock<E> {
         match &self.block {
             RpcBlockInner::Block(block) => block,
             RpcBlockInner::BlockAndBlobs(block, _) => block,
+            RpcBlockInner::BlockAndCustodyColumns(block, _) => block,
         }
     }
     fn block_cloned(&self) -> Arc<SignedBeaconBlock<E>> {
         match &self.block {
             RpcBlockInner::Block(block) => block.clone(),
             RpcBlockInner::BlockAndBlobs(block, _) => block.clone(),
+            RpcBlockInner::BlockAndCustodyColumns(block, _) => block.clone(),
         }
     }
     fn canonical_root(&self) -> Hash256 {
@@ -1,5 +1,6 @@
 use crate::beacon_chain::{
-    CanonicalHead, LightClientProducerEvent, BEACON_CHAIN_DB_KEY, ETH1_CACHE_DB_KEY, OP_POOL_DB_KEY,
+    CanonicalHead, LightClientProducerEvent, BEACON_CHAIN_DB_KEY, ETH1_CACHE_DB_KEY,
+    NAIVE_AGGREGATION_POOLS_DB_KEY, OP_POOL_DB_KEY,
 };
 use crate::beacon_proposer_cache::BeaconProposerCache;
 use crate::data_availability_checker::DataAvailabilityChecker;
@@ -12,6 +13,7 @@ use crate::head_tracker::HeadTracker;
 use crate::light_client_server_cache::LightClientServerCache;
 use crate::migrate::{BackgroundMigrator, MigratorConfig};
 use crate::persisted_beacon_chain::PersistedBeaconChain;
+use crate::persisted_naive_aggregation_pools::PersistedNaiveAggregationPools;
 use crate::shuffling_cache::{BlockShufflingIds, ShufflingCache};
 use crate::validator_monitor::{ValidatorMonitor, ValidatorMonitorConfig};
 use crate::validator_pubkey_cache::ValidatorPubkeyCache;
@@ -29,7 +31,7 @@ use operation_pool::{OperationPool, PersistedOperationPool};
 use parking_lot::{Mutex, RwLock};
 use proto_array::{DisallowedReOrgOffsets, ReOrgThreshold};
 use slasher::Slasher;
-use slog::{crit, debug, error, info, o, Logger};
+use slog::{crit, debug, error, info, o, warn, Logger};
 use slot_clock::{SlotClock, TestingSlotClock};
 use state_processing::{per_slot_processing, AllCaches};
 use std::marker::PhantomData;
@@ -84,6 +86,7 @@ pub struct BeaconChainBuilder<T: BeaconChainTypes> {
         ForkChoice<BeaconForkChoiceStore<T::EthSpec, T::HotStore, T::ColdStore>, T::EthSpec>,
     >,
     op_pool: Option<OperationPool<T::EthSpec>>,
+    naive_aggregation_pools: Option<PersistedNaiveAggregationPools<T::EthSpec>>,
     eth1_chain: Option<Eth1Chain<T::Eth1Chain, T::EthSpec>>,
     execution_layer: Option<ExecutionLayer<T::EthSpec>>,
     event_handler: Option<ServerSentEventHandler<T::EthSpec>>,
@@ -127,6 +130,7 @@ where
             genesis_state_root: None,
             fork_choice: None,
             op_pool: None,
+            naive_aggregation_pools: None,
             eth1_chain: None,
             execution_layer: None,
             event_handler: None,
@@ -316,6 +320,18 @@ where
                 .unwrap_or_else(OperationPool::new),
         );
 
+        // The naive aggregation pools are short-lived, so failing to restore them is not fatal.
+        self.naive_aggregation_pools = store
+            .get_item::<PersistedNaiveAggregationPools<E>>(&NAIVE_AGGREGATION_POOLS_DB_KEY)
+            .unwrap_or_else(|e| {
+                warn!(
+                    log,
+                    "Unable to restore naive aggregation pools";
+                    "error" => ?e
+                );
+                None
+            });
+
         let pubkey_cache = ValidatorPubkeyCache::load_from_store(store)
             .map_err(|e| format!("Unable to open persisted pubkey cache: {:?}", e))?;
 
@@ -887,6 +903,14 @@ where
             }
         };
 
+        let mut op_pool = self.op_pool.ok_or("Cannot build without op pool")?;
+        op_pool.set_packing_strategy(self.chain_config.op_pool_packing_strategy);
+
+        let (naive_aggregation_pool, naive_sync_aggregation_pool) = self
+            .naive_aggregation_pools
+            .map(|pools| pools.into_pools(slot_clock.now().unwrap_or(Slot::new(0))))
+            .unwrap_or_default();
+
         let beacon_chain = BeaconChain {
             spec: self.spec.clone(),
             config: self.chain_config,
@@ -896,11 +920,9 @@ where
                 .ok_or("Cannot build without task executor")?,
             store_migrator,
             slot_clock: slot_clock.clone(),
-            op_pool: self.op_pool.ok_or("Cannot build without op pool")?,
-            // TODO: allow for persisting and loading the pool from disk.
-            naive_aggregation_pool: <_>::default(),
-            // TODO: allow for persisting and loading the pool from disk.
-            naive_sync_aggregation_pool: <_>::default(),
+            op_pool,
+            naive_aggregation_pool: RwLock::new(naive_aggregation_pool),
+            naive_sync_aggregation_pool: RwLock::new(naive_sync_aggregation_pool),
             // TODO: allow for persisting and loading the pool from disk.
             observed_attestations: <_>::default(),
             // TODO: allow for persisting and loading the pool from disk.
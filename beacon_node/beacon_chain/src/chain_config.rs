@@ -1,3 +1,4 @@
+pub use operation_pool::PackingStrategy;
 pub use proto_array::{DisallowedReOrgOffsets, ReOrgThreshold};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -16,6 +17,9 @@ pub const DEFAULT_PREPARE_PAYLOAD_LOOKAHEAD_FACTOR: u32 = 3;
 /// Fraction of a slot lookahead for fork choice in the state advance timer (500ms on mainnet).
 pub const FORK_CHOICE_LOOKAHEAD_FACTOR: u32 = 24;
 
+/// Default number of epochs between each persistence of the op pools (about 25 minutes on mainnet).
+pub const DEFAULT_OP_POOL_PERSISTENCE_EPOCHS: u64 = 4;
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 pub struct ChainConfig {
     /// Maximum number of slots to skip when importing an attestation.
@@ -84,6 +88,13 @@ pub struct ChainConfig {
     pub epochs_per_migration: u64,
     /// When set to true Light client server computes and caches state proofs for serving updates
     pub enable_light_client_server: bool,
+    /// How the operation pool selects operations for inclusion in a block.
+    pub op_pool_packing_strategy: PackingStrategy,
+    /// Number of epochs between each persistence of the op pools to disk, in addition to
+    /// persisting them on shutdown.
+    ///
+    /// If `None`, the op pools are only persisted on shutdown.
+    pub op_pool_persistence_epochs: Option<u64>,
 }
 
 impl Default for ChainConfig {
@@ -115,6 +126,8 @@ impl Default for ChainConfig {
             always_prepare_payload: false,
             epochs_per_migration: crate::migrate::DEFAULT_EPOCHS_PER_MIGRATION,
             enable_light_client_server: false,
+            op_pool_packing_strategy: PackingStrategy::Greedy,
+            op_pool_persistence_epochs: Some(DEFAULT_OP_POOL_PERSISTENCE_EPOCHS),
         }
     }
 }
@@ -14,13 +14,18 @@ use std::sync::Arc;
 use std::time::Duration;
 use task_executor::TaskExecutor;
 use types::blob_sidecar::{BlobIdentifier, BlobSidecar, FixedBlobSidecarList};
-use types::{BlobSidecarList, ChainSpec, Epoch, EthSpec, Hash256, SignedBeaconBlock};
+use types::data_column_sidecar::{DataColumnIdentifier, DataColumnSidecarList};
+use types::{
+    BlobSidecarList, ChainSpec, DataColumnSidecar, Epoch, EthSpec, Hash256, SignedBeaconBlock,
+};
 
 mod error;
 mod overflow_lru_cache;
 mod state_lru_cache;
 
-use crate::data_column_verification::GossipVerifiedDataColumn;
+use crate::data_column_verification::{
+    verify_kzg_for_data_column_list, GossipVerifiedDataColumn, KzgVerifiedCustodyDataColumn,
+};
 pub use error::{Error as AvailabilityCheckError, ErrorCategory as AvailabilityCheckErrorCategory};
 use types::non_zero_usize::new_non_zero_usize;
 
@@ -140,6 +145,21 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
             })
     }
 
+    /// Return the set of imported custody column indexes for `block_root`. Returns None if there is
+    /// no block component for `block_root`.
+    pub fn imported_custody_column_indexes(&self, block_root: &Hash256) -> Option<Vec<u64>> {
+        self.availability_cache
+            .peek_pending_components(block_root, |components| {
+                components.map(|components| {
+                    components
+                        .get_cached_data_columns()
+                        .iter()
+                        .map(|data_column| data_column.index())
+                        .collect::<Vec<_>>()
+                })
+            })
+    }
+
     /// Get a blob from the availability cache.
     pub fn get_blob(
         &self,
@@ -148,6 +168,14 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
         self.availability_cache.peek_blob(blob_id)
     }
 
+    /// Get a data column from the availability cache.
+    pub fn get_data_column(
+        &self,
+        data_column_id: &DataColumnIdentifier,
+    ) -> Result<Option<Arc<DataColumnSidecar<T::EthSpec>>>, AvailabilityCheckError> {
+        self.availability_cache.peek_data_column(data_column_id)
+    }
+
     /// Put a list of blobs received via RPC into the availability cache. This performs KZG
     /// verification on the blobs in the list.
     pub fn put_rpc_blobs(
@@ -173,6 +201,31 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
             .put_kzg_verified_blobs(block_root, epoch, verified_blobs)
     }
 
+    /// Put a list of custody columns received via RPC into the availability cache. This performs
+    /// KZG verification on the columns in the list.
+    pub fn put_rpc_custody_columns(
+        &self,
+        block_root: Hash256,
+        epoch: Epoch,
+        custody_columns: DataColumnSidecarList<T::EthSpec>,
+    ) -> Result<Availability<T::EthSpec>, AvailabilityCheckError> {
+        let Some(kzg) = self.kzg.as_ref() else {
+            return Err(AvailabilityCheckError::KzgNotInitialized);
+        };
+
+        let verified_custody_columns = custody_columns
+            .into_iter()
+            .map(|column| KzgVerifiedCustodyDataColumn::new(column, kzg))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(AvailabilityCheckError::Kzg)?;
+
+        self.availability_cache.put_kzg_verified_data_columns(
+            block_root,
+            epoch,
+            verified_custody_columns,
+        )
+    }
+
     /// Check if we've cached other blobs for this block. If it completes a set and we also
     /// have a block cached, return the `Availability` variant triggering block import.
     /// Otherwise cache the blob sidecar.
@@ -221,9 +274,9 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
         &self,
         block: RpcBlock<T::EthSpec>,
     ) -> Result<MaybeAvailableBlock<T::EthSpec>, AvailabilityCheckError> {
-        let (block_root, block, blobs) = block.deconstruct();
-        match blobs {
-            None => {
+        let (block_root, block, blobs, data_columns) = block.deconstruct();
+        match (blobs, data_columns) {
+            (None, None) => {
                 if self.blobs_required_for_block(&block) {
                     Ok(MaybeAvailableBlock::AvailabilityPending { block_root, block })
                 } else {
@@ -231,11 +284,12 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
                         block_root,
                         block,
                         blobs: None,
+                        data_columns: None,
                         blobs_available_timestamp: None,
                     }))
                 }
             }
-            Some(blob_list) => {
+            (Some(blob_list), _) => {
                 let verified_blobs = if self.blobs_required_for_block(&block) {
                     let kzg = self
                         .kzg
@@ -251,6 +305,27 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
                     block_root,
                     block,
                     blobs: verified_blobs,
+                    data_columns: None,
+                    blobs_available_timestamp: None,
+                }))
+            }
+            (None, Some(data_column_list)) => {
+                let verified_data_columns = if self.blobs_required_for_block(&block) {
+                    let kzg = self
+                        .kzg
+                        .as_ref()
+                        .ok_or(AvailabilityCheckError::KzgNotInitialized)?;
+                    verify_kzg_for_data_column_list(data_column_list.iter(), kzg)
+                        .map_err(AvailabilityCheckError::Kzg)?;
+                    Some(data_column_list)
+                } else {
+                    None
+                };
+                Ok(MaybeAvailableBlock::Available(AvailableBlock {
+                    block_root,
+                    block,
+                    blobs: None,
+                    data_columns: verified_data_columns,
                     blobs_available_timestamp: None,
                 }))
             }
@@ -276,42 +351,52 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
             .flatten()
             .collect::<Vec<_>>()
             .into();
+        let all_data_columns: DataColumnSidecarList<T::EthSpec> = blocks
+            .iter()
+            .filter(|block| self.blobs_required_for_block(block.as_block()))
+            // this clone is cheap as it's cloning an Arc
+            .filter_map(|block| block.custody_columns().cloned())
+            .flatten()
+            .collect::<Vec<_>>();
 
-        // verify kzg for all blobs at once
-        if !all_blobs.is_empty() {
+        // verify kzg for all blobs and data columns at once
+        if !all_blobs.is_empty() || !all_data_columns.is_empty() {
             let kzg = self
                 .kzg
                 .as_ref()
                 .ok_or(AvailabilityCheckError::KzgNotInitialized)?;
-            verify_kzg_for_blob_list(all_blobs.iter(), kzg)?;
+            if !all_blobs.is_empty() {
+                verify_kzg_for_blob_list(all_blobs.iter(), kzg)?;
+            }
+            if !all_data_columns.is_empty() {
+                verify_kzg_for_data_column_list(all_data_columns.iter(), kzg)?;
+            }
         }
 
         for block in blocks {
-            let (block_root, block, blobs) = block.deconstruct();
-            match blobs {
-                None => {
-                    if self.blobs_required_for_block(&block) {
+            let (block_root, block, blobs, data_columns) = block.deconstruct();
+            let blobs_required = self.blobs_required_for_block(&block);
+            match (blobs, data_columns) {
+                (None, None) => {
+                    if blobs_required {
                         results.push(MaybeAvailableBlock::AvailabilityPending { block_root, block })
                     } else {
                         results.push(MaybeAvailableBlock::Available(AvailableBlock {
                             block_root,
                             block,
                             blobs: None,
+                            data_columns: None,
                             blobs_available_timestamp: None,
                         }))
                     }
                 }
-                Some(blob_list) => {
-                    let verified_blobs = if self.blobs_required_for_block(&block) {
-                        Some(blob_list)
-                    } else {
-                        None
-                    };
-                    // already verified kzg for all blobs
+                (blobs, data_columns) => {
+                    // already verified kzg for all blobs and data columns
                     results.push(MaybeAvailableBlock::Available(AvailableBlock {
                         block_root,
                         block,
-                        blobs: verified_blobs,
+                        blobs: blobs.filter(|_| blobs_required),
+                        data_columns: data_columns.filter(|_| blobs_required),
                         blobs_available_timestamp: None,
                     }))
                 }
@@ -477,6 +562,7 @@ pub struct AvailableBlock<E: EthSpec> {
     block_root: Hash256,
     block: Arc<SignedBeaconBlock<E>>,
     blobs: Option<BlobSidecarList<E>>,
+    data_columns: Option<DataColumnSidecarList<E>>,
     /// Timestamp at which this block first became available (UNIX timestamp, time since 1970).
     blobs_available_timestamp: Option<Duration>,
 }
@@ -486,11 +572,13 @@ impl<E: EthSpec> AvailableBlock<E> {
         block_root: Hash256,
         block: Arc<SignedBeaconBlock<E>>,
         blobs: Option<BlobSidecarList<E>>,
+        data_columns: Option<DataColumnSidecarList<E>>,
     ) -> Self {
         Self {
             block_root,
             block,
             blobs,
+            data_columns,
             blobs_available_timestamp: None,
         }
     }
@@ -506,6 +594,10 @@ impl<E: EthSpec> AvailableBlock<E> {
         self.blobs.as_ref()
     }
 
+    pub fn data_columns(&self) -> Option<&DataColumnSidecarList<E>> {
+        self.data_columns.as_ref()
+    }
+
     pub fn blobs_available_timestamp(&self) -> Option<Duration> {
         self.blobs_available_timestamp
     }
@@ -516,14 +608,16 @@ impl<E: EthSpec> AvailableBlock<E> {
         Hash256,
         Arc<SignedBeaconBlock<E>>,
         Option<BlobSidecarList<E>>,
+        Option<DataColumnSidecarList<E>>,
     ) {
         let AvailableBlock {
             block_root,
             block,
             blobs,
+            data_columns,
             blobs_available_timestamp: _,
         } = self;
-        (block_root, block, blobs)
+        (block_root, block, blobs, data_columns)
     }
 }
 
@@ -15,6 +15,12 @@ pub enum Error {
     SszTypes(ssz_types::Error),
     MissingBlobs,
     BlobIndexInvalid(u64),
+    DataColumnBlockRootMismatch {
+        block_root: Hash256,
+        data_column_block_root: Hash256,
+    },
+    DataColumnKzgCommitmentsMismatch(u64),
+    DataColumnInvalidInclusionProof(u64),
     StoreError(store::Error),
     DecodeError(ssz::DecodeError),
     ParentStateMissing(Hash256),
@@ -47,6 +53,9 @@ impl Error {
             | Error::SlotClockError => ErrorCategory::Internal,
             Error::Kzg(_)
             | Error::BlobIndexInvalid(_)
+            | Error::DataColumnBlockRootMismatch { .. }
+            | Error::DataColumnKzgCommitmentsMismatch(_)
+            | Error::DataColumnInvalidInclusionProof(_)
             | Error::KzgCommitmentMismatch { .. }
             | Error::KzgVerificationFailed => ErrorCategory::Malicious,
         }
@@ -14,7 +14,10 @@ use ssz_types::{FixedVector, VariableList};
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 use types::blob_sidecar::BlobIdentifier;
-use types::{BlobSidecar, ChainSpec, Epoch, EthSpec, Hash256, SignedBeaconBlock};
+use types::data_column_sidecar::DataColumnIdentifier;
+use types::{
+    BlobSidecar, ChainSpec, DataColumnSidecar, Epoch, EthSpec, Hash256, SignedBeaconBlock,
+};
 
 /// This represents the components of a partially available block
 ///
@@ -46,6 +49,11 @@ impl<E: EthSpec> PendingComponents<E> {
         &self.verified_blobs
     }
 
+    /// Returns an immutable reference to the cached data columns.
+    pub fn get_cached_data_columns(&self) -> &Vec<KzgVerifiedCustodyDataColumn<E>> {
+        &self.verified_data_columns
+    }
+
     /// Returns an immutable reference to the cached data column.
     pub fn get_cached_data_column(
         &self,
@@ -217,7 +225,11 @@ impl<E: EthSpec> PendingComponents<E> {
     ///
     /// WARNING: This function can potentially take a lot of time if the state needs to be
     /// reconstructed from disk. Ensure you are not holding any write locks while calling this.
-    pub fn make_available<R>(self, recover: R) -> Result<Availability<E>, AvailabilityCheckError>
+    pub fn make_available<R>(
+        self,
+        block_import_requirement: &BlockImportRequirement,
+        recover: R,
+    ) -> Result<Availability<E>, AvailabilityCheckError>
     where
         R: FnOnce(
             DietAvailabilityPendingExecutedBlock<E>,
@@ -226,7 +238,7 @@ impl<E: EthSpec> PendingComponents<E> {
         let Self {
             block_root,
             verified_blobs,
-            verified_data_columns: _,
+            verified_data_columns,
             executed_block,
         } = self;
 
@@ -239,17 +251,28 @@ impl<E: EthSpec> PendingComponents<E> {
         let Some(diet_executed_block) = executed_block else {
             return Err(AvailabilityCheckError::Unexpected);
         };
-        let num_blobs_expected = diet_executed_block.num_blobs_expected();
-        let Some(verified_blobs) = verified_blobs
-            .into_iter()
-            .cloned()
-            .map(|b| b.map(|b| b.to_blob()))
-            .take(num_blobs_expected)
-            .collect::<Option<Vec<_>>>()
-        else {
-            return Err(AvailabilityCheckError::Unexpected);
+        let (blobs, data_columns) = match block_import_requirement {
+            BlockImportRequirement::AllBlobs => {
+                let num_blobs_expected = diet_executed_block.num_blobs_expected();
+                let Some(verified_blobs) = verified_blobs
+                    .into_iter()
+                    .cloned()
+                    .map(|b| b.map(|b| b.to_blob()))
+                    .take(num_blobs_expected)
+                    .collect::<Option<Vec<_>>>()
+                else {
+                    return Err(AvailabilityCheckError::Unexpected);
+                };
+                (Some(VariableList::new(verified_blobs)?), None)
+            }
+            BlockImportRequirement::CustodyColumns(_) => {
+                let verified_data_columns = verified_data_columns
+                    .into_iter()
+                    .map(|d| d.clone_data_column())
+                    .collect::<Vec<_>>();
+                (None, Some(verified_data_columns))
+            }
         };
-        let verified_blobs = VariableList::new(verified_blobs)?;
 
         let executed_block = recover(diet_executed_block)?;
 
@@ -262,7 +285,8 @@ impl<E: EthSpec> PendingComponents<E> {
         let available_block = AvailableBlock {
             block_root,
             block,
-            blobs: Some(verified_blobs),
+            blobs,
+            data_columns,
             blobs_available_timestamp,
         };
         Ok(Availability::Available(Box::new(
@@ -352,6 +376,20 @@ impl<T: BeaconChainTypes> DataAvailabilityCheckerInner<T> {
         }
     }
 
+    /// Fetch a data column from the cache without affecting the LRU ordering
+    pub fn peek_data_column(
+        &self,
+        data_column_id: &DataColumnIdentifier,
+    ) -> Result<Option<Arc<DataColumnSidecar<T::EthSpec>>>, AvailabilityCheckError> {
+        if let Some(pending_components) = self.critical.read().peek(&data_column_id.block_root) {
+            Ok(pending_components
+                .get_cached_data_column(data_column_id.index)
+                .map(|data_column| data_column.clone_data_column()))
+        } else {
+            Ok(None)
+        }
+    }
+
     pub fn peek_pending_components<R, F: FnOnce(Option<&PendingComponents<T::EthSpec>>) -> R>(
         &self,
         block_root: &Hash256,
@@ -404,7 +442,7 @@ impl<T: BeaconChainTypes> DataAvailabilityCheckerInner<T> {
             write_lock.put(block_root, pending_components.clone());
             // No need to hold the write lock anymore
             drop(write_lock);
-            pending_components.make_available(|diet_block| {
+            pending_components.make_available(&block_import_requirement, |diet_block| {
                 self.state_cache.recover_pending_executed_block(diet_block)
             })
         } else {
@@ -413,8 +451,6 @@ impl<T: BeaconChainTypes> DataAvailabilityCheckerInner<T> {
         }
     }
 
-    // TODO(das): gossip and rpc code paths to be implemented.
-    #[allow(dead_code)]
     pub fn put_kzg_verified_data_columns<
         I: IntoIterator<Item = KzgVerifiedCustodyDataColumn<T::EthSpec>>,
     >(
@@ -439,7 +475,7 @@ impl<T: BeaconChainTypes> DataAvailabilityCheckerInner<T> {
             write_lock.put(block_root, pending_components.clone());
             // No need to hold the write lock anymore
             drop(write_lock);
-            pending_components.make_available(|diet_block| {
+            pending_components.make_available(&block_import_requirement, |diet_block| {
                 self.state_cache.recover_pending_executed_block(diet_block)
             })
         } else {
@@ -478,7 +514,7 @@ impl<T: BeaconChainTypes> DataAvailabilityCheckerInner<T> {
             write_lock.put(block_root, pending_components.clone());
             // No need to hold the write lock anymore
             drop(write_lock);
-            pending_components.make_available(|diet_block| {
+            pending_components.make_available(&block_import_requirement, |diet_block| {
                 self.state_cache.recover_pending_executed_block(diet_block)
             })
         } else {
@@ -538,7 +574,7 @@ mod test {
         blob_verification::GossipVerifiedBlob,
         block_verification::PayloadVerificationOutcome,
         block_verification_types::{AsBlock, BlockImportData},
-        data_availability_checker::STATE_LRU_CAPACITY,
+        data_availability_checker::{DataAvailabilityChecker, STATE_LRU_CAPACITY},
         eth1_finalization_cache::Eth1FinalizationData,
         test_utils::{BaseHarnessType, BeaconChainHarness, DiskHarnessType},
     };
@@ -547,10 +583,10 @@ mod test {
     use slog::{info, Logger};
     use state_processing::ConsensusContext;
     use std::collections::VecDeque;
-    use store::{HotColdDB, ItemStore, LevelDB, StoreConfig};
+    use store::{BeaconNodeBackend, HotColdDB, ItemStore, StoreConfig};
     use tempfile::{tempdir, TempDir};
     use types::non_zero_usize::new_non_zero_usize;
-    use types::{ExecPayload, MinimalEthSpec};
+    use types::{BlobsList, DataColumnSidecar, ExecPayload, MinimalEthSpec};
 
     const LOW_VALIDATOR_COUNT: usize = 32;
     const DEFAULT_TEST_CUSTODY_COLUMN_COUNT: usize = 8;
@@ -559,7 +595,7 @@ mod test {
         db_path: &TempDir,
         spec: ChainSpec,
         log: Logger,
-    ) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+    ) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>> {
         let hot_path = db_path.path().join("hot_db");
         let cold_path = db_path.path().join("cold_db");
         let blobs_path = db_path.path().join("blobs_db");
@@ -732,7 +768,11 @@ mod test {
     )
     where
         E: EthSpec,
-        T: BeaconChainTypes<HotStore = LevelDB<E>, ColdStore = LevelDB<E>, EthSpec = E>,
+        T: BeaconChainTypes<
+            HotStore = BeaconNodeBackend<E>,
+            ColdStore = BeaconNodeBackend<E>,
+            EthSpec = E,
+        >,
     {
         let log = test_logger();
         let chain_db_path = tempdir().expect("should get temp dir");
@@ -866,6 +906,91 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn put_rpc_custody_columns() {
+        type E = MinimalEthSpec;
+        type T = DiskHarnessType<E>;
+        let (harness, _cache, _path) = setup_harness_and_cache::<E, T>(4).await;
+
+        // Require custody columns rather than blobs from the first Deneb block onwards.
+        let mut spec = harness.spec.clone();
+        spec.eip7594_fork_epoch = spec.deneb_fork_epoch;
+        let custody_column_count =
+            spec.custody_requirement as usize * spec.data_columns_per_subnet();
+        let da_checker = DataAvailabilityChecker::<T>::new(
+            harness.chain.slot_clock.clone(),
+            harness.chain.kzg.clone(),
+            harness.chain.store.clone(),
+            &harness.chain.log,
+            spec.clone(),
+        )
+        .expect("should create data availability checker");
+
+        let (pending_block, blobs) = loop {
+            let (pending_block, blobs) = availability_pending_block(&harness).await;
+            if pending_block.num_blobs_expected() > 0 {
+                break (pending_block, blobs);
+            }
+        };
+        let root = pending_block.import_data.block_root;
+        let epoch = pending_block.block.epoch();
+        let blobs = BlobsList::<E>::from(
+            blobs
+                .iter()
+                .map(|blob| blob.as_blob().blob.clone())
+                .collect::<Vec<_>>(),
+        );
+        let custody_columns = DataColumnSidecar::build_sidecars(
+            &blobs,
+            pending_block.block.as_block(),
+            harness.chain.kzg.as_ref().expect("should have kzg"),
+            &spec,
+        )
+        .expect("should build data columns")
+        .into_iter()
+        .take(custody_column_count)
+        .collect::<Vec<_>>();
+        let (last_column, custody_columns) = custody_columns.split_last().unwrap();
+
+        let availability = da_checker
+            .put_rpc_custody_columns(root, epoch, custody_columns.to_vec())
+            .expect("should put custody columns");
+        assert_eq!(availability, Availability::MissingComponents(root));
+        assert_eq!(
+            da_checker.imported_custody_column_indexes(&root),
+            Some(custody_columns.iter().map(|column| column.index).collect())
+        );
+        let column_id = custody_columns[0].id();
+        assert_eq!(
+            da_checker.get_data_column(&column_id).unwrap(),
+            Some(custody_columns[0].clone())
+        );
+
+        let availability = da_checker
+            .availability_cache
+            .put_pending_executed_block(pending_block)
+            .expect("should put block");
+        assert_eq!(
+            availability,
+            Availability::MissingComponents(root),
+            "should be pending the last custody column"
+        );
+
+        let availability = da_checker
+            .put_rpc_custody_columns(root, epoch, vec![last_column.clone()])
+            .expect("should put custody column");
+        let Availability::Available(available_block) = availability else {
+            panic!("block should be available: {:?}", availability);
+        };
+        assert_eq!(
+            available_block
+                .block
+                .data_columns()
+                .map(|columns| columns.len()),
+            Some(custody_column_count)
+        );
+    }
+
     #[tokio::test]
     // ensure the state cache keeps memory usage low and that it can properly recover states
     // THIS TEST CAN BE DELETED ONCE TREE STATES IS MERGED AND WE RIP OUT THE STATE CACHE
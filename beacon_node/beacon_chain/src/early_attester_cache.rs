@@ -69,7 +69,7 @@ impl<E: EthSpec> EarlyAttesterCache<E> {
             },
         };
 
-        let (_, block, blobs) = block.deconstruct();
+        let (_, block, blobs, _) = block.deconstruct();
         let item = CacheItem {
             epoch,
             committee_lengths,
@@ -49,6 +49,7 @@ mod observed_slashable;
 pub mod otb_verification_service;
 mod persisted_beacon_chain;
 mod persisted_fork_choice;
+mod persisted_naive_aggregation_pools;
 mod pre_finalization_cache;
 pub mod proposer_prep_service;
 pub mod schema_change;
@@ -4,16 +4,22 @@ use parking_lot::{Mutex, RwLock};
 use slog::{debug, Logger};
 use ssz_types::FixedVector;
 use std::num::NonZeroUsize;
-use types::light_client_update::{FinalizedRootProofLen, FINALIZED_ROOT_INDEX};
+use std::sync::Arc;
+use types::light_client_update::{
+    FinalizedRootProofLen, NextSyncCommitteeProofLen, FINALIZED_ROOT_INDEX,
+    NEXT_SYNC_COMMITTEE_INDEX,
+};
 use types::non_zero_usize::new_non_zero_usize;
 use types::{
-    BeaconBlockRef, BeaconState, ChainSpec, EthSpec, ForkName, Hash256, LightClientFinalityUpdate,
-    LightClientOptimisticUpdate, Slot, SyncAggregate,
+    BeaconBlockRef, BeaconState, ChainSpec, EthSpec, ForkName, Hash256, LightClientError,
+    LightClientFinalityUpdate, LightClientOptimisticUpdate, LightClientUpdate, SignedBeaconBlock,
+    Slot, SyncAggregate, SyncCommittee,
 };
 
 /// A prev block cache miss requires to re-generate the state of the post-parent block. Items in the
-/// prev block cache are very small 32 * (6 + 1) = 224 bytes. 32 is an arbitrary number that
-/// represents unlikely re-orgs, while keeping the cache very small.
+/// prev block cache are small: 32 * (6 + 5 + 1) = 384 bytes, plus a pointer to the next sync
+/// committee which is shared with the state. 32 is an arbitrary number that represents unlikely
+/// re-orgs, while keeping the cache very small.
 const PREV_BLOCK_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(32);
 
 /// This cache computes light client messages ahead of time, required to satisfy p2p and API
@@ -30,8 +36,12 @@ pub struct LightClientServerCache<T: BeaconChainTypes> {
     latest_finality_update: RwLock<Option<LightClientFinalityUpdate<T::EthSpec>>>,
     /// Tracks a single global latest optimistic update out of all imported blocks.
     latest_optimistic_update: RwLock<Option<LightClientOptimisticUpdate<T::EthSpec>>>,
+    /// Caches the best update of the most recent sync committee period, alongside the period.
+    ///
+    /// The best update of every period is persisted to the database.
+    best_light_client_update: RwLock<Option<(u64, LightClientUpdate<T::EthSpec>)>>,
     /// Caches state proofs by block root
-    prev_block_cache: Mutex<lru::LruCache<Hash256, LightClientCachedData>>,
+    prev_block_cache: Mutex<lru::LruCache<Hash256, LightClientCachedData<T::EthSpec>>>,
 }
 
 impl<T: BeaconChainTypes> LightClientServerCache<T> {
@@ -39,6 +49,7 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
         Self {
             latest_finality_update: None.into(),
             latest_optimistic_update: None.into(),
+            best_light_client_update: None.into(),
             prev_block_cache: lru::LruCache::new(PREV_BLOCK_CACHE_SIZE).into(),
         }
     }
@@ -127,14 +138,18 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
             }
             None => true,
         };
+        // Immediately after checkpoint sync the finalized block may not be available yet.
+        let finalized_block = if cached_parts.finalized_block_root.is_zero() {
+            None
+        } else {
+            store.get_full_block(&cached_parts.finalized_block_root)?
+        };
+
         if is_latest_finality & !cached_parts.finalized_block_root.is_zero() {
-            // Immediately after checkpoint sync the finalized block may not be available yet.
-            if let Some(finalized_block) =
-                store.get_full_block(&cached_parts.finalized_block_root)?
-            {
+            if let Some(finalized_block) = &finalized_block {
                 *self.latest_finality_update.write() = Some(LightClientFinalityUpdate::new(
                     &attested_block,
-                    &finalized_block,
+                    finalized_block,
                     cached_parts.finality_branch.clone(),
                     sync_aggregate.clone(),
                     signature_slot,
@@ -149,6 +164,75 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
             }
         }
 
+        if let Some(finalized_block) = &finalized_block {
+            self.store_light_client_update_if_better(
+                &store,
+                sync_aggregate,
+                signature_slot,
+                &cached_parts,
+                &attested_block,
+                finalized_block,
+                chain_spec,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Compute the `LightClientUpdate` for the attested block, and persist it if it is better than
+    /// the best update known for its sync committee period.
+    #[allow(clippy::too_many_arguments)]
+    fn store_light_client_update_if_better(
+        &self,
+        store: &BeaconStore<T>,
+        sync_aggregate: &SyncAggregate<T::EthSpec>,
+        signature_slot: Slot,
+        cached_parts: &LightClientCachedData<T::EthSpec>,
+        attested_block: &SignedBeaconBlock<T::EthSpec>,
+        finalized_block: &SignedBeaconBlock<T::EthSpec>,
+        chain_spec: &ChainSpec,
+    ) -> Result<(), BeaconChainError> {
+        let attested_period = attested_block
+            .slot()
+            .epoch(T::EthSpec::slots_per_epoch())
+            .sync_committee_period(chain_spec)?;
+
+        let new_update = match LightClientUpdate::new(
+            sync_aggregate,
+            signature_slot,
+            cached_parts.next_sync_committee.clone(),
+            cached_parts.next_sync_committee_branch.clone(),
+            cached_parts.finality_branch.clone(),
+            attested_block,
+            finalized_block,
+            chain_spec,
+        ) {
+            Ok(update) => update,
+            // Blocks with too few participants or signed in the next period can't be used for
+            // updates.
+            Err(LightClientError::NotEnoughSyncCommitteeParticipants)
+            | Err(LightClientError::MismatchingPeriods) => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut best_update = self.best_light_client_update.write();
+        if best_update.as_ref().map(|(period, _)| *period) != Some(attested_period) {
+            *best_update = store
+                .get_light_client_update(attested_period)?
+                .map(|update| (attested_period, update));
+        }
+
+        let is_better = match best_update.as_ref() {
+            Some((_, prev_update)) => {
+                prev_update.is_better_light_client_update(&new_update, chain_spec)?
+            }
+            None => true,
+        };
+        if is_better {
+            store.store_light_client_update(attested_period, &new_update)?;
+            *best_update = Some((attested_period, new_update));
+        }
+
         Ok(())
     }
 
@@ -162,7 +246,7 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
         block_root: &Hash256,
         block_state_root: &Hash256,
         block_slot: Slot,
-    ) -> Result<LightClientCachedData, BeaconChainError> {
+    ) -> Result<LightClientCachedData<T::EthSpec>, BeaconChainError> {
         // Attempt to get the value from the cache first.
         if let Some(cached_parts) = self.prev_block_cache.lock().get(block_root) {
             return Ok(cached_parts.clone());
@@ -191,6 +275,25 @@ impl<T: BeaconChainTypes> LightClientServerCache<T> {
     pub fn get_latest_optimistic_update(&self) -> Option<LightClientOptimisticUpdate<T::EthSpec>> {
         self.latest_optimistic_update.read().clone()
     }
+
+    /// Fetch the best updates for up to `count` sync committee periods from `start_period`.
+    ///
+    /// Stops at the first period without an update, so the result is always contiguous.
+    pub fn get_light_client_updates(
+        &self,
+        store: &BeaconStore<T>,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Vec<LightClientUpdate<T::EthSpec>>, BeaconChainError> {
+        let mut updates = vec![];
+        for period in start_period..start_period.saturating_add(count) {
+            match store.get_light_client_update(period)? {
+                Some(update) => updates.push(update),
+                None => break,
+            }
+        }
+        Ok(updates)
+    }
 }
 
 impl<T: BeaconChainTypes> Default for LightClientServerCache<T> {
@@ -200,17 +303,24 @@ impl<T: BeaconChainTypes> Default for LightClientServerCache<T> {
 }
 
 type FinalityBranch = FixedVector<Hash256, FinalizedRootProofLen>;
+type NextSyncCommitteeBranch = FixedVector<Hash256, NextSyncCommitteeProofLen>;
 
 #[derive(Clone)]
-struct LightClientCachedData {
+struct LightClientCachedData<E: EthSpec> {
     finality_branch: FinalityBranch,
+    next_sync_committee_branch: NextSyncCommitteeBranch,
+    next_sync_committee: Arc<SyncCommittee<E>>,
     finalized_block_root: Hash256,
 }
 
-impl LightClientCachedData {
-    fn from_state<E: EthSpec>(state: &mut BeaconState<E>) -> Result<Self, BeaconChainError> {
+impl<E: EthSpec> LightClientCachedData<E> {
+    fn from_state(state: &mut BeaconState<E>) -> Result<Self, BeaconChainError> {
         Ok(Self {
             finality_branch: state.compute_merkle_proof(FINALIZED_ROOT_INDEX)?.into(),
+            next_sync_committee_branch: state
+                .compute_merkle_proof(NEXT_SYNC_COMMITTEE_INDEX)?
+                .into(),
+            next_sync_committee: state.next_sync_committee()?.clone(),
             finalized_block_root: state.finalized_checkpoint().root,
         })
     }
@@ -401,6 +401,10 @@ lazy_static! {
         try_create_histogram("beacon_persist_head", "Time taken to persist the canonical head");
     pub static ref PERSIST_OP_POOL: Result<Histogram> =
         try_create_histogram("beacon_persist_op_pool", "Time taken to persist the operations pool");
+    pub static ref PERSIST_NAIVE_AGGREGATION_POOLS: Result<Histogram> = try_create_histogram(
+        "beacon_persist_naive_aggregation_pools",
+        "Time taken to persist the naive aggregation pools"
+    );
     pub static ref PERSIST_ETH1_CACHE: Result<Histogram> =
         try_create_histogram("beacon_persist_eth1_cache", "Time taken to persist the eth1 caches");
     pub static ref PERSIST_FORK_CHOICE: Result<Histogram> =
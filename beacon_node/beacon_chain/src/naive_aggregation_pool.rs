@@ -195,6 +195,12 @@ where
         value: <Self::Value as AsReference>::Reference<'_>,
     ) -> Result<InsertOutcome, Error>;
 
+    /// Insert an already-aggregated `Value` into `Self`, replacing any existing value with the
+    /// same `Data`.
+    ///
+    /// This is used to restore the pool from disk and performs no aggregation.
+    fn insert_aggregate(&mut self, value: Self::Value) -> Result<(), Error>;
+
     /// Get a `Value` from `Self` based on `Data`.
     fn get(&self, data: &Self::Data) -> Option<Self::Value>;
 
@@ -280,6 +286,18 @@ impl<E: EthSpec> AggregateMap for AggregatedAttestationMap<E> {
         }
     }
 
+    fn insert_aggregate(&mut self, attestation: Attestation<E>) -> Result<(), Error> {
+        let attestation_key_root =
+            AttestationKey::from_attestation_ref(attestation.to_ref())?.tree_hash_root();
+        if self.map.len() >= MAX_ATTESTATIONS_PER_SLOT
+            && !self.map.contains_key(&attestation_key_root)
+        {
+            return Err(Error::ReachedMaxItemsPerSlot(MAX_ATTESTATIONS_PER_SLOT));
+        }
+        self.map.insert(attestation_key_root, attestation);
+        Ok(())
+    }
+
     /// Returns an aggregated `Attestation` with the given `data`, if any.
     ///
     /// The given `a.data.slot` must match the slot that `self` was initialized with.
@@ -386,6 +404,19 @@ impl<E: EthSpec> AggregateMap for SyncContributionAggregateMap<E> {
         }
     }
 
+    fn insert_aggregate(
+        &mut self,
+        contribution: SyncCommitteeContribution<E>,
+    ) -> Result<(), Error> {
+        let sync_data_root =
+            SyncContributionData::from_contribution(&contribution).tree_hash_root();
+        if self.map.len() >= E::sync_committee_size() && !self.map.contains_key(&sync_data_root) {
+            return Err(Error::ReachedMaxItemsPerSlot(E::sync_committee_size()));
+        }
+        self.map.insert(sync_data_root, contribution);
+        Ok(())
+    }
+
     /// Returns an aggregated `SyncCommitteeContribution` with the given `data`, if any.
     ///
     /// The given `data.slot` must match the slot that `self` was initialized with.
@@ -519,6 +550,31 @@ where
         outcome
     }
 
+    /// Insert an already-aggregated item into `self`, as when restoring the pool from disk.
+    ///
+    /// The item must have a `slot` that is not lower than `self.lowest_permissible_slot`.
+    pub fn insert_aggregate(&mut self, item: T::Value) -> Result<(), Error> {
+        let slot = item.get_slot();
+        let lowest_permissible_slot = self.lowest_permissible_slot;
+
+        if slot < lowest_permissible_slot {
+            return Err(Error::SlotTooLow {
+                slot,
+                lowest_permissible_slot,
+            });
+        }
+
+        let outcome = self
+            .maps
+            .entry(slot)
+            .or_insert_with(|| T::new(T::default_capacity()))
+            .insert_aggregate(item);
+
+        self.prune(slot);
+
+        outcome
+    }
+
     /// Returns the total number of items stored in `self`.
     pub fn num_items(&self) -> usize {
         self.maps.values().map(T::len).sum()
@@ -0,0 +1,196 @@
+use crate::naive_aggregation_pool::{
+    AggregatedAttestationMap, NaiveAggregationPool, SyncContributionAggregateMap,
+};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use store::{DBColumn, Error as StoreError, StoreItem};
+use types::attestation::AttestationOnDisk;
+use types::{Attestation, EthSpec, Slot, SyncCommitteeContribution};
+
+/// SSZ-serializable contents of the naive aggregation pools.
+///
+/// These pools only hold messages for the last few slots, so restoring them is only useful after
+/// a brief restart. Items which are too old to be kept at the current slot are dropped when the
+/// pools are restored.
+#[derive(Encode, Decode)]
+pub struct PersistedNaiveAggregationPools<E: EthSpec> {
+    pub attestations: Vec<AttestationOnDisk<E>>,
+    pub sync_contributions: Vec<SyncCommitteeContribution<E>>,
+}
+
+impl<E: EthSpec> PersistedNaiveAggregationPools<E> {
+    pub fn from_pools(
+        attestation_pool: &NaiveAggregationPool<AggregatedAttestationMap<E>>,
+        sync_pool: &NaiveAggregationPool<SyncContributionAggregateMap<E>>,
+    ) -> Self {
+        Self {
+            attestations: attestation_pool
+                .iter()
+                .cloned()
+                .map(AttestationOnDisk::from)
+                .collect(),
+            sync_contributions: sync_pool.iter().cloned().collect(),
+        }
+    }
+
+    /// Restore the pools, pruning them relative to `current_slot`.
+    ///
+    /// Items from slots after `current_slot` are dropped.
+    #[allow(clippy::type_complexity)]
+    pub fn into_pools(
+        mut self,
+        current_slot: Slot,
+    ) -> (
+        NaiveAggregationPool<AggregatedAttestationMap<E>>,
+        NaiveAggregationPool<SyncContributionAggregateMap<E>>,
+    ) {
+        // Restore the oldest items first, so that newer items don't cause them to be rejected.
+        let mut attestations: Vec<Attestation<E>> = self
+            .attestations
+            .into_iter()
+            .map(Attestation::from)
+            .filter(|attestation| attestation.data().slot <= current_slot)
+            .collect();
+        attestations.sort_by_key(|attestation| attestation.data().slot);
+        self.sync_contributions
+            .retain(|contribution| contribution.slot <= current_slot);
+        self.sync_contributions
+            .sort_by_key(|contribution| contribution.slot);
+
+        let mut attestation_pool = NaiveAggregationPool::default();
+        for attestation in attestations {
+            // Errors only arise for items which are too old or too numerous to keep.
+            let _ = attestation_pool.insert_aggregate(attestation);
+        }
+        attestation_pool.prune(current_slot);
+
+        let mut sync_pool = NaiveAggregationPool::default();
+        for contribution in self.sync_contributions {
+            let _ = sync_pool.insert_aggregate(contribution);
+        }
+        sync_pool.prune(current_slot);
+
+        (attestation_pool, sync_pool)
+    }
+}
+
+impl<E: EthSpec> StoreItem for PersistedNaiveAggregationPools<E> {
+    fn db_column() -> DBColumn {
+        DBColumn::OpPool
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Self::from_ssz_bytes(bytes).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::naive_aggregation_pool::AttestationKey;
+    use ssz_types::BitList;
+    use store::BitVector;
+    use types::sync_committee_contribution::SyncContributionData;
+    use types::{
+        test_utils::{generate_deterministic_keypair, test_random_instance},
+        AttestationBase, Fork, Hash256, MainnetEthSpec,
+    };
+
+    type E = MainnetEthSpec;
+
+    fn get_attestation(slot: Slot, i: usize) -> Attestation<E> {
+        let mut a: AttestationBase<E> = test_random_instance();
+        a.data.slot = slot;
+        a.aggregation_bits = BitList::with_capacity(4).expect("should create bitlist");
+        let mut a = Attestation::Base(a);
+        a.sign(
+            &generate_deterministic_keypair(i).sk,
+            i,
+            &Fork::default(),
+            Hash256::zero(),
+            &E::default_spec(),
+        )
+        .expect("should sign attestation");
+        a
+    }
+
+    #[test]
+    fn round_trip() {
+        let attestations = [
+            get_attestation(Slot::new(1), 0),
+            get_attestation(Slot::new(2), 1),
+        ];
+        let mut attestation_pool = NaiveAggregationPool::<AggregatedAttestationMap<E>>::default();
+        for attestation in &attestations {
+            attestation_pool
+                .insert(attestation.to_ref())
+                .expect("should insert attestation");
+        }
+
+        let mut contribution: SyncCommitteeContribution<E> = test_random_instance();
+        contribution.slot = Slot::new(2);
+        contribution.aggregation_bits = BitVector::new();
+        contribution
+            .aggregation_bits
+            .set(0, true)
+            .expect("should set aggregation bit");
+        let mut sync_pool = NaiveAggregationPool::<SyncContributionAggregateMap<E>>::default();
+        sync_pool
+            .insert(&contribution)
+            .expect("should insert contribution");
+
+        let bytes = PersistedNaiveAggregationPools::from_pools(&attestation_pool, &sync_pool)
+            .as_store_bytes();
+        let (restored_attestation_pool, restored_sync_pool) =
+            PersistedNaiveAggregationPools::<E>::from_store_bytes(&bytes)
+                .expect("should decode pools")
+                .into_pools(Slot::new(2));
+
+        assert_eq!(restored_attestation_pool.num_items(), attestations.len());
+        for attestation in &attestations {
+            let key = AttestationKey::from_attestation_ref(attestation.to_ref())
+                .expect("should create attestation key");
+            assert_eq!(
+                restored_attestation_pool.get(&key).as_ref(),
+                Some(attestation)
+            );
+        }
+
+        assert_eq!(restored_sync_pool.num_items(), 1);
+        assert_eq!(
+            restored_sync_pool.get(&SyncContributionData::from_contribution(&contribution)),
+            Some(contribution)
+        );
+    }
+
+    #[test]
+    fn prune_against_current_slot() {
+        let attestations = [
+            get_attestation(Slot::new(3), 0),
+            get_attestation(Slot::new(4), 1),
+            get_attestation(Slot::new(8), 2),
+        ];
+        let persisted = PersistedNaiveAggregationPools::<E> {
+            attestations: attestations
+                .iter()
+                .cloned()
+                .map(AttestationOnDisk::from)
+                .collect(),
+            sync_contributions: vec![],
+        };
+        let (restored_attestation_pool, _) = persisted.into_pools(Slot::new(7));
+
+        // The attestation at slot 3 is too old and the one at slot 8 is from the future.
+        assert_eq!(restored_attestation_pool.num_items(), 1);
+        let key = AttestationKey::from_attestation_ref(attestations[1].to_ref())
+            .expect("should create attestation key");
+        assert_eq!(
+            restored_attestation_pool.get(&key).as_ref(),
+            Some(&attestations[1])
+        );
+    }
+}
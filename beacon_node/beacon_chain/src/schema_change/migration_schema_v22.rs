@@ -0,0 +1,76 @@
+use crate::beacon_chain::BeaconChainTypes;
+use slog::{info, Logger};
+use ssz::{Decode, Encode};
+use std::sync::Arc;
+use store::config::{OnDiskStoreConfig, OnDiskStoreConfigV1};
+use store::metadata::CONFIG_KEY;
+use store::{
+    get_key_for_col, DBColumn, Error, HotColdDB, KeyValueStore, KeyValueStoreOp, StoreItem,
+};
+
+pub fn upgrade_to_v22<T: BeaconChainTypes>(
+    db: Arc<HotColdDB<T::EthSpec, T::HotStore, T::ColdStore>>,
+    log: Logger,
+) -> Result<Vec<KeyValueStoreOp>, Error> {
+    info!(log, "Upgrading from v21 to v22");
+
+    let Some(bytes) = db
+        .hot_db
+        .get_bytes(DBColumn::BeaconMeta.into(), CONFIG_KEY.as_bytes())?
+    else {
+        return Ok(vec![]);
+    };
+
+    // Databases prior to v22 never store state diffs.
+    let old_config = OnDiskStoreConfigV1::from_ssz_bytes(&bytes)?;
+    let new_config = OnDiskStoreConfig {
+        slots_per_restore_point: old_config.slots_per_restore_point,
+        hierarchy_exponents: vec![],
+    };
+
+    Ok(vec![new_config.as_kv_store_op(CONFIG_KEY)])
+}
+
+pub fn downgrade_from_v22<T: BeaconChainTypes>(
+    db: Arc<HotColdDB<T::EthSpec, T::HotStore, T::ColdStore>>,
+    log: Logger,
+) -> Result<Vec<KeyValueStoreOp>, Error> {
+    info!(log, "Downgrading from v22 to v21");
+
+    // Delete all state diffs from the freezer. The states they encode can still be reconstructed
+    // by replaying blocks from the restore points.
+    let mut cold_ops = vec![];
+    for res in db
+        .cold_db
+        .iter_column_keys::<Vec<u8>>(DBColumn::BeaconStateDiff)
+    {
+        let key = res?;
+        cold_ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+            DBColumn::BeaconStateDiff.into(),
+            &key,
+        )));
+    }
+    info!(
+        log,
+        "Deleting state diffs from the freezer";
+        "count" => cold_ops.len(),
+    );
+    db.cold_db.do_atomically(cold_ops)?;
+
+    let Some(bytes) = db
+        .hot_db
+        .get_bytes(DBColumn::BeaconMeta.into(), CONFIG_KEY.as_bytes())?
+    else {
+        return Ok(vec![]);
+    };
+
+    let new_config = OnDiskStoreConfig::from_store_bytes(&bytes)?;
+    let old_config = OnDiskStoreConfigV1 {
+        slots_per_restore_point: new_config.slots_per_restore_point,
+    };
+
+    Ok(vec![KeyValueStoreOp::PutKeyValue(
+        get_key_for_col(DBColumn::BeaconMeta.into(), CONFIG_KEY.as_bytes()),
+        old_config.as_ssz_bytes(),
+    )])
+}
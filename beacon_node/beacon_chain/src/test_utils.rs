@@ -54,7 +54,7 @@ use std::str::FromStr;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use store::{config::StoreConfig, HotColdDB, ItemStore, LevelDB, MemoryStore};
+use store::{config::StoreConfig, BeaconNodeBackend, HotColdDB, ItemStore, MemoryStore};
 use task_executor::TaskExecutor;
 use task_executor::{test_utils::TestRuntime, ShutdownReason};
 use tree_hash::TreeHash;
@@ -88,7 +88,7 @@ lazy_static! {
 pub type BaseHarnessType<E, THotStore, TColdStore> =
     Witness<TestingSlotClock, CachingEth1Backend<E>, E, THotStore, TColdStore>;
 
-pub type DiskHarnessType<E> = BaseHarnessType<E, LevelDB<E>, LevelDB<E>>;
+pub type DiskHarnessType<E> = BaseHarnessType<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>;
 pub type EphemeralHarnessType<E> = BaseHarnessType<E, MemoryStore<E>, MemoryStore<E>>;
 
 pub type BoxedMutator<E, Hot, Cold> = Box<
@@ -265,7 +265,10 @@ impl<E: EthSpec> Builder<EphemeralHarnessType<E>> {
 
 impl<E: EthSpec> Builder<DiskHarnessType<E>> {
     /// Disk store, start from genesis.
-    pub fn fresh_disk_store(mut self, store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>) -> Self {
+    pub fn fresh_disk_store(
+        mut self,
+        store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
+    ) -> Self {
         let validator_keypairs = self
             .validator_keypairs
             .clone()
@@ -290,7 +293,10 @@ impl<E: EthSpec> Builder<DiskHarnessType<E>> {
     }
 
     /// Disk store, resume.
-    pub fn resumed_disk_store(mut self, store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>) -> Self {
+    pub fn resumed_disk_store(
+        mut self,
+        store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
+    ) -> Self {
         let mutator = move |builder: BeaconChainBuilder<_>| {
             builder
                 .resume_from_db()
@@ -692,15 +698,10 @@ where
         self.mock_builder = Some(Arc::new(mock_builder));
 
         // Sanity check.
-        let el_builder = self
-            .chain
-            .execution_layer
-            .as_ref()
-            .unwrap()
-            .builder()
-            .unwrap();
-        let mock_el_builder = mock_el.el.builder().unwrap();
-        assert!(Arc::ptr_eq(&el_builder, &mock_el_builder));
+        let el_builder = self.chain.execution_layer.as_ref().unwrap().builders();
+        let mock_el_builder = mock_el.el.builders();
+        assert_eq!(el_builder.len(), 1);
+        assert!(Arc::ptr_eq(&el_builder[0], &mock_el_builder[0]));
 
         mock_builder_server
     }
@@ -864,6 +865,7 @@ where
                 Some(graffiti),
                 ProduceBlockVerification::VerifyRandao,
                 None,
+                None,
                 BlockProductionVersion::FullV2,
             )
             .await
@@ -928,6 +930,7 @@ where
                 Some(graffiti),
                 ProduceBlockVerification::VerifyRandao,
                 None,
+                None,
                 BlockProductionVersion::FullV2,
             )
             .await
@@ -19,6 +19,7 @@ use logging::test_logger;
 use maplit::hashset;
 use rand::Rng;
 use slot_clock::{SlotClock, TestingSlotClock};
+use ssz::Encode;
 use state_processing::{state_advance::complete_state_advance, BlockReplayer};
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -26,12 +27,21 @@ use std::convert::TryInto;
 use std::sync::Arc;
 use std::time::Duration;
 use store::chunked_vector::Chunk;
-use store::metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION, STATE_UPPER_LIMIT_NO_RETAIN};
+use store::config::{
+    OnDiskStoreConfig, OnDiskStoreConfigV1, DEFAULT_SLOTS_PER_RESTORE_POINT,
+    PREV_DEFAULT_SLOTS_PER_RESTORE_POINT,
+};
+use store::hdiff::HierarchyConfig;
+use store::metadata::{
+    SchemaVersion, CONFIG_KEY, CURRENT_SCHEMA_VERSION, SCHEMA_VERSION_KEY,
+    STATE_UPPER_LIMIT_NO_RETAIN,
+};
 use store::{
     chunked_vector::{chunk_key, Field},
     get_key_for_col,
     iter::{BlockRootsIterator, StateRootsIterator},
-    BlobInfo, DBColumn, HotColdDB, KeyValueStore, KeyValueStoreOp, LevelDB, StoreConfig,
+    BeaconNodeBackend, BlobInfo, DBColumn, HotColdDB, ItemStore, KeyValueStore, KeyValueStoreOp,
+    StoreConfig,
 };
 use tempfile::{tempdir, TempDir};
 use tokio::time::sleep;
@@ -50,7 +60,7 @@ lazy_static! {
 type E = MinimalEthSpec;
 type TestHarness = BeaconChainHarness<DiskHarnessType<E>>;
 
-fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>> {
     get_store_generic(db_path, StoreConfig::default(), test_spec::<E>())
 }
 
@@ -58,7 +68,7 @@ fn get_store_generic(
     db_path: &TempDir,
     config: StoreConfig,
     spec: ChainSpec,
-) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>> {
     let hot_path = db_path.path().join("hot_db");
     let cold_path = db_path.path().join("cold_db");
     let blobs_path = db_path.path().join("blobs_db");
@@ -77,7 +87,7 @@ fn get_store_generic(
 }
 
 fn get_harness(
-    store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
     validator_count: usize,
 ) -> TestHarness {
     // Most tests expect to retain historic states, so we use this as the default.
@@ -89,7 +99,7 @@ fn get_harness(
 }
 
 fn get_harness_generic(
-    store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
     validator_count: usize,
     chain_config: ChainConfig,
 ) -> TestHarness {
@@ -332,6 +342,55 @@ async fn heal_freezer_block_roots_at_genesis() {
     );
 }
 
+/// Tests that `store.repair_frozen_block_roots` overwrites corrupt block roots, including at skip
+/// slots.
+#[tokio::test]
+async fn repair_frozen_block_roots() {
+    let num_blocks_produced = E::slots_per_epoch() * 6;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    let current_state = harness.get_current_state();
+    let state_root = current_state.canonical_root().unwrap();
+    let all_validators = &harness.get_all_validators();
+    harness
+        .add_attested_blocks_at_slots(
+            current_state,
+            state_root,
+            &(1..=num_blocks_produced)
+                .filter(|i| i % 5 != 0)
+                .map(Slot::new)
+                .collect::<Vec<_>>(),
+            all_validators,
+        )
+        .await;
+
+    let split_slot = store.get_split_slot();
+    assert!(split_slot > 0);
+
+    // Nothing to repair in a healthy database.
+    assert_eq!(store.repair_frozen_block_roots().unwrap(), 0);
+
+    // Overwrite the block roots at a block slot and a skip slot with garbage.
+    let chunk_db_key = chunk_key(0);
+    let mut chunk =
+        Chunk::<Hash256>::load(&store.cold_db, DBColumn::BeaconBlockRoots, &chunk_db_key)
+            .unwrap()
+            .unwrap();
+    chunk.values[4] = Hash256::repeat_byte(0xff);
+    chunk.values[5] = Hash256::repeat_byte(0xff);
+
+    let mut ops = vec![];
+    chunk
+        .store(DBColumn::BeaconBlockRoots, &chunk_db_key, &mut ops)
+        .unwrap();
+    store.cold_db.do_atomically(ops).unwrap();
+
+    assert_eq!(store.repair_frozen_block_roots().unwrap(), 2);
+    check_freezer_block_roots(&harness, Slot::new(0), split_slot);
+}
+
 fn check_freezer_block_roots(harness: &TestHarness, start_slot: Slot, end_slot: Slot) {
     for slot in (start_slot.as_u64()..end_slot.as_u64()).map(Slot::new) {
         let (block_root, result_slot) = harness
@@ -373,6 +432,66 @@ async fn full_participation_no_skips() {
     check_iterators(&harness);
 }
 
+#[tokio::test]
+async fn hierarchical_state_diffs() {
+    let num_blocks_produced = E::slots_per_epoch() * 12;
+    let db_path = tempdir().unwrap();
+    let store_config = StoreConfig {
+        slots_per_restore_point: 8 * E::slots_per_epoch(),
+        hierarchy_config: Some(HierarchyConfig {
+            exponents: vec![3, 4],
+        }),
+        ..StoreConfig::default()
+    };
+    let store = get_store_generic(&db_path, store_config, test_spec::<E>());
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let diff_exists = |slot: u64| {
+        store
+            .cold_db
+            .key_exists(DBColumn::BeaconStateDiff.into(), &slot.to_be_bytes())
+            .unwrap()
+    };
+    let split_slot = store.get_split_slot().as_u64();
+    assert!(split_slot > 64);
+    for slot in (8..split_slot).step_by(8) {
+        assert_eq!(diff_exists(slot), slot % 64 != 0, "slot {slot}");
+    }
+
+    check_finalization(&harness, num_blocks_produced);
+    check_split_slot(&harness, store.clone());
+    check_chain_dump(&harness, num_blocks_produced + 1);
+    check_iterators(&harness);
+
+    // Converting to a sparser hierarchy removes the diffs that are no longer required.
+    store
+        .convert_freezer_hierarchy(Some(HierarchyConfig { exponents: vec![4] }))
+        .unwrap();
+    for slot in (8..split_slot).step_by(8) {
+        assert_eq!(
+            diff_exists(slot),
+            slot % 16 == 0 && slot % 64 != 0,
+            "slot {slot}"
+        );
+    }
+    check_chain_dump(&harness, num_blocks_produced + 1);
+
+    // Converting back to restore points only removes all diffs.
+    store.convert_freezer_hierarchy(None).unwrap();
+    for slot in (8..split_slot).step_by(8) {
+        assert!(!diff_exists(slot), "slot {slot}");
+    }
+    check_chain_dump(&harness, num_blocks_produced + 1);
+}
+
 #[tokio::test]
 async fn randomised_skips() {
     let num_slots = E::slots_per_epoch() * 5;
@@ -2544,10 +2663,10 @@ async fn weak_subjectivity_sync_test(slots: Vec<Slot>, checkpoint_slot: Slot) {
     // signatures correctly. Regression test for https://github.com/sigp/lighthouse/pull/5120.
     let mut batch_with_invalid_first_block = available_blocks.clone();
     batch_with_invalid_first_block[0] = {
-        let (block_root, block, blobs) = available_blocks[0].clone().deconstruct();
+        let (block_root, block, blobs, _) = available_blocks[0].clone().deconstruct();
         let mut corrupt_block = (*block).clone();
         *corrupt_block.signature_mut() = Signature::empty();
-        AvailableBlock::__new_for_testing(block_root, Arc::new(corrupt_block), blobs)
+        AvailableBlock::__new_for_testing(block_root, Arc::new(corrupt_block), blobs, None)
     };
 
     // Importing the invalid batch should error.
@@ -3113,6 +3232,83 @@ async fn schema_downgrade_to_min_version() {
     .expect_err("should not downgrade below minimum version");
 }
 
+/// Check that a database created with the previous default slots-per-restore-point, whose config
+/// is still in the layout from before schema v22, opens with the default flags.
+#[test]
+fn open_v21_config_with_prev_default_slots_per_restore_point() {
+    type Mainnet = MainnetEthSpec;
+
+    let db_path = tempdir().unwrap();
+    let hot_path = db_path.path().join("hot_db");
+    let cold_path = db_path.path().join("cold_db");
+    let blobs_path = db_path.path().join("blobs_db");
+    let spec = ChainSpec::mainnet();
+    let log = test_logger();
+
+    // Create a database and rewrite its metadata as a v21 database would have stored it.
+    let store = HotColdDB::<Mainnet, BeaconNodeBackend<Mainnet>, BeaconNodeBackend<Mainnet>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        StoreConfig {
+            slots_per_restore_point: PREV_DEFAULT_SLOTS_PER_RESTORE_POINT,
+            slots_per_restore_point_set_explicitly: true,
+            ..StoreConfig::default()
+        },
+        spec.clone(),
+        log.clone(),
+    )
+    .unwrap();
+    let v1_config = OnDiskStoreConfigV1 {
+        slots_per_restore_point: PREV_DEFAULT_SLOTS_PER_RESTORE_POINT,
+    };
+    store
+        .hot_db
+        .put_bytes(
+            DBColumn::BeaconMeta.into(),
+            CONFIG_KEY.as_bytes(),
+            &v1_config.as_ssz_bytes(),
+        )
+        .unwrap();
+    store.store_schema_version(SchemaVersion(21)).unwrap();
+    drop(store);
+
+    // Re-open the database with the default flags, migrating it to the current schema.
+    let migrate_spec = spec.clone();
+    let migrate_log = log.clone();
+    let store = HotColdDB::<Mainnet, BeaconNodeBackend<Mainnet>, BeaconNodeBackend<Mainnet>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |db, from, to| {
+            migrate_schema::<DiskHarnessType<Mainnet>>(db, 0, from, to, migrate_log, &migrate_spec)
+        },
+        StoreConfig {
+            slots_per_restore_point: DEFAULT_SLOTS_PER_RESTORE_POINT,
+            ..StoreConfig::default()
+        },
+        spec,
+        log,
+    )
+    .expect("database with the previous default spacing should open");
+
+    assert_eq!(
+        store.get_config().slots_per_restore_point,
+        PREV_DEFAULT_SLOTS_PER_RESTORE_POINT
+    );
+    let schema_version: SchemaVersion = store.hot_db.get(&SCHEMA_VERSION_KEY).unwrap().unwrap();
+    assert_eq!(schema_version, CURRENT_SCHEMA_VERSION);
+    let disk_config: OnDiskStoreConfig = store.hot_db.get(&CONFIG_KEY).unwrap().unwrap();
+    assert_eq!(
+        disk_config,
+        OnDiskStoreConfig {
+            slots_per_restore_point: PREV_DEFAULT_SLOTS_PER_RESTORE_POINT,
+            hierarchy_exponents: vec![],
+        }
+    );
+}
+
 /// Check that blob pruning prunes blobs older than the data availability boundary.
 #[tokio::test]
 async fn deneb_prune_blobs_happy_case() {
@@ -3563,7 +3759,10 @@ fn check_finalization(harness: &TestHarness, expected_slot: u64) {
 }
 
 /// Check that the HotColdDB's split_slot is equal to the start slot of the last finalized epoch.
-fn check_split_slot(harness: &TestHarness, store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>) {
+fn check_split_slot(
+    harness: &TestHarness,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
+) {
     let split_slot = store.get_split_slot();
     assert_eq!(
         harness
@@ -37,6 +37,10 @@
 //! Whenever the manager receives a notification that a worker has finished a parcel of work, it
 //! checks the queues to see if there are more parcels of work that can be spawned in a new worker
 //! task.
+//!
+//! The order in which queues are checked, their capacities and the number of workers each may
+//! occupy are determined by the `scheduler` module and can be configured with a
+//! `SchedulingPolicy`.
 
 use crate::work_reprocessing_queue::{
     QueuedBackfillBatch, QueuedGossipBlock, ReprocessQueueMessage,
@@ -46,11 +50,12 @@ use futures::task::Poll;
 use lighthouse_network::{MessageId, NetworkGlobals, PeerId};
 use logging::TimeLatch;
 use parking_lot::Mutex;
+use scheduler::{QueueId, Scheduler, SchedulingPolicy};
 use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, trace, warn, Logger};
 use slot_clock::SlotClock;
 use std::cmp;
-use std::collections::{HashSet, VecDeque};
+use std::collections::HashSet;
 use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
@@ -71,6 +76,7 @@ use work_reprocessing_queue::{
 };
 
 mod metrics;
+pub mod scheduler;
 pub mod work_reprocessing_queue;
 
 /// The maximum size of the channel for work events to the `BeaconProcessor`.
@@ -108,6 +114,7 @@ pub struct BeaconProcessorQueueLengths {
     unknown_light_client_update_queue: usize,
     rpc_block_queue: usize,
     rpc_blob_queue: usize,
+    rpc_custody_column_queue: usize,
     chain_segment_queue: usize,
     backfill_chain_segment: usize,
     gossip_block_queue: usize,
@@ -119,10 +126,13 @@ pub struct BeaconProcessorQueueLengths {
     bbroots_queue: usize,
     blbroots_queue: usize,
     blbrange_queue: usize,
+    dcbroots_queue: usize,
+    dcbrange_queue: usize,
     gossip_bls_to_execution_change_queue: usize,
     lc_bootstrap_queue: usize,
     lc_optimistic_update_queue: usize,
     lc_finality_update_queue: usize,
+    lc_update_range_queue: usize,
     api_request_p0_queue: usize,
     api_request_p1_queue: usize,
 }
@@ -161,6 +171,7 @@ impl BeaconProcessorQueueLengths {
             unknown_light_client_update_queue: 128,
             rpc_block_queue: 1024,
             rpc_blob_queue: 1024,
+            rpc_custody_column_queue: 1024,
             chain_segment_queue: 64,
             backfill_chain_segment: 64,
             gossip_block_queue: 1024,
@@ -172,14 +183,100 @@ impl BeaconProcessorQueueLengths {
             bbroots_queue: 1024,
             blbroots_queue: 1024,
             blbrange_queue: 1024,
+            dcbroots_queue: 1024,
+            dcbrange_queue: 1024,
             gossip_bls_to_execution_change_queue: 16384,
             lc_bootstrap_queue: 1024,
             lc_optimistic_update_queue: 512,
             lc_finality_update_queue: 512,
+            lc_update_range_queue: 512,
             api_request_p0_queue: 1024,
             api_request_p1_queue: 1024,
         })
     }
+
+    /// The default capacity of the given queue, which may be overridden by a `SchedulingPolicy`.
+    pub fn capacity(&self, queue_id: QueueId) -> usize {
+        match queue_id {
+            QueueId::ChainSegment => self.chain_segment_queue,
+            QueueId::RpcBlock => self.rpc_block_queue,
+            QueueId::RpcBlob => self.rpc_blob_queue,
+            QueueId::RpcCustodyColumn => self.rpc_custody_column_queue,
+            QueueId::DelayedBlock => self.delayed_block_queue,
+            QueueId::GossipBlock => self.gossip_block_queue,
+            QueueId::GossipBlob => self.gossip_blob_queue,
+            QueueId::GossipDataColumn => self.gossip_data_column_queue,
+            QueueId::ApiRequestP0 => self.api_request_p0_queue,
+            QueueId::Aggregate => self.aggregate_queue,
+            QueueId::Attestation => self.attestation_queue,
+            QueueId::SyncContribution => self.sync_contribution_queue,
+            QueueId::SyncMessage => self.sync_message_queue,
+            QueueId::UnknownBlockAggregate => self.unknown_block_aggregate_queue,
+            QueueId::UnknownBlockAttestation => self.unknown_block_attestation_queue,
+            QueueId::Status => self.status_queue,
+            QueueId::BlocksByRange => self.bbrange_queue,
+            QueueId::BlocksByRoots => self.bbroots_queue,
+            QueueId::BlobsByRange => self.blbrange_queue,
+            QueueId::BlobsByRoots => self.blbroots_queue,
+            QueueId::DataColumnsByRoots => self.dcbroots_queue,
+            QueueId::DataColumnsByRange => self.dcbrange_queue,
+            QueueId::GossipAttesterSlashing => self.gossip_attester_slashing_queue,
+            QueueId::GossipProposerSlashing => self.gossip_proposer_slashing_queue,
+            QueueId::GossipVoluntaryExit => self.gossip_voluntary_exit_queue,
+            QueueId::GossipBlsToExecutionChange => self.gossip_bls_to_execution_change_queue,
+            QueueId::ApiRequestP1 => self.api_request_p1_queue,
+            QueueId::BackfillChainSegment => self.backfill_chain_segment,
+            QueueId::LightClientBootstrap => self.lc_bootstrap_queue,
+            QueueId::LightClientOptimisticUpdate => self.lc_optimistic_update_queue,
+            QueueId::LightClientFinalityUpdate => self.lc_finality_update_queue,
+            QueueId::LightClientUpdatesByRange => self.lc_update_range_queue,
+            QueueId::GossipLightClientFinalityUpdate => self.finality_update_queue,
+            QueueId::GossipLightClientOptimisticUpdate => self.optimistic_update_queue,
+            QueueId::UnknownLightClientUpdate => self.unknown_light_client_update_queue,
+        }
+    }
+
+    /// Every queue with the same capacity, for testing.
+    #[cfg(test)]
+    pub(crate) fn uniform(capacity: usize) -> Self {
+        Self {
+            aggregate_queue: capacity,
+            attestation_queue: capacity,
+            unknown_block_aggregate_queue: capacity,
+            unknown_block_attestation_queue: capacity,
+            sync_message_queue: capacity,
+            sync_contribution_queue: capacity,
+            gossip_voluntary_exit_queue: capacity,
+            gossip_proposer_slashing_queue: capacity,
+            gossip_attester_slashing_queue: capacity,
+            finality_update_queue: capacity,
+            optimistic_update_queue: capacity,
+            unknown_light_client_update_queue: capacity,
+            rpc_block_queue: capacity,
+            rpc_blob_queue: capacity,
+            rpc_custody_column_queue: capacity,
+            chain_segment_queue: capacity,
+            backfill_chain_segment: capacity,
+            gossip_block_queue: capacity,
+            gossip_blob_queue: capacity,
+            gossip_data_column_queue: capacity,
+            delayed_block_queue: capacity,
+            status_queue: capacity,
+            bbrange_queue: capacity,
+            bbroots_queue: capacity,
+            blbroots_queue: capacity,
+            blbrange_queue: capacity,
+            dcbroots_queue: capacity,
+            dcbrange_queue: capacity,
+            gossip_bls_to_execution_change_queue: capacity,
+            lc_bootstrap_queue: capacity,
+            lc_optimistic_update_queue: capacity,
+            lc_finality_update_queue: capacity,
+            lc_update_range_queue: capacity,
+            api_request_p0_queue: capacity,
+            api_request_p1_queue: capacity,
+        }
+    }
 }
 
 /// The name of the manager tokio task.
@@ -223,6 +320,7 @@ pub const GOSSIP_LIGHT_CLIENT_OPTIMISTIC_UPDATE: &str = "light_client_optimistic
 pub const RPC_BLOCK: &str = "rpc_block";
 pub const IGNORED_RPC_BLOCK: &str = "ignored_rpc_block";
 pub const RPC_BLOBS: &str = "rpc_blob";
+pub const RPC_CUSTODY_COLUMN: &str = "rpc_custody_column";
 pub const CHAIN_SEGMENT: &str = "chain_segment";
 pub const CHAIN_SEGMENT_BACKFILL: &str = "chain_segment_backfill";
 pub const STATUS_PROCESSING: &str = "status_processing";
@@ -230,9 +328,12 @@ pub const BLOCKS_BY_RANGE_REQUEST: &str = "blocks_by_range_request";
 pub const BLOCKS_BY_ROOTS_REQUEST: &str = "blocks_by_roots_request";
 pub const BLOBS_BY_RANGE_REQUEST: &str = "blobs_by_range_request";
 pub const BLOBS_BY_ROOTS_REQUEST: &str = "blobs_by_roots_request";
+pub const DATA_COLUMNS_BY_ROOTS_REQUEST: &str = "data_columns_by_roots_request";
+pub const DATA_COLUMNS_BY_RANGE_REQUEST: &str = "data_columns_by_range_request";
 pub const LIGHT_CLIENT_BOOTSTRAP_REQUEST: &str = "light_client_bootstrap";
 pub const LIGHT_CLIENT_FINALITY_UPDATE_REQUEST: &str = "light_client_finality_update_request";
 pub const LIGHT_CLIENT_OPTIMISTIC_UPDATE_REQUEST: &str = "light_client_optimistic_update_request";
+pub const LIGHT_CLIENT_UPDATES_BY_RANGE_REQUEST: &str = "light_client_updates_by_range_request";
 pub const UNKNOWN_BLOCK_ATTESTATION: &str = "unknown_block_attestation";
 pub const UNKNOWN_BLOCK_AGGREGATE: &str = "unknown_block_aggregate";
 pub const UNKNOWN_LIGHT_CLIENT_UPDATE: &str = "unknown_light_client_update";
@@ -248,6 +349,7 @@ pub struct BeaconProcessorConfig {
     pub max_gossip_attestation_batch_size: usize,
     pub max_gossip_aggregate_batch_size: usize,
     pub enable_backfill_rate_limiting: bool,
+    pub scheduling_policy: SchedulingPolicy,
 }
 
 impl Default for BeaconProcessorConfig {
@@ -259,6 +361,7 @@ impl Default for BeaconProcessorConfig {
             max_gossip_attestation_batch_size: DEFAULT_MAX_GOSSIP_ATTESTATION_BATCH_SIZE,
             max_gossip_aggregate_batch_size: DEFAULT_MAX_GOSSIP_AGGREGATE_BATCH_SIZE,
             enable_backfill_rate_limiting: true,
+            scheduling_policy: SchedulingPolicy::default(),
         }
     }
 }
@@ -293,90 +396,6 @@ impl<E: EthSpec> Default for BeaconProcessorChannels<E> {
     }
 }
 
-/// A simple first-in-first-out queue with a maximum length.
-struct FifoQueue<T> {
-    queue: VecDeque<T>,
-    max_length: usize,
-}
-
-impl<T> FifoQueue<T> {
-    /// Create a new, empty queue with the given length.
-    pub fn new(max_length: usize) -> Self {
-        Self {
-            queue: VecDeque::default(),
-            max_length,
-        }
-    }
-
-    /// Add a new item to the queue.
-    ///
-    /// Drops `item` if the queue is full.
-    pub fn push(&mut self, item: T, item_desc: &str, log: &Logger) {
-        if self.queue.len() == self.max_length {
-            error!(
-                log,
-                "Work queue is full";
-                "msg" => "the system has insufficient resources for load",
-                "queue_len" => self.max_length,
-                "queue" => item_desc,
-            )
-        } else {
-            self.queue.push_back(item);
-        }
-    }
-
-    /// Remove the next item from the queue.
-    pub fn pop(&mut self) -> Option<T> {
-        self.queue.pop_front()
-    }
-
-    /// Returns the current length of the queue.
-    pub fn len(&self) -> usize {
-        self.queue.len()
-    }
-}
-
-/// A simple last-in-first-out queue with a maximum length.
-struct LifoQueue<T> {
-    queue: VecDeque<T>,
-    max_length: usize,
-}
-
-impl<T> LifoQueue<T> {
-    /// Create a new, empty queue with the given length.
-    pub fn new(max_length: usize) -> Self {
-        Self {
-            queue: VecDeque::default(),
-            max_length,
-        }
-    }
-
-    /// Add a new item to the front of the queue.
-    ///
-    /// If the queue is full, the item at the back of the queue is dropped.
-    pub fn push(&mut self, item: T) {
-        if self.queue.len() == self.max_length {
-            self.queue.pop_back();
-        }
-        self.queue.push_front(item);
-    }
-
-    /// Remove the next item from the queue.
-    pub fn pop(&mut self) -> Option<T> {
-        self.queue.pop_front()
-    }
-
-    /// Returns `true` if the queue is full.
-    pub fn is_full(&self) -> bool {
-        self.queue.len() >= self.max_length
-    }
-
-    /// Returns the current length of the queue.
-    pub fn len(&self) -> usize {
-        self.queue.len()
-    }
-}
-
 /// A handle that sends a message on the provided channel to a receiver when it gets dropped.
 ///
 /// The receiver task is responsible for removing the provided `entry` from the `DuplicateCache`
@@ -599,6 +618,9 @@ pub enum Work<E: EthSpec> {
     RpcBlobs {
         process_fn: AsyncFn,
     },
+    RpcCustodyColumn {
+        process_fn: AsyncFn,
+    },
     IgnoredRpcBlock {
         process_fn: BlockingFn,
     },
@@ -609,10 +631,13 @@ pub enum Work<E: EthSpec> {
     BlocksByRootsRequest(AsyncFn),
     BlobsByRangeRequest(BlockingFn),
     BlobsByRootsRequest(BlockingFn),
+    DataColumnsByRootsRequest(BlockingFn),
+    DataColumnsByRangeRequest(BlockingFn),
     GossipBlsToExecutionChange(BlockingFn),
     LightClientBootstrapRequest(BlockingFn),
     LightClientOptimisticUpdateRequest(BlockingFn),
     LightClientFinalityUpdateRequest(BlockingFn),
+    LightClientUpdatesByRangeRequest(BlockingFn),
     ApiRequestP0(BlockingOrAsync),
     ApiRequestP1(BlockingOrAsync),
 }
@@ -644,6 +669,7 @@ impl<E: EthSpec> Work<E> {
             Work::GossipLightClientOptimisticUpdate(_) => GOSSIP_LIGHT_CLIENT_OPTIMISTIC_UPDATE,
             Work::RpcBlock { .. } => RPC_BLOCK,
             Work::RpcBlobs { .. } => RPC_BLOBS,
+            Work::RpcCustodyColumn { .. } => RPC_CUSTODY_COLUMN,
             Work::IgnoredRpcBlock { .. } => IGNORED_RPC_BLOCK,
             Work::ChainSegment { .. } => CHAIN_SEGMENT,
             Work::ChainSegmentBackfill(_) => CHAIN_SEGMENT_BACKFILL,
@@ -652,9 +678,12 @@ impl<E: EthSpec> Work<E> {
             Work::BlocksByRootsRequest(_) => BLOCKS_BY_ROOTS_REQUEST,
             Work::BlobsByRangeRequest(_) => BLOBS_BY_RANGE_REQUEST,
             Work::BlobsByRootsRequest(_) => BLOBS_BY_ROOTS_REQUEST,
+            Work::DataColumnsByRootsRequest(_) => DATA_COLUMNS_BY_ROOTS_REQUEST,
+            Work::DataColumnsByRangeRequest(_) => DATA_COLUMNS_BY_RANGE_REQUEST,
             Work::LightClientBootstrapRequest(_) => LIGHT_CLIENT_BOOTSTRAP_REQUEST,
             Work::LightClientOptimisticUpdateRequest(_) => LIGHT_CLIENT_OPTIMISTIC_UPDATE_REQUEST,
             Work::LightClientFinalityUpdateRequest(_) => LIGHT_CLIENT_FINALITY_UPDATE_REQUEST,
+            Work::LightClientUpdatesByRangeRequest(_) => LIGHT_CLIENT_UPDATES_BY_RANGE_REQUEST,
             Work::UnknownBlockAttestation { .. } => UNKNOWN_BLOCK_ATTESTATION,
             Work::UnknownBlockAggregate { .. } => UNKNOWN_BLOCK_AGGREGATE,
             Work::GossipBlsToExecutionChange(_) => GOSSIP_BLS_TO_EXECUTION_CHANGE,
@@ -663,12 +692,59 @@ impl<E: EthSpec> Work<E> {
             Work::ApiRequestP1 { .. } => API_REQUEST_P1,
         }
     }
+
+    /// The queue which holds this work while it waits for a worker.
+    ///
+    /// Batches are assigned to the queue their items were taken from.
+    fn queue_id(&self) -> QueueId {
+        match self {
+            Work::GossipAttestation { .. } | Work::GossipAttestationBatch { .. } => {
+                QueueId::Attestation
+            }
+            Work::GossipAggregate { .. } | Work::GossipAggregateBatch { .. } => QueueId::Aggregate,
+            Work::GossipBlock(_) => QueueId::GossipBlock,
+            Work::GossipBlobSidecar(_) => QueueId::GossipBlob,
+            Work::GossipDataColumnSidecar(_) => QueueId::GossipDataColumn,
+            Work::DelayedImportBlock { .. } => QueueId::DelayedBlock,
+            Work::GossipVoluntaryExit(_) => QueueId::GossipVoluntaryExit,
+            Work::GossipProposerSlashing(_) => QueueId::GossipProposerSlashing,
+            Work::GossipAttesterSlashing(_) => QueueId::GossipAttesterSlashing,
+            Work::GossipSyncSignature(_) => QueueId::SyncMessage,
+            Work::GossipSyncContribution(_) => QueueId::SyncContribution,
+            Work::GossipLightClientFinalityUpdate(_) => QueueId::GossipLightClientFinalityUpdate,
+            Work::GossipLightClientOptimisticUpdate(_) => {
+                QueueId::GossipLightClientOptimisticUpdate
+            }
+            Work::RpcBlock { .. } | Work::IgnoredRpcBlock { .. } => QueueId::RpcBlock,
+            Work::RpcBlobs { .. } => QueueId::RpcBlob,
+            Work::RpcCustodyColumn { .. } => QueueId::RpcCustodyColumn,
+            Work::ChainSegment(_) => QueueId::ChainSegment,
+            Work::ChainSegmentBackfill(_) => QueueId::BackfillChainSegment,
+            Work::Status(_) => QueueId::Status,
+            Work::BlocksByRangeRequest(_) => QueueId::BlocksByRange,
+            Work::BlocksByRootsRequest(_) => QueueId::BlocksByRoots,
+            Work::BlobsByRangeRequest(_) => QueueId::BlobsByRange,
+            Work::BlobsByRootsRequest(_) => QueueId::BlobsByRoots,
+            Work::DataColumnsByRootsRequest(_) => QueueId::DataColumnsByRoots,
+            Work::DataColumnsByRangeRequest(_) => QueueId::DataColumnsByRange,
+            Work::LightClientBootstrapRequest(_) => QueueId::LightClientBootstrap,
+            Work::LightClientOptimisticUpdateRequest(_) => QueueId::LightClientOptimisticUpdate,
+            Work::LightClientFinalityUpdateRequest(_) => QueueId::LightClientFinalityUpdate,
+            Work::LightClientUpdatesByRangeRequest(_) => QueueId::LightClientUpdatesByRange,
+            Work::UnknownBlockAttestation { .. } => QueueId::UnknownBlockAttestation,
+            Work::UnknownBlockAggregate { .. } => QueueId::UnknownBlockAggregate,
+            Work::GossipBlsToExecutionChange(_) => QueueId::GossipBlsToExecutionChange,
+            Work::UnknownLightClientOptimisticUpdate { .. } => QueueId::UnknownLightClientUpdate,
+            Work::ApiRequestP0 { .. } => QueueId::ApiRequestP0,
+            Work::ApiRequestP1 { .. } => QueueId::ApiRequestP1,
+        }
+    }
 }
 
 /// Unifies all the messages processed by the `BeaconProcessor`.
 enum InboundEvent<E: EthSpec> {
-    /// A worker has completed a task and is free.
-    WorkerIdle,
+    /// A worker which was processing work from the given queue has completed it and is free.
+    WorkerIdle(QueueId),
     /// There is new work to be done.
     WorkEvent(WorkEvent<E>),
     /// A work event that was queued for re-processing has become ready.
@@ -681,7 +757,7 @@ enum InboundEvent<E: EthSpec> {
 /// control (specifically in the ordering of event processing).
 struct InboundEvents<E: EthSpec> {
     /// Used by workers when they finish a task.
-    idle_rx: mpsc::Receiver<()>,
+    idle_rx: mpsc::Receiver<QueueId>,
     /// Used by upstream processes to send new work to the `BeaconProcessor`.
     event_rx: mpsc::Receiver<WorkEvent<E>>,
     /// Used internally for queuing work ready to be re-processed.
@@ -695,8 +771,8 @@ impl<E: EthSpec> Stream for InboundEvents<E> {
         // Always check for idle workers before anything else. This allows us to ensure that a big
         // stream of new events doesn't suppress the processing of existing events.
         match self.idle_rx.poll_recv(cx) {
-            Poll::Ready(Some(())) => {
-                return Poll::Ready(Some(InboundEvent::WorkerIdle));
+            Poll::Ready(Some(queue_id)) => {
+                return Poll::Ready(Some(InboundEvent::WorkerIdle(queue_id)));
             }
             Poll::Ready(None) => {
                 return Poll::Ready(None);
@@ -766,67 +842,12 @@ impl<E: EthSpec> BeaconProcessor<E> {
         queue_lengths: BeaconProcessorQueueLengths,
     ) -> Result<(), String> {
         // Used by workers to communicate that they are finished a task.
-        let (idle_tx, idle_rx) = mpsc::channel::<()>(MAX_IDLE_QUEUE_LEN);
+        let (idle_tx, idle_rx) = mpsc::channel::<QueueId>(MAX_IDLE_QUEUE_LEN);
 
-        // Using LIFO queues for attestations since validator profits rely upon getting fresh
-        // attestations into blocks. Additionally, later attestations contain more information than
-        // earlier ones, so we consider them more valuable.
-        let mut aggregate_queue = LifoQueue::new(queue_lengths.aggregate_queue);
+        // Holds all queued work and decides which queue is served whenever a worker is free.
+        let mut scheduler = Scheduler::new(&queue_lengths, &self.config.scheduling_policy);
         let mut aggregate_debounce = TimeLatch::default();
-        let mut attestation_queue = LifoQueue::new(queue_lengths.attestation_queue);
         let mut attestation_debounce = TimeLatch::default();
-        let mut unknown_block_aggregate_queue =
-            LifoQueue::new(queue_lengths.unknown_block_aggregate_queue);
-        let mut unknown_block_attestation_queue =
-            LifoQueue::new(queue_lengths.unknown_block_attestation_queue);
-
-        let mut sync_message_queue = LifoQueue::new(queue_lengths.sync_message_queue);
-        let mut sync_contribution_queue = LifoQueue::new(queue_lengths.sync_contribution_queue);
-
-        // Using a FIFO queue for voluntary exits since it prevents exit censoring. I don't have
-        // a strong feeling about queue type for exits.
-        let mut gossip_voluntary_exit_queue =
-            FifoQueue::new(queue_lengths.gossip_voluntary_exit_queue);
-
-        // Using a FIFO queue for slashing to prevent people from flushing their slashings from the
-        // queues with lots of junk messages.
-        let mut gossip_proposer_slashing_queue =
-            FifoQueue::new(queue_lengths.gossip_proposer_slashing_queue);
-        let mut gossip_attester_slashing_queue =
-            FifoQueue::new(queue_lengths.gossip_attester_slashing_queue);
-
-        // Using a FIFO queue for light client updates to maintain sequence order.
-        let mut finality_update_queue = FifoQueue::new(queue_lengths.finality_update_queue);
-        let mut optimistic_update_queue = FifoQueue::new(queue_lengths.optimistic_update_queue);
-        let mut unknown_light_client_update_queue =
-            FifoQueue::new(queue_lengths.unknown_light_client_update_queue);
-
-        // Using a FIFO queue since blocks need to be imported sequentially.
-        let mut rpc_block_queue = FifoQueue::new(queue_lengths.rpc_block_queue);
-        let mut rpc_blob_queue = FifoQueue::new(queue_lengths.rpc_blob_queue);
-        let mut chain_segment_queue = FifoQueue::new(queue_lengths.chain_segment_queue);
-        let mut backfill_chain_segment = FifoQueue::new(queue_lengths.backfill_chain_segment);
-        let mut gossip_block_queue = FifoQueue::new(queue_lengths.gossip_block_queue);
-        let mut gossip_blob_queue = FifoQueue::new(queue_lengths.gossip_blob_queue);
-        let mut gossip_data_column_queue = FifoQueue::new(queue_lengths.gossip_data_column_queue);
-        let mut delayed_block_queue = FifoQueue::new(queue_lengths.delayed_block_queue);
-
-        let mut status_queue = FifoQueue::new(queue_lengths.status_queue);
-        let mut bbrange_queue = FifoQueue::new(queue_lengths.bbrange_queue);
-        let mut bbroots_queue = FifoQueue::new(queue_lengths.bbroots_queue);
-        let mut blbroots_queue = FifoQueue::new(queue_lengths.blbroots_queue);
-        let mut blbrange_queue = FifoQueue::new(queue_lengths.blbrange_queue);
-
-        let mut gossip_bls_to_execution_change_queue =
-            FifoQueue::new(queue_lengths.gossip_bls_to_execution_change_queue);
-
-        let mut lc_bootstrap_queue = FifoQueue::new(queue_lengths.lc_bootstrap_queue);
-        let mut lc_optimistic_update_queue =
-            FifoQueue::new(queue_lengths.lc_optimistic_update_queue);
-        let mut lc_finality_update_queue = FifoQueue::new(queue_lengths.lc_finality_update_queue);
-
-        let mut api_request_p0_queue = FifoQueue::new(queue_lengths.api_request_p0_queue);
-        let mut api_request_p1_queue = FifoQueue::new(queue_lengths.api_request_p1_queue);
 
         // Channels for sending work to the re-process scheduler (`work_reprocessing_tx`) and to
         // receive them back once they are ready (`ready_work_rx`).
@@ -856,8 +877,9 @@ impl<E: EthSpec> BeaconProcessor<E> {
 
             loop {
                 let work_event = match inbound_events.next().await {
-                    Some(InboundEvent::WorkerIdle) => {
+                    Some(InboundEvent::WorkerIdle(queue_id)) => {
                         self.current_workers = self.current_workers.saturating_sub(1);
+                        scheduler.worker_finished(queue_id);
                         None
                     }
                     Some(InboundEvent::WorkEvent(event)) if enable_backfill_rate_limiting => {
@@ -947,209 +969,16 @@ impl<E: EthSpec> BeaconProcessor<E> {
                     // We don't check the `work.drop_during_sync` here. We assume that if it made
                     // it into the queue at any point then we should process it.
                     None if can_spawn => {
-                        // Check for chain segments first, they're the most efficient way to get
-                        // blocks into the system.
-                        if let Some(item) = chain_segment_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        // Check sync blocks before gossip blocks, since we've already explicitly
-                        // requested these blocks.
-                        } else if let Some(item) = rpc_block_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = rpc_blob_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        // Check delayed blocks before gossip blocks, the gossip blocks might rely
-                        // on the delayed ones.
-                        } else if let Some(item) = delayed_block_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        // Check gossip blocks before gossip attestations, since a block might be
-                        // required to verify some attestations.
-                        } else if let Some(item) = gossip_block_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = gossip_blob_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = gossip_data_column_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        // Check the priority 0 API requests after blocks and blobs, but before attestations.
-                        } else if let Some(item) = api_request_p0_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        // Check the aggregates, *then* the unaggregates since we assume that
-                        // aggregates are more valuable to local validators and effectively give us
-                        // more information with less signature verification time.
-                        } else if aggregate_queue.len() > 0 {
-                            let batch_size = cmp::min(
-                                aggregate_queue.len(),
-                                self.config.max_gossip_aggregate_batch_size,
-                            );
-
-                            if batch_size < 2 {
-                                // One single aggregate is in the queue, process it individually.
-                                if let Some(item) = aggregate_queue.pop() {
-                                    self.spawn_worker(item, idle_tx);
-                                }
-                            } else {
-                                // Collect two or more aggregates into a batch, so they can take
-                                // advantage of batch signature verification.
-                                //
-                                // Note: this will convert the `Work::GossipAggregate` item into a
-                                // `Work::GossipAggregateBatch` item.
-                                let mut aggregates = Vec::with_capacity(batch_size);
-                                let mut process_batch_opt = None;
-                                for _ in 0..batch_size {
-                                    if let Some(item) = aggregate_queue.pop() {
-                                        match item {
-                                            Work::GossipAggregate {
-                                                aggregate,
-                                                process_individual: _,
-                                                process_batch,
-                                            } => {
-                                                aggregates.push(*aggregate);
-                                                if process_batch_opt.is_none() {
-                                                    process_batch_opt = Some(process_batch);
-                                                }
-                                            }
-                                            _ => {
-                                                error!(self.log, "Invalid item in aggregate queue");
-                                            }
-                                        }
-                                    }
-                                }
-
-                                if let Some(process_batch) = process_batch_opt {
-                                    // Process all aggregates with a single worker.
-                                    self.spawn_worker(
-                                        Work::GossipAggregateBatch {
-                                            aggregates,
-                                            process_batch,
-                                        },
-                                        idle_tx,
-                                    )
-                                } else {
-                                    // There is no good reason for this to
-                                    // happen, it is a serious logic error.
-                                    // Since we only form batches when multiple
-                                    // work items exist, we should always have a
-                                    // work closure at this point.
-                                    crit!(self.log, "Missing aggregate work");
-                                }
+                        if let Some(queue_id) = scheduler.next_ready_queue() {
+                            let work = match queue_id {
+                                QueueId::Aggregate => self.pop_aggregate_batch(&mut scheduler),
+                                QueueId::Attestation => self.pop_attestation_batch(&mut scheduler),
+                                _ => scheduler.pop(queue_id),
+                            };
+                            if let Some(work) = work {
+                                scheduler.worker_started(queue_id);
+                                self.spawn_worker(work, queue_id, idle_tx);
                             }
-                        // Check the unaggregated attestation queue.
-                        //
-                        // Potentially use batching.
-                        } else if attestation_queue.len() > 0 {
-                            let batch_size = cmp::min(
-                                attestation_queue.len(),
-                                self.config.max_gossip_attestation_batch_size,
-                            );
-
-                            if batch_size < 2 {
-                                // One single attestation is in the queue, process it individually.
-                                if let Some(item) = attestation_queue.pop() {
-                                    self.spawn_worker(item, idle_tx);
-                                }
-                            } else {
-                                // Collect two or more attestations into a batch, so they can take
-                                // advantage of batch signature verification.
-                                //
-                                // Note: this will convert the `Work::GossipAttestation` item into a
-                                // `Work::GossipAttestationBatch` item.
-                                let mut attestations = Vec::with_capacity(batch_size);
-                                let mut process_batch_opt = None;
-                                for _ in 0..batch_size {
-                                    if let Some(item) = attestation_queue.pop() {
-                                        match item {
-                                            Work::GossipAttestation {
-                                                attestation,
-                                                process_individual: _,
-                                                process_batch,
-                                            } => {
-                                                attestations.push(*attestation);
-                                                if process_batch_opt.is_none() {
-                                                    process_batch_opt = Some(process_batch);
-                                                }
-                                            }
-                                            _ => error!(
-                                                self.log,
-                                                "Invalid item in attestation queue"
-                                            ),
-                                        }
-                                    }
-                                }
-
-                                if let Some(process_batch) = process_batch_opt {
-                                    // Process all attestations with a single worker.
-                                    self.spawn_worker(
-                                        Work::GossipAttestationBatch {
-                                            attestations,
-                                            process_batch,
-                                        },
-                                        idle_tx,
-                                    )
-                                } else {
-                                    // There is no good reason for this to
-                                    // happen, it is a serious logic error.
-                                    // Since we only form batches when multiple
-                                    // work items exist, we should always have a
-                                    // work closure at this point.
-                                    crit!(self.log, "Missing attestations work");
-                                }
-                            }
-                        // Check sync committee messages after attestations as their rewards are lesser
-                        // and they don't influence fork choice.
-                        } else if let Some(item) = sync_contribution_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = sync_message_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        // Aggregates and unaggregates queued for re-processing are older and we
-                        // care about fresher ones, so check those first.
-                        } else if let Some(item) = unknown_block_aggregate_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = unknown_block_attestation_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        // Check RPC methods next. Status messages are needed for sync so
-                        // prioritize them over syncing requests from other peers (BlocksByRange
-                        // and BlocksByRoot)
-                        } else if let Some(item) = status_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = bbrange_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = bbroots_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = blbrange_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = blbroots_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        // Check slashings after all other consensus messages so we prioritize
-                        // following head.
-                        //
-                        // Check attester slashings before proposer slashings since they have the
-                        // potential to slash multiple validators at once.
-                        } else if let Some(item) = gossip_attester_slashing_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = gossip_proposer_slashing_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        // Check exits and address changes late since our validators don't get
-                        // rewards from them.
-                        } else if let Some(item) = gossip_voluntary_exit_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = gossip_bls_to_execution_change_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        // Check the priority 1 API requests after we've
-                        // processed all the interesting things from the network
-                        // and things required for us to stay in good repute
-                        // with our P2P peers.
-                        } else if let Some(item) = api_request_p1_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        // Handle backfill sync chain segments.
-                        } else if let Some(item) = backfill_chain_segment.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        // Handle light client requests.
-                        } else if let Some(item) = lc_bootstrap_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = lc_optimistic_update_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = lc_finality_update_queue.pop() {
-                            self.spawn_worker(item, idle_tx);
-                            // This statement should always be the final else statement.
                         } else {
                             // Let the journal know that a worker is freed and there's nothing else
                             // for it to do.
@@ -1192,9 +1021,13 @@ impl<E: EthSpec> BeaconProcessor<E> {
                     Some(WorkEvent { work, .. }) => {
                         let work_id = work.str_id();
 
+                        let queue_id = work.queue_id();
+
                         match work {
-                            _ if can_spawn => self.spawn_worker(work, idle_tx),
-                            Work::GossipAttestation { .. } => attestation_queue.push(work),
+                            _ if can_spawn && scheduler.has_worker_capacity(queue_id) => {
+                                scheduler.worker_started(queue_id);
+                                self.spawn_worker(work, queue_id, idle_tx)
+                            }
                             // Attestation batches are formed internally within the
                             // `BeaconProcessor`, they are not sent from external services.
                             Work::GossipAttestationBatch { .. } => crit!(
@@ -1202,7 +1035,6 @@ impl<E: EthSpec> BeaconProcessor<E> {
                                     "Unsupported inbound event";
                                     "type" => "GossipAttestationBatch"
                             ),
-                            Work::GossipAggregate { .. } => aggregate_queue.push(work),
                             // Aggregate batches are formed internally within the `BeaconProcessor`,
                             // they are not sent from external services.
                             Work::GossipAggregateBatch { .. } => crit!(
@@ -1210,87 +1042,7 @@ impl<E: EthSpec> BeaconProcessor<E> {
                                     "Unsupported inbound event";
                                     "type" => "GossipAggregateBatch"
                             ),
-                            Work::GossipBlock { .. } => {
-                                gossip_block_queue.push(work, work_id, &self.log)
-                            }
-                            Work::GossipBlobSidecar { .. } => {
-                                gossip_blob_queue.push(work, work_id, &self.log)
-                            }
-                            Work::GossipDataColumnSidecar { .. } => {
-                                gossip_data_column_queue.push(work, work_id, &self.log)
-                            }
-                            Work::DelayedImportBlock { .. } => {
-                                delayed_block_queue.push(work, work_id, &self.log)
-                            }
-                            Work::GossipVoluntaryExit { .. } => {
-                                gossip_voluntary_exit_queue.push(work, work_id, &self.log)
-                            }
-                            Work::GossipProposerSlashing { .. } => {
-                                gossip_proposer_slashing_queue.push(work, work_id, &self.log)
-                            }
-                            Work::GossipAttesterSlashing { .. } => {
-                                gossip_attester_slashing_queue.push(work, work_id, &self.log)
-                            }
-                            Work::GossipSyncSignature { .. } => sync_message_queue.push(work),
-                            Work::GossipSyncContribution { .. } => {
-                                sync_contribution_queue.push(work)
-                            }
-                            Work::GossipLightClientFinalityUpdate { .. } => {
-                                finality_update_queue.push(work, work_id, &self.log)
-                            }
-                            Work::GossipLightClientOptimisticUpdate { .. } => {
-                                optimistic_update_queue.push(work, work_id, &self.log)
-                            }
-                            Work::RpcBlock { .. } | Work::IgnoredRpcBlock { .. } => {
-                                rpc_block_queue.push(work, work_id, &self.log)
-                            }
-                            Work::RpcBlobs { .. } => rpc_blob_queue.push(work, work_id, &self.log),
-                            Work::ChainSegment { .. } => {
-                                chain_segment_queue.push(work, work_id, &self.log)
-                            }
-                            Work::ChainSegmentBackfill { .. } => {
-                                backfill_chain_segment.push(work, work_id, &self.log)
-                            }
-                            Work::Status { .. } => status_queue.push(work, work_id, &self.log),
-                            Work::BlocksByRangeRequest { .. } => {
-                                bbrange_queue.push(work, work_id, &self.log)
-                            }
-                            Work::BlocksByRootsRequest { .. } => {
-                                bbroots_queue.push(work, work_id, &self.log)
-                            }
-                            Work::BlobsByRangeRequest { .. } => {
-                                blbrange_queue.push(work, work_id, &self.log)
-                            }
-                            Work::LightClientBootstrapRequest { .. } => {
-                                lc_bootstrap_queue.push(work, work_id, &self.log)
-                            }
-                            Work::LightClientOptimisticUpdateRequest { .. } => {
-                                lc_optimistic_update_queue.push(work, work_id, &self.log)
-                            }
-                            Work::LightClientFinalityUpdateRequest { .. } => {
-                                lc_finality_update_queue.push(work, work_id, &self.log)
-                            }
-                            Work::UnknownBlockAttestation { .. } => {
-                                unknown_block_attestation_queue.push(work)
-                            }
-                            Work::UnknownBlockAggregate { .. } => {
-                                unknown_block_aggregate_queue.push(work)
-                            }
-                            Work::GossipBlsToExecutionChange { .. } => {
-                                gossip_bls_to_execution_change_queue.push(work, work_id, &self.log)
-                            }
-                            Work::BlobsByRootsRequest { .. } => {
-                                blbroots_queue.push(work, work_id, &self.log)
-                            }
-                            Work::UnknownLightClientOptimisticUpdate { .. } => {
-                                unknown_light_client_update_queue.push(work, work_id, &self.log)
-                            }
-                            Work::ApiRequestP0 { .. } => {
-                                api_request_p0_queue.push(work, work_id, &self.log)
-                            }
-                            Work::ApiRequestP1 { .. } => {
-                                api_request_p1_queue.push(work, work_id, &self.log)
-                            }
+                            _ => scheduler.push(queue_id, work, work_id, &self.log),
                         }
                     }
                 }
@@ -1301,88 +1053,92 @@ impl<E: EthSpec> BeaconProcessor<E> {
                 );
                 metrics::set_gauge(
                     &metrics::BEACON_PROCESSOR_UNAGGREGATED_ATTESTATION_QUEUE_TOTAL,
-                    attestation_queue.len() as i64,
+                    scheduler.len(QueueId::Attestation) as i64,
                 );
                 metrics::set_gauge(
                     &metrics::BEACON_PROCESSOR_AGGREGATED_ATTESTATION_QUEUE_TOTAL,
-                    aggregate_queue.len() as i64,
+                    scheduler.len(QueueId::Aggregate) as i64,
                 );
                 metrics::set_gauge(
                     &metrics::BEACON_PROCESSOR_SYNC_MESSAGE_QUEUE_TOTAL,
-                    sync_message_queue.len() as i64,
+                    scheduler.len(QueueId::SyncMessage) as i64,
                 );
                 metrics::set_gauge(
                     &metrics::BEACON_PROCESSOR_SYNC_CONTRIBUTION_QUEUE_TOTAL,
-                    sync_contribution_queue.len() as i64,
+                    scheduler.len(QueueId::SyncContribution) as i64,
                 );
                 metrics::set_gauge(
                     &metrics::BEACON_PROCESSOR_GOSSIP_BLOCK_QUEUE_TOTAL,
-                    gossip_block_queue.len() as i64,
+                    scheduler.len(QueueId::GossipBlock) as i64,
                 );
                 metrics::set_gauge(
                     &metrics::BEACON_PROCESSOR_GOSSIP_BLOB_QUEUE_TOTAL,
-                    gossip_blob_queue.len() as i64,
+                    scheduler.len(QueueId::GossipBlob) as i64,
                 );
                 metrics::set_gauge(
                     &metrics::BEACON_PROCESSOR_GOSSIP_DATA_COLUMN_QUEUE_TOTAL,
-                    gossip_data_column_queue.len() as i64,
+                    scheduler.len(QueueId::GossipDataColumn) as i64,
                 );
                 metrics::set_gauge(
                     &metrics::BEACON_PROCESSOR_RPC_BLOCK_QUEUE_TOTAL,
-                    rpc_block_queue.len() as i64,
+                    scheduler.len(QueueId::RpcBlock) as i64,
                 );
                 metrics::set_gauge(
                     &metrics::BEACON_PROCESSOR_RPC_BLOB_QUEUE_TOTAL,
-                    rpc_blob_queue.len() as i64,
+                    scheduler.len(QueueId::RpcBlob) as i64,
+                );
+                metrics::set_gauge(
+                    &metrics::BEACON_PROCESSOR_RPC_CUSTODY_COLUMN_QUEUE_TOTAL,
+                    scheduler.len(QueueId::RpcCustodyColumn) as i64,
                 );
                 metrics::set_gauge(
                     &metrics::BEACON_PROCESSOR_CHAIN_SEGMENT_QUEUE_TOTAL,
-                    chain_segment_queue.len() as i64,
+                    scheduler.len(QueueId::ChainSegment) as i64,
                 );
                 metrics::set_gauge(
                     &metrics::BEACON_PROCESSOR_BACKFILL_CHAIN_SEGMENT_QUEUE_TOTAL,
-                    backfill_chain_segment.len() as i64,
+                    scheduler.len(QueueId::BackfillChainSegment) as i64,
                 );
                 metrics::set_gauge(
                     &metrics::BEACON_PROCESSOR_EXIT_QUEUE_TOTAL,
-                    gossip_voluntary_exit_queue.len() as i64,
+                    scheduler.len(QueueId::GossipVoluntaryExit) as i64,
                 );
                 metrics::set_gauge(
                     &metrics::BEACON_PROCESSOR_PROPOSER_SLASHING_QUEUE_TOTAL,
-                    gossip_proposer_slashing_queue.len() as i64,
+                    scheduler.len(QueueId::GossipProposerSlashing) as i64,
                 );
                 metrics::set_gauge(
                     &metrics::BEACON_PROCESSOR_ATTESTER_SLASHING_QUEUE_TOTAL,
-                    gossip_attester_slashing_queue.len() as i64,
+                    scheduler.len(QueueId::GossipAttesterSlashing) as i64,
                 );
                 metrics::set_gauge(
                     &metrics::BEACON_PROCESSOR_BLS_TO_EXECUTION_CHANGE_QUEUE_TOTAL,
-                    gossip_bls_to_execution_change_queue.len() as i64,
+                    scheduler.len(QueueId::GossipBlsToExecutionChange) as i64,
                 );
                 metrics::set_gauge(
                     &metrics::BEACON_PROCESSOR_API_REQUEST_P0_QUEUE_TOTAL,
-                    api_request_p0_queue.len() as i64,
+                    scheduler.len(QueueId::ApiRequestP0) as i64,
                 );
                 metrics::set_gauge(
                     &metrics::BEACON_PROCESSOR_API_REQUEST_P1_QUEUE_TOTAL,
-                    api_request_p1_queue.len() as i64,
+                    scheduler.len(QueueId::ApiRequestP1) as i64,
                 );
 
-                if aggregate_queue.is_full() && aggregate_debounce.elapsed() {
+                if scheduler.is_full(QueueId::Aggregate) && aggregate_debounce.elapsed() {
                     error!(
                         self.log,
                         "Aggregate attestation queue full";
                         "msg" => "the system has insufficient resources for load",
-                        "queue_len" => aggregate_queue.max_length,
+                        "queue_len" => scheduler.capacity(QueueId::Aggregate),
                     )
                 }
 
-                if attestation_queue.is_full() && attestation_debounce.elapsed() {
+                if scheduler.is_full(QueueId::Attestation) && attestation_debounce.elapsed() {
                     error!(
                         self.log,
                         "Attestation queue full";
                         "msg" => "the system has insufficient resources for load",
-                        "queue_len" => attestation_queue.max_length,
+                        "queue_len" => scheduler.capacity(QueueId::Attestation),
                     )
                 }
             }
@@ -1393,10 +1149,114 @@ impl<E: EthSpec> BeaconProcessor<E> {
         Ok(())
     }
 
-    /// Spawns a blocking worker thread to process some `Work`.
+    /// Pops aggregates from the `Aggregate` queue, collecting two or more of them into a batch so
+    /// they can take advantage of batch signature verification.
     ///
-    /// Sends an message on `idle_tx` when the work is complete and the task is stopping.
-    fn spawn_worker(&mut self, work: Work<E>, idle_tx: mpsc::Sender<()>) {
+    /// Note: this will convert the `Work::GossipAggregate` items into a
+    /// `Work::GossipAggregateBatch` item.
+    fn pop_aggregate_batch(&self, scheduler: &mut Scheduler<Work<E>>) -> Option<Work<E>> {
+        let batch_size = cmp::min(
+            scheduler.len(QueueId::Aggregate),
+            self.config.max_gossip_aggregate_batch_size,
+        );
+
+        if batch_size < 2 {
+            // One single aggregate is in the queue, process it individually.
+            return scheduler.pop(QueueId::Aggregate);
+        }
+
+        let mut aggregates = Vec::with_capacity(batch_size);
+        let mut process_batch_opt = None;
+        for _ in 0..batch_size {
+            if let Some(item) = scheduler.pop(QueueId::Aggregate) {
+                match item {
+                    Work::GossipAggregate {
+                        aggregate,
+                        process_individual: _,
+                        process_batch,
+                    } => {
+                        aggregates.push(*aggregate);
+                        if process_batch_opt.is_none() {
+                            process_batch_opt = Some(process_batch);
+                        }
+                    }
+                    _ => {
+                        error!(self.log, "Invalid item in aggregate queue");
+                    }
+                }
+            }
+        }
+
+        if let Some(process_batch) = process_batch_opt {
+            // Process all aggregates with a single worker.
+            Some(Work::GossipAggregateBatch {
+                aggregates,
+                process_batch,
+            })
+        } else {
+            // There is no good reason for this to happen, it is a serious logic error. Since we
+            // only form batches when multiple work items exist, we should always have a work
+            // closure at this point.
+            crit!(self.log, "Missing aggregate work");
+            None
+        }
+    }
+
+    /// Pops unaggregated attestations from the `Attestation` queue, collecting two or more of
+    /// them into a batch so they can take advantage of batch signature verification.
+    ///
+    /// Note: this will convert the `Work::GossipAttestation` items into a
+    /// `Work::GossipAttestationBatch` item.
+    fn pop_attestation_batch(&self, scheduler: &mut Scheduler<Work<E>>) -> Option<Work<E>> {
+        let batch_size = cmp::min(
+            scheduler.len(QueueId::Attestation),
+            self.config.max_gossip_attestation_batch_size,
+        );
+
+        if batch_size < 2 {
+            // One single attestation is in the queue, process it individually.
+            return scheduler.pop(QueueId::Attestation);
+        }
+
+        let mut attestations = Vec::with_capacity(batch_size);
+        let mut process_batch_opt = None;
+        for _ in 0..batch_size {
+            if let Some(item) = scheduler.pop(QueueId::Attestation) {
+                match item {
+                    Work::GossipAttestation {
+                        attestation,
+                        process_individual: _,
+                        process_batch,
+                    } => {
+                        attestations.push(*attestation);
+                        if process_batch_opt.is_none() {
+                            process_batch_opt = Some(process_batch);
+                        }
+                    }
+                    _ => error!(self.log, "Invalid item in attestation queue"),
+                }
+            }
+        }
+
+        if let Some(process_batch) = process_batch_opt {
+            // Process all attestations with a single worker.
+            Some(Work::GossipAttestationBatch {
+                attestations,
+                process_batch,
+            })
+        } else {
+            // There is no good reason for this to happen, it is a serious logic error. Since we
+            // only form batches when multiple work items exist, we should always have a work
+            // closure at this point.
+            crit!(self.log, "Missing attestations work");
+            None
+        }
+    }
+
+    /// Spawns a blocking worker thread to process some `Work` taken from the queue `queue_id`.
+    ///
+    /// Sends `queue_id` on `idle_tx` when the work is complete and the task is stopping.
+    fn spawn_worker(&mut self, work: Work<E>, queue_id: QueueId, idle_tx: mpsc::Sender<QueueId>) {
         let work_id = work.str_id();
         let worker_timer =
             metrics::start_timer_vec(&metrics::BEACON_PROCESSOR_WORKER_TIME, &[work_id]);
@@ -1412,6 +1272,7 @@ impl<E: EthSpec> BeaconProcessor<E> {
         // As such, this instantiation should happen as early in the function as possible.
         let send_idle_on_drop = SendOnDrop {
             tx: idle_tx,
+            queue_id,
             _worker_timer: worker_timer,
             log: self.log.clone(),
         };
@@ -1474,16 +1335,19 @@ impl<E: EthSpec> BeaconProcessor<E> {
                 beacon_block_root: _,
                 process_fn,
             } => task_spawner.spawn_async(process_fn),
-            Work::RpcBlock { process_fn } | Work::RpcBlobs { process_fn } => {
-                task_spawner.spawn_async(process_fn)
-            }
+            Work::RpcBlock { process_fn }
+            | Work::RpcBlobs { process_fn }
+            | Work::RpcCustodyColumn { process_fn } => task_spawner.spawn_async(process_fn),
             Work::IgnoredRpcBlock { process_fn } => task_spawner.spawn_blocking(process_fn),
             Work::GossipBlock(work)
             | Work::GossipBlobSidecar(work)
             | Work::GossipDataColumnSidecar(work) => task_spawner.spawn_async(async move {
                 work.await;
             }),
-            Work::BlobsByRangeRequest(process_fn) | Work::BlobsByRootsRequest(process_fn) => {
+            Work::BlobsByRangeRequest(process_fn)
+            | Work::BlobsByRootsRequest(process_fn)
+            | Work::DataColumnsByRootsRequest(process_fn)
+            | Work::DataColumnsByRangeRequest(process_fn) => {
                 task_spawner.spawn_blocking(process_fn)
             }
             Work::BlocksByRangeRequest(work) | Work::BlocksByRootsRequest(work) => {
@@ -1505,7 +1369,8 @@ impl<E: EthSpec> BeaconProcessor<E> {
             | Work::GossipBlsToExecutionChange(process_fn)
             | Work::LightClientBootstrapRequest(process_fn)
             | Work::LightClientOptimisticUpdateRequest(process_fn)
-            | Work::LightClientFinalityUpdateRequest(process_fn) => {
+            | Work::LightClientFinalityUpdateRequest(process_fn)
+            | Work::LightClientUpdatesByRangeRequest(process_fn) => {
                 task_spawner.spawn_blocking(process_fn)
             }
         };
@@ -1562,7 +1427,8 @@ impl TaskSpawner {
 ///
 /// https://doc.rust-lang.org/std/ops/trait.Drop.html#panics
 pub struct SendOnDrop {
-    tx: mpsc::Sender<()>,
+    tx: mpsc::Sender<QueueId>,
+    queue_id: QueueId,
     // The field is unused, but it's here to ensure the timer is dropped once the task has finished.
     _worker_timer: Option<metrics::HistogramTimer>,
     log: Logger,
@@ -1570,7 +1436,7 @@ pub struct SendOnDrop {
 
 impl Drop for SendOnDrop {
     fn drop(&mut self) {
-        if let Err(e) = self.tx.try_send(()) {
+        if let Err(e) = self.tx.try_send(self.queue_id) {
             warn!(
                 self.log,
                 "Unable to free worker";
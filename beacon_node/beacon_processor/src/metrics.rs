@@ -25,6 +25,11 @@ lazy_static::lazy_static! {
         "Time taken for a worker to fully process some parcel of work.",
         &["type"]
     );
+    pub static ref BEACON_PROCESSOR_QUEUE_WAIT_SECONDS: Result<HistogramVec> = try_create_histogram_vec(
+        "beacon_processor_queue_wait_seconds",
+        "Time a parcel of work spent in a queue before being taken by a worker.",
+        &["queue"]
+    );
     pub static ref BEACON_PROCESSOR_WORKERS_SPAWNED_TOTAL: Result<IntCounter> = try_create_int_counter(
         "beacon_processor_workers_spawned_total",
         "The number of workers ever spawned by the gossip processing pool."
@@ -86,6 +91,10 @@ lazy_static::lazy_static! {
         "beacon_processor_rpc_blob_queue_total",
         "Count of blobs from the rpc waiting to be verified."
     );
+    pub static ref BEACON_PROCESSOR_RPC_CUSTODY_COLUMN_QUEUE_TOTAL: Result<IntGauge> = try_create_int_gauge(
+        "beacon_processor_rpc_custody_column_queue_total",
+        "Count of custody columns from the rpc waiting to be imported."
+    );
     // Chain segments.
     pub static ref BEACON_PROCESSOR_CHAIN_SEGMENT_QUEUE_TOTAL: Result<IntGauge> = try_create_int_gauge(
         "beacon_processor_chain_segment_queue_total",
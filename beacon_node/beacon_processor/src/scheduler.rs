@@ -0,0 +1,478 @@
+//! Provides the queues of the `BeaconProcessor` and the policy which decides which of them is
+//! served next.
+//!
+//! Each kind of `Work` is placed into one of the queues identified by `QueueId`. Whenever a worker
+//! is available, the manager starts the next item from the non-empty queue with the lowest
+//! `priority`, skipping any queue which has already reached its `max_workers` limit.
+//!
+//! The defaults reproduce the long-standing hard-coded behaviour: queues are served in the order
+//! they are declared in `QueueId`, attestation and sync committee queues are LIFO and all others
+//! are FIFO. Any of these properties can be overridden per queue with a `SchedulingPolicy`.
+
+use crate::{metrics, BeaconProcessorQueueLengths};
+use serde::{Deserialize, Serialize};
+use slog::{error, Logger};
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Instant;
+use strum::IntoStaticStr;
+
+/// The gap between the default priorities of adjacent queues, leaving room to slot a queue in
+/// between two others without having to re-prioritise both.
+pub const DEFAULT_PRIORITY_STEP: u32 = 10;
+
+/// Identifies a work queue in the `BeaconProcessor`.
+///
+/// Variants are declared in order of their default priority, highest first.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, IntoStaticStr,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum QueueId {
+    /// Chain segments are the most efficient way to get blocks into the system.
+    ChainSegment,
+    /// Sync blocks are checked before gossip blocks, since we've explicitly requested them.
+    RpcBlock,
+    RpcBlob,
+    RpcCustodyColumn,
+    /// Delayed blocks are checked before gossip blocks, which might rely on them.
+    DelayedBlock,
+    /// Gossip blocks are checked before attestations, which might require them for verification.
+    GossipBlock,
+    GossipBlob,
+    GossipDataColumn,
+    /// Priority 0 API requests are served after blocks and blobs, but before attestations.
+    ApiRequestP0,
+    /// Aggregates are checked before unaggregated attestations, since they are more valuable to
+    /// local validators and carry more information per signature verification.
+    Aggregate,
+    Attestation,
+    /// Sync committee messages are checked after attestations since their rewards are lesser and
+    /// they don't influence fork choice.
+    SyncContribution,
+    SyncMessage,
+    /// Attestations queued for re-processing are older than fresh ones.
+    UnknownBlockAggregate,
+    UnknownBlockAttestation,
+    /// Status messages are needed for sync so they're prioritised over requests from peers.
+    Status,
+    BlocksByRange,
+    BlocksByRoots,
+    BlobsByRange,
+    BlobsByRoots,
+    DataColumnsByRoots,
+    DataColumnsByRange,
+    /// Attester slashings are checked before proposer slashings since they can slash multiple
+    /// validators at once.
+    GossipAttesterSlashing,
+    GossipProposerSlashing,
+    /// Exits and address changes are checked late since our validators don't get rewards from
+    /// them.
+    GossipVoluntaryExit,
+    GossipBlsToExecutionChange,
+    /// Priority 1 API requests are served once everything from the network which keeps us in
+    /// good repute with our peers has been handled.
+    ApiRequestP1,
+    BackfillChainSegment,
+    LightClientBootstrap,
+    LightClientOptimisticUpdate,
+    LightClientFinalityUpdate,
+    LightClientUpdatesByRange,
+    GossipLightClientFinalityUpdate,
+    GossipLightClientOptimisticUpdate,
+    UnknownLightClientUpdate,
+}
+
+impl QueueId {
+    /// Every queue, in declaration order (i.e., the default priority order).
+    pub const ALL: [QueueId; 35] = [
+        QueueId::ChainSegment,
+        QueueId::RpcBlock,
+        QueueId::RpcBlob,
+        QueueId::RpcCustodyColumn,
+        QueueId::DelayedBlock,
+        QueueId::GossipBlock,
+        QueueId::GossipBlob,
+        QueueId::GossipDataColumn,
+        QueueId::ApiRequestP0,
+        QueueId::Aggregate,
+        QueueId::Attestation,
+        QueueId::SyncContribution,
+        QueueId::SyncMessage,
+        QueueId::UnknownBlockAggregate,
+        QueueId::UnknownBlockAttestation,
+        QueueId::Status,
+        QueueId::BlocksByRange,
+        QueueId::BlocksByRoots,
+        QueueId::BlobsByRange,
+        QueueId::BlobsByRoots,
+        QueueId::DataColumnsByRoots,
+        QueueId::DataColumnsByRange,
+        QueueId::GossipAttesterSlashing,
+        QueueId::GossipProposerSlashing,
+        QueueId::GossipVoluntaryExit,
+        QueueId::GossipBlsToExecutionChange,
+        QueueId::ApiRequestP1,
+        QueueId::BackfillChainSegment,
+        QueueId::LightClientBootstrap,
+        QueueId::LightClientOptimisticUpdate,
+        QueueId::LightClientFinalityUpdate,
+        QueueId::LightClientUpdatesByRange,
+        QueueId::GossipLightClientFinalityUpdate,
+        QueueId::GossipLightClientOptimisticUpdate,
+        QueueId::UnknownLightClientUpdate,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The priority of this queue if it is not overridden. Lower values are served first.
+    pub fn default_priority(self) -> u32 {
+        self.index() as u32 * DEFAULT_PRIORITY_STEP
+    }
+
+    /// The order of this queue if it is not overridden.
+    ///
+    /// LIFO queues are used for attestations since validator profits rely upon getting fresh
+    /// attestations into blocks, and later attestations contain more information than earlier
+    /// ones. Everything else is FIFO, which prevents censoring (e.g. of exits and slashings by
+    /// flooding the queue with junk) and keeps blocks and light client updates in sequence.
+    pub fn default_order(self) -> QueueOrder {
+        match self {
+            QueueId::Aggregate
+            | QueueId::Attestation
+            | QueueId::UnknownBlockAggregate
+            | QueueId::UnknownBlockAttestation
+            | QueueId::SyncMessage
+            | QueueId::SyncContribution => QueueOrder::Lifo,
+            _ => QueueOrder::Fifo,
+        }
+    }
+}
+
+/// The order in which items are taken from a queue, and which item is dropped when it is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QueueOrder {
+    /// Oldest first. New items are dropped when the queue is full.
+    Fifo,
+    /// Newest first. The oldest item is dropped when the queue is full.
+    Lifo,
+}
+
+/// Overrides for a single queue. Fields which are `None` keep their defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QueuePolicy {
+    /// The maximum number of items held in the queue.
+    #[serde(default)]
+    pub capacity: Option<usize>,
+    /// Queues with lower values are served first.
+    #[serde(default)]
+    pub priority: Option<u32>,
+    /// The maximum number of workers concurrently processing items from this queue.
+    #[serde(default)]
+    pub max_workers: Option<usize>,
+    #[serde(default)]
+    pub order: Option<QueueOrder>,
+}
+
+/// Per-queue overrides of the default scheduling behaviour.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SchedulingPolicy {
+    #[serde(default)]
+    pub queues: BTreeMap<QueueId, QueuePolicy>,
+}
+
+impl SchedulingPolicy {
+    /// Returns an error if any override would leave a queue unable to make progress.
+    pub fn validate(&self) -> Result<(), String> {
+        for (queue_id, policy) in &self.queues {
+            let name: &'static str = queue_id.into();
+            if policy.capacity == Some(0) {
+                return Err(format!("capacity of queue {} must be non-zero", name));
+            }
+            if policy.max_workers == Some(0) {
+                return Err(format!("max_workers of queue {} must be non-zero", name));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A queue of items with a maximum length, which records how long each item waited.
+struct WorkQueue<T> {
+    id: QueueId,
+    items: VecDeque<(Instant, T)>,
+    capacity: usize,
+    order: QueueOrder,
+    max_workers: Option<usize>,
+    active_workers: usize,
+}
+
+impl<T> WorkQueue<T> {
+    fn push(&mut self, item: T, item_desc: &str, log: &Logger) {
+        let full = self.items.len() >= self.capacity;
+        match self.order {
+            QueueOrder::Fifo => {
+                if full {
+                    error!(
+                        log,
+                        "Work queue is full";
+                        "msg" => "the system has insufficient resources for load",
+                        "queue_len" => self.capacity,
+                        "queue" => item_desc,
+                    );
+                    return;
+                }
+                self.items.push_back((Instant::now(), item));
+            }
+            QueueOrder::Lifo => {
+                if full {
+                    self.items.pop_back();
+                }
+                self.items.push_front((Instant::now(), item));
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let (queued_at, item) = self.items.pop_front()?;
+        metrics::observe_timer_vec(
+            &metrics::BEACON_PROCESSOR_QUEUE_WAIT_SECONDS,
+            &[self.id.into()],
+            queued_at.elapsed(),
+        );
+        Some(item)
+    }
+
+    fn has_worker_capacity(&self) -> bool {
+        self.max_workers
+            .map_or(true, |max_workers| self.active_workers < max_workers)
+    }
+}
+
+/// Holds every queue of the `BeaconProcessor` and decides which is served next.
+pub struct Scheduler<T> {
+    /// Indexed by `QueueId::index`.
+    queues: Vec<WorkQueue<T>>,
+    /// Queues sorted by priority, highest priority first.
+    priority_order: Vec<QueueId>,
+}
+
+impl<T> Scheduler<T> {
+    pub fn new(queue_lengths: &BeaconProcessorQueueLengths, policy: &SchedulingPolicy) -> Self {
+        let queues = QueueId::ALL
+            .iter()
+            .map(|&id| {
+                let overrides = policy.queues.get(&id).cloned().unwrap_or_default();
+                WorkQueue {
+                    id,
+                    items: VecDeque::new(),
+                    capacity: overrides
+                        .capacity
+                        .unwrap_or_else(|| queue_lengths.capacity(id)),
+                    order: overrides.order.unwrap_or_else(|| id.default_order()),
+                    max_workers: overrides.max_workers,
+                    active_workers: 0,
+                }
+            })
+            .collect();
+
+        // The sort is stable, so queues with equal priorities keep their default order.
+        let mut priority_order = QueueId::ALL.to_vec();
+        priority_order.sort_by_key(|id| {
+            policy
+                .queues
+                .get(id)
+                .and_then(|overrides| overrides.priority)
+                .unwrap_or_else(|| id.default_priority())
+        });
+
+        Self {
+            queues,
+            priority_order,
+        }
+    }
+
+    fn queue(&self, id: QueueId) -> &WorkQueue<T> {
+        &self.queues[id.index()]
+    }
+
+    fn queue_mut(&mut self, id: QueueId) -> &mut WorkQueue<T> {
+        &mut self.queues[id.index()]
+    }
+
+    /// Add an item to a queue, dropping an item if the queue is full.
+    pub fn push(&mut self, id: QueueId, item: T, item_desc: &str, log: &Logger) {
+        self.queue_mut(id).push(item, item_desc, log)
+    }
+
+    /// Remove the next item from a queue.
+    pub fn pop(&mut self, id: QueueId) -> Option<T> {
+        self.queue_mut(id).pop()
+    }
+
+    pub fn len(&self, id: QueueId) -> usize {
+        self.queue(id).items.len()
+    }
+
+    pub fn capacity(&self, id: QueueId) -> usize {
+        self.queue(id).capacity
+    }
+
+    pub fn is_full(&self, id: QueueId) -> bool {
+        self.len(id) >= self.capacity(id)
+    }
+
+    /// Returns `true` if another worker may be started for items from the given queue.
+    pub fn has_worker_capacity(&self, id: QueueId) -> bool {
+        self.queue(id).has_worker_capacity()
+    }
+
+    pub fn worker_started(&mut self, id: QueueId) {
+        let queue = self.queue_mut(id);
+        queue.active_workers = queue.active_workers.saturating_add(1);
+    }
+
+    pub fn worker_finished(&mut self, id: QueueId) {
+        let queue = self.queue_mut(id);
+        queue.active_workers = queue.active_workers.saturating_sub(1);
+    }
+
+    /// Returns the highest priority queue which has an item waiting and is below its worker limit.
+    pub fn next_ready_queue(&self) -> Option<QueueId> {
+        self.priority_order
+            .iter()
+            .copied()
+            .find(|&id| self.len(id) > 0 && self.has_worker_capacity(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use logging::test_logger;
+
+    fn scheduler(policy: SchedulingPolicy) -> Scheduler<u64> {
+        Scheduler::new(&BeaconProcessorQueueLengths::uniform(4), &policy)
+    }
+
+    #[test]
+    fn queue_ids_are_declared_in_default_order() {
+        for (i, id) in QueueId::ALL.iter().enumerate() {
+            assert_eq!(id.index(), i);
+        }
+    }
+
+    #[test]
+    fn default_priorities_follow_declaration_order() {
+        let log = test_logger();
+        let mut scheduler = scheduler(SchedulingPolicy::default());
+
+        scheduler.push(QueueId::ApiRequestP1, 1, "api", &log);
+        scheduler.push(QueueId::Attestation, 2, "attestation", &log);
+        scheduler.push(QueueId::GossipBlock, 3, "block", &log);
+
+        assert_eq!(scheduler.next_ready_queue(), Some(QueueId::GossipBlock));
+        assert_eq!(scheduler.pop(QueueId::GossipBlock), Some(3));
+        assert_eq!(scheduler.next_ready_queue(), Some(QueueId::Attestation));
+        assert_eq!(scheduler.pop(QueueId::Attestation), Some(2));
+        assert_eq!(scheduler.next_ready_queue(), Some(QueueId::ApiRequestP1));
+        assert_eq!(scheduler.pop(QueueId::ApiRequestP1), Some(1));
+        assert_eq!(scheduler.next_ready_queue(), None);
+    }
+
+    #[test]
+    fn priority_override() {
+        let log = test_logger();
+        let mut policy = SchedulingPolicy::default();
+        policy.queues.insert(
+            QueueId::ApiRequestP1,
+            QueuePolicy {
+                priority: Some(0),
+                ..QueuePolicy::default()
+            },
+        );
+        let mut scheduler = scheduler(policy);
+
+        scheduler.push(QueueId::ChainSegment, 1, "chain_segment", &log);
+        scheduler.push(QueueId::ApiRequestP1, 2, "api", &log);
+
+        // Ties are broken by the default order.
+        assert_eq!(scheduler.next_ready_queue(), Some(QueueId::ChainSegment));
+        scheduler.pop(QueueId::ChainSegment);
+        assert_eq!(scheduler.next_ready_queue(), Some(QueueId::ApiRequestP1));
+    }
+
+    #[test]
+    fn worker_cap_skips_queue() {
+        let log = test_logger();
+        let mut policy = SchedulingPolicy::default();
+        policy.queues.insert(
+            QueueId::ApiRequestP0,
+            QueuePolicy {
+                max_workers: Some(1),
+                ..QueuePolicy::default()
+            },
+        );
+        let mut scheduler = scheduler(policy);
+
+        scheduler.push(QueueId::ApiRequestP0, 1, "api", &log);
+        scheduler.push(QueueId::Attestation, 2, "attestation", &log);
+
+        scheduler.worker_started(QueueId::ApiRequestP0);
+        assert!(!scheduler.has_worker_capacity(QueueId::ApiRequestP0));
+        assert_eq!(scheduler.next_ready_queue(), Some(QueueId::Attestation));
+
+        scheduler.worker_finished(QueueId::ApiRequestP0);
+        assert_eq!(scheduler.next_ready_queue(), Some(QueueId::ApiRequestP0));
+    }
+
+    #[test]
+    fn fifo_and_lifo_orders() {
+        let log = test_logger();
+        let mut policy = SchedulingPolicy::default();
+        policy.queues.insert(
+            QueueId::Attestation,
+            QueuePolicy {
+                order: Some(QueueOrder::Fifo),
+                ..QueuePolicy::default()
+            },
+        );
+        let mut scheduler = scheduler(policy);
+
+        for i in 0..5 {
+            scheduler.push(QueueId::Aggregate, i, "aggregate", &log);
+            scheduler.push(QueueId::Attestation, i, "attestation", &log);
+        }
+
+        // LIFO drops the oldest item when full and returns the newest first.
+        assert!(scheduler.is_full(QueueId::Aggregate));
+        assert_eq!(scheduler.pop(QueueId::Aggregate), Some(4));
+        // FIFO drops the newest item when full and returns the oldest first.
+        assert!(scheduler.is_full(QueueId::Attestation));
+        assert_eq!(scheduler.pop(QueueId::Attestation), Some(0));
+        for _ in 0..3 {
+            scheduler.pop(QueueId::Attestation);
+        }
+        assert_eq!(scheduler.pop(QueueId::Attestation), None);
+    }
+
+    #[test]
+    fn validate_rejects_zero_limits() {
+        let mut policy = SchedulingPolicy::default();
+        assert!(policy.validate().is_ok());
+
+        policy.queues.insert(
+            QueueId::Status,
+            QueuePolicy {
+                max_workers: Some(0),
+                ..QueuePolicy::default()
+            },
+        );
+        assert!(policy.validate().is_err());
+    }
+}
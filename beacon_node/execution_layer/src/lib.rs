@@ -5,7 +5,7 @@
 //! deposit-contract functionality that the `beacon_node/eth1` crate already provides.
 
 use crate::payload_cache::PayloadCache;
-use arc_swap::ArcSwapOption;
+use arc_swap::ArcSwap;
 use auth::{strip_prefix, Auth, JwtKey};
 pub use block_hash::calculate_execution_block_hash;
 use builder_client::BuilderHttpClient;
@@ -19,19 +19,20 @@ use eth2::types::FullPayloadContents;
 use eth2::types::{builder_bid::SignedBuilderBid, BlobsBundle, ForkVersionedResponse};
 use ethers_core::types::Transaction as EthersTransaction;
 use fork_choice::ForkchoiceUpdateParameters;
+use futures::future::join_all;
 use lru::LruCache;
-use payload_status::process_payload_status;
 pub use payload_status::PayloadStatus;
+use payload_status::{process_payload_status, reconcile_payload_statuses};
 use sensitive_url::SensitiveUrl;
 use serde::{Deserialize, Serialize};
-use slog::{crit, debug, error, info, warn, Logger};
+use slog::{crit, debug, error, info, o, warn, Logger};
 use slot_clock::SlotClock;
 use std::collections::HashMap;
 use std::fmt;
 use std::future::Future;
 use std::io::Write;
 use std::num::NonZeroUsize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use strum::AsRefStr;
@@ -75,6 +76,10 @@ pub const DEFAULT_JWT_FILE: &str = "jwt.hex";
 /// in an LRU cache to avoid redundant lookups. This is the size of that cache.
 const EXECUTION_BLOCKS_LRU_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(128);
 
+/// The number of winning builder bids for which we remember the relay that supplied them, so that
+/// the signed blinded block can be returned to the same relay.
+const BUILDER_BID_RELAYS_LRU_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(32);
+
 /// A fee recipient address for use during block production. Only used as a very last resort if
 /// there is no address provided by the user.
 ///
@@ -144,6 +149,10 @@ pub enum Error {
         transactions_root: Hash256,
     },
     InvalidJWTSecret(String),
+    RedundantSecretFileMismatch {
+        endpoints: usize,
+        secret_files: usize,
+    },
     InvalidForkForPayload,
     InvalidPayloadBody(String),
     InvalidPayloadConversion,
@@ -327,6 +336,9 @@ pub struct BuilderParams {
     pub pubkey: PublicKeyBytes,
     pub slot: Slot,
     pub chain_health: ChainHealth,
+    /// The minimum builder bid in Gwei requested for this proposal, which takes precedence over
+    /// `Config::builder_min_bid_gwei`.
+    pub min_bid_gwei: Option<u64>,
 }
 
 #[derive(PartialEq)]
@@ -346,9 +358,27 @@ pub enum FailedCondition {
 
 type PayloadContentsRefTuple<'a, E> = (ExecutionPayloadRef<'a, E>, Option<&'a BlobsBundle<E>>);
 
+/// The response from a single relay to a `get_header` request.
+type RelayBidResult<E> =
+    Result<Option<ForkVersionedResponse<SignedBuilderBid<E>>>, builder_client::Error>;
+
+/// The most valuable valid bid from the auction, alongside the relay which supplied it.
+type WinningBuilderBid<E> = (
+    ForkVersionedResponse<SignedBuilderBid<E>>,
+    Arc<BuilderHttpClient>,
+);
+
 struct Inner<E: EthSpec> {
-    engine: Arc<Engine>,
-    builder: ArcSwapOption<BuilderHttpClient>,
+    /// The attached execution engines. The first is the primary engine, which is used for
+    /// deposit-contract and PoW queries.
+    engines: Vec<Arc<Engine>>,
+    /// The number of engines which must report a payload as invalid before it is treated as such.
+    invalid_payload_quorum: usize,
+    builders: ArcSwap<Vec<Arc<BuilderHttpClient>>>,
+    /// The relay which supplied each winning builder bid, keyed by payload block hash.
+    builder_bid_relays: Mutex<LruCache<ExecutionBlockHash, Arc<BuilderHttpClient>>>,
+    /// Builder bids below this value (in Gwei) lose to the local payload.
+    builder_min_bid_gwei: Option<u64>,
     execution_engine_forkchoice_lock: Mutex<()>,
     suggested_fee_recipient: Option<Address>,
     proposer_preparation_data: Mutex<HashMap<u64, ProposerPreparationDataEntry>>,
@@ -368,13 +398,25 @@ struct Inner<E: EthSpec> {
 pub struct Config {
     /// Endpoint url for EL nodes that are running the engine api.
     pub execution_endpoint: Option<SensitiveUrl>,
+    /// Additional engine api endpoints which receive every `newPayload` and `forkchoiceUpdated`
+    /// call alongside `execution_endpoint`.
+    pub redundant_execution_endpoints: Vec<SensitiveUrl>,
+    /// JWT secrets for `redundant_execution_endpoints`, in the same order. When empty, the
+    /// `secret_file` is used for every endpoint.
+    pub redundant_secret_files: Vec<PathBuf>,
+    /// The number of engines which must agree that a payload is invalid before it is treated as
+    /// invalid. Defaults to a majority of the attached engines.
+    pub invalid_payload_quorum: Option<usize>,
     /// Endpoint urls for services providing the builder api.
-    pub builder_url: Option<SensitiveUrl>,
+    pub builder_urls: Vec<SensitiveUrl>,
     /// The timeout value used when making a request to fetch a block header
     /// from the builder api.
     pub builder_header_timeout: Option<Duration>,
     /// User agent to send with requests to the builder API.
     pub builder_user_agent: Option<String>,
+    /// Builder bids worth less than this many Gwei are ignored in favour of the local payload,
+    /// unless overridden for a particular proposal.
+    pub builder_min_bid_gwei: Option<u64>,
     /// JWT secret for the above endpoint running the engine api.
     pub secret_file: Option<PathBuf>,
     /// The default fee recipient to use on the beacon node if none if provided from
@@ -401,9 +443,13 @@ impl<E: EthSpec> ExecutionLayer<E> {
     pub fn from_config(config: Config, executor: TaskExecutor, log: Logger) -> Result<Self, Error> {
         let Config {
             execution_endpoint: url,
-            builder_url,
+            redundant_execution_endpoints,
+            redundant_secret_files,
+            invalid_payload_quorum,
+            builder_urls,
             builder_user_agent,
             builder_header_timeout,
+            builder_min_bid_gwei,
             secret_file,
             suggested_fee_recipient,
             jwt_id,
@@ -417,46 +463,51 @@ impl<E: EthSpec> ExecutionLayer<E> {
         // Use the default jwt secret path if not provided via cli.
         let secret_file = secret_file.unwrap_or_else(|| default_datadir.join(DEFAULT_JWT_FILE));
 
-        let jwt_key = if secret_file.exists() {
-            // Read secret from file if it already exists
-            std::fs::read_to_string(&secret_file)
-                .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))
-                .and_then(|ref s| {
-                    let secret = JwtKey::from_slice(
-                        &hex::decode(strip_prefix(s.trim_end()))
-                            .map_err(|e| format!("Invalid hex string: {:?}", e))?,
-                    )?;
-                    Ok(secret)
-                })
-                .map_err(Error::InvalidJWTSecret)
+        // Redundant engines either share the primary JWT secret or each have their own.
+        let redundant_secret_files = if redundant_secret_files.is_empty() {
+            vec![secret_file.clone(); redundant_execution_endpoints.len()]
+        } else if redundant_secret_files.len() == redundant_execution_endpoints.len() {
+            redundant_secret_files
         } else {
-            // Create a new file and write a randomly generated secret to it if file does not exist
-            warn!(log, "No JWT found on disk. Generating"; "path" => %secret_file.display());
-            std::fs::File::options()
-                .write(true)
-                .create_new(true)
-                .open(&secret_file)
-                .map_err(|e| format!("Failed to open JWT secret file. Error: {:?}", e))
-                .and_then(|mut f| {
-                    let secret = auth::JwtKey::random();
-                    f.write_all(secret.hex_string().as_bytes())
-                        .map_err(|e| format!("Failed to write to JWT secret file: {:?}", e))?;
-                    Ok(secret)
-                })
-                .map_err(Error::InvalidJWTSecret)
-        }?;
+            return Err(Error::RedundantSecretFileMismatch {
+                endpoints: redundant_execution_endpoints.len(),
+                secret_files: redundant_secret_files.len(),
+            });
+        };
 
-        let engine: Engine = {
-            let auth = Auth::new(jwt_key, jwt_id, jwt_version);
+        let endpoints = std::iter::once((execution_url, secret_file)).chain(
+            redundant_execution_endpoints
+                .into_iter()
+                .zip(redundant_secret_files),
+        );
+        let mut engines = vec![];
+        for (i, (execution_url, secret_file)) in endpoints.enumerate() {
+            let jwt_key = load_or_create_jwt_key(&secret_file, &log)?;
+            let auth = Auth::new(jwt_key, jwt_id.clone(), jwt_version.clone());
             debug!(log, "Loaded execution endpoint"; "endpoint" => %execution_url, "jwt_path" => ?secret_file.as_path());
+            // Redundant engines are labelled in their logs so that they can be told apart from
+            // the primary engine.
+            let engine_log = if i == 0 {
+                log.clone()
+            } else {
+                log.new(o!("execution_endpoint" => execution_url.to_string()))
+            };
             let api = HttpJsonRpc::new_with_auth(execution_url, auth, execution_timeout_multiplier)
                 .map_err(Error::ApiError)?;
-            Engine::new(api, executor.clone(), &log)
-        };
+            engines.push(Arc::new(Engine::new(api, executor.clone(), &engine_log)));
+        }
+
+        // By default a majority of engines must agree before a payload is considered invalid.
+        let invalid_payload_quorum = invalid_payload_quorum
+            .unwrap_or(engines.len() / 2 + 1)
+            .clamp(1, engines.len());
 
         let inner = Inner {
-            engine: Arc::new(engine),
-            builder: ArcSwapOption::empty(),
+            engines,
+            invalid_payload_quorum,
+            builders: ArcSwap::from_pointee(vec![]),
+            builder_bid_relays: Mutex::new(LruCache::new(BUILDER_BID_RELAYS_LRU_CACHE_SIZE)),
+            builder_min_bid_gwei,
             execution_engine_forkchoice_lock: <_>::default(),
             suggested_fee_recipient,
             proposer_preparation_data: Mutex::new(HashMap::new()),
@@ -472,22 +523,40 @@ impl<E: EthSpec> ExecutionLayer<E> {
             inner: Arc::new(inner),
         };
 
-        if let Some(builder_url) = builder_url {
-            el.set_builder_url(builder_url, builder_user_agent, builder_header_timeout)?;
+        if !builder_urls.is_empty() {
+            el.set_builder_urls(builder_urls, builder_user_agent, builder_header_timeout)?;
         }
 
         Ok(el)
     }
 
+    /// The primary execution engine.
     fn engine(&self) -> &Arc<Engine> {
-        &self.inner.engine
+        &self.inner.engines[0]
+    }
+
+    /// All attached execution engines, starting with the primary.
+    fn engines(&self) -> &[Arc<Engine>] {
+        &self.inner.engines
+    }
+
+    /// Returns the first synced engine, preferring the primary. Falls back to the primary engine
+    /// if none are synced.
+    async fn healthy_engine(&self) -> &Arc<Engine> {
+        for engine in self.engines() {
+            if engine.is_synced().await {
+                return engine;
+            }
+        }
+        self.engine()
     }
 
-    pub fn builder(&self) -> Option<Arc<BuilderHttpClient>> {
-        self.inner.builder.load_full()
+    /// Returns the builder relays currently in use, which may be empty.
+    pub fn builders(&self) -> Arc<Vec<Arc<BuilderHttpClient>>> {
+        self.inner.builders.load_full()
     }
 
-    /// Set the builder URL after initialization.
+    /// Set a single builder URL after initialization, replacing any existing relays.
     ///
     /// This is useful for breaking circular dependencies between mock ELs and mock builders in
     /// tests.
@@ -497,19 +566,39 @@ impl<E: EthSpec> ExecutionLayer<E> {
         builder_user_agent: Option<String>,
         builder_header_timeout: Option<Duration>,
     ) -> Result<(), Error> {
-        let builder_client = BuilderHttpClient::new(
-            builder_url.clone(),
+        self.set_builder_urls(
+            vec![builder_url],
             builder_user_agent,
             builder_header_timeout,
         )
-        .map_err(Error::Builder)?;
-        info!(
-            self.log(),
-            "Using external block builder";
-            "builder_url" => ?builder_url,
-            "local_user_agent" => builder_client.get_user_agent(),
-        );
-        self.inner.builder.swap(Some(Arc::new(builder_client)));
+    }
+
+    /// Set the builder relay URLs after initialization, replacing any existing relays.
+    pub fn set_builder_urls(
+        &self,
+        builder_urls: Vec<SensitiveUrl>,
+        builder_user_agent: Option<String>,
+        builder_header_timeout: Option<Duration>,
+    ) -> Result<(), Error> {
+        let builders = builder_urls
+            .into_iter()
+            .map(|builder_url| {
+                let builder_client = BuilderHttpClient::new(
+                    builder_url.clone(),
+                    builder_user_agent.clone(),
+                    builder_header_timeout,
+                )
+                .map_err(Error::Builder)?;
+                info!(
+                    self.log(),
+                    "Using external block builder";
+                    "builder_url" => ?builder_url,
+                    "local_user_agent" => builder_client.get_user_agent(),
+                );
+                Ok(Arc::new(builder_client))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        self.inner.builders.store(Arc::new(builders));
         Ok(())
     }
 
@@ -616,7 +705,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
 
     /// Performs a single execution of the watchdog routine.
     pub async fn watchdog_task(&self) {
-        self.engine().upcheck().await;
+        self.upcheck().await;
     }
 
     /// Spawns a routine which cleans the cached proposer data periodically.
@@ -658,9 +747,14 @@ impl<E: EthSpec> ExecutionLayer<E> {
         self.spawn(preparation_cleaner, "exec_preparation_cleanup");
     }
 
-    /// Returns `true` if the execution engine is synced and reachable.
+    /// Returns `true` if any execution engine is synced and reachable.
     pub async fn is_synced(&self) -> bool {
-        self.engine().is_synced().await
+        for engine in self.engines() {
+            if engine.is_synced().await {
+                return true;
+            }
+        }
+        false
     }
 
     /// Execution nodes return a "SYNCED" response when they do not have any peers.
@@ -673,7 +767,8 @@ impl<E: EthSpec> ExecutionLayer<E> {
         let synced = self.is_synced().await;
         if synced {
             if let Ok(Some(block)) = self
-                .engine()
+                .healthy_engine()
+                .await
                 .api
                 .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
                 .await
@@ -692,7 +787,15 @@ impl<E: EthSpec> ExecutionLayer<E> {
     /// be used to give an indication on the HTTP API that the node's execution layer is struggling,
     /// which can in turn be used by the VC.
     pub async fn is_offline_or_erroring(&self) -> bool {
-        self.engine().is_offline().await || *self.inner.last_new_payload_errored.read().await
+        if *self.inner.last_new_payload_errored.read().await {
+            return true;
+        }
+        for engine in self.engines() {
+            if !engine.is_offline().await {
+                return false;
+            }
+        }
+        true
     }
 
     /// Updates the proposer preparation data provided by validators
@@ -904,16 +1007,19 @@ impl<E: EthSpec> ExecutionLayer<E> {
     }
 
     /// Fetches local and builder paylaods concurrently, Logs and returns results.
+    ///
+    /// A header is requested from every configured relay in parallel. Each request is bounded by
+    /// the builder header timeout, so a slow relay cannot delay the auction beyond that.
     async fn fetch_builder_and_local_payloads(
         &self,
-        builder: &BuilderHttpClient,
+        builders: &[Arc<BuilderHttpClient>],
         parent_hash: ExecutionBlockHash,
         builder_params: &BuilderParams,
         payload_attributes: &PayloadAttributes,
         forkchoice_update_params: ForkchoiceUpdateParameters,
         current_fork: ForkName,
     ) -> (
-        Result<Option<ForkVersionedResponse<SignedBuilderBid<E>>>, builder_client::Error>,
+        Vec<(Arc<BuilderHttpClient>, RelayBidResult<E>)>,
         Result<GetPayloadResponse<E>, Error>,
     ) {
         let slot = builder_params.slot;
@@ -921,19 +1027,30 @@ impl<E: EthSpec> ExecutionLayer<E> {
 
         info!(
             self.log(),
-            "Requesting blinded header from connected builder";
+            "Requesting blinded header from connected builders";
             "slot" => ?slot,
             "pubkey" => ?pubkey,
             "parent_hash" => ?parent_hash,
+            "relays" => builders.len(),
         );
 
-        // Wait for the builder *and* local EL to produce a payload (or return an error).
-        let ((relay_result, relay_duration), (local_result, local_duration)) = tokio::join!(
-            timed_future(metrics::GET_BLINDED_PAYLOAD_BUILDER, async {
-                builder
-                    .get_builder_header::<E>(slot, parent_hash, pubkey)
-                    .await
-            }),
+        // Wait for the builders *and* local EL to produce a payload (or return an error).
+        let ((relay_results, relay_duration), (local_result, local_duration)) = tokio::join!(
+            timed_future(
+                metrics::GET_BLINDED_PAYLOAD_BUILDER,
+                join_all(builders.iter().map(|builder| async move {
+                    let start = Instant::now();
+                    let result = builder
+                        .get_builder_header::<E>(slot, parent_hash, pubkey)
+                        .await;
+                    metrics::observe_timer_vec(
+                        &metrics::EXECUTION_LAYER_BUILDER_HEADER_TIMES,
+                        &[&builder.server().to_string()],
+                        start.elapsed(),
+                    );
+                    (builder.clone(), result)
+                }))
+            ),
             timed_future(metrics::GET_BLINDED_PAYLOAD_LOCAL, async {
                 self.get_full_payload_caching(
                     parent_hash,
@@ -949,14 +1066,27 @@ impl<E: EthSpec> ExecutionLayer<E> {
             })
         );
 
+        for (builder, relay_result) in &relay_results {
+            debug!(
+                self.log(),
+                "Requested blinded header from builder";
+                "relay" => %builder.server(),
+                "relay_fee_recipient" => match relay_result {
+                    Ok(Some(r)) => format!("{:?}", r.data.message.header().fee_recipient()),
+                    Ok(None) => "empty response".to_string(),
+                    Err(_) => "request failed".to_string(),
+                },
+                "parent_hash" => ?parent_hash,
+            );
+        }
+
         info!(
             self.log(),
             "Requested blinded execution payload";
-            "relay_fee_recipient" => match &relay_result {
-                Ok(Some(r)) => format!("{:?}", r.data.message.header().fee_recipient()),
-                Ok(None) => "empty response".to_string(),
-                Err(_) => "request failed".to_string(),
-            },
+            "relay_bids" => relay_results
+                .iter()
+                .filter(|(_, result)| matches!(result, Ok(Some(_))))
+                .count(),
             "relay_response_ms" => relay_duration.as_millis(),
             "local_fee_recipient" => match &local_result {
                 Ok(get_payload_response) => format!("{:?}", get_payload_response.fee_recipient()),
@@ -966,7 +1096,126 @@ impl<E: EthSpec> ExecutionLayer<E> {
             "parent_hash" => ?parent_hash,
         );
 
-        (relay_result, local_result)
+        (relay_results, local_result)
+    }
+
+    /// Verifies every bid received from the relays and returns the most valuable valid bid,
+    /// alongside the relay which supplied it.
+    ///
+    /// Returns an error only if *every* relay request failed, in which case the first error is
+    /// returned.
+    #[allow(clippy::too_many_arguments)]
+    fn select_builder_bid(
+        &self,
+        relay_results: Vec<(Arc<BuilderHttpClient>, RelayBidResult<E>)>,
+        parent_hash: ExecutionBlockHash,
+        payload_attributes: &PayloadAttributes,
+        local_block_number: Option<u64>,
+        current_fork: ForkName,
+        spec: &ChainSpec,
+    ) -> Result<Option<WinningBuilderBid<E>>, builder_client::Error> {
+        let mut best_bid: Option<WinningBuilderBid<E>> = None;
+        let mut first_error = None;
+        let mut any_response = false;
+
+        for (builder, relay_result) in relay_results {
+            let relay = builder.server().to_string();
+            let bid = match relay_result {
+                Ok(Some(bid)) => bid,
+                Ok(None) => {
+                    any_response = true;
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_BUILDER_BID_OUTCOME,
+                        &[&relay, metrics::NO_BID],
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_BUILDER_BID_OUTCOME,
+                        &[&relay, metrics::FAILURE],
+                    );
+                    warn!(
+                        self.log(),
+                        "Builder error when requesting payload";
+                        "relay" => %relay,
+                        "relay_error" => ?e,
+                        "parent_hash" => ?parent_hash,
+                    );
+                    first_error.get_or_insert(e);
+                    continue;
+                }
+            };
+            any_response = true;
+
+            if let Err(reason) = verify_builder_bid(
+                &bid,
+                parent_hash,
+                payload_attributes,
+                local_block_number,
+                current_fork,
+                spec,
+            ) {
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
+                    &[reason.as_ref().as_ref()],
+                );
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_BUILDER_BID_OUTCOME,
+                    &[&relay, metrics::INVALID],
+                );
+                warn!(
+                    self.log(),
+                    "Builder returned invalid payload";
+                    "info" => "ignoring bid from this relay",
+                    "relay" => %relay,
+                    "reason" => %reason,
+                    "relay_block_hash" => ?bid.data.message.header().block_hash(),
+                    "parent_hash" => ?parent_hash,
+                );
+                continue;
+            }
+
+            metrics::inc_counter_vec(
+                &metrics::EXECUTION_LAYER_BUILDER_BID_OUTCOME,
+                &[&relay, metrics::VALID],
+            );
+
+            let is_better = match &best_bid {
+                Some((best, _)) => *bid.data.message.value() > *best.data.message.value(),
+                None => true,
+            };
+            if is_better {
+                best_bid = Some((bid, builder));
+            }
+        }
+
+        match (best_bid, first_error) {
+            (Some((bid, builder)), _) => {
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_BUILDER_BIDS_WON,
+                    &[&builder.server().to_string()],
+                );
+                Ok(Some((bid, builder)))
+            }
+            (None, Some(e)) if !any_response => Err(e),
+            (None, _) => Ok(None),
+        }
+    }
+
+    /// Remember which relay supplied the winning bid, so the signed blinded block can be returned
+    /// to it for unblinding.
+    async fn record_winning_relay(
+        &self,
+        bid: &SignedBuilderBid<E>,
+        builder: Arc<BuilderHttpClient>,
+    ) {
+        let block_hash = bid.message.header().block_hash();
+        self.inner
+            .builder_bid_relays
+            .lock()
+            .await
+            .put(block_hash, builder);
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -980,7 +1229,8 @@ impl<E: EthSpec> ExecutionLayer<E> {
         builder_boost_factor: Option<u64>,
         spec: &ChainSpec,
     ) -> Result<ProvenancedPayload<BlockProposalContentsType<E>>, Error> {
-        let Some(builder) = self.builder() else {
+        let builders = self.builders();
+        if builders.is_empty() {
             // no builder.. return local payload
             return self
                 .get_full_payload_caching(
@@ -992,7 +1242,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
                 .await
                 .and_then(GetPayloadResponseType::try_into)
                 .map(ProvenancedPayload::Local);
-        };
+        }
 
         // check chain health
         if builder_params.chain_health != ChainHealth::Healthy {
@@ -1018,6 +1268,10 @@ impl<E: EthSpec> ExecutionLayer<E> {
                     "got healthy but also not healthy.. this shouldn't happen!"
                 ),
             }
+            metrics::inc_counter_vec(
+                &metrics::EXECUTION_LAYER_PAYLOAD_SOURCE_DECISION,
+                &[metrics::LOCAL, metrics::CHAIN_UNHEALTHY],
+            );
             return self
                 .get_full_payload_caching(
                     parent_hash,
@@ -1030,9 +1284,9 @@ impl<E: EthSpec> ExecutionLayer<E> {
                 .map(ProvenancedPayload::Local);
         }
 
-        let (relay_result, local_result) = self
+        let (relay_results, local_result) = self
             .fetch_builder_and_local_payloads(
-                builder.as_ref(),
+                &builders,
                 parent_hash,
                 &builder_params,
                 payload_attributes,
@@ -1041,15 +1295,35 @@ impl<E: EthSpec> ExecutionLayer<E> {
             )
             .await;
 
+        let relay_result = self.select_builder_bid(
+            relay_results,
+            parent_hash,
+            payload_attributes,
+            local_result.as_ref().ok().map(|local| local.block_number()),
+            current_fork,
+            spec,
+        );
+
+        // A minimum bid supplied with the request takes precedence over the configured default.
+        let min_bid = builder_params
+            .min_bid_gwei
+            .or(self.inner.builder_min_bid_gwei)
+            .map(|gwei| Uint256::from(gwei).saturating_mul(Uint256::from(1_000_000_000_u64)));
+
         match (relay_result, local_result) {
             (Err(e), Ok(local)) => {
                 warn!(
                     self.log(),
-                    "Builder error when requesting payload";
+                    "All builders failed when requesting payload";
                     "info" => "falling back to local execution client",
                     "relay_error" => ?e,
                     "local_block_hash" => ?local.block_hash(),
                     "parent_hash" => ?parent_hash,
+                    "reason" => metrics::BUILDER_ERROR,
+                );
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_PAYLOAD_SOURCE_DECISION,
+                    &[metrics::LOCAL, metrics::BUILDER_ERROR],
                 );
                 Ok(ProvenancedPayload::Local(BlockProposalContentsType::Full(
                     local.try_into()?,
@@ -1058,10 +1332,15 @@ impl<E: EthSpec> ExecutionLayer<E> {
             (Ok(None), Ok(local)) => {
                 info!(
                     self.log(),
-                    "Builder did not return a payload";
+                    "Builders did not return a valid payload";
                     "info" => "falling back to local execution client",
                     "local_block_hash" => ?local.block_hash(),
                     "parent_hash" => ?parent_hash,
+                    "reason" => metrics::NO_BID,
+                );
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_PAYLOAD_SOURCE_DECISION,
+                    &[metrics::LOCAL, metrics::NO_BID],
                 );
                 Ok(ProvenancedPayload::Local(BlockProposalContentsType::Full(
                     local.try_into()?,
@@ -1083,7 +1362,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
                 crit!(
                     self.log(),
                     "Unable to produce execution payload";
-                    "info" => "the local EL failed and the builder returned nothing - \
+                    "info" => "the local EL failed and the builders returned no valid bid - \
                         the block proposal will be missed",
                     "local_error" => ?local_error,
                     "parent_hash" => ?parent_hash,
@@ -1091,46 +1370,39 @@ impl<E: EthSpec> ExecutionLayer<E> {
 
                 Err(Error::CannotProduceHeader)
             }
-            (Ok(Some(relay)), Ok(local)) => {
+            (Ok(Some((relay, builder))), Ok(local)) => {
                 let header = &relay.data.message.header();
 
                 info!(
                     self.log(),
                     "Received local and builder payloads";
+                    "relay" => %builder.server(),
                     "relay_block_hash" => ?header.block_hash(),
                     "local_block_hash" => ?local.block_hash(),
                     "parent_hash" => ?parent_hash,
                 );
 
-                // check relay payload validity
-                if let Err(reason) = verify_builder_bid(
-                    &relay,
-                    parent_hash,
-                    payload_attributes,
-                    Some(local.block_number()),
-                    current_fork,
-                    spec,
-                ) {
-                    // relay payload invalid -> return local
-                    metrics::inc_counter_vec(
-                        &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
-                        &[reason.as_ref().as_ref()],
-                    );
-                    warn!(
+                let relay_value = *relay.data.message.value();
+                let local_value = *local.block_value();
+
+                if min_bid.is_some_and(|min_bid| relay_value < min_bid) {
+                    info!(
                         self.log(),
-                        "Builder returned invalid payload";
-                        "info" => "using local payload",
-                        "reason" => %reason,
-                        "relay_block_hash" => ?header.block_hash(),
-                        "parent_hash" => ?parent_hash,
+                        "Relay bid is below the minimum, using local payload";
+                        "local_block_value" => %local_value,
+                        "relay_value" => %relay_value,
+                        "min_bid" => ?min_bid,
+                        "reason" => metrics::BID_BELOW_MINIMUM,
+                    );
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_PAYLOAD_SOURCE_DECISION,
+                        &[metrics::LOCAL, metrics::BID_BELOW_MINIMUM],
                     );
                     return Ok(ProvenancedPayload::Local(BlockProposalContentsType::Full(
                         local.try_into()?,
                     )));
                 }
 
-                let relay_value = *relay.data.message.value();
-
                 let boosted_relay_value = match builder_boost_factor {
                     Some(builder_boost_factor) => {
                         (relay_value / 100).saturating_mul(builder_boost_factor.into())
@@ -1138,8 +1410,6 @@ impl<E: EthSpec> ExecutionLayer<E> {
                     None => relay_value,
                 };
 
-                let local_value = *local.block_value();
-
                 if local_value >= boosted_relay_value {
                     info!(
                         self.log(),
@@ -1148,6 +1418,11 @@ impl<E: EthSpec> ExecutionLayer<E> {
                         "relay_value" => %relay_value,
                         "boosted_relay_value" => %boosted_relay_value,
                         "builder_boost_factor" => ?builder_boost_factor,
+                        "reason" => metrics::LOCAL_MORE_PROFITABLE,
+                    );
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_PAYLOAD_SOURCE_DECISION,
+                        &[metrics::LOCAL, metrics::LOCAL_MORE_PROFITABLE],
                     );
                     return Ok(ProvenancedPayload::Local(BlockProposalContentsType::Full(
                         local.try_into()?,
@@ -1159,7 +1434,12 @@ impl<E: EthSpec> ExecutionLayer<E> {
                         self.log(),
                         "Using local payload because execution engine suggested we ignore builder payload";
                         "local_block_value" => %local_value,
-                        "relay_value" => %relay_value
+                        "relay_value" => %relay_value,
+                        "reason" => metrics::ENGINE_OVERRIDE,
+                    );
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_PAYLOAD_SOURCE_DECISION,
+                        &[metrics::LOCAL, metrics::ENGINE_OVERRIDE],
                     );
                     return Ok(ProvenancedPayload::Local(BlockProposalContentsType::Full(
                         local.try_into()?,
@@ -1172,47 +1452,49 @@ impl<E: EthSpec> ExecutionLayer<E> {
                     "local_block_value" => %local_value,
                     "relay_value" => %relay_value,
                     "boosted_relay_value" => %boosted_relay_value,
-                    "builder_boost_factor" => ?builder_boost_factor
+                    "builder_boost_factor" => ?builder_boost_factor,
+                    "reason" => metrics::BUILDER_MORE_PROFITABLE,
+                );
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_PAYLOAD_SOURCE_DECISION,
+                    &[metrics::BUILDER, metrics::BUILDER_MORE_PROFITABLE],
                 );
 
+                self.record_winning_relay(&relay.data, builder).await;
                 Ok(ProvenancedPayload::try_from(relay.data.message)?)
             }
-            (Ok(Some(relay)), Err(local_error)) => {
+            (Ok(Some((relay, builder))), Err(local_error)) => {
                 let header = &relay.data.message.header();
 
                 info!(
                     self.log(),
                     "Received builder payload with local error";
+                    "relay" => %builder.server(),
                     "relay_block_hash" => ?header.block_hash(),
                     "local_error" => ?local_error,
                     "parent_hash" => ?parent_hash,
+                    "reason" => metrics::LOCAL_ERROR,
+                );
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_PAYLOAD_SOURCE_DECISION,
+                    &[metrics::BUILDER, metrics::LOCAL_ERROR],
                 );
 
-                match verify_builder_bid(
-                    &relay,
-                    parent_hash,
-                    payload_attributes,
-                    None,
-                    current_fork,
-                    spec,
-                ) {
-                    Ok(()) => Ok(ProvenancedPayload::try_from(relay.data.message)?),
-                    Err(reason) => {
-                        metrics::inc_counter_vec(
-                            &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
-                            &[reason.as_ref().as_ref()],
-                        );
-                        crit!(
-                            self.log(),
-                            "Builder returned invalid payload";
-                            "info" => "no local payload either - unable to propose block",
-                            "reason" => %reason,
-                            "relay_block_hash" => ?header.block_hash(),
-                            "parent_hash" => ?parent_hash,
-                        );
-                        Err(Error::CannotProduceHeader)
-                    }
+                // Without a local payload the builder bid is used even if it is below the
+                // minimum, since proposing a low-value block is better than missing the slot.
+                let relay_value = *relay.data.message.value();
+                if min_bid.is_some_and(|min_bid| relay_value < min_bid) {
+                    warn!(
+                        self.log(),
+                        "Using relay bid below the minimum";
+                        "info" => "the local execution engine failed to produce a payload",
+                        "relay_value" => %relay_value,
+                        "min_bid" => ?min_bid,
+                    );
                 }
+
+                self.record_winning_relay(&relay.data, builder).await;
+                Ok(ProvenancedPayload::try_from(relay.data.message)?)
             }
         }
     }
@@ -1246,7 +1528,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
             PayloadContentsRefTuple<E>,
         ) -> Option<FullPayloadContents<E>>,
     ) -> Result<GetPayloadResponseType<E>, Error> {
-        self.engine()
+        self.healthy_engine().await
             .request(move |engine| async move {
                 let payload_id = if let Some(id) = engine
                     .get_payload_id(&parent_hash, payload_attributes)
@@ -1357,12 +1639,14 @@ impl<E: EthSpec> ExecutionLayer<E> {
         let block_hash = new_payload_request.block_hash();
         let parent_hash = new_payload_request.parent_hash();
 
-        let result = self
-            .engine()
-            .request(|engine| engine.api.new_payload(new_payload_request))
-            .await;
+        // Send the payload to every engine so that they all stay in sync.
+        let results = join_all(self.engines().iter().map(|engine| {
+            let new_payload_request = new_payload_request.clone();
+            engine.request(|engine| engine.api.new_payload(new_payload_request))
+        }))
+        .await;
 
-        if let Ok(status) = &result {
+        for status in results.iter().flatten() {
             let status_str = <&'static str>::from(status.status);
             metrics::inc_counter_vec(
                 &metrics::EXECUTION_LAYER_PAYLOAD_STATUS,
@@ -1378,16 +1662,21 @@ impl<E: EthSpec> ExecutionLayer<E> {
                 "response_time_ms" => timer.elapsed().as_millis()
             );
         }
-        *self.inner.last_new_payload_errored.write().await = result.is_err();
-
-        process_payload_status(block_hash, result, self.log())
+        *self.inner.last_new_payload_errored.write().await =
+            results.iter().all(|result| result.is_err());
+
+        let statuses = results
+            .into_iter()
+            .map(|result| process_payload_status(block_hash, result, self.log()))
+            .collect();
+        reconcile_payload_statuses(statuses, self.inner.invalid_payload_quorum, self.log())
             .map_err(Box::new)
             .map_err(Error::EngineError)
     }
 
-    /// Update engine sync status.
+    /// Update the sync status of every engine.
     pub async fn upcheck(&self) {
-        self.engine().upcheck().await;
+        join_all(self.engines().iter().map(|engine| engine.upcheck())).await;
     }
 
     /// Register that the given `validator_index` is going to produce a block at `slot`.
@@ -1501,33 +1790,46 @@ impl<E: EthSpec> ExecutionLayer<E> {
             finalized_block_hash,
         };
 
-        self.engine()
-            .set_latest_forkchoice_state(forkchoice_state)
-            .await;
-
-        let result = self
-            .engine()
-            .request(|engine| async move {
+        // Update every engine so that any of them is able to build the next payload.
+        let results = join_all(self.engines().iter().map(|engine| {
+            let payload_attributes = payload_attributes.clone();
+            async move {
+                engine.set_latest_forkchoice_state(forkchoice_state).await;
                 engine
-                    .notify_forkchoice_updated(forkchoice_state, payload_attributes, self.log())
+                    .request(|engine| async move {
+                        engine
+                            .notify_forkchoice_updated(
+                                forkchoice_state,
+                                payload_attributes,
+                                self.log(),
+                            )
+                            .await
+                    })
                     .await
-            })
-            .await;
+            }
+        }))
+        .await;
 
-        if let Ok(status) = &result {
-            metrics::inc_counter_vec(
-                &metrics::EXECUTION_LAYER_PAYLOAD_STATUS,
-                &["forkchoice_updated", status.payload_status.status.into()],
-            );
-        }
+        let statuses = results
+            .into_iter()
+            .map(|result| {
+                if let Ok(status) = &result {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_PAYLOAD_STATUS,
+                        &["forkchoice_updated", status.payload_status.status.into()],
+                    );
+                }
+                process_payload_status(
+                    head_block_hash,
+                    result.map(|response| response.payload_status),
+                    self.log(),
+                )
+            })
+            .collect();
 
-        process_payload_status(
-            head_block_hash,
-            result.map(|response| response.payload_status),
-            self.log(),
-        )
-        .map_err(Box::new)
-        .map_err(Error::EngineError)
+        reconcile_payload_statuses(statuses, self.inner.invalid_payload_quorum, self.log())
+            .map_err(Box::new)
+            .map_err(Error::EngineError)
     }
 
     /// Returns the execution engine capabilities resulting from a call to
@@ -1543,7 +1845,8 @@ impl<E: EthSpec> ExecutionLayer<E> {
         &self,
         age_limit: Option<Duration>,
     ) -> Result<EngineCapabilities, Error> {
-        self.engine()
+        self.healthy_engine()
+            .await
             .request(|engine| engine.get_engine_capabilities(age_limit))
             .await
             .map_err(Into::into)
@@ -1562,7 +1865,8 @@ impl<E: EthSpec> ExecutionLayer<E> {
         &self,
         age_limit: Option<Duration>,
     ) -> Result<Vec<ClientVersionV1>, Error> {
-        self.engine()
+        self.healthy_engine()
+            .await
             .request(|engine| engine.get_engine_version(age_limit))
             .await
             .map_err(Into::into)
@@ -1772,7 +2076,8 @@ impl<E: EthSpec> ExecutionLayer<E> {
         &self,
         hashes: Vec<ExecutionBlockHash>,
     ) -> Result<Vec<Option<ExecutionPayloadBodyV1<E>>>, Error> {
-        self.engine()
+        self.healthy_engine()
+            .await
             .request(|engine: &Engine| async move {
                 engine.api.get_payload_bodies_by_hash_v1(hashes).await
             })
@@ -1787,7 +2092,8 @@ impl<E: EthSpec> ExecutionLayer<E> {
         count: u64,
     ) -> Result<Vec<Option<ExecutionPayloadBodyV1<E>>>, Error> {
         let _timer = metrics::start_timer(&metrics::EXECUTION_LAYER_GET_PAYLOAD_BODIES_BY_RANGE);
-        self.engine()
+        self.healthy_engine()
+            .await
             .request(|engine: &Engine| async move {
                 engine
                     .api
@@ -1850,7 +2156,8 @@ impl<E: EthSpec> ExecutionLayer<E> {
         &self,
         query: BlockByNumberQuery<'_>,
     ) -> Result<Option<ExecutionBlock>, Error> {
-        self.engine()
+        self.healthy_engine()
+            .await
             .request(|engine| async move { engine.api.get_block_by_number(query).await })
             .await
             .map_err(Box::new)
@@ -1862,7 +2169,8 @@ impl<E: EthSpec> ExecutionLayer<E> {
         hash: ExecutionBlockHash,
         fork: ForkName,
     ) -> Result<Option<ExecutionPayload<E>>, Error> {
-        self.engine()
+        self.healthy_engine()
+            .await
             .request(|engine| async move {
                 self.get_payload_by_hash_from_engine(engine, hash, fork)
                     .await
@@ -2048,59 +2356,90 @@ impl<E: EthSpec> ExecutionLayer<E> {
             "root" => ?block_root,
         );
 
-        if let Some(builder) = self.builder() {
-            let (payload_result, duration) =
-                timed_future(metrics::POST_BLINDED_PAYLOAD_BUILDER, async {
-                    builder
-                        .post_builder_blinded_blocks(block)
-                        .await
-                        .map_err(Error::Builder)
-                        .map(|d| d.data)
-                })
+        let builders = self.builders();
+        if builders.is_empty() {
+            return Err(Error::NoPayloadBuilder);
+        }
+
+        // Return the block to the relay which supplied the winning bid. If we don't know which
+        // relay that was (e.g. the bid was produced before a restart), offer it to all of them.
+        let winning_relay = match block.message().execution_payload() {
+            Ok(payload) => self
+                .inner
+                .builder_bid_relays
+                .lock()
+                .await
+                .get(&payload.block_hash())
+                .cloned(),
+            Err(_) => None,
+        };
+        let relays = match winning_relay {
+            Some(builder) => vec![builder],
+            None => builders.to_vec(),
+        };
+
+        let (payload_result, duration) =
+            timed_future(metrics::POST_BLINDED_PAYLOAD_BUILDER, async {
+                let results = join_all(
+                    relays
+                        .iter()
+                        .map(|builder| builder.post_builder_blinded_blocks(block)),
+                )
                 .await;
 
-            match &payload_result {
-                Ok(unblinded_response) => {
-                    metrics::inc_counter_vec(
-                        &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
-                        &[metrics::SUCCESS],
-                    );
-                    let payload = unblinded_response.payload_ref();
-                    info!(
-                        self.log(),
-                        "Builder successfully revealed payload";
-                        "relay_response_ms" => duration.as_millis(),
-                        "block_root" => ?block_root,
-                        "fee_recipient" => ?payload.fee_recipient(),
-                        "block_hash" => ?payload.block_hash(),
-                        "parent_hash" => ?payload.parent_hash()
-                    )
-                }
-                Err(e) => {
-                    metrics::inc_counter_vec(
-                        &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
-                        &[metrics::FAILURE],
-                    );
-                    warn!(
-                        self.log(),
-                        "Builder failed to reveal payload";
-                        "info" => "this is common behaviour for some builders and may not indicate an issue",
-                        "error" => ?e,
-                        "relay_response_ms" => duration.as_millis(),
-                        "block_root" => ?block_root,
-                        "parent_hash" => ?block
-                            .message()
-                            .execution_payload()
-                            .map(|payload| format!("{}", payload.parent_hash()))
-                            .unwrap_or_else(|_| "unknown".to_string())
-                    )
+                let mut first_error = None;
+                for (builder, result) in relays.iter().zip(results) {
+                    match result {
+                        Ok(response) => return Ok((builder.clone(), response.data)),
+                        Err(e) => {
+                            first_error.get_or_insert(e);
+                        }
+                    }
                 }
-            }
+                Err(first_error.map_or(Error::NoPayloadBuilder, Error::Builder))
+            })
+            .await;
 
-            payload_result
-        } else {
-            Err(Error::NoPayloadBuilder)
+        match &payload_result {
+            Ok((builder, unblinded_response)) => {
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
+                    &[metrics::SUCCESS],
+                );
+                let payload = unblinded_response.payload_ref();
+                info!(
+                    self.log(),
+                    "Builder successfully revealed payload";
+                    "relay" => %builder.server(),
+                    "relay_response_ms" => duration.as_millis(),
+                    "block_root" => ?block_root,
+                    "fee_recipient" => ?payload.fee_recipient(),
+                    "block_hash" => ?payload.block_hash(),
+                    "parent_hash" => ?payload.parent_hash()
+                )
+            }
+            Err(e) => {
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
+                    &[metrics::FAILURE],
+                );
+                warn!(
+                    self.log(),
+                    "Builder failed to reveal payload";
+                    "info" => "this is common behaviour for some builders and may not indicate an issue",
+                    "error" => ?e,
+                    "relay_response_ms" => duration.as_millis(),
+                    "block_root" => ?block_root,
+                    "parent_hash" => ?block
+                        .message()
+                        .execution_payload()
+                        .map(|payload| format!("{}", payload.parent_hash()))
+                        .unwrap_or_else(|_| "unknown".to_string())
+                )
+            }
         }
+
+        payload_result.map(|(_, payload)| payload)
     }
 }
 
@@ -2246,6 +2585,39 @@ fn verify_builder_bid<E: EthSpec>(
     }
 }
 
+/// Reads the hex-encoded JWT secret at `secret_file`, generating and persisting a random secret if
+/// the file does not exist.
+fn load_or_create_jwt_key(secret_file: &Path, log: &Logger) -> Result<JwtKey, Error> {
+    if secret_file.exists() {
+        // Read secret from file if it already exists
+        std::fs::read_to_string(secret_file)
+            .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))
+            .and_then(|ref s| {
+                let secret = JwtKey::from_slice(
+                    &hex::decode(strip_prefix(s.trim_end()))
+                        .map_err(|e| format!("Invalid hex string: {:?}", e))?,
+                )?;
+                Ok(secret)
+            })
+            .map_err(Error::InvalidJWTSecret)
+    } else {
+        // Create a new file and write a randomly generated secret to it if file does not exist
+        warn!(log, "No JWT found on disk. Generating"; "path" => %secret_file.display());
+        std::fs::File::options()
+            .write(true)
+            .create_new(true)
+            .open(secret_file)
+            .map_err(|e| format!("Failed to open JWT secret file. Error: {:?}", e))
+            .and_then(|mut f| {
+                let secret = auth::JwtKey::random();
+                f.write_all(secret.hex_string().as_bytes())
+                    .map_err(|e| format!("Failed to write to JWT secret file: {:?}", e))?;
+                Ok(secret)
+            })
+            .map_err(Error::InvalidJWTSecret)
+    }
+}
+
 /// A helper function to record the time it takes to execute a future.
 async fn timed_future<F: Future<Output = T>, T>(metric: &str, future: F) -> (T, Duration) {
     let start = Instant::now();
@@ -2416,4 +2788,115 @@ mod test {
             })
             .await;
     }
+
+    /// Builds an execution layer which is attached to every endpoint in `urls`, with the first
+    /// as the primary engine.
+    fn redundant_execution_layer(
+        runtime: &TestRuntime,
+        urls: Vec<SensitiveUrl>,
+        invalid_payload_quorum: Option<usize>,
+    ) -> ExecutionLayer<MainnetEthSpec> {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), hex::encode(test_utils::DEFAULT_JWT_SECRET)).unwrap();
+        let config = Config {
+            execution_endpoint: Some(urls[0].clone()),
+            redundant_execution_endpoints: urls[1..].to_vec(),
+            secret_file: Some(file.path().into()),
+            invalid_payload_quorum,
+            ..Default::default()
+        };
+        let executor = runtime.task_executor.clone();
+        ExecutionLayer::from_config(config, executor.clone(), executor.log().clone()).unwrap()
+    }
+
+    fn mock_url(mock: &MockExecutionLayer) -> SensitiveUrl {
+        SensitiveUrl::parse(&mock.server.url()).unwrap()
+    }
+
+    fn fcu_status(status: PayloadStatusV1Status, head: ExecutionBlockHash) -> PayloadStatusV1 {
+        PayloadStatusV1 {
+            status,
+            latest_valid_hash: match status {
+                PayloadStatusV1Status::Valid | PayloadStatusV1Status::Invalid => Some(head),
+                _ => None,
+            },
+            validation_error: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn redundant_engines_require_quorum_for_invalid() {
+        let runtime = TestRuntime::default();
+        let mocks = (0..3)
+            .map(|_| MockExecutionLayer::default_params(runtime.task_executor.clone()))
+            .collect::<Vec<_>>();
+        let el = redundant_execution_layer(&runtime, mocks.iter().map(mock_url).collect(), None);
+        assert_eq!(el.inner.invalid_payload_quorum, 2);
+
+        let notify = |head| {
+            el.notify_forkchoice_updated(
+                head,
+                ExecutionBlockHash::zero(),
+                ExecutionBlockHash::zero(),
+                Slot::new(0),
+                Hash256::zero(),
+            )
+        };
+
+        // A single engine reporting the payload as invalid is outvoted.
+        let head = ExecutionBlockHash::repeat_byte(1);
+        mocks[0]
+            .server
+            .set_fcu_payload_status(head, fcu_status(PayloadStatusV1Status::Invalid, head));
+        for mock in &mocks[1..] {
+            mock.server
+                .set_fcu_payload_status(head, fcu_status(PayloadStatusV1Status::Valid, head));
+        }
+        assert_eq!(notify(head).await.unwrap(), PayloadStatus::Valid);
+
+        // Once a quorum agrees, the payload is invalid.
+        let head = ExecutionBlockHash::repeat_byte(2);
+        for mock in &mocks[..2] {
+            mock.server
+                .set_fcu_payload_status(head, fcu_status(PayloadStatusV1Status::Invalid, head));
+        }
+        mocks[2]
+            .server
+            .set_fcu_payload_status(head, fcu_status(PayloadStatusV1Status::Valid, head));
+        assert!(matches!(
+            notify(head).await.unwrap(),
+            PayloadStatus::Invalid { .. }
+        ));
+
+        // Without a valid response or quorum, the payload is treated optimistically.
+        let head = ExecutionBlockHash::repeat_byte(3);
+        mocks[0]
+            .server
+            .set_fcu_payload_status(head, fcu_status(PayloadStatusV1Status::Invalid, head));
+        for mock in &mocks[1..] {
+            mock.server
+                .set_fcu_payload_status(head, fcu_status(PayloadStatusV1Status::Syncing, head));
+        }
+        assert_eq!(notify(head).await.unwrap(), PayloadStatus::Syncing);
+    }
+
+    #[tokio::test]
+    async fn redundant_engines_serve_requests_when_primary_offline() {
+        let runtime = TestRuntime::default();
+        let mock = MockExecutionLayer::default_params(runtime.task_executor.clone());
+        let offline_url = SensitiveUrl::parse("http://127.0.0.1:1").unwrap();
+        let el = redundant_execution_layer(&runtime, vec![offline_url, mock_url(&mock)], None);
+
+        el.upcheck().await;
+        assert!(!el.engine().is_synced().await);
+        assert!(el.is_synced().await);
+        assert!(!el.is_offline_or_erroring().await);
+
+        // Requests are served by the synced redundant engine.
+        let block = el
+            .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
+            .await
+            .unwrap();
+        assert!(block.is_some());
+    }
 }
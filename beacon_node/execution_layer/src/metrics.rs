@@ -15,6 +15,16 @@ pub const LOCAL: &str = "local";
 pub const BUILDER: &str = "builder";
 pub const SUCCESS: &str = "success";
 pub const FAILURE: &str = "failure";
+pub const VALID: &str = "valid";
+pub const INVALID: &str = "invalid";
+pub const NO_BID: &str = "no_bid";
+pub const CHAIN_UNHEALTHY: &str = "chain_unhealthy";
+pub const BUILDER_ERROR: &str = "builder_error";
+pub const LOCAL_ERROR: &str = "local_error";
+pub const BID_BELOW_MINIMUM: &str = "bid_below_minimum";
+pub const LOCAL_MORE_PROFITABLE: &str = "local_more_profitable";
+pub const BUILDER_MORE_PROFITABLE: &str = "builder_more_profitable";
+pub const ENGINE_OVERRIDE: &str = "engine_override";
 
 lazy_static::lazy_static! {
     pub static ref EXECUTION_LAYER_PROPOSER_INSERTED: Result<IntCounter> = try_create_int_counter(
@@ -74,6 +84,11 @@ lazy_static::lazy_static! {
         "The source of each payload returned from get_payload",
         &["source"]
     );
+    pub static ref EXECUTION_LAYER_PAYLOAD_SOURCE_DECISION: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_payload_source_decision",
+        "The source chosen when both local and builder payloads were considered, and why",
+        &["source", "reason"]
+    );
     pub static ref EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS: Result<IntCounterVec> = try_create_int_counter_vec(
         "execution_layer_get_payload_builder_rejections",
         "The reasons why a payload from a builder was rejected",
@@ -84,4 +99,21 @@ lazy_static::lazy_static! {
         "The gwei bid value of payloads received by local EEs or builders. Only shows values up to i64::MAX.",
         &["source"]
     );
+    pub static ref EXECUTION_LAYER_BUILDER_HEADER_TIMES: Result<HistogramVec> =
+        try_create_histogram_vec_with_buckets(
+        "execution_layer_builder_header_times",
+        "Duration of get_header calls to each builder relay",
+        decimal_buckets(-2, 1),
+        &["relay"]
+    );
+    pub static ref EXECUTION_LAYER_BUILDER_BID_OUTCOME: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_builder_bid_outcome",
+        "The outcome of each get_header request to a builder relay",
+        &["relay", "outcome"]
+    );
+    pub static ref EXECUTION_LAYER_BUILDER_BIDS_WON: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_builder_bids_won",
+        "Count of times each builder relay supplied the highest valid bid",
+        &["relay"]
+    );
 }
@@ -108,3 +108,74 @@ pub fn process_payload_status(
         },
     }
 }
+
+/// Combines the payload statuses returned by several execution engines into a single status.
+///
+/// A payload is only treated as invalid once `invalid_quorum` engines report it as such. Below
+/// that threshold, a single `VALID` response is sufficient to treat the payload as valid, and any
+/// remaining disagreement is resolved optimistically as `SYNCING`. An error is only returned if
+/// every engine errored.
+pub fn reconcile_payload_statuses(
+    statuses: Vec<Result<PayloadStatus, EngineError>>,
+    invalid_quorum: usize,
+    log: &Logger,
+) -> Result<PayloadStatus, EngineError> {
+    let num_engines = statuses.len();
+    let mut first_error = None;
+    let mut first_invalid = None;
+    let mut invalid_count = 0;
+    let mut any_valid = false;
+    let mut any_accepted = false;
+    let mut any_response = false;
+
+    for status in statuses {
+        match status {
+            Ok(PayloadStatus::Valid) => any_valid = true,
+            Ok(PayloadStatus::Accepted) => any_accepted = true,
+            Ok(PayloadStatus::Syncing) => (),
+            Ok(
+                status @ (PayloadStatus::Invalid { .. } | PayloadStatus::InvalidBlockHash { .. }),
+            ) => {
+                invalid_count += 1;
+                first_invalid.get_or_insert(status);
+            }
+            Err(e) => {
+                first_error.get_or_insert(e);
+                continue;
+            }
+        }
+        any_response = true;
+    }
+
+    if let Some(invalid) = first_invalid {
+        if invalid_count >= invalid_quorum {
+            if any_valid {
+                warn!(
+                    log,
+                    "Execution engines disagree on payload validity";
+                    "info" => "treating payload as invalid since the quorum was reached",
+                    "invalid_count" => invalid_count,
+                    "invalid_quorum" => invalid_quorum,
+                    "num_engines" => num_engines,
+                );
+            }
+            return Ok(invalid);
+        }
+
+        warn!(
+            log,
+            "Execution engines disagree on payload validity";
+            "info" => "ignoring invalid status since the quorum was not reached",
+            "invalid_count" => invalid_count,
+            "invalid_quorum" => invalid_quorum,
+            "num_engines" => num_engines,
+        );
+    }
+
+    match first_error {
+        Some(error) if !any_response => Err(error),
+        _ if any_valid => Ok(PayloadStatus::Valid),
+        _ if any_accepted => Ok(PayloadStatus::Accepted),
+        _ => Ok(PayloadStatus::Syncing),
+    }
+}
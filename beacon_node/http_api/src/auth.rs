@@ -0,0 +1,248 @@
+//! Optional bearer-token authentication and per-token request budgets for the HTTP API.
+//!
+//! When an `ApiAuthConfig` is provided, every request must carry an `Authorization: Bearer <token>`
+//! header matching one of the configured tokens. Each token is granted a set of `ApiScope`s and may
+//! have a budget of requests per minute. Requests are checked before they are routed, so rejected
+//! requests never reach a handler or the beacon processor.
+
+use crate::metrics;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Instant;
+use warp::filters::BoxedFilter;
+use warp::http::Method;
+use warp::path::FullPath;
+use warp::{Filter, Rejection};
+
+/// The prefix expected before the token in the `Authorization` header.
+const BEARER_PREFIX: &str = "Bearer ";
+
+/// The label used in metrics for requests which do not present a known token.
+const UNKNOWN_TOKEN: &str = "unknown";
+
+/// A class of routes which a token may be permitted to access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiScope {
+    /// `GET` requests to the standard `/eth/*` routes, excluding `/eth/*/validator/*`.
+    ReadOnly,
+    /// `/eth/*/validator/*` routes and any non-`GET` request to the standard `/eth/*` routes.
+    Validator,
+    /// The non-standard `/lighthouse/*` routes.
+    Admin,
+}
+
+impl ApiScope {
+    /// Returns the scope required to make a `method` request to `path`.
+    pub fn required_for(method: &Method, path: &str) -> Self {
+        let mut segments = path.trim_start_matches('/').split('/');
+        match (segments.next(), segments.nth(1)) {
+            (Some("lighthouse"), _) => ApiScope::Admin,
+            (_, Some("validator")) => ApiScope::Validator,
+            _ if method != Method::GET => ApiScope::Validator,
+            _ => ApiScope::ReadOnly,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            ApiScope::ReadOnly => "read_only",
+            ApiScope::Validator => "validator",
+            ApiScope::Admin => "admin",
+        }
+    }
+}
+
+/// A named token and the routes it may access.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiToken {
+    /// Identifies the token in logs and metrics.
+    pub name: String,
+    pub token: String,
+    pub scopes: Vec<ApiScope>,
+    /// The maximum number of requests per minute, or unlimited if `None`.
+    #[serde(default)]
+    pub requests_per_minute: Option<u32>,
+}
+
+impl fmt::Debug for ApiToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Avoid leaking the secret into logs.
+        f.debug_struct("ApiToken")
+            .field("name", &self.name)
+            .field("scopes", &self.scopes)
+            .field("requests_per_minute", &self.requests_per_minute)
+            .finish()
+    }
+}
+
+/// The set of tokens accepted by the HTTP API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiAuthConfig {
+    pub tokens: Vec<ApiToken>,
+}
+
+impl ApiAuthConfig {
+    /// Returns an error if the tokens are ambiguous or could never be used.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut names = HashSet::new();
+        let mut secrets = HashSet::new();
+        for token in &self.tokens {
+            if !names.insert(token.name.as_str()) {
+                return Err(format!("duplicate API token name: {}", token.name));
+            }
+            if token.token.is_empty() {
+                return Err(format!("API token {} is empty", token.name));
+            }
+            if !secrets.insert(token.token.as_str()) {
+                return Err(format!("API token {} is not unique", token.name));
+            }
+            if token.requests_per_minute == Some(0) {
+                return Err(format!(
+                    "requests_per_minute of API token {} must be non-zero",
+                    token.name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A token bucket which refills continuously up to `capacity`.
+struct RequestBudget {
+    capacity: f64,
+    available: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl RequestBudget {
+    fn per_minute(requests: u32) -> Self {
+        let capacity = f64::from(requests);
+        Self {
+            capacity,
+            available: capacity,
+            refill_per_sec: capacity / 60.0,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Consumes one request from the budget, returning `false` if it is exhausted.
+    fn try_consume(&mut self, now: Instant) -> bool {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.available = (self.available + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+
+        if self.available >= 1.0 {
+            self.available -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+struct TokenState {
+    name: String,
+    scopes: HashSet<ApiScope>,
+    budget: Option<Mutex<RequestBudget>>,
+}
+
+/// Checks requests against the configured tokens.
+pub struct ApiAuth {
+    /// Maps each expected `Authorization` header value to its token.
+    tokens: HashMap<String, TokenState>,
+}
+
+impl ApiAuth {
+    pub fn new(config: &ApiAuthConfig) -> Result<Self, String> {
+        config.validate()?;
+        let tokens = config
+            .tokens
+            .iter()
+            .map(|token| {
+                let state = TokenState {
+                    name: token.name.clone(),
+                    scopes: token.scopes.iter().copied().collect(),
+                    budget: token
+                        .requests_per_minute
+                        .map(|requests| Mutex::new(RequestBudget::per_minute(requests))),
+                };
+                (format!("{}{}", BEARER_PREFIX, token.token), state)
+            })
+            .collect();
+        Ok(Self { tokens })
+    }
+
+    /// Authorizes a `method` request to `path` which presented the `Authorization` value `header`.
+    pub fn authorize(&self, method: &Method, path: &str, header: &str) -> Result<(), Rejection> {
+        let Some(token) = self.tokens.get(header) else {
+            metrics::inc_counter_vec(
+                &metrics::HTTP_API_AUTH_REQUESTS_TOTAL,
+                &[UNKNOWN_TOKEN, "unauthorized"],
+            );
+            return Err(warp_utils::reject::invalid_auth(
+                "unknown bearer token".to_string(),
+            ));
+        };
+
+        let scope = ApiScope::required_for(method, path);
+        if !token.scopes.contains(&scope) {
+            metrics::inc_counter_vec(
+                &metrics::HTTP_API_AUTH_REQUESTS_TOTAL,
+                &[&token.name, "forbidden"],
+            );
+            return Err(warp_utils::reject::invalid_auth(format!(
+                "token {} does not have the {} scope",
+                token.name,
+                scope.as_str()
+            )));
+        }
+
+        if let Some(budget) = &token.budget {
+            if !budget.lock().try_consume(Instant::now()) {
+                metrics::inc_counter_vec(
+                    &metrics::HTTP_API_AUTH_REQUESTS_TOTAL,
+                    &[&token.name, "rate_limited"],
+                );
+                return Err(warp_utils::reject::too_many_requests(format!(
+                    "request budget of token {} is exhausted",
+                    token.name
+                )));
+            }
+        }
+
+        metrics::inc_counter_vec(
+            &metrics::HTTP_API_AUTH_REQUESTS_TOTAL,
+            &[&token.name, "allowed"],
+        );
+        Ok(())
+    }
+}
+
+/// Returns a filter which rejects requests not permitted by `auth`, or accepts every request if
+/// `auth` is `None`.
+pub fn auth_filter(auth: Option<Arc<ApiAuth>>) -> BoxedFilter<()> {
+    match auth {
+        None => warp::any().boxed(),
+        Some(auth) => warp::any()
+            .map(move || auth.clone())
+            .and(warp::method())
+            .and(warp::path::full())
+            .and(warp::header::header("Authorization"))
+            .and_then(
+                |auth: Arc<ApiAuth>, method: Method, path: FullPath, header: String| async move {
+                    auth.authorize(&method, path.as_str(), &header)
+                },
+            )
+            .untuple_one()
+            .boxed(),
+    }
+}
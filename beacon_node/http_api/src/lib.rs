@@ -7,13 +7,16 @@
 
 mod attestation_performance;
 mod attester_duties;
+pub mod auth;
 mod block_id;
 mod block_packing_efficiency;
 mod block_rewards;
 mod build_block_contents;
 mod builder_states;
 mod database;
+mod merkle_proofs;
 mod metrics;
+mod op_pool;
 mod produce_block;
 mod proposer_duties;
 mod publish_attestations;
@@ -30,6 +33,7 @@ mod validator_inclusion;
 mod validators;
 mod version;
 
+use crate::auth::{auth_filter, ApiAuth, ApiAuthConfig};
 use crate::produce_block::{produce_blinded_block_v2, produce_block_v2, produce_block_v3};
 use crate::version::fork_versioned_response;
 use beacon_chain::{
@@ -48,7 +52,11 @@ use eth2::types::{
     ValidatorsRequestBody,
 };
 use eth2::{CONSENSUS_VERSION_HEADER, CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER};
-use lighthouse_network::{types::SyncState, EnrExt, NetworkGlobals, PeerId, PubsubMessage};
+use lighthouse_network::{
+    rpc::{Protocol, Quota},
+    types::SyncState,
+    EnrExt, NetworkGlobals, PeerConnectionStatus, PeerId, PeerPolicy, PubsubMessage,
+};
 use lighthouse_version::version_with_platform;
 use logging::SSELoggingComponents;
 use network::{NetworkMessage, NetworkSenders, ValidatorSubscriptionMessage};
@@ -80,17 +88,17 @@ use tokio_stream::{
 };
 use types::{
     fork_versioned_response::EmptyMetadata, Attestation, AttestationData, AttestationShufflingId,
-    AttesterSlashing, BeaconStateError, CommitteeCache, ConfigAndPreset, Epoch, EthSpec, ForkName,
-    ForkVersionedResponse, Hash256, ProposerPreparationData, ProposerSlashing, RelativeEpoch,
-    SignedAggregateAndProof, SignedBlindedBeaconBlock, SignedBlsToExecutionChange,
+    AttesterSlashing, BeaconStateError, ChainSpec, CommitteeCache, ConfigAndPreset, Epoch, EthSpec,
+    ForkName, ForkVersionedResponse, Hash256, ProposerPreparationData, ProposerSlashing,
+    RelativeEpoch, SignedAggregateAndProof, SignedBlindedBeaconBlock, SignedBlsToExecutionChange,
     SignedContributionAndProof, SignedValidatorRegistrationData, SignedVoluntaryExit, Slot,
-    SyncCommitteeMessage, SyncContributionData,
+    SyncCommitteeMessage, SyncContributionData, MAX_REQUEST_LIGHT_CLIENT_UPDATES,
 };
 use validator::pubkey_to_validator_index;
 use version::{
     add_consensus_version_header, add_ssz_content_type_header,
     execution_optimistic_finalized_fork_versioned_response, inconsistent_fork_rejection,
-    unsupported_version_rejection, V1, V2, V3,
+    unsupported_version_rejection, V0, V1, V2, V3,
 };
 use warp::http::StatusCode;
 use warp::hyper::Body;
@@ -151,6 +159,8 @@ pub struct Config {
     #[serde(with = "eth2::types::serde_status_code")]
     pub duplicate_block_status_code: StatusCode,
     pub enable_light_client_server: bool,
+    /// Requires requests to present one of these tokens, if set.
+    pub auth: Option<ApiAuthConfig>,
 }
 
 impl Default for Config {
@@ -167,6 +177,7 @@ impl Default for Config {
             enable_beacon_processor: true,
             duplicate_block_status_code: StatusCode::ACCEPTED,
             enable_light_client_server: false,
+            auth: None,
         }
     }
 }
@@ -328,7 +339,7 @@ pub fn serve<T: BeaconChainTypes>(
     let cors_builder = {
         let builder = warp::cors()
             .allow_methods(vec!["GET", "POST"])
-            .allow_headers(vec!["Content-Type"]);
+            .allow_headers(vec!["Content-Type", "Authorization"]);
 
         warp_utils::cors::set_builder_origins(
             builder,
@@ -345,6 +356,22 @@ pub fn serve<T: BeaconChainTypes>(
         ));
     }
 
+    // Create a filter that rejects requests which are unauthenticated or over budget, if enabled.
+    let api_auth = config
+        .auth
+        .as_ref()
+        .map(|auth_config| ApiAuth::new(auth_config).map(Arc::new))
+        .transpose()
+        .map_err(|e| Error::Other(format!("Invalid HTTP API auth config: {}", e)))?;
+    if let Some(auth_config) = &config.auth {
+        info!(
+            log,
+            "HTTP API authentication enabled";
+            "tokens" => auth_config.tokens.len(),
+        );
+    }
+    let api_auth_filter = auth_filter(api_auth);
+
     // Create a filter that extracts the endpoint version.
     let any_version = warp::path(API_PREFIX).and(warp::path::param::<EndpointVersion>().or_else(
         |_| async move {
@@ -367,6 +394,7 @@ pub fn serve<T: BeaconChainTypes>(
             .untuple_one()
     };
 
+    let eth_v0 = single_version(V0);
     let eth_v1 = single_version(V1);
     let eth_v2 = single_version(V2);
 
@@ -1626,6 +1654,78 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    let beacon_proof_path = eth_v0.and(warp::path("beacon")).and(warp::path("proof"));
+
+    // GET beacon/proof/state/{state_id}?paths
+    let get_beacon_proof_state = beacon_proof_path
+        .clone()
+        .and(warp::path("state"))
+        .and(warp::path::param::<StateId>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid state ID".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(multi_key_query::<api_types::MerkleProofQuery>())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |state_id: StateId,
+             query_res: Result<api_types::MerkleProofQuery, warp::Rejection>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let query = query_res?;
+                    let _permit = merkle_proofs::state_request_permit()?;
+                    let (data, execution_optimistic, finalized) = state_id
+                        .map_state_and_execution_optimistic_and_finalized(
+                            &chain,
+                            |state, execution_optimistic, finalized| {
+                                Ok((
+                                    merkle_proofs::state_proofs(state, &query.paths)?,
+                                    execution_optimistic,
+                                    finalized,
+                                ))
+                            },
+                        )?;
+
+                    Ok(api_types::ExecutionOptimisticFinalizedResponse {
+                        data,
+                        execution_optimistic: Some(execution_optimistic),
+                        finalized: Some(finalized),
+                    })
+                })
+            },
+        );
+
+    // GET beacon/proof/block/{block_id}?paths
+    let get_beacon_proof_block = beacon_proof_path
+        .and(warp::path("block"))
+        .and(block_id_or_err)
+        .and(warp::path::end())
+        .and(multi_key_query::<api_types::MerkleProofQuery>())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |block_id: BlockId,
+             query_res: Result<api_types::MerkleProofQuery, warp::Rejection>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let query = query_res?;
+                    let (block, execution_optimistic, finalized) =
+                        block_id.blinded_block(&chain)?;
+                    let data = merkle_proofs::block_proofs(block.message(), &query.paths)?;
+
+                    Ok(api_types::ExecutionOptimisticFinalizedResponse {
+                        data,
+                        execution_optimistic: Some(execution_optimistic),
+                        finalized: Some(finalized),
+                    })
+                })
+            },
+        );
+
     // GET beacon/blocks/{block_id}/attestations
     let get_beacon_block_attestations = beacon_blocks_path_any
         .clone()
@@ -2484,6 +2584,73 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET beacon/light_client/updates
+    let get_beacon_light_client_updates = beacon_light_client_path
+        .clone()
+        .and(task_spawner_filter.clone())
+        .and(warp::path("updates"))
+        .and(warp::path::end())
+        .and(warp::query::<api_types::LightClientUpdatesQuery>())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .then(
+            |chain: Arc<BeaconChain<T>>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             query: api_types::LightClientUpdatesQuery,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    let count = std::cmp::min(query.count, MAX_REQUEST_LIGHT_CLIENT_UPDATES);
+                    let updates = chain
+                        .get_light_client_updates(query.start_period, count)
+                        .map_err(|e| {
+                            warp_utils::reject::custom_server_error(format!(
+                                "Unable to obtain light client updates: {e:?}"
+                            ))
+                        })?;
+
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => {
+                            // Each update is prefixed by its length and the fork digest of the
+                            // fork it was created in, as per the beacon-API spec.
+                            let mut bytes = vec![];
+                            for update in updates {
+                                let fork_name = update.map_with_fork_name(|fork_name| fork_name);
+                                let fork_digest = ChainSpec::compute_fork_digest(
+                                    chain.spec.fork_version_for_name(fork_name),
+                                    chain.genesis_validators_root,
+                                );
+                                let update_bytes = update.as_ssz_bytes();
+                                let response_len = (fork_digest.len() + update_bytes.len()) as u64;
+                                bytes.extend_from_slice(&response_len.to_le_bytes());
+                                bytes.extend_from_slice(&fork_digest);
+                                bytes.extend_from_slice(&update_bytes);
+                            }
+                            Response::builder()
+                                .status(200)
+                                .body(bytes.into())
+                                .map(|res: Response<Body>| add_ssz_content_type_header(res))
+                                .map_err(|e| {
+                                    warp_utils::reject::custom_server_error(format!(
+                                        "failed to create response: {}",
+                                        e
+                                    ))
+                                })
+                        }
+                        _ => {
+                            let updates = updates
+                                .into_iter()
+                                .map(|update| ForkVersionedResponse {
+                                    version: Some(update.map_with_fork_name(|fork_name| fork_name)),
+                                    metadata: EmptyMetadata {},
+                                    data: update,
+                                })
+                                .collect::<Vec<_>>();
+                            Ok(warp::reply::json(&updates).into_response())
+                        }
+                    }
+                })
+            },
+        );
+
     /*
      * beacon/rewards
      */
@@ -3527,7 +3694,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .and(warp_utils::json::json())
-        .and(network_tx_filter)
+        .and(network_tx_filter.clone())
         .and(log_filter.clone())
         .then(
             |not_synced_filter: Result<(), Rejection>,
@@ -3768,44 +3935,62 @@ pub fn serve<T: BeaconChainTypes>(
                         // send the response back to our original HTTP request
                         // task via a channel.
                         let builder_future = async move {
-                            let arc_builder = chain
+                            let builders = chain
                                 .execution_layer
                                 .as_ref()
                                 .ok_or(BeaconChainError::ExecutionLayerMissing)
                                 .map_err(warp_utils::reject::beacon_chain_error)?
-                                .builder();
-                            let builder = arc_builder
-                                .as_ref()
+                                .builders();
+
+                            // Register with every relay so that each of them is able to bid
+                            // for our proposals. Succeed if any relay accepted the registrations.
+                            let results =
+                                futures::future::join_all(builders.iter().map(|builder| async {
+                                    let result = builder
+                                        .post_builder_validators(&filtered_registration_data)
+                                        .await;
+                                    if let Err(e) = &result {
+                                        warn!(
+                                            log,
+                                            "Relay error when registering validator(s)";
+                                            "relay" => %builder.server(),
+                                            "num_registrations" => filtered_registration_data.len(),
+                                            "error" => ?e
+                                        );
+                                    }
+                                    result
+                                }))
+                                .await;
+
+                            let mut first_error = None;
+                            for result in results {
+                                match result {
+                                    Ok(resp) => return Ok(warp::reply::json(&resp).into_response()),
+                                    Err(e) => {
+                                        first_error.get_or_insert(e);
+                                    }
+                                }
+                            }
+
+                            let e = first_error
                                 .ok_or(BeaconChainError::BuilderMissing)
                                 .map_err(warp_utils::reject::beacon_chain_error)?;
-                            builder
-                                .post_builder_validators(&filtered_registration_data)
-                                .await
-                                .map(|resp| warp::reply::json(&resp).into_response())
-                                .map_err(|e| {
-                                    warn!(
-                                        log,
-                                        "Relay error when registering validator(s)";
-                                        "num_registrations" => filtered_registration_data.len(),
-                                        "error" => ?e
-                                    );
-                                    // Forward the HTTP status code if we are able to, otherwise fall back
-                                    // to a server error.
-                                    if let eth2::Error::ServerMessage(message) = e {
-                                        if message.code == StatusCode::BAD_REQUEST.as_u16() {
-                                            return warp_utils::reject::custom_bad_request(
-                                                message.message,
-                                            );
-                                        } else {
-                                            // According to the spec this response should only be a 400 or 500,
-                                            // so we fall back to a 500 here.
-                                            return warp_utils::reject::custom_server_error(
-                                                message.message,
-                                            );
-                                        }
-                                    }
-                                    warp_utils::reject::custom_server_error(format!("{e:?}"))
-                                })
+                            // Forward the HTTP status code if we are able to, otherwise fall back
+                            // to a server error.
+                            if let eth2::Error::ServerMessage(message) = e {
+                                if message.code == StatusCode::BAD_REQUEST.as_u16() {
+                                    return Err(warp_utils::reject::custom_bad_request(
+                                        message.message,
+                                    ));
+                                } else {
+                                    // According to the spec this response should only be a 400 or 500,
+                                    // so we fall back to a 500 here.
+                                    return Err(warp_utils::reject::custom_server_error(
+                                        message.message,
+                                    ));
+                                }
+                            }
+                            Err(warp_utils::reject::custom_server_error(format!("{e:?}")))
                         };
                         tokio::task::spawn(async move { tx.send(builder_future.await) });
 
@@ -4112,6 +4297,291 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/peers/bans
+    let get_lighthouse_peers_bans = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("bans"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let now = std::time::Instant::now();
+                    let peers = network_globals.peers.read();
+                    let bans = peers
+                        .peers()
+                        .filter_map(|(peer_id, peer_info)| {
+                            let PeerConnectionStatus::Banned { since } =
+                                peer_info.connection_status()
+                            else {
+                                return None;
+                            };
+                            Some(eth2::lighthouse::PeerBan {
+                                peer_id: peer_id.to_string(),
+                                score: peer_info.score().score(),
+                                client: peer_info.client().to_string(),
+                                ip_addresses: peer_info.seen_ip_addresses().collect(),
+                                banned_for: now.saturating_duration_since(*since).as_secs(),
+                                expires_in: peer_info
+                                    .score()
+                                    .ban_expiry()
+                                    .map(|expiry| expiry.saturating_duration_since(now).as_secs()),
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    Ok(api_types::GenericResponse::from(bans))
+                })
+            },
+        );
+
+    // POST lighthouse/peers/bans/clear
+    let post_lighthouse_peers_bans_clear = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("bans"))
+        .and(warp::path("clear"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_tx_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    publish_network_message(&network_tx, NetworkMessage::ClearPeerBans)
+                })
+            },
+        );
+
+    // GET lighthouse/peers/policy
+    let get_lighthouse_peers_policy = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("policy"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(api_types::GenericResponse::from(
+                        network_globals.peers.read().peer_policy().clone(),
+                    ))
+                })
+            },
+        );
+
+    // POST lighthouse/peers/policy
+    let post_lighthouse_peers_policy = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("policy"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(network_tx_filter.clone())
+        .then(
+            |peer_policy: PeerPolicy,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    peer_policy.validate().map_err(|e| {
+                        warp_utils::reject::custom_bad_request(format!(
+                            "invalid peer policy: {}",
+                            e
+                        ))
+                    })?;
+                    let (result_tx, result_rx) = oneshot::channel();
+                    publish_network_message(
+                        &network_tx,
+                        NetworkMessage::UpdatePeerPolicy {
+                            peer_policy,
+                            result_tx,
+                        },
+                    )?;
+                    result_rx
+                        .await
+                        .map_err(|_| {
+                            warp_utils::reject::custom_server_error(
+                                "network service did not respond".to_string(),
+                            )
+                        })?
+                        .map_err(|e| {
+                            warp_utils::reject::custom_server_error(format!(
+                                "unable to update peer policy: {}",
+                                e
+                            ))
+                        })?;
+                    Ok(warp::reply::json(&()).into_response())
+                })
+            },
+        );
+
+    // GET lighthouse/rpc/rate_limits
+    let get_lighthouse_rpc_rate_limits = warp::path("lighthouse")
+        .and(warp::path("rpc"))
+        .and(warp::path("rate_limits"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let rate_limits = network_globals.inbound_rate_limits.read();
+                    let quotas = rate_limits
+                        .quotas
+                        .iter()
+                        .map(|(protocol, quota)| eth2::lighthouse::RpcQuota {
+                            protocol: protocol.to_string(),
+                            max_tokens: quota.max_tokens(),
+                            replenish_all_every: quota.replenish_all_every().as_secs(),
+                        })
+                        .collect::<Vec<_>>();
+                    let mut peer_multipliers = rate_limits
+                        .peer_multipliers
+                        .iter()
+                        .map(
+                            |(peer_id, multiplier)| eth2::lighthouse::RpcPeerMultiplier {
+                                peer_id: peer_id.to_string(),
+                                multiplier: Some(*multiplier),
+                            },
+                        )
+                        .collect::<Vec<_>>();
+                    peer_multipliers.sort_by(|a, b| a.peer_id.cmp(&b.peer_id));
+                    let mut rejected_requests = rate_limits
+                        .rejected_requests
+                        .iter()
+                        .flat_map(|(peer_id, counts)| {
+                            counts.iter().map(move |(protocol, count)| {
+                                eth2::lighthouse::RpcRejectedRequests {
+                                    peer_id: peer_id.to_string(),
+                                    protocol: protocol.to_string(),
+                                    count: *count,
+                                }
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    rejected_requests
+                        .sort_by(|a, b| (&a.peer_id, &a.protocol).cmp(&(&b.peer_id, &b.protocol)));
+                    Ok(api_types::GenericResponse::from(
+                        eth2::lighthouse::RpcRateLimits {
+                            enabled: !quotas.is_empty(),
+                            quotas,
+                            peer_multipliers,
+                            rejected_requests,
+                        },
+                    ))
+                })
+            },
+        );
+
+    // POST lighthouse/rpc/rate_limits
+    let post_lighthouse_rpc_rate_limits = warp::path("lighthouse")
+        .and(warp::path("rpc"))
+        .and(warp::path("rate_limits"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .and(network_tx_filter.clone())
+        .then(
+            |update: eth2::lighthouse::RpcRateLimitsUpdate,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    if network_globals.inbound_rate_limits.read().quotas.is_empty() {
+                        return Err(warp_utils::reject::custom_bad_request(
+                            "inbound rate limiting is disabled".to_string(),
+                        ));
+                    }
+                    let quotas = update
+                        .quotas
+                        .into_iter()
+                        .map(|quota| {
+                            let protocol = quota.protocol.parse::<Protocol>().map_err(|_| {
+                                warp_utils::reject::custom_bad_request(format!(
+                                    "unknown protocol: {}",
+                                    quota.protocol
+                                ))
+                            })?;
+                            let rpc_quota =
+                                Quota::n_every(quota.max_tokens, quota.replenish_all_every);
+                            rpc_quota.validate().map_err(|e| {
+                                warp_utils::reject::custom_bad_request(format!(
+                                    "invalid quota for {}: {}",
+                                    quota.protocol, e
+                                ))
+                            })?;
+                            Ok((protocol, rpc_quota))
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let peer_multipliers = update
+                        .peer_multipliers
+                        .into_iter()
+                        .map(|peer_multiplier| {
+                            let peer_id =
+                                peer_multiplier.peer_id.parse::<PeerId>().map_err(|_| {
+                                    warp_utils::reject::custom_bad_request(format!(
+                                        "invalid peer id: {}",
+                                        peer_multiplier.peer_id
+                                    ))
+                                })?;
+                            if let Some(multiplier) = peer_multiplier.multiplier {
+                                if !multiplier.is_finite() || multiplier <= 0.0 {
+                                    return Err(warp_utils::reject::custom_bad_request(format!(
+                                        "invalid multiplier for {}: must be positive",
+                                        peer_id
+                                    )));
+                                }
+                            }
+                            Ok((peer_id, peer_multiplier.multiplier))
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let (result_tx, result_rx) = oneshot::channel();
+                    publish_network_message(
+                        &network_tx,
+                        NetworkMessage::UpdateInboundRateLimits {
+                            quotas,
+                            peer_multipliers,
+                            result_tx,
+                        },
+                    )?;
+                    result_rx
+                        .await
+                        .map_err(|_| {
+                            warp_utils::reject::custom_server_error(
+                                "network service did not respond".to_string(),
+                            )
+                        })?
+                        .map_err(|e| {
+                            warp_utils::reject::custom_bad_request(format!(
+                                "unable to update rate limits: {}",
+                                e
+                            ))
+                        })?;
+                    Ok(warp::reply::json(&()).into_response())
+                })
+            },
+        );
+
+    // POST lighthouse/peers/policy/reload
+    let post_lighthouse_peers_policy_reload = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("policy"))
+        .and(warp::path("reload"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_tx_filter)
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    publish_network_message(&network_tx, NetworkMessage::ReloadPeerPolicy)
+                })
+            },
+        );
+
     // GET lighthouse/peers/connected
     let get_lighthouse_peers_connected = warp::path("lighthouse")
         .and(warp::path("peers"))
@@ -4158,6 +4628,21 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/operation_pool?kind,slot,validator_index
+    let get_lighthouse_operation_pool = warp::path("lighthouse")
+        .and(warp::path("operation_pool"))
+        .and(warp::path::end())
+        .and(warp::query::<eth2::lighthouse::OperationPoolQuery>())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |query, task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(op_pool::get_operation_pool_contents(query, chain))
+                })
+            },
+        );
+
     // GET lighthouse/validator_inclusion/{epoch}/{validator_id}
     let get_lighthouse_validator_inclusion_global = warp::path("lighthouse")
         .and(warp::path("validator_inclusion"))
@@ -4564,128 +5049,147 @@ pub fn serve<T: BeaconChainTypes>(
 
     // Define the ultimate set of routes that will be provided to the server.
     // Use `uor` rather than `or` in order to simplify types (see `UnifyingOrFilter`).
-    let routes = warp::get()
+    //
+    // Authentication is checked before routing so that rejected requests are never processed.
+    let routes = api_auth_filter
         .and(
-            get_beacon_genesis
-                .uor(get_beacon_state_root)
-                .uor(get_beacon_state_fork)
-                .uor(get_beacon_state_finality_checkpoints)
-                .uor(get_beacon_state_validator_balances)
-                .uor(get_beacon_state_validators_id)
-                .uor(get_beacon_state_validators)
-                .uor(get_beacon_state_committees)
-                .uor(get_beacon_state_sync_committees)
-                .uor(get_beacon_state_randao)
-                .uor(get_beacon_headers)
-                .uor(get_beacon_headers_block_id)
-                .uor(get_beacon_block)
-                .uor(get_beacon_block_attestations)
-                .uor(get_beacon_blinded_block)
-                .uor(get_beacon_block_root)
-                .uor(get_blobs)
-                .uor(get_beacon_pool_attestations)
-                .uor(get_beacon_pool_attester_slashings)
-                .uor(get_beacon_pool_proposer_slashings)
-                .uor(get_beacon_pool_voluntary_exits)
-                .uor(get_beacon_pool_bls_to_execution_changes)
-                .uor(get_beacon_deposit_snapshot)
-                .uor(get_beacon_rewards_blocks)
-                .uor(get_config_fork_schedule)
-                .uor(get_config_spec)
-                .uor(get_config_deposit_contract)
-                .uor(get_debug_beacon_states)
-                .uor(get_debug_beacon_heads)
-                .uor(get_debug_fork_choice)
-                .uor(get_node_identity)
-                .uor(get_node_version)
-                .uor(get_node_syncing)
-                .uor(get_node_health)
-                .uor(get_node_peers_by_id)
-                .uor(get_node_peers)
-                .uor(get_node_peer_count)
-                .uor(get_validator_duties_proposer)
-                .uor(get_validator_blocks)
-                .uor(get_validator_blinded_blocks)
-                .uor(get_validator_attestation_data)
-                .uor(get_validator_aggregate_attestation)
-                .uor(get_validator_sync_committee_contribution)
-                .uor(get_lighthouse_health)
-                .uor(get_lighthouse_ui_health)
-                .uor(get_lighthouse_ui_validator_count)
-                .uor(get_lighthouse_syncing)
-                .uor(get_lighthouse_nat)
-                .uor(get_lighthouse_peers)
-                .uor(get_lighthouse_peers_connected)
-                .uor(get_lighthouse_proto_array)
-                .uor(get_lighthouse_validator_inclusion_global)
-                .uor(get_lighthouse_validator_inclusion)
-                .uor(get_lighthouse_eth1_syncing)
-                .uor(get_lighthouse_eth1_block_cache)
-                .uor(get_lighthouse_eth1_deposit_cache)
-                .uor(get_lighthouse_staking)
-                .uor(get_lighthouse_database_info)
-                .uor(get_lighthouse_block_rewards)
-                .uor(get_lighthouse_attestation_performance)
-                .uor(
-                    enable(ctx.config.enable_light_client_server)
-                        .and(get_beacon_light_client_optimistic_update),
-                )
-                .uor(
-                    enable(ctx.config.enable_light_client_server)
-                        .and(get_beacon_light_client_finality_update),
+            warp::get()
+                .and(
+                    get_beacon_genesis
+                        .uor(get_beacon_state_root)
+                        .uor(get_beacon_state_fork)
+                        .uor(get_beacon_state_finality_checkpoints)
+                        .uor(get_beacon_state_validator_balances)
+                        .uor(get_beacon_state_validators_id)
+                        .uor(get_beacon_state_validators)
+                        .uor(get_beacon_state_committees)
+                        .uor(get_beacon_state_sync_committees)
+                        .uor(get_beacon_state_randao)
+                        .uor(get_beacon_headers)
+                        .uor(get_beacon_headers_block_id)
+                        .uor(get_beacon_block)
+                        .uor(get_beacon_block_attestations)
+                        .uor(get_beacon_blinded_block)
+                        .uor(get_beacon_block_root)
+                        .uor(get_beacon_proof_state)
+                        .uor(get_beacon_proof_block)
+                        .uor(get_blobs)
+                        .uor(get_beacon_pool_attestations)
+                        .uor(get_beacon_pool_attester_slashings)
+                        .uor(get_beacon_pool_proposer_slashings)
+                        .uor(get_beacon_pool_voluntary_exits)
+                        .uor(get_beacon_pool_bls_to_execution_changes)
+                        .uor(get_beacon_deposit_snapshot)
+                        .uor(get_beacon_rewards_blocks)
+                        .uor(get_config_fork_schedule)
+                        .uor(get_config_spec)
+                        .uor(get_config_deposit_contract)
+                        .uor(get_debug_beacon_states)
+                        .uor(get_debug_beacon_heads)
+                        .uor(get_debug_fork_choice)
+                        .uor(get_node_identity)
+                        .uor(get_node_version)
+                        .uor(get_node_syncing)
+                        .uor(get_node_health)
+                        .uor(get_node_peers_by_id)
+                        .uor(get_node_peers)
+                        .uor(get_node_peer_count)
+                        .uor(get_validator_duties_proposer)
+                        .uor(get_validator_blocks)
+                        .uor(get_validator_blinded_blocks)
+                        .uor(get_validator_attestation_data)
+                        .uor(get_validator_aggregate_attestation)
+                        .uor(get_validator_sync_committee_contribution)
+                        .uor(get_lighthouse_health)
+                        .uor(get_lighthouse_ui_health)
+                        .uor(get_lighthouse_ui_validator_count)
+                        .uor(get_lighthouse_syncing)
+                        .uor(get_lighthouse_nat)
+                        .uor(get_lighthouse_peers)
+                        .uor(get_lighthouse_peers_connected)
+                        .uor(get_lighthouse_peers_bans)
+                        .uor(get_lighthouse_peers_policy)
+                        .uor(get_lighthouse_rpc_rate_limits)
+                        .uor(get_lighthouse_proto_array)
+                        .uor(get_lighthouse_operation_pool)
+                        .uor(get_lighthouse_validator_inclusion_global)
+                        .uor(get_lighthouse_validator_inclusion)
+                        .uor(get_lighthouse_eth1_syncing)
+                        .uor(get_lighthouse_eth1_block_cache)
+                        .uor(get_lighthouse_eth1_deposit_cache)
+                        .uor(get_lighthouse_staking)
+                        .uor(get_lighthouse_database_info)
+                        .uor(get_lighthouse_block_rewards)
+                        .uor(get_lighthouse_attestation_performance)
+                        .uor(
+                            enable(ctx.config.enable_light_client_server)
+                                .and(get_beacon_light_client_optimistic_update),
+                        )
+                        .uor(
+                            enable(ctx.config.enable_light_client_server)
+                                .and(get_beacon_light_client_finality_update),
+                        )
+                        .uor(
+                            enable(ctx.config.enable_light_client_server)
+                                .and(get_beacon_light_client_bootstrap),
+                        )
+                        .uor(
+                            enable(ctx.config.enable_light_client_server)
+                                .and(get_beacon_light_client_updates),
+                        )
+                        .uor(get_lighthouse_block_packing_efficiency)
+                        .uor(get_lighthouse_merge_readiness)
+                        .uor(get_events)
+                        .uor(get_expected_withdrawals)
+                        .uor(lighthouse_log_events.boxed())
+                        .recover(warp_utils::reject::handle_rejection),
                 )
+                .boxed()
                 .uor(
-                    enable(ctx.config.enable_light_client_server)
-                        .and(get_beacon_light_client_bootstrap),
-                )
-                .uor(get_lighthouse_block_packing_efficiency)
-                .uor(get_lighthouse_merge_readiness)
-                .uor(get_events)
-                .uor(get_expected_withdrawals)
-                .uor(lighthouse_log_events.boxed())
-                .recover(warp_utils::reject::handle_rejection),
-        )
-        .boxed()
-        .uor(
-            warp::post().and(
-                warp::header::exact(CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER)
-                    // Routes which expect `application/octet-stream` go within this `and`.
-                    .and(
-                        post_beacon_blocks_ssz
-                            .uor(post_beacon_blocks_v2_ssz)
-                            .uor(post_beacon_blinded_blocks_ssz)
-                            .uor(post_beacon_blinded_blocks_v2_ssz),
-                    )
-                    .uor(post_beacon_blocks)
-                    .uor(post_beacon_blinded_blocks)
-                    .uor(post_beacon_blocks_v2)
-                    .uor(post_beacon_blinded_blocks_v2)
-                    .uor(post_beacon_pool_attestations)
-                    .uor(post_beacon_pool_attester_slashings)
-                    .uor(post_beacon_pool_proposer_slashings)
-                    .uor(post_beacon_pool_voluntary_exits)
-                    .uor(post_beacon_pool_sync_committees)
-                    .uor(post_beacon_pool_bls_to_execution_changes)
-                    .uor(post_beacon_state_validators)
-                    .uor(post_beacon_state_validator_balances)
-                    .uor(post_beacon_rewards_attestations)
-                    .uor(post_beacon_rewards_sync_committee)
-                    .uor(post_validator_duties_attester)
-                    .uor(post_validator_duties_sync)
-                    .uor(post_validator_aggregate_and_proofs)
-                    .uor(post_validator_contribution_and_proofs)
-                    .uor(post_validator_beacon_committee_subscriptions)
-                    .uor(post_validator_sync_committee_subscriptions)
-                    .uor(post_validator_prepare_beacon_proposer)
-                    .uor(post_validator_register_validator)
-                    .uor(post_validator_liveness_epoch)
-                    .uor(post_lighthouse_liveness)
-                    .uor(post_lighthouse_database_reconstruct)
-                    .uor(post_lighthouse_block_rewards)
-                    .uor(post_lighthouse_ui_validator_metrics)
-                    .uor(post_lighthouse_ui_validator_info)
-                    .recover(warp_utils::reject::handle_rejection),
-            ),
+                    warp::post().and(
+                        warp::header::exact(CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER)
+                            // Routes which expect `application/octet-stream` go within this `and`.
+                            .and(
+                                post_beacon_blocks_ssz
+                                    .uor(post_beacon_blocks_v2_ssz)
+                                    .uor(post_beacon_blinded_blocks_ssz)
+                                    .uor(post_beacon_blinded_blocks_v2_ssz),
+                            )
+                            .uor(post_beacon_blocks)
+                            .uor(post_beacon_blinded_blocks)
+                            .uor(post_beacon_blocks_v2)
+                            .uor(post_beacon_blinded_blocks_v2)
+                            .uor(post_beacon_pool_attestations)
+                            .uor(post_beacon_pool_attester_slashings)
+                            .uor(post_beacon_pool_proposer_slashings)
+                            .uor(post_beacon_pool_voluntary_exits)
+                            .uor(post_beacon_pool_sync_committees)
+                            .uor(post_beacon_pool_bls_to_execution_changes)
+                            .uor(post_beacon_state_validators)
+                            .uor(post_beacon_state_validator_balances)
+                            .uor(post_beacon_rewards_attestations)
+                            .uor(post_beacon_rewards_sync_committee)
+                            .uor(post_validator_duties_attester)
+                            .uor(post_validator_duties_sync)
+                            .uor(post_validator_aggregate_and_proofs)
+                            .uor(post_validator_contribution_and_proofs)
+                            .uor(post_validator_beacon_committee_subscriptions)
+                            .uor(post_validator_sync_committee_subscriptions)
+                            .uor(post_validator_prepare_beacon_proposer)
+                            .uor(post_validator_register_validator)
+                            .uor(post_validator_liveness_epoch)
+                            .uor(post_lighthouse_liveness)
+                            .uor(post_lighthouse_database_reconstruct)
+                            .uor(post_lighthouse_peers_bans_clear)
+                            .uor(post_lighthouse_peers_policy)
+                            .uor(post_lighthouse_peers_policy_reload)
+                            .uor(post_lighthouse_rpc_rate_limits)
+                            .uor(post_lighthouse_block_rewards)
+                            .uor(post_lighthouse_ui_validator_metrics)
+                            .uor(post_lighthouse_ui_validator_info)
+                            .recover(warp_utils::reject::handle_rejection),
+                    ),
+                ),
         )
         .recover(warp_utils::reject::handle_rejection)
         .with(slog_logging(log.clone()))
@@ -0,0 +1,578 @@
+//! Merkle proofs of individual fields of states and blocks.
+//!
+//! Fields are selected by paths of `.`-separated field names and list indices, such as
+//! `finalized_checkpoint.root`, `validators.5.effective_balance` or `body.blob_kzg_commitments.0`.
+//! Each proof is a single-leaf proof against the `hash_tree_root` of the whole object.
+use eth2::types::{MerkleProof, MerkleProofsData};
+use merkle_proof::MerkleTree;
+use std::collections::{hash_map::Entry, HashMap};
+use std::rc::Rc;
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tree_hash::TreeHash;
+use types::{
+    typenum::Unsigned, AbstractExecPayload, BeaconBlockHeader, BeaconBlockRef, BeaconState,
+    Checkpoint, Eth1Data, EthSpec, Hash256, HistoricalSummary, Validator,
+};
+use warp_utils::reject::{custom_bad_request, too_many_requests};
+
+/// The separator between the segments of a path.
+const PATH_SEPARATOR: char = '.';
+
+/// The maximum number of paths which may be proven in a single request.
+///
+/// Each proof into a large list (e.g. the validator registry) reuses the tree of that list, but
+/// this still bounds the remaining work done per request.
+const MAX_PATHS_PER_REQUEST: usize = 32;
+
+/// The maximum number of state proof requests which may be computed at the same time.
+///
+/// Proving a path into the validator registry hashes every validator, so requests beyond this
+/// limit are rejected instead of occupying more of the blocking thread pool.
+const MAX_CONCURRENT_STATE_REQUESTS: usize = 2;
+
+lazy_static::lazy_static! {
+    static ref STATE_REQUEST_PERMITS: Semaphore = Semaphore::new(MAX_CONCURRENT_STATE_REQUESTS);
+}
+
+/// Reserve one of the limited slots for computing state proofs, which must be held until the
+/// proofs are computed.
+pub fn state_request_permit() -> Result<SemaphorePermit<'static>, warp::Rejection> {
+    STATE_REQUEST_PERMITS.try_acquire().map_err(|_| {
+        too_many_requests(format!(
+            "at most {} state proof requests may be processed at once",
+            MAX_CONCURRENT_STATE_REQUESTS
+        ))
+    })
+}
+
+/// One level of a proof: the tree of a container or list, and the index of the node to prove.
+struct Level {
+    tree: Rc<MerkleTree>,
+    depth: usize,
+    index: usize,
+    /// The length of the list, if this level is a list which mixes its length into its root.
+    length: Option<usize>,
+}
+
+impl Level {
+    /// A level for the field called `name` in a container with the given fields.
+    fn container(fields: Vec<(&'static str, Hash256)>, name: &str) -> Result<Self, String> {
+        let index = fields
+            .iter()
+            .position(|(field, _)| *field == name)
+            .ok_or_else(|| format!("unknown field `{}`", name))?;
+        let leaves = fields.into_iter().map(|(_, root)| root).collect::<Vec<_>>();
+        let depth = depth(leaves.len());
+        Ok(Self {
+            tree: Rc::new(MerkleTree::create(&leaves, depth)),
+            depth,
+            index,
+            length: None,
+        })
+    }
+}
+
+/// The tree of a vector or list, from which levels for any of its elements can be made.
+///
+/// Building the tree of a large list (e.g. the validator registry) requires hashing every element,
+/// so each tree is built at most once per request.
+struct Tree {
+    tree: Rc<MerkleTree>,
+    depth: usize,
+    /// The number of elements in the vector or list.
+    len: usize,
+    /// The number of elements packed into each leaf.
+    elements_per_leaf: usize,
+    /// Whether this is a list, which mixes its length into its root.
+    is_list: bool,
+}
+
+impl Tree {
+    /// The tree of a vector with the given leaves.
+    fn vector(leaves: Vec<Hash256>) -> Self {
+        let depth = depth(leaves.len());
+        Self {
+            tree: Rc::new(MerkleTree::create(&leaves, depth)),
+            depth,
+            len: leaves.len(),
+            elements_per_leaf: 1,
+            is_list: false,
+        }
+    }
+
+    /// The tree of a list of composite values with at most `limit` elements.
+    fn list<'a, T: TreeHash + 'a>(items: impl IntoIterator<Item = &'a T>, limit: usize) -> Self {
+        let leaves = items
+            .into_iter()
+            .map(TreeHash::tree_hash_root)
+            .collect::<Vec<_>>();
+        let depth = depth(limit);
+        Self {
+            tree: Rc::new(MerkleTree::create(&leaves, depth)),
+            depth,
+            len: leaves.len(),
+            elements_per_leaf: 1,
+            is_list: true,
+        }
+    }
+
+    /// The tree of a packed vector or list of `u64`s with at most `limit` values.
+    ///
+    /// Four values are packed into each leaf, so a proven leaf also contains its neighbours.
+    fn packed_u64(values: Vec<u64>, limit: usize, is_list: bool) -> Self {
+        let values_per_chunk = 32 / std::mem::size_of::<u64>();
+        let leaves = values
+            .chunks(values_per_chunk)
+            .map(|chunk| {
+                let mut leaf = [0; 32];
+                for (i, value) in chunk.iter().enumerate() {
+                    leaf[i * 8..(i + 1) * 8].copy_from_slice(&value.to_le_bytes());
+                }
+                Hash256::from(leaf)
+            })
+            .collect::<Vec<_>>();
+        let depth = depth(limit.div_ceil(values_per_chunk));
+        Self {
+            tree: Rc::new(MerkleTree::create(&leaves, depth)),
+            depth,
+            len: values.len(),
+            elements_per_leaf: values_per_chunk,
+            is_list,
+        }
+    }
+
+    /// A level for the element at `index`.
+    fn level(&self, index: &str) -> Result<Level, String> {
+        let index = parse_index(index, self.len)?;
+        Ok(Level {
+            tree: self.tree.clone(),
+            depth: self.depth,
+            index: index / self.elements_per_leaf,
+            length: self.is_list.then_some(self.len),
+        })
+    }
+}
+
+/// Returns the tree of the field `field`, building it with `build` only if it is not in `trees`.
+fn cached_tree<'a, F>(
+    trees: &'a mut HashMap<String, Tree>,
+    field: &str,
+    build: F,
+) -> Result<&'a Tree, String>
+where
+    F: FnOnce() -> Result<Tree, String>,
+{
+    match trees.entry(field.to_string()) {
+        Entry::Occupied(entry) => Ok(entry.into_mut()),
+        Entry::Vacant(entry) => Ok(entry.insert(build()?)),
+    }
+}
+
+/// The root of a container with the given fields.
+fn container_root(fields: &[(&'static str, Hash256)]) -> Hash256 {
+    let leaves = fields.iter().map(|(_, root)| *root).collect::<Vec<_>>();
+    MerkleTree::create(&leaves, depth(fields.len())).hash()
+}
+
+/// The depth of a tree with `num_leaves` leaves.
+fn depth(num_leaves: usize) -> usize {
+    num_leaves.next_power_of_two().trailing_zeros() as usize
+}
+
+fn parse_index(segment: &str, len: usize) -> Result<usize, String> {
+    let index = segment
+        .parse::<usize>()
+        .map_err(|_| format!("invalid index `{}`", segment))?;
+    if index < len {
+        Ok(index)
+    } else {
+        Err(format!(
+            "index {} is out of bounds for length {}",
+            index, len
+        ))
+    }
+}
+
+fn element<T>(element: Option<&T>) -> Result<&T, String> {
+    element.ok_or_else(|| "index is out of bounds".to_string())
+}
+
+/// Combine the `levels` of a path, from the root downwards, into a single proof.
+fn prove(path: &str, levels: &[Level]) -> Result<MerkleProof, String> {
+    let mut gindex = 1u64;
+    for level in levels {
+        if level.length.is_some() {
+            // The contents of a list are the left child of its root.
+            gindex *= 2;
+        }
+        gindex = (gindex << level.depth) + level.index as u64;
+    }
+
+    let mut leaf = None;
+    let mut branch = vec![];
+    for level in levels.iter().rev() {
+        let (node, proof) = level
+            .tree
+            .generate_proof(level.index, level.depth)
+            .map_err(|e| format!("unable to generate proof: {:?}", e))?;
+        leaf.get_or_insert(node);
+        branch.extend(proof);
+        if let Some(length) = level.length {
+            let mut length_root = [0; 32];
+            length_root[..8].copy_from_slice(&(length as u64).to_le_bytes());
+            branch.push(Hash256::from(length_root));
+        }
+    }
+
+    Ok(MerkleProof {
+        path: path.to_string(),
+        gindex,
+        leaf: leaf.ok_or("empty path")?,
+        branch,
+    })
+}
+
+/// Prove each of `paths`, where `levels` resolves a path to its levels.
+fn prove_paths<F>(
+    paths: &[String],
+    root: Hash256,
+    mut levels: F,
+) -> Result<MerkleProofsData, warp::Rejection>
+where
+    F: FnMut(&[&str]) -> Result<Vec<Level>, String>,
+{
+    if paths.is_empty() {
+        return Err(custom_bad_request("no paths provided".to_string()));
+    }
+    if paths.len() > MAX_PATHS_PER_REQUEST {
+        return Err(custom_bad_request(format!(
+            "at most {} paths may be requested at once",
+            MAX_PATHS_PER_REQUEST
+        )));
+    }
+
+    let proofs = paths
+        .iter()
+        .map(|path| {
+            let segments = path.split(PATH_SEPARATOR).collect::<Vec<_>>();
+            levels(&segments)
+                .and_then(|levels| prove(path, &levels))
+                .map_err(|e| custom_bad_request(format!("invalid path {}: {}", path, e)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(MerkleProofsData { root, proofs })
+}
+
+/// Prove the fields of `state` at each of `paths`.
+pub fn state_proofs<E: EthSpec>(
+    state: &BeaconState<E>,
+    paths: &[String],
+) -> Result<MerkleProofsData, warp::Rejection> {
+    let field_roots = state.field_roots();
+    let root = container_root(&field_roots);
+    let mut trees = HashMap::new();
+
+    prove_paths(paths, root, |segments| {
+        let [field, rest @ ..] = segments else {
+            return Err("empty path".to_string());
+        };
+        let mut levels = vec![Level::container(field_roots.clone(), field)?];
+        let level = match (*field, rest) {
+            (_, []) => None,
+            ("latest_block_header", [subfield]) => Some(Level::container(
+                header_fields(state.latest_block_header()),
+                subfield,
+            )?),
+            ("eth1_data", [subfield]) => Some(Level::container(
+                eth1_data_fields(state.eth1_data()),
+                subfield,
+            )?),
+            ("previous_justified_checkpoint", [subfield]) => Some(Level::container(
+                checkpoint_fields(&state.previous_justified_checkpoint()),
+                subfield,
+            )?),
+            ("current_justified_checkpoint", [subfield]) => Some(Level::container(
+                checkpoint_fields(&state.current_justified_checkpoint()),
+                subfield,
+            )?),
+            ("finalized_checkpoint", [subfield]) => Some(Level::container(
+                checkpoint_fields(&state.finalized_checkpoint()),
+                subfield,
+            )?),
+            ("block_roots", [index]) => Some(
+                cached_tree(&mut trees, field, || {
+                    Ok(Tree::vector(state.block_roots().iter().copied().collect()))
+                })?
+                .level(index)?,
+            ),
+            ("state_roots", [index]) => Some(
+                cached_tree(&mut trees, field, || {
+                    Ok(Tree::vector(state.state_roots().iter().copied().collect()))
+                })?
+                .level(index)?,
+            ),
+            ("randao_mixes", [index]) => Some(
+                cached_tree(&mut trees, field, || {
+                    Ok(Tree::vector(state.randao_mixes().iter().copied().collect()))
+                })?
+                .level(index)?,
+            ),
+            ("slashings", [index]) => Some(
+                cached_tree(&mut trees, field, || {
+                    Ok(Tree::packed_u64(
+                        state.slashings().iter().copied().collect(),
+                        E::EpochsPerSlashingsVector::to_usize(),
+                        false,
+                    ))
+                })?
+                .level(index)?,
+            ),
+            ("historical_roots", [index]) => Some(
+                cached_tree(&mut trees, field, || {
+                    Ok(Tree::list(
+                        state.historical_roots().iter(),
+                        E::HistoricalRootsLimit::to_usize(),
+                    ))
+                })?
+                .level(index)?,
+            ),
+            ("eth1_data_votes", [index, subfields @ ..]) => {
+                levels.push(
+                    cached_tree(&mut trees, field, || {
+                        Ok(Tree::list(
+                            state.eth1_data_votes().iter(),
+                            E::SlotsPerEth1VotingPeriod::to_usize(),
+                        ))
+                    })?
+                    .level(index)?,
+                );
+                match subfields {
+                    [] => None,
+                    [subfield] => Some(Level::container(
+                        eth1_data_fields(element(state.eth1_data_votes().get(levels[1].index))?),
+                        subfield,
+                    )?),
+                    _ => return Err("unsupported path".to_string()),
+                }
+            }
+            ("validators", [index, subfields @ ..]) => {
+                levels.push(
+                    cached_tree(&mut trees, field, || {
+                        Ok(Tree::list(
+                            state.validators().iter(),
+                            E::ValidatorRegistryLimit::to_usize(),
+                        ))
+                    })?
+                    .level(index)?,
+                );
+                match subfields {
+                    [] => None,
+                    [subfield] => Some(Level::container(
+                        validator_fields(element(state.validators().get(levels[1].index))?),
+                        subfield,
+                    )?),
+                    _ => return Err("unsupported path".to_string()),
+                }
+            }
+            ("balances", [index]) => Some(
+                cached_tree(&mut trees, field, || {
+                    Ok(Tree::packed_u64(
+                        state.balances().iter().copied().collect(),
+                        E::ValidatorRegistryLimit::to_usize(),
+                        true,
+                    ))
+                })?
+                .level(index)?,
+            ),
+            ("inactivity_scores", [index]) => Some(
+                cached_tree(&mut trees, field, || {
+                    Ok(Tree::packed_u64(
+                        state
+                            .inactivity_scores()
+                            .map_err(|e| format!("{:?}", e))?
+                            .iter()
+                            .copied()
+                            .collect(),
+                        E::ValidatorRegistryLimit::to_usize(),
+                        true,
+                    ))
+                })?
+                .level(index)?,
+            ),
+            ("historical_summaries", [index, subfields @ ..]) => {
+                let historical_summaries = state
+                    .historical_summaries()
+                    .map_err(|e| format!("{:?}", e))?;
+                levels.push(
+                    cached_tree(&mut trees, field, || {
+                        Ok(Tree::list(
+                            historical_summaries.iter(),
+                            E::HistoricalRootsLimit::to_usize(),
+                        ))
+                    })?
+                    .level(index)?,
+                );
+                match subfields {
+                    [] => None,
+                    [subfield] => Some(Level::container(
+                        historical_summary_fields(element(
+                            historical_summaries.get(levels[1].index),
+                        )?),
+                        subfield,
+                    )?),
+                    _ => return Err("unsupported path".to_string()),
+                }
+            }
+            _ => return Err("unsupported path".to_string()),
+        };
+        levels.extend(level);
+        Ok(levels)
+    })
+}
+
+/// Prove the fields of `block` at each of `paths`.
+pub fn block_proofs<E: EthSpec, Payload: AbstractExecPayload<E>>(
+    block: BeaconBlockRef<E, Payload>,
+    paths: &[String],
+) -> Result<MerkleProofsData, warp::Rejection> {
+    let body = block.body();
+    let fields = vec![
+        ("slot", block.slot().tree_hash_root()),
+        ("proposer_index", block.proposer_index().tree_hash_root()),
+        ("parent_root", block.parent_root().tree_hash_root()),
+        ("state_root", block.state_root().tree_hash_root()),
+        ("body", block.body_root()),
+    ];
+    let root = container_root(&fields);
+    let mut trees = HashMap::new();
+
+    prove_paths(paths, root, |segments| {
+        let [field, rest @ ..] = segments else {
+            return Err("empty path".to_string());
+        };
+        let mut levels = vec![Level::container(fields.clone(), field)?];
+        match (*field, rest) {
+            (_, []) => {}
+            ("body", [body_field, rest @ ..]) => {
+                levels.push(Level::container(body.field_roots(), body_field)?);
+                let level = match (*body_field, rest) {
+                    (_, []) => None,
+                    ("proposer_slashings", [index]) => Some(
+                        cached_tree(&mut trees, body_field, || {
+                            Ok(Tree::list(
+                                body.proposer_slashings().iter(),
+                                E::MaxProposerSlashings::to_usize(),
+                            ))
+                        })?
+                        .level(index)?,
+                    ),
+                    ("deposits", [index]) => Some(
+                        cached_tree(&mut trees, body_field, || {
+                            Ok(Tree::list(
+                                body.deposits().iter(),
+                                E::MaxDeposits::to_usize(),
+                            ))
+                        })?
+                        .level(index)?,
+                    ),
+                    ("voluntary_exits", [index]) => Some(
+                        cached_tree(&mut trees, body_field, || {
+                            Ok(Tree::list(
+                                body.voluntary_exits().iter(),
+                                E::MaxVoluntaryExits::to_usize(),
+                            ))
+                        })?
+                        .level(index)?,
+                    ),
+                    ("bls_to_execution_changes", [index]) => Some(
+                        cached_tree(&mut trees, body_field, || {
+                            Ok(Tree::list(
+                                body.bls_to_execution_changes()
+                                    .map_err(|e| format!("{:?}", e))?
+                                    .iter(),
+                                E::MaxBlsToExecutionChanges::to_usize(),
+                            ))
+                        })?
+                        .level(index)?,
+                    ),
+                    ("blob_kzg_commitments", [index]) => Some(
+                        cached_tree(&mut trees, body_field, || {
+                            Ok(Tree::list(
+                                body.blob_kzg_commitments()
+                                    .map_err(|e| format!("{:?}", e))?
+                                    .iter(),
+                                E::MaxBlobCommitmentsPerBlock::to_usize(),
+                            ))
+                        })?
+                        .level(index)?,
+                    ),
+                    _ => return Err("unsupported path".to_string()),
+                };
+                levels.extend(level);
+            }
+            _ => return Err("unsupported path".to_string()),
+        }
+        Ok(levels)
+    })
+}
+
+fn checkpoint_fields(checkpoint: &Checkpoint) -> Vec<(&'static str, Hash256)> {
+    vec![
+        ("epoch", checkpoint.epoch.tree_hash_root()),
+        ("root", checkpoint.root.tree_hash_root()),
+    ]
+}
+
+fn header_fields(header: &BeaconBlockHeader) -> Vec<(&'static str, Hash256)> {
+    vec![
+        ("slot", header.slot.tree_hash_root()),
+        ("proposer_index", header.proposer_index.tree_hash_root()),
+        ("parent_root", header.parent_root.tree_hash_root()),
+        ("state_root", header.state_root.tree_hash_root()),
+        ("body_root", header.body_root.tree_hash_root()),
+    ]
+}
+
+fn eth1_data_fields(eth1_data: &Eth1Data) -> Vec<(&'static str, Hash256)> {
+    vec![
+        ("deposit_root", eth1_data.deposit_root.tree_hash_root()),
+        ("deposit_count", eth1_data.deposit_count.tree_hash_root()),
+        ("block_hash", eth1_data.block_hash.tree_hash_root()),
+    ]
+}
+
+fn validator_fields(validator: &Validator) -> Vec<(&'static str, Hash256)> {
+    vec![
+        ("pubkey", validator.pubkey.tree_hash_root()),
+        (
+            "withdrawal_credentials",
+            validator.withdrawal_credentials.tree_hash_root(),
+        ),
+        (
+            "effective_balance",
+            validator.effective_balance.tree_hash_root(),
+        ),
+        ("slashed", validator.slashed.tree_hash_root()),
+        (
+            "activation_eligibility_epoch",
+            validator.activation_eligibility_epoch.tree_hash_root(),
+        ),
+        (
+            "activation_epoch",
+            validator.activation_epoch.tree_hash_root(),
+        ),
+        ("exit_epoch", validator.exit_epoch.tree_hash_root()),
+        (
+            "withdrawable_epoch",
+            validator.withdrawable_epoch.tree_hash_root(),
+        ),
+    ]
+}
+
+fn historical_summary_fields(summary: &HistoricalSummary) -> Vec<(&'static str, Hash256)> {
+    vec![
+        ("block_summary_root", summary.block_summary_root()),
+        ("state_summary_root", summary.state_summary_root()),
+    ]
+}
@@ -0,0 +1,102 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{
+    OperationKind, OperationPoolContents, OperationPoolQuery, OperationPoolStats,
+};
+use std::sync::Arc;
+use types::{AttesterSlashing, Slot};
+
+/// Returns the statistics and filtered contents of the operation pool and naive aggregation pools.
+pub fn get_operation_pool_contents<T: BeaconChainTypes>(
+    query: OperationPoolQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> OperationPoolContents<T::EthSpec> {
+    let op_pool = &chain.op_pool;
+    let naive_aggregation_pool = chain.naive_aggregation_pool.read();
+    let naive_sync_aggregation_pool = chain.naive_sync_aggregation_pool.read();
+
+    let bls_to_execution_changes = op_pool.get_all_bls_to_execution_changes();
+    let attestation_stats = op_pool.attestation_stats();
+    let stats = OperationPoolStats {
+        attestations: attestation_stats.num_attestations,
+        attestation_data: attestation_stats.num_attestation_data,
+        naive_attestations: naive_aggregation_pool.num_items(),
+        sync_contributions: op_pool.num_sync_contributions(),
+        naive_sync_contributions: naive_sync_aggregation_pool.num_items(),
+        attester_slashings: op_pool.num_attester_slashings(),
+        proposer_slashings: op_pool.num_proposer_slashings(),
+        voluntary_exits: op_pool.num_voluntary_exits(),
+        bls_to_execution_changes: bls_to_execution_changes.len(),
+    };
+
+    let slot_matches = |slot: Slot| query.slot.map_or(true, |query_slot| query_slot == slot);
+    let validator_matches = |validator_index: u64| {
+        query
+            .validator_index
+            .map_or(true, |query_index| query_index == validator_index)
+    };
+    let slashes_validator = |slashing: &AttesterSlashing<T::EthSpec>| {
+        query.validator_index.map_or(true, |query_index| {
+            slashing
+                .attestation_1()
+                .attesting_indices_iter()
+                .any(|index| *index == query_index)
+                && slashing
+                    .attestation_2()
+                    .attesting_indices_iter()
+                    .any(|index| *index == query_index)
+        })
+    };
+
+    let mut attestations = vec![];
+    if query.includes(OperationKind::Attestations) {
+        attestations = op_pool.get_filtered_attestations(|data| slot_matches(data.slot));
+        attestations.extend(
+            naive_aggregation_pool
+                .iter()
+                .filter(|attestation| slot_matches(attestation.data().slot))
+                .cloned(),
+        );
+    }
+
+    let mut sync_contributions = vec![];
+    if query.includes(OperationKind::SyncContributions) {
+        sync_contributions = op_pool.get_all_sync_contributions();
+        sync_contributions.extend(naive_sync_aggregation_pool.iter().cloned());
+        sync_contributions.retain(|contribution| slot_matches(contribution.slot));
+    }
+
+    let mut attester_slashings = vec![];
+    if query.includes(OperationKind::AttesterSlashings) {
+        attester_slashings = op_pool.get_all_attester_slashings();
+        attester_slashings.retain(slashes_validator);
+    }
+
+    let mut proposer_slashings = vec![];
+    if query.includes(OperationKind::ProposerSlashings) {
+        proposer_slashings = op_pool.get_all_proposer_slashings();
+        proposer_slashings.retain(|slashing| validator_matches(slashing.proposer_index()));
+    }
+
+    let mut voluntary_exits = vec![];
+    if query.includes(OperationKind::VoluntaryExits) {
+        voluntary_exits = op_pool.get_all_voluntary_exits();
+        voluntary_exits.retain(|exit| validator_matches(exit.message.validator_index));
+    }
+
+    let mut filtered_bls_to_execution_changes = vec![];
+    if query.includes(OperationKind::BlsToExecutionChanges) {
+        filtered_bls_to_execution_changes = bls_to_execution_changes;
+        filtered_bls_to_execution_changes
+            .retain(|change| validator_matches(change.message.validator_index));
+    }
+
+    OperationPoolContents {
+        stats,
+        attestations,
+        sync_contributions,
+        attester_slashings,
+        proposer_slashings,
+        voluntary_exits,
+        bls_to_execution_changes: filtered_bls_to_execution_changes,
+    }
+}
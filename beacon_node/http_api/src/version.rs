@@ -12,6 +12,7 @@ use types::{
 };
 use warp::reply::{self, Reply, Response};
 
+pub const V0: EndpointVersion = EndpointVersion(0);
 pub const V1: EndpointVersion = EndpointVersion(1);
 pub const V2: EndpointVersion = EndpointVersion(2);
 pub const V3: EndpointVersion = EndpointVersion(3);
@@ -0,0 +1,221 @@
+//! Tests for bearer-token authentication and per-token request budgets.
+use http_api::auth::{auth_filter, ApiAuth, ApiAuthConfig, ApiScope, ApiToken};
+use std::sync::Arc;
+use warp::http::{Method, StatusCode};
+use warp::Filter;
+
+fn token(name: &str, scopes: Vec<ApiScope>, requests_per_minute: Option<u32>) -> ApiToken {
+    ApiToken {
+        name: name.to_string(),
+        token: format!("{}-secret", name),
+        scopes,
+        requests_per_minute,
+    }
+}
+
+/// Returns the status of a request to a server which replies `200` to every authorized request.
+async fn status(
+    auth: &Arc<ApiAuth>,
+    method: Method,
+    path: &str,
+    token: Option<&str>,
+) -> StatusCode {
+    let routes = auth_filter(Some(auth.clone()))
+        .map(warp::reply)
+        .recover(warp_utils::reject::handle_rejection);
+    let mut request = warp::test::request().method(method.as_str()).path(path);
+    if let Some(token) = token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+    request.reply(&routes).await.status()
+}
+
+#[test]
+fn required_scopes() {
+    assert_eq!(
+        ApiScope::required_for(&Method::GET, "/eth/v1/node/version"),
+        ApiScope::ReadOnly
+    );
+    assert_eq!(
+        ApiScope::required_for(&Method::POST, "/eth/v1/beacon/pool/attestations"),
+        ApiScope::Validator
+    );
+    assert_eq!(
+        ApiScope::required_for(&Method::GET, "/eth/v3/validator/blocks/1"),
+        ApiScope::Validator
+    );
+    assert_eq!(
+        ApiScope::required_for(&Method::GET, "/lighthouse/health"),
+        ApiScope::Admin
+    );
+    assert_eq!(
+        ApiScope::required_for(&Method::POST, "/lighthouse/database/reconstruct"),
+        ApiScope::Admin
+    );
+}
+
+#[test]
+fn invalid_configs() {
+    let duplicate_name = ApiAuthConfig {
+        tokens: vec![
+            token("a", vec![], None),
+            ApiToken {
+                token: "other".to_string(),
+                ..token("a", vec![], None)
+            },
+        ],
+    };
+    assert!(duplicate_name.validate().is_err());
+
+    let duplicate_token = ApiAuthConfig {
+        tokens: vec![
+            token("a", vec![], None),
+            ApiToken {
+                name: "b".to_string(),
+                ..token("a", vec![], None)
+            },
+        ],
+    };
+    assert!(duplicate_token.validate().is_err());
+
+    let zero_budget = ApiAuthConfig {
+        tokens: vec![token("a", vec![], Some(0))],
+    };
+    assert!(zero_budget.validate().is_err());
+}
+
+#[tokio::test]
+async fn scopes_are_enforced() {
+    let auth = Arc::new(
+        ApiAuth::new(&ApiAuthConfig {
+            tokens: vec![
+                token("dashboard", vec![ApiScope::ReadOnly], None),
+                token("admin", vec![ApiScope::Admin], None),
+            ],
+        })
+        .unwrap(),
+    );
+
+    assert_eq!(
+        status(&auth, Method::GET, "/eth/v1/node/version", None).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        status(&auth, Method::GET, "/eth/v1/node/version", Some("wrong")).await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        status(
+            &auth,
+            Method::GET,
+            "/eth/v1/node/version",
+            Some("dashboard-secret")
+        )
+        .await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        status(
+            &auth,
+            Method::POST,
+            "/eth/v1/beacon/pool/attestations",
+            Some("dashboard-secret")
+        )
+        .await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        status(
+            &auth,
+            Method::GET,
+            "/lighthouse/health",
+            Some("dashboard-secret")
+        )
+        .await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        status(
+            &auth,
+            Method::GET,
+            "/lighthouse/health",
+            Some("admin-secret")
+        )
+        .await,
+        StatusCode::OK
+    );
+    // Scopes are not hierarchical.
+    assert_eq!(
+        status(
+            &auth,
+            Method::GET,
+            "/eth/v1/node/version",
+            Some("admin-secret")
+        )
+        .await,
+        StatusCode::FORBIDDEN
+    );
+}
+
+#[tokio::test]
+async fn budgets_are_enforced_per_token() {
+    let auth = Arc::new(
+        ApiAuth::new(&ApiAuthConfig {
+            tokens: vec![
+                token("limited", vec![ApiScope::ReadOnly], Some(2)),
+                token("unlimited", vec![ApiScope::ReadOnly], None),
+            ],
+        })
+        .unwrap(),
+    );
+
+    for _ in 0..2 {
+        assert_eq!(
+            status(
+                &auth,
+                Method::GET,
+                "/eth/v1/node/version",
+                Some("limited-secret")
+            )
+            .await,
+            StatusCode::OK
+        );
+    }
+    assert_eq!(
+        status(
+            &auth,
+            Method::GET,
+            "/eth/v1/node/version",
+            Some("limited-secret")
+        )
+        .await,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+
+    // Other tokens are unaffected.
+    for _ in 0..10 {
+        assert_eq!(
+            status(
+                &auth,
+                Method::GET,
+                "/eth/v1/node/version",
+                Some("unlimited-secret")
+            )
+            .await,
+            StatusCode::OK
+        );
+    }
+}
+
+#[tokio::test]
+async fn disabled_auth_accepts_everything() {
+    let routes = auth_filter(None)
+        .map(warp::reply)
+        .recover(warp_utils::reject::handle_rejection);
+    let response = warp::test::request()
+        .method("POST")
+        .path("/lighthouse/database/reconstruct")
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
@@ -1,5 +1,6 @@
 #![cfg(not(debug_assertions))] // Tests are too slow in debug.
 
+pub mod auth_tests;
 pub mod broadcast_validation_tests;
 pub mod fork_tests;
 pub mod interactive_tests;
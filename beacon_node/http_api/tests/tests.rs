@@ -5,6 +5,9 @@ use beacon_chain::{
 };
 use environment::null_logger;
 use eth2::{
+    lighthouse::{
+        RpcPeerMultiplier, RpcQuota, RpcRateLimits, RpcRateLimitsUpdate, RpcRejectedRequests,
+    },
     mixin::{RequestAccept, ResponseForkName, ResponseOptional},
     reqwest::RequestBuilder,
     types::{
@@ -23,8 +26,10 @@ use http_api::{
     test_utils::{create_api_server, ApiServer},
     BlockId, StateId,
 };
-use lighthouse_network::{types::SyncState, Enr, EnrExt, PeerId};
-use network::NetworkReceivers;
+use lighthouse_network::peer_manager::peer_policy::{PeerGroup, PeerMatcher};
+use lighthouse_network::rpc::{Protocol, Quota};
+use lighthouse_network::{types::SyncState, Enr, EnrExt, PeerId, PeerPolicy};
+use network::{NetworkMessage, NetworkReceivers};
 use proto_array::ExecutionStatus;
 use sensitive_url::SensitiveUrl;
 use slot_clock::SlotClock;
@@ -1324,6 +1329,127 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_beacon_proof_state(self) -> Self {
+        let paths = [
+            "slot",
+            "finalized_checkpoint.root",
+            "validators.3.effective_balance",
+            "balances.5",
+            "block_roots.1",
+            "slashings.2",
+        ]
+        .map(String::from);
+
+        for state_id in self.interesting_state_ids() {
+            let result = self
+                .client
+                .get_beacon_proof_state(state_id.0, &paths)
+                .await
+                .unwrap()
+                .map(|res| res.data);
+
+            let Ok((state, _execution_optimistic, _finalized)) = state_id.state(&self.chain) else {
+                assert_eq!(result, None, "{:?}", state_id);
+                continue;
+            };
+            let (root, _, _) = state_id.root(&self.chain).unwrap();
+            let data = result.unwrap();
+
+            assert_eq!(data.root, root, "{:?}", state_id);
+            assert_eq!(data.proofs.len(), paths.len());
+            for (proof, path) in data.proofs.iter().zip(&paths) {
+                assert_eq!(&proof.path, path);
+                assert!(proof.verify(root), "{:?} {}", state_id, path);
+            }
+            assert_eq!(data.proofs[0].leaf, state.slot().tree_hash_root());
+            assert_eq!(data.proofs[1].leaf, state.finalized_checkpoint().root);
+            assert_eq!(
+                data.proofs[2].leaf,
+                state
+                    .validators()
+                    .get(3)
+                    .unwrap()
+                    .effective_balance
+                    .tree_hash_root()
+            );
+            // Balances are packed four to a leaf.
+            assert_eq!(
+                data.proofs[3].leaf.as_bytes()[8..16],
+                state.balances().get(5).unwrap().to_le_bytes()
+            );
+            assert_eq!(data.proofs[4].leaf, *state.block_roots().get(1).unwrap());
+        }
+
+        for path in [
+            "",
+            "unknown",
+            "validators.1000000",
+            "validators.one",
+            "validators.1.unknown",
+            "finalized_checkpoint.root.0",
+        ] {
+            let error = self
+                .client
+                .get_beacon_proof_state(CoreStateId::Head, &[path.to_string()])
+                .await
+                .unwrap_err();
+            assert_eq!(error.status().unwrap(), 400, "{}", path);
+        }
+
+        self
+    }
+
+    pub async fn test_beacon_proof_block(self) -> Self {
+        let paths =
+            ["state_root", "body", "body.randao_reveal", "body.eth1_data"].map(String::from);
+
+        for block_id in self.interesting_block_ids() {
+            let result = self
+                .client
+                .get_beacon_proof_block(block_id.0, &paths)
+                .await
+                .unwrap()
+                .map(|res| res.data);
+
+            let Ok((root, _execution_optimistic, _finalized)) = block_id.root(&self.chain) else {
+                assert_eq!(result, None, "{:?}", block_id);
+                continue;
+            };
+            let Some(block) = self.chain.get_blinded_block(&root).unwrap() else {
+                assert_eq!(result, None, "{:?}", block_id);
+                continue;
+            };
+            // Requests for skipped slots return 404.
+            if let CoreBlockId::Slot(slot) = block_id.0 {
+                if block.slot() != slot {
+                    assert_eq!(result, None, "{:?}", block_id);
+                    continue;
+                }
+            }
+            let data = result.unwrap();
+
+            assert_eq!(data.root, root, "{:?}", block_id);
+            for (proof, path) in data.proofs.iter().zip(&paths) {
+                assert!(proof.verify(root), "{:?} {}", block_id, path);
+            }
+            assert_eq!(data.proofs[0].leaf, block.state_root());
+            assert_eq!(data.proofs[1].leaf, block.message().body_root());
+            assert_eq!(
+                data.proofs[3].leaf,
+                block.message().body().eth1_data().tree_hash_root()
+            );
+        }
+
+        let error = self
+            .client
+            .get_beacon_proof_block(CoreBlockId::Head, &["body.unknown".to_string()])
+            .await
+            .unwrap_err();
+        assert_eq!(error.status().unwrap(), 400);
+
+        self
+    }
+
     pub async fn test_post_beacon_blocks_valid(mut self) -> Self {
         let next_block = self.next_block.clone();
 
@@ -2997,7 +3123,7 @@ impl ApiTester {
 
             let (response, metadata) = self
                 .client
-                .get_validator_blocks_v3_ssz::<E>(slot, &randao_reveal, None, None)
+                .get_validator_blocks_v3_ssz::<E>(slot, &randao_reveal, None, None, None)
                 .await
                 .unwrap();
 
@@ -3892,7 +4018,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -3919,7 +4045,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, Some(0))
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, Some(0), None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -3946,7 +4072,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, Some(u64::MAX))
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, Some(u64::MAX), None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -3965,6 +4091,40 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_payload_v3_builder_bid_below_min_bid(self) -> Self {
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+
+        let (proposer_index, randao_reveal) = self.get_test_randao(slot, epoch).await;
+
+        // The builder bid would win with the maximum boost factor, but no bid can meet the minimum.
+        let (payload_type, metadata) = self
+            .client
+            .get_validator_blocks_v3::<E>(
+                slot,
+                &randao_reveal,
+                None,
+                Some(u64::MAX),
+                Some(u64::MAX),
+            )
+            .await
+            .unwrap();
+        Self::check_block_v3_metadata(&metadata, &payload_type);
+
+        let payload: FullPayload<E> = match payload_type.data {
+            ProduceBlockV3Response::Full(payload) => {
+                payload.block().body().execution_payload().unwrap().into()
+            }
+            ProduceBlockV3Response::Blinded(_) => panic!("Expecting a full payload"),
+        };
+
+        let expected_fee_recipient = Address::from_low_u64_be(proposer_index as u64);
+        assert_eq!(payload.fee_recipient(), expected_fee_recipient);
+        assert_eq!(payload.gas_limit(), 16_384);
+
+        self
+    }
+
     pub async fn test_payload_respects_registration(self) -> Self {
         let slot = self.chain.slot().unwrap();
         let epoch = self.chain.epoch().unwrap();
@@ -4051,7 +4211,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -4128,7 +4288,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -4219,7 +4379,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -4306,7 +4466,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -4393,7 +4553,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -4478,7 +4638,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -4535,7 +4695,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -4602,7 +4762,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -4711,7 +4871,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(next_slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(next_slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -4732,7 +4892,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(next_slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(next_slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -4861,7 +5021,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(next_slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(next_slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -4892,7 +5052,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(next_slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(next_slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -4973,7 +5133,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -5043,7 +5203,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -5056,6 +5216,119 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_builder_highest_relay_bid_chosen(self) -> Self {
+        // Start a second relay alongside the harness's mock builder.
+        let mock_el_url = SensitiveUrl::parse(
+            self.harness
+                .mock_execution_layer
+                .as_ref()
+                .unwrap()
+                .server
+                .url()
+                .as_str(),
+        )
+        .unwrap();
+        let beacon_url = SensitiveUrl::parse(self.client.as_ref()).unwrap();
+        let (second_builder, (addr, second_builder_server)) = MockBuilder::<E>::new_for_testing(
+            mock_el_url,
+            beacon_url,
+            self.harness.spec.clone(),
+            self.harness.runtime.task_executor.clone(),
+        );
+        self.harness.runtime.task_executor.spawn(
+            async move { second_builder_server.await },
+            "second_mock_builder_server",
+        );
+
+        let el = self.chain.execution_layer.as_ref().unwrap();
+        let first_url = el.builders()[0].server().clone();
+        let second_url = SensitiveUrl::parse(&format!("http://127.0.0.1:{}", addr.port())).unwrap();
+        el.set_builder_urls(vec![first_url, second_url], None, None)
+            .unwrap();
+
+        // Both relays beat the local payload, but the second relay bids the most.
+        self.mock_builder
+            .as_ref()
+            .unwrap()
+            .add_operation(Operation::Value(Uint256::from(
+                DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI + 1,
+            )));
+        second_builder.add_operation(Operation::Value(Uint256::from(
+            DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI + 2,
+        )));
+
+        // Registrations are forwarded to every relay.
+        let tester = self.test_post_validator_register_validator().await;
+
+        let fork = tester.chain.canonical_head.cached_head().head_fork();
+        let genesis_validators_root = tester.chain.genesis_validators_root;
+        let slot = tester.chain.slot().unwrap();
+        let epoch = tester.chain.epoch().unwrap();
+
+        let proposer_pubkey_bytes = tester
+            .client
+            .get_validator_duties_proposer(epoch)
+            .await
+            .unwrap()
+            .data
+            .into_iter()
+            .find(|duty| duty.slot == slot)
+            .map(|duty| duty.pubkey)
+            .unwrap();
+        let proposer_pubkey = (&proposer_pubkey_bytes).try_into().unwrap();
+        let sk = tester
+            .validator_keypairs()
+            .iter()
+            .find(|kp| kp.pk == proposer_pubkey)
+            .map(|kp| kp.sk.clone())
+            .unwrap();
+
+        let randao_reveal = {
+            let domain =
+                tester
+                    .chain
+                    .spec
+                    .get_domain(epoch, Domain::Randao, &fork, genesis_validators_root);
+            let message = epoch.signing_root(domain);
+            sk.sign(message).into()
+        };
+
+        let (payload_type, metadata) = tester
+            .client
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None, None)
+            .await
+            .unwrap();
+        Self::check_block_v3_metadata(&metadata, &payload_type);
+        assert_eq!(
+            metadata.execution_payload_value,
+            Uint256::from(DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI + 2)
+        );
+
+        let block = match payload_type.data {
+            ProduceBlockV3Response::Blinded(block) => block,
+            ProduceBlockV3Response::Full(_) => panic!("Expecting a blinded payload"),
+        };
+
+        // The payload is revealed by the winning relay when the signed block is published.
+        let signed_block = block.sign(&sk, &fork, genesis_validators_root, &tester.chain.spec);
+        tester
+            .client
+            .post_beacon_blinded_blocks(&signed_block)
+            .await
+            .unwrap();
+
+        let head_block = tester
+            .client
+            .get_beacon_blocks(CoreBlockId::Head)
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        assert_eq!(head_block.clone_as_blinded(), signed_block);
+
+        tester
+    }
+
     pub async fn test_local_payload_chosen_when_equally_profitable(self) -> Self {
         // Mutate value.
         self.mock_builder
@@ -5108,7 +5381,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -5173,7 +5446,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -5236,7 +5509,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -5309,7 +5582,7 @@ impl ApiTester {
 
         let (payload_type, metadata) = self
             .client
-            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None, None)
             .await
             .unwrap();
         Self::check_block_v3_metadata(&metadata, &payload_type);
@@ -5348,6 +5621,58 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_operation_pool(self) -> Self {
+        let contents = self
+            .client
+            .get_lighthouse_operation_pool::<E>(None, None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            contents.stats.attestations,
+            self.chain.op_pool.num_attestations()
+        );
+        assert_eq!(
+            contents.stats.voluntary_exits,
+            self.chain.op_pool.num_voluntary_exits()
+        );
+        assert_eq!(
+            contents.voluntary_exits,
+            self.chain.op_pool.get_all_voluntary_exits()
+        );
+        assert_eq!(
+            contents.proposer_slashings,
+            self.chain.op_pool.get_all_proposer_slashings()
+        );
+
+        // Filtering by kind removes the other kinds of contents, but not their stats.
+        let filtered = self
+            .client
+            .get_lighthouse_operation_pool::<E>(
+                Some(&[eth2::lighthouse::OperationKind::VoluntaryExits]),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(filtered.stats, contents.stats);
+        assert_eq!(filtered.voluntary_exits, contents.voluntary_exits);
+        assert!(filtered.attestations.is_empty());
+        assert!(filtered.proposer_slashings.is_empty());
+
+        // Filtering by a validator with no operations returns no exits.
+        let filtered = self
+            .client
+            .get_lighthouse_operation_pool::<E>(None, None, Some(u64::MAX))
+            .await
+            .unwrap();
+
+        assert!(filtered.voluntary_exits.is_empty());
+
+        self
+    }
+
     pub async fn test_get_lighthouse_validator_inclusion_global(self) -> Self {
         let epoch = self.chain.epoch().unwrap() - 1;
         self.client
@@ -5425,6 +5750,260 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_peers_bans(self) -> Self {
+        let bans = self.client.get_lighthouse_peers_bans().await.unwrap().data;
+        let expected = self
+            .ctx
+            .network_globals
+            .as_ref()
+            .unwrap()
+            .peers
+            .read()
+            .banned_peers()
+            .count();
+        assert_eq!(bans.len(), expected);
+        self
+    }
+
+    pub async fn test_post_lighthouse_peers_bans_clear(mut self) -> Self {
+        self.client
+            .post_lighthouse_peers_bans_clear()
+            .await
+            .unwrap();
+        assert!(matches!(
+            self.network_rx.network_recv.recv().await,
+            Some(NetworkMessage::ClearPeerBans)
+        ));
+        self
+    }
+
+    pub async fn test_get_lighthouse_peers_policy(self) -> Self {
+        let policy = self
+            .client
+            .get_lighthouse_peers_policy()
+            .await
+            .unwrap()
+            .data;
+        let expected = self
+            .ctx
+            .network_globals
+            .as_ref()
+            .unwrap()
+            .peers
+            .read()
+            .peer_policy()
+            .clone();
+        assert_eq!(policy, expected);
+        self
+    }
+
+    pub async fn test_post_lighthouse_peers_policy(mut self) -> Self {
+        let static_peer = PeerId::random();
+        let policy = PeerPolicy {
+            deny: PeerMatcher {
+                ip_ranges: vec!["10.0.0.0/8".parse().unwrap()],
+                agents: vec!["badclient".into()],
+                ..Default::default()
+            },
+            groups: vec![PeerGroup {
+                name: "partners".into(),
+                members: PeerMatcher::default(),
+                static_peers: vec![format!("/ip4/192.0.2.1/tcp/9000/p2p/{static_peer}")
+                    .parse()
+                    .unwrap()],
+                max_peers: Some(5),
+            }],
+        };
+
+        let client = &self.client;
+        let network_rx = &mut self.network_rx;
+        let (result, ()) = tokio::join!(client.post_lighthouse_peers_policy(&policy), async {
+            match network_rx.network_recv.recv().await {
+                Some(NetworkMessage::UpdatePeerPolicy {
+                    peer_policy,
+                    result_tx,
+                }) => {
+                    assert_eq!(peer_policy, policy);
+                    result_tx.send(Ok(())).unwrap();
+                }
+                other => panic!("unexpected network message: {:?}", other),
+            }
+        });
+        result.unwrap();
+
+        // Failing to write the policy is reported to the caller.
+        let (result, ()) = tokio::join!(client.post_lighthouse_peers_policy(&policy), async {
+            match network_rx.network_recv.recv().await {
+                Some(NetworkMessage::UpdatePeerPolicy { result_tx, .. }) => {
+                    result_tx.send(Err("disk full".into())).unwrap();
+                }
+                other => panic!("unexpected network message: {:?}", other),
+            }
+        });
+        assert_eq!(result.unwrap_err().status().map(Into::into), Some(500));
+
+        // Static peers must include their peer id.
+        let mut invalid_policy = policy;
+        invalid_policy.groups[0].static_peers = vec!["/ip4/192.0.2.1/tcp/9000".parse().unwrap()];
+        assert_eq!(
+            self.client
+                .post_lighthouse_peers_policy(&invalid_policy)
+                .await
+                .unwrap_err()
+                .status()
+                .map(Into::into),
+            Some(400)
+        );
+        self
+    }
+
+    pub async fn test_post_lighthouse_peers_policy_reload(mut self) -> Self {
+        self.client
+            .post_lighthouse_peers_policy_reload()
+            .await
+            .unwrap();
+        assert!(matches!(
+            self.network_rx.network_recv.recv().await,
+            Some(NetworkMessage::ReloadPeerPolicy)
+        ));
+        self
+    }
+
+    pub async fn test_get_lighthouse_rpc_rate_limits(self) -> Self {
+        let peer_id = PeerId::random();
+        {
+            let mut rate_limits = self
+                .ctx
+                .network_globals
+                .as_ref()
+                .unwrap()
+                .inbound_rate_limits
+                .write();
+            rate_limits.quotas = vec![(Protocol::BlocksByRange, Quota::n_every(1024, 10))];
+            rate_limits.peer_multipliers.insert(peer_id, 4.0);
+            rate_limits
+                .rejected_requests
+                .entry(peer_id)
+                .or_default()
+                .insert(Protocol::BlocksByRange, 3);
+        }
+
+        let rate_limits = self
+            .client
+            .get_lighthouse_rpc_rate_limits()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(
+            rate_limits,
+            RpcRateLimits {
+                enabled: true,
+                quotas: vec![RpcQuota {
+                    protocol: "beacon_blocks_by_range".into(),
+                    max_tokens: 1024,
+                    replenish_all_every: 10,
+                }],
+                peer_multipliers: vec![RpcPeerMultiplier {
+                    peer_id: peer_id.to_string(),
+                    multiplier: Some(4.0),
+                }],
+                rejected_requests: vec![RpcRejectedRequests {
+                    peer_id: peer_id.to_string(),
+                    protocol: "beacon_blocks_by_range".into(),
+                    count: 3,
+                }],
+            }
+        );
+        self
+    }
+
+    pub async fn test_post_lighthouse_rpc_rate_limits(mut self) -> Self {
+        let peer_id = PeerId::random();
+        let update = RpcRateLimitsUpdate {
+            quotas: vec![RpcQuota {
+                protocol: "beacon_blocks_by_range".into(),
+                max_tokens: 2048,
+                replenish_all_every: 10,
+            }],
+            peer_multipliers: vec![RpcPeerMultiplier {
+                peer_id: peer_id.to_string(),
+                multiplier: None,
+            }],
+        };
+
+        let client = &self.client;
+        let network_rx = &mut self.network_rx;
+        let (result, ()) = tokio::join!(client.post_lighthouse_rpc_rate_limits(&update), async {
+            match network_rx.network_recv.recv().await {
+                Some(NetworkMessage::UpdateInboundRateLimits {
+                    quotas,
+                    peer_multipliers,
+                    result_tx,
+                }) => {
+                    assert_eq!(
+                        quotas,
+                        vec![(Protocol::BlocksByRange, Quota::n_every(2048, 10))]
+                    );
+                    assert_eq!(peer_multipliers, vec![(peer_id, None)]);
+                    result_tx.send(Ok(())).unwrap();
+                }
+                other => panic!("unexpected network message: {:?}", other),
+            }
+        });
+        result.unwrap();
+
+        // Failing to apply the update is reported to the caller.
+        let (result, ()) = tokio::join!(client.post_lighthouse_rpc_rate_limits(&update), async {
+            match network_rx.network_recv.recv().await {
+                Some(NetworkMessage::UpdateInboundRateLimits { result_tx, .. }) => {
+                    result_tx
+                        .send(Err("Inbound rate limiting is disabled".into()))
+                        .unwrap();
+                }
+                other => panic!("unexpected network message: {:?}", other),
+            }
+        });
+        assert_eq!(result.unwrap_err().status().map(Into::into), Some(400));
+
+        let invalid_updates = [
+            RpcRateLimitsUpdate {
+                quotas: vec![RpcQuota {
+                    protocol: "unknown".into(),
+                    max_tokens: 1,
+                    replenish_all_every: 1,
+                }],
+                ..Default::default()
+            },
+            RpcRateLimitsUpdate {
+                quotas: vec![RpcQuota {
+                    protocol: "beacon_blocks_by_range".into(),
+                    max_tokens: 1,
+                    replenish_all_every: u64::MAX,
+                }],
+                ..Default::default()
+            },
+            RpcRateLimitsUpdate {
+                peer_multipliers: vec![RpcPeerMultiplier {
+                    peer_id: peer_id.to_string(),
+                    multiplier: Some(0.0),
+                }],
+                ..Default::default()
+            },
+        ];
+        for invalid_update in &invalid_updates {
+            assert_eq!(
+                self.client
+                    .post_lighthouse_rpc_rate_limits(invalid_update)
+                    .await
+                    .unwrap_err()
+                    .status()
+                    .map(Into::into),
+                Some(400)
+            );
+        }
+        self
+    }
+
     pub async fn test_post_lighthouse_liveness(self) -> Self {
         let epoch = self.chain.epoch().unwrap();
         let head_state = self.chain.head_beacon_state_cloned();
@@ -5982,6 +6561,10 @@ async fn beacon_get() {
         .await
         .test_beacon_blocks_root()
         .await
+        .test_beacon_proof_state()
+        .await
+        .test_beacon_proof_block()
+        .await
         .test_get_beacon_pool_attestations()
         .await
         .test_get_beacon_pool_attester_slashings()
@@ -6534,6 +7117,14 @@ async fn post_validator_max_builder_boost_factor() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn post_validator_builder_bid_below_min_bid() {
+    ApiTester::new_mev_tester()
+        .await
+        .test_payload_v3_builder_bid_below_min_bid()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn post_validator_register_valid_v3() {
     ApiTester::new_mev_tester()
@@ -6742,6 +7333,14 @@ async fn builder_payload_chosen_by_profit_v3() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_highest_relay_bid_chosen() {
+    ApiTester::new_mev_tester_default_payload_value()
+        .await
+        .test_builder_highest_relay_bid_chosen()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn builder_works_post_capella() {
     let mut config = ApiTesterConfig {
@@ -6822,6 +7421,8 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_proto_array()
         .await
+        .test_get_lighthouse_operation_pool()
+        .await
         .test_get_lighthouse_validator_inclusion()
         .await
         .test_get_lighthouse_validator_inclusion_global()
@@ -6838,6 +7439,20 @@ async fn lighthouse_endpoints() {
         .await
         .test_post_lighthouse_database_reconstruct()
         .await
+        .test_get_lighthouse_peers_bans()
+        .await
+        .test_post_lighthouse_peers_bans_clear()
+        .await
+        .test_get_lighthouse_peers_policy()
+        .await
+        .test_post_lighthouse_peers_policy()
+        .await
+        .test_post_lighthouse_peers_policy_reload()
+        .await
+        .test_get_lighthouse_rpc_rate_limits()
+        .await
+        .test_post_lighthouse_rpc_rate_limits()
+        .await
         .test_post_lighthouse_liveness()
         .await;
 }
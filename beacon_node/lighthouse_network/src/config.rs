@@ -19,6 +19,12 @@ pub const DEFAULT_IPV4_ADDRESS: Ipv4Addr = Ipv4Addr::UNSPECIFIED;
 pub const DEFAULT_TCP_PORT: u16 = 9000u16;
 pub const DEFAULT_DISC_PORT: u16 = 9000u16;
 pub const DEFAULT_QUIC_PORT: u16 = 9001u16;
+/// Default number of epochs an operation must be pending before it is re-published on gossip.
+pub const DEFAULT_OPERATION_REBROADCAST_EPOCHS: u64 = 4;
+/// Default maximum number of operations to re-publish in each round.
+pub const DEFAULT_OPERATION_REBROADCAST_LIMIT: usize = 64;
+/// Default maximum size of a network recording, in megabytes.
+pub const DEFAULT_NETWORK_RECORDING_MAX_SIZE_MB: u64 = 4096;
 
 /// The maximum size of gossip messages.
 pub fn gossip_max_size(is_merge_enabled: bool, gossip_max_size: usize) -> usize {
@@ -88,6 +94,17 @@ pub struct Config {
     /// Disables peer scoring altogether.
     pub disable_peer_scoring: bool,
 
+    /// A YAML file containing the peer policy, which denies peers and defines groups of static
+    /// peers.
+    pub peer_policy_file: Option<PathBuf>,
+
+    /// A file to record the gossip messages and RPC traffic received from peers to, so that it
+    /// can be replayed later.
+    pub network_recording_file: Option<PathBuf>,
+
+    /// The size in bytes at which recording network traffic stops.
+    pub network_recording_max_size: u64,
+
     /// Client version
     pub client_version: String,
 
@@ -138,6 +155,16 @@ pub struct Config {
 
     /// Configuration for the inbound rate limiter (requests received by this node).
     pub inbound_rate_limiter_config: Option<InboundRateLimiterConfig>,
+
+    /// Number of epochs a still-valid slashing, exit or address change must remain unincluded in
+    /// the op pool before it is re-published on gossip. This is also the minimum number of epochs
+    /// between re-publications of the same operation.
+    ///
+    /// If `None`, operations are never re-published.
+    pub operation_rebroadcast_epochs: Option<u64>,
+
+    /// Maximum number of operations to re-publish in each round.
+    pub operation_rebroadcast_limit: usize,
 }
 
 impl Config {
@@ -332,6 +359,9 @@ impl Default for Config {
             libp2p_nodes: vec![],
             trusted_peers: vec![],
             disable_peer_scoring: false,
+            peer_policy_file: None,
+            network_recording_file: None,
+            network_recording_max_size: DEFAULT_NETWORK_RECORDING_MAX_SIZE_MB * 1024 * 1024,
             client_version: lighthouse_version::version_with_platform(),
             disable_discovery: false,
             disable_quic_support: false,
@@ -348,6 +378,8 @@ impl Default for Config {
             outbound_rate_limiter_config: None,
             invalid_block_storage: None,
             inbound_rate_limiter_config: None,
+            operation_rebroadcast_epochs: Some(DEFAULT_OPERATION_REBROADCAST_EPOCHS),
+            operation_rebroadcast_limit: DEFAULT_OPERATION_REBROADCAST_LIMIT,
         }
     }
 }
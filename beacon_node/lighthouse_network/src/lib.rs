@@ -117,7 +117,9 @@ pub use libp2p::{core::ConnectedPoint, PeerId, Swarm};
 pub use libp2p::{multiaddr, Multiaddr};
 pub use metrics::scrape_discovery_metrics;
 pub use peer_manager::{
+    peer_policy::PeerPolicy,
     peerdb::client::Client,
+    peerdb::persisted_peer::PersistedPeer,
     peerdb::score::{PeerAction, ReportSource},
     peerdb::PeerDB,
     ConnectionDirection, PeerConnectionStatus, PeerInfo, PeerManager, SyncInfo, SyncStatus,
@@ -82,6 +82,12 @@ lazy_static! {
         "RPC requests total",
         &["type"]
     );
+    pub static ref TOTAL_RPC_RATE_LIMITED_REQUESTS_PER_CLIENT: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "libp2p_rpc_rate_limited_requests_per_client",
+            "Inbound RPC requests rejected by the rate limiter per protocol and client",
+            &["protocol", "client"]
+        );
     pub static ref PEER_ACTION_EVENTS_PER_CLIENT: Result<IntCounterVec> =
         try_create_int_counter_vec(
             "libp2p_peer_actions_per_client",
@@ -138,6 +144,14 @@ lazy_static! {
             &["type"]
         );
 
+    /*
+     * Network recording
+     */
+    pub static ref NETWORK_RECORDING_DROPPED_RECORDS: Result<IntCounter> = try_create_int_counter(
+        "libp2p_network_recording_dropped_records_total",
+        "Count of messages not recorded because the recording writer was falling behind"
+    );
+
     /*
      * Peer Reporting
      */
@@ -10,6 +10,8 @@ use delay_map::HashSetDelay;
 use discv5::Enr;
 use libp2p::identify::Info as IdentifyInfo;
 use lru_cache::LRUTimeCache;
+use peer_policy::PeerPolicy;
+use peerdb::persisted_peer::PersistedPeer;
 use peerdb::{BanOperation, BanResult, ScoreUpdateResult};
 use rand::seq::SliceRandom;
 use slog::{debug, error, trace, warn};
@@ -39,6 +41,7 @@ use strum::IntoEnumIterator;
 
 pub mod config;
 mod network_behaviour;
+pub mod peer_policy;
 
 /// The heartbeat performs regular updates such as updating reputations and performing discovery
 /// requests. This defines the interval in seconds.
@@ -65,6 +68,11 @@ pub const MIN_OUTBOUND_ONLY_FACTOR: f32 = 0.2;
 /// limit is 55, and we are at 55 peers, the following parameter provisions a few more slots of
 /// dialing priority peers we need for validator duties.
 pub const PRIORITY_PEER_EXCESS: f32 = 0.2;
+/// The delay before the first redial of a disconnected static peer. The delay doubles after each
+/// failed attempt, up to `STATIC_PEER_MAX_BACKOFF`.
+pub const STATIC_PEER_INITIAL_BACKOFF: Duration = Duration::from_secs(5);
+/// The maximum delay between redials of a static peer.
+pub const STATIC_PEER_MAX_BACKOFF: Duration = Duration::from_secs(300);
 
 /// The main struct that handles peer's reputation and connection status.
 pub struct PeerManager<E: EthSpec> {
@@ -82,6 +90,12 @@ pub struct PeerManager<E: EthSpec> {
     target_peers: usize,
     /// Peers queued to be dialed.
     peers_to_dial: Vec<Enr>,
+    /// Static peers from the peer policy queued to be dialed.
+    static_peers_to_dial: Vec<PeerId>,
+    /// Disconnected static peers awaiting a redial.
+    static_peer_redials: HashSetDelay<PeerId>,
+    /// The delay before the next redial of each disconnected static peer.
+    static_peer_backoff: HashMap<PeerId, Duration>,
     /// The number of temporarily banned peers. This is used to prevent instantaneous
     /// reconnection.
     // NOTE: This just prevents re-connections. The state of the peer is otherwise unaffected. A
@@ -164,6 +178,9 @@ impl<E: EthSpec> PeerManager<E> {
             network_globals,
             events: SmallVec::new(),
             peers_to_dial: Default::default(),
+            static_peers_to_dial: Default::default(),
+            static_peer_redials: HashSetDelay::new(STATIC_PEER_INITIAL_BACKOFF),
+            static_peer_backoff: Default::default(),
             inbound_ping_peers: HashSetDelay::new(Duration::from_secs(ping_interval_inbound)),
             outbound_ping_peers: HashSetDelay::new(Duration::from_secs(ping_interval_outbound)),
             status_peers: HashSetDelay::new(Duration::from_secs(status_interval)),
@@ -222,6 +239,63 @@ impl<E: EthSpec> PeerManager<E> {
         self.handle_score_action(peer_id, action, reason);
     }
 
+    /// Restores peers persisted before a restart, banning any peers which are still banned.
+    pub fn restore_peers(&mut self, persisted_peers: Vec<PersistedPeer>) {
+        let (ban_operations, purged_peers) = self
+            .network_globals
+            .peers
+            .write()
+            .restore_peers(persisted_peers);
+
+        for (peer_id, ban_operation) in ban_operations {
+            self.handle_ban_operation(&peer_id, ban_operation, None);
+        }
+        self.events.extend(
+            purged_peers
+                .into_iter()
+                .map(|(peer_id, unbanned_ips)| PeerManagerEvent::UnBanned(peer_id, unbanned_ips)),
+        );
+    }
+
+    /// Lifts the bans of all banned peers, returning the number of peers which were unbanned.
+    pub fn clear_bans(&mut self) -> usize {
+        let unbanned_peers = self.network_globals.peers.write().clear_bans();
+        let num_unbanned = unbanned_peers.len();
+        self.events.extend(
+            unbanned_peers
+                .into_iter()
+                .map(|(peer_id, unbanned_ips)| PeerManagerEvent::UnBanned(peer_id, unbanned_ips)),
+        );
+        num_unbanned
+    }
+
+    /// Replaces the peer policy. Peers which violate the new policy are disconnected and static
+    /// peers which are not connected are dialed.
+    pub fn set_peer_policy(&mut self, peer_policy: PeerPolicy) {
+        let static_peers = peer_policy.static_peer_ids();
+        let violating_peers = {
+            let mut peerdb = self.network_globals.peers.write();
+            peerdb.set_peer_policy(peer_policy);
+            peerdb.peers_violating_policy()
+        };
+
+        self.static_peer_backoff
+            .retain(|peer_id, _| static_peers.contains(peer_id));
+        for peer_id in static_peers {
+            if !self.static_peer_redials.contains_key(&peer_id)
+                && !self
+                    .network_globals
+                    .peers
+                    .read()
+                    .is_connected_or_dialing(&peer_id)
+            {
+                self.static_peer_redials.insert_at(peer_id, Duration::ZERO);
+            }
+        }
+
+        self.disconnect_violating_peers(violating_peers);
+    }
+
     /// Upon adjusting a Peer's score, there are times the peer manager must pass messages up to
     /// libp2p. This function handles the conditional logic associated with each score update
     /// result.
@@ -451,6 +525,8 @@ impl<E: EthSpec> PeerManager<E> {
     }
 
     /// Updates `PeerInfo` with `identify` information.
+    ///
+    /// Peers whose agent string is denied by the peer policy are disconnected.
     pub fn identify(&mut self, peer_id: &PeerId, info: &IdentifyInfo) {
         if let Some(peer_info) = self.network_globals.peers.write().peer_info_mut(peer_id) {
             let previous_kind = peer_info.client().kind;
@@ -472,6 +548,15 @@ impl<E: EthSpec> PeerManager<E> {
         } else {
             error!(self.log, "Received an Identify response from an unknown peer"; "peer_id" => peer_id.to_string());
         }
+
+        let denied = {
+            let peerdb = self.network_globals.peers.read();
+            peerdb.is_connected(peer_id) && peerdb.is_denied(peer_id)
+        };
+        if denied {
+            debug!(self.log, "Disconnecting peer denied by the peer policy"; "peer_id" => %peer_id, "agent_version" => &info.agent_version);
+            self.disconnect_peer(*peer_id, GoodbyeReason::Banned);
+        }
     }
 
     /// An error has occurred in the RPC.
@@ -520,7 +605,10 @@ impl<E: EthSpec> PeerManager<E> {
                 RPCResponseErrorCode::Unknown => PeerAction::HighToleranceError,
                 RPCResponseErrorCode::ResourceUnavailable => {
                     // Don't ban on this because we want to retry with a block by root request.
-                    if matches!(protocol, Protocol::BlobsByRoot) {
+                    if matches!(
+                        protocol,
+                        Protocol::BlobsByRoot | Protocol::DataColumnsByRoot
+                    ) {
                         return;
                     }
 
@@ -558,7 +646,10 @@ impl<E: EthSpec> PeerManager<E> {
                     Protocol::LightClientBootstrap => return,
                     Protocol::LightClientOptimisticUpdate => return,
                     Protocol::LightClientFinalityUpdate => return,
+                    Protocol::LightClientUpdatesByRange => return,
                     Protocol::BlobsByRoot => PeerAction::MidToleranceError,
+                    Protocol::DataColumnsByRoot => PeerAction::MidToleranceError,
+                    Protocol::DataColumnsByRange => PeerAction::MidToleranceError,
                     Protocol::Goodbye => PeerAction::LowToleranceError,
                     Protocol::MetaData => PeerAction::LowToleranceError,
                     Protocol::Status => PeerAction::LowToleranceError,
@@ -577,10 +668,13 @@ impl<E: EthSpec> PeerManager<E> {
                     Protocol::BlocksByRoot => return,
                     Protocol::BlobsByRange => return,
                     Protocol::BlobsByRoot => return,
+                    Protocol::DataColumnsByRoot => return,
+                    Protocol::DataColumnsByRange => return,
                     Protocol::Goodbye => return,
                     Protocol::LightClientBootstrap => return,
                     Protocol::LightClientOptimisticUpdate => return,
                     Protocol::LightClientFinalityUpdate => return,
+                    Protocol::LightClientUpdatesByRange => return,
                     Protocol::MetaData => PeerAction::Fatal,
                     Protocol::Status => PeerAction::Fatal,
                 }
@@ -597,9 +691,12 @@ impl<E: EthSpec> PeerManager<E> {
                     Protocol::BlocksByRoot => PeerAction::MidToleranceError,
                     Protocol::BlobsByRange => PeerAction::MidToleranceError,
                     Protocol::BlobsByRoot => PeerAction::MidToleranceError,
+                    Protocol::DataColumnsByRoot => PeerAction::MidToleranceError,
+                    Protocol::DataColumnsByRange => PeerAction::MidToleranceError,
                     Protocol::LightClientBootstrap => return,
                     Protocol::LightClientOptimisticUpdate => return,
                     Protocol::LightClientFinalityUpdate => return,
+                    Protocol::LightClientUpdatesByRange => return,
                     Protocol::Goodbye => return,
                     Protocol::MetaData => return,
                     Protocol::Status => return,
@@ -769,6 +866,8 @@ impl<E: EthSpec> PeerManager<E> {
                 .into_iter()
                 .map(|(peer_id, unbanned_ips)| PeerManagerEvent::UnBanned(peer_id, unbanned_ips)),
         );
+
+        self.schedule_static_peer_redial(peer_id);
     }
 
     /// Registers a peer as connected. The `ingoing` parameter determines if the peer is being
@@ -824,6 +923,53 @@ impl<E: EthSpec> PeerManager<E> {
             .notify_disconnecting(&peer_id, false);
     }
 
+    /// Gracefully disconnects peers which violate the peer policy.
+    fn disconnect_violating_peers(&mut self, violating_peers: Vec<(PeerId, GoodbyeReason)>) {
+        for (peer_id, reason) in violating_peers {
+            debug!(self.log, "Disconnecting peer which violates the peer policy"; "peer_id" => %peer_id, "reason" => %reason);
+            self.disconnect_peer(peer_id, reason);
+        }
+    }
+
+    /// Schedules a redial of a disconnected static peer. The delay doubles with each consecutive
+    /// attempt and is reset once the peer connects.
+    fn schedule_static_peer_redial(&mut self, peer_id: &PeerId) {
+        if !self
+            .network_globals
+            .peers
+            .read()
+            .peer_policy()
+            .is_static(peer_id)
+        {
+            return;
+        }
+        let backoff = self
+            .static_peer_backoff
+            .entry(*peer_id)
+            .or_insert(STATIC_PEER_INITIAL_BACKOFF);
+        self.static_peer_redials.insert_at(*peer_id, *backoff);
+        *backoff = (*backoff * 2).min(STATIC_PEER_MAX_BACKOFF);
+    }
+
+    /// Queues a static peer whose redial delay has elapsed to be dialed, unless it has since
+    /// connected or been removed from the peer policy. Static peers which may not be dialed, for
+    /// instance because they are banned, are tried again later.
+    fn redial_static_peer(&mut self, peer_id: PeerId) {
+        let should_dial = {
+            let peerdb = self.network_globals.peers.read();
+            if peerdb.is_connected_or_dialing(&peer_id) || !peerdb.peer_policy().is_static(&peer_id)
+            {
+                return;
+            }
+            peerdb.should_dial(&peer_id)
+        };
+        if should_dial {
+            self.static_peers_to_dial.push(peer_id);
+        } else {
+            self.schedule_static_peer_redial(&peer_id);
+        }
+    }
+
     /// Run discovery query for additional sync committee peers if we fall below `TARGET_PEERS`.
     fn maintain_sync_committee_peers(&mut self) {
         // Remove expired entries
@@ -943,6 +1089,14 @@ impl<E: EthSpec> PeerManager<E> {
         // Keep track of the number of outbound peers we are pruning.
         let mut outbound_peers_pruned = 0;
 
+        // Static peers are never pruned.
+        let static_peers = self
+            .network_globals
+            .peers
+            .read()
+            .peer_policy()
+            .static_peer_ids();
+
         macro_rules! prune_peers {
             ($filter: expr) => {
                 let filter = $filter;
@@ -952,8 +1106,11 @@ impl<E: EthSpec> PeerManager<E> {
                     .read()
                     .worst_connected_peers()
                     .iter()
-                    .filter(|(_, info)| {
-                        !info.has_future_duty() && !info.is_trusted() && filter(*info)
+                    .filter(|(peer_id, info)| {
+                        !info.has_future_duty()
+                            && !info.is_trusted()
+                            && !static_peers.contains(*peer_id)
+                            && filter(*info)
                     })
                 {
                     if peers_to_prune.len()
@@ -1003,8 +1160,11 @@ impl<E: EthSpec> PeerManager<E> {
             > = HashMap::new();
 
             for (peer_id, info) in self.network_globals.peers.read().connected_peers() {
-                // Ignore peers we trust or that we are already pruning
-                if info.is_trusted() || peers_to_prune.contains(peer_id) {
+                // Ignore peers we trust, static peers or peers that we are already pruning
+                if info.is_trusted()
+                    || static_peers.contains(peer_id)
+                    || peers_to_prune.contains(peer_id)
+                {
                     continue;
                 }
 
@@ -1175,6 +1335,11 @@ impl<E: EthSpec> PeerManager<E> {
         // Maintain minimum count for sync committee peers.
         self.maintain_sync_committee_peers();
 
+        // Disconnect peers which are denied by the peer policy or exceed the limit of a group. This
+        // includes peers which only joined a group once their client was identified.
+        let violating_peers = self.network_globals.peers.read().peers_violating_policy();
+        self.disconnect_violating_peers(violating_peers);
+
         // Prune any excess peers back to our target in such a way that incentivises good scores and
         // a uniform distribution of subnets.
         self.prune_excess_peers();
@@ -2214,6 +2379,47 @@ mod tests {
         assert!(connected_peers.contains(&peers[7]));
     }
 
+    #[tokio::test]
+    async fn test_peer_manager_static_peer_redial_backoff() {
+        use peer_policy::{PeerGroup, PeerMatcher};
+
+        let mut peer_manager = build_peer_manager(3).await;
+        let static_peer = PeerId::random();
+        peer_manager.set_peer_policy(PeerPolicy {
+            deny: PeerMatcher::default(),
+            groups: vec![PeerGroup {
+                name: "static".into(),
+                members: PeerMatcher::default(),
+                static_peers: vec![format!("/ip4/192.0.2.1/tcp/9000/p2p/{static_peer}")
+                    .parse()
+                    .unwrap()],
+                max_peers: None,
+            }],
+        });
+        // New static peers are dialed straight away.
+        assert!(peer_manager.static_peer_redials.contains_key(&static_peer));
+
+        // Each failed dial doubles the delay before the next attempt.
+        peer_manager.inject_disconnect(&static_peer);
+        assert_eq!(
+            peer_manager.static_peer_backoff.get(&static_peer),
+            Some(&(STATIC_PEER_INITIAL_BACKOFF * 2))
+        );
+        for _ in 0..10 {
+            peer_manager.inject_disconnect(&static_peer);
+        }
+        assert_eq!(
+            peer_manager.static_peer_backoff.get(&static_peer),
+            Some(&STATIC_PEER_MAX_BACKOFF)
+        );
+
+        // Peers removed from the policy are no longer redialed.
+        peer_manager.set_peer_policy(PeerPolicy::default());
+        assert!(peer_manager.static_peer_backoff.is_empty());
+        peer_manager.inject_disconnect(&static_peer);
+        assert!(peer_manager.static_peer_backoff.is_empty());
+    }
+
     // Test properties PeerManager should have using randomly generated input.
     #[cfg(test)]
     mod property_based_tests {
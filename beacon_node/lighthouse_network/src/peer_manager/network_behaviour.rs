@@ -18,6 +18,7 @@ use crate::rpc::GoodbyeReason;
 use crate::types::SyncState;
 use crate::{metrics, ClearDialError};
 
+use super::peerdb::BanResult;
 use super::{ConnectingType, PeerManager, PeerManagerEvent};
 
 impl<E: EthSpec> NetworkBehaviour for PeerManager<E> {
@@ -86,12 +87,42 @@ impl<E: EthSpec> NetworkBehaviour for PeerManager<E> {
             }
         }
 
+        loop {
+            match self.static_peer_redials.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(peer_id))) => self.redial_static_peer(peer_id),
+                Poll::Ready(Some(Err(e))) => {
+                    error!(self.log, "Failed to check for static peers to redial"; "error" => e.to_string())
+                }
+                Poll::Ready(None) | Poll::Pending => break,
+            }
+        }
+
         if !self.events.is_empty() {
             return Poll::Ready(ToSwarm::GenerateEvent(self.events.remove(0)));
         } else {
             self.events.shrink_to_fit();
         }
 
+        if let Some(peer_id) = self.static_peers_to_dial.pop() {
+            let multiaddrs = self
+                .network_globals
+                .peers
+                .read()
+                .peer_policy()
+                .static_peers()
+                .remove(&peer_id)
+                .unwrap_or_default();
+            debug!(self.log, "Dialing static peer"; "peer_id" => %peer_id, "multiaddrs" => ?multiaddrs);
+            self.inject_peer_connection(&peer_id, ConnectingType::Dialing, None);
+
+            return Poll::Ready(ToSwarm::Dial {
+                opts: DialOpts::peer_id(peer_id)
+                    .condition(PeerCondition::Disconnected)
+                    .addresses(multiaddrs)
+                    .build(),
+            });
+        }
+
         if let Some(enr) = self.peers_to_dial.pop() {
             let peer_id = enr.peer_id();
             self.inject_peer_connection(&peer_id, ConnectingType::Dialing, Some(enr.clone()));
@@ -172,14 +203,10 @@ impl<E: EthSpec> NetworkBehaviour for PeerManager<E> {
         remote_addr: &libp2p::Multiaddr,
     ) -> Result<(), ConnectionDenied> {
         // get the IP address to verify it's not banned.
-        let ip = match remote_addr.iter().next() {
-            Some(libp2p::multiaddr::Protocol::Ip6(ip)) => IpAddr::V6(ip),
-            Some(libp2p::multiaddr::Protocol::Ip4(ip)) => IpAddr::V4(ip),
-            _ => {
-                return Err(ConnectionDenied::new(format!(
-                    "Connection to peer rejected: invalid multiaddr: {remote_addr}"
-                )))
-            }
+        let Some(ip) = multiaddr_ip(remote_addr) else {
+            return Err(ConnectionDenied::new(format!(
+                "Connection to peer rejected: invalid multiaddr: {remote_addr}"
+            )));
         };
 
         if self.network_globals.peers.read().is_ip_banned(&ip) {
@@ -188,6 +215,12 @@ impl<E: EthSpec> NetworkBehaviour for PeerManager<E> {
             )));
         }
 
+        if self.network_globals.peers.read().is_ip_denied(&ip) {
+            return Err(ConnectionDenied::new(format!(
+                "Connection to peer rejected: peer {ip} is denied by the peer policy"
+            )));
+        }
+
         Ok(())
     }
 
@@ -216,6 +249,12 @@ impl<E: EthSpec> NetworkBehaviour for PeerManager<E> {
         _role_override: libp2p::core::Endpoint,
     ) -> Result<libp2p::swarm::THandler<Self>, libp2p::swarm::ConnectionDenied> {
         trace!(self.log, "Outbound connection"; "peer_id" => %peer_id, "multiaddr" => %addr);
+        if multiaddr_ip(addr).map_or(false, |ip| {
+            self.network_globals.peers.read().is_ip_denied(&ip)
+        }) {
+            debug!(self.log, "Connected a peer denied by the peer policy. Rejecting connection"; "peer_id" => %peer_id, "multiaddr" => %addr);
+            return Err(ConnectionDenied::new(BanResult::Denied));
+        }
         match self.ban_status(&peer_id) {
             Some(cause) => {
                 error!(self.log, "Connected a banned peer. Rejecting connection"; "peer_id" => %peer_id);
@@ -251,8 +290,16 @@ impl<E: EthSpec> PeerManager<E> {
 
         // Count dialing peers in the limit if the peer dialed us.
         let count_dialing = endpoint.is_listener();
+        // Static peers are always allowed to connect.
+        let is_static = self
+            .network_globals
+            .peers
+            .read()
+            .peer_policy()
+            .is_static(&peer_id);
         // Check the connection limits
         if self.peer_limit_reached(count_dialing)
+            && !is_static
             && self
                 .network_globals
                 .peers
@@ -279,6 +326,22 @@ impl<E: EthSpec> PeerManager<E> {
                     .push(PeerManagerEvent::PeerConnectedOutgoing(peer_id));
             }
         };
+
+        // Reset the redial backoff of a static peer now that it has connected.
+        self.static_peer_backoff.remove(&peer_id);
+        self.static_peer_redials.remove(&peer_id);
+
+        // The peer's address is now known, so check the limits of the groups it belongs to.
+        let limited_group = self
+            .network_globals
+            .peers
+            .read()
+            .group_limit_reached(&peer_id)
+            .map(str::to_owned);
+        if let Some(group) = limited_group {
+            debug!(self.log, "Peer group limit reached"; "peer_id" => %peer_id, "group" => group);
+            self.disconnect_peer(peer_id, GoodbyeReason::TooManyPeers);
+        }
     }
 
     fn on_connection_closed(
@@ -334,3 +397,12 @@ impl<E: EthSpec> PeerManager<E> {
         }
     }
 }
+
+/// The IP address at the start of a multiaddr, if any.
+fn multiaddr_ip(addr: &libp2p::Multiaddr) -> Option<IpAddr> {
+    match addr.iter().next() {
+        Some(libp2p::multiaddr::Protocol::Ip6(ip)) => Some(IpAddr::V6(ip)),
+        Some(libp2p::multiaddr::Protocol::Ip4(ip)) => Some(IpAddr::V4(ip)),
+        _ => None,
+    }
+}
@@ -0,0 +1,511 @@
+//! An operator-defined policy which denies peers and maintains groups of static peers.
+//!
+//! The policy is read from a YAML file and can be replaced at runtime. For example:
+//!
+//! ```yaml
+//! deny:
+//!   peer_ids: ["16Uiu2HAm..."]
+//!   ip_ranges: ["10.0.0.0/8", "2001:db8::/32"]
+//!   agents: ["badclient"]
+//! groups:
+//!   - name: partners
+//!     static_peers: ["/ip4/192.0.2.1/tcp/9000/p2p/16Uiu2HAm..."]
+//!     ip_ranges: ["192.0.2.0/24"]
+//!     max_peers: 5
+//! ```
+
+use super::peerdb::peer_info::PeerInfo;
+use crate::{multiaddr::Protocol, Multiaddr, PeerId, PeerIdSerialized};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::{self, File};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use types::EthSpec;
+
+/// A range of IP addresses in CIDR notation, e.g. `10.0.0.0/8`.
+///
+/// An address without a prefix length matches only that address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpRange {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpRange {
+    /// Returns true if `ip` lies within the range. IPv4-mapped IPv6 addresses are matched against
+    /// both IPv6 and IPv4 ranges.
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        self.contains_exact(*ip)
+            || match ip {
+                IpAddr::V6(v6) => v6
+                    .to_ipv4_mapped()
+                    .map_or(false, |v4| self.contains_exact(IpAddr::V4(v4))),
+                IpAddr::V4(_) => false,
+            }
+    }
+
+    /// Returns true if `ip` lies within the range, without converting between address families.
+    fn contains_exact(&self, ip: IpAddr) -> bool {
+        match (self.network, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - u32::from(self.prefix_len))
+                    .unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - u32::from(self.prefix_len))
+                    .unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for IpRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (network, prefix_len) = match s.split_once('/') {
+            Some((network, prefix_len)) => (network, Some(prefix_len)),
+            None => (s, None),
+        };
+        let network =
+            IpAddr::from_str(network).map_err(|e| format!("Invalid IP range {}: {}", s, e))?;
+        let max_prefix_len = if network.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => prefix_len
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix_len| *prefix_len <= max_prefix_len)
+                .ok_or_else(|| format!("Invalid prefix length in IP range {}", s))?,
+            None => max_prefix_len,
+        };
+        Ok(Self {
+            network,
+            prefix_len,
+        })
+    }
+}
+
+impl fmt::Display for IpRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix_len)
+    }
+}
+
+impl Serialize for IpRange {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for IpRange {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serializes a list of peer ids as their base58 representations.
+mod serde_peer_ids {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(peer_ids: &[PeerId], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(peer_ids.iter().map(|peer_id| peer_id.to_string()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<PeerId>, D::Error> {
+        Ok(Vec::<PeerIdSerialized>::deserialize(deserializer)?
+            .into_iter()
+            .map(PeerId::from)
+            .collect())
+    }
+}
+
+/// Selects peers by their id, the IP addresses they have been seen on, or their agent string.
+///
+/// A peer is selected if it matches any of the entries.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PeerMatcher {
+    #[serde(default, with = "serde_peer_ids")]
+    pub peer_ids: Vec<PeerId>,
+    #[serde(default)]
+    pub ip_ranges: Vec<IpRange>,
+    /// Case-insensitive substrings of the agent string reported by the peer's identify protocol.
+    #[serde(default)]
+    pub agents: Vec<String>,
+}
+
+impl PeerMatcher {
+    /// Returns true if the IP address lies within one of the IP ranges.
+    pub fn matches_ip(&self, ip: &IpAddr) -> bool {
+        self.ip_ranges.iter().any(|range| range.contains(ip))
+    }
+
+    /// Returns true if the agent string contains one of the agent entries.
+    pub fn matches_agent(&self, agent_string: &str) -> bool {
+        let agent_string = agent_string.to_lowercase();
+        self.agents
+            .iter()
+            .any(|agent| agent_string.contains(&agent.to_lowercase()))
+    }
+
+    /// Returns true if the peer, or any of the information known about it, matches an entry.
+    pub fn matches<E: EthSpec>(&self, peer_id: &PeerId, info: Option<&PeerInfo<E>>) -> bool {
+        if self.peer_ids.contains(peer_id) {
+            return true;
+        }
+        info.map_or(false, |info| {
+            info.seen_ip_addresses().any(|ip| self.matches_ip(&ip))
+                || info
+                    .client()
+                    .agent_string
+                    .as_ref()
+                    .map_or(false, |agent_string| self.matches_agent(agent_string))
+        })
+    }
+}
+
+/// A named group of peers, which may be limited in number and may contain static peers that are
+/// always dialed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "PeerGroupFields")]
+pub struct PeerGroup {
+    pub name: String,
+    /// Selects the peers which belong to the group, in addition to its static peers.
+    #[serde(flatten)]
+    pub members: PeerMatcher,
+    /// Peers which are dialed at startup and redialed whenever they disconnect. Each address must
+    /// end with the `/p2p/<peer-id>` of the peer.
+    #[serde(default)]
+    pub static_peers: Vec<Multiaddr>,
+    /// The maximum number of connected peers from the group. Static peers are always allowed to
+    /// connect but count towards the limit.
+    #[serde(default)]
+    pub max_peers: Option<usize>,
+}
+
+/// The fields of a `PeerGroup` as they appear in the policy file.
+///
+/// `deny_unknown_fields` can't be combined with the flattened `PeerGroup::members`, so the group
+/// is deserialized from this explicit set of keys instead.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PeerGroupFields {
+    name: String,
+    #[serde(default, with = "serde_peer_ids")]
+    peer_ids: Vec<PeerId>,
+    #[serde(default)]
+    ip_ranges: Vec<IpRange>,
+    #[serde(default)]
+    agents: Vec<String>,
+    #[serde(default)]
+    static_peers: Vec<Multiaddr>,
+    #[serde(default)]
+    max_peers: Option<usize>,
+}
+
+impl From<PeerGroupFields> for PeerGroup {
+    fn from(fields: PeerGroupFields) -> Self {
+        Self {
+            name: fields.name,
+            members: PeerMatcher {
+                peer_ids: fields.peer_ids,
+                ip_ranges: fields.ip_ranges,
+                agents: fields.agents,
+            },
+            static_peers: fields.static_peers,
+            max_peers: fields.max_peers,
+        }
+    }
+}
+
+impl PeerGroup {
+    /// Returns true if the peer is one of the group's static peers or matches its members.
+    pub fn contains<E: EthSpec>(&self, peer_id: &PeerId, info: Option<&PeerInfo<E>>) -> bool {
+        self.static_peers
+            .iter()
+            .any(|addr| static_peer_id(addr).as_ref() == Some(peer_id))
+            || self.members.matches(peer_id, info)
+    }
+}
+
+/// A policy which denies peers from connecting and defines groups of peers.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PeerPolicy {
+    /// Peers which are refused connections and are never dialed.
+    #[serde(default)]
+    pub deny: PeerMatcher,
+    #[serde(default)]
+    pub groups: Vec<PeerGroup>,
+}
+
+impl PeerPolicy {
+    /// Reads and validates a policy from a YAML file.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let file = File::open(path)
+            .map_err(|e| format!("Unable to open peer policy file {:?}: {}", path, e))?;
+        let policy: Self = serde_yaml::from_reader(file)
+            .map_err(|e| format!("Unable to parse peer policy file {:?}: {}", path, e))?;
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    /// Writes the policy to a YAML file, replacing its contents.
+    ///
+    /// The policy is first written to a temporary file which is then renamed over `path`, so that
+    /// a crash never leaves the file partially written.
+    pub fn to_file(&self, path: &Path) -> Result<(), String> {
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+
+        let mut file = File::create(&temp_path)
+            .map_err(|e| format!("Unable to create peer policy file {:?}: {}", temp_path, e))?;
+        serde_yaml::to_writer(&mut file, self)
+            .map_err(|e| format!("Unable to write peer policy file {:?}: {}", temp_path, e))?;
+        file.sync_all()
+            .map_err(|e| format!("Unable to sync peer policy file {:?}: {}", temp_path, e))?;
+        fs::rename(&temp_path, path)
+            .map_err(|e| format!("Unable to replace peer policy file {:?}: {}", path, e))
+    }
+
+    /// Checks that group names are unique and that static peers have a peer id and are not
+    /// denied.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut names = HashSet::new();
+        for group in &self.groups {
+            if !names.insert(group.name.as_str()) {
+                return Err(format!("Duplicate peer group name: {}", group.name));
+            }
+            for addr in &group.static_peers {
+                let peer_id = static_peer_id(addr).ok_or_else(|| {
+                    format!(
+                        "Static peer {} in group {} does not end with /p2p/<peer-id>",
+                        addr, group.name
+                    )
+                })?;
+                if self.deny.peer_ids.contains(&peer_id) {
+                    return Err(format!(
+                        "Static peer {} in group {} is denied",
+                        peer_id, group.name
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if the peer is denied by the policy.
+    pub fn is_denied<E: EthSpec>(&self, peer_id: &PeerId, info: Option<&PeerInfo<E>>) -> bool {
+        self.deny.matches(peer_id, info)
+    }
+
+    /// Returns true if the peer is a static peer of any group.
+    pub fn is_static(&self, peer_id: &PeerId) -> bool {
+        self.static_peer_ids().contains(peer_id)
+    }
+
+    /// The ids of all static peers.
+    pub fn static_peer_ids(&self) -> HashSet<PeerId> {
+        self.static_peer_addresses()
+            .map(|(peer_id, _)| peer_id)
+            .collect()
+    }
+
+    /// The static peers of all groups along with the addresses they are dialed on.
+    pub fn static_peers(&self) -> HashMap<PeerId, Vec<Multiaddr>> {
+        let mut static_peers: HashMap<PeerId, Vec<Multiaddr>> = HashMap::new();
+        for (peer_id, addr) in self.static_peer_addresses() {
+            static_peers.entry(peer_id).or_default().push(addr);
+        }
+        static_peers
+    }
+
+    /// The groups which limit their number of peers.
+    pub fn limited_groups(&self) -> impl Iterator<Item = (&PeerGroup, usize)> {
+        self.groups
+            .iter()
+            .filter_map(|group| group.max_peers.map(|max_peers| (group, max_peers)))
+    }
+
+    /// Iterates over the static peer addresses, with the trailing `/p2p/<peer-id>` removed.
+    fn static_peer_addresses(&self) -> impl Iterator<Item = (PeerId, Multiaddr)> + '_ {
+        self.groups
+            .iter()
+            .flat_map(|group| group.static_peers.iter())
+            .filter_map(|addr| {
+                let peer_id = static_peer_id(addr)?;
+                let mut addr = addr.clone();
+                addr.pop();
+                Some((peer_id, addr))
+            })
+    }
+}
+
+/// The peer id at the end of a static peer's address.
+fn static_peer_id(addr: &Multiaddr) -> Option<PeerId> {
+    match addr.iter().last() {
+        Some(Protocol::P2p(peer_id)) => Some(peer_id),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MinimalEthSpec as E;
+
+    #[test]
+    fn ip_range_contains() {
+        let range: IpRange = "10.1.0.0/16".parse().unwrap();
+        assert!(range.contains(&"10.1.2.3".parse().unwrap()));
+        assert!(!range.contains(&"10.2.0.1".parse().unwrap()));
+        assert!(range.contains(&"::ffff:10.1.0.1".parse().unwrap()));
+
+        // IPv4-mapped addresses also match IPv6 ranges.
+        let mapped: IpRange = "::ffff:0:0/96".parse().unwrap();
+        assert!(mapped.contains(&"::ffff:10.1.0.1".parse().unwrap()));
+        assert!(!mapped.contains(&"10.1.0.1".parse().unwrap()));
+        assert!(!mapped.contains(&"2001:db8::1".parse().unwrap()));
+
+        let range: IpRange = "2001:db8::/32".parse().unwrap();
+        assert!(range.contains(&"2001:db8::1".parse().unwrap()));
+        assert!(!range.contains(&"2001:db9::1".parse().unwrap()));
+        assert!(!range.contains(&"10.1.2.3".parse().unwrap()));
+
+        let any: IpRange = "0.0.0.0/0".parse().unwrap();
+        assert!(any.contains(&"192.0.2.1".parse().unwrap()));
+
+        let single: IpRange = "192.0.2.1".parse().unwrap();
+        assert_eq!(single.to_string(), "192.0.2.1/32");
+        assert!(!single.contains(&"192.0.2.2".parse().unwrap()));
+
+        assert!("10.0.0.0/33".parse::<IpRange>().is_err());
+        assert!("not-an-ip/8".parse::<IpRange>().is_err());
+    }
+
+    #[test]
+    fn parse_and_validate_policy() {
+        let denied = PeerId::random();
+        let static_peer = PeerId::random();
+        let yaml = format!(
+            r#"
+deny:
+  peer_ids: ["{denied}"]
+  agents: ["BadClient"]
+groups:
+  - name: partners
+    static_peers: ["/ip4/192.0.2.1/tcp/9000/p2p/{static_peer}"]
+    ip_ranges: ["192.0.2.0/24"]
+    max_peers: 2
+"#
+        );
+        let policy: PeerPolicy = serde_yaml::from_str(&yaml).unwrap();
+        policy.validate().unwrap();
+
+        assert!(policy.is_denied::<E>(&denied, None));
+        assert!(!policy.is_denied::<E>(&static_peer, None));
+        assert!(policy.deny.matches_agent("badclient/v1.0.0"));
+        assert!(policy.is_static(&static_peer));
+        assert_eq!(
+            policy.static_peers().get(&static_peer),
+            Some(&vec!["/ip4/192.0.2.1/tcp/9000".parse().unwrap()])
+        );
+        assert!(policy.groups[0].contains::<E>(&static_peer, None));
+        assert_eq!(policy.limited_groups().count(), 1);
+
+        // The policy survives a round trip through the file format.
+        let reparsed: PeerPolicy =
+            serde_yaml::from_str(&serde_yaml::to_string(&policy).unwrap()).unwrap();
+        assert_eq!(reparsed, policy);
+    }
+
+    #[test]
+    fn unknown_policy_keys() {
+        // A typo of `ip_ranges` in a group, the deny list and the top level.
+        let group_typo = r#"
+groups:
+  - name: partners
+    ip_range: ["192.0.2.0/24"]
+"#;
+        let deny_typo = r#"
+deny:
+  ip_range: ["192.0.2.0/24"]
+"#;
+        let policy_typo = r#"
+ip_range: ["192.0.2.0/24"]
+"#;
+        for yaml in [group_typo, deny_typo, policy_typo] {
+            let err = serde_yaml::from_str::<PeerPolicy>(yaml).unwrap_err();
+            assert!(
+                err.to_string().contains("unknown field `ip_range`"),
+                "unexpected error: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn write_policy_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("peer_policy.yaml");
+        fs::write(&path, "groups: [").unwrap();
+
+        let policy = PeerPolicy {
+            deny: PeerMatcher {
+                peer_ids: vec![PeerId::random()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        policy.to_file(&path).unwrap();
+        assert_eq!(PeerPolicy::from_file(&path).unwrap(), policy);
+        // Only the policy file remains.
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn invalid_policies() {
+        let peer_id = PeerId::random();
+        let group = |static_peers: Vec<Multiaddr>| PeerGroup {
+            name: "group".into(),
+            members: PeerMatcher::default(),
+            static_peers,
+            max_peers: None,
+        };
+
+        let policy = PeerPolicy {
+            groups: vec![group(vec!["/ip4/192.0.2.1/tcp/9000".parse().unwrap()])],
+            ..Default::default()
+        };
+        assert!(policy.validate().is_err());
+
+        let addr: Multiaddr = format!("/ip4/192.0.2.1/tcp/9000/p2p/{peer_id}")
+            .parse()
+            .unwrap();
+        let policy = PeerPolicy {
+            groups: vec![group(vec![addr.clone()]), group(vec![])],
+            ..Default::default()
+        };
+        assert!(policy.validate().is_err());
+
+        let policy = PeerPolicy {
+            deny: PeerMatcher {
+                peer_ids: vec![peer_id],
+                ..Default::default()
+            },
+            groups: vec![group(vec![addr])],
+        };
+        assert!(policy.validate().is_err());
+    }
+}
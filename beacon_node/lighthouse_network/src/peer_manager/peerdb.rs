@@ -1,6 +1,9 @@
+use super::peer_policy::PeerPolicy;
 use crate::discovery::CombinedKey;
+use crate::rpc::GoodbyeReason;
 use crate::{metrics, multiaddr::Multiaddr, types::Subnet, Enr, Gossipsub, PeerId};
 use peer_info::{ConnectionDirection, PeerConnectionStatus, PeerInfo};
+use persisted_peer::PersistedPeer;
 use rand::seq::SliceRandom;
 use score::{PeerAction, ReportSource, Score, ScoreState};
 use slog::{crit, debug, error, trace, warn};
@@ -16,6 +19,7 @@ use types::EthSpec;
 
 pub mod client;
 pub mod peer_info;
+pub mod persisted_peer;
 pub mod score;
 pub mod sync_status;
 
@@ -42,6 +46,8 @@ pub struct PeerDB<E: EthSpec> {
     banned_peers_count: BannedPeersCount,
     /// Specifies if peer scoring is disabled.
     disable_peer_scoring: bool,
+    /// The operator-defined policy which denies peers and limits groups of peers.
+    peer_policy: PeerPolicy,
     /// PeerDB's logger
     log: slog::Logger,
 }
@@ -58,12 +64,18 @@ impl<E: EthSpec> PeerDB<E> {
             disconnected_peers: 0,
             banned_peers_count: BannedPeersCount::default(),
             disable_peer_scoring,
+            peer_policy: PeerPolicy::default(),
             peers,
         }
     }
 
     /* Getters */
 
+    /// The current peer policy.
+    pub fn peer_policy(&self) -> &PeerPolicy {
+        &self.peer_policy
+    }
+
     /// Gives the score of a peer, or default score if it is unknown.
     pub fn score(&self, peer_id: &PeerId) -> f64 {
         self.peers
@@ -129,6 +141,7 @@ impl<E: EthSpec> PeerDB<E> {
                 | Some(PeerConnectionStatus::Unknown { .. })
                 | None
         ) && !self.score_state_banned_or_disconnected(peer_id)
+            && !self.is_denied(peer_id)
     }
 
     /// Returns true if the peer is synced at least to our current head.
@@ -146,6 +159,9 @@ impl<E: EthSpec> PeerDB<E> {
     ///
     /// This is used to determine if we should accept incoming connections or not.
     pub fn ban_status(&self, peer_id: &PeerId) -> Option<BanResult> {
+        if self.is_denied(peer_id) {
+            return Some(BanResult::Denied);
+        }
         self.peers
             .get(peer_id)
             .and_then(|peer| match peer.score_state() {
@@ -165,6 +181,37 @@ impl<E: EthSpec> PeerDB<E> {
         self.banned_peers_count.ip_is_banned(ip)
     }
 
+    /// Returns true if the peer policy denies the peer, by its id or by the addresses and agent
+    /// string we know of.
+    pub fn is_denied(&self, peer_id: &PeerId) -> bool {
+        self.peer_policy.is_denied(peer_id, self.peers.get(peer_id))
+    }
+
+    /// Returns true if the peer policy denies the IP address.
+    pub fn is_ip_denied(&self, ip: &IpAddr) -> bool {
+        self.peer_policy.deny.matches_ip(ip)
+    }
+
+    /// Returns the name of a group of the peer whose limit has been reached by the other connected
+    /// peers in the group. Static peers are never limited.
+    pub fn group_limit_reached(&self, peer_id: &PeerId) -> Option<&str> {
+        if self.peer_policy.is_static(peer_id) {
+            return None;
+        }
+        let info = self.peers.get(peer_id);
+        self.peer_policy
+            .limited_groups()
+            .find(|(group, max_peers)| {
+                group.contains(peer_id, info)
+                    && self
+                        .connected_peers()
+                        .filter(|(id, info)| *id != peer_id && group.contains(id, Some(*info)))
+                        .count()
+                        >= *max_peers
+            })
+            .map(|(group, _)| group.name.as_str())
+    }
+
     /// Returns true if the Peer is either banned or in the disconnected state.
     fn score_state_banned_or_disconnected(&self, peer_id: &PeerId) -> bool {
         if let Some(peer) = self.peers.get(peer_id) {
@@ -672,6 +719,20 @@ impl<E: EthSpec> PeerDB<E> {
         );
     }
 
+    /// Sets a peer from a replayed network recording as connected.
+    ///
+    /// Replayed peers have no real connection, so they are registered without an ENR or address.
+    pub fn connect_replayed(&mut self, peer_id: &PeerId) {
+        self.update_connection_state(
+            peer_id,
+            NewConnectionState::Connected {
+                enr: None,
+                seen_address: Multiaddr::empty(),
+                direction: ConnectionDirection::Incoming,
+            },
+        );
+    }
+
     /// Updates the connection state. MUST ONLY BE USED IN TESTS.
     pub fn __add_connected_peer_testing_only(&mut self, peer_id: &PeerId) -> Option<BanOperation> {
         let enr_key = CombinedKey::generate_secp256k1();
@@ -998,6 +1059,148 @@ impl<E: EthSpec> PeerDB<E> {
         self.update_connection_state(peer_id, NewConnectionState::Disconnecting { to_ban });
     }
 
+    /// Returns the reputation and client information of all non-trusted peers, so that they can
+    /// be restored after a restart.
+    pub fn persisted_peers(&self) -> Vec<PersistedPeer> {
+        self.peers
+            .iter()
+            .filter_map(|(peer_id, info)| PersistedPeer::from_peer_info(peer_id, info))
+            .collect()
+    }
+
+    /// Restores peers persisted before a restart as disconnected peers, or as banned peers if
+    /// their scores are still low enough. Peers which are already known are left untouched.
+    ///
+    /// Returns the ban operations for the restored banned peers, along with any banned peers that
+    /// were dropped to stay within our limits.
+    // VISIBILITY: Only the peer manager can adjust the connection state.
+    #[must_use = "Banned peers need to be reported to libp2p."]
+    pub(super) fn restore_peers(
+        &mut self,
+        persisted_peers: Vec<PersistedPeer>,
+    ) -> (Vec<(PeerId, BanOperation)>, Vec<(PeerId, Vec<IpAddr>)>) {
+        let mut ban_operations = Vec::new();
+        if self.disable_peer_scoring {
+            debug!(self.log, "Peer scoring is disabled, not restoring peers");
+            return (ban_operations, Vec::new());
+        }
+
+        for persisted_peer in persisted_peers {
+            let Some(peer_id) = persisted_peer.peer_id() else {
+                warn!(self.log, "Ignoring persisted peer with an invalid id");
+                continue;
+            };
+            if self.peers.contains_key(&peer_id) {
+                continue;
+            }
+            let info = persisted_peer.to_peer_info();
+            let is_banned = info.score_is_banned();
+            self.peers.insert(peer_id, info);
+            self.update_connection_state(&peer_id, NewConnectionState::Disconnected);
+
+            if is_banned {
+                if let Some(ban_operation) =
+                    self.update_connection_state(&peer_id, NewConnectionState::Banned)
+                {
+                    ban_operations.push((peer_id, ban_operation));
+                }
+                // Keep the original ban time so that the oldest bans are still dropped first.
+                if let (Some(since), Some(info)) =
+                    (persisted_peer.banned_since(), self.peers.get_mut(&peer_id))
+                {
+                    info.set_connection_status(PeerConnectionStatus::Banned { since });
+                }
+            }
+        }
+
+        let purged_peers = self.shrink_to_fit();
+        (ban_operations, purged_peers)
+    }
+
+    /// Replaces the peer policy, returning the previous policy.
+    pub(super) fn set_peer_policy(&mut self, peer_policy: PeerPolicy) -> PeerPolicy {
+        std::mem::replace(&mut self.peer_policy, peer_policy)
+    }
+
+    /// Returns the connected peers which should be disconnected to comply with the peer policy,
+    /// along with the reason for disconnecting them.
+    ///
+    /// Denied peers are disconnected, as are the worst scoring peers of groups which exceed their
+    /// limit. Static peers are never disconnected to satisfy a group limit.
+    pub(super) fn peers_violating_policy(&self) -> Vec<(PeerId, GoodbyeReason)> {
+        let mut violating_peers: HashMap<PeerId, GoodbyeReason> = self
+            .connected_peers()
+            .filter(|(peer_id, info)| self.peer_policy.is_denied(peer_id, Some(*info)))
+            .map(|(peer_id, _)| (*peer_id, GoodbyeReason::Banned))
+            .collect();
+
+        let static_peers = self.peer_policy.static_peer_ids();
+        let best_connected_peers = self
+            .worst_connected_peers()
+            .into_iter()
+            .rev()
+            .collect::<Vec<_>>();
+        for (group, max_peers) in self.peer_policy.limited_groups() {
+            let members = best_connected_peers
+                .iter()
+                .filter(|(peer_id, info)| {
+                    !violating_peers.contains_key(*peer_id) && group.contains(peer_id, Some(*info))
+                })
+                .map(|(peer_id, _)| **peer_id)
+                .collect::<Vec<_>>();
+            // Keep the static peers and the best scoring of the remaining peers.
+            let mut remaining = max_peers.saturating_sub(
+                members
+                    .iter()
+                    .filter(|peer_id| static_peers.contains(peer_id))
+                    .count(),
+            );
+            for peer_id in members
+                .into_iter()
+                .filter(|peer_id| !static_peers.contains(peer_id))
+            {
+                if remaining > 0 {
+                    remaining -= 1;
+                } else {
+                    violating_peers.insert(peer_id, GoodbyeReason::TooManyPeers);
+                }
+            }
+        }
+
+        violating_peers.into_iter().collect()
+    }
+
+    /// Lifts the bans of all banned peers by resetting their scores.
+    ///
+    /// Returns the unbanned peers along with their IP addresses which are no longer banned.
+    // VISIBILITY: Only the peer manager can adjust the connection state.
+    #[must_use = "Unbanned peers need to be reported to libp2p."]
+    pub(super) fn clear_bans(&mut self) -> Vec<(PeerId, Vec<IpAddr>)> {
+        let banned_peers = self.banned_peers().copied().collect::<Vec<_>>();
+        for peer_id in &banned_peers {
+            if let Some(info) = self.peers.get_mut(peer_id) {
+                info.clear_score();
+            }
+            self.update_connection_state(peer_id, NewConnectionState::Unbanned);
+        }
+
+        banned_peers
+            .into_iter()
+            .map(|peer_id| {
+                let unbanned_ips = self
+                    .peers
+                    .get(&peer_id)
+                    .map(|info| {
+                        info.seen_ip_addresses()
+                            .filter(|ip| !self.is_ip_banned(ip))
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                (peer_id, unbanned_ips)
+            })
+            .collect()
+    }
+
     /// Removes banned and disconnected peers from the DB if we have reached any of our limits.
     /// Drops the peers with the lowest reputation so that the number of disconnected peers is less
     /// than MAX_DC_PEERS
@@ -1201,6 +1404,8 @@ pub enum BanResult {
     BadScore,
     /// The peer should be banned because it is connecting from a banned IP address.
     BannedIp(IpAddr),
+    /// The peer is denied by the peer policy.
+    Denied,
 }
 
 impl Display for BanResult {
@@ -1208,6 +1413,7 @@ impl Display for BanResult {
         match self {
             BanResult::BadScore => write!(f, "Peer has a bad score"),
             BanResult::BannedIp(addr) => write!(f, "Peer address: {} is banned", addr),
+            BanResult::Denied => write!(f, "Peer is denied by the peer policy"),
         }
     }
 }
@@ -2040,4 +2246,152 @@ mod tests {
             Score::max_score().score()
         );
     }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_restore_persisted_peers() {
+        let mut pdb = get_db();
+        let ip = Ipv4Addr::new(1, 2, 3, 4).into();
+        let banned_peer = connect_peer_with_ips(&mut pdb, vec![ip]);
+        let _ = pdb.report_peer(
+            &banned_peer,
+            PeerAction::Fatal,
+            ReportSource::PeerManager,
+            "",
+        );
+        let _ = pdb.inject_disconnect(&banned_peer);
+        let healthy_peer = connect_peer_with_ips(&mut pdb, vec![ip]);
+        add_score(&mut pdb, &healthy_peer, -10.0);
+        let trusted_peer = PeerId::random();
+        pdb.peers
+            .insert(trusted_peer, PeerInfo::trusted_peer_info());
+
+        let persisted_peers = pdb.persisted_peers();
+        assert_eq!(persisted_peers.len(), 2);
+
+        let mut restored = get_db();
+        let (ban_operations, purged_peers) = restored.restore_peers(persisted_peers.clone());
+        assert!(purged_peers.is_empty());
+        assert!(matches!(
+            ban_operations.as_slice(),
+            [(peer_id, BanOperation::ReadyToBan(_))] if *peer_id == banned_peer
+        ));
+
+        // The banned peer remains banned and the healthy peer keeps its score.
+        assert!(restored.ban_status(&banned_peer).is_some());
+        assert!(restored.peer_info(&banned_peer).unwrap().is_banned());
+        assert!(restored.peer_info(&healthy_peer).unwrap().is_disconnected());
+        assert_eq!(
+            restored.score(&healthy_peer).round(),
+            pdb.score(&healthy_peer).round()
+        );
+        assert!(restored.peer_info(&trusted_peer).is_none());
+        assert_eq!(restored.disconnected_peers, 1);
+        assert_eq!(restored.banned_peers_count.banned_peers(), 1);
+
+        // Known peers are not overwritten.
+        let (ban_operations, _) = restored.restore_peers(persisted_peers);
+        assert!(ban_operations.is_empty());
+        assert_eq!(restored.banned_peers_count.banned_peers(), 1);
+    }
+
+    #[test]
+    fn test_clear_bans() {
+        let mut pdb = get_db();
+        let ip = Ipv4Addr::new(1, 2, 3, 4).into();
+        let peers = (0..BANNED_PEERS_PER_IP_THRESHOLD + 1)
+            .map(|_| connect_peer_with_ips(&mut pdb, vec![ip]))
+            .collect::<Vec<_>>();
+        for peer in &peers {
+            let _ = pdb.report_peer(peer, PeerAction::Fatal, ReportSource::PeerManager, "");
+            let _ = pdb.inject_disconnect(peer);
+        }
+        assert!(pdb.is_ip_banned(&ip));
+
+        let unbanned = pdb.clear_bans();
+        assert_eq!(unbanned.len(), peers.len());
+        assert!(unbanned.iter().all(|(_, ips)| ips == &vec![ip]));
+        for peer in &peers {
+            assert!(pdb.ban_status(peer).is_none());
+            assert!(pdb.peer_info(peer).unwrap().is_disconnected());
+        }
+        assert!(!pdb.is_ip_banned(&ip));
+        assert_eq!(pdb.banned_peers_count.banned_peers(), 0);
+        assert_eq!(pdb.disconnected_peers, peers.len());
+    }
+
+    #[test]
+    fn test_peer_policy_denies_peers() {
+        use crate::peer_manager::peer_policy::PeerMatcher;
+
+        let mut pdb = get_db();
+        let denied_ip = Ipv4Addr::new(1, 2, 3, 4).into();
+        let denied_by_ip = connect_peer_with_ips(&mut pdb, vec![denied_ip]);
+        let allowed = connect_peer_with_ips(&mut pdb, vec![Ipv4Addr::new(5, 6, 7, 8).into()]);
+        let denied_by_id = PeerId::random();
+
+        pdb.set_peer_policy(PeerPolicy {
+            deny: PeerMatcher {
+                peer_ids: vec![denied_by_id],
+                ip_ranges: vec!["1.2.3.0/24".parse().unwrap()],
+                agents: vec![],
+            },
+            groups: vec![],
+        });
+
+        assert!(pdb.is_ip_denied(&denied_ip));
+        assert!(matches!(
+            pdb.ban_status(&denied_by_ip),
+            Some(BanResult::Denied)
+        ));
+        assert!(matches!(
+            pdb.ban_status(&denied_by_id),
+            Some(BanResult::Denied)
+        ));
+        assert!(pdb.ban_status(&allowed).is_none());
+        assert!(!pdb.should_dial(&denied_by_id));
+
+        let violating_peers = pdb.peers_violating_policy();
+        assert_eq!(violating_peers.len(), 1);
+        assert_eq!(violating_peers[0].0, denied_by_ip);
+        assert!(matches!(violating_peers[0].1, GoodbyeReason::Banned));
+    }
+
+    #[test]
+    fn test_peer_policy_group_limit() {
+        use crate::peer_manager::peer_policy::{PeerGroup, PeerMatcher};
+
+        let mut pdb = get_db();
+        let ip = |i| Ipv4Addr::new(192, 0, 2, i).into();
+        let static_peer = connect_peer_with_ips(&mut pdb, vec![ip(1)]);
+        let worst_peer = connect_peer_with_ips(&mut pdb, vec![ip(2)]);
+        let best_peer = connect_peer_with_ips(&mut pdb, vec![ip(3)]);
+        let outsider = connect_peer_with_ips(&mut pdb, vec![Ipv4Addr::new(5, 6, 7, 8).into()]);
+        add_score(&mut pdb, &worst_peer, -10.0);
+
+        pdb.set_peer_policy(PeerPolicy {
+            deny: PeerMatcher::default(),
+            groups: vec![PeerGroup {
+                name: "limited".into(),
+                members: PeerMatcher {
+                    ip_ranges: vec!["192.0.2.0/24".parse().unwrap()],
+                    ..Default::default()
+                },
+                static_peers: vec![format!("/ip4/192.0.2.1/tcp/9000/p2p/{static_peer}")
+                    .parse()
+                    .unwrap()],
+                max_peers: Some(2),
+            }],
+        });
+
+        assert_eq!(pdb.group_limit_reached(&best_peer), Some("limited"));
+        assert_eq!(pdb.group_limit_reached(&static_peer), None);
+        assert_eq!(pdb.group_limit_reached(&outsider), None);
+
+        // The static peer is kept and the remaining slot goes to the best scoring peer.
+        let violating_peers = pdb.peers_violating_policy();
+        assert_eq!(violating_peers.len(), 1);
+        assert_eq!(violating_peers[0].0, worst_peer);
+        assert!(matches!(violating_peers[0].1, GoodbyeReason::TooManyPeers));
+    }
 }
@@ -82,6 +82,22 @@ impl<E: EthSpec> PeerInfo<E> {
         }
     }
 
+    /// Return a PeerInfo struct for a peer restored from a previous run.
+    pub(super) fn restored_peer_info(
+        score: Score,
+        client: Client,
+        seen_multiaddrs: HashSet<Multiaddr>,
+        sync_status: SyncStatus,
+    ) -> Self {
+        PeerInfo {
+            score,
+            client,
+            seen_multiaddrs,
+            sync_status,
+            ..Default::default()
+        }
+    }
+
     /// Returns if the peer is subscribed to a given `Subnet` from the metadata attnets/syncnets field.
     pub fn on_subnet_metadata(&self, subnet: &Subnet) -> bool {
         if let Some(meta_data) = &self.meta_data {
@@ -407,6 +423,13 @@ impl<E: EthSpec> PeerInfo<E> {
         self.score.update_gossipsub_score(new_score, ignore);
     }
 
+    /// Resets a non-trusted peer's score to the default, lifting any ban.
+    pub(super) fn clear_score(&mut self) {
+        if !self.is_trusted {
+            self.score = Score::default();
+        }
+    }
+
     #[cfg(test)]
     /// Resets the peers score.
     pub fn reset_score(&mut self) {
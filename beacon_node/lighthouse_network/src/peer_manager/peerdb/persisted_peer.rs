@@ -0,0 +1,156 @@
+//! The parts of a peer's information which are kept across restarts.
+
+use super::client::Client;
+use super::peer_info::{PeerConnectionStatus, PeerInfo};
+use super::score::Score;
+use super::sync_status::{SyncInfo, SyncStatus};
+use crate::{multiaddr::Multiaddr, PeerId};
+use ssz_derive::{Decode, Encode};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use types::EthSpec;
+
+const SYNC_STATUS_UNKNOWN: u8 = 0;
+const SYNC_STATUS_SYNCED: u8 = 1;
+const SYNC_STATUS_ADVANCED: u8 = 2;
+const SYNC_STATUS_BEHIND: u8 = 3;
+const SYNC_STATUS_IRRELEVANT: u8 = 4;
+
+/// A peer's reputation, ban and client information in a form which can be written to disk.
+///
+/// An `Instant` is only meaningful within the process that created it, so times are stored as
+/// seconds since the UNIX epoch.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedPeer {
+    peer_id: Vec<u8>,
+    /// The bits of the peer's lighthouse score.
+    lighthouse_score: u64,
+    /// The time from which the score decays. This is in the future whilst a banned peer serves
+    /// the minimum duration of its ban.
+    score_decays_from: u64,
+    /// The time at which the peer was banned, if it is banned.
+    banned_since: Option<u64>,
+    seen_multiaddrs: Vec<Vec<u8>>,
+    agent_string: Option<Vec<u8>>,
+    protocol_version: Vec<u8>,
+    sync_status: u8,
+    sync_info: Option<SyncInfo>,
+}
+
+impl PersistedPeer {
+    /// Captures the information of a peer, or returns `None` for a trusted peer, whose maximum
+    /// score is not worth keeping.
+    pub fn from_peer_info<E: EthSpec>(peer_id: &PeerId, info: &PeerInfo<E>) -> Option<Self> {
+        if info.is_trusted() {
+            return None;
+        }
+        let (lighthouse_score, score_decays_from) = info.score().decaying_score()?;
+        let banned_since = match info.connection_status() {
+            PeerConnectionStatus::Banned { since } => Some(to_unix_secs(*since)),
+            _ => None,
+        };
+        let (sync_status, sync_info) = match info.sync_status().clone() {
+            SyncStatus::Unknown => (SYNC_STATUS_UNKNOWN, None),
+            SyncStatus::Synced { info } => (SYNC_STATUS_SYNCED, Some(info)),
+            SyncStatus::Advanced { info } => (SYNC_STATUS_ADVANCED, Some(info)),
+            SyncStatus::Behind { info } => (SYNC_STATUS_BEHIND, Some(info)),
+            SyncStatus::IrrelevantPeer => (SYNC_STATUS_IRRELEVANT, None),
+        };
+
+        Some(Self {
+            peer_id: peer_id.to_bytes(),
+            lighthouse_score: lighthouse_score.to_bits(),
+            score_decays_from: to_unix_secs(score_decays_from),
+            banned_since,
+            seen_multiaddrs: info.seen_multiaddrs().map(|addr| addr.to_vec()).collect(),
+            agent_string: info
+                .client()
+                .agent_string
+                .as_ref()
+                .map(|agent| agent.as_bytes().to_vec()),
+            protocol_version: info.client().protocol_version.as_bytes().to_vec(),
+            sync_status,
+            sync_info,
+        })
+    }
+
+    /// The id of the peer, or `None` if the stored bytes are invalid.
+    pub fn peer_id(&self) -> Option<PeerId> {
+        PeerId::from_bytes(&self.peer_id).ok()
+    }
+
+    /// The time at which the peer was banned, if it was banned when it was persisted.
+    pub(super) fn banned_since(&self) -> Option<Instant> {
+        self.banned_since.map(from_unix_secs)
+    }
+
+    /// Rebuilds the peer's information, with the peer in the unknown connection state.
+    pub(super) fn to_peer_info<E: EthSpec>(&self) -> PeerInfo<E> {
+        let lighthouse_score = f64::from_bits(self.lighthouse_score);
+        let score_decays_from = Duration::from_secs(self.score_decays_from);
+        let unix_now = unix_now();
+        let score = match score_decays_from.checked_sub(unix_now) {
+            // The peer is still serving the minimum duration of its ban.
+            Some(until) => Score::restore(lighthouse_score, Instant::now() + until, Duration::ZERO),
+            // Apply the decay which would have occurred whilst we were offline.
+            None => Score::restore(
+                lighthouse_score,
+                Instant::now(),
+                unix_now - score_decays_from,
+            ),
+        };
+        let client = self
+            .agent_string
+            .as_ref()
+            .map(|agent| {
+                Client::from_agent_string(
+                    String::from_utf8_lossy(agent).into_owned(),
+                    String::from_utf8_lossy(&self.protocol_version).into_owned(),
+                )
+            })
+            .unwrap_or_default();
+        let seen_multiaddrs = self
+            .seen_multiaddrs
+            .iter()
+            .filter_map(|bytes| Multiaddr::try_from(bytes.clone()).ok())
+            .collect();
+        let sync_status = match (self.sync_status, self.sync_info.clone()) {
+            (SYNC_STATUS_SYNCED, Some(info)) => SyncStatus::Synced { info },
+            (SYNC_STATUS_ADVANCED, Some(info)) => SyncStatus::Advanced { info },
+            (SYNC_STATUS_BEHIND, Some(info)) => SyncStatus::Behind { info },
+            (SYNC_STATUS_IRRELEVANT, _) => SyncStatus::IrrelevantPeer,
+            _ => SyncStatus::Unknown,
+        };
+
+        PeerInfo::restored_peer_info(score, client, seen_multiaddrs, sync_status)
+    }
+}
+
+/// The current time as a duration since the UNIX epoch.
+fn unix_now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// Converts an `Instant`, which may be in the future, to seconds since the UNIX epoch.
+fn to_unix_secs(instant: Instant) -> u64 {
+    let now = Instant::now();
+    match instant.checked_duration_since(now) {
+        Some(until) => unix_now().saturating_add(until),
+        None => unix_now().saturating_sub(now.duration_since(instant)),
+    }
+    .as_secs()
+}
+
+/// Converts seconds since the UNIX epoch to an `Instant`, saturating at the current time if the
+/// time precedes the earliest representable `Instant`.
+fn from_unix_secs(secs: u64) -> Instant {
+    let now = Instant::now();
+    let time = Duration::from_secs(secs);
+    match time.checked_sub(unix_now()) {
+        Some(until) => now + until,
+        None => now
+            .checked_sub(unix_now().saturating_sub(time))
+            .unwrap_or(now),
+    }
+}
@@ -260,6 +260,33 @@ impl RealScore {
     pub fn is_good_gossipsub_peer(&self) -> bool {
         self.gossipsub_score >= 0.0
     }
+
+    /// Builds a score from a persisted lighthouse score which has already decayed for `decayed`,
+    /// and decays further from `last_updated`.
+    ///
+    /// The gossipsub score is not restored, as it is recomputed by gossipsub once the peer
+    /// reconnects.
+    fn restore(lighthouse_score: f64, last_updated: Instant, decayed: Duration) -> Self {
+        let decay_factor = (*HALFLIFE_DECAY * decayed.as_secs() as f64).exp();
+        let mut score = RealScore {
+            lighthouse_score: (lighthouse_score * decay_factor).clamp(MIN_SCORE, MAX_SCORE),
+            last_updated,
+            ..RealScore::default()
+        };
+        // Recompute the score without `update_state`, which would extend an existing ban.
+        score.recompute_score();
+        score
+    }
+
+    /// Estimates the time at which the score will have decayed enough for the peer to be unbanned,
+    /// if it is currently banned.
+    fn ban_expiry(&self) -> Option<Instant> {
+        if self.score > MIN_SCORE_BEFORE_BAN {
+            return None;
+        }
+        let halflives = (self.score / MIN_SCORE_BEFORE_BAN).log2().max(0.0);
+        Some(self.last_updated + Duration::from_secs_f64(halflives * SCORE_HALFLIFE))
+    }
 }
 
 #[derive(PartialEq, Clone, Debug, Serialize)]
@@ -310,6 +337,29 @@ impl Score {
         Self::Max
     }
 
+    /// Restores a score persisted with `decaying_score`, applying any decay which would have
+    /// occurred whilst it was persisted.
+    pub(crate) fn restore(lighthouse_score: f64, last_updated: Instant, decayed: Duration) -> Self {
+        Self::Real(RealScore::restore(lighthouse_score, last_updated, decayed))
+    }
+
+    /// Returns the lighthouse score and the time from which it decays, or `None` for the maximum
+    /// score of a trusted peer.
+    pub(crate) fn decaying_score(&self) -> Option<(f64, Instant)> {
+        match self {
+            Self::Max => None,
+            Self::Real(score) => Some((score.lighthouse_score, score.last_updated)),
+        }
+    }
+
+    /// Estimates the time at which a banned peer will be unbanned, if its score is banned.
+    pub fn ban_expiry(&self) -> Option<Instant> {
+        match self {
+            Self::Max => None,
+            Self::Real(score) => score.ban_expiry(),
+        }
+    }
+
     /// Returns the expected state of the peer given it's score.
     pub(crate) fn state(&self) -> ScoreState {
         match self.score() {
@@ -400,6 +450,37 @@ mod tests {
         assert!(score.score() > MIN_SCORE_BEFORE_BAN);
     }
 
+    #[test]
+    fn test_restored_ban_expiry() {
+        let now = Instant::now();
+        let mut score = RealScore::default();
+        score.test_add(MIN_SCORE_BEFORE_BAN * 2.0);
+        let (lighthouse_score, last_updated) = Score::Real(score.clone())
+            .decaying_score()
+            .expect("should have a real score");
+
+        // Restoring a score neither resets nor extends the ban.
+        let restored = RealScore::restore(lighthouse_score, last_updated, Duration::ZERO);
+        assert_eq!(restored, score);
+
+        // Doubling the threshold takes one halflife to decay past the ban threshold.
+        let expiry = restored.ban_expiry().expect("should be banned");
+        assert_eq!(
+            expiry,
+            last_updated + Duration::from_secs_f64(SCORE_HALFLIFE)
+        );
+        assert!(expiry > now + BANNED_BEFORE_DECAY);
+
+        // A score which decayed whilst persisted is restored with the decay applied.
+        let decayed = RealScore::restore(
+            lighthouse_score,
+            now,
+            Duration::from_secs_f64(SCORE_HALFLIFE * 2.0),
+        );
+        assert!(decayed.ban_expiry().is_none());
+        assert_eq!(Score::Real(decayed).state(), ScoreState::ForcedDisconnect);
+    }
+
     #[test]
     fn test_very_negative_gossipsub_score() {
         let mut score = Score::default();
@@ -1,6 +1,7 @@
 //! Handles individual sync status for peers.
 
 use serde::Serialize;
+use ssz_derive::{Decode, Encode};
 use types::{Epoch, Hash256, Slot};
 
 #[derive(Clone, Debug, Serialize)]
@@ -19,7 +20,7 @@ pub enum SyncStatus {
 }
 
 /// A relevant peer's sync information.
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Encode, Decode)]
 pub struct SyncInfo {
     pub head_slot: Slot,
     pub head_root: Hash256,
@@ -4,6 +4,8 @@
 //! direct peer-to-peer communication primarily for sending/receiving chain information for
 //! syncing.
 
+use crate::metrics;
+use crate::types::NetworkGlobals;
 use futures::future::FutureExt;
 use handler::RPCHandler;
 use libp2p::swarm::{
@@ -12,7 +14,7 @@ use libp2p::swarm::{
 };
 use libp2p::swarm::{ConnectionClosed, FromSwarm, SubstreamProtocol, THandlerInEvent};
 use libp2p::PeerId;
-use rate_limiter::{RPCRateLimiter as RateLimiter, RateLimitedErr};
+use rate_limiter::{validate_peer_multiplier, RPCRateLimiter as RateLimiter, RateLimitedErr};
 use slog::{crit, debug, o};
 use std::marker::PhantomData;
 use std::sync::Arc;
@@ -22,7 +24,7 @@ use types::{EthSpec, ForkContext};
 
 pub(crate) use handler::{HandlerErr, HandlerEvent};
 pub(crate) use methods::{MetaData, MetaDataV1, MetaDataV2, Ping, RPCCodedResponse, RPCResponse};
-pub(crate) use protocol::InboundRequest;
+pub(crate) use protocol::{InboundRequest, SupportedProtocol};
 
 pub use handler::SubstreamId;
 pub use methods::{
@@ -31,6 +33,7 @@ pub use methods::{
 };
 pub(crate) use outbound::OutboundRequest;
 pub use protocol::{max_rpc_size, Protocol, RPCError};
+pub use rate_limiter::{InboundRateLimits, Quota};
 
 use self::config::{InboundRateLimiterConfig, OutboundRateLimiterConfig};
 use self::protocol::RPCProtocol;
@@ -129,6 +132,8 @@ pub struct RPC<Id: ReqId, E: EthSpec> {
     log: slog::Logger,
     /// Networking constant values
     network_params: NetworkParams,
+    /// Network globals, used to expose the inbound rate limits.
+    network_globals: Arc<NetworkGlobals<E>>,
 }
 
 impl<Id: ReqId, E: EthSpec> RPC<Id, E> {
@@ -139,6 +144,7 @@ impl<Id: ReqId, E: EthSpec> RPC<Id, E> {
         outbound_rate_limiter_config: Option<OutboundRateLimiterConfig>,
         log: slog::Logger,
         network_params: NetworkParams,
+        network_globals: Arc<NetworkGlobals<E>>,
     ) -> Self {
         let log = log.new(o!("service" => "libp2p_rpc"));
 
@@ -148,6 +154,10 @@ impl<Id: ReqId, E: EthSpec> RPC<Id, E> {
                 .expect("Inbound limiter configuration parameters are valid")
         });
 
+        if let Some(limiter) = inbound_limiter.as_ref() {
+            network_globals.inbound_rate_limits.write().quotas = limiter.quotas();
+        }
+
         let self_limiter = outbound_rate_limiter_config.map(|config| {
             SelfRateLimiter::new(config, log.clone()).expect("Configuration parameters are valid")
         });
@@ -160,7 +170,73 @@ impl<Id: ReqId, E: EthSpec> RPC<Id, E> {
             enable_light_client_server,
             log,
             network_params,
+            network_globals,
+        }
+    }
+
+    /// Replaces the inbound quotas of the given protocols and sets the multipliers applied to the
+    /// inbound quotas of the given peers, or removes them if `None`.
+    ///
+    /// Fails without applying any update if inbound rate limiting is disabled or any quota or
+    /// multiplier is invalid.
+    pub fn update_inbound_rate_limits(
+        &mut self,
+        quotas: Vec<(Protocol, Quota)>,
+        peer_multipliers: Vec<(PeerId, Option<f64>)>,
+    ) -> Result<(), String> {
+        let limiter = self
+            .limiter
+            .as_mut()
+            .ok_or("Inbound rate limiting is disabled")?;
+        for (protocol, quota) in &quotas {
+            quota
+                .validate()
+                .map_err(|e| format!("Invalid quota for {}: {}", protocol, e))?;
+        }
+        for (peer_id, multiplier) in &peer_multipliers {
+            if let Some(multiplier) = multiplier {
+                validate_peer_multiplier(*multiplier)
+                    .map_err(|e| format!("Invalid multiplier for {}: {}", peer_id, e))?;
+            }
+        }
+
+        for (protocol, quota) in quotas {
+            limiter.set_quota(protocol, quota)?;
+        }
+        let mut inbound_rate_limits = self.network_globals.inbound_rate_limits.write();
+        for (peer_id, multiplier) in peer_multipliers {
+            limiter.set_peer_multiplier(peer_id, multiplier)?;
+            match multiplier {
+                Some(multiplier) => {
+                    inbound_rate_limits
+                        .peer_multipliers
+                        .insert(peer_id, multiplier);
+                }
+                None => {
+                    inbound_rate_limits.peer_multipliers.remove(&peer_id);
+                }
+            }
         }
+        inbound_rate_limits.quotas = limiter.quotas();
+        Ok(())
+    }
+
+    /// Records an inbound request from `peer_id` rejected by the rate limiter.
+    fn record_rate_limited(&self, peer_id: PeerId, protocol: Protocol) {
+        let client = self.network_globals.client(&peer_id);
+        metrics::inc_counter_vec(
+            &metrics::TOTAL_RPC_RATE_LIMITED_REQUESTS_PER_CLIENT,
+            &[protocol.as_ref(), client.kind.as_ref()],
+        );
+        *self
+            .network_globals
+            .inbound_rate_limits
+            .write()
+            .rejected_requests
+            .entry(peer_id)
+            .or_default()
+            .entry(protocol)
+            .or_default() += 1;
     }
 
     /// Sends an RPC response.
@@ -301,6 +377,13 @@ where
             if remaining_established > 0 {
                 return;
             }
+            // Only the rejected requests of connected peers are kept.
+            self.network_globals
+                .inbound_rate_limits
+                .write()
+                .rejected_requests
+                .remove(&peer_id);
+
             // Get a list of pending requests from the self rate limiter
             if let Some(limiter) = self.self_limiter.as_mut() {
                 for (id, proto) in limiter.peer_disconnected(peer_id) {
@@ -368,12 +451,16 @@ where
                                     | Protocol::BlobsByRange
                                     | Protocol::BlocksByRoot
                                     | Protocol::BlobsByRoot
+                                    | Protocol::DataColumnsByRoot
+                                    | Protocol::DataColumnsByRange
+                                    | Protocol::LightClientUpdatesByRange
                             ) {
                                 debug!(self.log, "Request too large to process"; "request" => %req, "protocol" => %protocol);
                             } else {
                                 // Other protocols shouldn't be sending large messages, we should flag the peer kind
                                 crit!(self.log, "Request size too large to ever be processed"; "protocol" => %protocol);
                             }
+                            self.record_rate_limited(peer_id, protocol);
                             // send an error code to the peer.
                             // the handler upon receiving the error code will send it back to the behaviour
                             self.send_response(
@@ -388,6 +475,7 @@ where
                         Err(RateLimitedErr::TooSoon(wait_time)) => {
                             debug!(self.log, "Request exceeds the rate limit";
                         "request" => %req, "peer_id" => %peer_id, "wait_time_ms" => wait_time.as_millis());
+                            self.record_rate_limited(peer_id, req.versioned_protocol().protocol());
                             // send an error code to the peer.
                             // the handler upon receiving the error code will send it back to the behaviour
                             self.send_response(
@@ -471,6 +559,11 @@ where
                             ResponseTermination::BlocksByRoot => Protocol::BlocksByRoot,
                             ResponseTermination::BlobsByRange => Protocol::BlobsByRange,
                             ResponseTermination::BlobsByRoot => Protocol::BlobsByRoot,
+                            ResponseTermination::DataColumnsByRoot => Protocol::DataColumnsByRoot,
+                            ResponseTermination::DataColumnsByRange => Protocol::DataColumnsByRange,
+                            ResponseTermination::LightClientUpdatesByRange => {
+                                Protocol::LightClientUpdatesByRange
+                            }
                         },
                     ),
                 };
@@ -3,6 +3,7 @@ use crate::rpc::Protocol;
 use fnv::FnvHashMap;
 use libp2p::PeerId;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::future::Future;
 use std::hash::Hash;
 use std::pin::Pin;
@@ -73,6 +74,59 @@ impl Quota {
             max_tokens: n,
         }
     }
+
+    /// The maximum number of tokens that can be used in an instantaneous batch.
+    pub fn max_tokens(&self) -> u64 {
+        self.max_tokens
+    }
+
+    /// How often the `max_tokens` are fully replenished.
+    pub fn replenish_all_every(&self) -> Duration {
+        self.replenish_all_every
+    }
+
+    /// Checks that the quota can be enforced by a rate limiter.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        self.tau_and_t().map(|_| ())
+    }
+
+    /// After how long the bucket is full, and how often 1T is replenished.
+    fn tau_and_t(&self) -> Result<(Nanosecs, Nanosecs), &'static str> {
+        if self.max_tokens == 0 {
+            return Err("Max number of tokens should be positive");
+        }
+        let tau = self.replenish_all_every.as_nanos();
+        if tau == 0 {
+            return Err("Replenish time must be positive");
+        }
+        let t = (tau / self.max_tokens as u128)
+            .try_into()
+            .map_err(|_| "total replenish time is too long")?;
+        let tau = tau
+            .try_into()
+            .map_err(|_| "total replenish time is too long")?;
+        Ok((tau, t))
+    }
+}
+
+/// Checks that a multiplier can be applied to the quotas of a peer.
+pub fn validate_peer_multiplier(multiplier: f64) -> Result<(), &'static str> {
+    if !multiplier.is_finite() || multiplier <= 0.0 {
+        return Err("Multiplier must be a positive number");
+    }
+    Ok(())
+}
+
+/// The inbound rate limits in effect and the requests they have rejected. This is kept in the
+/// `NetworkGlobals` so that it can be inspected outside of the network thread.
+#[derive(Debug, Clone, Default)]
+pub struct InboundRateLimits {
+    /// The quota of each protocol. This is empty if inbound rate limiting is disabled.
+    pub quotas: Vec<(Protocol, Quota)>,
+    /// Multipliers applied to the quotas of specific peers.
+    pub peer_multipliers: HashMap<PeerId, f64>,
+    /// The number of requests rejected from each connected peer, per protocol.
+    pub rejected_requests: HashMap<PeerId, HashMap<Protocol, u64>>,
 }
 
 /// Manages rate limiting of requests per peer, with differentiated rates per protocol.
@@ -97,12 +151,20 @@ pub struct RPCRateLimiter {
     blbrange_rl: Limiter<PeerId>,
     /// BlobsByRoot rate limiter.
     blbroot_rl: Limiter<PeerId>,
+    /// DataColumnsByRoot rate limiter.
+    dcbroot_rl: Limiter<PeerId>,
+    /// DataColumnsByRange rate limiter.
+    dcbrange_rl: Limiter<PeerId>,
     /// LightClientBootstrap rate limiter.
     lc_bootstrap_rl: Limiter<PeerId>,
     /// LightClientOptimisticUpdate rate limiter.
     lc_optimistic_update_rl: Limiter<PeerId>,
     /// LightClientFinalityUpdate rate limiter.
     lc_finality_update_rl: Limiter<PeerId>,
+    /// LightClientUpdatesByRange rate limiter.
+    lc_updates_by_range_rl: Limiter<PeerId>,
+    /// Multipliers applied to the quotas of specific peers.
+    peer_multipliers: FnvHashMap<PeerId, f64>,
 }
 
 /// Error type for non conformant requests
@@ -133,12 +195,18 @@ pub struct RPCRateLimiterBuilder {
     blbrange_quota: Option<Quota>,
     /// Quota for the BlobsByRoot protocol.
     blbroot_quota: Option<Quota>,
+    /// Quota for the DataColumnsByRoot protocol.
+    dcbroot_quota: Option<Quota>,
+    /// Quota for the DataColumnsByRange protocol.
+    dcbrange_quota: Option<Quota>,
     /// Quota for the LightClientBootstrap protocol.
     lcbootstrap_quota: Option<Quota>,
     /// Quota for the LightClientOptimisticUpdate protocol.
     lc_optimistic_update_quota: Option<Quota>,
     /// Quota for the LightClientOptimisticUpdate protocol.
     lc_finality_update_quota: Option<Quota>,
+    /// Quota for the LightClientUpdatesByRange protocol.
+    lc_updates_by_range_quota: Option<Quota>,
 }
 
 impl RPCRateLimiterBuilder {
@@ -154,9 +222,12 @@ impl RPCRateLimiterBuilder {
             Protocol::BlocksByRoot => self.bbroots_quota = q,
             Protocol::BlobsByRange => self.blbrange_quota = q,
             Protocol::BlobsByRoot => self.blbroot_quota = q,
+            Protocol::DataColumnsByRoot => self.dcbroot_quota = q,
+            Protocol::DataColumnsByRange => self.dcbrange_quota = q,
             Protocol::LightClientBootstrap => self.lcbootstrap_quota = q,
             Protocol::LightClientOptimisticUpdate => self.lc_optimistic_update_quota = q,
             Protocol::LightClientFinalityUpdate => self.lc_finality_update_quota = q,
+            Protocol::LightClientUpdatesByRange => self.lc_updates_by_range_quota = q,
         }
         self
     }
@@ -182,6 +253,9 @@ impl RPCRateLimiterBuilder {
         let lc_finality_update_quota = self
             .lc_finality_update_quota
             .ok_or("LightClientFinalityUpdate quota not specified")?;
+        let lc_updates_by_range_quota = self
+            .lc_updates_by_range_quota
+            .ok_or("LightClientUpdatesByRange quota not specified")?;
 
         let blbrange_quota = self
             .blbrange_quota
@@ -191,6 +265,14 @@ impl RPCRateLimiterBuilder {
             .blbroot_quota
             .ok_or("BlobsByRoot quota not specified")?;
 
+        let dcbroot_quota = self
+            .dcbroot_quota
+            .ok_or("DataColumnsByRoot quota not specified")?;
+
+        let dcbrange_quota = self
+            .dcbrange_quota
+            .ok_or("DataColumnsByRange quota not specified")?;
+
         // create the rate limiters
         let ping_rl = Limiter::from_quota(ping_quota)?;
         let metadata_rl = Limiter::from_quota(metadata_quota)?;
@@ -200,9 +282,12 @@ impl RPCRateLimiterBuilder {
         let bbrange_rl = Limiter::from_quota(bbrange_quota)?;
         let blbrange_rl = Limiter::from_quota(blbrange_quota)?;
         let blbroot_rl = Limiter::from_quota(blbroots_quota)?;
+        let dcbroot_rl = Limiter::from_quota(dcbroot_quota)?;
+        let dcbrange_rl = Limiter::from_quota(dcbrange_quota)?;
         let lc_bootstrap_rl = Limiter::from_quota(lc_bootstrap_quota)?;
         let lc_optimistic_update_rl = Limiter::from_quota(lc_optimistic_update_quota)?;
         let lc_finality_update_rl = Limiter::from_quota(lc_finality_update_quota)?;
+        let lc_updates_by_range_rl = Limiter::from_quota(lc_updates_by_range_quota)?;
 
         // check for peers to prune every 30 seconds, starting in 30 seconds
         let prune_every = tokio::time::Duration::from_secs(30);
@@ -218,9 +303,13 @@ impl RPCRateLimiterBuilder {
             bbrange_rl,
             blbrange_rl,
             blbroot_rl,
+            dcbroot_rl,
+            dcbrange_rl,
             lc_bootstrap_rl,
             lc_optimistic_update_rl,
             lc_finality_update_rl,
+            lc_updates_by_range_rl,
+            peer_multipliers: FnvHashMap::default(),
             init_time: Instant::now(),
         })
     }
@@ -262,9 +351,12 @@ impl RPCRateLimiter {
             blocks_by_root_quota,
             blobs_by_range_quota,
             blobs_by_root_quota,
+            data_columns_by_root_quota,
+            data_columns_by_range_quota,
             light_client_bootstrap_quota,
             light_client_optimistic_update_quota,
             light_client_finality_update_quota,
+            light_client_updates_by_range_quota,
         } = config;
 
         Self::builder()
@@ -276,6 +368,8 @@ impl RPCRateLimiter {
             .set_quota(Protocol::BlocksByRoot, blocks_by_root_quota)
             .set_quota(Protocol::BlobsByRange, blobs_by_range_quota)
             .set_quota(Protocol::BlobsByRoot, blobs_by_root_quota)
+            .set_quota(Protocol::DataColumnsByRoot, data_columns_by_root_quota)
+            .set_quota(Protocol::DataColumnsByRange, data_columns_by_range_quota)
             .set_quota(Protocol::LightClientBootstrap, light_client_bootstrap_quota)
             .set_quota(
                 Protocol::LightClientOptimisticUpdate,
@@ -285,6 +379,10 @@ impl RPCRateLimiter {
                 Protocol::LightClientFinalityUpdate,
                 light_client_finality_update_quota,
             )
+            .set_quota(
+                Protocol::LightClientUpdatesByRange,
+                light_client_updates_by_range_quota,
+            )
             .build()
     }
 
@@ -300,10 +398,86 @@ impl RPCRateLimiter {
     ) -> Result<(), RateLimitedErr> {
         let time_since_start = self.init_time.elapsed();
         let tokens = request.max_responses().max(1);
+        let multiplier = self.peer_multipliers.get(peer_id).copied();
+
+        let limiter = self.limiter_mut(request.protocol());
+        match multiplier {
+            Some(multiplier) => {
+                limiter.allows_with_multiplier(time_since_start, peer_id, tokens, multiplier)
+            }
+            None => limiter.allows(time_since_start, peer_id, tokens),
+        }
+    }
+
+    /// Replaces the quota of a protocol. The state of peers already being rate limited is kept.
+    pub fn set_quota(&mut self, protocol: Protocol, quota: Quota) -> Result<(), &'static str> {
+        self.limiter_mut(protocol).set_quota(quota)
+    }
+
+    /// Sets the multiplier applied to the quotas of a peer, or removes it if `None`.
+    ///
+    /// A multiplier of 2.0 allows the peer twice as many tokens in the same period.
+    pub fn set_peer_multiplier(
+        &mut self,
+        peer_id: PeerId,
+        multiplier: Option<f64>,
+    ) -> Result<(), &'static str> {
+        match multiplier {
+            Some(multiplier) => {
+                validate_peer_multiplier(multiplier)?;
+                self.peer_multipliers.insert(peer_id, multiplier);
+            }
+            None => {
+                self.peer_multipliers.remove(&peer_id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the quota of every protocol.
+    pub fn quotas(&self) -> Vec<(Protocol, Quota)> {
+        [
+            Protocol::Ping,
+            Protocol::Status,
+            Protocol::MetaData,
+            Protocol::Goodbye,
+            Protocol::BlocksByRange,
+            Protocol::BlocksByRoot,
+            Protocol::BlobsByRange,
+            Protocol::BlobsByRoot,
+            Protocol::DataColumnsByRoot,
+            Protocol::DataColumnsByRange,
+            Protocol::LightClientBootstrap,
+            Protocol::LightClientOptimisticUpdate,
+            Protocol::LightClientFinalityUpdate,
+            Protocol::LightClientUpdatesByRange,
+        ]
+        .into_iter()
+        .map(|protocol| (protocol, self.limiter(protocol).quota.clone()))
+        .collect()
+    }
+
+    fn limiter(&self, protocol: Protocol) -> &Limiter<PeerId> {
+        match protocol {
+            Protocol::Ping => &self.ping_rl,
+            Protocol::Status => &self.status_rl,
+            Protocol::MetaData => &self.metadata_rl,
+            Protocol::Goodbye => &self.goodbye_rl,
+            Protocol::BlocksByRange => &self.bbrange_rl,
+            Protocol::BlocksByRoot => &self.bbroots_rl,
+            Protocol::BlobsByRange => &self.blbrange_rl,
+            Protocol::BlobsByRoot => &self.blbroot_rl,
+            Protocol::DataColumnsByRoot => &self.dcbroot_rl,
+            Protocol::DataColumnsByRange => &self.dcbrange_rl,
+            Protocol::LightClientBootstrap => &self.lc_bootstrap_rl,
+            Protocol::LightClientOptimisticUpdate => &self.lc_optimistic_update_rl,
+            Protocol::LightClientFinalityUpdate => &self.lc_finality_update_rl,
+            Protocol::LightClientUpdatesByRange => &self.lc_updates_by_range_rl,
+        }
+    }
 
-        let check =
-            |limiter: &mut Limiter<PeerId>| limiter.allows(time_since_start, peer_id, tokens);
-        let limiter = match request.protocol() {
+    fn limiter_mut(&mut self, protocol: Protocol) -> &mut Limiter<PeerId> {
+        match protocol {
             Protocol::Ping => &mut self.ping_rl,
             Protocol::Status => &mut self.status_rl,
             Protocol::MetaData => &mut self.metadata_rl,
@@ -312,11 +486,13 @@ impl RPCRateLimiter {
             Protocol::BlocksByRoot => &mut self.bbroots_rl,
             Protocol::BlobsByRange => &mut self.blbrange_rl,
             Protocol::BlobsByRoot => &mut self.blbroot_rl,
+            Protocol::DataColumnsByRoot => &mut self.dcbroot_rl,
+            Protocol::DataColumnsByRange => &mut self.dcbrange_rl,
             Protocol::LightClientBootstrap => &mut self.lc_bootstrap_rl,
             Protocol::LightClientOptimisticUpdate => &mut self.lc_optimistic_update_rl,
             Protocol::LightClientFinalityUpdate => &mut self.lc_finality_update_rl,
-        };
-        check(limiter)
+            Protocol::LightClientUpdatesByRange => &mut self.lc_updates_by_range_rl,
+        }
     }
 
     pub fn prune(&mut self) {
@@ -329,6 +505,8 @@ impl RPCRateLimiter {
         self.bbroots_rl.prune(time_since_start);
         self.blbrange_rl.prune(time_since_start);
         self.blbroot_rl.prune(time_since_start);
+        self.dcbroot_rl.prune(time_since_start);
+        self.dcbrange_rl.prune(time_since_start);
     }
 }
 
@@ -347,6 +525,8 @@ impl Future for RPCRateLimiter {
 /// Per key rate limiter using the token bucket / leaky bucket as a meter rate limiting algorithm,
 /// with the GCRA implementation.
 pub struct Limiter<Key: Hash + Eq + Clone> {
+    /// The quota this limiter enforces.
+    quota: Quota,
     /// After how long is the bucket considered full via replenishing 1T every `t`.
     tau: Nanosecs,
     /// How often is 1T replenished.
@@ -357,37 +537,57 @@ pub struct Limiter<Key: Hash + Eq + Clone> {
 
 impl<Key: Hash + Eq + Clone> Limiter<Key> {
     pub fn from_quota(quota: Quota) -> Result<Self, &'static str> {
-        if quota.max_tokens == 0 {
-            return Err("Max number of tokens should be positive");
-        }
-        let tau = quota.replenish_all_every.as_nanos();
-        if tau == 0 {
-            return Err("Replenish time must be positive");
-        }
-        let t = (tau / quota.max_tokens as u128)
-            .try_into()
-            .map_err(|_| "total replenish time is too long")?;
-        let tau = tau
-            .try_into()
-            .map_err(|_| "total replenish time is too long")?;
+        let (tau, t) = quota.tau_and_t()?;
         Ok(Limiter {
+            quota,
             tau,
             t,
             tat_per_key: FnvHashMap::default(),
         })
     }
 
+    /// Replaces the quota of the limiter, keeping the TAT of every key.
+    pub fn set_quota(&mut self, quota: Quota) -> Result<(), &'static str> {
+        let (tau, t) = quota.tau_and_t()?;
+        self.quota = quota;
+        self.tau = tau;
+        self.t = t;
+        Ok(())
+    }
+
     pub fn allows(
         &mut self,
         time_since_start: Duration,
         key: &Key,
         tokens: u64,
+    ) -> Result<(), RateLimitedErr> {
+        let t = self.t;
+        self.allows_with_t(time_since_start, key, tokens, t)
+    }
+
+    /// Like `allows`, but with `multiplier` times as many tokens replenished in the same period.
+    pub fn allows_with_multiplier(
+        &mut self,
+        time_since_start: Duration,
+        key: &Key,
+        tokens: u64,
+        multiplier: f64,
+    ) -> Result<(), RateLimitedErr> {
+        let t = ((self.t as f64 / multiplier) as Nanosecs).max(1);
+        self.allows_with_t(time_since_start, key, tokens, t)
+    }
+
+    fn allows_with_t(
+        &mut self,
+        time_since_start: Duration,
+        key: &Key,
+        tokens: u64,
+        t: Nanosecs,
     ) -> Result<(), RateLimitedErr> {
         let time_since_start = time_since_start.as_nanos() as u64;
         let tau = self.tau;
-        let t = self.t;
         // how long does it take to replenish these tokens
-        let additional_time = t * tokens;
+        let additional_time = t.saturating_mul(tokens);
         if additional_time > tau {
             // the time required to process this amount of tokens is longer than the time that
             // makes the bucket full. So, this batch can _never_ be processed
@@ -424,8 +624,13 @@ impl<Key: Hash + Eq + Clone> Limiter<Key> {
 
 #[cfg(test)]
 mod tests {
-    use crate::rpc::rate_limiter::{Limiter, Quota};
+    use crate::rpc::config::RateLimiterConfig;
+    use crate::rpc::methods::{DataColumnsByRangeRequest, DataColumnsByRootRequest};
+    use crate::rpc::rate_limiter::{Limiter, Quota, RPCRateLimiter, RateLimitedErr};
+    use crate::rpc::InboundRequest;
+    use libp2p::PeerId;
     use std::time::Duration;
+    use types::{DataColumnIdentifier, EthSpec, Hash256, MainnetEthSpec};
 
     #[test]
     fn it_works_a() {
@@ -492,4 +697,100 @@ mod tests {
             .allows(Duration::from_secs_f32(0.4), &key, 1)
             .is_err());
     }
+
+    #[test]
+    fn multiplier_scales_quota() {
+        let mut limiter = Limiter::from_quota(Quota::n_every(2, 2)).unwrap();
+        let key = 10;
+
+        // A batch larger than the quota is only possible with a multiplier.
+        assert!(limiter
+            .allows(Duration::from_secs_f32(0.0), &key, 4)
+            .is_err());
+        assert!(limiter
+            .allows_with_multiplier(Duration::from_secs_f32(0.0), &key, 4, 2.0)
+            .is_ok());
+        assert!(limiter
+            .allows_with_multiplier(Duration::from_secs_f32(0.1), &key, 1, 2.0)
+            .is_err());
+        // One token is replenished every 0.5s when doubled, instead of every 1s.
+        assert!(limiter
+            .allows_with_multiplier(Duration::from_secs_f32(0.5), &key, 1, 2.0)
+            .is_ok());
+    }
+
+    #[test]
+    fn set_quota_keeps_state() {
+        let mut limiter = Limiter::from_quota(Quota::n_every(2, 2)).unwrap();
+        let key = 10;
+
+        assert!(limiter
+            .allows(Duration::from_secs_f32(0.0), &key, 2)
+            .is_ok());
+        assert!(limiter.set_quota(Quota::n_every(0, 2)).is_err());
+        limiter.set_quota(Quota::n_every(4, 2)).unwrap();
+        assert_eq!(limiter.quota, Quota::n_every(4, 2));
+        // The previous batch is still accounted for, but tokens are now replenished every 0.5s.
+        assert!(limiter
+            .allows(Duration::from_secs_f32(0.0), &key, 1)
+            .is_err());
+        assert!(limiter
+            .allows(Duration::from_secs_f32(0.5), &key, 1)
+            .is_ok());
+    }
+
+    #[test]
+    fn validate_quota() {
+        assert!(Quota::n_every(2, 2).validate().is_ok());
+        assert!(Quota::n_every(0, 2).validate().is_err());
+        assert!(Quota::n_every(2, 0).validate().is_err());
+        // Too long to be represented in nanoseconds.
+        assert!(Quota::n_every(2, u64::MAX).validate().is_err());
+    }
+
+    #[test]
+    fn data_column_quotas() {
+        let spec = MainnetEthSpec::default_spec();
+        let config: RateLimiterConfig =
+            "data_column_sidecars_by_root:2/100;data_column_sidecars_by_range:4/100"
+                .parse()
+                .unwrap();
+        let mut limiter = RPCRateLimiter::new_with_config(config).unwrap();
+        let peer_id = PeerId::random();
+
+        // Data columns by range consumes one token per requested column per slot.
+        let by_range =
+            InboundRequest::<MainnetEthSpec>::DataColumnsByRange(DataColumnsByRangeRequest {
+                start_slot: 0,
+                count: 2,
+                columns: vec![1, 2],
+            });
+        assert!(limiter.allows(&peer_id, &by_range).is_ok());
+        assert!(matches!(
+            limiter.allows(&peer_id, &by_range),
+            Err(RateLimitedErr::TooSoon(_))
+        ));
+
+        // Data columns by root is limited independently, one token per identifier.
+        let by_root = |n: u64| {
+            InboundRequest::<MainnetEthSpec>::DataColumnsByRoot(DataColumnsByRootRequest::new(
+                (0..n)
+                    .map(|index| DataColumnIdentifier {
+                        block_root: Hash256::zero(),
+                        index,
+                    })
+                    .collect(),
+                &spec,
+            ))
+        };
+        assert!(matches!(
+            limiter.allows(&peer_id, &by_root(3)),
+            Err(RateLimitedErr::TooLarge)
+        ));
+        assert!(limiter.allows(&peer_id, &by_root(2)).is_ok());
+        assert!(matches!(
+            limiter.allows(&peer_id, &by_root(1)),
+            Err(RateLimitedErr::TooSoon(_))
+        ));
+    }
 }
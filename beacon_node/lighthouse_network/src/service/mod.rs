@@ -1,11 +1,13 @@
 use self::behaviour::Behaviour;
 use self::gossip_cache::GossipCache;
+use self::recorder::{BackgroundRecorder, TrafficRecorder};
 use crate::config::{gossipsub_config, GossipsubConfigParams, NetworkLoad};
 use crate::discovery::{
     subnet_predicate, DiscoveredPeers, Discovery, FIND_NODE_QUERY_CLOSEST_PEERS,
 };
 use crate::peer_manager::{
-    config::Config as PeerManagerCfg, peerdb::score::PeerAction, peerdb::score::ReportSource,
+    config::Config as PeerManagerCfg, peer_policy::PeerPolicy,
+    peerdb::persisted_peer::PersistedPeer, peerdb::score::PeerAction, peerdb::score::ReportSource,
     ConnectionDirection, PeerManager, PeerManagerEvent,
 };
 use crate::peer_manager::{MIN_OUTBOUND_ONLY_FACTOR, PEER_EXCESS_FACTOR, PRIORITY_PEER_EXCESS};
@@ -50,6 +52,7 @@ pub mod api_types;
 mod behaviour;
 mod gossip_cache;
 pub mod gossipsub_scoring_parameters;
+pub mod recorder;
 pub mod utils;
 /// The number of peers we target per subnet for discovery queries.
 pub const TARGET_SUBNET_PEERS: usize = 3;
@@ -120,7 +123,11 @@ pub struct Network<E: EthSpec> {
     enr_fork_id: EnrForkId,
     /// Directory where metadata is stored.
     network_dir: PathBuf,
+    /// The file the peer policy is read from and written to, if any.
+    peer_policy_file: Option<PathBuf>,
     fork_context: Arc<ForkContext>,
+    /// Records the messages received from peers, if enabled.
+    traffic_recorder: Option<BackgroundRecorder<E>>,
     /// Gossipsub score parameters.
     score_settings: PeerScoreSettings<E>,
     /// The interval for updating gossipsub scores
@@ -319,6 +326,7 @@ impl<E: EthSpec> Network<E> {
             config.outbound_rate_limiter_config.clone(),
             log.clone(),
             network_params,
+            network_globals.clone(),
         );
 
         let discovery = {
@@ -443,12 +451,29 @@ impl<E: EthSpec> Network<E> {
             }
         };
 
+        let traffic_recorder = match &config.network_recording_file {
+            Some(path) => {
+                info!(
+                    log,
+                    "Recording network traffic";
+                    "path" => %path.display(),
+                    "max_size" => config.network_recording_max_size,
+                );
+                let recorder = TrafficRecorder::create(path, ctx.fork_context.clone())?
+                    .with_max_size(config.network_recording_max_size);
+                Some(BackgroundRecorder::spawn(recorder, log.clone())?)
+            }
+            None => None,
+        };
+
         let mut network = Network {
             swarm,
             network_globals,
             enr_fork_id,
             network_dir: config.network_dir.clone(),
+            peer_policy_file: config.peer_policy_file.clone(),
             fork_context: ctx.fork_context,
+            traffic_recorder,
             score_settings,
             update_gossipsub_scores,
             gossip_cache,
@@ -456,6 +481,11 @@ impl<E: EthSpec> Network<E> {
             log,
         };
 
+        if let Some(peer_policy_file) = &config.peer_policy_file {
+            let peer_policy = PeerPolicy::from_file(peer_policy_file)?;
+            network.peer_manager_mut().set_peer_policy(peer_policy);
+        }
+
         network.start(&config).await?;
 
         let network_globals = network.network_globals.clone();
@@ -994,6 +1024,55 @@ impl<E: EthSpec> Network<E> {
         self.discovery_mut().add_enr(enr);
     }
 
+    /// Returns the reputation and client information of known peers, to be persisted.
+    pub fn persisted_peers(&self) -> Vec<PersistedPeer> {
+        self.network_globals.peers.read().persisted_peers()
+    }
+
+    /// Restores peers which were persisted before a restart.
+    pub fn restore_peers(&mut self, persisted_peers: Vec<PersistedPeer>) {
+        self.peer_manager_mut().restore_peers(persisted_peers);
+    }
+
+    /// Lifts the bans of all banned peers, returning the number of peers which were unbanned.
+    pub fn clear_peer_bans(&mut self) -> usize {
+        self.peer_manager_mut().clear_bans()
+    }
+
+    /// Replaces the peer policy, writing it to the peer policy file if one is configured.
+    pub fn update_peer_policy(&mut self, peer_policy: PeerPolicy) -> Result<(), String> {
+        peer_policy.validate()?;
+        if let Some(peer_policy_file) = &self.peer_policy_file {
+            peer_policy.to_file(peer_policy_file)?;
+        }
+        self.peer_manager_mut().set_peer_policy(peer_policy);
+        Ok(())
+    }
+
+    /// Re-reads the peer policy from the peer policy file.
+    pub fn reload_peer_policy(&mut self) -> Result<(), String> {
+        let peer_policy_file = self
+            .peer_policy_file
+            .as_ref()
+            .ok_or("No peer policy file is configured")?;
+        let peer_policy = PeerPolicy::from_file(peer_policy_file)?;
+        self.peer_manager_mut().set_peer_policy(peer_policy);
+        Ok(())
+    }
+
+    /// Replaces the inbound RPC quotas of the given protocols and sets or removes the quota
+    /// multipliers of the given peers.
+    ///
+    /// Either every update is applied, or none are.
+    pub fn update_inbound_rate_limits(
+        &mut self,
+        quotas: Vec<(Protocol, Quota)>,
+        peer_multipliers: Vec<(PeerId, Option<f64>)>,
+    ) -> Result<(), String> {
+        self.eth2_rpc_mut()
+            .update_inbound_rate_limits(quotas, peer_multipliers)
+    }
+
     /// Updates a subnet value to the ENR attnets/syncnets bitfield.
     ///
     /// The `value` is `true` if a subnet is being added and false otherwise.
@@ -1191,6 +1270,10 @@ impl<E: EthSpec> Network<E> {
                 &metrics::TOTAL_RPC_REQUESTS,
                 &["light_client_finality_update"],
             ),
+            Request::LightClientUpdatesByRange(_) => metrics::inc_counter_vec(
+                &metrics::TOTAL_RPC_REQUESTS,
+                &["light_client_updates_by_range"],
+            ),
             Request::BlocksByRange { .. } => {
                 metrics::inc_counter_vec(&metrics::TOTAL_RPC_REQUESTS, &["blocks_by_range"])
             }
@@ -1203,6 +1286,12 @@ impl<E: EthSpec> Network<E> {
             Request::BlobsByRoot { .. } => {
                 metrics::inc_counter_vec(&metrics::TOTAL_RPC_REQUESTS, &["blobs_by_root"])
             }
+            Request::DataColumnsByRoot { .. } => {
+                metrics::inc_counter_vec(&metrics::TOTAL_RPC_REQUESTS, &["data_columns_by_root"])
+            }
+            Request::DataColumnsByRange { .. } => {
+                metrics::inc_counter_vec(&metrics::TOTAL_RPC_REQUESTS, &["data_columns_by_range"])
+            }
         }
         NetworkEvent::RequestReceived {
             peer_id,
@@ -1237,6 +1326,28 @@ impl<E: EthSpec> Network<E> {
         }
     }
 
+    /// Sends the RPC requests and responses received from peers to the traffic recorder, if
+    /// enabled. Gossip messages are recorded before they are decoded in `inject_gs_event`.
+    fn record_event(&mut self, event: &NetworkEvent<E>) {
+        let Some(recorder) = self.traffic_recorder.as_ref() else {
+            return;
+        };
+        let recording = match event {
+            NetworkEvent::RequestReceived {
+                peer_id, request, ..
+            } => recorder.record_request(*peer_id, request.clone()),
+            NetworkEvent::ResponseReceived {
+                peer_id,
+                id,
+                response,
+            } => recorder.record_response(*peer_id, *id, response.clone()),
+            _ => true,
+        };
+        if !recording {
+            self.traffic_recorder = None;
+        }
+    }
+
     /* Sub-behaviour event handling functions */
 
     /// Handle a gossipsub event.
@@ -1247,6 +1358,16 @@ impl<E: EthSpec> Network<E> {
                 message_id: id,
                 message: gs_msg,
             } => {
+                if let Some(recorder) = self.traffic_recorder.as_ref() {
+                    if !recorder.record_gossip(
+                        propagation_source,
+                        id.clone(),
+                        gs_msg.topic.clone(),
+                        gs_msg.data.clone(),
+                    ) {
+                        self.traffic_recorder = None;
+                    }
+                }
                 // Note: We are keeping track here of the peer that sent us the message, not the
                 // peer that originally published the message.
                 match PubsubMessage::decode(&gs_msg.topic, &gs_msg.data, &self.fork_context) {
@@ -1522,6 +1643,22 @@ impl<E: EthSpec> Network<E> {
                             self.build_request(peer_request_id, peer_id, Request::BlobsByRoot(req));
                         Some(event)
                     }
+                    InboundRequest::DataColumnsByRoot(req) => {
+                        let event = self.build_request(
+                            peer_request_id,
+                            peer_id,
+                            Request::DataColumnsByRoot(req),
+                        );
+                        Some(event)
+                    }
+                    InboundRequest::DataColumnsByRange(req) => {
+                        let event = self.build_request(
+                            peer_request_id,
+                            peer_id,
+                            Request::DataColumnsByRange(req),
+                        );
+                        Some(event)
+                    }
                     InboundRequest::LightClientBootstrap(req) => {
                         let event = self.build_request(
                             peer_request_id,
@@ -1546,6 +1683,14 @@ impl<E: EthSpec> Network<E> {
                         );
                         Some(event)
                     }
+                    InboundRequest::LightClientUpdatesByRange(req) => {
+                        let event = self.build_request(
+                            peer_request_id,
+                            peer_id,
+                            Request::LightClientUpdatesByRange(req),
+                        );
+                        Some(event)
+                    }
                 }
             }
             HandlerEvent::Ok(RPCReceived::Response(id, resp)) => {
@@ -1579,6 +1724,12 @@ impl<E: EthSpec> Network<E> {
                     RPCResponse::BlobsByRoot(resp) => {
                         self.build_response(id, peer_id, Response::BlobsByRoot(Some(resp)))
                     }
+                    RPCResponse::DataColumnsByRoot(resp) => {
+                        self.build_response(id, peer_id, Response::DataColumnsByRoot(Some(resp)))
+                    }
+                    RPCResponse::DataColumnsByRange(resp) => {
+                        self.build_response(id, peer_id, Response::DataColumnsByRange(Some(resp)))
+                    }
                     // Should never be reached
                     RPCResponse::LightClientBootstrap(bootstrap) => {
                         self.build_response(id, peer_id, Response::LightClientBootstrap(bootstrap))
@@ -1593,6 +1744,11 @@ impl<E: EthSpec> Network<E> {
                         peer_id,
                         Response::LightClientFinalityUpdate(update),
                     ),
+                    RPCResponse::LightClientUpdatesByRange(update) => self.build_response(
+                        id,
+                        peer_id,
+                        Response::LightClientUpdatesByRange(Some(update)),
+                    ),
                 }
             }
             HandlerEvent::Ok(RPCReceived::EndOfStream(id, termination)) => {
@@ -1601,6 +1757,11 @@ impl<E: EthSpec> Network<E> {
                     ResponseTermination::BlocksByRoot => Response::BlocksByRoot(None),
                     ResponseTermination::BlobsByRange => Response::BlobsByRange(None),
                     ResponseTermination::BlobsByRoot => Response::BlobsByRoot(None),
+                    ResponseTermination::DataColumnsByRoot => Response::DataColumnsByRoot(None),
+                    ResponseTermination::DataColumnsByRange => Response::DataColumnsByRange(None),
+                    ResponseTermination::LightClientUpdatesByRange => {
+                        Response::LightClientUpdatesByRange(None)
+                    }
                 };
                 self.build_response(id, peer_id, response)
             }
@@ -1856,6 +2017,7 @@ impl<E: EthSpec> Network<E> {
             };
 
             if let Some(ev) = maybe_event {
+                self.record_event(&ev);
                 return Poll::Ready(ev);
             }
         }
@@ -0,0 +1,1100 @@
+//! Records the gossip messages and RPC traffic received from peers, so that it can be replayed
+//! against a local chain to reproduce issues deterministically.
+//!
+//! A recording starts with a header, followed by length-prefixed SSZ encoded records. Gossip
+//! messages are stored snappy compressed, as they are sent on the wire. RPC messages are stored
+//! SSZ encoded, along with the protocol and fork required to decode them.
+//!
+//! On a running node, records are written by a dedicated thread (see `BackgroundRecorder`) so that
+//! recording never delays the network service.
+
+use crate::metrics;
+use crate::rpc::codec::ssz_snappy::{handle_rpc_request, handle_rpc_response};
+use crate::rpc::methods::{
+    BlocksByRangeRequest, BlocksByRootRequest, OldBlocksByRangeRequest, OldBlocksByRangeRequestV1,
+    OldBlocksByRangeRequestV2, RPCResponse,
+};
+use crate::rpc::{InboundRequest, SupportedProtocol};
+use crate::service::api_types::{
+    AppRequestId, Request, Response, SingleLookupReqId, SyncRequestId,
+};
+use crate::types::PubsubMessage;
+use gossipsub::{MessageId, TopicHash};
+use libp2p::PeerId;
+use slog::{warn, Logger};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use strum::IntoEnumIterator;
+use types::{EthSpec, ForkContext, ForkName};
+
+/// The bytes every recording starts with.
+const RECORDING_MAGIC: &[u8; 4] = b"LHNR";
+/// The version of the recording format.
+const RECORDING_VERSION: u8 = 1;
+/// The length of the header: the magic bytes, the version and the start time.
+const HEADER_LEN: usize = 13;
+/// Records longer than this are considered corrupt when reading a recording.
+const MAX_RECORD_LEN: usize = 64 * 1024 * 1024;
+/// How often buffered records are written to the recording.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+/// The number of messages which can wait for the writer thread before further messages are dropped.
+const RECORDER_CHANNEL_CAPACITY: usize = 4_096;
+
+/// The kinds of records.
+const GOSSIP: u8 = 0;
+const REQUEST: u8 = 1;
+const RESPONSE: u8 = 2;
+const END_OF_STREAM: u8 = 3;
+
+/// A single message received from a peer, as stored in a recording.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+struct Record {
+    /// Milliseconds since the start of the recording.
+    timestamp_ms: u64,
+    kind: u8,
+    peer_id: Vec<u8>,
+    /// The gossip topic, or the protocol of an RPC message.
+    protocol: Vec<u8>,
+    /// The gossip message id, or the id of the request an RPC response belongs to.
+    id: Vec<u8>,
+    /// The fork an RPC response is decoded with. Empty if the response has no context bytes.
+    fork_name: Vec<u8>,
+    /// The snappy compressed gossip message, or the SSZ encoded RPC message.
+    payload: Vec<u8>,
+}
+
+/// A message received from a peer, as read from a recording.
+#[derive(Debug, Clone)]
+pub enum RecordedEvent<E: EthSpec> {
+    /// A gossip message, with the peer it was received from.
+    Gossip {
+        id: MessageId,
+        source: PeerId,
+        topic: TopicHash,
+        message: PubsubMessage<E>,
+    },
+    /// An RPC request sent to us.
+    Request { peer_id: PeerId, request: Request },
+    /// A response to an RPC request we sent.
+    Response {
+        peer_id: PeerId,
+        id: AppRequestId,
+        response: Response<E>,
+    },
+}
+
+/// A `RecordedEvent` along with the time it was received.
+#[derive(Debug, Clone)]
+pub struct RecordedMessage<E: EthSpec> {
+    /// The time since the start of the recording.
+    pub elapsed: Duration,
+    pub event: RecordedEvent<E>,
+}
+
+/// An error reading a message from a recording.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordingError {
+    /// The recording is corrupt or could not be read. No further messages are read from it.
+    Read(String),
+    /// A record could not be decoded into a message. Later messages may still be read.
+    Decode(String),
+}
+
+/// Writes the messages received from peers to a recording.
+pub struct TrafficRecorder<W: Write> {
+    writer: W,
+    fork_context: Arc<ForkContext>,
+    start: Instant,
+    last_flush: Instant,
+    /// The number of bytes written to the recording so far.
+    written: u64,
+    /// Recording stops before the recording grows beyond this many bytes.
+    max_size: Option<u64>,
+}
+
+impl TrafficRecorder<BufWriter<File>> {
+    /// Creates a recording at `path`, replacing any existing file.
+    pub fn create(path: &Path, fork_context: Arc<ForkContext>) -> Result<Self, String> {
+        let file = File::create(path)
+            .map_err(|e| format!("Unable to create {}: {}", path.display(), e))?;
+        Self::new(BufWriter::new(file), fork_context)
+    }
+}
+
+impl<W: Write> TrafficRecorder<W> {
+    /// Starts a recording, writing its header to `writer`.
+    pub fn new(mut writer: W, fork_context: Arc<ForkContext>) -> Result<Self, String> {
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        writer
+            .write_all(RECORDING_MAGIC)
+            .and_then(|()| writer.write_all(&[RECORDING_VERSION]))
+            .and_then(|()| writer.write_all(&started_at.to_le_bytes()))
+            .map_err(|e| format!("Unable to write recording header: {}", e))?;
+        let now = Instant::now();
+        Ok(Self {
+            writer,
+            fork_context,
+            start: now,
+            last_flush: now,
+            written: HEADER_LEN as u64,
+            max_size: None,
+        })
+    }
+
+    /// Limits the recording to `max_size` bytes. Writing a record which would make the recording
+    /// larger fails, leaving the recording ending with the last record which fit.
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Records a gossip message. `data` is the decompressed message, which may not be valid.
+    pub fn record_gossip(
+        &mut self,
+        source: PeerId,
+        id: &MessageId,
+        topic: &TopicHash,
+        data: &[u8],
+    ) -> Result<(), String> {
+        let payload = snap::raw::Encoder::new()
+            .compress_vec(data)
+            .map_err(|e| format!("Unable to compress gossip message: {}", e))?;
+        self.write_record(Record {
+            timestamp_ms: self.elapsed_ms(),
+            kind: GOSSIP,
+            peer_id: source.to_bytes(),
+            protocol: topic.as_str().as_bytes().to_vec(),
+            id: id.0.clone(),
+            fork_name: vec![],
+            payload,
+        })
+    }
+
+    /// Records an RPC request sent to us.
+    pub fn record_request(&mut self, peer_id: PeerId, request: &Request) -> Result<(), String> {
+        let (protocol, payload) = encode_request(request);
+        self.write_record(Record {
+            timestamp_ms: self.elapsed_ms(),
+            kind: REQUEST,
+            peer_id: peer_id.to_bytes(),
+            protocol: protocol_to_string(protocol).into_bytes(),
+            id: vec![],
+            fork_name: vec![],
+            payload,
+        })
+    }
+
+    /// Records a response to an RPC request we sent.
+    pub fn record_response<E: EthSpec>(
+        &mut self,
+        peer_id: PeerId,
+        id: &AppRequestId,
+        response: &Response<E>,
+    ) -> Result<(), String> {
+        let (protocol, chunk) = encode_response(response, &self.fork_context);
+        let (kind, fork_name, payload) = match chunk {
+            Some((fork_name, payload)) => (RESPONSE, fork_name, payload),
+            None => (END_OF_STREAM, None, vec![]),
+        };
+        self.write_record(Record {
+            timestamp_ms: self.elapsed_ms(),
+            kind,
+            peer_id: peer_id.to_bytes(),
+            protocol: protocol_to_string(protocol).into_bytes(),
+            id: encode_app_request_id(id),
+            fork_name: fork_name
+                .map(|fork_name| fork_name.to_string().into_bytes())
+                .unwrap_or_default(),
+            payload,
+        })
+    }
+
+    /// Writes any buffered records to the recording.
+    pub fn flush(&mut self) -> Result<(), String> {
+        self.last_flush = Instant::now();
+        self.writer
+            .flush()
+            .map_err(|e| format!("Unable to flush recording: {}", e))
+    }
+
+    fn elapsed_ms(&self) -> u64 {
+        self.start.elapsed().as_millis() as u64
+    }
+
+    fn write_record(&mut self, record: Record) -> Result<(), String> {
+        let bytes = record.as_ssz_bytes();
+        let len = 4 + bytes.len() as u64;
+        if let Some(max_size) = self.max_size {
+            if self.written + len > max_size {
+                self.flush()?;
+                return Err(format!(
+                    "Recording reached its maximum size of {} bytes",
+                    max_size
+                ));
+            }
+        }
+        self.writer
+            .write_all(&(bytes.len() as u32).to_le_bytes())
+            .and_then(|()| self.writer.write_all(&bytes))
+            .map_err(|e| format!("Unable to write record: {}", e))?;
+        self.written += len;
+        if self.last_flush.elapsed() >= FLUSH_INTERVAL {
+            self.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// A message waiting to be written to a recording by the writer thread.
+enum PendingRecord<E: EthSpec> {
+    Gossip {
+        source: PeerId,
+        id: MessageId,
+        topic: TopicHash,
+        data: Vec<u8>,
+    },
+    Request {
+        peer_id: PeerId,
+        request: Request,
+    },
+    Response {
+        peer_id: PeerId,
+        id: AppRequestId,
+        response: Response<E>,
+    },
+}
+
+/// Passes the messages received from peers to a dedicated thread, which encodes them and writes
+/// them to a `TrafficRecorder`.
+///
+/// Messages are never waited on: if the writer thread falls behind, further messages are dropped
+/// and counted by the `libp2p_network_recording_dropped_records_total` metric.
+pub struct BackgroundRecorder<E: EthSpec> {
+    sender: SyncSender<PendingRecord<E>>,
+}
+
+impl<E: EthSpec> BackgroundRecorder<E> {
+    /// Spawns the thread writing to `recorder`. The thread stops when the `BackgroundRecorder` is
+    /// dropped, or when writing a record fails.
+    pub fn spawn<W: Write + Send + 'static>(
+        mut recorder: TrafficRecorder<W>,
+        log: Logger,
+    ) -> Result<Self, String> {
+        let (sender, receiver) = mpsc::sync_channel(RECORDER_CHANNEL_CAPACITY);
+        thread::Builder::new()
+            .name("network_recorder".to_string())
+            .spawn(move || {
+                if let Err(e) = write_records(&mut recorder, receiver) {
+                    warn!(log, "Stopped recording network traffic"; "error" => e);
+                }
+            })
+            .map_err(|e| format!("Unable to spawn the recording thread: {}", e))?;
+        Ok(Self { sender })
+    }
+
+    /// Records a gossip message. `data` is the decompressed message, which may not be valid.
+    ///
+    /// Returns `false` once the writer thread has stopped.
+    pub fn record_gossip(
+        &self,
+        source: PeerId,
+        id: MessageId,
+        topic: TopicHash,
+        data: Vec<u8>,
+    ) -> bool {
+        self.send(PendingRecord::Gossip {
+            source,
+            id,
+            topic,
+            data,
+        })
+    }
+
+    /// Records an RPC request sent to us.
+    ///
+    /// Returns `false` once the writer thread has stopped.
+    pub fn record_request(&self, peer_id: PeerId, request: Request) -> bool {
+        self.send(PendingRecord::Request { peer_id, request })
+    }
+
+    /// Records a response to an RPC request we sent.
+    ///
+    /// Returns `false` once the writer thread has stopped.
+    pub fn record_response(
+        &self,
+        peer_id: PeerId,
+        id: AppRequestId,
+        response: Response<E>,
+    ) -> bool {
+        self.send(PendingRecord::Response {
+            peer_id,
+            id,
+            response,
+        })
+    }
+
+    fn send(&self, record: PendingRecord<E>) -> bool {
+        match self.sender.try_send(record) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                metrics::inc_counter(&metrics::NETWORK_RECORDING_DROPPED_RECORDS);
+                true
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        }
+    }
+}
+
+/// Writes the records received on `receiver` until all senders are dropped, flushing whenever no
+/// record arrives for `FLUSH_INTERVAL`.
+fn write_records<E: EthSpec, W: Write>(
+    recorder: &mut TrafficRecorder<W>,
+    receiver: Receiver<PendingRecord<E>>,
+) -> Result<(), String> {
+    loop {
+        let record = match receiver.recv_timeout(FLUSH_INTERVAL) {
+            Ok(record) => record,
+            Err(RecvTimeoutError::Timeout) => {
+                recorder.flush()?;
+                continue;
+            }
+            Err(RecvTimeoutError::Disconnected) => return recorder.flush(),
+        };
+        match record {
+            PendingRecord::Gossip {
+                source,
+                id,
+                topic,
+                data,
+            } => recorder.record_gossip(source, &id, &topic, &data)?,
+            PendingRecord::Request { peer_id, request } => {
+                recorder.record_request(peer_id, &request)?
+            }
+            PendingRecord::Response {
+                peer_id,
+                id,
+                response,
+            } => recorder.record_response(peer_id, &id, &response)?,
+        }
+    }
+}
+
+/// Reads the messages of a recording, in the order they were received.
+pub struct TrafficRecording<E: EthSpec, R: Read> {
+    reader: R,
+    fork_context: Arc<ForkContext>,
+    started_at: Duration,
+    /// Set once the recording can't be read any further.
+    finished: bool,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> TrafficRecording<E, BufReader<File>> {
+    /// Opens the recording at `path`.
+    pub fn open(path: &Path, fork_context: Arc<ForkContext>) -> Result<Self, String> {
+        let file =
+            File::open(path).map_err(|e| format!("Unable to open {}: {}", path.display(), e))?;
+        Self::new(BufReader::new(file), fork_context)
+    }
+}
+
+impl<E: EthSpec, R: Read> TrafficRecording<E, R> {
+    /// Reads the header of a recording from `reader`.
+    ///
+    /// The `fork_context` must be for the network the recording was made on, although the genesis
+    /// validators root may differ.
+    pub fn new(mut reader: R, fork_context: Arc<ForkContext>) -> Result<Self, String> {
+        let mut header = [0; HEADER_LEN];
+        reader
+            .read_exact(&mut header)
+            .map_err(|e| format!("Unable to read recording header: {}", e))?;
+        if &header[..4] != RECORDING_MAGIC {
+            return Err("Not a network recording".to_string());
+        }
+        if header[4] != RECORDING_VERSION {
+            return Err(format!("Unsupported recording version {}", header[4]));
+        }
+        let mut started_at = [0; 8];
+        started_at.copy_from_slice(&header[5..]);
+        Ok(Self {
+            reader,
+            fork_context,
+            started_at: Duration::from_millis(u64::from_le_bytes(started_at)),
+            finished: false,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// The time the recording was started, since the UNIX epoch.
+    pub fn started_at(&self) -> Duration {
+        self.started_at
+    }
+
+    fn read_record(&mut self) -> Result<Option<Record>, String> {
+        let mut len = [0; 4];
+        match self.reader.read_exact(&mut len) {
+            Ok(()) => {}
+            // The recording ends cleanly between records.
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(format!("Unable to read record: {}", e)),
+        }
+        let len = u32::from_le_bytes(len) as usize;
+        if len > MAX_RECORD_LEN {
+            return Err(format!("Record length {} is too long", len));
+        }
+        let mut bytes = vec![0; len];
+        self.reader
+            .read_exact(&mut bytes)
+            .map_err(|e| format!("Unable to read record: {}", e))?;
+        Record::from_ssz_bytes(&bytes)
+            .map(Some)
+            .map_err(|e| format!("Invalid record: {:?}", e))
+    }
+
+    fn decode_record(&self, record: Record) -> Result<RecordedMessage<E>, String> {
+        let peer_id =
+            PeerId::from_bytes(&record.peer_id).map_err(|e| format!("Invalid peer id: {}", e))?;
+        let protocol = String::from_utf8(record.protocol)
+            .map_err(|_| "Invalid record protocol".to_string())?;
+        let event = match record.kind {
+            GOSSIP => {
+                let topic = TopicHash::from_raw(protocol);
+                let data = snap::raw::Decoder::new()
+                    .decompress_vec(&record.payload)
+                    .map_err(|e| format!("Unable to decompress gossip message: {}", e))?;
+                let message = PubsubMessage::decode(&topic, &data, &self.fork_context)?;
+                RecordedEvent::Gossip {
+                    id: MessageId::new(&record.id),
+                    source: peer_id,
+                    topic,
+                    message,
+                }
+            }
+            REQUEST => {
+                let protocol = protocol_from_str(&protocol)?;
+                let request = decode_request::<E>(protocol, &record.payload, &self.fork_context)?;
+                RecordedEvent::Request { peer_id, request }
+            }
+            RESPONSE | END_OF_STREAM => {
+                let protocol = protocol_from_str(&protocol)?;
+                let response = if record.kind == END_OF_STREAM {
+                    end_of_stream(protocol)?
+                } else {
+                    let fork_name = if record.fork_name.is_empty() {
+                        None
+                    } else {
+                        let fork_name = String::from_utf8(record.fork_name)
+                            .map_err(|_| "Invalid record fork name".to_string())?;
+                        Some(fork_name.parse::<ForkName>()?)
+                    };
+                    decode_response(protocol, &record.payload, fork_name)?
+                };
+                RecordedEvent::Response {
+                    peer_id,
+                    id: decode_app_request_id(&record.id)?,
+                    response,
+                }
+            }
+            kind => return Err(format!("Unknown record kind {}", kind)),
+        };
+        Ok(RecordedMessage {
+            elapsed: Duration::from_millis(record.timestamp_ms),
+            event,
+        })
+    }
+}
+
+impl<E: EthSpec, R: Read> Iterator for TrafficRecording<E, R> {
+    type Item = Result<RecordedMessage<E>, RecordingError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.read_record() {
+            Ok(Some(record)) => Some(self.decode_record(record).map_err(RecordingError::Decode)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                // The position of the next record is unknown, so stop reading.
+                self.finished = true;
+                Some(Err(RecordingError::Read(e)))
+            }
+        }
+    }
+}
+
+fn protocol_to_string(protocol: SupportedProtocol) -> String {
+    format!("{}/{}", protocol.protocol(), protocol.version_string())
+}
+
+fn protocol_from_str(protocol: &str) -> Result<SupportedProtocol, String> {
+    SupportedProtocol::iter()
+        .find(|supported| protocol_to_string(*supported) == protocol)
+        .ok_or_else(|| format!("Unknown protocol {}", protocol))
+}
+
+/// Returns the protocol and SSZ encoding of a request, as it would have been received.
+fn encode_request(request: &Request) -> (SupportedProtocol, Vec<u8>) {
+    match request {
+        Request::Status(req) => (SupportedProtocol::StatusV1, req.as_ssz_bytes()),
+        Request::BlocksByRange(BlocksByRangeRequest::V1(req)) => (
+            SupportedProtocol::BlocksByRangeV1,
+            OldBlocksByRangeRequestV1 {
+                start_slot: req.start_slot,
+                count: req.count,
+                step: 1,
+            }
+            .as_ssz_bytes(),
+        ),
+        Request::BlocksByRange(BlocksByRangeRequest::V2(req)) => (
+            SupportedProtocol::BlocksByRangeV2,
+            OldBlocksByRangeRequestV2 {
+                start_slot: req.start_slot,
+                count: req.count,
+                step: 1,
+            }
+            .as_ssz_bytes(),
+        ),
+        Request::BlocksByRoot(BlocksByRootRequest::V1(req)) => (
+            SupportedProtocol::BlocksByRootV1,
+            req.block_roots.as_ssz_bytes(),
+        ),
+        Request::BlocksByRoot(BlocksByRootRequest::V2(req)) => (
+            SupportedProtocol::BlocksByRootV2,
+            req.block_roots.as_ssz_bytes(),
+        ),
+        Request::BlobsByRange(req) => (SupportedProtocol::BlobsByRangeV1, req.as_ssz_bytes()),
+        Request::BlobsByRoot(req) => (
+            SupportedProtocol::BlobsByRootV1,
+            req.blob_ids.as_ssz_bytes(),
+        ),
+        Request::DataColumnsByRoot(req) => (
+            SupportedProtocol::DataColumnsByRootV1,
+            req.data_column_ids.as_ssz_bytes(),
+        ),
+        Request::DataColumnsByRange(req) => {
+            (SupportedProtocol::DataColumnsByRangeV1, req.as_ssz_bytes())
+        }
+        Request::LightClientBootstrap(req) => (
+            SupportedProtocol::LightClientBootstrapV1,
+            req.root.as_ssz_bytes(),
+        ),
+        Request::LightClientOptimisticUpdate => {
+            (SupportedProtocol::LightClientOptimisticUpdateV1, vec![])
+        }
+        Request::LightClientFinalityUpdate => {
+            (SupportedProtocol::LightClientFinalityUpdateV1, vec![])
+        }
+        Request::LightClientUpdatesByRange(req) => (
+            SupportedProtocol::LightClientUpdatesByRangeV1,
+            req.as_ssz_bytes(),
+        ),
+    }
+}
+
+fn decode_request<E: EthSpec>(
+    protocol: SupportedProtocol,
+    bytes: &[u8],
+    fork_context: &ForkContext,
+) -> Result<Request, String> {
+    let request = handle_rpc_request::<E>(protocol, bytes, &fork_context.spec)
+        .map_err(|e| format!("Unable to decode {:?} request: {}", protocol, e))?
+        .ok_or_else(|| format!("Empty {:?} request", protocol))?;
+    match request {
+        InboundRequest::Status(req) => Ok(Request::Status(req)),
+        InboundRequest::BlocksByRange(OldBlocksByRangeRequest::V1(req)) => Ok(
+            Request::BlocksByRange(BlocksByRangeRequest::new_v1(req.start_slot, req.count)),
+        ),
+        InboundRequest::BlocksByRange(OldBlocksByRangeRequest::V2(req)) => Ok(
+            Request::BlocksByRange(BlocksByRangeRequest::new(req.start_slot, req.count)),
+        ),
+        InboundRequest::BlocksByRoot(req) => Ok(Request::BlocksByRoot(req)),
+        InboundRequest::BlobsByRange(req) => Ok(Request::BlobsByRange(req)),
+        InboundRequest::BlobsByRoot(req) => Ok(Request::BlobsByRoot(req)),
+        InboundRequest::DataColumnsByRoot(req) => Ok(Request::DataColumnsByRoot(req)),
+        InboundRequest::DataColumnsByRange(req) => Ok(Request::DataColumnsByRange(req)),
+        InboundRequest::LightClientBootstrap(req) => Ok(Request::LightClientBootstrap(req)),
+        InboundRequest::LightClientOptimisticUpdate => Ok(Request::LightClientOptimisticUpdate),
+        InboundRequest::LightClientFinalityUpdate => Ok(Request::LightClientFinalityUpdate),
+        InboundRequest::LightClientUpdatesByRange(req) => {
+            Ok(Request::LightClientUpdatesByRange(req))
+        }
+        InboundRequest::Goodbye(_) | InboundRequest::Ping(_) | InboundRequest::MetaData(_) => {
+            Err(format!("{:?} requests are not recorded", protocol))
+        }
+    }
+}
+
+/// Returns the protocol of a response and, unless it ends a stream of responses, its fork and SSZ
+/// encoding. The fork is the one that determines the context bytes on the wire.
+#[allow(clippy::type_complexity)]
+fn encode_response<E: EthSpec>(
+    response: &Response<E>,
+    fork_context: &ForkContext,
+) -> (SupportedProtocol, Option<(Option<ForkName>, Vec<u8>)>) {
+    match response {
+        Response::Status(resp) => (
+            SupportedProtocol::StatusV1,
+            Some((None, resp.as_ssz_bytes())),
+        ),
+        Response::BlocksByRange(resp) => (
+            SupportedProtocol::BlocksByRangeV2,
+            resp.as_ref()
+                .map(|block| (Some(block.fork_name_unchecked()), block.as_ssz_bytes())),
+        ),
+        Response::BlocksByRoot(resp) => (
+            SupportedProtocol::BlocksByRootV2,
+            resp.as_ref()
+                .map(|block| (Some(block.fork_name_unchecked()), block.as_ssz_bytes())),
+        ),
+        Response::BlobsByRange(resp) => (
+            SupportedProtocol::BlobsByRangeV1,
+            resp.as_ref()
+                .map(|blob| (Some(ForkName::Deneb), blob.as_ssz_bytes())),
+        ),
+        Response::BlobsByRoot(resp) => (
+            SupportedProtocol::BlobsByRootV1,
+            resp.as_ref()
+                .map(|blob| (Some(ForkName::Deneb), blob.as_ssz_bytes())),
+        ),
+        Response::DataColumnsByRoot(resp) => (
+            SupportedProtocol::DataColumnsByRootV1,
+            resp.as_ref().map(|data_column| {
+                let fork_name = fork_context.spec.fork_name_at_slot::<E>(data_column.slot());
+                (Some(fork_name), data_column.as_ssz_bytes())
+            }),
+        ),
+        Response::DataColumnsByRange(resp) => (
+            SupportedProtocol::DataColumnsByRangeV1,
+            resp.as_ref().map(|data_column| {
+                let fork_name = fork_context.spec.fork_name_at_slot::<E>(data_column.slot());
+                (Some(fork_name), data_column.as_ssz_bytes())
+            }),
+        ),
+        Response::LightClientBootstrap(resp) => (
+            SupportedProtocol::LightClientBootstrapV1,
+            Some((
+                Some(resp.map_with_fork_name(|fork_name| fork_name)),
+                resp.as_ssz_bytes(),
+            )),
+        ),
+        Response::LightClientOptimisticUpdate(resp) => (
+            SupportedProtocol::LightClientOptimisticUpdateV1,
+            Some((
+                Some(resp.map_with_fork_name(|fork_name| fork_name)),
+                resp.as_ssz_bytes(),
+            )),
+        ),
+        Response::LightClientFinalityUpdate(resp) => (
+            SupportedProtocol::LightClientFinalityUpdateV1,
+            Some((
+                Some(resp.map_with_fork_name(|fork_name| fork_name)),
+                resp.as_ssz_bytes(),
+            )),
+        ),
+        Response::LightClientUpdatesByRange(resp) => (
+            SupportedProtocol::LightClientUpdatesByRangeV1,
+            resp.as_ref().map(|update| {
+                (
+                    Some(update.map_with_fork_name(|fork_name| fork_name)),
+                    update.as_ssz_bytes(),
+                )
+            }),
+        ),
+    }
+}
+
+fn decode_response<E: EthSpec>(
+    protocol: SupportedProtocol,
+    bytes: &[u8],
+    fork_name: Option<ForkName>,
+) -> Result<Response<E>, String> {
+    let response = handle_rpc_response::<E>(protocol, bytes, fork_name)
+        .map_err(|e| format!("Unable to decode {:?} response: {}", protocol, e))?
+        .ok_or_else(|| format!("Empty {:?} response", protocol))?;
+    match response {
+        RPCResponse::Status(resp) => Ok(Response::Status(resp)),
+        RPCResponse::BlocksByRange(resp) => Ok(Response::BlocksByRange(Some(resp))),
+        RPCResponse::BlocksByRoot(resp) => Ok(Response::BlocksByRoot(Some(resp))),
+        RPCResponse::BlobsByRange(resp) => Ok(Response::BlobsByRange(Some(resp))),
+        RPCResponse::BlobsByRoot(resp) => Ok(Response::BlobsByRoot(Some(resp))),
+        RPCResponse::DataColumnsByRoot(resp) => Ok(Response::DataColumnsByRoot(Some(resp))),
+        RPCResponse::DataColumnsByRange(resp) => Ok(Response::DataColumnsByRange(Some(resp))),
+        RPCResponse::LightClientBootstrap(resp) => Ok(Response::LightClientBootstrap(resp)),
+        RPCResponse::LightClientOptimisticUpdate(resp) => {
+            Ok(Response::LightClientOptimisticUpdate(resp))
+        }
+        RPCResponse::LightClientFinalityUpdate(resp) => {
+            Ok(Response::LightClientFinalityUpdate(resp))
+        }
+        RPCResponse::LightClientUpdatesByRange(resp) => {
+            Ok(Response::LightClientUpdatesByRange(Some(resp)))
+        }
+        RPCResponse::Pong(_) | RPCResponse::MetaData(_) => {
+            Err(format!("{:?} responses are not recorded", protocol))
+        }
+    }
+}
+
+/// Returns the response that ends a stream of responses for `protocol`.
+fn end_of_stream<E: EthSpec>(protocol: SupportedProtocol) -> Result<Response<E>, String> {
+    match protocol {
+        SupportedProtocol::BlocksByRangeV1 | SupportedProtocol::BlocksByRangeV2 => {
+            Ok(Response::BlocksByRange(None))
+        }
+        SupportedProtocol::BlocksByRootV1 | SupportedProtocol::BlocksByRootV2 => {
+            Ok(Response::BlocksByRoot(None))
+        }
+        SupportedProtocol::BlobsByRangeV1 => Ok(Response::BlobsByRange(None)),
+        SupportedProtocol::BlobsByRootV1 => Ok(Response::BlobsByRoot(None)),
+        SupportedProtocol::DataColumnsByRootV1 => Ok(Response::DataColumnsByRoot(None)),
+        SupportedProtocol::DataColumnsByRangeV1 => Ok(Response::DataColumnsByRange(None)),
+        SupportedProtocol::LightClientUpdatesByRangeV1 => {
+            Ok(Response::LightClientUpdatesByRange(None))
+        }
+        _ => Err(format!("{:?} responses are not streamed", protocol)),
+    }
+}
+
+/// Encodes an `AppRequestId` as a tag followed by its numeric ids in little endian.
+fn encode_app_request_id(id: &AppRequestId) -> Vec<u8> {
+    let (tag, ids) = match id {
+        AppRequestId::Router => (0, vec![]),
+        AppRequestId::Sync(SyncRequestId::SingleBlock { id }) => (1, vec![id.lookup_id, id.req_id]),
+        AppRequestId::Sync(SyncRequestId::SingleBlob { id }) => (2, vec![id.lookup_id, id.req_id]),
+        AppRequestId::Sync(SyncRequestId::SingleCustodyColumns { id }) => {
+            (3, vec![id.lookup_id, id.req_id])
+        }
+        AppRequestId::Sync(SyncRequestId::RangeBlockAndBlobs { id }) => (4, vec![*id]),
+    };
+    let mut bytes = vec![tag];
+    for id in ids {
+        bytes.extend_from_slice(&id.to_le_bytes());
+    }
+    bytes
+}
+
+fn decode_app_request_id(bytes: &[u8]) -> Result<AppRequestId, String> {
+    let (tag, ids) = bytes
+        .split_first()
+        .ok_or_else(|| "Missing request id".to_string())?;
+    if ids.len() % 4 != 0 {
+        return Err("Invalid request id".to_string());
+    }
+    let ids = ids
+        .chunks_exact(4)
+        .map(|id| u32::from_le_bytes([id[0], id[1], id[2], id[3]]))
+        .collect::<Vec<_>>();
+    let lookup_id = || match ids.as_slice() {
+        [lookup_id, req_id] => Ok(SingleLookupReqId {
+            lookup_id: *lookup_id,
+            req_id: *req_id,
+        }),
+        _ => Err("Invalid lookup request id".to_string()),
+    };
+    match tag {
+        0 => Ok(AppRequestId::Router),
+        1 => Ok(AppRequestId::Sync(SyncRequestId::SingleBlock {
+            id: lookup_id()?,
+        })),
+        2 => Ok(AppRequestId::Sync(SyncRequestId::SingleBlob {
+            id: lookup_id()?,
+        })),
+        3 => Ok(AppRequestId::Sync(SyncRequestId::SingleCustodyColumns {
+            id: lookup_id()?,
+        })),
+        4 => match ids.as_slice() {
+            [id] => Ok(AppRequestId::Sync(SyncRequestId::RangeBlockAndBlobs {
+                id: *id,
+            })),
+            _ => Err("Invalid range request id".to_string()),
+        },
+        tag => Err(format!("Unknown request id tag {}", tag)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::methods::StatusMessage;
+    use crate::types::{GossipEncoding, GossipKind, GossipTopic};
+    use types::{
+        BeaconBlock, ChainSpec, Epoch, Hash256, MainnetEthSpec, Signature, SignedBeaconBlock, Slot,
+    };
+
+    type E = MainnetEthSpec;
+
+    fn fork_context() -> Arc<ForkContext> {
+        let mut spec = E::default_spec();
+        spec.altair_fork_epoch = Some(Epoch::new(1));
+        Arc::new(ForkContext::new::<E>(Slot::new(0), Hash256::zero(), &spec))
+    }
+
+    fn empty_base_block(spec: &ChainSpec) -> Arc<SignedBeaconBlock<E>> {
+        Arc::new(SignedBeaconBlock::from_block(
+            BeaconBlock::empty(spec),
+            Signature::empty(),
+        ))
+    }
+
+    #[test]
+    fn recording_round_trip() {
+        let fork_context = fork_context();
+        let peer_id = PeerId::random();
+        let block = empty_base_block(&fork_context.spec);
+        let topic = TopicHash::from_raw(
+            GossipTopic::new(
+                GossipKind::BeaconBlock,
+                GossipEncoding::default(),
+                fork_context.to_context_bytes(ForkName::Base).unwrap(),
+            )
+            .to_string(),
+        );
+        let message = PubsubMessage::BeaconBlock(block.clone());
+        let request = Request::BlocksByRange(BlocksByRangeRequest::new(0, 32));
+        let range_id = AppRequestId::Sync(SyncRequestId::RangeBlockAndBlobs { id: 7 });
+        let status = Response::<E>::Status(StatusMessage {
+            fork_digest: [1; 4],
+            finalized_root: Hash256::repeat_byte(2),
+            finalized_epoch: Epoch::new(3),
+            head_root: Hash256::repeat_byte(4),
+            head_slot: Slot::new(5),
+        });
+
+        let mut bytes = vec![];
+        let mut recorder = TrafficRecorder::new(&mut bytes, fork_context.clone()).unwrap();
+        recorder
+            .record_gossip(
+                peer_id,
+                &MessageId::new(&[9]),
+                &topic,
+                &message.encode(GossipEncoding::default()),
+            )
+            .unwrap();
+        recorder.record_request(peer_id, &request).unwrap();
+        recorder
+            .record_response(
+                peer_id,
+                &range_id,
+                &Response::<E>::BlocksByRange(Some(block.clone())),
+            )
+            .unwrap();
+        recorder
+            .record_response(peer_id, &range_id, &Response::<E>::BlocksByRange(None))
+            .unwrap();
+        recorder
+            .record_response(peer_id, &AppRequestId::Router, &status)
+            .unwrap();
+        recorder.flush().unwrap();
+        drop(recorder);
+
+        let events = TrafficRecording::<E, _>::new(bytes.as_slice(), fork_context)
+            .unwrap()
+            .map(|message| message.unwrap().event)
+            .collect::<Vec<_>>();
+        assert_eq!(events.len(), 5);
+        assert!(matches!(
+            &events[0],
+            RecordedEvent::Gossip {
+                id: recorded_id,
+                source,
+                topic: recorded_topic,
+                message: recorded,
+            } if *recorded_id == MessageId::new(&[9])
+                && *source == peer_id
+                && *recorded_topic == topic
+                && *recorded == message
+        ));
+        assert!(matches!(
+            &events[1],
+            RecordedEvent::Request {
+                peer_id: recorded_peer_id,
+                request: recorded,
+            } if *recorded_peer_id == peer_id && *recorded == request
+        ));
+        assert!(matches!(
+            &events[2],
+            RecordedEvent::Response {
+                id: AppRequestId::Sync(SyncRequestId::RangeBlockAndBlobs { id: 7 }),
+                response: Response::BlocksByRange(Some(recorded)),
+                ..
+            } if *recorded == block
+        ));
+        assert!(matches!(
+            &events[3],
+            RecordedEvent::Response {
+                response: Response::BlocksByRange(None),
+                ..
+            }
+        ));
+        assert!(matches!(
+            &events[4],
+            RecordedEvent::Response {
+                id: AppRequestId::Router,
+                response,
+                ..
+            } if *response == status
+        ));
+    }
+
+    #[test]
+    fn invalid_recordings_are_rejected() {
+        let fork_context = fork_context();
+        assert!(
+            TrafficRecording::<E, _>::new(&b"not a recording"[..], fork_context.clone()).is_err()
+        );
+
+        // A recording truncated in the middle of a record.
+        let mut bytes = vec![];
+        let mut recorder = TrafficRecorder::new(&mut bytes, fork_context.clone()).unwrap();
+        recorder
+            .record_request(PeerId::random(), &Request::LightClientFinalityUpdate)
+            .unwrap();
+        recorder.flush().unwrap();
+        drop(recorder);
+        bytes.pop();
+        let mut recording = TrafficRecording::<E, _>::new(bytes.as_slice(), fork_context).unwrap();
+        assert!(matches!(
+            recording.next(),
+            Some(Err(RecordingError::Read(_)))
+        ));
+        assert!(recording.next().is_none());
+    }
+
+    #[test]
+    fn undecodable_records_are_skipped() {
+        let fork_context = fork_context();
+        let peer_id = PeerId::random();
+        let topic = TopicHash::from_raw(
+            GossipTopic::new(
+                GossipKind::BeaconBlock,
+                GossipEncoding::default(),
+                fork_context.to_context_bytes(ForkName::Base).unwrap(),
+            )
+            .to_string(),
+        );
+
+        let mut bytes = vec![];
+        let mut recorder = TrafficRecorder::new(&mut bytes, fork_context.clone()).unwrap();
+        recorder
+            .record_request(peer_id, &Request::LightClientFinalityUpdate)
+            .unwrap();
+        recorder
+            .record_gossip(peer_id, &MessageId::new(&[1]), &topic, &[0xff; 16])
+            .unwrap();
+        recorder
+            .record_request(peer_id, &Request::LightClientOptimisticUpdate)
+            .unwrap();
+        recorder.flush().unwrap();
+        drop(recorder);
+
+        let messages = TrafficRecording::<E, _>::new(bytes.as_slice(), fork_context)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(messages.len(), 3);
+        assert!(matches!(
+            &messages[0],
+            Ok(RecordedMessage {
+                event: RecordedEvent::Request {
+                    request: Request::LightClientFinalityUpdate,
+                    ..
+                },
+                ..
+            })
+        ));
+        assert!(matches!(&messages[1], Err(RecordingError::Decode(_))));
+        assert!(matches!(
+            &messages[2],
+            Ok(RecordedMessage {
+                event: RecordedEvent::Request {
+                    request: Request::LightClientOptimisticUpdate,
+                    ..
+                },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn recording_stops_at_max_size() {
+        let fork_context = fork_context();
+        let peer_id = PeerId::random();
+
+        let mut bytes = vec![];
+        let mut recorder = TrafficRecorder::new(&mut bytes, fork_context.clone()).unwrap();
+        recorder
+            .record_request(peer_id, &Request::LightClientFinalityUpdate)
+            .unwrap();
+        let one_record = recorder.written;
+        let mut recorder = recorder.with_max_size(one_record + 1);
+        assert!(recorder
+            .record_request(peer_id, &Request::LightClientOptimisticUpdate)
+            .is_err());
+        drop(recorder);
+        assert_eq!(bytes.len() as u64, one_record);
+
+        let messages = TrafficRecording::<E, _>::new(bytes.as_slice(), fork_context)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].is_ok());
+    }
+
+    #[test]
+    fn background_recorder_writes_records() {
+        let fork_context = fork_context();
+        let peer_id = PeerId::random();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("network.rec");
+
+        let recorder = BackgroundRecorder::<E>::spawn(
+            TrafficRecorder::create(&path, fork_context.clone()).unwrap(),
+            logging::test_logger(),
+        )
+        .unwrap();
+        assert!(recorder.record_request(peer_id, Request::LightClientFinalityUpdate));
+        assert!(recorder.record_response(
+            peer_id,
+            AppRequestId::Router,
+            Response::BlocksByRange(None)
+        ));
+        // The writer thread flushes and exits once the recorder is dropped.
+        drop(recorder);
+
+        let start = Instant::now();
+        let messages = loop {
+            let messages = TrafficRecording::<E, _>::open(&path, fork_context.clone())
+                .unwrap()
+                .collect::<Vec<_>>();
+            if messages.len() == 2 || start.elapsed() > Duration::from_secs(5) {
+                break messages;
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        assert_eq!(messages.len(), 2);
+        assert!(matches!(
+            &messages[1],
+            Ok(RecordedMessage {
+                event: RecordedEvent::Response {
+                    response: Response::BlocksByRange(None),
+                    ..
+                },
+                ..
+            })
+        ));
+    }
+}
@@ -1,13 +1,15 @@
 //! A collection of variables that are accessible outside of the network thread itself.
+use crate::discovery::peer_id_to_node_id;
 use crate::peer_manager::peerdb::PeerDB;
-use crate::rpc::{MetaData, MetaDataV2};
+use crate::rpc::{InboundRateLimits, MetaData, MetaDataV2};
 use crate::types::{BackFillState, SyncState};
 use crate::Client;
 use crate::EnrExt;
 use crate::{Enr, GossipTopic, Multiaddr, PeerId};
 use parking_lot::RwLock;
 use std::collections::HashSet;
-use types::EthSpec;
+use types::data_column_sidecar::ColumnIndex;
+use types::{ChainSpec, DataColumnSubnetId, EthSpec};
 
 pub struct NetworkGlobals<E: EthSpec> {
     /// The current local ENR.
@@ -26,6 +28,8 @@ pub struct NetworkGlobals<E: EthSpec> {
     pub sync_state: RwLock<SyncState>,
     /// The current state of the backfill sync.
     pub backfill_state: RwLock<BackFillState>,
+    /// The inbound RPC rate limits and the requests they have rejected.
+    pub inbound_rate_limits: RwLock<InboundRateLimits>,
 }
 
 impl<E: EthSpec> NetworkGlobals<E> {
@@ -45,6 +49,7 @@ impl<E: EthSpec> NetworkGlobals<E> {
             gossipsub_subscriptions: RwLock::new(HashSet::new()),
             sync_state: RwLock::new(SyncState::Stalled),
             backfill_state: RwLock::new(BackFillState::NotRequired),
+            inbound_rate_limits: RwLock::new(InboundRateLimits::default()),
         }
     }
 
@@ -103,6 +108,42 @@ impl<E: EthSpec> NetworkGlobals<E> {
             .unwrap_or_default()
     }
 
+    /// Returns the data column indices that this node is required to custody.
+    pub fn custody_columns(&self, spec: &ChainSpec) -> Vec<ColumnIndex> {
+        DataColumnSubnetId::compute_custody_columns::<E>(
+            self.local_enr().node_id().raw().into(),
+            spec.custody_requirement,
+            spec,
+        )
+        .collect()
+    }
+
+    /// Returns the connected peers that are required to custody the data column `column_index`.
+    ///
+    /// Only the minimum custody requirement is assumed for each peer, so peers that advertise a
+    /// larger custody subnet count may custody columns that are not accounted for here.
+    pub fn custody_peers_for_column(
+        &self,
+        column_index: ColumnIndex,
+        spec: &ChainSpec,
+    ) -> Vec<PeerId> {
+        self.peers
+            .read()
+            .connected_peer_ids()
+            .filter(|peer_id| {
+                peer_id_to_node_id(peer_id).map_or(false, |node_id| {
+                    DataColumnSubnetId::compute_custody_columns::<E>(
+                        node_id.raw().into(),
+                        spec.custody_requirement,
+                        spec,
+                    )
+                    .any(|index| index == column_index)
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
     /// Updates the syncing state of the node.
     ///
     /// The old state is returned
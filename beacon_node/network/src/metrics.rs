@@ -224,6 +224,19 @@ lazy_static! {
         "Time taken for network to handle an event sent to the network service.",
         &["type"]
     );
+
+    /*
+     * Operation rebroadcast metrics
+     */
+    pub static ref OPERATION_REBROADCAST_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "network_operation_rebroadcast_total",
+        "Count of pending operations re-published on gossip, per operation type",
+        &["kind"]
+    );
+    pub static ref OPERATION_REBROADCAST_PENDING: Result<IntGauge> = try_create_int_gauge(
+        "network_operation_rebroadcast_pending",
+        "Number of still-valid operations in the op pool tracked for rebroadcast"
+    );
 }
 
 lazy_static! {
@@ -5,6 +5,7 @@ use crate::{
     network_beacon_processor::{
         ChainSegmentProcessId, DuplicateCache, InvalidBlockStorage, NetworkBeaconProcessor,
     },
+    replay::TrafficReplay,
     service::NetworkMessage,
     sync::{manager::BlockProcessType, SyncMessage},
 };
@@ -15,22 +16,30 @@ use beacon_chain::test_utils::{
 use beacon_chain::{BeaconChain, WhenSlotSkipped};
 use beacon_processor::{work_reprocessing_queue::*, *};
 use lighthouse_network::discovery::ConnectionId;
-use lighthouse_network::rpc::methods::BlobsByRangeRequest;
+use lighthouse_network::rpc::methods::{
+    BlobsByRangeRequest, DataColumnsByRangeRequest, DataColumnsByRootRequest,
+};
 use lighthouse_network::rpc::SubstreamId;
+use lighthouse_network::service::recorder::{TrafficRecorder, TrafficRecording};
 use lighthouse_network::{
     discv5::enr::{self, CombinedKey},
     rpc::methods::{MetaData, MetaDataV2},
-    types::{EnrAttestationBitfield, EnrSyncCommitteeBitfield},
-    Client, MessageId, NetworkGlobals, PeerId, Response,
+    types::{
+        EnrAttestationBitfield, EnrSyncCommitteeBitfield, GossipEncoding, GossipKind, GossipTopic,
+    },
+    Client, MessageId, NetworkGlobals, PeerId, PubsubMessage, Response, TopicHash,
 };
 use slot_clock::SlotClock;
 use std::iter::Iterator;
 use std::sync::Arc;
 use std::time::Duration;
+use store::KeyValueStore;
+use task_executor::TaskExecutor;
 use tokio::sync::mpsc;
 use types::blob_sidecar::FixedBlobSidecarList;
 use types::{
-    Attestation, AttesterSlashing, BlobSidecar, BlobSidecarList, Epoch, Hash256, MainnetEthSpec,
+    data_column_sidecar::ColumnIndex, Attestation, AttesterSlashing, BlobSidecar, BlobSidecarList,
+    DataColumnIdentifier, DataColumnSidecar, Epoch, ForkContext, Hash256, MainnetEthSpec,
     ProposerSlashing, SignedAggregateAndProof, SignedBeaconBlock, SignedVoluntaryExit, Slot,
     SubnetId,
 };
@@ -60,11 +69,14 @@ struct TestRig {
     proposer_slashing: ProposerSlashing,
     voluntary_exit: SignedVoluntaryExit,
     beacon_processor_tx: BeaconProcessorSend<E>,
+    work_reprocessing_tx: mpsc::Sender<ReprocessQueueMessage>,
     work_journal_rx: mpsc::Receiver<&'static str>,
     _network_rx: mpsc::UnboundedReceiver<NetworkMessage<E>>,
     _sync_rx: mpsc::UnboundedReceiver<SyncMessage<E>>,
     duplicate_cache: DuplicateCache,
     network_beacon_processor: Arc<NetworkBeaconProcessor<T>>,
+    network_globals: Arc<NetworkGlobals<E>>,
+    executor: TaskExecutor,
     _harness: BeaconChainHarness<T>,
 }
 
@@ -226,15 +238,15 @@ impl TestRig {
         let network_beacon_processor = Arc::new(network_beacon_processor);
 
         let beacon_processor = BeaconProcessor {
-            network_globals,
-            executor,
+            network_globals: network_globals.clone(),
+            executor: executor.clone(),
             current_workers: 0,
             config: beacon_processor_config,
             log: log.clone(),
         }
         .spawn_manager(
             beacon_processor_rx,
-            work_reprocessing_tx,
+            work_reprocessing_tx.clone(),
             work_reprocessing_rx,
             Some(work_journal_tx),
             harness.chain.slot_clock.clone(),
@@ -264,11 +276,14 @@ impl TestRig {
             proposer_slashing,
             voluntary_exit,
             beacon_processor_tx,
+            work_reprocessing_tx,
             work_journal_rx,
             _network_rx,
             _sync_rx,
             duplicate_cache,
             network_beacon_processor,
+            network_globals,
+            executor,
             _harness: harness,
         }
     }
@@ -293,6 +308,66 @@ impl TestRig {
             .unwrap();
     }
 
+    /// Returns a recording containing `next_block` received on gossip.
+    /// Records `next_block` being received on gossip. If `with_invalid_records` is set, it is
+    /// preceded and followed by gossip messages which can't be decoded.
+    pub fn record_gossip_block(
+        &self,
+        fork_context: Arc<ForkContext>,
+        with_invalid_records: bool,
+    ) -> Vec<u8> {
+        let fork_digest = fork_context
+            .to_context_bytes(self.next_block.fork_name_unchecked())
+            .unwrap();
+        let topic = GossipTopic::new(
+            GossipKind::BeaconBlock,
+            GossipEncoding::default(),
+            fork_digest,
+        );
+        let topic = TopicHash::from_raw(topic.to_string());
+        let message = PubsubMessage::BeaconBlock(self.next_block.clone());
+        let invalid_message = [0xff; 16];
+
+        let mut recording = vec![];
+        let mut recorder = TrafficRecorder::new(&mut recording, fork_context).unwrap();
+        if with_invalid_records {
+            recorder
+                .record_gossip(junk_peer_id(), &junk_message_id(), &topic, &invalid_message)
+                .unwrap();
+        }
+        recorder
+            .record_gossip(
+                junk_peer_id(),
+                &junk_message_id(),
+                &topic,
+                &message.encode(GossipEncoding::default()),
+            )
+            .unwrap();
+        if with_invalid_records {
+            recorder
+                .record_gossip(junk_peer_id(), &junk_message_id(), &topic, &invalid_message)
+                .unwrap();
+        }
+        recorder.flush().unwrap();
+        drop(recorder);
+        recording
+    }
+
+    /// Spawns a `Router` which replays recordings to the `BeaconProcessor`.
+    pub fn spawn_traffic_replay(
+        &self,
+    ) -> (TrafficReplay<T>, mpsc::UnboundedReceiver<NetworkMessage<E>>) {
+        TrafficReplay::spawn(
+            self.chain.clone(),
+            self.network_globals.clone(),
+            self.executor.clone(),
+            self.beacon_processor_tx.clone(),
+            self.work_reprocessing_tx.clone(),
+            self.network_beacon_processor.log.clone(),
+        )
+        .unwrap()
+    }
+
     pub fn enqueue_gossip_blob(&self, blob_index: usize) {
         if let Some(blobs) = self.next_blobs.as_ref() {
             let blob = blobs.get(blob_index).unwrap();
@@ -359,6 +434,69 @@ impl TestRig {
             .unwrap();
     }
 
+    pub fn enqueue_data_columns_by_range_request(&self, count: u64, columns: Vec<ColumnIndex>) {
+        self.network_beacon_processor
+            .send_data_columns_by_range_request(
+                PeerId::random(),
+                (ConnectionId::new_unchecked(42), SubstreamId::new(24)),
+                DataColumnsByRangeRequest {
+                    start_slot: 0,
+                    count,
+                    columns,
+                },
+            )
+            .unwrap();
+    }
+
+    pub fn enqueue_data_columns_by_root_request(&self, data_column_ids: Vec<DataColumnIdentifier>) {
+        self.network_beacon_processor
+            .send_data_columns_by_roots_request(
+                PeerId::random(),
+                (ConnectionId::new_unchecked(42), SubstreamId::new(24)),
+                DataColumnsByRootRequest::new(data_column_ids, &self.chain.spec),
+            )
+            .unwrap();
+    }
+
+    /// Stores placeholder data columns with the given `columns` indices for every block with blobs
+    /// in the first `slot_count` slots. Returns the identifiers of the stored columns.
+    pub fn store_data_columns(
+        &self,
+        slot_count: u64,
+        columns: &[ColumnIndex],
+    ) -> Vec<DataColumnIdentifier> {
+        let mut ops = vec![];
+        let mut data_column_ids = vec![];
+        for slot in 0..slot_count {
+            let Some(block_root) = self
+                .chain
+                .block_root_at_slot(Slot::new(slot), WhenSlotSkipped::None)
+                .unwrap()
+            else {
+                continue;
+            };
+            let block = self.chain.get_blinded_block(&block_root).unwrap().unwrap();
+            if block.num_expected_blobs() == 0 {
+                continue;
+            }
+            let data_columns = columns
+                .iter()
+                .map(|&index| {
+                    data_column_ids.push(DataColumnIdentifier { block_root, index });
+                    let mut data_column = DataColumnSidecar::empty();
+                    data_column.index = index;
+                    data_column.signed_block_header = block.signed_block_header();
+                    Arc::new(data_column)
+                })
+                .collect();
+            self.chain
+                .store
+                .data_columns_as_kv_store_ops(&block_root, data_columns, &mut ops);
+        }
+        self.chain.store.blobs_db.do_atomically(ops).unwrap();
+        data_column_ids
+    }
+
     pub fn enqueue_backfill_batch(&self) {
         self.network_beacon_processor
             .send_chain_segment(
@@ -686,6 +824,45 @@ async fn import_gossip_block_at_current_slot() {
     );
 }
 
+/// Ensure a gossip block can be recorded and replayed through the `Router`.
+#[tokio::test]
+async fn replay_recorded_gossip_block() {
+    replay_recorded_gossip_block_with(false).await;
+}
+
+/// Ensure that records which can't be decoded are skipped, rather than ending a replay.
+#[tokio::test]
+async fn replay_recording_with_invalid_records() {
+    replay_recorded_gossip_block_with(true).await;
+}
+
+async fn replay_recorded_gossip_block_with(with_invalid_records: bool) {
+    let mut rig = TestRig::new(SMALL_CHAIN).await;
+
+    let fork_context = Arc::new(ForkContext::new::<E>(
+        rig.chain.slot().unwrap(),
+        rig.chain.genesis_validators_root,
+        &rig.chain.spec,
+    ));
+    let recording = rig.record_gossip_block(fork_context.clone(), with_invalid_records);
+
+    let (mut replay, _network_rx) = rig.spawn_traffic_replay();
+    let recording = TrafficRecording::new(recording.as_slice(), fork_context).unwrap();
+    assert_eq!(replay.replay(recording, false).await, Ok(1));
+
+    rig.assert_event_journal(&[GOSSIP_BLOCK, WORKER_FREED, NOTHING_TO_DO])
+        .await;
+
+    // Blobs aren't recorded, so the block can only become head if it has none.
+    if rig.next_blobs.is_none() {
+        assert_eq!(
+            rig.head_root(),
+            rig.next_block.canonical_root(),
+            "block should be imported and become head"
+        );
+    }
+}
+
 /// Ensure a valid attestation can be imported.
 #[tokio::test]
 async fn import_gossip_attestation() {
@@ -1115,3 +1292,71 @@ async fn test_blobs_by_range() {
     }
     assert_eq!(blob_count, actual_count);
 }
+
+#[tokio::test]
+async fn test_data_columns_by_range() {
+    if test_spec::<E>().deneb_fork_epoch.is_none() {
+        return;
+    };
+    let mut rig = TestRig::new(64).await;
+    let slot_count = 32;
+    let columns = vec![0, 3];
+    // Columns outside of the requested range or indices are not served.
+    let data_column_count = rig.store_data_columns(slot_count, &columns).len();
+    rig.store_data_columns(slot_count * 2, &[1]);
+    rig.enqueue_data_columns_by_range_request(slot_count, columns);
+
+    let mut actual_count = 0;
+    while let Some(next) = rig._network_rx.recv().await {
+        if let NetworkMessage::SendResponse {
+            peer_id: _,
+            response: Response::DataColumnsByRange(data_column),
+            id: _,
+        } = next
+        {
+            if data_column.is_some() {
+                actual_count += 1;
+            } else {
+                break;
+            }
+        } else {
+            panic!("unexpected message {:?}", next);
+        }
+    }
+    assert_eq!(data_column_count, actual_count);
+}
+
+#[tokio::test]
+async fn test_data_columns_by_root() {
+    if test_spec::<E>().deneb_fork_epoch.is_none() {
+        return;
+    };
+    let mut rig = TestRig::new(64).await;
+    let mut data_column_ids = rig.store_data_columns(64, &[2]);
+    let data_column_count = data_column_ids.len();
+    // Unknown columns are skipped.
+    data_column_ids.push(DataColumnIdentifier {
+        block_root: Hash256::repeat_byte(0xff),
+        index: 2,
+    });
+    rig.enqueue_data_columns_by_root_request(data_column_ids);
+
+    let mut actual_count = 0;
+    while let Some(next) = rig._network_rx.recv().await {
+        if let NetworkMessage::SendResponse {
+            peer_id: _,
+            response: Response::DataColumnsByRoot(data_column),
+            id: _,
+        } = next
+        {
+            if data_column.is_some() {
+                actual_count += 1;
+            } else {
+                break;
+            }
+        } else {
+            panic!("unexpected message {:?}", next);
+        }
+    }
+    assert_eq!(data_column_count, actual_count);
+}
@@ -0,0 +1,280 @@
+//! Re-publishes slashings, exits and address changes which sit in the op pool without being
+//! included in a block.
+//!
+//! Operations are only gossiped once when they're first received, so an operation which misses
+//! the mempools of the next few proposers (e.g. because they restarted, or were not yet connected
+//! to the mesh) may never be included. This module periodically selects operations which are still
+//! valid against the head and have been pending for a while, so that they can be published again.
+//!
+//! Peers which have already seen an operation will ignore the rebroadcast via their
+//! `observed_operations` cache without penalising us. To match that cache, operations are
+//! tracked by the validator indices they affect, rather than by their content.
+use crate::metrics;
+use lighthouse_network::PubsubMessage;
+use operation_pool::PendingOperations;
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use std::collections::{BTreeSet, HashMap};
+use types::{Epoch, EthSpec};
+
+/// Identifies an operation in the same way as `ObservedOperations`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum OperationKey {
+    VoluntaryExit(u64),
+    ProposerSlashing(u64),
+    AttesterSlashing(Vec<u64>),
+    BlsToExecutionChange(u64),
+}
+
+impl OperationKey {
+    fn kind(&self) -> &'static str {
+        match self {
+            OperationKey::VoluntaryExit(_) => "voluntary_exit",
+            OperationKey::ProposerSlashing(_) => "proposer_slashing",
+            OperationKey::AttesterSlashing(_) => "attester_slashing",
+            OperationKey::BlsToExecutionChange(_) => "bls_to_execution_change",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PendingOperation {
+    /// The epoch at which the operation was first seen in the op pool.
+    first_seen: Epoch,
+    /// The epoch at which the operation was last rebroadcast, if ever.
+    last_published: Option<Epoch>,
+}
+
+impl PendingOperation {
+    /// The epoch from which the operation's next rebroadcast should be counted.
+    fn last_activity(&self) -> Epoch {
+        self.last_published.unwrap_or(self.first_seen)
+    }
+}
+
+/// Tracks how long operations have been pending, and selects those due to be rebroadcast.
+pub struct OperationRebroadcaster {
+    /// The number of epochs an operation must be pending before it is rebroadcast, which is also
+    /// the minimum number of epochs between rebroadcasts of the same operation.
+    interval_epochs: u64,
+    /// The maximum number of operations to rebroadcast in a single round.
+    max_per_round: usize,
+    pending: HashMap<OperationKey, PendingOperation>,
+}
+
+impl OperationRebroadcaster {
+    pub fn new(interval_epochs: u64, max_per_round: usize) -> Self {
+        Self {
+            interval_epochs,
+            max_per_round,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Returns the operations from `operations` which are due to be rebroadcast at
+    /// `current_epoch`, and records them as published.
+    ///
+    /// `operations` should contain every operation which is still pending, since any operation
+    /// which is absent is assumed to have been included or invalidated and is forgotten.
+    pub fn select<E: EthSpec>(
+        &mut self,
+        current_epoch: Epoch,
+        operations: PendingOperations<E>,
+    ) -> Vec<PubsubMessage<E>> {
+        let PendingOperations {
+            proposer_slashings,
+            attester_slashings,
+            voluntary_exits,
+            bls_to_execution_changes,
+        } = operations;
+
+        let candidates = proposer_slashings
+            .into_iter()
+            .map(|slashing| {
+                (
+                    OperationKey::ProposerSlashing(slashing.proposer_index()),
+                    PubsubMessage::ProposerSlashing(Box::new(slashing)),
+                )
+            })
+            .chain(attester_slashings.into_iter().map(|slashing| {
+                let indices_1 = slashing
+                    .attestation_1()
+                    .attesting_indices_iter()
+                    .copied()
+                    .collect::<BTreeSet<_>>();
+                let slashable_indices = slashing
+                    .attestation_2()
+                    .attesting_indices_iter()
+                    .filter(|index| indices_1.contains(index))
+                    .copied()
+                    .collect::<BTreeSet<_>>();
+                (
+                    OperationKey::AttesterSlashing(slashable_indices.into_iter().collect()),
+                    PubsubMessage::AttesterSlashing(Box::new(slashing)),
+                )
+            }))
+            .chain(voluntary_exits.into_iter().map(|exit| {
+                (
+                    OperationKey::VoluntaryExit(exit.message.validator_index),
+                    PubsubMessage::VoluntaryExit(Box::new(exit)),
+                )
+            }))
+            .chain(bls_to_execution_changes.into_iter().map(|address_change| {
+                (
+                    OperationKey::BlsToExecutionChange(address_change.message.validator_index),
+                    PubsubMessage::BlsToExecutionChange(Box::new(address_change)),
+                )
+            }))
+            .collect::<HashMap<_, _>>();
+
+        // Forget about operations which are no longer pending.
+        self.pending.retain(|key, _| candidates.contains_key(key));
+
+        let mut due = vec![];
+        for (key, message) in candidates {
+            let pending = self.pending.entry(key.clone()).or_insert(PendingOperation {
+                first_seen: current_epoch,
+                last_published: None,
+            });
+            if current_epoch >= pending.last_activity() + self.interval_epochs {
+                due.push((pending.last_activity(), key, message));
+            }
+        }
+
+        // Favour the operations which have been waiting the longest, breaking ties randomly so
+        // that a large backlog is worked through fairly.
+        due.shuffle(&mut thread_rng());
+        due.sort_by_key(|(last_activity, _, _)| *last_activity);
+        due.truncate(self.max_per_round);
+
+        metrics::set_gauge(
+            &metrics::OPERATION_REBROADCAST_PENDING,
+            self.pending.len() as i64,
+        );
+
+        due.into_iter()
+            .map(|(_, key, message)| {
+                metrics::inc_counter_vec(&metrics::OPERATION_REBROADCAST_TOTAL, &[key.kind()]);
+                if let Some(pending) = self.pending.get_mut(&key) {
+                    pending.last_published = Some(current_epoch);
+                }
+                message
+            })
+            .collect()
+    }
+
+    /// The number of pending operations being tracked.
+    pub fn num_pending(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{
+        Address, BlsToExecutionChange, MainnetEthSpec, PublicKeyBytes, Signature,
+        SignedBlsToExecutionChange, SignedVoluntaryExit, VoluntaryExit,
+    };
+
+    type E = MainnetEthSpec;
+
+    fn exit(validator_index: u64) -> SignedVoluntaryExit {
+        SignedVoluntaryExit {
+            message: VoluntaryExit {
+                epoch: Epoch::new(0),
+                validator_index,
+            },
+            signature: Signature::empty(),
+        }
+    }
+
+    fn address_change(validator_index: u64) -> SignedBlsToExecutionChange {
+        SignedBlsToExecutionChange {
+            message: BlsToExecutionChange {
+                validator_index,
+                from_bls_pubkey: PublicKeyBytes::empty(),
+                to_execution_address: Address::zero(),
+            },
+            signature: Signature::empty(),
+        }
+    }
+
+    fn operations(exits: &[u64], address_changes: &[u64]) -> PendingOperations<E> {
+        PendingOperations {
+            proposer_slashings: vec![],
+            attester_slashings: vec![],
+            voluntary_exits: exits.iter().copied().map(exit).collect(),
+            bls_to_execution_changes: address_changes
+                .iter()
+                .copied()
+                .map(address_change)
+                .collect(),
+        }
+    }
+
+    fn exit_indices(messages: &[PubsubMessage<E>]) -> BTreeSet<u64> {
+        messages
+            .iter()
+            .filter_map(|message| match message {
+                PubsubMessage::VoluntaryExit(exit) => Some(exit.message.validator_index),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn waits_for_interval() {
+        let mut rebroadcaster = OperationRebroadcaster::new(4, usize::MAX);
+
+        for epoch in 0..4 {
+            let messages = rebroadcaster.select(Epoch::new(epoch), operations(&[1], &[2]));
+            assert!(messages.is_empty(), "epoch {epoch}");
+        }
+        assert_eq!(rebroadcaster.num_pending(), 2);
+
+        let messages = rebroadcaster.select(Epoch::new(4), operations(&[1], &[2]));
+        assert_eq!(messages.len(), 2);
+
+        // Nothing is republished until another interval has passed.
+        for epoch in 5..8 {
+            let messages = rebroadcaster.select(Epoch::new(epoch), operations(&[1], &[2]));
+            assert!(messages.is_empty(), "epoch {epoch}");
+        }
+        let messages = rebroadcaster.select(Epoch::new(8), operations(&[1], &[2]));
+        assert_eq!(messages.len(), 2);
+    }
+
+    #[test]
+    fn forgets_included_operations() {
+        let mut rebroadcaster = OperationRebroadcaster::new(2, usize::MAX);
+
+        rebroadcaster.select(Epoch::new(0), operations(&[1, 2], &[]));
+        assert_eq!(rebroadcaster.num_pending(), 2);
+
+        // Exit 2 was included, so it is no longer tracked.
+        rebroadcaster.select(Epoch::new(1), operations(&[1], &[]));
+        assert_eq!(rebroadcaster.num_pending(), 1);
+
+        // If exit 2 reappears (e.g. after a re-org) its wait starts again.
+        let messages = rebroadcaster.select(Epoch::new(2), operations(&[1, 2], &[]));
+        assert_eq!(exit_indices(&messages), BTreeSet::from([1]));
+    }
+
+    #[test]
+    fn respects_limit_and_prefers_oldest() {
+        let mut rebroadcaster = OperationRebroadcaster::new(1, 1);
+
+        assert!(rebroadcaster
+            .select(Epoch::new(0), operations(&[1, 2], &[]))
+            .is_empty());
+
+        let first = exit_indices(&rebroadcaster.select(Epoch::new(1), operations(&[1, 2], &[])));
+        assert_eq!(first.len(), 1);
+
+        // The exit which missed out has waited the longest, so it is published next.
+        let second = exit_indices(&rebroadcaster.select(Epoch::new(2), operations(&[1, 2], &[])));
+        assert_eq!(second.len(), 1);
+        assert_ne!(first, second);
+    }
+}
@@ -0,0 +1,81 @@
+use lighthouse_network::PersistedPeer;
+use ssz::{Decode, Encode};
+use std::sync::Arc;
+use store::{DBColumn, Error as StoreError, HotColdDB, ItemStore, StoreItem};
+use types::{EthSpec, Hash256};
+
+/// 32-byte key for accessing the `PersistedPeerDb`. All zero because `PersistedPeerDb` has its
+/// own column.
+pub const PEER_DB_KEY: Hash256 = Hash256::zero();
+
+/// Loads the peers persisted before the last shutdown, if any.
+pub fn load_peers<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+) -> Result<Vec<PersistedPeer>, store::Error> {
+    Ok(store
+        .get_item::<PersistedPeerDb>(&PEER_DB_KEY)?
+        .map(|persisted| persisted.peers)
+        .unwrap_or_default())
+}
+
+/// Attempt to persist the reputation of our known peers to `store`.
+pub fn persist_peers<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+    peers: Vec<PersistedPeer>,
+) -> Result<(), store::Error> {
+    store.put_item(&PEER_DB_KEY, &PersistedPeerDb { peers })
+}
+
+/// Wrapper around the peers of the `PeerDB` for persistence to disk.
+pub struct PersistedPeerDb {
+    pub peers: Vec<PersistedPeer>,
+}
+
+impl StoreItem for PersistedPeerDb {
+    fn db_column() -> DBColumn {
+        DBColumn::PeerDb
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.peers.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        let peers = Vec::<PersistedPeer>::from_ssz_bytes(bytes)?;
+        Ok(PersistedPeerDb { peers })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lighthouse_network::{PeerId, PeerInfo};
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use store::config::StoreConfig;
+    use store::MemoryStore;
+    use types::{ChainSpec, MinimalEthSpec};
+
+    #[test]
+    fn test_persisted_peers() {
+        let log = NullLoggerBuilder.build().unwrap();
+        let store: HotColdDB<
+            MinimalEthSpec,
+            MemoryStore<MinimalEthSpec>,
+            MemoryStore<MinimalEthSpec>,
+        > = HotColdDB::open_ephemeral(StoreConfig::default(), ChainSpec::minimal(), log).unwrap();
+        let store = Arc::new(store);
+        assert!(load_peers(store.clone()).unwrap().is_empty());
+
+        let peer_id = PeerId::random();
+        let peers =
+            vec![
+                PersistedPeer::from_peer_info(&peer_id, &PeerInfo::<MinimalEthSpec>::default())
+                    .unwrap(),
+            ];
+        persist_peers(store.clone(), peers.clone()).unwrap();
+
+        let loaded = load_peers(store).unwrap();
+        assert_eq!(loaded, peers);
+        assert_eq!(loaded[0].peer_id(), Some(peer_id));
+    }
+}
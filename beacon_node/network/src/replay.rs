@@ -0,0 +1,154 @@
+//! Replays a recording of the messages received from peers against a local chain.
+//!
+//! Recordings are made by the `TrafficRecorder` in `lighthouse_network` when the
+//! `--network-recording-file` flag is set. Replaying them feeds each message to the `Router`, just
+//! as the network service would, so that sync and gossip issues can be reproduced
+//! deterministically in tests.
+
+use crate::error;
+use crate::network_beacon_processor::InvalidBlockStorage;
+use crate::router::{Router, RouterMessage};
+use crate::service::NetworkMessage;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use beacon_processor::{work_reprocessing_queue::ReprocessQueueMessage, BeaconProcessorSend};
+use lighthouse_network::discovery::ConnectionId;
+use lighthouse_network::rpc::SubstreamId;
+use lighthouse_network::service::recorder::{RecordedEvent, RecordingError, TrafficRecording};
+use lighthouse_network::{NetworkGlobals, PeerId};
+use slog::{debug, warn};
+use std::collections::HashSet;
+use std::io::Read;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+/// Feeds the messages of a recording to a `Router`.
+pub struct TrafficReplay<T: BeaconChainTypes> {
+    router_send: mpsc::UnboundedSender<RouterMessage<T::EthSpec>>,
+    network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+    /// The peers which have been marked as connected.
+    peers: HashSet<PeerId>,
+    /// Used to give each replayed request a unique id.
+    next_substream_id: usize,
+    log: slog::Logger,
+}
+
+impl<T: BeaconChainTypes> TrafficReplay<T> {
+    /// Spawns a `Router` for `beacon_chain`, which the recording is replayed to.
+    ///
+    /// The returned receiver yields the messages the `Router` and sync send to the network, such
+    /// as responses to replayed requests.
+    pub fn spawn(
+        beacon_chain: Arc<BeaconChain<T>>,
+        network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+        executor: task_executor::TaskExecutor,
+        beacon_processor_send: BeaconProcessorSend<T::EthSpec>,
+        beacon_processor_reprocess_tx: mpsc::Sender<ReprocessQueueMessage>,
+        log: slog::Logger,
+    ) -> error::Result<(Self, mpsc::UnboundedReceiver<NetworkMessage<T::EthSpec>>)> {
+        let (network_send, network_recv) = mpsc::unbounded_channel();
+        let router_send = Router::spawn(
+            beacon_chain,
+            network_globals.clone(),
+            network_send,
+            executor,
+            InvalidBlockStorage::Disabled,
+            beacon_processor_send,
+            beacon_processor_reprocess_tx,
+            log.clone(),
+        )?;
+        let replay = Self {
+            router_send,
+            network_globals,
+            peers: HashSet::new(),
+            next_substream_id: 0,
+            log,
+        };
+        Ok((replay, network_recv))
+    }
+
+    /// Sends each message of `recording` to the `Router`, returning the number of messages sent.
+    ///
+    /// Records which can't be decoded are logged and skipped. If `realtime` is set, messages are
+    /// sent with the same delays between them as when they were recorded, otherwise they are sent
+    /// as fast as possible.
+    pub async fn replay<R: Read>(
+        &mut self,
+        recording: TrafficRecording<T::EthSpec, R>,
+        realtime: bool,
+    ) -> Result<usize, String> {
+        let start = Instant::now();
+        let mut count = 0;
+        let mut skipped = 0;
+        for message in recording {
+            let message = match message {
+                Ok(message) => message,
+                Err(RecordingError::Decode(e)) => {
+                    warn!(self.log, "Skipping invalid record in network recording"; "error" => e);
+                    skipped += 1;
+                    continue;
+                }
+                Err(RecordingError::Read(e)) => return Err(e),
+            };
+            if realtime {
+                tokio::time::sleep_until(start + message.elapsed).await;
+            }
+
+            let router_message = match message.event {
+                RecordedEvent::Gossip {
+                    id,
+                    source,
+                    topic: _,
+                    message,
+                } => {
+                    self.add_peer(source);
+                    RouterMessage::PubsubMessage(id, source, message, true)
+                }
+                RecordedEvent::Request { peer_id, request } => {
+                    self.add_peer(peer_id);
+                    let substream_id = SubstreamId::new(self.next_substream_id);
+                    self.next_substream_id += 1;
+                    RouterMessage::RPCRequestReceived {
+                        peer_id,
+                        id: (ConnectionId::new_unchecked(0), substream_id),
+                        request,
+                    }
+                }
+                RecordedEvent::Response {
+                    peer_id,
+                    id,
+                    response,
+                } => {
+                    self.add_peer(peer_id);
+                    RouterMessage::RPCResponseReceived {
+                        peer_id,
+                        request_id: id,
+                        response,
+                    }
+                }
+            };
+            self.router_send
+                .send(router_message)
+                .map_err(|_| "Router has shut down".to_string())?;
+            count += 1;
+        }
+        debug!(
+            self.log,
+            "Replayed network recording";
+            "messages" => count,
+            "skipped" => skipped,
+        );
+        Ok(count)
+    }
+
+    /// Marks `peer_id` as connected, so that messages from it are handled as they would be from a
+    /// real peer.
+    fn add_peer(&mut self, peer_id: PeerId) {
+        if self.peers.insert(peer_id) {
+            self.network_globals
+                .peers
+                .write()
+                .connect_replayed(&peer_id);
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use crate::nat;
 use crate::network_beacon_processor::InvalidBlockStorage;
+use crate::operation_rebroadcast::OperationRebroadcaster;
 use crate::persisted_dht::{clear_dht, load_dht, persist_dht};
+use crate::persisted_peers::{load_peers, persist_peers};
 use crate::router::{Router, RouterMessage};
 use crate::subnet_service::SyncCommitteeService;
 use crate::{error, metrics};
@@ -18,25 +20,27 @@ use lighthouse_network::service::Network;
 use lighthouse_network::types::GossipKind;
 use lighthouse_network::{prometheus_client::registry::Registry, MessageAcceptance};
 use lighthouse_network::{
-    rpc::{GoodbyeReason, RPCResponseErrorCode},
-    Context, PeerAction, PeerRequestId, PubsubMessage, ReportSource, Request, Response, Subnet,
+    rpc::{GoodbyeReason, Protocol, Quota, RPCResponseErrorCode},
+    Context, PeerAction, PeerPolicy, PeerRequestId, PubsubMessage, ReportSource, Request, Response,
+    Subnet,
 };
 use lighthouse_network::{
     service::api_types::AppRequestId,
     types::{core_topics_to_subscribe, GossipEncoding, GossipTopic},
     MessageId, NetworkEvent, NetworkGlobals, PeerId,
 };
+use operation_pool::PendingOperations;
 use slog::{crit, debug, error, info, o, trace, warn};
 use std::collections::BTreeSet;
 use std::{collections::HashSet, pin::Pin, sync::Arc, time::Duration};
 use store::HotColdDB;
 use strum::IntoStaticStr;
 use task_executor::ShutdownReason;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tokio::time::Sleep;
 use types::{
-    ChainSpec, EthSpec, ForkContext, Slot, SubnetId, SyncCommitteeSubscription, SyncSubnetId,
-    Unsigned, ValidatorSubscription,
+    ChainSpec, Epoch, EthSpec, ForkContext, Slot, SubnetId, SyncCommitteeSubscription,
+    SyncSubnetId, Unsigned, ValidatorSubscription,
 };
 
 mod tests;
@@ -101,6 +105,23 @@ pub enum NetworkMessage<E: EthSpec> {
         reason: GoodbyeReason,
         source: ReportSource,
     },
+    /// Lifts the bans of all banned peers.
+    ClearPeerBans,
+    /// Replaces the peer policy, sending the result of writing it to the peer policy file on
+    /// `result_tx`.
+    UpdatePeerPolicy {
+        peer_policy: PeerPolicy,
+        result_tx: oneshot::Sender<Result<(), String>>,
+    },
+    /// Re-reads the peer policy from the peer policy file.
+    ReloadPeerPolicy,
+    /// Replaces inbound RPC quotas and sets or removes per-peer quota multipliers, sending the
+    /// result on `result_tx`. Either every update is applied, or none are.
+    UpdateInboundRateLimits {
+        quotas: Vec<(Protocol, Quota)>,
+        peer_multipliers: Vec<(PeerId, Option<f64>)>,
+        result_tx: oneshot::Sender<Result<(), String>>,
+    },
 }
 
 /// Messages triggered by validators that may trigger a subscription to a subnet.
@@ -193,6 +214,16 @@ pub struct NetworkService<T: BeaconChainTypes> {
     metrics_update: tokio::time::Interval,
     /// gossipsub_parameter_update timer
     gossipsub_parameter_update: tokio::time::Interval,
+    /// Selects unincluded operations to re-publish, if enabled.
+    operation_rebroadcaster: Option<OperationRebroadcaster>,
+    /// A timer for re-publishing unincluded operations, which fires once per epoch.
+    operation_rebroadcast_update: tokio::time::Interval,
+    /// Sends the pending operations, which are found on a blocking thread, back to the service.
+    pending_operations_send: mpsc::Sender<(Epoch, PendingOperations<T::EthSpec>)>,
+    /// Receives the pending operations to select rebroadcasts from.
+    pending_operations_recv: mpsc::Receiver<(Epoch, PendingOperations<T::EthSpec>)>,
+    /// The executor used to find pending operations off the network service's task.
+    executor: task_executor::TaskExecutor,
     /// enable_light_client_server indicator
     enable_light_client_server: bool,
     /// The logger for the network service.
@@ -292,6 +323,15 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             }
         }
 
+        // Restore the reputation of the peers we knew before shutting down.
+        match load_peers::<T::EthSpec, T::HotStore, T::ColdStore>(store.clone()) {
+            Ok(peers) => {
+                debug!(network_log, "Restoring persisted peers"; "peers" => peers.len());
+                libp2p.restore_peers(peers);
+            }
+            Err(e) => warn!(network_log, "Failed to load persisted peers"; "error" => ?e),
+        }
+
         let invalid_block_storage = config
             .invalid_block_storage
             .clone()
@@ -329,6 +369,15 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         // create a timer for updating gossipsub parameters
         let gossipsub_parameter_update = tokio::time::interval(Duration::from_secs(60));
 
+        // create a timer for re-publishing operations which have not been included
+        let operation_rebroadcaster = config
+            .operation_rebroadcast_epochs
+            .map(|epochs| OperationRebroadcaster::new(epochs, config.operation_rebroadcast_limit));
+        let operation_rebroadcast_update = tokio::time::interval(Duration::from_secs(
+            beacon_chain.spec.seconds_per_slot * T::EthSpec::slots_per_epoch(),
+        ));
+        let (pending_operations_send, pending_operations_recv) = mpsc::channel(1);
+
         let NetworkReceivers {
             network_recv,
             validator_subscription_recv,
@@ -354,6 +403,11 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             metrics_enabled: config.metrics_enabled,
             metrics_update,
             gossipsub_parameter_update,
+            operation_rebroadcaster,
+            operation_rebroadcast_update,
+            pending_operations_send,
+            pending_operations_recv,
+            executor,
             fork_context,
             log: network_log,
             enable_light_client_server: config.enable_light_client_server,
@@ -448,6 +502,10 @@ impl<T: BeaconChainTypes> NetworkService<T> {
 
                     _ = self.gossipsub_parameter_update.tick() => self.update_gossipsub_parameters(),
 
+                    _ = self.operation_rebroadcast_update.tick(), if self.operation_rebroadcaster.is_some() => self.find_pending_operations(),
+
+                    Some((current_epoch, pending)) = self.pending_operations_recv.recv() => self.rebroadcast_operations(current_epoch, pending),
+
                     // handle a message sent to the network
                     Some(msg) = self.network_recv.recv() => self.on_network_msg(msg, &mut shutdown_sender).await,
 
@@ -673,6 +731,43 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 reason,
                 source,
             } => self.libp2p.goodbye_peer(&peer_id, reason, source),
+            NetworkMessage::ClearPeerBans => {
+                let unbanned_peers = self.libp2p.clear_peer_bans();
+                info!(self.log, "Cleared peer bans"; "unbanned_peers" => unbanned_peers);
+            }
+            NetworkMessage::UpdatePeerPolicy {
+                peer_policy,
+                result_tx,
+            } => {
+                let result = self.libp2p.update_peer_policy(peer_policy);
+                match &result {
+                    Ok(()) => info!(self.log, "Updated peer policy"),
+                    Err(e) => error!(self.log, "Failed to update peer policy"; "error" => e),
+                }
+                // The caller may no longer be waiting for the result.
+                let _ = result_tx.send(result);
+            }
+            NetworkMessage::ReloadPeerPolicy => match self.libp2p.reload_peer_policy() {
+                Ok(()) => info!(self.log, "Reloaded peer policy"),
+                Err(e) => error!(self.log, "Failed to reload peer policy"; "error" => e),
+            },
+            NetworkMessage::UpdateInboundRateLimits {
+                quotas,
+                peer_multipliers,
+                result_tx,
+            } => {
+                let result = self
+                    .libp2p
+                    .update_inbound_rate_limits(quotas, peer_multipliers);
+                match &result {
+                    Ok(()) => info!(self.log, "Updated inbound RPC rate limits"),
+                    Err(e) => {
+                        error!(self.log, "Failed to update inbound RPC rate limits"; "error" => e)
+                    }
+                }
+                // The caller may no longer be waiting for the result.
+                let _ = result_tx.send(result);
+            }
             NetworkMessage::SubscribeCoreTopics => {
                 if self.subscribed_core_topics() {
                     return;
@@ -801,6 +896,64 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         }
     }
 
+    /// Find the slashings, exits and address changes which are still valid against the head on a
+    /// blocking thread, sending them back to the service to be rebroadcast.
+    ///
+    /// Checking the operations verifies their signatures, which is too slow for the network
+    /// service's task.
+    fn find_pending_operations(&mut self) {
+        // Our view of which operations are still valid is unreliable until we're synced.
+        if !self.network_globals.sync_state().is_synced() {
+            return;
+        }
+        let Ok(current_epoch) = self.beacon_chain.epoch() else {
+            return;
+        };
+
+        let beacon_chain = self.beacon_chain.clone();
+        let pending_operations_send = self.pending_operations_send.clone();
+        let log = self.log.clone();
+        self.executor.spawn_blocking(
+            move || {
+                let head = beacon_chain.canonical_head.cached_head();
+                let pending = beacon_chain
+                    .op_pool
+                    .get_pending_operations(&head.snapshot.beacon_state, &beacon_chain.spec);
+                if pending_operations_send
+                    .try_send((current_epoch, pending))
+                    .is_err()
+                {
+                    debug!(log, "Dropping pending operations"; "epoch" => current_epoch);
+                }
+            },
+            "find_pending_operations",
+        );
+    }
+
+    /// Re-publish slashings, exits and address changes which have been waiting in the op pool
+    /// without being included.
+    fn rebroadcast_operations(
+        &mut self,
+        current_epoch: Epoch,
+        pending: PendingOperations<T::EthSpec>,
+    ) {
+        let Some(rebroadcaster) = self.operation_rebroadcaster.as_mut() else {
+            return;
+        };
+        let messages = rebroadcaster.select(current_epoch, pending);
+
+        if !messages.is_empty() {
+            debug!(
+                self.log,
+                "Rebroadcasting pending operations";
+                "count" => messages.len(),
+                "tracked" => rebroadcaster.num_pending(),
+                "epoch" => current_epoch,
+            );
+            self.libp2p.publish(messages);
+        }
+    }
+
     fn update_gossipsub_parameters(&mut self) {
         if let Ok(slot) = self.beacon_chain.slot() {
             let active_validators_opt = self
@@ -989,6 +1142,21 @@ impl<T: BeaconChainTypes> Drop for NetworkService<T> {
                 "Saved DHT state";
             ),
         }
+
+        let peers = self.libp2p.persisted_peers();
+        let num_peers = peers.len();
+        match persist_peers::<T::EthSpec, T::HotStore, T::ColdStore>(self.store.clone(), peers) {
+            Err(e) => error!(
+                self.log,
+                "Failed to persist peers on drop";
+                "error" => ?e
+            ),
+            Ok(_) => debug!(
+                self.log,
+                "Saved peer reputations";
+                "peers" => num_peers,
+            ),
+        }
         info!(self.log, "Network service shutdown");
     }
 }
@@ -14,7 +14,8 @@ use beacon_chain::builder::Witness;
 use beacon_chain::data_availability_checker::Availability;
 use beacon_chain::eth1_chain::CachingEth1Backend;
 use beacon_chain::test_utils::{
-    build_log, generate_rand_block_and_blobs, BeaconChainHarness, EphemeralHarnessType, NumBlobs,
+    build_log, generate_rand_block_and_blobs, test_spec, BeaconChainHarness, EphemeralHarnessType,
+    NumBlobs,
 };
 use beacon_chain::{
     AvailabilityPendingExecutedBlock, PayloadVerificationOutcome, PayloadVerificationStatus,
@@ -30,8 +31,10 @@ use store::MemoryStore;
 use tokio::sync::mpsc;
 use types::test_utils::TestRandom;
 use types::{
+    data_column_sidecar::ColumnIndex,
     test_utils::{SeedableRng, XorShiftRng},
-    BlobSidecar, ForkName, MinimalEthSpec as E, SignedBeaconBlock, Slot,
+    BlobSidecar, BlobsList, ChainSpec, DataColumnSidecar, Epoch, ForkName, MinimalEthSpec as E,
+    SignedBeaconBlock, Slot,
 };
 use types::{BeaconState, BeaconStateBase};
 
@@ -87,12 +90,16 @@ const PARENT_FAIL_TOLERANCE: u8 = SINGLE_BLOCK_LOOKUP_MAX_ATTEMPTS;
 
 impl TestRig {
     fn test_setup() -> Self {
+        Self::test_setup_with_spec(test_spec::<E>())
+    }
+
+    fn test_setup_with_spec(spec: ChainSpec) -> Self {
         let enable_log = cfg!(feature = "test_logger");
         let log = build_log(slog::Level::Trace, enable_log);
 
         // Initialise a new beacon chain
         let harness = BeaconChainHarness::<EphemeralHarnessType<E>>::builder(E)
-            .default_spec()
+            .spec(spec)
             .logger(log.clone())
             .deterministic_keypairs(1)
             .fresh_ephemeral_store()
@@ -153,6 +160,17 @@ impl TestRig {
         }
     }
 
+    fn test_setup_after_peerdas() -> Option<Self> {
+        let mut spec = test_spec::<E>();
+        spec.eip7594_fork_epoch = Some(Epoch::new(0));
+        let r = Self::test_setup_with_spec(spec);
+        if r.after_deneb() {
+            Some(r)
+        } else {
+            None
+        }
+    }
+
     fn log(&self, msg: &str) {
         info!(self.log, "TEST_RIG"; "msg" => msg);
     }
@@ -387,6 +405,17 @@ impl TestRig {
         })
     }
 
+    fn single_custody_column_component_processed(
+        &mut self,
+        id: Id,
+        result: BlockProcessingResult<E>,
+    ) {
+        self.send_sync_message(SyncMessage::BlockComponentProcessed {
+            process_type: BlockProcessType::SingleCustodyColumn { id },
+            result,
+        })
+    }
+
     fn parent_lookup_block_response(
         &mut self,
         id: SingleLookupReqId,
@@ -484,6 +513,56 @@ impl TestRig {
         );
     }
 
+    fn single_lookup_custody_column_response(
+        &mut self,
+        id: SingleLookupReqId,
+        peer_id: PeerId,
+        data_column: Option<Arc<DataColumnSidecar<E>>>,
+    ) {
+        self.log("single_lookup_custody_column_response");
+        self.send_sync_message(SyncMessage::RpcDataColumn {
+            request_id: SyncRequestId::SingleCustodyColumns { id },
+            peer_id,
+            data_column,
+            seen_timestamp: D,
+        });
+    }
+
+    /// Responds to each custody request with the requested columns from `data_columns`.
+    fn complete_custody_column_download(
+        &mut self,
+        requests: Vec<(SingleLookupReqId, PeerId, Vec<ColumnIndex>)>,
+        data_columns: &[Arc<DataColumnSidecar<E>>],
+    ) {
+        for (id, peer_id, indices) in requests {
+            for index in indices {
+                let data_column = data_columns[index as usize].clone();
+                self.single_lookup_custody_column_response(id, peer_id, Some(data_column));
+            }
+            self.single_lookup_custody_column_response(id, peer_id, None);
+        }
+    }
+
+    fn rand_data_columns(
+        &self,
+        block: &SignedBeaconBlock<E>,
+        blobs: &[BlobSidecar<E>],
+    ) -> Vec<Arc<DataColumnSidecar<E>>> {
+        let blobs = BlobsList::<E>::from(
+            blobs
+                .iter()
+                .map(|blob| blob.blob.clone())
+                .collect::<Vec<_>>(),
+        );
+        DataColumnSidecar::build_sidecars(
+            &blobs,
+            block,
+            self.harness.chain.kzg.as_ref().expect("should have kzg"),
+            &self.harness.chain.spec,
+        )
+        .expect("should build data columns")
+    }
+
     fn complete_lookup_block_download(&mut self, block: SignedBeaconBlock<E>) {
         let block_root = block.canonical_root();
         let id = self.expect_block_lookup_request(block_root);
@@ -668,6 +747,46 @@ impl TestRig {
             .unwrap_or_else(|e| panic!("Expected blob request for {for_block:?}: {e}"))
     }
 
+    /// Returns all the `DataColumnsByRoot` requests of the custody lookup of `for_block`, one per
+    /// peer.
+    #[track_caller]
+    fn expect_custody_column_lookup_requests(
+        &mut self,
+        for_block: Hash256,
+    ) -> Vec<(SingleLookupReqId, PeerId, Vec<ColumnIndex>)> {
+        let mut requests = vec![];
+        while let Ok(request) = self.pop_received_network_event(|ev| match ev {
+            NetworkMessage::SendRequest {
+                peer_id,
+                request: Request::DataColumnsByRoot(request),
+                request_id: AppRequestId::Sync(SyncRequestId::SingleCustodyColumns { id }),
+            } if request
+                .data_column_ids
+                .as_slice()
+                .iter()
+                .all(|r| r.block_root == for_block) =>
+            {
+                let indices = request
+                    .data_column_ids
+                    .as_slice()
+                    .iter()
+                    .map(|r| r.index)
+                    .collect();
+                Some((*id, *peer_id, indices))
+            }
+            _ => None,
+        }) {
+            requests.push(request);
+        }
+        if requests.is_empty() {
+            panic!(
+                "Expected custody column requests for {for_block:?}: {:?}",
+                self.network_rx_queue
+            );
+        }
+        requests
+    }
+
     #[track_caller]
     fn expect_block_parent_request(&mut self, for_block: Hash256) -> SingleLookupReqId {
         self.pop_received_network_event(|ev| match ev {
@@ -723,6 +842,11 @@ impl TestRig {
                     (ev.work_type() == beacon_processor::RPC_BLOBS).then_some(())
                 })
                 .unwrap_or_else(|e| panic!("Expected blobs work event: {e}")),
+            ResponseType::CustodyColumn => self
+                .pop_received_processor_event(|ev| {
+                    (ev.work_type() == beacon_processor::RPC_CUSTODY_COLUMN).then_some(())
+                })
+                .unwrap_or_else(|e| panic!("Expected custody column work event: {e}")),
         }
     }
 
@@ -1565,6 +1689,63 @@ fn block_in_processing_cache_becomes_valid_imported() {
     r.expect_no_active_lookups();
 }
 
+#[test]
+fn custody_columns_lookup_happy_path() {
+    let Some(mut r) = TestRig::test_setup_after_peerdas() else {
+        return;
+    };
+    let (block, blobs) = r.rand_block_and_blobs(NumBlobs::Number(1));
+    let block_root = block.canonical_root();
+    let data_columns = r.rand_data_columns(&block, &blobs);
+    let peer_id = r.new_connected_peer();
+    r.trigger_unknown_block_from_attestation(block_root, peer_id);
+    // Should download the block, then request the custody columns instead of blobs
+    r.complete_lookup_block_download(block);
+    let requests = r.expect_custody_column_lookup_requests(block_root);
+    r.expect_empty_network();
+    r.complete_lookup_block_import_valid(block_root, false);
+    // Resolve custody columns and expect lookup completed
+    r.complete_custody_column_download(requests, &data_columns);
+    r.expect_block_process(ResponseType::CustodyColumn);
+    let id = r.find_single_lookup_for(block_root);
+    r.single_custody_column_component_processed(
+        id,
+        BlockProcessingResult::Ok(AvailabilityProcessingStatus::Imported(block_root)),
+    );
+    r.expect_no_active_lookups();
+}
+
+#[test]
+fn custody_columns_lookup_invalid_inclusion_proof() {
+    let Some(mut r) = TestRig::test_setup_after_peerdas() else {
+        return;
+    };
+    let (block, blobs) = r.rand_block_and_blobs(NumBlobs::Number(1));
+    let block_root = block.canonical_root();
+    let data_columns = r.rand_data_columns(&block, &blobs);
+    let peer_id = r.new_connected_peer();
+    r.trigger_unknown_block_from_attestation(block_root, peer_id);
+    r.complete_lookup_block_download(block);
+    let requests = r.expect_custody_column_lookup_requests(block_root);
+    r.complete_lookup_block_import_valid(block_root, false);
+    // Respond with a column that does not prove its commitments against the block
+    let (id, column_peer_id, indices) = requests[0].clone();
+    let mut data_column = (*data_columns[indices[0] as usize]).clone();
+    data_column.kzg_commitments_inclusion_proof[0] = Hash256::repeat_byte(1);
+    r.single_lookup_custody_column_response(id, column_peer_id, Some(data_column.into()));
+    r.expect_penalty(column_peer_id, "InvalidInclusionProof");
+    // Should retry the custody columns and complete the lookup
+    let requests = r.expect_custody_column_lookup_requests(block_root);
+    r.complete_custody_column_download(requests, &data_columns);
+    r.expect_block_process(ResponseType::CustodyColumn);
+    let id = r.find_single_lookup_for(block_root);
+    r.single_custody_column_component_processed(
+        id,
+        BlockProcessingResult::Ok(AvailabilityProcessingStatus::Imported(block_root)),
+    );
+    r.expect_no_active_lookups();
+}
+
 // IGNORE: wait for change that delays blob fetching to knowing the block
 #[ignore]
 #[test]
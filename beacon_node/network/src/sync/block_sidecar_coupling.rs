@@ -1,21 +1,46 @@
-use beacon_chain::block_verification_types::RpcBlock;
+use beacon_chain::{
+    block_verification_types::RpcBlock, data_availability_checker::AvailabilityCheckError,
+};
 use lighthouse_network::PeerId;
 use ssz_types::VariableList;
-use std::{collections::VecDeque, sync::Arc};
-use types::{BlobSidecar, EthSpec, SignedBeaconBlock};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+use types::{
+    data_column_sidecar::ColumnIndex, BlobSidecar, DataColumnSidecar, EthSpec, Hash256,
+    SignedBeaconBlock,
+};
 
 use super::range_sync::ByRangeRequestType;
 
+/// Failure to couple the responses of a range request, attributed to the peer that served the
+/// faulty data.
+#[derive(Debug)]
+pub struct CouplingError {
+    pub msg: String,
+    pub peer_id: PeerId,
+}
+
 #[derive(Debug)]
 pub struct BlocksAndBlobsRequestInfo<E: EthSpec> {
     /// Blocks we have received awaiting for their corresponding sidecar.
     accumulated_blocks: VecDeque<Arc<SignedBeaconBlock<E>>>,
     /// Sidecars we have received awaiting for their corresponding block.
     accumulated_sidecars: VecDeque<Arc<BlobSidecar<E>>>,
+    /// Custody columns we have received awaiting for their corresponding block.
+    accumulated_custody_columns: VecDeque<Arc<DataColumnSidecar<E>>>,
     /// Whether the individual RPC request for blocks is finished or not.
     is_blocks_stream_terminated: bool,
     /// Whether the individual RPC request for sidecars is finished or not.
     is_sidecars_stream_terminated: bool,
+    /// Number of `DataColumnsByRange` streams that have terminated.
+    custody_columns_streams_terminated: usize,
+    /// The custody columns requested for every block of this batch.
+    expected_custody_columns: Vec<ColumnIndex>,
+    /// The peers each `DataColumnsByRange` request was made to and the columns requested from
+    /// them, one entry per request.
+    pub(crate) custody_column_peers: Vec<(PeerId, Vec<ColumnIndex>)>,
     /// Used to determine if this accumulator should wait for a sidecars stream termination
     request_type: ByRangeRequestType,
     /// The peer the request was made to.
@@ -27,17 +52,50 @@ impl<E: EthSpec> BlocksAndBlobsRequestInfo<E> {
         Self {
             accumulated_blocks: <_>::default(),
             accumulated_sidecars: <_>::default(),
+            accumulated_custody_columns: <_>::default(),
             is_blocks_stream_terminated: <_>::default(),
             is_sidecars_stream_terminated: <_>::default(),
+            custody_columns_streams_terminated: <_>::default(),
+            expected_custody_columns: <_>::default(),
+            custody_column_peers: <_>::default(),
             request_type,
             peer_id,
         }
     }
 
+    /// Registers the custody columns expected for each block and the peers the
+    /// `DataColumnsByRange` requests were sent to. Only relevant for `BlocksAndColumns` requests.
+    pub fn with_custody_columns(
+        mut self,
+        expected_custody_columns: Vec<ColumnIndex>,
+        custody_column_peers: Vec<(PeerId, Vec<ColumnIndex>)>,
+    ) -> Self {
+        self.expected_custody_columns = expected_custody_columns;
+        self.custody_column_peers = custody_column_peers;
+        self
+    }
+
     pub fn get_request_type(&self) -> ByRangeRequestType {
         self.request_type
     }
 
+    /// Returns true if `peer_id` is serving any of the streams of this request.
+    pub fn is_peer_involved(&self, peer_id: &PeerId) -> bool {
+        self.peer_id == *peer_id
+            || self
+                .custody_column_peers
+                .iter()
+                .any(|(column_peer, _)| column_peer == peer_id)
+    }
+
+    /// Returns the peer `index` was requested from, or the block peer if it was not requested.
+    fn custody_column_peer(&self, index: ColumnIndex) -> PeerId {
+        self.custody_column_peers
+            .iter()
+            .find(|(_, columns)| columns.contains(&index))
+            .map_or(self.peer_id, |(column_peer, _)| *column_peer)
+    }
+
     pub fn add_block_response(&mut self, block_opt: Option<Arc<SignedBeaconBlock<E>>>) {
         match block_opt {
             Some(block) => self.accumulated_blocks.push_back(block),
@@ -52,7 +110,26 @@ impl<E: EthSpec> BlocksAndBlobsRequestInfo<E> {
         }
     }
 
-    pub fn into_responses(self) -> Result<Vec<RpcBlock<E>>, String> {
+    pub fn add_custody_column_response(&mut self, column_opt: Option<Arc<DataColumnSidecar<E>>>) {
+        match column_opt {
+            Some(column) => self.accumulated_custody_columns.push_back(column),
+            None => self.custody_columns_streams_terminated += 1,
+        }
+    }
+
+    pub fn into_responses(self) -> Result<Vec<RpcBlock<E>>, CouplingError> {
+        match self.request_type {
+            ByRangeRequestType::BlocksAndColumns => self.into_responses_with_custody_columns(),
+            ByRangeRequestType::Blocks | ByRangeRequestType::BlocksAndBlobs => {
+                // Blocks and blobs are served by the same peer.
+                let peer_id = self.peer_id;
+                self.into_responses_with_blobs()
+                    .map_err(|msg| CouplingError { msg, peer_id })
+            }
+        }
+    }
+
+    fn into_responses_with_blobs(self) -> Result<Vec<RpcBlock<E>>, String> {
         let BlocksAndBlobsRequestInfo {
             accumulated_blocks,
             accumulated_sidecars,
@@ -99,12 +176,105 @@ impl<E: EthSpec> BlocksAndBlobsRequestInfo<E> {
         Ok(responses)
     }
 
-    pub fn is_finished(&self) -> bool {
-        let blobs_requested = match self.request_type {
-            ByRangeRequestType::Blocks => false,
-            ByRangeRequestType::BlocksAndBlobs => true,
+    fn into_responses_with_custody_columns(self) -> Result<Vec<RpcBlock<E>>, CouplingError> {
+        let coupling_error = |index: ColumnIndex, msg: String| CouplingError {
+            msg,
+            peer_id: self.custody_column_peer(index),
         };
-        self.is_blocks_stream_terminated && (!blobs_requested || self.is_sidecars_stream_terminated)
+
+        // Columns arrive from multiple streams, so they are not globally ordered. Group them by
+        // block root first and pair each group with its block afterwards.
+        let mut custody_columns_by_block =
+            HashMap::<Hash256, HashMap<ColumnIndex, Arc<DataColumnSidecar<E>>>>::new();
+        for column in self.accumulated_custody_columns.iter() {
+            if !self.expected_custody_columns.contains(&column.index) {
+                return Err(coupling_error(
+                    column.index,
+                    format!("Unrequested custody column index {}", column.index),
+                ));
+            }
+            if custody_columns_by_block
+                .entry(column.block_root())
+                .or_default()
+                .insert(column.index, column.clone())
+                .is_some()
+            {
+                return Err(coupling_error(
+                    column.index,
+                    "Repeat custody column index".to_string(),
+                ));
+            }
+        }
+
+        let mut responses = Vec::with_capacity(self.accumulated_blocks.len());
+        for block in self.accumulated_blocks.iter() {
+            let block_root = block.canonical_root();
+            let mut columns_by_index = custody_columns_by_block
+                .remove(&block_root)
+                .unwrap_or_default();
+
+            let custody_columns = if block.num_expected_blobs() > 0 {
+                self.expected_custody_columns
+                    .iter()
+                    .map(|index| {
+                        columns_by_index.remove(index).ok_or_else(|| {
+                            coupling_error(*index, format!("Missing custody column index {index}"))
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?
+            } else if let Some(index) = columns_by_index.keys().next() {
+                return Err(coupling_error(
+                    *index,
+                    "Received custody columns for a block without blobs".to_string(),
+                ));
+            } else {
+                vec![]
+            };
+
+            let rpc_block = RpcBlock::new_with_custody_columns(
+                Some(block_root),
+                block.clone(),
+                custody_columns,
+            )
+            .map_err(|e| match e {
+                AvailabilityCheckError::DataColumnKzgCommitmentsMismatch(index)
+                | AvailabilityCheckError::DataColumnInvalidInclusionProof(index) => {
+                    coupling_error(index, format!("{e:?}"))
+                }
+                _ => CouplingError {
+                    msg: format!("{e:?}"),
+                    peer_id: self.peer_id,
+                },
+            })?;
+            responses.push(rpc_block);
+        }
+
+        // if there are custody columns left without a block, throw an error.
+        if let Some(index) = custody_columns_by_block
+            .values()
+            .flat_map(|columns| columns.keys())
+            .next()
+        {
+            return Err(coupling_error(
+                *index,
+                "Received custody columns that don't pair well".to_string(),
+            ));
+        }
+
+        Ok(responses)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        if !self.is_blocks_stream_terminated {
+            return false;
+        }
+        match self.request_type {
+            ByRangeRequestType::Blocks => true,
+            ByRangeRequestType::BlocksAndBlobs => self.is_sidecars_stream_terminated,
+            ByRangeRequestType::BlocksAndColumns => {
+                self.custody_columns_streams_terminated >= self.custody_column_peers.len()
+            }
+        }
     }
 }
 
@@ -115,7 +285,24 @@ mod tests {
     use beacon_chain::test_utils::{generate_rand_block_and_blobs, NumBlobs};
     use lighthouse_network::PeerId;
     use rand::SeedableRng;
-    use types::{test_utils::XorShiftRng, ForkName, MinimalEthSpec as E};
+    use std::sync::Arc;
+    use types::{
+        test_utils::XorShiftRng, DataColumnSidecar, ForkName, MinimalEthSpec as E,
+        SignedBeaconBlock,
+    };
+
+    fn custody_column_for_block(
+        block: &SignedBeaconBlock<E>,
+        index: u64,
+    ) -> Arc<DataColumnSidecar<E>> {
+        let body = block.message().body();
+        let mut column = DataColumnSidecar::<E>::empty();
+        column.index = index;
+        column.kzg_commitments = body.blob_kzg_commitments().unwrap().clone();
+        column.kzg_commitments_inclusion_proof = body.kzg_commitments_merkle_proof().unwrap();
+        column.signed_block_header = block.signed_block_header();
+        Arc::new(column)
+    }
 
     #[test]
     fn no_blobs_into_responses() {
@@ -164,4 +351,121 @@ mod tests {
         assert!(info.is_finished());
         info.into_responses().unwrap();
     }
+
+    #[test]
+    fn custody_columns_into_responses() {
+        let peer_id = PeerId::random();
+        let column_peers = vec![(PeerId::random(), vec![1]), (PeerId::random(), vec![3])];
+        let mut info =
+            BlocksAndBlobsRequestInfo::<E>::new(ByRangeRequestType::BlocksAndColumns, peer_id)
+                .with_custody_columns(vec![1, 3], column_peers.clone());
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let blocks = (0..4)
+            .map(|i| {
+                // Every other block has no blobs, and therefore no columns.
+                let num_blobs = if i % 2 == 0 {
+                    NumBlobs::Number(1)
+                } else {
+                    NumBlobs::None
+                };
+                generate_rand_block_and_blobs::<E>(ForkName::Deneb, num_blobs, &mut rng).0
+            })
+            .collect::<Vec<_>>();
+
+        // Each column peer serves one of the custody columns for all blocks with data.
+        for block in blocks.iter().filter(|block| block.num_expected_blobs() > 0) {
+            info.add_custody_column_response(Some(custody_column_for_block(block, 3)));
+            info.add_custody_column_response(Some(custody_column_for_block(block, 1)));
+        }
+        for block in blocks {
+            info.add_block_response(Some(block.into()));
+        }
+        info.add_block_response(None);
+        info.add_custody_column_response(None);
+        assert!(!info.is_finished());
+        info.add_custody_column_response(None);
+        assert!(info.is_finished());
+        assert!(column_peers
+            .iter()
+            .all(|(peer, _)| info.is_peer_involved(peer)));
+
+        let responses = info.into_responses().unwrap();
+        let num_columns = responses
+            .iter()
+            .map(|block| block.n_data_columns())
+            .collect::<Vec<_>>();
+        assert_eq!(num_columns, vec![2, 0, 2, 0]);
+    }
+
+    #[test]
+    fn missing_custody_columns_into_responses() {
+        let peer_id = PeerId::random();
+        let column_peer = PeerId::random();
+        let mut info =
+            BlocksAndBlobsRequestInfo::<E>::new(ByRangeRequestType::BlocksAndColumns, peer_id)
+                .with_custody_columns(vec![1, 3], vec![(column_peer, vec![1, 3])]);
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let block =
+            generate_rand_block_and_blobs::<E>(ForkName::Deneb, NumBlobs::Number(1), &mut rng).0;
+
+        // Only one of the two custody columns is returned.
+        info.add_custody_column_response(Some(custody_column_for_block(&block, 1)));
+        info.add_custody_column_response(None);
+        info.add_block_response(Some(block.into()));
+        info.add_block_response(None);
+
+        assert!(info.is_finished());
+        assert_eq!(info.into_responses().unwrap_err().peer_id, column_peer);
+    }
+
+    #[test]
+    fn mismatched_commitments_custody_columns_into_responses() {
+        let peer_id = PeerId::random();
+        let column_peers = vec![(PeerId::random(), vec![1]), (PeerId::random(), vec![3])];
+        let mut info =
+            BlocksAndBlobsRequestInfo::<E>::new(ByRangeRequestType::BlocksAndColumns, peer_id)
+                .with_custody_columns(vec![1, 3], column_peers.clone());
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let block =
+            generate_rand_block_and_blobs::<E>(ForkName::Deneb, NumBlobs::Number(2), &mut rng).0;
+
+        // Column 3 carries commitments that are not the block's, but is otherwise well formed.
+        let mut column = (*custody_column_for_block(&block, 3)).clone();
+        column.kzg_commitments = column.kzg_commitments[..1].to_vec().into();
+        info.add_custody_column_response(Some(custody_column_for_block(&block, 1)));
+        info.add_custody_column_response(Some(Arc::new(column)));
+        info.add_custody_column_response(None);
+        info.add_custody_column_response(None);
+        info.add_block_response(Some(block.into()));
+        info.add_block_response(None);
+
+        assert!(info.is_finished());
+        let err = info.into_responses().unwrap_err();
+        assert_eq!(err.peer_id, column_peers[1].0);
+        assert!(err.msg.contains("DataColumnKzgCommitmentsMismatch(3)"));
+    }
+
+    #[test]
+    fn invalid_inclusion_proof_custody_columns_into_responses() {
+        let peer_id = PeerId::random();
+        let column_peer = PeerId::random();
+        let mut info =
+            BlocksAndBlobsRequestInfo::<E>::new(ByRangeRequestType::BlocksAndColumns, peer_id)
+                .with_custody_columns(vec![1], vec![(column_peer, vec![1])]);
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let block =
+            generate_rand_block_and_blobs::<E>(ForkName::Deneb, NumBlobs::Number(1), &mut rng).0;
+
+        let mut column = (*custody_column_for_block(&block, 1)).clone();
+        column.kzg_commitments_inclusion_proof = Default::default();
+        info.add_custody_column_response(Some(Arc::new(column)));
+        info.add_custody_column_response(None);
+        info.add_block_response(Some(block.into()));
+        info.add_block_response(None);
+
+        assert!(info.is_finished());
+        let err = info.into_responses().unwrap_err();
+        assert_eq!(err.peer_id, column_peer);
+        assert!(err.msg.contains("DataColumnInvalidInclusionProof(1)"));
+    }
 }
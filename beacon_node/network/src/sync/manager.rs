@@ -42,7 +42,7 @@ use crate::network_beacon_processor::{ChainSegmentProcessId, NetworkBeaconProces
 use crate::service::NetworkMessage;
 use crate::status::ToStatusMessage;
 use crate::sync::block_lookups::{
-    BlobRequestState, BlockComponent, BlockRequestState, DownloadResult,
+    BlobRequestState, BlockComponent, BlockRequestState, CustodyRequestState, DownloadResult,
 };
 use crate::sync::block_sidecar_coupling::BlocksAndBlobsRequestInfo;
 use beacon_chain::block_verification_types::AsBlock;
@@ -101,6 +101,14 @@ pub enum SyncMessage<E: EthSpec> {
         seen_timestamp: Duration,
     },
 
+    /// A data column has been received from the RPC.
+    RpcDataColumn {
+        request_id: SyncRequestId,
+        peer_id: PeerId,
+        data_column: Option<Arc<DataColumnSidecar<E>>>,
+        seen_timestamp: Duration,
+    },
+
     /// A block with an unknown parent has been received.
     UnknownParentBlock(PeerId, RpcBlock<E>, Hash256),
 
@@ -145,6 +153,7 @@ pub enum SyncMessage<E: EthSpec> {
 pub enum BlockProcessType {
     SingleBlock { id: Id },
     SingleBlob { id: Id },
+    SingleCustodyColumn { id: Id },
 }
 
 #[derive(Debug)]
@@ -337,8 +346,13 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             SyncRequestId::SingleBlob { id } => {
                 self.on_single_blob_response(id, peer_id, RpcEvent::RPCError(error))
             }
+            SyncRequestId::SingleCustodyColumns { id } => {
+                self.on_single_custody_columns_response(id, peer_id, RpcEvent::RPCError(error))
+            }
             SyncRequestId::RangeBlockAndBlobs { id } => {
-                if let Some(sender_id) = self.network.range_request_failed(id) {
+                // The request may have failed on a column peer, but range sync tracks the batch
+                // with the peer serving its blocks.
+                if let Some((sender_id, peer_id)) = self.network.range_request_failed(id) {
                     match sender_id {
                         RangeRequestId::RangeSync { chain_id, batch_id } => {
                             self.range_sync.inject_error(
@@ -614,6 +628,12 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                 blob_sidecar,
                 seen_timestamp,
             } => self.rpc_blob_received(request_id, peer_id, blob_sidecar, seen_timestamp),
+            SyncMessage::RpcDataColumn {
+                request_id,
+                peer_id,
+                data_column,
+                seen_timestamp,
+            } => self.rpc_data_column_received(request_id, peer_id, data_column, seen_timestamp),
             SyncMessage::UnknownParentBlock(peer_id, block, block_root) => {
                 let block_slot = block.slot();
                 let parent_root = block.parent_root();
@@ -846,6 +866,9 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             SyncRequestId::SingleBlob { .. } => {
                 crit!(self.log, "Block received during blob request"; "peer_id" => %peer_id  );
             }
+            SyncRequestId::SingleCustodyColumns { .. } => {
+                crit!(self.log, "Block received during custody columns request"; "peer_id" => %peer_id  );
+            }
             SyncRequestId::RangeBlockAndBlobs { id } => {
                 self.range_block_and_blobs_response(id, peer_id, block.into())
             }
@@ -880,6 +903,9 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             SyncRequestId::SingleBlock { .. } => {
                 crit!(self.log, "Single blob received during block request"; "peer_id" => %peer_id  );
             }
+            SyncRequestId::SingleCustodyColumns { .. } => {
+                crit!(self.log, "Single blob received during custody columns request"; "peer_id" => %peer_id  );
+            }
             SyncRequestId::SingleBlob { id } => self.on_single_blob_response(
                 id,
                 peer_id,
@@ -911,6 +937,51 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         }
     }
 
+    fn rpc_data_column_received(
+        &mut self,
+        request_id: SyncRequestId,
+        peer_id: PeerId,
+        data_column: Option<Arc<DataColumnSidecar<T::EthSpec>>>,
+        seen_timestamp: Duration,
+    ) {
+        match request_id {
+            SyncRequestId::SingleBlock { .. } | SyncRequestId::SingleBlob { .. } => {
+                crit!(self.log, "Single data column received during block or blob request"; "peer_id" => %peer_id  );
+            }
+            SyncRequestId::SingleCustodyColumns { id } => self.on_single_custody_columns_response(
+                id,
+                peer_id,
+                match data_column {
+                    Some(data_column) => RpcEvent::Response(data_column, seen_timestamp),
+                    None => RpcEvent::StreamTermination,
+                },
+            ),
+            SyncRequestId::RangeBlockAndBlobs { id } => {
+                self.range_block_and_blobs_response(id, peer_id, data_column.into())
+            }
+        }
+    }
+
+    fn on_single_custody_columns_response(
+        &mut self,
+        id: SingleLookupReqId,
+        peer_id: PeerId,
+        data_column: RpcEvent<Arc<DataColumnSidecar<T::EthSpec>>>,
+    ) {
+        if let Some((custody_id, resp)) =
+            self.network
+                .on_data_columns_by_root_response(id, peer_id, data_column)
+        {
+            self.block_lookups
+                .on_download_response::<CustodyRequestState<T::EthSpec>>(
+                    custody_id,
+                    peer_id,
+                    resp,
+                    &mut self.network,
+                )
+        }
+    }
+
     /// Handles receiving a response for a range sync request that should have both blocks and
     /// blobs.
     fn range_block_and_blobs_response(
@@ -923,6 +994,9 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             .network
             .range_block_and_blob_response(id, block_or_blob)
         {
+            // The last response may come from a column peer, but range sync tracks the batch with
+            // the peer serving its blocks.
+            let peer_id = resp.peer_id;
             match resp.responses {
                 Ok(blocks) => {
                     match resp.sender_id {
@@ -970,15 +1044,18 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                         "Blocks and blobs request for range received invalid data";
                         "peer_id" => %peer_id,
                         "sender_id" => ?resp.sender_id,
-                        "error" => e.clone()
+                        "faulty_peer_id" => %e.peer_id,
+                        "error" => e.msg.clone()
                     );
                     let id = SyncRequestId::RangeBlockAndBlobs { id };
+                    // Columns may be served by peers other than the batch peer, so penalise the
+                    // one that sent the faulty data.
                     self.network.report_peer(
-                        peer_id,
+                        e.peer_id,
                         PeerAction::MidToleranceError,
                         "block_blob_faulty_batch",
                     );
-                    self.inject_error(peer_id, id, RPCError::InvalidData(e))
+                    self.inject_error(peer_id, id, RPCError::InvalidData(e.msg))
                 }
             }
         }
@@ -1,9 +1,14 @@
 //! Provides network functionality for the Syncing thread. This fundamentally wraps a network
 //! channel and stores a global RPC ID to perform requests.
 
-use self::requests::{ActiveBlobsByRootRequest, ActiveBlocksByRootRequest};
-pub use self::requests::{BlobsByRootSingleBlockRequest, BlocksByRootSingleRequest};
-use super::block_sidecar_coupling::BlocksAndBlobsRequestInfo;
+use self::requests::{
+    ActiveBlobsByRootRequest, ActiveBlocksByRootRequest, ActiveCustodyRequest,
+    ActiveDataColumnsByRootRequest,
+};
+pub use self::requests::{
+    BlobsByRootSingleBlockRequest, BlocksByRootSingleRequest, DataColumnsByRootSingleBlockRequest,
+};
+use super::block_sidecar_coupling::{BlocksAndBlobsRequestInfo, CouplingError};
 use super::range_sync::{BatchId, ByRangeRequestType, ChainId};
 use crate::metrics;
 use crate::network_beacon_processor::NetworkBeaconProcessor;
@@ -14,25 +19,32 @@ use crate::sync::manager::BlockProcessType;
 use beacon_chain::block_verification_types::RpcBlock;
 use beacon_chain::{BeaconChain, BeaconChainTypes, BlockProcessStatus, EngineState};
 use fnv::FnvHashMap;
-use lighthouse_network::rpc::methods::BlobsByRangeRequest;
+use lighthouse_network::rpc::methods::{BlobsByRangeRequest, DataColumnsByRangeRequest};
 use lighthouse_network::rpc::{BlocksByRangeRequest, GoodbyeReason, RPCError};
 use lighthouse_network::service::api_types::{AppRequestId, Id, SingleLookupReqId, SyncRequestId};
 use lighthouse_network::{Client, NetworkGlobals, PeerAction, PeerId, ReportSource, Request};
+use rand::seq::SliceRandom;
 pub use requests::LookupVerifyError;
 use slog::{debug, error, trace, warn};
 use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc;
 use types::blob_sidecar::FixedBlobSidecarList;
-use types::{BlobSidecar, EthSpec, Hash256, SignedBeaconBlock};
+use types::data_column_sidecar::ColumnIndex;
+use types::{
+    BlobSidecar, DataColumnSidecar, DataColumnSidecarList, EthSpec, Hash256, SignedBeaconBlock,
+};
 
 mod requests;
 
 pub struct BlocksAndBlobsByRangeResponse<E: EthSpec> {
     pub sender_id: RangeRequestId,
-    pub responses: Result<Vec<RpcBlock<E>>, String>,
+    pub responses: Result<Vec<RpcBlock<E>>, CouplingError>,
     pub request_type: ByRangeRequestType,
+    /// The peer serving the blocks of this request, and the one range sync tracks the batch with.
+    pub peer_id: PeerId,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -123,6 +135,13 @@ pub struct SyncNetworkContext<T: BeaconChainTypes> {
     /// A mapping of active BlobsByRoot requests, including both current slot and parent lookups.
     blobs_by_root_requests: FnvHashMap<SingleLookupReqId, ActiveBlobsByRootRequest<T::EthSpec>>,
 
+    /// A mapping of active DataColumnsByRoot requests, each one part of a custody lookup.
+    data_columns_by_root_requests:
+        FnvHashMap<SingleLookupReqId, ActiveDataColumnsByRootRequest<T::EthSpec>>,
+
+    /// A mapping of active custody lookups, keyed by the id returned to lookup sync.
+    custody_by_root_requests: FnvHashMap<SingleLookupReqId, ActiveCustodyRequest<T::EthSpec>>,
+
     /// BlocksByRange requests paired with BlobsByRange
     range_blocks_and_blobs_requests:
         FnvHashMap<Id, (RangeRequestId, BlocksAndBlobsRequestInfo<T::EthSpec>)>,
@@ -144,6 +163,7 @@ pub struct SyncNetworkContext<T: BeaconChainTypes> {
 pub enum BlockOrBlob<E: EthSpec> {
     Block(Option<Arc<SignedBeaconBlock<E>>>),
     Blob(Option<Arc<BlobSidecar<E>>>),
+    CustodyColumns(Option<Arc<DataColumnSidecar<E>>>),
 }
 
 impl<E: EthSpec> From<Option<Arc<SignedBeaconBlock<E>>>> for BlockOrBlob<E> {
@@ -158,6 +178,12 @@ impl<E: EthSpec> From<Option<Arc<BlobSidecar<E>>>> for BlockOrBlob<E> {
     }
 }
 
+impl<E: EthSpec> From<Option<Arc<DataColumnSidecar<E>>>> for BlockOrBlob<E> {
+    fn from(data_column: Option<Arc<DataColumnSidecar<E>>>) -> Self {
+        BlockOrBlob::CustodyColumns(data_column)
+    }
+}
+
 impl<T: BeaconChainTypes> SyncNetworkContext<T> {
     pub fn new(
         network_send: mpsc::UnboundedSender<NetworkMessage<T::EthSpec>>,
@@ -171,6 +197,8 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             request_id: 1,
             blocks_by_root_requests: <_>::default(),
             blobs_by_root_requests: <_>::default(),
+            data_columns_by_root_requests: <_>::default(),
+            custody_by_root_requests: <_>::default(),
             range_blocks_and_blobs_requests: FnvHashMap::default(),
             network_beacon_processor,
             chain,
@@ -184,7 +212,7 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             self.range_blocks_and_blobs_requests
                 .iter()
                 .filter_map(|(id, request)| {
-                    if request.1.peer_id == *peer_id {
+                    if request.1.is_peer_involved(peer_id) {
                         Some(SyncRequestId::RangeBlockAndBlobs { id: *id })
                     } else {
                         None
@@ -212,9 +240,21 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
                 }
             });
 
+        let failed_data_column_ids =
+            self.data_columns_by_root_requests
+                .iter()
+                .filter_map(|(id, request)| {
+                    if request.peer_id == *peer_id {
+                        Some(SyncRequestId::SingleCustodyColumns { id: *id })
+                    } else {
+                        None
+                    }
+                });
+
         failed_range_ids
             .chain(failed_block_ids)
             .chain(failed_blob_ids)
+            .chain(failed_data_column_ids)
             .collect()
     }
 
@@ -312,30 +352,85 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         request: BlocksByRangeRequest,
         sender_id: RangeRequestId,
     ) -> Result<Id, RpcRequestSendError> {
+        let start_slot = *request.start_slot();
+        let count = *request.count();
         let id = self.blocks_by_range_request(peer_id, batch_type, request)?;
-        self.range_blocks_and_blobs_requests.insert(
-            id,
-            (
-                sender_id,
-                BlocksAndBlobsRequestInfo::new(batch_type, peer_id),
-            ),
-        );
+        let mut info = BlocksAndBlobsRequestInfo::new(batch_type, peer_id);
+
+        if matches!(batch_type, ByRangeRequestType::BlocksAndColumns) {
+            let custody_columns = self.network_globals().custody_columns(&self.chain.spec);
+            let mut custody_column_peers = vec![];
+            for (column_peer, columns) in
+                self.select_peers_for_custody_columns(&custody_columns, peer_id)
+            {
+                debug!(
+                    self.log,
+                    "Sending DataColumnsByRange request";
+                    "method" => "DataColumnsByRange",
+                    "count" => count,
+                    "columns" => ?columns,
+                    "peer" => %column_peer,
+                );
+
+                self.network_send
+                    .send(NetworkMessage::SendRequest {
+                        peer_id: column_peer,
+                        request: Request::DataColumnsByRange(DataColumnsByRangeRequest {
+                            start_slot,
+                            count,
+                            columns: columns.clone(),
+                        }),
+                        request_id: AppRequestId::Sync(SyncRequestId::RangeBlockAndBlobs { id }),
+                    })
+                    .map_err(|_| RpcRequestSendError::NetworkSendError)?;
+                custody_column_peers.push((column_peer, columns));
+            }
+            info = info.with_custody_columns(custody_columns, custody_column_peers);
+        }
+
+        self.range_blocks_and_blobs_requests
+            .insert(id, (sender_id, info));
         Ok(id)
     }
 
-    pub fn range_request_failed(&mut self, request_id: Id) -> Option<RangeRequestId> {
+    /// Groups `columns` by the peer each one will be requested from. Each column is assigned to a
+    /// random connected peer custodying it, falling back to `default_peer` if there is none.
+    fn select_peers_for_custody_columns(
+        &self,
+        columns: &[ColumnIndex],
+        default_peer: PeerId,
+    ) -> HashMap<PeerId, Vec<ColumnIndex>> {
+        let mut columns_by_peer: HashMap<PeerId, Vec<ColumnIndex>> = HashMap::new();
+        for column_index in columns {
+            let peer_id = self
+                .network_globals()
+                .custody_peers_for_column(*column_index, &self.chain.spec)
+                .choose(&mut rand::thread_rng())
+                .copied()
+                .unwrap_or(default_peer);
+            columns_by_peer
+                .entry(peer_id)
+                .or_default()
+                .push(*column_index);
+        }
+        columns_by_peer
+    }
+
+    /// Drops a failed range request. Returns the id of its sender and the peer that served the
+    /// blocks of the request, if the request was still active.
+    pub fn range_request_failed(&mut self, request_id: Id) -> Option<(RangeRequestId, PeerId)> {
         let sender_id = self
             .range_blocks_and_blobs_requests
             .remove(&request_id)
-            .map(|(sender_id, _info)| sender_id);
-        if let Some(sender_id) = sender_id {
+            .map(|(sender_id, info)| (sender_id, info.peer_id));
+        if let Some((sender_id, peer_id)) = sender_id {
             debug!(
                 self.log,
                 "Sync range request failed";
                 "request_id" => request_id,
                 "sender_id" => ?sender_id
             );
-            Some(sender_id)
+            Some((sender_id, peer_id))
         } else {
             debug!(self.log, "Sync range request failed"; "request_id" => request_id);
             None
@@ -359,15 +454,20 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         match block_or_blob {
             BlockOrBlob::Block(maybe_block) => info.add_block_response(maybe_block),
             BlockOrBlob::Blob(maybe_sidecar) => info.add_sidecar_response(maybe_sidecar),
+            BlockOrBlob::CustodyColumns(maybe_column) => {
+                info.add_custody_column_response(maybe_column)
+            }
         }
         if info.is_finished() {
             // If the request is finished, dequeue everything
             let (sender_id, info) = entry.remove();
             let request_type = info.get_request_type();
+            let peer_id = info.peer_id;
             Some(BlocksAndBlobsByRangeResponse {
                 sender_id,
                 request_type,
                 responses: info.into_responses(),
+                peer_id,
             })
         } else {
             None
@@ -447,23 +547,14 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         lookup_id: SingleLookupId,
         peer_id: PeerId,
         block_root: Hash256,
-        downloaded_block_expected_blobs: Option<usize>,
+        downloaded_block: Option<Arc<SignedBeaconBlock<T::EthSpec>>>,
     ) -> Result<LookupRequestResult, RpcRequestSendError> {
-        let Some(expected_blobs) = downloaded_block_expected_blobs.or_else(|| {
-            // If the block is already being processed or fully validated, retrieve how many blobs
-            // it expects. Consider any stage of the block. If the block root has been validated, we
-            // can assert that this is the correct value of `blob_kzg_commitments_count`.
-            match self.chain.get_block_process_status(&block_root) {
-                BlockProcessStatus::Unknown => None,
-                BlockProcessStatus::NotValidated(block)
-                | BlockProcessStatus::ExecutionValidated(block) => Some(block.num_expected_blobs()),
-            }
-        }) else {
+        let Some(block) = self.downloaded_or_processing_block(block_root, downloaded_block) else {
             // Wait to download the block before downloading blobs. Then we can be sure that the
             // block has data, so there's no need to do "blind" requests for all possible blobs and
             // latter handle the case where if the peer sent no blobs, penalize.
-            // - if `downloaded_block_expected_blobs` is Some = block is downloading or processing.
-            // - if `num_expected_blobs` returns Some = block is processed.
+            // - if `downloaded_block` is Some = block is downloading or processing.
+            // - if `get_block_process_status` returns a block = block is processed.
             //
             // Lookup sync event safety: Reaching this code means that a block is not in any pre-import
             // cache nor in the request state of this lookup. Therefore, the block must either: (1) not
@@ -474,6 +565,12 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             return Ok(LookupRequestResult::Pending("waiting for block download"));
         };
 
+        // Blocks from PeerDAS epochs have their data fetched as custody columns instead.
+        if self.is_peer_das_enabled_for_block(&block) {
+            return Ok(LookupRequestResult::NoRequestNeeded);
+        }
+
+        let expected_blobs = block.num_expected_blobs();
         let imported_blob_indexes = self
             .chain
             .data_availability_checker
@@ -522,6 +619,127 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         Ok(LookupRequestResult::RequestSent(req_id))
     }
 
+    /// Request the custody columns of `block_root` that are not yet imported. The columns are
+    /// requested from the peers custodying them, with one `DataColumnsByRoot` request per peer.
+    /// The lookup receives a single result once all of those requests complete.
+    ///
+    /// Returns `NoRequestNeeded` if the block has no data or is from a pre-PeerDAS epoch.
+    pub fn custody_lookup_request(
+        &mut self,
+        lookup_id: SingleLookupId,
+        lookup_peer: PeerId,
+        block_root: Hash256,
+        downloaded_block: Option<Arc<SignedBeaconBlock<T::EthSpec>>>,
+    ) -> Result<LookupRequestResult, RpcRequestSendError> {
+        let Some(block) = self.downloaded_or_processing_block(block_root, downloaded_block) else {
+            // Lookup sync event safety: Refer to `Self::blob_lookup_request`
+            return Ok(LookupRequestResult::Pending("waiting for block download"));
+        };
+
+        if !self.is_peer_das_enabled_for_block(&block) || block.num_expected_blobs() == 0 {
+            return Ok(LookupRequestResult::NoRequestNeeded);
+        }
+
+        let imported_column_indexes = self
+            .chain
+            .data_availability_checker
+            .imported_custody_column_indexes(&block_root)
+            .unwrap_or_default();
+        // Include only the custody columns not yet imported (received through gossip)
+        let custody_indexes_to_fetch = self
+            .network_globals()
+            .custody_columns(&self.chain.spec)
+            .into_iter()
+            .filter(|index| !imported_column_indexes.contains(index))
+            .collect::<Vec<_>>();
+
+        if custody_indexes_to_fetch.is_empty() {
+            // No columns required, do not issue any request
+            return Ok(LookupRequestResult::NoRequestNeeded);
+        }
+
+        let req_id = self.next_id();
+        let id = SingleLookupReqId { lookup_id, req_id };
+        let columns_by_peer =
+            self.select_peers_for_custody_columns(&custody_indexes_to_fetch, lookup_peer);
+
+        debug!(
+            self.log,
+            "Starting custody columns lookup";
+            "block_root" => ?block_root,
+            "indices" => ?custody_indexes_to_fetch,
+            "peers" => columns_by_peer.len(),
+            "id" => ?id
+        );
+
+        for (peer_id, indices) in columns_by_peer.iter() {
+            let sub_id = SingleLookupReqId {
+                lookup_id,
+                req_id: self.next_id(),
+            };
+
+            debug!(
+                self.log,
+                "Sending DataColumnsByRoot Request";
+                "method" => "DataColumnsByRoot",
+                "block_root" => ?block_root,
+                "indices" => ?indices,
+                "peer" => %peer_id,
+                "id" => ?sub_id
+            );
+
+            let request = DataColumnsByRootSingleBlockRequest {
+                block_root,
+                indices: indices.clone(),
+            };
+
+            // Lookup sync event safety: Refer to `Self::block_lookup_request` `network_send.send`
+            // call. Each sub-request resolves the custody request or fails it entirely.
+            self.network_send
+                .send(NetworkMessage::SendRequest {
+                    peer_id: *peer_id,
+                    request: Request::DataColumnsByRoot(
+                        request.clone().into_request(&self.chain.spec),
+                    ),
+                    request_id: AppRequestId::Sync(SyncRequestId::SingleCustodyColumns {
+                        id: sub_id,
+                    }),
+                })
+                .map_err(|_| RpcRequestSendError::NetworkSendError)?;
+
+            self.data_columns_by_root_requests.insert(
+                sub_id,
+                ActiveDataColumnsByRootRequest::new(request, *peer_id, id),
+            );
+        }
+
+        self.custody_by_root_requests.insert(
+            id,
+            ActiveCustodyRequest::new(block_root, columns_by_peer.len()),
+        );
+
+        Ok(LookupRequestResult::RequestSent(req_id))
+    }
+
+    /// Returns the block of `block_root` if it has been downloaded by the lookup, or if it's
+    /// already being processed or fully validated. Consider any stage of the block. If the block
+    /// root has been validated, we can assert that its data commitments are correct.
+    fn downloaded_or_processing_block(
+        &self,
+        block_root: Hash256,
+        downloaded_block: Option<Arc<SignedBeaconBlock<T::EthSpec>>>,
+    ) -> Option<Arc<SignedBeaconBlock<T::EthSpec>>> {
+        downloaded_block.or_else(|| match self.chain.get_block_process_status(&block_root) {
+            BlockProcessStatus::Unknown => None,
+            BlockProcessStatus::NotValidated(block)
+            | BlockProcessStatus::ExecutionValidated(block) => Some(block),
+        })
+    }
+
+    fn is_peer_das_enabled_for_block(&self, block: &SignedBeaconBlock<T::EthSpec>) -> bool {
+        self.chain.spec.is_peer_das_enabled_for_epoch(block.epoch())
+    }
+
     pub fn is_execution_engine_online(&self) -> bool {
         self.execution_engine_state == EngineState::Online
     }
@@ -605,7 +823,11 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
 
         if let Some(data_availability_boundary) = self.chain.data_availability_boundary() {
             if epoch >= data_availability_boundary {
-                ByRangeRequestType::BlocksAndBlobs
+                if self.chain.spec.is_peer_das_enabled_for_epoch(epoch) {
+                    ByRangeRequestType::BlocksAndColumns
+                } else {
+                    ByRangeRequestType::BlocksAndBlobs
+                }
             } else {
                 ByRangeRequestType::Blocks
             }
@@ -714,6 +936,76 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         }
     }
 
+    /// Handles a response of one of the `DataColumnsByRoot` requests of a custody lookup. Returns
+    /// the id of the custody lookup and its result once all its requests complete, or as soon as
+    /// any of them fails.
+    #[allow(clippy::type_complexity)]
+    pub fn on_data_columns_by_root_response(
+        &mut self,
+        id: SingleLookupReqId,
+        peer_id: PeerId,
+        data_column: RpcEvent<Arc<DataColumnSidecar<T::EthSpec>>>,
+    ) -> Option<(
+        SingleLookupReqId,
+        RpcResponseResult<DataColumnSidecarList<T::EthSpec>>,
+    )> {
+        let Entry::Occupied(mut request) = self.data_columns_by_root_requests.entry(id) else {
+            metrics::inc_counter_vec(
+                &metrics::SYNC_UNKNOWN_NETWORK_REQUESTS,
+                &["data_columns_by_root"],
+            );
+            return None;
+        };
+        let parent_id = request.get().parent_request_id;
+
+        let resp = match data_column {
+            RpcEvent::Response(data_column, seen_timestamp) => {
+                let request = request.get_mut();
+                match request.add_response(data_column) {
+                    Ok(Some(data_columns)) => Ok((data_columns, seen_timestamp)),
+                    Ok(None) => return None,
+                    Err(e) => Err((e.into(), request.resolve())),
+                }
+            }
+            RpcEvent::StreamTermination => match request.remove().terminate() {
+                Ok(_) => return None,
+                // (err, false = not resolved) because terminate returns Ok() if resolved
+                Err(e) => Err((e.into(), false)),
+            },
+            RpcEvent::RPCError(e) => Err((e.into(), request.remove().resolve())),
+        };
+
+        match resp {
+            Ok((data_columns, seen_timestamp)) => {
+                // The custody request may have already failed due to a sibling request
+                let custody_request = self.custody_by_root_requests.get_mut(&parent_id)?;
+                let data_columns = custody_request.on_request_completed(data_columns)?;
+                debug!(
+                    self.log,
+                    "Custody columns lookup completed";
+                    "block_root" => ?custody_request.block_root(),
+                    "id" => ?parent_id
+                );
+                self.custody_by_root_requests.remove(&parent_id);
+                Some((parent_id, Ok((data_columns, seen_timestamp))))
+            }
+            // Same as `Self::on_single_blob_response`, a request only returns a single result
+            // downstream.
+            Err((e, resolved)) => {
+                if let RpcResponseError::VerifyError(e) = &e {
+                    self.report_peer(peer_id, PeerAction::LowToleranceError, e.into());
+                }
+                if resolved {
+                    return None;
+                }
+                // A failed request fails the whole custody request. Responses of the remaining
+                // requests are ignored once the custody request is dropped.
+                self.custody_by_root_requests.remove(&parent_id)?;
+                Some((parent_id, Err(e)))
+            }
+        }
+    }
+
     pub fn send_block_for_processing(
         &self,
         id: Id,
@@ -776,6 +1068,37 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             })
     }
 
+    pub fn send_custody_columns_for_processing(
+        &self,
+        id: Id,
+        block_root: Hash256,
+        custody_columns: DataColumnSidecarList<T::EthSpec>,
+        duration: Duration,
+    ) -> Result<(), SendErrorProcessor> {
+        let beacon_processor = self
+            .beacon_processor_if_enabled()
+            .ok_or(SendErrorProcessor::ProcessorNotAvailable)?;
+
+        debug!(self.log, "Sending custody columns for processing"; "block" => ?block_root, "id" => id);
+        // Lookup sync event safety: If `beacon_processor.send_rpc_custody_columns` returns Ok() sync
+        // must receive a single `SyncMessage::BlockComponentProcessed` event with this process type
+        beacon_processor
+            .send_rpc_custody_columns(
+                block_root,
+                custody_columns,
+                duration,
+                BlockProcessType::SingleCustodyColumn { id },
+            )
+            .map_err(|e| {
+                error!(
+                    self.log,
+                    "Failed to send sync custody columns to processor";
+                    "error" => ?e
+                );
+                SendErrorProcessor::SendError
+            })
+    }
+
     pub(crate) fn register_metrics(&self) {
         metrics::set_gauge_vec(
             &metrics::SYNC_ACTIVE_NETWORK_REQUESTS,
@@ -787,6 +1110,16 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             &["blobs_by_root"],
             self.blobs_by_root_requests.len() as i64,
         );
+        metrics::set_gauge_vec(
+            &metrics::SYNC_ACTIVE_NETWORK_REQUESTS,
+            &["data_columns_by_root"],
+            self.data_columns_by_root_requests.len() as i64,
+        );
+        metrics::set_gauge_vec(
+            &metrics::SYNC_ACTIVE_NETWORK_REQUESTS,
+            &["custody_by_root"],
+            self.custody_by_root_requests.len() as i64,
+        );
         metrics::set_gauge_vec(
             &metrics::SYNC_ACTIVE_NETWORK_REQUESTS,
             &["range_blocks"],
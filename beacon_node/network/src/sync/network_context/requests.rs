@@ -1,12 +1,18 @@
 use beacon_chain::get_block_root;
 use lighthouse_network::{
-    rpc::{methods::BlobsByRootRequest, BlocksByRootRequest},
+    rpc::{
+        methods::{BlobsByRootRequest, DataColumnsByRootRequest},
+        BlocksByRootRequest,
+    },
+    service::api_types::SingleLookupReqId,
     PeerId,
 };
 use std::sync::Arc;
 use strum::IntoStaticStr;
 use types::{
-    blob_sidecar::BlobIdentifier, BlobSidecar, ChainSpec, EthSpec, Hash256, SignedBeaconBlock,
+    blob_sidecar::BlobIdentifier,
+    data_column_sidecar::{ColumnIndex, DataColumnIdentifier},
+    BlobSidecar, ChainSpec, DataColumnSidecar, EthSpec, Hash256, SignedBeaconBlock,
 };
 
 #[derive(Debug, PartialEq, Eq, IntoStaticStr)]
@@ -16,6 +22,7 @@ pub enum LookupVerifyError {
     TooManyResponses,
     UnrequestedBlockRoot(Hash256),
     UnrequestedBlobIndex(u64),
+    UnrequestedColumnIndex(u64),
     InvalidInclusionProof,
     DuplicateData,
 }
@@ -164,3 +171,142 @@ impl<E: EthSpec> ActiveBlobsByRootRequest<E> {
         std::mem::replace(&mut self.resolved, true)
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct DataColumnsByRootSingleBlockRequest {
+    pub block_root: Hash256,
+    pub indices: Vec<ColumnIndex>,
+}
+
+impl DataColumnsByRootSingleBlockRequest {
+    pub fn into_request(self, spec: &ChainSpec) -> DataColumnsByRootRequest {
+        DataColumnsByRootRequest::new(
+            self.indices
+                .into_iter()
+                .map(|index| DataColumnIdentifier {
+                    block_root: self.block_root,
+                    index,
+                })
+                .collect(),
+            spec,
+        )
+    }
+}
+
+/// A `DataColumnsByRoot` request made to a single peer as part of a custody lookup. A custody
+/// lookup may be split into several of these requests, one per peer custodying the columns.
+pub struct ActiveDataColumnsByRootRequest<E: EthSpec> {
+    request: DataColumnsByRootSingleBlockRequest,
+    items: Vec<Arc<DataColumnSidecar<E>>>,
+    resolved: bool,
+    pub(crate) peer_id: PeerId,
+    /// Id of the custody lookup request this request is part of.
+    pub(crate) parent_request_id: SingleLookupReqId,
+}
+
+impl<E: EthSpec> ActiveDataColumnsByRootRequest<E> {
+    pub fn new(
+        request: DataColumnsByRootSingleBlockRequest,
+        peer_id: PeerId,
+        parent_request_id: SingleLookupReqId,
+    ) -> Self {
+        Self {
+            request,
+            items: vec![],
+            resolved: false,
+            peer_id,
+            parent_request_id,
+        }
+    }
+
+    /// Appends a chunk to this multi-item request. If all expected chunks are received, this
+    /// method returns `Some`, resolving the request before the stream terminator.
+    /// The active request SHOULD be dropped after `add_response` returns an error
+    pub fn add_response(
+        &mut self,
+        data_column: Arc<DataColumnSidecar<E>>,
+    ) -> Result<Option<Vec<Arc<DataColumnSidecar<E>>>>, LookupVerifyError> {
+        if self.resolved {
+            return Err(LookupVerifyError::TooManyResponses);
+        }
+
+        let block_root = data_column.block_root();
+        if self.request.block_root != block_root {
+            return Err(LookupVerifyError::UnrequestedBlockRoot(block_root));
+        }
+        if !data_column.verify_inclusion_proof() {
+            return Err(LookupVerifyError::InvalidInclusionProof);
+        }
+        if !self.request.indices.contains(&data_column.index) {
+            return Err(LookupVerifyError::UnrequestedColumnIndex(data_column.index));
+        }
+        if self.items.iter().any(|d| d.index == data_column.index) {
+            return Err(LookupVerifyError::DuplicateData);
+        }
+
+        self.items.push(data_column);
+        if self.items.len() >= self.request.indices.len() {
+            // All expected chunks received, return result early
+            self.resolved = true;
+            Ok(Some(std::mem::take(&mut self.items)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn terminate(self) -> Result<(), LookupVerifyError> {
+        if self.resolved {
+            Ok(())
+        } else {
+            Err(LookupVerifyError::NotEnoughResponsesReturned {
+                expected: self.request.indices.len(),
+                actual: self.items.len(),
+            })
+        }
+    }
+
+    /// Mark request as resolved (= has returned something downstream) while marking this status as
+    /// true for future calls.
+    pub fn resolve(&mut self) -> bool {
+        std::mem::replace(&mut self.resolved, true)
+    }
+}
+
+/// Tracks the download of all the custody columns of a single block. The columns are fetched with
+/// one `ActiveDataColumnsByRootRequest` per custodial peer, and this request resolves once every
+/// one of them has returned its columns.
+pub struct ActiveCustodyRequest<E: EthSpec> {
+    block_root: Hash256,
+    /// Number of `DataColumnsByRoot` requests that have not returned yet.
+    pending_requests: usize,
+    columns: Vec<Arc<DataColumnSidecar<E>>>,
+}
+
+impl<E: EthSpec> ActiveCustodyRequest<E> {
+    pub fn new(block_root: Hash256, pending_requests: usize) -> Self {
+        Self {
+            block_root,
+            pending_requests,
+            columns: vec![],
+        }
+    }
+
+    pub fn block_root(&self) -> Hash256 {
+        self.block_root
+    }
+
+    /// Registers the columns returned by one of the `DataColumnsByRoot` requests of this custody
+    /// request. Returns all the columns once the last pending request has returned.
+    pub fn on_request_completed(
+        &mut self,
+        columns: Vec<Arc<DataColumnSidecar<E>>>,
+    ) -> Option<Vec<Arc<DataColumnSidecar<E>>>> {
+        self.columns.extend(columns);
+        self.pending_requests = self.pending_requests.saturating_sub(1);
+        if self.pending_requests == 0 {
+            Some(std::mem::take(&mut self.columns))
+        } else {
+            None
+        }
+    }
+}
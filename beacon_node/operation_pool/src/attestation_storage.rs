@@ -331,8 +331,14 @@ impl<E: EthSpec> AttestationMap<E> {
     /// Aggregate Electra attestations for the same attestation data signed by different
     /// committees.
     ///
+    /// The best attestation for each committee is aggregated into one on-chain aggregate. If
+    /// `layered` is set, the second best for each committee is aggregated into another, and so on.
+    /// This lets inferior attestations share a place in the block with attestations from other
+    /// committees, rather than each occupying a place of their own. Otherwise the inferior
+    /// attestations are left as-is.
+    ///
     /// Non-Electra attestations are left as-is.
-    pub fn aggregate_across_committees(&mut self, checkpoint_key: CheckpointKey) {
+    pub fn aggregate_across_committees(&mut self, checkpoint_key: CheckpointKey, layered: bool) {
         let Some(attestation_map) = self.checkpoint_map.get_mut(&checkpoint_key) else {
             return;
         };
@@ -340,14 +346,13 @@ impl<E: EthSpec> AttestationMap<E> {
             let unaggregated_attestations = std::mem::take(compact_indexed_attestations);
             let mut aggregated_attestations: Vec<CompactIndexedAttestation<E>> = vec![];
 
-            // Aggregate the best attestations for each committee and leave the rest.
-            let mut best_attestations_by_committee: BTreeMap<
+            let mut attestations_by_committee: BTreeMap<
                 u64,
-                CompactIndexedAttestationElectra<E>,
+                Vec<CompactIndexedAttestationElectra<E>>,
             > = BTreeMap::new();
 
             for committee_attestation in unaggregated_attestations {
-                let mut electra_attestation = match committee_attestation {
+                let electra_attestation = match committee_attestation {
                     CompactIndexedAttestation::Electra(att)
                         if att.committee_bits.num_set_bits() == 1 =>
                     {
@@ -365,35 +370,46 @@ impl<E: EthSpec> AttestationMap<E> {
                     }
                 };
                 if let Some(committee_index) = electra_attestation.committee_index() {
-                    if let Some(existing_attestation) =
-                        best_attestations_by_committee.get_mut(&committee_index)
-                    {
-                        // Search for the best (most aggregation bits) attestation for this committee
-                        // index.
-                        if electra_attestation.aggregation_bits.num_set_bits()
-                            > existing_attestation.aggregation_bits.num_set_bits()
-                        {
-                            // New attestation is better than the previously known one for this
-                            // committee. Replace it.
-                            std::mem::swap(existing_attestation, &mut electra_attestation);
-                        }
-                        // Put the inferior attestation into the list of aggregated attestations
-                        // without performing any cross-committee aggregation.
-                        aggregated_attestations
-                            .push(CompactIndexedAttestation::Electra(electra_attestation));
-                    } else {
-                        // First attestation seen for this committee. Place it in the map
-                        // provisionally.
-                        best_attestations_by_committee.insert(committee_index, electra_attestation);
-                    }
+                    attestations_by_committee
+                        .entry(committee_index)
+                        .or_default()
+                        .push(electra_attestation);
                 }
             }
 
-            if let Some(on_chain_aggregate) =
-                Self::compute_on_chain_aggregate(best_attestations_by_committee)
-            {
+            // Sort each committee's attestations so that the best (most aggregation bits) is last.
+            for attestations in attestations_by_committee.values_mut() {
+                attestations.sort_by_key(|att| att.aggregation_bits.num_set_bits());
+            }
+
+            // Take the next best attestation from each committee until all have been aggregated, or
+            // only the best if not layering.
+            loop {
+                let next_best_by_committee = attestations_by_committee
+                    .iter_mut()
+                    .filter_map(|(committee_index, attestations)| {
+                        Some((*committee_index, attestations.pop()?))
+                    })
+                    .collect();
+                let Some(on_chain_aggregate) =
+                    Self::compute_on_chain_aggregate(next_best_by_committee)
+                else {
+                    break;
+                };
                 aggregated_attestations
                     .push(CompactIndexedAttestation::Electra(on_chain_aggregate));
+
+                if !layered {
+                    // Put the inferior attestations into the list of aggregated attestations
+                    // without performing any cross-committee aggregation.
+                    aggregated_attestations.extend(
+                        std::mem::take(&mut attestations_by_committee)
+                            .into_values()
+                            .flatten()
+                            .map(CompactIndexedAttestation::Electra),
+                    );
+                    break;
+                }
             }
 
             *compact_indexed_attestations = aggregated_attestations;
@@ -24,10 +24,11 @@ use crate::attestation_storage::{AttestationMap, CheckpointKey};
 use crate::bls_to_execution_changes::BlsToExecutionChanges;
 use crate::sync_aggregate_id::SyncAggregateId;
 use attester_slashing::AttesterSlashingMaxCover;
-use max_cover::maximum_cover;
+use max_cover::{maximum_cover, maximum_cover_until};
 use parking_lot::{RwLock, RwLockWriteGuard};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
+use serde::{Deserialize, Serialize};
 use state_processing::per_block_processing::errors::AttestationValidationError;
 use state_processing::per_block_processing::{
     get_slashable_indices_modular, verify_exit, VerifySignatures,
@@ -36,6 +37,7 @@ use state_processing::{SigVerifiedOp, VerifyOperation};
 use std::collections::{hash_map::Entry, HashMap, HashSet};
 use std::marker::PhantomData;
 use std::ptr;
+use std::time::{Duration, Instant};
 use types::{
     sync_aggregate::Error as SyncAggregateError, typenum::Unsigned, AbstractExecPayload,
     Attestation, AttestationData, AttesterSlashing, BeaconState, BeaconStateError, ChainSpec,
@@ -61,9 +63,54 @@ pub struct OperationPool<E: EthSpec + Default> {
     bls_to_execution_changes: RwLock<BlsToExecutionChanges<E>>,
     /// Reward cache for accelerating attestation packing.
     reward_cache: RwLock<RewardCache>,
+    /// How operations are selected for inclusion in a block.
+    packing_strategy: PackingStrategy,
     _phantom: PhantomData<E>,
 }
 
+/// How the operation pool selects operations for inclusion in a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PackingStrategy {
+    /// Select attestations and attester slashings with a greedy approximation to the maximum
+    /// cover, and proposer slashings in no particular order.
+    #[default]
+    Greedy,
+    /// Search for the attestations and slashings which maximise the proposer's reward, spending up
+    /// to `time_budget` on each block and falling back to the greedy selection when the budget
+    /// runs out.
+    ///
+    /// Electra attestations from different committees are also aggregated in layers, see
+    /// `AttestationMap::aggregate_across_committees`.
+    ///
+    /// The sync aggregate needs no search: it is independent of the other operations, and is
+    /// already formed from the best contribution for every subcommittee.
+    RewardOptimised { time_budget: Duration },
+}
+
+impl PackingStrategy {
+    /// The time by which the packing of a block started now must finish, or `None` if packing
+    /// doesn't search for the best operations.
+    pub fn deadline(&self) -> Option<Instant> {
+        match self {
+            PackingStrategy::Greedy => None,
+            PackingStrategy::RewardOptimised { time_budget } => Some(Instant::now() + *time_budget),
+        }
+    }
+}
+
+/// Select up to `limit` of `items`, searching for the best selection until `deadline` if it is
+/// set, or using the greedy selection otherwise.
+fn pack<I, T>(items: I, limit: usize, label: &str, deadline: Option<Instant>) -> Vec<T>
+where
+    I: IntoIterator<Item = T>,
+    T: MaxCover,
+{
+    match deadline {
+        Some(deadline) => maximum_cover_until(items, limit, label, deadline),
+        None => maximum_cover(items, limit, label),
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum OpPoolError {
     GetAttestationsTotalBalanceError(BeaconStateError),
@@ -102,6 +149,17 @@ impl<E: EthSpec> OperationPool<E> {
         Self::default()
     }
 
+    /// Set how operations are selected for inclusion in a block.
+    pub fn set_packing_strategy(&mut self, packing_strategy: PackingStrategy) {
+        self.packing_strategy = packing_strategy;
+    }
+
+    /// The deadline for packing a block started now, to be shared by every call that selects
+    /// operations for the block. See `PackingStrategy::deadline`.
+    pub fn packing_deadline(&self) -> Option<Instant> {
+        self.packing_strategy.deadline()
+    }
+
     /// Insert a sync contribution into the pool. We don't aggregate these contributions until they
     /// are retrieved from the pool.
     ///
@@ -181,6 +239,18 @@ impl<E: EthSpec> OperationPool<E> {
             .sum()
     }
 
+    /// Returns all known sync contributions.
+    ///
+    /// This method may return objects that are invalid for block inclusion.
+    pub fn get_all_sync_contributions(&self) -> Vec<SyncCommitteeContribution<E>> {
+        self.sync_contributions
+            .read()
+            .values()
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
     /// Remove sync contributions which are too old to be included in a block.
     pub fn prune_sync_contributions(&self, current_slot: Slot) {
         // Prune sync contributions that are from before the previous slot.
@@ -254,6 +324,25 @@ impl<E: EthSpec> OperationPool<E> {
         prev_epoch_validity_filter: impl for<'a> FnMut(&CompactAttestationRef<'a, E>) -> bool + Send,
         curr_epoch_validity_filter: impl for<'a> FnMut(&CompactAttestationRef<'a, E>) -> bool + Send,
         spec: &ChainSpec,
+    ) -> Result<Vec<Attestation<E>>, OpPoolError> {
+        self.get_attestations_before(
+            state,
+            prev_epoch_validity_filter,
+            curr_epoch_validity_filter,
+            self.packing_deadline(),
+            spec,
+        )
+    }
+
+    /// Get a list of attestations for inclusion in a block, as for `get_attestations`, finishing
+    /// the search for the best attestations by `deadline`.
+    pub fn get_attestations_before(
+        &self,
+        state: &BeaconState<E>,
+        prev_epoch_validity_filter: impl for<'a> FnMut(&CompactAttestationRef<'a, E>) -> bool + Send,
+        curr_epoch_validity_filter: impl for<'a> FnMut(&CompactAttestationRef<'a, E>) -> bool + Send,
+        deadline: Option<Instant>,
+        spec: &ChainSpec,
     ) -> Result<Vec<Attestation<E>>, OpPoolError> {
         let fork_name = state.fork_name_unchecked();
         if !matches!(state, BeaconState::Base(_)) {
@@ -287,8 +376,12 @@ impl<E: EthSpec> OperationPool<E> {
         let mut all_attestations = self.attestations.write();
 
         if fork_name.electra_enabled() {
-            all_attestations.aggregate_across_committees(prev_epoch_key);
-            all_attestations.aggregate_across_committees(curr_epoch_key);
+            let layered = matches!(
+                self.packing_strategy,
+                PackingStrategy::RewardOptimised { .. }
+            );
+            all_attestations.aggregate_across_committees(prev_epoch_key, layered);
+            all_attestations.aggregate_across_committees(curr_epoch_key, layered);
         }
 
         let all_attestations = parking_lot::RwLockWriteGuard::downgrade(all_attestations);
@@ -338,12 +431,22 @@ impl<E: EthSpec> OperationPool<E> {
                 if prev_epoch_key == curr_epoch_key {
                     vec![]
                 } else {
-                    maximum_cover(prev_epoch_att, prev_epoch_limit, "prev_epoch_attestations")
+                    pack(
+                        prev_epoch_att,
+                        prev_epoch_limit,
+                        "prev_epoch_attestations",
+                        deadline,
+                    )
                 }
             },
             move || {
                 let _timer = metrics::start_timer(&metrics::ATTESTATION_CURR_EPOCH_PACKING_TIME);
-                maximum_cover(curr_epoch_att, curr_epoch_limit, "curr_epoch_attestations")
+                pack(
+                    curr_epoch_att,
+                    curr_epoch_limit,
+                    "curr_epoch_attestations",
+                    deadline,
+                )
             },
         );
 
@@ -395,8 +498,37 @@ impl<E: EthSpec> OperationPool<E> {
         Vec<AttesterSlashing<E>>,
         Vec<SignedVoluntaryExit>,
     ) {
+        self.get_slashings_and_exits_before(state, self.packing_deadline(), spec)
+    }
+
+    /// Get slashings and exits for inclusion in a block, as for `get_slashings_and_exits`,
+    /// finishing the search for the best attester slashings by `deadline`.
+    pub fn get_slashings_and_exits_before(
+        &self,
+        state: &BeaconState<E>,
+        deadline: Option<Instant>,
+        spec: &ChainSpec,
+    ) -> (
+        Vec<ProposerSlashing>,
+        Vec<AttesterSlashing<E>>,
+        Vec<SignedVoluntaryExit>,
+    ) {
+        let proposer_slashings_reader = self.proposer_slashings.read();
+        let mut candidate_proposer_slashings: Vec<_> = proposer_slashings_reader.values().collect();
+        if let PackingStrategy::RewardOptimised { .. } = self.packing_strategy {
+            // Proposer slashings never conflict with each other, so the most valuable are those
+            // which slash the most stake.
+            candidate_proposer_slashings.sort_by_key(|slashing| {
+                std::cmp::Reverse(
+                    state
+                        .validators()
+                        .get(slashing.as_inner().proposer_index() as usize)
+                        .map_or(0, |validator| validator.effective_balance),
+                )
+            });
+        }
         let proposer_slashings = filter_limit_operations(
-            self.proposer_slashings.read().values(),
+            candidate_proposer_slashings.into_iter(),
             |slashing| {
                 slashing.signature_is_still_valid(&state.fork())
                     && state
@@ -415,7 +547,7 @@ impl<E: EthSpec> OperationPool<E> {
             .map(|s| s.proposer_index())
             .collect();
 
-        let attester_slashings = self.get_attester_slashings(state, &mut to_be_slashed);
+        let attester_slashings = self.get_attester_slashings(state, &mut to_be_slashed, deadline);
 
         let voluntary_exits = self.get_voluntary_exits(
             state,
@@ -433,6 +565,7 @@ impl<E: EthSpec> OperationPool<E> {
         &self,
         state: &BeaconState<E>,
         to_be_slashed: &mut HashSet<u64>,
+        deadline: Option<Instant>,
     ) -> Vec<AttesterSlashing<E>> {
         let reader = self.attester_slashings.read();
 
@@ -444,10 +577,11 @@ impl<E: EthSpec> OperationPool<E> {
             }
         });
 
-        maximum_cover(
+        pack(
             relevant_attester_slashings,
             E::MaxAttesterSlashings::to_usize(),
             "attester_slashings",
+            deadline,
         )
         .into_iter()
         .map(|cover| {
@@ -641,6 +775,82 @@ impl<E: EthSpec> OperationPool<E> {
             .prune(head_block, head_state, spec)
     }
 
+    /// Get all slashings, exits and address changes which could still be included in a block
+    /// built on top of `state`.
+    ///
+    /// Unlike `get_slashings_and_exits`, no per-block limits are applied and conflicts between
+    /// operations are ignored. This is intended for selecting operations to re-publish on gossip.
+    pub fn get_pending_operations(
+        &self,
+        state: &BeaconState<E>,
+        spec: &ChainSpec,
+    ) -> PendingOperations<E> {
+        let current_epoch = state.current_epoch();
+        let proposer_slashings = filter_limit_operations(
+            self.proposer_slashings.read().values(),
+            |slashing| {
+                slashing.signature_is_still_valid(&state.fork())
+                    && state
+                        .validators()
+                        .get(slashing.as_inner().proposer_index() as usize)
+                        .map_or(false, |validator| validator.is_slashable_at(current_epoch))
+            },
+            |slashing| slashing.as_inner().clone(),
+            usize::MAX,
+        );
+        let attester_slashings = filter_limit_operations(
+            self.attester_slashings.read().iter(),
+            |slashing| {
+                slashing.signature_is_still_valid(&state.fork())
+                    && get_slashable_indices_modular(
+                        state,
+                        slashing.as_inner().to_ref(),
+                        |_, validator| validator.is_slashable_at(current_epoch),
+                    )
+                    .map_or(false, |indices| !indices.is_empty())
+            },
+            |slashing| slashing.as_inner().clone(),
+            usize::MAX,
+        );
+        let voluntary_exits = filter_limit_operations(
+            self.voluntary_exits.read().values(),
+            |exit| {
+                exit.signature_is_still_valid(&state.fork())
+                    && verify_exit(state, None, exit.as_inner(), VerifySignatures::False, spec)
+                        .is_ok()
+            },
+            |exit| exit.as_inner().clone(),
+            usize::MAX,
+        );
+        // Address changes can't be gossiped prior to Capella.
+        let bls_to_execution_changes = if state.fork_name_unchecked().capella_enabled() {
+            filter_limit_operations(
+                self.bls_to_execution_changes.read().iter_fifo(),
+                |address_change| {
+                    address_change.signature_is_still_valid(&state.fork())
+                        && state
+                            .get_validator(
+                                address_change.as_inner().message.validator_index as usize,
+                            )
+                            .map_or(false, |validator| {
+                                !validator.has_eth1_withdrawal_credential(spec)
+                            })
+                },
+                |address_change| address_change.as_inner().clone(),
+                usize::MAX,
+            )
+        } else {
+            vec![]
+        };
+
+        PendingOperations {
+            proposer_slashings,
+            attester_slashings,
+            voluntary_exits,
+            bls_to_execution_changes,
+        }
+    }
+
     /// Prune all types of transactions given the latest head state and head fork.
     pub fn prune_all<Payload: AbstractExecPayload<E>>(
         &self,
@@ -733,6 +943,15 @@ impl<E: EthSpec> OperationPool<E> {
     }
 }
 
+/// Operations from the pool which are still valid for inclusion on top of some state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingOperations<E: EthSpec> {
+    pub proposer_slashings: Vec<ProposerSlashing>,
+    pub attester_slashings: Vec<AttesterSlashing<E>>,
+    pub voluntary_exits: Vec<SignedVoluntaryExit>,
+    pub bls_to_execution_changes: Vec<SignedBlsToExecutionChange>,
+}
+
 /// Filter up to a maximum number of operations out of an iterator.
 fn filter_limit_operations<'a, T, V: 'a, I, F, G>(
     operations: I,
@@ -1434,6 +1653,99 @@ mod release_tests {
         }
     }
 
+    /// Sum the proposer rewards for including `attestations` in a block at `state`.
+    fn total_attestation_reward(
+        state: &BeaconState<MainnetEthSpec>,
+        attestations: Vec<Attestation<MainnetEthSpec>>,
+        spec: &ChainSpec,
+    ) -> u64 {
+        let total_active_balance = state.get_total_active_balance().unwrap();
+        let mut reward_cache = RewardCache::default();
+        reward_cache.update(state).unwrap();
+
+        let mut seen_indices = BTreeSet::<u64>::new();
+        let mut total_reward = 0;
+        for att in attestations {
+            let attesting_indices = get_attesting_indices_from_state(state, att.to_ref()).unwrap();
+            let split_attestation = SplitAttestation::new(att, attesting_indices);
+            let fresh_validators_rewards = AttMaxCover::new(
+                split_attestation.as_ref(),
+                state,
+                &reward_cache,
+                total_active_balance,
+                spec,
+            )
+            .unwrap()
+            .fresh_validators_rewards;
+            for (validator_index, reward) in fresh_validators_rewards {
+                if seen_indices.insert(validator_index) {
+                    total_reward += reward;
+                }
+            }
+        }
+        total_reward
+    }
+
+    #[test]
+    fn reward_optimised_packing_at_least_greedy() {
+        let num_committees = 4;
+        let (harness, ref spec) = attestation_test_state::<MainnetEthSpec>(num_committees);
+
+        let mut state = get_current_state_initialize_epoch_cache(&harness, &spec);
+        let greedy_op_pool = OperationPool::<MainnetEthSpec>::new();
+        let mut optimised_op_pool = OperationPool::<MainnetEthSpec>::new();
+        optimised_op_pool.set_packing_strategy(PackingStrategy::RewardOptimised {
+            time_budget: Duration::from_millis(500),
+        });
+
+        let slot = state.slot();
+        let num_validators = num_committees
+            * MainnetEthSpec::slots_per_epoch() as usize
+            * spec.target_committee_size;
+        let attestations = harness.make_attestations(
+            (0..num_validators).collect::<Vec<_>>().as_slice(),
+            &state,
+            Hash256::zero(),
+            SignedBeaconBlockHash::from(Hash256::zero()),
+            slot,
+        );
+
+        // Insert overlapping aggregates of varying sizes, so that packing is non-trivial.
+        for (atts, _) in attestations {
+            let att_0 = atts[0].0.clone();
+            for step_size in [2, 3, 5] {
+                for chunk in atts.chunks_exact(step_size) {
+                    let mut aggregate = att_0.clone();
+                    for (att, _) in chunk {
+                        aggregate.aggregate(att.to_ref());
+                    }
+                    let attesting_indices =
+                        get_attesting_indices_from_state(&state, aggregate.to_ref()).unwrap();
+                    greedy_op_pool
+                        .insert_attestation(aggregate.clone(), attesting_indices.clone())
+                        .unwrap();
+                    optimised_op_pool
+                        .insert_attestation(aggregate, attesting_indices)
+                        .unwrap();
+                }
+            }
+        }
+
+        *state.slot_mut() += spec.min_attestation_inclusion_delay;
+        let greedy_attestations = greedy_op_pool
+            .get_attestations(&state, |_| true, |_| true, spec)
+            .unwrap();
+        let optimised_attestations = optimised_op_pool
+            .get_attestations(&state, |_| true, |_| true, spec)
+            .unwrap();
+
+        assert!(!optimised_attestations.is_empty());
+        assert!(
+            total_attestation_reward(&state, optimised_attestations, spec)
+                >= total_attestation_reward(&state, greedy_attestations, spec)
+        );
+    }
+
     /// Insert two slashings for the same proposer and ensure only one is returned.
     #[test]
     fn duplicate_proposer_slashing() {
@@ -1495,6 +1807,36 @@ mod release_tests {
         );
     }
 
+    // Slashings for validators which are already slashed should not be considered pending.
+    #[test]
+    fn pending_operations_exclude_slashed() {
+        let harness = get_harness(32, None);
+        let spec = &harness.spec;
+        let mut state = harness.get_current_state();
+        let op_pool = OperationPool::<MainnetEthSpec>::new();
+
+        let proposer_slashing = harness.make_proposer_slashing(0);
+        let attester_slashing = harness.make_attester_slashing(vec![1, 3]);
+        op_pool.insert_proposer_slashing(proposer_slashing.clone().validate(&state, spec).unwrap());
+        op_pool.insert_attester_slashing(attester_slashing.clone().validate(&state, spec).unwrap());
+
+        let pending = op_pool.get_pending_operations(&state, spec);
+        assert_eq!(pending.proposer_slashings, vec![proposer_slashing]);
+        assert_eq!(pending.attester_slashings, vec![attester_slashing.clone()]);
+
+        state.validators_mut().get_mut(0).unwrap().slashed = true;
+        state.validators_mut().get_mut(1).unwrap().slashed = true;
+
+        // The attester slashing remains pending while it can still slash validator 3.
+        let pending = op_pool.get_pending_operations(&state, spec);
+        assert!(pending.proposer_slashings.is_empty());
+        assert_eq!(pending.attester_slashings, vec![attester_slashing]);
+
+        state.validators_mut().get_mut(3).unwrap().slashed = true;
+        let pending = op_pool.get_pending_operations(&state, spec);
+        assert!(pending.attester_slashings.is_empty());
+    }
+
     // Check that we get maximum coverage for attester slashings (highest qty of validators slashed)
     #[test]
     fn simple_max_cover_attester_slashing() {
@@ -2129,7 +2471,7 @@ mod release_tests {
         // slashed.
         let mut to_be_slashed = hashset! {0};
         let attester_slashings =
-            op_pool.get_attester_slashings(&altair_head.beacon_state, &mut to_be_slashed);
+            op_pool.get_attester_slashings(&altair_head.beacon_state, &mut to_be_slashed, None);
         assert!(attester_slashings.contains(&slashing2));
         assert!(attester_slashings.contains(&slashing3));
         assert_eq!(attester_slashings.len(), 2);
@@ -1,5 +1,7 @@
 use crate::metrics;
 use itertools::Itertools;
+use std::cmp::Reverse;
+use std::time::Instant;
 
 /// Trait for types that we can compute a maximum cover for.
 ///
@@ -100,6 +102,128 @@ where
     result
 }
 
+/// Compute a maximum cover, searching for an optimal solution until `deadline`.
+///
+/// The greedy solution from `maximum_cover` is the starting point, so the result is never worse
+/// than it. A depth-first branch-and-bound search then tries to improve on it, pruning every branch
+/// whose bound (its score plus the highest initial scores of the items that could still be added
+/// within `limit`) can't beat the best solution found so far. The bound is admissible because
+/// scores only decrease as elements are covered. If `deadline` passes first, the best solution
+/// found so far is returned.
+///
+/// Like `maximum_cover`, the returned items are in order of non-increasing score.
+pub fn maximum_cover_until<I, T>(
+    items_iter: I,
+    limit: usize,
+    label: &str,
+    deadline: Instant,
+) -> Vec<T>
+where
+    I: IntoIterator<Item = T>,
+    T: MaxCover,
+{
+    let items: Vec<(u64, T)> = items_iter
+        .into_iter()
+        .map(|item| (item.score() as u64, item))
+        .filter(|(score, _)| *score != 0)
+        .sorted_by_key(|(score, _)| Reverse(*score))
+        .collect();
+    let greedy = maximum_cover(items.iter().map(|(_, item)| item.clone()), limit, label);
+
+    let score_sums = std::iter::once(0)
+        .chain(items.iter().scan(0, |sum, (score, _)| {
+            *sum += score;
+            Some(*sum)
+        }))
+        .collect();
+    let mut search = CoverSearch {
+        items: &items,
+        score_sums,
+        best_score: total_score(&greedy),
+        best: greedy,
+        improved: false,
+        timed_out: false,
+        deadline,
+    };
+    search.extend(&mut vec![], 0, 0, limit);
+
+    let outcome = if search.timed_out {
+        "timed_out"
+    } else {
+        "complete"
+    };
+    metrics::inc_counter_vec(&metrics::MAX_COVER_SEARCH_TOTAL, &[label, outcome]);
+    if search.improved {
+        metrics::inc_counter_vec(&metrics::MAX_COVER_SEARCH_IMPROVED_TOTAL, &[label]);
+    }
+
+    search.best
+}
+
+fn total_score<T: MaxCover>(items: &[T]) -> u64 {
+    items.iter().map(|item| item.score() as u64).sum()
+}
+
+/// State of a branch-and-bound search for a maximum cover.
+struct CoverSearch<'a, T> {
+    /// The items with their initial scores, in order of decreasing score.
+    items: &'a [(u64, T)],
+    /// The sum of the scores of the first `i` items, at index `i`.
+    score_sums: Vec<u64>,
+    best: Vec<T>,
+    best_score: u64,
+    /// Whether a solution better than the initial one has been found.
+    improved: bool,
+    timed_out: bool,
+    deadline: Instant,
+}
+
+impl<'a, T: MaxCover> CoverSearch<'a, T> {
+    /// Try every extension of `chosen` by up to `remaining` of the items from `start` onwards.
+    ///
+    /// The items in `chosen` have their covering sets updated for the items chosen before them.
+    /// Each item is the first to be added in its own branch, and only the items after it are
+    /// considered further down that branch, so that each selection is visited once.
+    fn extend(&mut self, chosen: &mut Vec<T>, score: u64, start: usize, remaining: usize) {
+        for i in start..self.items.len() {
+            // Items are sorted, so once a bound fails all later bounds fail too.
+            let end = std::cmp::min(i + remaining, self.items.len());
+            let bound = score + self.score_sums[end] - self.score_sums[i];
+            if bound <= self.best_score {
+                return;
+            }
+            if self.timed_out || Instant::now() >= self.deadline {
+                self.timed_out = true;
+                return;
+            }
+
+            // Only the item being added is updated for the items already chosen, rather than every
+            // remaining item.
+            let mut candidate = self.items[i].1.clone();
+            for previous in chosen.iter() {
+                candidate.update_covering_set(previous.intermediate(), previous.covering_set());
+            }
+            let candidate_score = candidate.score() as u64;
+            if candidate_score == 0 {
+                continue;
+            }
+
+            let new_score = score + candidate_score;
+            chosen.push(candidate);
+            if new_score > self.best_score {
+                self.best = chosen.clone();
+                self.best.sort_by_key(|item| Reverse(item.score()));
+                self.best_score = new_score;
+                self.improved = true;
+            }
+            if remaining > 1 {
+                self.extend(chosen, new_score, i + 1, remaining - 1);
+            }
+            chosen.pop();
+        }
+    }
+}
+
 /// Perform a greedy merge of two max cover solutions, preferring higher-score values.
 pub fn merge_solutions<I1, I2, T>(cover1: I1, cover2: I2, limit: usize) -> Vec<T::Object>
 where
@@ -207,6 +331,78 @@ mod test {
         assert_eq!(quality(&cover), 11);
     }
 
+    fn far_deadline() -> Instant {
+        Instant::now() + std::time::Duration::from_secs(60)
+    }
+
+    #[test]
+    fn optimal_zero_limit() {
+        let cover = maximum_cover_until(example_system(), 0, "test", far_deadline());
+        assert!(cover.is_empty());
+    }
+
+    #[test]
+    fn optimal_exclude_zero_score() {
+        let sets = example_system();
+        for k in 2..10 {
+            let cover = maximum_cover_until(sets.clone(), k, "test", far_deadline());
+            assert_eq!(cover.len(), 2);
+            assert_eq!(quality(&cover), 5);
+        }
+    }
+
+    // The same system as `suboptimal`, for which the search finds the optimal solution.
+    #[test]
+    fn optimal_beats_greedy() {
+        let sets = vec![
+            HashSet::from_iter(vec![0, 1, 8, 11, 14]),
+            HashSet::from_iter(vec![2, 3, 7, 9, 10]),
+            HashSet::from_iter(vec![4, 5, 6, 12, 13]),
+            HashSet::from_iter(vec![9, 10]),
+            HashSet::from_iter(vec![5, 6, 7, 8]),
+            HashSet::from_iter(vec![0, 1, 2, 3, 4]),
+        ];
+        let cover = maximum_cover_until(sets.clone(), 3, "test", far_deadline());
+        assert_eq!(quality(&cover), 15);
+        assert_eq!(cover.len(), 3);
+        for set in &sets[0..3] {
+            assert!(cover.contains(set));
+        }
+    }
+
+    // With no time to search, the greedy solution is returned.
+    #[test]
+    fn optimal_expired_deadline_falls_back_to_greedy() {
+        let sets = vec![
+            HashSet::from_iter(vec![0, 1, 8, 11, 14]),
+            HashSet::from_iter(vec![2, 3, 7, 9, 10]),
+            HashSet::from_iter(vec![4, 5, 6, 12, 13]),
+            HashSet::from_iter(vec![9, 10]),
+            HashSet::from_iter(vec![5, 6, 7, 8]),
+            HashSet::from_iter(vec![0, 1, 2, 3, 4]),
+        ];
+        let cover = maximum_cover_until(sets.clone(), 3, "test", Instant::now());
+        assert_eq!(cover, maximum_cover(sets, 3, "test"));
+        assert_eq!(quality(&cover), 11);
+    }
+
+    #[test]
+    fn optimal_intersecting() {
+        let sets = vec![
+            HashSet::from_iter(vec![1, 2, 3, 4, 5, 6, 7, 8]),
+            HashSet::from_iter(vec![1, 2, 3, 9, 10, 11]),
+            HashSet::from_iter(vec![4, 5, 6, 12, 13, 14]),
+            HashSet::from_iter(vec![7, 8, 15, 16, 17, 18]),
+            HashSet::from_iter(vec![1, 2, 9, 10]),
+            HashSet::from_iter(vec![1, 5, 6, 8]),
+            HashSet::from_iter(vec![1, 7, 11, 19]),
+        ];
+        let cover = maximum_cover_until(sets, 5, "test", far_deadline());
+        assert_eq!(quality(&cover), 19);
+        // Scores are non-increasing, as required by `merge_solutions`.
+        assert!(cover.windows(2).all(|pair| pair[0].len() >= pair[1].len()));
+    }
+
     #[test]
     fn intersecting_ok() {
         let sets = vec![
@@ -28,4 +28,15 @@ lazy_static! {
         "Number of non-trivial items considered in a max coverage optimisation",
         &["label"]
     );
+    pub static ref MAX_COVER_SEARCH_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "op_pool_max_cover_search_total",
+        "Count of searches for an optimal max cover, by whether they completed before the deadline",
+        &["label", "outcome"]
+    );
+    pub static ref MAX_COVER_SEARCH_IMPROVED_TOTAL: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "op_pool_max_cover_search_improved_total",
+            "Count of searches for an optimal max cover which improved on the greedy solution",
+            &["label"]
+        );
 }
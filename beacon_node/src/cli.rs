@@ -51,6 +51,16 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("beacon-node-backend")
+                .long("beacon-node-backend")
+                .value_name("DATABASE")
+                .value_parser(store::DatabaseBackend::VARIANTS.to_vec())
+                .help("Set the database backend to be used by the beacon node. A database can \
+                       only be opened with the backend it was created with.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /*
          * Network parameters.
          */
@@ -353,6 +363,38 @@ pub fn cli_app() -> Command {
                 .display_order(0)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("peer-policy-file")
+                .long("peer-policy-file")
+                .value_name("FILE")
+                .help("Path to a YAML file containing the peer policy, which denies peers by peer id, \
+                       IP range or client agent string and defines groups of peers with optional \
+                       connection limits and static peers that are always redialed. The file is \
+                       rewritten when the policy is updated through the HTTP API.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("network-recording-file")
+                .long("network-recording-file")
+                .value_name("FILE")
+                .help("Record all gossip messages and RPC requests and responses received from \
+                       peers to this file, so that they can be replayed against a local chain to \
+                       reproduce sync and gossip issues. Any existing file is overwritten. \
+                       Recordings grow quickly and should only be enabled while debugging.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("network-recording-max-size")
+                .long("network-recording-max-size")
+                .value_name("MEGABYTES")
+                .help("Stop recording network traffic once the recording reaches this size. \
+                       [default: 4096]")
+                .requires("network-recording-file")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("genesis-backfill")
                 .long("genesis-backfill")
@@ -577,6 +619,18 @@ pub fn cli_app() -> Command {
                 .display_order(0)
                 .default_value_if("enable_http", ArgPredicate::IsPresent, "true")
         )
+        .arg(
+            Arg::new("http-api-tokens-file")
+                .long("http-api-tokens-file")
+                .requires("enable_http")
+                .value_name("FILE")
+                .help("Path to a JSON file of named bearer tokens. When provided, every HTTP API \
+                       request must present one of these tokens in its Authorization header. \
+                       Each token is limited to its listed scopes (read_only, validator or admin) \
+                       and an optional number of requests per minute.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /* Prometheus metrics HTTP server related arguments */
         .arg(
             Arg::new("metrics")
@@ -733,6 +787,19 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("hierarchy-exponents")
+                .long("hierarchy-exponents")
+                .value_name("EXPONENTS")
+                .help("Store diffs of finalized states between restore points in the freezer DB. \
+                       Takes a comma-separated list of strictly increasing exponents, each \
+                       layer storing a diff every 2^exponent slots, e.g. \"5,9,11\". Each \
+                       spacing must be a multiple of the slots per epoch and divide the slots \
+                       per restore point. Cannot be changed after initialization without \
+                       running `lighthouse db convert-freezer`.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("epochs-per-migration")
                 .long("epochs-per-migration")
@@ -781,7 +848,10 @@ pub fn cli_app() -> Command {
                 .alias("execution-endpoints")
                 .help("Server endpoint for an execution layer JWT-authenticated HTTP \
                        JSON-RPC connection. Uses the same endpoint to populate the \
-                       deposit cache.")
+                       deposit cache. A comma-separated list of endpoints may be provided, \
+                       in which case every endpoint is kept in sync and payloads are built \
+                       by the first healthy one. The first endpoint is used for the deposit \
+                       cache.")
                 .action(ArgAction::Set)
                 .display_order(0)
         )
@@ -791,7 +861,10 @@ pub fn cli_app() -> Command {
                 .value_name("EXECUTION-JWT")
                 .alias("jwt-secrets")
                 .help("File path which contains the hex-encoded JWT secret for the \
-                       execution endpoint provided in the --execution-endpoint flag.")
+                       execution endpoint provided in the --execution-endpoint flag. When \
+                       multiple endpoints are provided, either a single path which is shared \
+                       by all endpoints or a comma-separated list with one path per endpoint \
+                       may be given.")
                 .requires("execution-endpoint")
                 .action(ArgAction::Set)
                 .display_order(0)
@@ -832,6 +905,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("execution-invalid-quorum")
+                .long("execution-invalid-quorum")
+                .value_name("NUM")
+                .help("The number of execution endpoints which must report a payload as \
+                       invalid before it is treated as invalid. Defaults to a majority of the \
+                       endpoints provided to --execution-endpoint.")
+                .requires("execution-endpoint")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("suggested-fee-recipient")
                 .long("suggested-fee-recipient")
@@ -848,7 +932,10 @@ pub fn cli_app() -> Command {
                 .long("builder")
                 .alias("payload-builder")
                 .alias("payload-builders")
-                .help("The URL of a service compatible with the MEV-boost API.")
+                .help("The URL of a service compatible with the MEV-boost API. A \
+                       comma-separated list of relay URLs may be provided, in which case \
+                       headers are requested from all of them in parallel and the most \
+                       valuable valid bid is used.")
                 .requires("execution-endpoint")
                 .action(ArgAction::Set)
                 .display_order(0)
@@ -888,6 +975,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("builder-min-bid-gwei")
+                .long("builder-min-bid-gwei")
+                .value_name("GWEI")
+                .help("The minimum value (in Gwei) of a builder bid. Bids below this value \
+                       are ignored and the locally built payload is proposed instead. May be \
+                       overridden per validator from the validator client.")
+                .requires("builder")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /* Deneb settings */
         .arg(
             Arg::new("trusted-setup-file-override")
@@ -1348,6 +1446,52 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("op-pool-packing-time-budget")
+                .long("op-pool-packing-time-budget")
+                .value_name("MILLISECONDS")
+                .help("Search for the attestations and slashings which maximise the proposer \
+                       reward when producing a block, spending up to this many milliseconds in \
+                       total on each block. If the search doesn't finish in time the best selection found so \
+                       far is used, which is never worse than the default greedy selection.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("op-pool-persistence-epochs")
+                .long("op-pool-persistence-epochs")
+                .value_name("EPOCHS")
+                .help("The number of epochs between each save of the operation pools to disk, so \
+                       that they survive an unclean shutdown. The pools are always saved on a \
+                       clean shutdown. Set to 0 to only save them on shutdown.")
+                .default_value("4")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("operation-rebroadcast-epochs")
+                .long("operation-rebroadcast-epochs")
+                .value_name("EPOCHS")
+                .help("The number of epochs that a valid slashing, voluntary exit or BLS to \
+                       execution change must wait in the operation pool without being included \
+                       before it is re-published on gossip. Operations are re-published again \
+                       after each further interval of this many epochs. Must be greater than 2 so \
+                       that re-published messages aren't dropped as duplicates. Set to 0 to never \
+                       re-publish operations.")
+                .default_value("4")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("operation-rebroadcast-limit")
+                .long("operation-rebroadcast-limit")
+                .value_name("COUNT")
+                .help("The maximum number of operations to re-publish on gossip each epoch. \
+                       Operations which have waited the longest are re-published first.")
+                .default_value("64")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("paranoid-block-proposal")
                 .long("paranoid-block-proposal")
@@ -1551,6 +1695,16 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("beacon-processor-scheduling-policy")
+                .long("beacon-processor-scheduling-policy")
+                .value_name("FILE")
+                .help("Path to a JSON file which overrides the capacity, priority, order (fifo or \
+                       lifo) or maximum concurrent workers of individual beacon processor queues.")
+                .hide(true)
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("disable-duplicate-warn-logs")
                 .long("disable-duplicate-warn-logs")
@@ -1,7 +1,7 @@
 use beacon_chain::chain_config::{
-    DisallowedReOrgOffsets, ReOrgThreshold, DEFAULT_PREPARE_PAYLOAD_LOOKAHEAD_FACTOR,
-    DEFAULT_RE_ORG_HEAD_THRESHOLD, DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION,
-    DEFAULT_RE_ORG_PARENT_THRESHOLD,
+    DisallowedReOrgOffsets, PackingStrategy, ReOrgThreshold,
+    DEFAULT_PREPARE_PAYLOAD_LOOKAHEAD_FACTOR, DEFAULT_RE_ORG_HEAD_THRESHOLD,
+    DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION, DEFAULT_RE_ORG_PARENT_THRESHOLD,
 };
 use beacon_chain::graffiti_calculator::GraffitiOrigin;
 use beacon_chain::TrustedSetup;
@@ -13,6 +13,7 @@ use directory::{DEFAULT_BEACON_NODE_DIR, DEFAULT_NETWORK_DIR, DEFAULT_ROOT_DIR};
 use environment::RuntimeContext;
 use execution_layer::DEFAULT_JWT_FILE;
 use genesis::Eth1Endpoint;
+use http_api::auth::ApiAuthConfig;
 use http_api::TlsConfig;
 use lighthouse_network::ListenAddress;
 use lighthouse_network::{multiaddr::Protocol, Enr, Multiaddr, NetworkConfig, PeerIdSerialized};
@@ -30,6 +31,9 @@ use std::time::Duration;
 use types::graffiti::GraffitiString;
 use types::{Checkpoint, Epoch, EthSpec, Hash256, PublicKeyBytes};
 
+/// The directory within the data directory containing the slasher database, by default.
+pub const DEFAULT_SLASHER_DIR: &str = "slasher_db";
+
 /// Gets the fully-initialized global client.
 ///
 /// The top-level `clap` arguments should be provided as `cli_args`.
@@ -171,6 +175,17 @@ pub fn get_config<E: EthSpec>(
 
         client_config.http_api.enable_light_client_server =
             cli_args.get_flag("light-client-server");
+
+        if let Some(tokens_path) = cli_args.get_one::<String>("http-api-tokens-file") {
+            let file = std::fs::File::open(tokens_path)
+                .map_err(|e| format!("Failed to open HTTP API tokens file: {}", e))?;
+            let auth_config: ApiAuthConfig = serde_json::from_reader(file)
+                .map_err(|e| format!("Unable to read HTTP API tokens file: {}", e))?;
+            auth_config
+                .validate()
+                .map_err(|e| format!("Invalid HTTP API tokens file: {}", e))?;
+            client_config.http_api.auth = Some(auth_config);
+        }
     }
 
     if cli_args.get_flag("light-client-server") {
@@ -287,18 +302,38 @@ pub fn get_config<E: EthSpec>(
         // stakers. The merge is already complicated enough.
         client_config.sync_eth1_chain = true;
 
-        // Parse a single execution endpoint, logging warnings if multiple endpoints are supplied.
-        let execution_endpoint =
-            parse_only_one_value(endpoints, SensitiveUrl::parse, "--execution-endpoint", log)?;
+        // The first execution endpoint is the primary, any others are redundant engines which
+        // are kept in sync with it.
+        let mut execution_endpoints =
+            parse_values(endpoints, SensitiveUrl::parse, "--execution-endpoint")?.into_iter();
+        let execution_endpoint = execution_endpoints
+            .next()
+            .ok_or("Must provide at least one value to --execution-endpoint")?;
+        el_config.redundant_execution_endpoints = execution_endpoints.collect();
 
         // JWTs are required if `--execution-endpoint` is supplied. They can be either passed via
         // file_path or directly as string.
 
         let secret_file: PathBuf;
-        // Parse a single JWT secret from a given file_path, logging warnings if multiple are supplied.
+        // Parse the JWT secrets from the given file paths. Either a single secret is shared by all
+        // execution endpoints, or one secret is provided for each endpoint.
         if let Some(secret_files) = cli_args.get_one::<String>("execution-jwt") {
-            secret_file =
-                parse_only_one_value(secret_files, PathBuf::from_str, "--execution-jwt", log)?;
+            let mut secret_files =
+                parse_values(secret_files, PathBuf::from_str, "--execution-jwt")?.into_iter();
+            secret_file = secret_files
+                .next()
+                .ok_or("Must provide at least one value to --execution-jwt")?;
+            el_config.redundant_secret_files = secret_files.collect();
+            if !el_config.redundant_secret_files.is_empty()
+                && el_config.redundant_secret_files.len()
+                    != el_config.redundant_execution_endpoints.len()
+            {
+                return Err(
+                    "--execution-jwt must contain either a single value or one value for each \
+                     --execution-endpoint"
+                        .to_string(),
+                );
+            }
 
         // Check if the JWT secret key is passed directly via cli flag and persist it to the default
         // file location.
@@ -323,9 +358,7 @@ pub fn get_config<E: EthSpec>(
 
         // Parse and set the payload builder, if any.
         if let Some(endpoint) = cli_args.get_one::<String>("builder") {
-            let payload_builder =
-                parse_only_one_value(endpoint, SensitiveUrl::parse, "--builder", log)?;
-            el_config.builder_url = Some(payload_builder);
+            el_config.builder_urls = parse_values(endpoint, SensitiveUrl::parse, "--builder")?;
 
             el_config.builder_user_agent =
                 clap_utils::parse_optional(cli_args, "builder-user-agent")?;
@@ -333,6 +366,9 @@ pub fn get_config<E: EthSpec>(
             el_config.builder_header_timeout =
                 clap_utils::parse_optional(cli_args, "builder-header-timeout")?
                     .map(Duration::from_millis);
+
+            el_config.builder_min_bid_gwei =
+                clap_utils::parse_optional(cli_args, "builder-min-bid-gwei")?;
         }
 
         if cli_args.get_flag("always-prefer-builder-payload") {
@@ -350,6 +386,8 @@ pub fn get_config<E: EthSpec>(
             clap_utils::parse_optional(cli_args, "suggested-fee-recipient")?;
         el_config.jwt_id = clap_utils::parse_optional(cli_args, "execution-jwt-id")?;
         el_config.jwt_version = clap_utils::parse_optional(cli_args, "execution-jwt-version")?;
+        el_config.invalid_payload_quorum =
+            clap_utils::parse_optional(cli_args, "execution-invalid-quorum")?;
         el_config
             .default_datadir
             .clone_from(client_config.data_dir());
@@ -395,6 +433,10 @@ pub fn get_config<E: EthSpec>(
         client_config.blobs_db_path = Some(PathBuf::from(blobs_db_dir));
     }
 
+    if let Some(backend) = clap_utils::parse_optional(cli_args, "beacon-node-backend")? {
+        client_config.store.backend = backend;
+    }
+
     let (sprp, sprp_explicit) = get_slots_per_restore_point::<E>(clap_utils::parse_optional(
         cli_args,
         "slots-per-restore-point",
@@ -402,6 +444,10 @@ pub fn get_config<E: EthSpec>(
     client_config.store.slots_per_restore_point = sprp;
     client_config.store.slots_per_restore_point_set_explicitly = sprp_explicit;
 
+    if let Some(hierarchy_config) = clap_utils::parse_optional(cli_args, "hierarchy-exponents")? {
+        client_config.store.hierarchy_config = Some(hierarchy_config);
+    }
+
     if let Some(block_cache_size) = cli_args.get_one::<String>("block-cache-size") {
         client_config.store.block_cache_size = block_cache_size
             .parse()
@@ -647,7 +693,7 @@ pub fn get_config<E: EthSpec>(
         let slasher_dir = if let Some(slasher_dir) = cli_args.get_one::<String>("slasher-dir") {
             PathBuf::from(slasher_dir)
         } else {
-            client_config.data_dir().join("slasher_db")
+            client_config.data_dir().join(DEFAULT_SLASHER_DIR)
         };
 
         let mut slasher_config = slasher::Config::new(slasher_dir);
@@ -822,6 +868,18 @@ pub fn get_config<E: EthSpec>(
 
     client_config.chain.paranoid_block_proposal = cli_args.get_flag("paranoid-block-proposal");
 
+    let op_pool_persistence_epochs: u64 =
+        clap_utils::parse_required(cli_args, "op-pool-persistence-epochs")?;
+    client_config.chain.op_pool_persistence_epochs =
+        Some(op_pool_persistence_epochs).filter(|epochs| *epochs != 0);
+
+    if let Some(time_budget) = clap_utils::parse_optional(cli_args, "op-pool-packing-time-budget")?
+    {
+        client_config.chain.op_pool_packing_strategy = PackingStrategy::RewardOptimised {
+            time_budget: Duration::from_millis(time_budget),
+        };
+    }
+
     /*
      * Builder fallback configs.
      */
@@ -889,6 +947,18 @@ pub fn get_config<E: EthSpec>(
         .max_gossip_aggregate_batch_size =
         clap_utils::parse_required(cli_args, "beacon-processor-aggregate-batch-size")?;
 
+    if let Some(policy_path) = cli_args.get_one::<String>("beacon-processor-scheduling-policy") {
+        let file = std::fs::File::open(policy_path)
+            .map_err(|e| format!("Failed to open scheduling policy file: {}", e))?;
+        client_config.beacon_processor.scheduling_policy = serde_json::from_reader(file)
+            .map_err(|e| format!("Unable to read scheduling policy file: {}", e))?;
+        client_config
+            .beacon_processor
+            .scheduling_policy
+            .validate()
+            .map_err(|e| format!("Invalid scheduling policy: {}", e))?;
+    }
+
     Ok(client_config)
 }
 
@@ -1212,6 +1282,16 @@ pub fn set_network_config(
         }
     }
 
+    config.peer_policy_file = clap_utils::parse_optional(cli_args, "peer-policy-file")?;
+
+    config.network_recording_file = clap_utils::parse_optional(cli_args, "network-recording-file")?;
+
+    if let Some(max_size_mb) =
+        clap_utils::parse_optional::<u64>(cli_args, "network-recording-max-size")?
+    {
+        config.network_recording_max_size = max_size_mb * 1024 * 1024;
+    }
+
     if let Some(enr_udp_port_str) = cli_args.get_one::<String>("enr-udp-port") {
         config.enr_udp4_port = Some(
             enr_udp_port_str
@@ -1413,6 +1493,19 @@ pub fn set_network_config(
     // Light client server config.
     config.enable_light_client_server = parse_flag(cli_args, "light-client-server");
 
+    let operation_rebroadcast_epochs: u64 =
+        clap_utils::parse_required(cli_args, "operation-rebroadcast-epochs")?;
+    if (1..=2).contains(&operation_rebroadcast_epochs) {
+        return Err(format!(
+            "--operation-rebroadcast-epochs must be 0 or greater than 2, got {}",
+            operation_rebroadcast_epochs
+        ));
+    }
+    config.operation_rebroadcast_epochs =
+        Some(operation_rebroadcast_epochs).filter(|epochs| *epochs != 0);
+    config.operation_rebroadcast_limit =
+        clap_utils::parse_required(cli_args, "operation-rebroadcast-limit")?;
+
     // The self limiter is enabled by default. If the `self-limiter-protocols` flag is not provided,
     // the default params will be used.
     config.outbound_rate_limiter_config = if parse_flag(cli_args, "disable-self-limiter") {
@@ -1489,36 +1582,14 @@ pub fn get_slots_per_restore_point<E: EthSpec>(
 }
 
 /// Parses the `cli_value` as a comma-separated string of values to be parsed with `parser`.
-///
-/// If there is more than one value, log a warning. If there are no values, return an error.
-pub fn parse_only_one_value<F, T, U>(
-    cli_value: &str,
-    parser: F,
-    flag_name: &str,
-    log: &Logger,
-) -> Result<T, String>
+pub fn parse_values<F, T, U>(cli_value: &str, parser: F, flag_name: &str) -> Result<Vec<T>, String>
 where
     F: Fn(&str) -> Result<T, U>,
     U: Debug,
 {
-    let values = cli_value
+    cli_value
         .split(',')
         .map(parser)
         .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("{} contains an invalid value {:?}", flag_name, e))?;
-
-    if values.len() > 1 {
-        warn!(
-            log,
-            "Multiple values provided";
-            "info" => "multiple values are deprecated, only the first value will be used",
-            "count" => values.len(),
-            "flag" => flag_name
-        );
-    }
-
-    values
-        .into_iter()
-        .next()
-        .ok_or(format!("Must provide at least one value to {}", flag_name))
+        .map_err(|e| format!("{} contains an invalid value {:?}", flag_name, e))
 }
@@ -0,0 +1,199 @@
+//! Runtime selection of the key-value store used by the on-disk `HotColdDB`.
+use crate::config::DatabaseBackend;
+use crate::leveldb_store::LevelDB;
+#[cfg(feature = "redb")]
+use crate::redb_store::Redb;
+use crate::{
+    ColumnIter, ColumnKeyIter, DBColumn, Error, ItemStore, Key, KeyValueStore, KeyValueStoreOp,
+    RawEntryIter, RawKeyIter,
+};
+use parking_lot::MutexGuard;
+use std::path::Path;
+use types::EthSpec;
+
+/// File created by LevelDB in every database directory.
+const LEVELDB_CURRENT_FILENAME: &str = "CURRENT";
+
+/// Name of the file used by redb within each database directory.
+pub const REDB_DATA_FILENAME: &str = "database.redb";
+
+/// A key-value store whose implementation is chosen by `StoreConfig::backend`.
+pub enum BeaconNodeBackend<E: EthSpec> {
+    LevelDb(LevelDB<E>),
+    #[cfg(feature = "redb")]
+    Redb(Redb<E>),
+}
+
+impl<E: EthSpec> BeaconNodeBackend<E> {
+    /// Open a database at `path` using `backend`, creating a new database if one does not already
+    /// exist.
+    ///
+    /// Returns an error if the directory already contains a database written by another backend.
+    pub fn open(backend: DatabaseBackend, path: &Path) -> Result<Self, Error> {
+        if let Some(on_disk) = detect_backend(path)? {
+            if on_disk != backend {
+                return Err(Error::ConfigError(
+                    crate::config::StoreConfigError::MismatchedDatabaseBackend {
+                        config: backend,
+                        on_disk,
+                    },
+                ));
+            }
+        }
+
+        match backend {
+            DatabaseBackend::LevelDb => LevelDB::open(path).map(Self::LevelDb),
+            #[cfg(feature = "redb")]
+            DatabaseBackend::Redb => Redb::open(path).map(Self::Redb),
+        }
+    }
+
+    /// The backend this database was opened with.
+    pub fn backend(&self) -> DatabaseBackend {
+        match self {
+            Self::LevelDb(_) => DatabaseBackend::LevelDb,
+            #[cfg(feature = "redb")]
+            Self::Redb(_) => DatabaseBackend::Redb,
+        }
+    }
+}
+
+/// Guess which backend created the database in `path` from the files present on disk.
+///
+/// Returns `None` if the directory is empty or doesn't exist, and an error if the database was
+/// created by a backend that this build does not support.
+pub fn detect_backend(path: &Path) -> Result<Option<DatabaseBackend>, Error> {
+    if path.join(REDB_DATA_FILENAME).exists() {
+        #[cfg(feature = "redb")]
+        return Ok(Some(DatabaseBackend::Redb));
+        #[cfg(not(feature = "redb"))]
+        return Err(unsupported_backend_error("redb"));
+    }
+    if path.join(LEVELDB_CURRENT_FILENAME).exists() {
+        return Ok(Some(DatabaseBackend::LevelDb));
+    }
+    Ok(None)
+}
+
+/// The error returned when the database was created by a backend this build was compiled without.
+pub fn unsupported_backend_error(name: &str) -> Error {
+    Error::UnsupportedDatabaseBackend(format!(
+        "database was created with the {name} backend, which this build does not support"
+    ))
+}
+
+impl<E: EthSpec> KeyValueStore<E> for BeaconNodeBackend<E> {
+    fn get_bytes(&self, column: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        match self {
+            Self::LevelDb(db) => db.get_bytes(column, key),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.get_bytes(column, key),
+        }
+    }
+
+    fn put_bytes(&self, column: &str, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => db.put_bytes(column, key, value),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.put_bytes(column, key, value),
+        }
+    }
+
+    fn put_bytes_sync(&self, column: &str, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => db.put_bytes_sync(column, key, value),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.put_bytes_sync(column, key, value),
+        }
+    }
+
+    fn sync(&self) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => KeyValueStore::<E>::sync(db),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => KeyValueStore::<E>::sync(db),
+        }
+    }
+
+    fn key_exists(&self, column: &str, key: &[u8]) -> Result<bool, Error> {
+        match self {
+            Self::LevelDb(db) => db.key_exists(column, key),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.key_exists(column, key),
+        }
+    }
+
+    fn key_delete(&self, column: &str, key: &[u8]) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => db.key_delete(column, key),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.key_delete(column, key),
+        }
+    }
+
+    fn do_atomically(&self, batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => db.do_atomically(batch),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => db.do_atomically(batch),
+        }
+    }
+
+    fn begin_rw_transaction(&self) -> MutexGuard<()> {
+        match self {
+            Self::LevelDb(db) => KeyValueStore::<E>::begin_rw_transaction(db),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => KeyValueStore::<E>::begin_rw_transaction(db),
+        }
+    }
+
+    fn compact_column(&self, column: DBColumn) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => KeyValueStore::<E>::compact_column(db, column),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => KeyValueStore::<E>::compact_column(db, column),
+        }
+    }
+
+    fn compact(&self) -> Result<(), Error> {
+        match self {
+            Self::LevelDb(db) => KeyValueStore::<E>::compact(db),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => KeyValueStore::<E>::compact(db),
+        }
+    }
+
+    fn iter_column_from<K: Key>(&self, column: DBColumn, from: &[u8]) -> ColumnIter<K> {
+        match self {
+            Self::LevelDb(db) => KeyValueStore::<E>::iter_column_from(db, column, from),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => KeyValueStore::<E>::iter_column_from(db, column, from),
+        }
+    }
+
+    fn iter_raw_entries(&self, column: DBColumn, prefix: &[u8]) -> RawEntryIter {
+        match self {
+            Self::LevelDb(db) => KeyValueStore::<E>::iter_raw_entries(db, column, prefix),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => KeyValueStore::<E>::iter_raw_entries(db, column, prefix),
+        }
+    }
+
+    fn iter_raw_keys(&self, column: DBColumn, prefix: &[u8]) -> RawKeyIter {
+        match self {
+            Self::LevelDb(db) => KeyValueStore::<E>::iter_raw_keys(db, column, prefix),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => KeyValueStore::<E>::iter_raw_keys(db, column, prefix),
+        }
+    }
+
+    fn iter_column_keys<K: Key>(&self, column: DBColumn) -> ColumnKeyIter<K> {
+        match self {
+            Self::LevelDb(db) => KeyValueStore::<E>::iter_column_keys(db, column),
+            #[cfg(feature = "redb")]
+            Self::Redb(db) => KeyValueStore::<E>::iter_column_keys(db, column),
+        }
+    }
+}
+
+impl<E: EthSpec> ItemStore<E> for BeaconNodeBackend<E> {}
@@ -1,8 +1,10 @@
+use crate::hdiff::{self, HierarchyConfig, HierarchyModuli};
 use crate::{DBColumn, Error, StoreItem};
 use serde::{Deserialize, Serialize};
 use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
 use std::num::NonZeroUsize;
+use strum::{Display, EnumString, EnumVariantNames};
 use types::non_zero_usize::new_non_zero_usize;
 use types::{EthSpec, MinimalEthSpec};
 
@@ -13,6 +15,7 @@ pub const DEFAULT_STATE_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(128);
 pub const DEFAULT_HISTORIC_STATE_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(1);
 pub const DEFAULT_EPOCHS_PER_BLOB_PRUNE: u64 = 1;
 pub const DEFAULT_BLOB_PUNE_MARGIN_EPOCHS: u64 = 0;
+pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::LevelDb;
 
 /// Database configuration parameters.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -40,17 +43,56 @@ pub struct StoreConfig {
     /// The margin for blob pruning in epochs. The oldest blobs are pruned up until
     /// data_availability_boundary - blob_prune_margin_epochs. Default: 0.
     pub blob_prune_margin_epochs: u64,
+    /// Key-value store used for the hot, cold and blobs databases.
+    pub backend: DatabaseBackend,
+    /// Layers of state diffs to store between restore points in the freezer database.
+    ///
+    /// If `None`, only restore points are stored.
+    pub hierarchy_config: Option<HierarchyConfig>,
+}
+
+/// Key-value store backing the `HotColdDB`.
+///
+/// The backend is recorded in the database metadata when it is first created, and a database
+/// may only be re-opened with the same backend.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumString, EnumVariantNames,
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum DatabaseBackend {
+    LevelDb,
+    #[cfg(feature = "redb")]
+    Redb,
 }
 
 /// Variant of `StoreConfig` that gets written to disk. Contains immutable configuration params.
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub struct OnDiskStoreConfig {
     pub slots_per_restore_point: u64,
+    /// Exponents of the state diff layers in the freezer, empty if diffs are not stored.
+    pub hierarchy_exponents: Vec<u8>,
+}
+
+/// The on-disk store config prior to schema v22, without hierarchy exponents.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct OnDiskStoreConfigV1 {
+    pub slots_per_restore_point: u64,
 }
 
 #[derive(Debug, Clone)]
 pub enum StoreConfigError {
-    MismatchedSlotsPerRestorePoint { config: u64, on_disk: u64 },
+    MismatchedSlotsPerRestorePoint {
+        config: u64,
+        on_disk: u64,
+    },
+    MismatchedDatabaseBackend {
+        config: DatabaseBackend,
+        on_disk: DatabaseBackend,
+    },
+    MismatchedHierarchyExponents {
+        config: Vec<u8>,
+        on_disk: Vec<u8>,
+    },
 }
 
 impl Default for StoreConfig {
@@ -68,6 +110,8 @@ impl Default for StoreConfig {
             prune_blobs: true,
             epochs_per_blob_prune: DEFAULT_EPOCHS_PER_BLOB_PRUNE,
             blob_prune_margin_epochs: DEFAULT_BLOB_PUNE_MARGIN_EPOCHS,
+            backend: DEFAULT_BACKEND,
+            hierarchy_config: None,
         }
     }
 }
@@ -76,9 +120,28 @@ impl StoreConfig {
     pub fn as_disk_config(&self) -> OnDiskStoreConfig {
         OnDiskStoreConfig {
             slots_per_restore_point: self.slots_per_restore_point,
+            hierarchy_exponents: self.hierarchy_exponents(),
         }
     }
 
+    /// The exponents of the state diff layers, empty if diffs are disabled.
+    pub fn hierarchy_exponents(&self) -> Vec<u8> {
+        self.hierarchy_config
+            .as_ref()
+            .map_or(vec![], |config| config.exponents.clone())
+    }
+
+    /// Compute the slot spacing of each state diff layer, if diffs are enabled.
+    pub fn hierarchy_moduli(
+        &self,
+        slots_per_epoch: u64,
+    ) -> Result<Option<HierarchyModuli>, hdiff::Error> {
+        self.hierarchy_config
+            .as_ref()
+            .map(|config| config.to_moduli(self.slots_per_restore_point, slots_per_epoch))
+            .transpose()
+    }
+
     pub fn check_compatibility(
         &self,
         on_disk_config: &OnDiskStoreConfig,
@@ -89,6 +152,26 @@ impl StoreConfig {
                 on_disk: on_disk_config.slots_per_restore_point,
             });
         }
+        if self.hierarchy_exponents() != on_disk_config.hierarchy_exponents {
+            return Err(StoreConfigError::MismatchedHierarchyExponents {
+                config: self.hierarchy_exponents(),
+                on_disk: on_disk_config.hierarchy_exponents.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Check that the backend recorded in the database metadata matches the configured backend.
+    pub fn check_backend_compatibility(
+        &self,
+        on_disk_backend: DatabaseBackend,
+    ) -> Result<(), StoreConfigError> {
+        if self.backend != on_disk_backend {
+            return Err(StoreConfigError::MismatchedDatabaseBackend {
+                config: self.backend,
+                on_disk: on_disk_backend,
+            });
+        }
         Ok(())
     }
 }
@@ -106,3 +189,21 @@ impl StoreItem for OnDiskStoreConfig {
         Ok(Self::from_ssz_bytes(bytes)?)
     }
 }
+
+/// The backend is stored by name so that a binary compiled without support for some backend can
+/// still report which backend a database was created with.
+impl StoreItem for DatabaseBackend {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.to_string().into_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let name = std::str::from_utf8(bytes).map_err(|_| Error::InvalidBytes)?;
+        name.parse()
+            .map_err(|_| crate::beacon_node_backend::unsupported_backend_error(name))
+    }
+}
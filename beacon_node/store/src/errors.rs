@@ -1,5 +1,6 @@
 use crate::chunked_vector::ChunkError;
 use crate::config::StoreConfigError;
+use crate::hdiff;
 use crate::hot_cold_store::HotColdDBError;
 use ssz::DecodeError;
 use state_processing::BlockReplayError;
@@ -11,6 +12,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     SszDecodeError(DecodeError),
     VectorChunkError(ChunkError),
+    HDiffError(hdiff::Error),
     BeaconStateError(BeaconStateError),
     PartialBeaconStateError,
     HotColdDBError(HotColdDBError),
@@ -57,6 +59,9 @@ pub enum Error {
         state_root: Hash256,
         slot: Slot,
     },
+    /// The database was created with a backend that this binary was not compiled with. Contains a
+    /// message naming the backend.
+    UnsupportedDatabaseBackend(String),
 }
 
 pub trait HandleUnavailable<T> {
@@ -85,6 +90,12 @@ impl From<ChunkError> for Error {
     }
 }
 
+impl From<hdiff::Error> for Error {
+    fn from(e: hdiff::Error) -> Error {
+        Error::HDiffError(e)
+    }
+}
+
 impl From<HotColdDBError> for Error {
     fn from(e: HotColdDBError) -> Error {
         Error::HotColdDBError(e)
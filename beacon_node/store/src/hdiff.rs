@@ -0,0 +1,431 @@
+//! Hierarchical state diffs for the freezer database.
+//!
+//! States are stored in layers. The top layer consists of full snapshots, which are the existing
+//! restore points spaced `slots_per_restore_point` apart. Each lower layer stores a diff every
+//! `2^exponent` slots, relative to the most recent state of the layer above. States that do not
+//! lie on any layer are reconstructed by replaying blocks on top of the closest lower state in the
+//! bottom layer.
+//!
+//! Only the validator registry and balances are diffed, as they account for the bulk of a
+//! state's size. The remaining fields are stored in full alongside the diff.
+use crate::{DBColumn, PartialBeaconState, StoreItem};
+use serde::{Deserialize, Serialize};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use std::str::FromStr;
+use types::{BeaconState, BeaconStateError, EthSpec, List, Slot, Validator};
+
+#[derive(Debug)]
+pub enum Error {
+    /// The exponents must be non-empty and strictly increasing.
+    ExponentsNotIncreasing,
+    /// Diff layers must lie on epoch boundaries, so the hot database holds their full states.
+    ExponentBelowEpoch {
+        exponent: u8,
+        slots_per_epoch: u64,
+    },
+    /// The top diff layer must divide the restore point interval, which acts as the snapshot
+    /// layer.
+    ExponentNotDividingRestorePoint {
+        exponent: u8,
+        slots_per_restore_point: u64,
+    },
+    /// A diff referred to an index beyond the end of the base state's list.
+    InvalidIndex {
+        index: usize,
+        len: usize,
+    },
+    InvalidBalancesLength(usize),
+    Compression(snap::Error),
+    BeaconStateError(BeaconStateError),
+}
+
+impl From<BeaconStateError> for Error {
+    fn from(e: BeaconStateError) -> Self {
+        Self::BeaconStateError(e)
+    }
+}
+
+impl From<snap::Error> for Error {
+    fn from(e: snap::Error) -> Self {
+        Self::Compression(e)
+    }
+}
+
+/// User-provided configuration for the diff layers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HierarchyConfig {
+    /// Base-2 logarithms of the spacing of each diff layer, from the most to least frequent.
+    pub exponents: Vec<u8>,
+}
+
+impl FromStr for HierarchyConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let exponents = s
+            .split(',')
+            .map(|s| {
+                s.trim()
+                    .parse::<u8>()
+                    .map_err(|e| format!("invalid hierarchy exponent {s:?}: {e:?}"))
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+
+        if exponents.is_empty() || exponents.windows(2).any(|w| w[0] >= w[1]) {
+            return Err("hierarchy exponents must be strictly increasing".to_string());
+        }
+        Ok(Self { exponents })
+    }
+}
+
+impl std::fmt::Display for HierarchyConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let exponents = self
+            .exponents
+            .iter()
+            .map(u8::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        write!(f, "{exponents}")
+    }
+}
+
+impl HierarchyConfig {
+    /// Validate the config and compute the slot spacing of each layer.
+    pub fn to_moduli(
+        &self,
+        slots_per_restore_point: u64,
+        slots_per_epoch: u64,
+    ) -> Result<HierarchyModuli, Error> {
+        if self.exponents.is_empty() || self.exponents.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(Error::ExponentsNotIncreasing);
+        }
+
+        let moduli = self
+            .exponents
+            .iter()
+            .map(|&exponent| {
+                let modulus = 1u64.checked_shl(exponent.into()).unwrap_or(0);
+                if modulus == 0 || modulus % slots_per_epoch != 0 {
+                    Err(Error::ExponentBelowEpoch {
+                        exponent,
+                        slots_per_epoch,
+                    })
+                } else if modulus >= slots_per_restore_point
+                    || slots_per_restore_point % modulus != 0
+                {
+                    Err(Error::ExponentNotDividingRestorePoint {
+                        exponent,
+                        slots_per_restore_point,
+                    })
+                } else {
+                    Ok(modulus)
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(HierarchyModuli {
+            moduli,
+            snapshot_modulus: slots_per_restore_point,
+        })
+    }
+}
+
+/// How the state at a particular slot is stored in the freezer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageStrategy {
+    /// Stored in full as a restore point.
+    Snapshot,
+    /// Stored as a diff relative to the state at the given slot.
+    DiffFrom(Slot),
+    /// Not stored, reconstructed by replaying blocks from the state at the given slot.
+    ReplayFrom(Slot),
+}
+
+/// The slot spacing of each diff layer, along with the snapshot spacing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HierarchyModuli {
+    /// Spacing of each diff layer, in increasing order.
+    moduli: Vec<u64>,
+    /// Spacing of the full snapshots (restore points).
+    snapshot_modulus: u64,
+}
+
+impl HierarchyModuli {
+    /// Number of layers, including the snapshot layer.
+    pub fn num_layers(&self) -> usize {
+        self.moduli.len() + 1
+    }
+
+    /// The least frequent layer that `slot` lies on, if any.
+    ///
+    /// The snapshot layer has index `self.num_layers() - 1`.
+    pub fn layer(&self, slot: Slot) -> Option<usize> {
+        if slot % self.snapshot_modulus == 0 {
+            return Some(self.moduli.len());
+        }
+        self.moduli.iter().rposition(|&modulus| slot % modulus == 0)
+    }
+
+    pub fn storage_strategy(&self, slot: Slot) -> StorageStrategy {
+        match self.layer(slot) {
+            Some(layer) if layer == self.moduli.len() => StorageStrategy::Snapshot,
+            Some(layer) => {
+                let base_modulus = self
+                    .moduli
+                    .get(layer + 1)
+                    .copied()
+                    .unwrap_or(self.snapshot_modulus);
+                StorageStrategy::DiffFrom(slot / base_modulus * base_modulus)
+            }
+            None => {
+                let modulus = self
+                    .moduli
+                    .first()
+                    .copied()
+                    .unwrap_or(self.snapshot_modulus);
+                StorageStrategy::ReplayFrom(slot / modulus * modulus)
+            }
+        }
+    }
+
+    /// Iterate over all slots in `start..end` that are stored as diffs, in ascending order.
+    pub fn diff_slots(&self, start: Slot, end: Slot) -> impl Iterator<Item = Slot> + '_ {
+        let step = self
+            .moduli
+            .first()
+            .copied()
+            .unwrap_or(self.snapshot_modulus);
+        let first = start.as_u64().div_ceil(step) * step;
+        (first..end.as_u64())
+            .step_by(step as usize)
+            .map(Slot::new)
+            .filter(|slot| matches!(self.storage_strategy(*slot), StorageStrategy::DiffFrom(_)))
+    }
+}
+
+/// A validator that was added or modified relative to the base state.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct ValidatorDiff {
+    pub index: u64,
+    pub validator: Validator,
+}
+
+/// The difference between a base state and a target state, stored in the freezer.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct HDiff {
+    /// Slot of the state that this diff must be applied to.
+    pub base_slot: Slot,
+    /// SSZ bytes of the target `PartialBeaconState`, with empty validators and balances.
+    pub partial_state_bytes: Vec<u8>,
+    /// Validators which differ from the base state, in ascending index order.
+    pub validators_diff: Vec<ValidatorDiff>,
+    /// Snappy-compressed little-endian wrapping deltas for every balance in the target state.
+    pub balances_diff: Vec<u8>,
+}
+
+impl HDiff {
+    /// Compute the diff required to produce `target` from `base`.
+    pub fn compute<E: EthSpec>(
+        base_slot: Slot,
+        base: &BeaconState<E>,
+        target: &BeaconState<E>,
+    ) -> Result<Self, Error> {
+        let mut partial_state = PartialBeaconState::from_state_forgetful(target);
+        *partial_state.validators_mut() = List::default();
+        *partial_state.balances_mut() = List::default();
+
+        let validators_diff = target
+            .validators()
+            .iter()
+            .enumerate()
+            .filter(|(i, validator)| base.validators().get(*i) != Some(*validator))
+            .map(|(i, validator)| ValidatorDiff {
+                index: i as u64,
+                validator: validator.clone(),
+            })
+            .collect();
+
+        let mut balance_deltas = Vec::with_capacity(target.balances().len() * 8);
+        for (i, balance) in target.balances().iter().enumerate() {
+            let base_balance = base.balances().get(i).copied().unwrap_or(0);
+            balance_deltas.extend_from_slice(&balance.wrapping_sub(base_balance).to_le_bytes());
+        }
+        let balances_diff = snap::raw::Encoder::new().compress_vec(&balance_deltas)?;
+
+        Ok(Self {
+            base_slot,
+            partial_state_bytes: partial_state.as_ssz_bytes(),
+            validators_diff,
+            balances_diff,
+        })
+    }
+
+    /// Set the validators and balances of `state` by applying this diff to those of `base`.
+    ///
+    /// The remaining fields of `state` are expected to be loaded from `partial_state_bytes`.
+    pub fn apply_registry<E: EthSpec>(
+        &self,
+        base: &BeaconState<E>,
+        state: &mut BeaconState<E>,
+    ) -> Result<(), Error> {
+        let mut validators = base.validators().clone();
+        for ValidatorDiff { index, validator } in &self.validators_diff {
+            let index = *index as usize;
+            let len = validators.len();
+            if let Some(existing) = validators.get_mut(index) {
+                *existing = validator.clone();
+            } else if index == len {
+                validators
+                    .push(validator.clone())
+                    .map_err(BeaconStateError::from)?;
+            } else {
+                return Err(Error::InvalidIndex { index, len });
+            }
+        }
+
+        let balance_deltas = snap::raw::Decoder::new().decompress_vec(&self.balances_diff)?;
+        if balance_deltas.len() % 8 != 0 {
+            return Err(Error::InvalidBalancesLength(balance_deltas.len()));
+        }
+        let mut balances = base.balances().clone();
+        for (i, chunk) in balance_deltas.chunks_exact(8).enumerate() {
+            let mut delta_bytes = [0; 8];
+            delta_bytes.copy_from_slice(chunk);
+            let delta = u64::from_le_bytes(delta_bytes);
+
+            let len = balances.len();
+            if let Some(balance) = balances.get_mut(i) {
+                *balance = balance.wrapping_add(delta);
+            } else if i == len {
+                balances.push(delta).map_err(BeaconStateError::from)?;
+            } else {
+                return Err(Error::InvalidIndex { index: i, len });
+            }
+        }
+
+        *state.validators_mut() = validators;
+        *state.balances_mut() = balances;
+        Ok(())
+    }
+}
+
+impl StoreItem for HDiff {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconStateDiff
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, crate::Error> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
+/// The most recent state on each layer, used as the base when computing or applying diffs.
+///
+/// When states are stored in ascending slot order the base of every diff is guaranteed to be
+/// present, as no other state on the base's layer can be stored in between.
+#[derive(Debug)]
+pub struct HDiffBaseCache<E: EthSpec> {
+    layers: Vec<Option<(Slot, BeaconState<E>)>>,
+}
+
+impl<E: EthSpec> HDiffBaseCache<E> {
+    /// Create an empty cache with room for every layer of `hierarchy`.
+    ///
+    /// If diffs are disabled the cache never holds any states.
+    pub fn new(hierarchy: Option<&HierarchyModuli>) -> Self {
+        let num_layers = hierarchy.map_or(0, HierarchyModuli::num_layers);
+        Self {
+            layers: vec![None; num_layers],
+        }
+    }
+
+    pub fn insert(&mut self, layer: usize, slot: Slot, state: BeaconState<E>) {
+        if let Some(entry) = self.layers.get_mut(layer) {
+            *entry = Some((slot, state));
+        }
+    }
+
+    pub fn get(&self, slot: Slot) -> Option<&BeaconState<E>> {
+        self.layers
+            .iter()
+            .flatten()
+            .find(|(cached_slot, _)| *cached_slot == slot)
+            .map(|(_, state)| state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn moduli() -> HierarchyModuli {
+        HierarchyConfig::from_str("5,7")
+            .unwrap()
+            .to_moduli(1024, 32)
+            .unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let config = HierarchyConfig::from_str("5, 9,11").unwrap();
+        assert_eq!(config.exponents, vec![5, 9, 11]);
+        assert_eq!(config.to_string(), "5,9,11");
+
+        assert!(HierarchyConfig::from_str("").is_err());
+        assert!(HierarchyConfig::from_str("9,5").is_err());
+        assert!(HierarchyConfig::from_str("5,5").is_err());
+    }
+
+    #[test]
+    fn invalid_moduli() {
+        let below_epoch = HierarchyConfig { exponents: vec![3] };
+        assert!(below_epoch.to_moduli(1024, 32).is_err());
+
+        let above_restore_point = HierarchyConfig {
+            exponents: vec![5, 10],
+        };
+        assert!(above_restore_point.to_moduli(1024, 32).is_err());
+    }
+
+    #[test]
+    fn storage_strategy() {
+        let moduli = moduli();
+
+        assert_eq!(
+            moduli.storage_strategy(Slot::new(0)),
+            StorageStrategy::Snapshot
+        );
+        assert_eq!(
+            moduli.storage_strategy(Slot::new(2048)),
+            StorageStrategy::Snapshot
+        );
+        assert_eq!(
+            moduli.storage_strategy(Slot::new(1024 + 128)),
+            StorageStrategy::DiffFrom(Slot::new(1024))
+        );
+        assert_eq!(
+            moduli.storage_strategy(Slot::new(1024 + 128 + 32)),
+            StorageStrategy::DiffFrom(Slot::new(1024 + 128))
+        );
+        assert_eq!(
+            moduli.storage_strategy(Slot::new(1024 + 128 + 33)),
+            StorageStrategy::ReplayFrom(Slot::new(1024 + 128 + 32))
+        );
+    }
+
+    #[test]
+    fn diff_slots() {
+        let moduli = moduli();
+        let slots = moduli
+            .diff_slots(Slot::new(1), Slot::new(257))
+            .collect::<Vec<_>>();
+        let expected = [32, 64, 96, 128, 160, 192, 224, 256].map(Slot::new);
+        assert_eq!(slots, expected);
+    }
+}
@@ -1,29 +1,32 @@
+use crate::beacon_node_backend::BeaconNodeBackend;
 use crate::chunked_vector::{
-    store_updated_vector, BlockRoots, HistoricalRoots, HistoricalSummaries, RandaoMixes, StateRoots,
+    chunk_key, store_updated_vector, BlockRoots, Chunk, Field, HistoricalRoots,
+    HistoricalSummaries, RandaoMixes, StateRoots,
 };
+use crate::config::DatabaseBackend;
 use crate::config::{
-    OnDiskStoreConfig, StoreConfig, DEFAULT_SLOTS_PER_RESTORE_POINT,
+    OnDiskStoreConfig, OnDiskStoreConfigV1, StoreConfig, DEFAULT_SLOTS_PER_RESTORE_POINT,
     PREV_DEFAULT_SLOTS_PER_RESTORE_POINT,
 };
 use crate::forwards_iter::{HybridForwardsBlockRootsIterator, HybridForwardsStateRootsIterator};
+use crate::hdiff::{HDiff, HDiffBaseCache, HierarchyConfig, HierarchyModuli, StorageStrategy};
 use crate::impls::beacon_state::{get_full_state, store_full_state};
 use crate::iter::{BlockRootsIterator, ParentRootBlockIterator, RootsIterator};
 use crate::leveldb_store::BytesKey;
-use crate::leveldb_store::LevelDB;
 use crate::memory_store::MemoryStore;
 use crate::metadata::{
     AnchorInfo, BlobInfo, CompactionTimestamp, PruningCheckpoint, SchemaVersion, ANCHOR_INFO_KEY,
     BLOB_INFO_KEY, COMPACTION_TIMESTAMP_KEY, CONFIG_KEY, CURRENT_SCHEMA_VERSION,
-    PRUNING_CHECKPOINT_KEY, SCHEMA_VERSION_KEY, SPLIT_KEY, STATE_UPPER_LIMIT_NO_RETAIN,
+    DATABASE_BACKEND_KEY, PRUNING_CHECKPOINT_KEY, SCHEMA_VERSION_KEY, SPLIT_KEY,
+    STATE_UPPER_LIMIT_NO_RETAIN,
 };
 use crate::metrics;
 use crate::state_cache::{PutStateOutcome, StateCache};
 use crate::{
-    get_key_for_col, ChunkWriter, DBColumn, DatabaseBlock, Error, ItemStore, KeyValueStoreOp,
-    PartialBeaconState, StoreItem, StoreOp,
+    get_data_column_key, get_key_for_col, ChunkWriter, DBColumn, DatabaseBlock, Error, ItemStore,
+    KeyValueStoreOp, PartialBeaconState, StoreItem, StoreOp, DATA_COLUMN_DB_KEY_SIZE,
 };
 use itertools::process_results;
-use leveldb::iterator::LevelDBIterator;
 use lru::LruCache;
 use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
@@ -74,6 +77,13 @@ pub struct HotColdDB<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> {
     state_cache: Mutex<StateCache<E>>,
     /// LRU cache of replayed states.
     historic_state_cache: Mutex<LruCache<Slot, BeaconState<E>>>,
+    /// Spacing of the state diff layers in the freezer, if enabled.
+    hierarchy: Option<HierarchyModuli>,
+    /// The most recently frozen state on each diff layer, used as the base for new diffs.
+    hdiff_base_cache: Mutex<HDiffBaseCache<E>>,
+    /// The most recently loaded diff base on each diff layer, so that states diffed against the
+    /// same bases can be loaded without applying every diff beneath them again.
+    hdiff_load_cache: Mutex<HDiffBaseCache<E>>,
     /// Chain spec.
     pub(crate) spec: ChainSpec,
     /// Logger.
@@ -135,6 +145,7 @@ pub enum HotColdDBError {
     MissingStateToFreeze(Hash256),
     MissingRestorePointHash(u64),
     MissingRestorePoint(Hash256),
+    MissingHDiff(Slot),
     MissingColdStateSummary(Hash256),
     MissingHotStateSummary(Hash256),
     MissingEpochBoundaryState(Hash256),
@@ -175,6 +186,7 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
         log: Logger,
     ) -> Result<HotColdDB<E, MemoryStore<E>, MemoryStore<E>>, Error> {
         Self::verify_config(&config)?;
+        let hierarchy = config.hierarchy_moduli(E::slots_per_epoch())?;
 
         let db = HotColdDB {
             split: RwLock::new(Split::default()),
@@ -186,6 +198,9 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
             block_cache: Mutex::new(BlockCache::new(config.block_cache_size)),
             state_cache: Mutex::new(StateCache::new(config.state_cache_size)),
             historic_state_cache: Mutex::new(LruCache::new(config.historic_state_cache_size)),
+            hdiff_base_cache: Mutex::new(HDiffBaseCache::new(hierarchy.as_ref())),
+            hdiff_load_cache: Mutex::new(HDiffBaseCache::new(hierarchy.as_ref())),
+            hierarchy,
             config,
             spec,
             log,
@@ -196,7 +211,7 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
     }
 }
 
-impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
+impl<E: EthSpec> HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>> {
     /// Open a new or existing database, with the given paths to the hot and cold DBs.
     ///
     /// The `migrate_schema` function is passed in so that the parent `BeaconChain` can provide
@@ -216,12 +231,15 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
             split: RwLock::new(Split::default()),
             anchor_info: RwLock::new(None),
             blob_info: RwLock::new(BlobInfo::default()),
-            cold_db: LevelDB::open(cold_path)?,
-            blobs_db: LevelDB::open(blobs_db_path)?,
-            hot_db: LevelDB::open(hot_path)?,
+            cold_db: BeaconNodeBackend::open(config.backend, cold_path)?,
+            blobs_db: BeaconNodeBackend::open(config.backend, blobs_db_path)?,
+            hot_db: BeaconNodeBackend::open(config.backend, hot_path)?,
             block_cache: Mutex::new(BlockCache::new(config.block_cache_size)),
             state_cache: Mutex::new(StateCache::new(config.state_cache_size)),
             historic_state_cache: Mutex::new(LruCache::new(config.historic_state_cache_size)),
+            hierarchy: None,
+            hdiff_base_cache: Mutex::new(HDiffBaseCache::new(None)),
+            hdiff_load_cache: Mutex::new(HDiffBaseCache::new(None)),
             config,
             spec,
             log,
@@ -231,16 +249,16 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
         // Allow the slots-per-restore-point value to stay at the previous default if the config
         // uses the new default. Don't error on a failed read because the config itself may need
         // migrating.
-        if let Ok(Some(disk_config)) = db.load_config() {
+        if let Ok(Some(disk_slots_per_restore_point)) = db.load_disk_slots_per_restore_point() {
             if !db.config.slots_per_restore_point_set_explicitly
-                && disk_config.slots_per_restore_point == PREV_DEFAULT_SLOTS_PER_RESTORE_POINT
+                && disk_slots_per_restore_point == PREV_DEFAULT_SLOTS_PER_RESTORE_POINT
                 && db.config.slots_per_restore_point == DEFAULT_SLOTS_PER_RESTORE_POINT
             {
                 debug!(
                     db.log,
                     "Ignoring slots-per-restore-point config in favour of on-disk value";
                     "config" => db.config.slots_per_restore_point,
-                    "on_disk" => disk_config.slots_per_restore_point,
+                    "on_disk" => disk_slots_per_restore_point,
                 );
 
                 // Mutate the in-memory config so that it's compatible.
@@ -248,6 +266,23 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
             }
         }
 
+        // The diff layers depend on the final slots-per-restore-point value.
+        db.hierarchy = db.config.hierarchy_moduli(E::slots_per_epoch())?;
+        db.hdiff_base_cache = Mutex::new(HDiffBaseCache::new(db.hierarchy.as_ref()));
+        db.hdiff_load_cache = Mutex::new(HDiffBaseCache::new(db.hierarchy.as_ref()));
+
+        // Ensure that the database was created with the configured backend. Existing databases
+        // created prior to the introduction of alternative backends are always LevelDB.
+        let on_disk_backend = match db.load_database_backend()? {
+            Some(backend) => Some(backend),
+            None if db.load_schema_version()?.is_some() => Some(DatabaseBackend::LevelDb),
+            None => None,
+        };
+        if let Some(on_disk_backend) = on_disk_backend {
+            db.config.check_backend_compatibility(on_disk_backend)?;
+        }
+        db.store_database_backend()?;
+
         // Load the previous split slot from the database (if any). This ensures we can
         // stop and restart correctly. This needs to occur *before* running any migrations
         // because some migrations load states and depend on the split.
@@ -337,23 +372,19 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
 
     /// Return an iterator over the state roots of all temporary states.
     pub fn iter_temporary_state_roots(&self) -> impl Iterator<Item = Result<Hash256, Error>> + '_ {
-        let column = DBColumn::BeaconStateTemporary;
-        let start_key =
-            BytesKey::from_vec(get_key_for_col(column.into(), Hash256::zero().as_bytes()));
-
-        let keys_iter = self.hot_db.keys_iter();
-        keys_iter.seek(&start_key);
-
-        keys_iter
-            .take_while(move |key| key.matches_column(column))
-            .map(move |bytes_key| {
-                bytes_key.remove_column(column).ok_or_else(|| {
-                    HotColdDBError::IterationError {
-                        unexpected_key: bytes_key,
-                    }
-                    .into()
-                })
-            })
+        self.hot_db
+            .iter_column_keys::<Hash256>(DBColumn::BeaconStateTemporary)
+    }
+
+    /// Load the backend recorded in the database metadata, if any.
+    fn load_database_backend(&self) -> Result<Option<DatabaseBackend>, Error> {
+        self.hot_db.get(&DATABASE_BACKEND_KEY)
+    }
+
+    /// Record the backend in use in the database metadata.
+    fn store_database_backend(&self) -> Result<(), Error> {
+        self.hot_db
+            .put(&DATABASE_BACKEND_KEY, &self.hot_db.backend())
     }
 }
 
@@ -602,6 +633,12 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             .key_delete(DBColumn::BeaconBlock.into(), block_root.as_bytes())?;
         self.hot_db
             .key_delete(DBColumn::ExecPayload.into(), block_root.as_bytes())?;
+        for column_index in self.get_data_column_keys(*block_root)? {
+            self.blobs_db.key_delete(
+                DBColumn::BeaconDataColumn.into(),
+                &get_data_column_key(block_root, &column_index),
+            )?;
+        }
         self.blobs_db
             .key_delete(DBColumn::BeaconBlob.into(), block_root.as_bytes())
     }
@@ -626,6 +663,24 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         ops.push(KeyValueStoreOp::PutKeyValue(db_key, blobs.as_ssz_bytes()));
     }
 
+    pub fn data_columns_as_kv_store_ops(
+        &self,
+        block_root: &Hash256,
+        data_columns: DataColumnSidecarList<E>,
+        ops: &mut Vec<KeyValueStoreOp>,
+    ) {
+        for data_column in data_columns {
+            let db_key = get_key_for_col(
+                DBColumn::BeaconDataColumn.into(),
+                &get_data_column_key(block_root, &data_column.index),
+            );
+            ops.push(KeyValueStoreOp::PutKeyValue(
+                db_key,
+                data_column.as_ssz_bytes(),
+            ));
+        }
+    }
+
     pub fn put_state_summary(
         &self,
         state_root: &Hash256,
@@ -909,6 +964,14 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                     self.blobs_as_kv_store_ops(&block_root, blobs, &mut key_value_batch);
                 }
 
+                StoreOp::PutDataColumns(block_root, data_columns) => {
+                    self.data_columns_as_kv_store_ops(
+                        &block_root,
+                        data_columns,
+                        &mut key_value_batch,
+                    );
+                }
+
                 StoreOp::PutStateSummary(state_root, summary) => {
                     key_value_batch.push(summary.as_kv_store_op(state_root));
                 }
@@ -933,6 +996,16 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                     key_value_batch.push(KeyValueStoreOp::DeleteKey(key));
                 }
 
+                StoreOp::DeleteDataColumns(block_root, column_indices) => {
+                    for index in column_indices {
+                        let key = get_key_for_col(
+                            DBColumn::BeaconDataColumn.into(),
+                            &get_data_column_key(&block_root, &index),
+                        );
+                        key_value_batch.push(KeyValueStoreOp::DeleteKey(key));
+                    }
+                }
+
                 StoreOp::DeleteState(state_root, slot) => {
                     let state_summary_key =
                         get_key_for_col(DBColumn::BeaconStateSummary.into(), state_root.as_bytes());
@@ -963,6 +1036,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         batch: Vec<StoreOp<E>>,
     ) -> Result<(), Error> {
         let mut blobs_to_delete = Vec::new();
+        let mut data_columns_to_delete = Vec::new();
         let (blobs_ops, hot_db_ops): (Vec<StoreOp<E>>, Vec<StoreOp<E>>) =
             batch.into_iter().partition(|store_op| match store_op {
                 StoreOp::PutBlobs(_, _) => true,
@@ -982,6 +1056,26 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                     }
                     true
                 }
+                StoreOp::PutDataColumns(_, _) => true,
+                StoreOp::DeleteDataColumns(block_root, column_indices) => {
+                    for index in column_indices {
+                        match self.get_data_column(block_root, index) {
+                            Ok(Some(data_column)) => {
+                                data_columns_to_delete.push((*block_root, data_column));
+                            }
+                            Err(e) => {
+                                error!(
+                                    self.log, "Error getting data column";
+                                    "block_root" => %block_root,
+                                    "column_index" => index,
+                                    "error" => ?e
+                                );
+                            }
+                            _ => (),
+                        }
+                    }
+                    true
+                }
                 StoreOp::PutBlock(_, _) | StoreOp::DeleteBlock(_) => false,
                 _ => false,
             });
@@ -1017,6 +1111,20 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                         Some((block_root, blobs)) => StoreOp::PutBlobs(block_root, blobs),
                         None => return Err(HotColdDBError::Rollback.into()),
                     },
+                    StoreOp::PutDataColumns(block_root, data_columns) => {
+                        StoreOp::DeleteDataColumns(
+                            *block_root,
+                            data_columns.iter().map(|c| c.index).collect(),
+                        )
+                    }
+                    StoreOp::DeleteDataColumns(block_root, _) => StoreOp::PutDataColumns(
+                        *block_root,
+                        data_columns_to_delete
+                            .iter()
+                            .filter(|(root, _)| root == block_root)
+                            .map(|(_, data_column)| data_column.clone())
+                            .collect(),
+                    ),
                     _ => return Err(HotColdDBError::Rollback.into()),
                 };
                 *op = reverse_op;
@@ -1034,6 +1142,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
                 StoreOp::PutBlobs(_, _) => (),
 
+                StoreOp::PutDataColumns(_, _) => (),
+
                 StoreOp::PutState(_, _) => (),
 
                 StoreOp::PutStateSummary(_, _) => (),
@@ -1053,6 +1163,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
                 StoreOp::DeleteBlobs(_) => (),
 
+                StoreOp::DeleteDataColumns(_, _) => (),
+
                 StoreOp::DeleteExecutionPayload(_) => (),
 
                 StoreOp::KeyValueOp(_) => (),
@@ -1249,7 +1361,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
     /// Store a pre-finalization state in the freezer database.
     ///
-    /// If the state doesn't lie on a restore point boundary then just its summary will be stored.
+    /// If the state doesn't lie on a restore point boundary or a diff layer then just its summary
+    /// will be stored.
     pub fn store_cold_state(
         &self,
         state_root: &Hash256,
@@ -1258,8 +1371,22 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     ) -> Result<(), Error> {
         ops.push(ColdStateSummary { slot: state.slot() }.as_kv_store_op(*state_root));
 
-        if state.slot() % self.config.slots_per_restore_point != 0 {
-            return Ok(());
+        match self.storage_strategy(state.slot()) {
+            StorageStrategy::Snapshot => (),
+            StorageStrategy::DiffFrom(base_slot) => {
+                if let Some(hierarchy) = &self.hierarchy {
+                    let mut base_cache = self.hdiff_base_cache.lock();
+                    self.store_cold_state_as_diff(
+                        hierarchy,
+                        &mut base_cache,
+                        base_slot,
+                        state,
+                        ops,
+                    )?;
+                }
+                return Ok(());
+            }
+            StorageStrategy::ReplayFrom(_) => return Ok(()),
         }
 
         trace!(
@@ -1289,6 +1416,82 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         let restore_point_index = state.slot().as_u64() / self.config.slots_per_restore_point;
         self.store_restore_point_hash(restore_point_index, *state_root, ops);
 
+        // 4. Keep the restore point as the base for the next diffs.
+        if let Some(layer) = self.hierarchy.as_ref().and_then(|h| h.layer(state.slot())) {
+            self.hdiff_base_cache
+                .lock()
+                .insert(layer, state.slot(), state.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Determine how the frozen state at `slot` is stored.
+    fn storage_strategy(&self, slot: Slot) -> StorageStrategy {
+        if let Some(hierarchy) = &self.hierarchy {
+            return hierarchy.storage_strategy(slot);
+        }
+        let slots_per_restore_point = self.config.slots_per_restore_point;
+        if slot % slots_per_restore_point == 0 {
+            StorageStrategy::Snapshot
+        } else {
+            StorageStrategy::ReplayFrom(slot / slots_per_restore_point * slots_per_restore_point)
+        }
+    }
+
+    /// Store `state` as a diff relative to the frozen state at `base_slot`.
+    ///
+    /// The base state is taken from `base_cache` if present, otherwise it is loaded from the
+    /// freezer, so it must have been stored (and committed) already.
+    fn store_cold_state_as_diff(
+        &self,
+        hierarchy: &HierarchyModuli,
+        base_cache: &mut HDiffBaseCache<E>,
+        base_slot: Slot,
+        state: &BeaconState<E>,
+        ops: &mut Vec<KeyValueStoreOp>,
+    ) -> Result<(), Error> {
+        trace!(
+            self.log,
+            "Creating state diff";
+            "slot" => state.slot(),
+            "base_slot" => base_slot,
+        );
+
+        let base = match base_cache.get(base_slot) {
+            Some(base) => base.clone(),
+            None => {
+                let base = self.load_hdiff_base(base_slot)?;
+                if let Some(layer) = hierarchy.layer(base_slot) {
+                    base_cache.insert(layer, base_slot, base.clone());
+                }
+                base
+            }
+        };
+
+        // 1. Store the diff itself, keyed by slot.
+        let diff = HDiff::compute(base_slot, &base, state)?;
+        ops.push(KeyValueStoreOp::PutKeyValue(
+            get_key_for_col(
+                DBColumn::BeaconStateDiff.into(),
+                &state.slot().as_u64().to_be_bytes(),
+            ),
+            diff.as_store_bytes(),
+        ));
+
+        // 2. Store updated vector entries, which are not included in the diff.
+        let db = &self.cold_db;
+        store_updated_vector(BlockRoots, db, state, &self.spec, ops)?;
+        store_updated_vector(StateRoots, db, state, &self.spec, ops)?;
+        store_updated_vector(HistoricalRoots, db, state, &self.spec, ops)?;
+        store_updated_vector(RandaoMixes, db, state, &self.spec, ops)?;
+        store_updated_vector(HistoricalSummaries, db, state, &self.spec, ops)?;
+
+        // 3. Keep the state as the base for diffs on the layer below.
+        if let Some(layer) = hierarchy.layer(state.slot()) {
+            base_cache.insert(layer, state.slot(), state.clone());
+        }
+
         Ok(())
     }
 
@@ -1330,8 +1533,15 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             .cold_db
             .get_bytes(DBColumn::BeaconState.into(), state_root.as_bytes())?
             .ok_or(HotColdDBError::MissingRestorePoint(*state_root))?;
+        let mut state = self.load_partial_state_vectors(&partial_state_bytes)?;
+        state.apply_pending_mutations()?;
+        Ok(state)
+    }
+
+    /// Decode a `PartialBeaconState` and fill in its vector fields from the freezer.
+    fn load_partial_state_vectors(&self, bytes: &[u8]) -> Result<BeaconState<E>, Error> {
         let mut partial_state: PartialBeaconState<E> =
-            PartialBeaconState::from_ssz_bytes(&partial_state_bytes, &self.spec)?;
+            PartialBeaconState::from_ssz_bytes(bytes, &self.spec)?;
 
         // Fill in the fields of the partial state.
         partial_state.load_block_roots(&self.cold_db, &self.spec)?;
@@ -1340,8 +1550,55 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         partial_state.load_randao_mixes(&self.cold_db, &self.spec)?;
         partial_state.load_historical_summaries(&self.cold_db, &self.spec)?;
 
-        let mut state: BeaconState<E> = partial_state.try_into()?;
+        Ok(partial_state.try_into()?)
+    }
+
+    /// Load the diff stored for the frozen state at `slot`, if any.
+    fn load_hdiff(&self, slot: Slot) -> Result<Option<HDiff>, Error> {
+        self.cold_db
+            .get_bytes(
+                DBColumn::BeaconStateDiff.into(),
+                &slot.as_u64().to_be_bytes(),
+            )?
+            .map(|bytes| HDiff::from_store_bytes(&bytes))
+            .transpose()
+    }
+
+    /// Load the frozen state at `slot` by applying its diff, if one is stored.
+    fn load_cold_state_from_hdiff(&self, slot: Slot) -> Result<Option<BeaconState<E>>, Error> {
+        let Some(diff) = self.load_hdiff(slot)? else {
+            return Ok(None);
+        };
+        let base = self.load_hdiff_base(diff.base_slot)?;
+
+        let mut state = self.load_partial_state_vectors(&diff.partial_state_bytes)?;
+        diff.apply_registry(&base, &mut state)?;
         state.apply_pending_mutations()?;
+        Ok(Some(state))
+    }
+
+    /// Load the frozen state at `slot` that a diff was computed against.
+    ///
+    /// The base must be either a restore point or a state stored as a diff. Bases are cached by
+    /// layer, so that loading nearby states only applies the diffs which differ between them.
+    fn load_hdiff_base(&self, slot: Slot) -> Result<BeaconState<E>, Error> {
+        if let Some(state) = self.hdiff_load_cache.lock().get(slot) {
+            return Ok(state.clone());
+        }
+
+        let slots_per_restore_point = self.config.slots_per_restore_point;
+        let state = if slot % slots_per_restore_point == 0 {
+            self.load_restore_point_by_index(slot.as_u64() / slots_per_restore_point)?
+        } else {
+            self.load_cold_state_from_hdiff(slot)?
+                .ok_or(HotColdDBError::MissingHDiff(slot))?
+        };
+
+        if let Some(layer) = self.hierarchy.as_ref().and_then(|h| h.layer(slot)) {
+            self.hdiff_load_cache
+                .lock()
+                .insert(layer, slot, state.clone());
+        }
         Ok(state)
     }
 
@@ -1360,6 +1617,12 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             return Ok(state.clone());
         }
 
+        // States stored as diffs don't require any block replay.
+        if let Some(state) = self.load_cold_state_from_hdiff(slot)? {
+            self.historic_state_cache.lock().put(slot, state.clone());
+            return Ok(state);
+        }
+
         // 1. Load the restore points either side of the intermediate state.
         let low_restore_point_idx = slot.as_u64() / self.config.slots_per_restore_point;
         let high_restore_point_idx = low_restore_point_idx + 1;
@@ -1380,6 +1643,16 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             }
         }
 
+        // Replay from the closest diff if it is more recent than any cached state.
+        if let StorageStrategy::ReplayFrom(base_slot) = self.storage_strategy(slot) {
+            if low_slot < base_slot {
+                if let Some(state) = self.load_cold_state_from_hdiff(base_slot)? {
+                    low_slot = base_slot;
+                    low_state = Some(state);
+                }
+            }
+        }
+
         // If low_state is still None, use load_restore_point_by_index to load the state.
         let low_state = match low_state {
             Some(state) => state,
@@ -1552,6 +1825,84 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         }
     }
 
+    /// Fetch the data column with index `column_index` for a given block from the store.
+    pub fn get_data_column(
+        &self,
+        block_root: &Hash256,
+        column_index: &ColumnIndex,
+    ) -> Result<Option<Arc<DataColumnSidecar<E>>>, Error> {
+        match self.blobs_db.get_bytes(
+            DBColumn::BeaconDataColumn.into(),
+            &get_data_column_key(block_root, column_index),
+        )? {
+            Some(ref data_column_bytes) => Ok(Some(Arc::new(DataColumnSidecar::from_ssz_bytes(
+                data_column_bytes,
+            )?))),
+            None => Ok(None),
+        }
+    }
+
+    /// Fetch the indices of all data columns stored for a given block.
+    pub fn get_data_column_keys(&self, block_root: Hash256) -> Result<Vec<ColumnIndex>, Error> {
+        self.blobs_db
+            .iter_raw_keys(DBColumn::BeaconDataColumn, block_root.as_bytes())
+            .map(|key| {
+                let key = key?;
+                let index_bytes = key
+                    .get(32..DATA_COLUMN_DB_KEY_SIZE)
+                    .and_then(|bytes| <[u8; 8]>::try_from(bytes).ok())
+                    .ok_or(Error::InvalidKey)?;
+                Ok(ColumnIndex::from_be_bytes(index_bytes))
+            })
+            .collect()
+    }
+
+    /// Fetch the best light client update for a sync committee period.
+    pub fn get_light_client_update(
+        &self,
+        sync_committee_period: u64,
+    ) -> Result<Option<LightClientUpdate<E>>, Error> {
+        let Some(bytes) = self.hot_db.get_bytes(
+            DBColumn::LightClientUpdate.into(),
+            &sync_committee_period.to_be_bytes(),
+        )?
+        else {
+            return Ok(None);
+        };
+
+        // Updates are prefixed by their attested slot, which determines their fork.
+        if bytes.len() < 8 {
+            return Err(ssz::DecodeError::InvalidByteLength {
+                len: bytes.len(),
+                expected: 8,
+            }
+            .into());
+        }
+        let (slot_bytes, update_bytes) = bytes.split_at(8);
+        let attested_slot = Slot::from_ssz_bytes(slot_bytes)?;
+        let fork_name = self.spec.fork_name_at_slot::<E>(attested_slot);
+        Ok(Some(LightClientUpdate::from_ssz_bytes(
+            update_bytes,
+            fork_name,
+        )?))
+    }
+
+    /// Store the best light client update for a sync committee period, replacing any existing
+    /// update.
+    pub fn store_light_client_update(
+        &self,
+        sync_committee_period: u64,
+        update: &LightClientUpdate<E>,
+    ) -> Result<(), Error> {
+        let mut bytes = update.attested_header_slot().as_ssz_bytes();
+        bytes.extend(update.as_ssz_bytes());
+        self.hot_db.put_bytes(
+            DBColumn::LightClientUpdate.into(),
+            &sync_committee_period.to_be_bytes(),
+            &bytes,
+        )
+    }
+
     /// Get a reference to the `ChainSpec` used by the database.
     pub fn get_chain_spec(&self) -> &ChainSpec {
         &self.spec
@@ -1845,6 +2196,22 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         self.hot_db.get(&CONFIG_KEY)
     }
 
+    /// Load the slots-per-restore-point of the config on disk, which may still be in the layout
+    /// from before schema v22 because the schema is only migrated once the database is open.
+    fn load_disk_slots_per_restore_point(&self) -> Result<Option<u64>, Error> {
+        let Some(bytes) = self
+            .hot_db
+            .get_bytes(DBColumn::BeaconMeta.into(), CONFIG_KEY.as_bytes())?
+        else {
+            return Ok(None);
+        };
+        let slots_per_restore_point = match OnDiskStoreConfig::from_store_bytes(&bytes) {
+            Ok(config) => config.slots_per_restore_point,
+            Err(_) => OnDiskStoreConfigV1::from_ssz_bytes(&bytes)?.slots_per_restore_point,
+        };
+        Ok(Some(slots_per_restore_point))
+    }
+
     /// Write the config to disk.
     fn store_config(&self) -> Result<(), Error> {
         self.hot_db.put(&CONFIG_KEY, &self.config.as_disk_config())
@@ -2257,6 +2624,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
         let mut ops = vec![];
         let mut last_pruned_block_root = None;
+        let mut data_column_ops = vec![];
+        let mut last_pruned_data_columns_block_root = None;
 
         for res in self.forwards_block_roots_iterator_until(
             oldest_blob_slot,
@@ -2296,11 +2665,21 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                 ops.push(StoreOp::DeleteBlobs(block_root));
             }
 
+            if Some(block_root) != last_pruned_data_columns_block_root {
+                let column_indices = self.get_data_column_keys(block_root)?;
+                if !column_indices.is_empty() {
+                    last_pruned_data_columns_block_root = Some(block_root);
+                    data_column_ops.push(StoreOp::DeleteDataColumns(block_root, column_indices));
+                }
+            }
+
             if slot >= end_slot {
                 break;
             }
         }
         let blob_lists_pruned = ops.len();
+        let data_column_lists_pruned = data_column_ops.len();
+        ops.extend(data_column_ops);
         let new_blob_info = BlobInfo {
             oldest_blob_slot: Some(end_slot + 1),
             blobs_db: blob_info.blobs_db,
@@ -2313,6 +2692,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             self.log,
             "Blob pruning complete";
             "blob_lists_pruned" => blob_lists_pruned,
+            "data_column_lists_pruned" => data_column_lists_pruned,
         );
 
         Ok(())
@@ -2396,6 +2776,66 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         Ok(())
     }
 
+    /// Rewrite the frozen block roots between the oldest block and the split by following the
+    /// parent links back from the split block.
+    ///
+    /// Unlike the `heal_*` methods this overwrites existing values, so it can be used to repair
+    /// block roots that have been corrupted. Returns the number of slots that were changed.
+    pub fn repair_frozen_block_roots(&self) -> Result<usize, Error> {
+        let split = self.get_split_info();
+        let oldest_block_slot = self.get_oldest_block_slot();
+        let chunk_size = <BlockRoots as Field<E>>::chunk_size();
+
+        let mut ops = vec![];
+        let mut num_repaired = 0;
+        // The chunk being modified, its index, and whether it has changed.
+        let mut current_chunk: Option<(usize, Chunk<Hash256>, bool)> = None;
+        let mut end_slot = split.slot;
+
+        for res in ParentRootBlockIterator::new(self, split.block_root) {
+            let (block_root, block) = res?;
+            let start_slot = std::cmp::max(block.slot(), oldest_block_slot);
+
+            for slot in (start_slot.as_usize()..end_slot.as_usize()).rev() {
+                let chunk_index = slot / chunk_size;
+                if current_chunk.as_ref().map(|(i, _, _)| *i) != Some(chunk_index) {
+                    if let Some((i, chunk, true)) = current_chunk.take() {
+                        chunk.store(DBColumn::BeaconBlockRoots, &chunk_key(i), &mut ops)?;
+                    }
+                    let mut chunk = Chunk::load(
+                        &self.cold_db,
+                        DBColumn::BeaconBlockRoots,
+                        &chunk_key(chunk_index),
+                    )?
+                    .unwrap_or_default();
+                    chunk.values.resize(chunk_size, Hash256::zero());
+                    current_chunk = Some((chunk_index, chunk, false));
+                }
+
+                if let Some((_, chunk, changed)) = current_chunk.as_mut() {
+                    let value = &mut chunk.values[slot % chunk_size];
+                    if *value != block_root {
+                        *value = block_root;
+                        *changed = true;
+                        num_repaired += 1;
+                    }
+                }
+            }
+
+            if block.slot() <= oldest_block_slot {
+                break;
+            }
+            end_slot = block.slot();
+        }
+
+        if let Some((i, chunk, true)) = current_chunk {
+            chunk.store(DBColumn::BeaconBlockRoots, &chunk_key(i), &mut ops)?;
+        }
+        self.cold_db.do_atomically(ops)?;
+
+        Ok(num_repaired)
+    }
+
     /// Delete *all* states from the freezer database and update the anchor accordingly.
     ///
     /// WARNING: this method deletes the genesis state and replaces it with the provided
@@ -2445,6 +2885,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             DBColumn::BeaconHistoricalRoots,
             DBColumn::BeaconRandaoMixes,
             DBColumn::BeaconHistoricalSummaries,
+            DBColumn::BeaconStateDiff,
         ];
 
         for column in columns {
@@ -2486,6 +2927,103 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         Ok(())
     }
 
+    /// Re-encode the frozen states between restore points according to a new diff hierarchy.
+    ///
+    /// Diffs are written in ascending slot order, overwriting any existing diff at the same slot.
+    /// As every diff records its own base, the freezer remains readable throughout. Diffs that are
+    /// not part of the `target` hierarchy are deleted at the end. If `target` is `None`, all diffs
+    /// are deleted and only restore points are kept.
+    ///
+    /// The on-disk config is updated to `target`, so the node must be restarted with matching
+    /// hierarchy exponents.
+    pub fn convert_freezer_hierarchy(&self, target: Option<HierarchyConfig>) -> Result<(), Error> {
+        let target_hierarchy = target
+            .as_ref()
+            .map(|config| {
+                config.to_moduli(self.config.slots_per_restore_point, E::slots_per_epoch())
+            })
+            .transpose()?;
+
+        if let Some(hierarchy) = &target_hierarchy {
+            let (lower_limit, upper_limit) = self.get_historic_state_limits();
+            let split_slot = self.get_split_slot();
+            let mut base_cache = HDiffBaseCache::new(Some(hierarchy));
+            let mut num_diffs = 0;
+
+            for slot in hierarchy
+                .diff_slots(Slot::new(0), split_slot)
+                .filter(|slot| *slot <= lower_limit || *slot >= upper_limit)
+            {
+                let Some(state) = self.load_cold_state_by_slot(slot)? else {
+                    continue;
+                };
+                let StorageStrategy::DiffFrom(base_slot) = hierarchy.storage_strategy(slot) else {
+                    continue;
+                };
+
+                let mut ops = vec![];
+                self.store_cold_state_as_diff(
+                    hierarchy,
+                    &mut base_cache,
+                    base_slot,
+                    &state,
+                    &mut ops,
+                )?;
+                self.cold_db.do_atomically(ops)?;
+
+                num_diffs += 1;
+                if num_diffs % 1024 == 0 {
+                    info!(
+                        self.log,
+                        "Freezer conversion in progress";
+                        "slot" => slot,
+                        "split_slot" => split_slot,
+                    );
+                }
+            }
+            info!(self.log, "Stored state diffs"; "count" => num_diffs);
+        }
+
+        // Delete the diffs which aren't part of the target hierarchy.
+        let mut cold_ops = vec![];
+        for res in self
+            .cold_db
+            .iter_column_keys::<Vec<u8>>(DBColumn::BeaconStateDiff)
+        {
+            let key = res?;
+            let slot_bytes = <[u8; 8]>::try_from(key.as_slice()).map_err(|_| Error::InvalidKey)?;
+            let slot = Slot::new(u64::from_be_bytes(slot_bytes));
+            let keep = target_hierarchy.as_ref().map_or(false, |hierarchy| {
+                matches!(
+                    hierarchy.storage_strategy(slot),
+                    StorageStrategy::DiffFrom(_)
+                )
+            });
+            if !keep {
+                cold_ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                    DBColumn::BeaconStateDiff.into(),
+                    &key,
+                )));
+            }
+        }
+        info!(
+            self.log,
+            "Deleting unused state diffs";
+            "count" => cold_ops.len(),
+        );
+        self.cold_db.do_atomically(cold_ops)?;
+
+        let disk_config = OnDiskStoreConfig {
+            slots_per_restore_point: self.config.slots_per_restore_point,
+            hierarchy_exponents: target.map_or(vec![], |config| config.exponents),
+        };
+        self.hot_db.put(&CONFIG_KEY, &disk_config)?;
+        self.historic_state_cache.lock().clear();
+        *self.hdiff_load_cache.lock() = HDiffBaseCache::new(self.hierarchy.as_ref());
+
+        Ok(())
+    }
+
     /// Prune states from the hot database which are prior to the split.
     ///
     /// This routine is important for cleaning up advanced states which are stored in the database
@@ -2573,6 +3111,10 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
     let mut hot_db_ops = vec![];
     let mut cold_db_ops = vec![];
 
+    // States to be stored as diffs. These are stored in ascending slot order after the main loop,
+    // so that the base of each diff has already been written.
+    let mut diff_states = vec![];
+
     // Chunk writer for the linear block roots in the freezer DB.
     // Start at the new upper limit because we iterate backwards.
     let new_frozen_block_root_upper_limit = finalized_state.slot().as_usize().saturating_sub(1);
@@ -2624,17 +3166,22 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
         let op = cold_state_summary.as_kv_store_op(state_root);
         cold_db_ops.push(op);
 
-        if slot % store.config.slots_per_restore_point == 0 {
-            let state: BeaconState<E> = get_full_state(&store.hot_db, &state_root, &store.spec)?
-                .ok_or(HotColdDBError::MissingStateToFreeze(state_root))?;
+        match store.storage_strategy(slot) {
+            StorageStrategy::Snapshot => {
+                let state: BeaconState<E> =
+                    get_full_state(&store.hot_db, &state_root, &store.spec)?
+                        .ok_or(HotColdDBError::MissingStateToFreeze(state_root))?;
 
-            store.store_cold_state(&state_root, &state, &mut cold_db_ops)?;
+                store.store_cold_state(&state_root, &state, &mut cold_db_ops)?;
 
-            // Commit the batch of cold DB ops whenever a full state is written. Each state stored
-            // may read the linear fields of previous states stored.
-            store
-                .cold_db
-                .do_atomically(std::mem::take(&mut cold_db_ops))?;
+                // Commit the batch of cold DB ops whenever a full state is written. Each state
+                // stored may read the linear fields of previous states stored.
+                store
+                    .cold_db
+                    .do_atomically(std::mem::take(&mut cold_db_ops))?;
+            }
+            StorageStrategy::DiffFrom(_) => diff_states.push(state_root),
+            StorageStrategy::ReplayFrom(_) => (),
         }
     }
 
@@ -2642,6 +3189,16 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
     block_root_writer.write(&mut cold_db_ops)?;
     store.cold_db.do_atomically(cold_db_ops)?;
 
+    // Store the diffs, committing each one as the next diff may use it as its base.
+    for state_root in diff_states.into_iter().rev() {
+        let state: BeaconState<E> = get_full_state(&store.hot_db, &state_root, &store.spec)?
+            .ok_or(HotColdDBError::MissingStateToFreeze(state_root))?;
+
+        let mut diff_ops = vec![];
+        store.store_cold_state(&state_root, &state, &mut diff_ops)?;
+        store.cold_db.do_atomically(diff_ops)?;
+    }
+
     // Warning: Critical section.  We have to take care not to put any of the two databases in an
     //          inconsistent state if the OS process dies at any point during the freezing
     //          procedure.
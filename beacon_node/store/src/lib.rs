@@ -2,11 +2,12 @@
 //!
 //! Provides the following stores:
 //!
-//! - `HotColdDB`: an on-disk store backed by leveldb. Used in production.
+//! - `HotColdDB`: an on-disk store backed by leveldb or (optionally) redb. Used in production.
 //! - `MemoryStore`: an in-memory store backed by a hash-map. Used for testing.
 //!
 //! Provides a simple API for storing/retrieving all types that sometimes needs type-hints. See
 //! tests for implementation examples.
+pub mod beacon_node_backend;
 mod chunk_writer;
 pub mod chunked_iter;
 pub mod chunked_vector;
@@ -15,6 +16,7 @@ pub mod consensus_context;
 pub mod errors;
 mod forwards_iter;
 mod garbage_collection;
+pub mod hdiff;
 pub mod hot_cold_store;
 mod impls;
 mod leveldb_store;
@@ -23,17 +25,22 @@ pub mod metadata;
 pub mod metrics;
 mod partial_beacon_state;
 pub mod reconstruct;
+#[cfg(feature = "redb")]
+mod redb_store;
 pub mod state_cache;
 
 pub mod iter;
 
+pub use self::beacon_node_backend::BeaconNodeBackend;
 pub use self::chunk_writer::ChunkWriter;
-pub use self::config::StoreConfig;
+pub use self::config::{DatabaseBackend, StoreConfig};
 pub use self::consensus_context::OnDiskConsensusContext;
 pub use self::hot_cold_store::{HotColdDB, HotStateSummary, Split};
 pub use self::leveldb_store::LevelDB;
 pub use self::memory_store::MemoryStore;
 pub use self::partial_beacon_state::PartialBeaconState;
+#[cfg(feature = "redb")]
+pub use self::redb_store::Redb;
 pub use crate::metadata::BlobInfo;
 pub use errors::Error;
 pub use impls::beacon_state::StorageContainer as BeaconStateStorageContainer;
@@ -50,6 +57,9 @@ pub type ColumnKeyIter<'a, K> = Box<dyn Iterator<Item = Result<K, Error>> + 'a>;
 pub type RawEntryIter<'a> = Box<dyn Iterator<Item = Result<(Vec<u8>, Vec<u8>), Error>> + 'a>;
 pub type RawKeyIter<'a> = Box<dyn Iterator<Item = Result<Vec<u8>, Error>> + 'a>;
 
+/// Size of the keys in `DBColumn::BeaconDataColumn`: a 32 byte block root and an 8 byte index.
+pub const DATA_COLUMN_DB_KEY_SIZE: usize = 32 + 8;
+
 pub trait KeyValueStore<E: EthSpec>: Sync + Send + Sized + 'static {
     /// Retrieve some bytes in `column` with `key`.
     fn get_bytes(&self, column: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error>;
@@ -143,6 +153,13 @@ pub fn get_key_for_col(column: &str, key: &[u8]) -> Vec<u8> {
     result
 }
 
+/// Key for a single data column: the block root followed by the big-endian column index.
+pub fn get_data_column_key(block_root: &Hash256, column_index: &ColumnIndex) -> Vec<u8> {
+    let mut result = block_root.as_bytes().to_vec();
+    result.extend_from_slice(&column_index.to_be_bytes());
+    result
+}
+
 pub fn get_col_from_key(key: &[u8]) -> Option<String> {
     if key.len() < 3 {
         return None;
@@ -210,11 +227,13 @@ pub enum StoreOp<'a, E: EthSpec> {
     PutBlock(Hash256, Arc<SignedBeaconBlock<E>>),
     PutState(Hash256, &'a BeaconState<E>),
     PutBlobs(Hash256, BlobSidecarList<E>),
+    PutDataColumns(Hash256, DataColumnSidecarList<E>),
     PutStateSummary(Hash256, HotStateSummary),
     PutStateTemporaryFlag(Hash256),
     DeleteStateTemporaryFlag(Hash256),
     DeleteBlock(Hash256),
     DeleteBlobs(Hash256),
+    DeleteDataColumns(Hash256, Vec<ColumnIndex>),
     DeleteState(Hash256, Option<Slot>),
     DeleteExecutionPayload(Hash256),
     KeyValueOp(KeyValueStoreOp),
@@ -230,6 +249,9 @@ pub enum DBColumn {
     BeaconBlock,
     #[strum(serialize = "blb")]
     BeaconBlob,
+    /// For individual data column sidecars, keyed by block root and column index.
+    #[strum(serialize = "bdc")]
+    BeaconDataColumn,
     /// For full `BeaconState`s in the hot database (finalized or fork-boundary states).
     #[strum(serialize = "ste")]
     BeaconState,
@@ -267,11 +289,20 @@ pub enum DBColumn {
     BeaconRandaoMixes,
     #[strum(serialize = "dht")]
     DhtEnrs,
+    /// For the reputation of peers, persisted across restarts.
+    #[strum(serialize = "pdb")]
+    PeerDb,
     /// For Optimistically Imported Merge Transition Blocks
     #[strum(serialize = "otb")]
     OptimisticTransitionBlock,
     #[strum(serialize = "bhs")]
     BeaconHistoricalSummaries,
+    /// For hierarchical diffs of frozen states, keyed by slot.
+    #[strum(serialize = "bsd")]
+    BeaconStateDiff,
+    /// For the best `LightClientUpdate` of each sync committee period, keyed by period.
+    #[strum(serialize = "lcu")]
+    LightClientUpdate,
     #[strum(serialize = "olc")]
     OverflowLRUCache,
 }
@@ -311,12 +342,16 @@ impl DBColumn {
             | Self::PubkeyCache
             | Self::BeaconRestorePoint
             | Self::DhtEnrs
+            | Self::PeerDb
             | Self::OptimisticTransitionBlock => 32,
             Self::BeaconBlockRoots
             | Self::BeaconStateRoots
             | Self::BeaconHistoricalRoots
             | Self::BeaconHistoricalSummaries
-            | Self::BeaconRandaoMixes => 8,
+            | Self::BeaconRandaoMixes
+            | Self::BeaconStateDiff
+            | Self::LightClientUpdate => 8,
+            Self::BeaconDataColumn => DATA_COLUMN_DB_KEY_SIZE,
         }
     }
 }
@@ -396,6 +431,58 @@ mod tests {
         test_impl(store);
     }
 
+    #[cfg(feature = "redb")]
+    #[test]
+    fn redb() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        let store = Redb::open(path).unwrap();
+
+        test_impl(store);
+    }
+
+    #[test]
+    fn beacon_node_backend_detect() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        let store = LevelDB::<MinimalEthSpec>::open(path).unwrap();
+        drop(store);
+
+        assert_eq!(
+            beacon_node_backend::detect_backend(path).unwrap(),
+            Some(DatabaseBackend::LevelDb)
+        );
+        assert!(BeaconNodeBackend::<MinimalEthSpec>::open(DatabaseBackend::LevelDb, path).is_ok());
+    }
+
+    #[cfg(feature = "redb")]
+    #[test]
+    fn beacon_node_backend_mismatch() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        let store = LevelDB::<MinimalEthSpec>::open(path).unwrap();
+        drop(store);
+
+        assert!(BeaconNodeBackend::<MinimalEthSpec>::open(DatabaseBackend::Redb, path).is_err());
+    }
+
+    #[cfg(not(feature = "redb"))]
+    #[test]
+    fn beacon_node_backend_unsupported() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        std::fs::write(path.join(beacon_node_backend::REDB_DATA_FILENAME), []).unwrap();
+
+        assert!(matches!(
+            beacon_node_backend::detect_backend(path),
+            Err(Error::UnsupportedDatabaseBackend(_))
+        ));
+        assert!(matches!(
+            BeaconNodeBackend::<MinimalEthSpec>::open(DatabaseBackend::LevelDb, path),
+            Err(Error::UnsupportedDatabaseBackend(_))
+        ));
+    }
+
     #[test]
     fn memorydb() {
         let store = MemoryStore::open();
@@ -0,0 +1,317 @@
+#![cfg(feature = "redb")]
+use super::*;
+use crate::beacon_node_backend::REDB_DATA_FILENAME;
+use crate::hot_cold_store::HotColdDBError;
+use crate::leveldb_store::BytesKey;
+use parking_lot::{Mutex, RwLock};
+use redb::{Durability, TableDefinition};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+/// All columns are stored in a single table, with keys prefixed by the column (like LevelDB).
+const TABLE: TableDefinition<&[u8], &[u8]> = TableDefinition::new("lighthouse");
+
+/// A wrapped redb database.
+pub struct Redb<E: EthSpec> {
+    /// The database is only written to mutably during compaction, which requires `&mut`.
+    db: RwLock<redb::Database>,
+    path: PathBuf,
+    /// A mutex to synchronise sensitive read-write transactions.
+    transaction_mutex: Mutex<()>,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> Redb<E> {
+    /// Open a database in the directory `path`, creating a new database if one does not already
+    /// exist.
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        std::fs::create_dir_all(path).map_err(|e| Error::DBError {
+            message: format!("Unable to create database directory: {e:?}"),
+        })?;
+        let db_path = path.join(REDB_DATA_FILENAME);
+        let db = redb::Database::create(&db_path)?;
+
+        // Create the table up-front so that read transactions never observe it missing.
+        let txn = db.begin_write()?;
+        txn.open_table(TABLE)?;
+        txn.commit()?;
+
+        Ok(Self {
+            db: RwLock::new(db),
+            path: db_path,
+            transaction_mutex: Mutex::new(()),
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Path to the redb file backing this database.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn put_bytes_with_durability(
+        &self,
+        col: &str,
+        key: &[u8],
+        val: &[u8],
+        durability: Durability,
+    ) -> Result<(), Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter_vec(&metrics::DISK_DB_WRITE_COUNT, &[col]);
+        metrics::inc_counter_vec_by(&metrics::DISK_DB_WRITE_BYTES, &[col], val.len() as u64);
+        let _timer = metrics::start_timer(&metrics::DISK_DB_WRITE_TIMES);
+
+        let mut txn = self.db.read().begin_write()?;
+        txn.set_durability(durability);
+        {
+            let mut table = txn.open_table(TABLE)?;
+            table.insert(column_key.as_slice(), val)?;
+        }
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Iterate over all `(key, value)` pairs from `start_key` while `predicate` holds.
+    fn iter_from_while(
+        &self,
+        start_key: Vec<u8>,
+        predicate: impl Fn(&[u8]) -> bool + 'static,
+    ) -> Box<dyn Iterator<Item = Result<(Vec<u8>, Vec<u8>), Error>>> {
+        let range = self
+            .db
+            .read()
+            .begin_read()
+            .map_err(Error::from)
+            .and_then(|txn| txn.open_table(TABLE).map_err(Error::from))
+            .and_then(|table| {
+                let range: std::ops::RangeFrom<&[u8]> = start_key.as_slice()..;
+                table.range(range).map_err(Error::from)
+            });
+
+        match range {
+            Ok(range) => Box::new(
+                range
+                    .map(|res| {
+                        res.map(|(k, v)| (k.value().to_vec(), v.value().to_vec()))
+                            .map_err(Error::from)
+                    })
+                    .take_while(move |res| match res {
+                        Ok((key, _)) => predicate(key),
+                        Err(_) => true,
+                    }),
+            ),
+            Err(e) => Box::new(std::iter::once(Err(e))),
+        }
+    }
+}
+
+impl<E: EthSpec> KeyValueStore<E> for Redb<E> {
+    /// Store some `value` in `column`, indexed with `key`.
+    fn put_bytes(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        self.put_bytes_with_durability(col, key, val, Durability::Eventual)
+    }
+
+    fn put_bytes_sync(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        self.put_bytes_with_durability(col, key, val, Durability::Immediate)
+    }
+
+    fn sync(&self) -> Result<(), Error> {
+        // Committing an empty transaction with immediate durability flushes all prior commits.
+        let mut txn = self.db.read().begin_write()?;
+        txn.set_durability(Durability::Immediate);
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Retrieve some bytes in `column` with `key`.
+    fn get_bytes(&self, col: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter_vec(&metrics::DISK_DB_READ_COUNT, &[col]);
+        let timer = metrics::start_timer(&metrics::DISK_DB_READ_TIMES);
+
+        let txn = self.db.read().begin_read()?;
+        let table = txn.open_table(TABLE)?;
+        let result = table.get(column_key.as_slice())?.map(|access_guard| {
+            let bytes = access_guard.value().to_vec();
+            metrics::inc_counter_vec_by(&metrics::DISK_DB_READ_BYTES, &[col], bytes.len() as u64);
+            metrics::stop_timer(timer);
+            bytes
+        });
+        Ok(result)
+    }
+
+    /// Return `true` if `key` exists in `column`.
+    fn key_exists(&self, col: &str, key: &[u8]) -> Result<bool, Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter_vec(&metrics::DISK_DB_EXISTS_COUNT, &[col]);
+
+        let txn = self.db.read().begin_read()?;
+        let table = txn.open_table(TABLE)?;
+        Ok(table.get(column_key.as_slice())?.is_some())
+    }
+
+    /// Removes `key` from `column`.
+    fn key_delete(&self, col: &str, key: &[u8]) -> Result<(), Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter_vec(&metrics::DISK_DB_DELETE_COUNT, &[col]);
+
+        let mut txn = self.db.read().begin_write()?;
+        txn.set_durability(Durability::Eventual);
+        {
+            let mut table = txn.open_table(TABLE)?;
+            table.remove(column_key.as_slice())?;
+        }
+        txn.commit()?;
+        Ok(())
+    }
+
+    fn do_atomically(&self, ops_batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        let mut txn = self.db.read().begin_write()?;
+        txn.set_durability(Durability::Eventual);
+        {
+            let mut table = txn.open_table(TABLE)?;
+
+            for op in ops_batch {
+                match op {
+                    KeyValueStoreOp::PutKeyValue(key, value) => {
+                        let col = get_col_from_key(&key).unwrap_or("unknown".to_owned());
+                        metrics::inc_counter_vec(&metrics::DISK_DB_WRITE_COUNT, &[&col]);
+                        metrics::inc_counter_vec_by(
+                            &metrics::DISK_DB_WRITE_BYTES,
+                            &[&col],
+                            value.len() as u64,
+                        );
+
+                        table.insert(key.as_slice(), value.as_slice())?;
+                    }
+
+                    KeyValueStoreOp::DeleteKey(key) => {
+                        let col = get_col_from_key(&key).unwrap_or("unknown".to_owned());
+                        metrics::inc_counter_vec(&metrics::DISK_DB_DELETE_COUNT, &[&col]);
+
+                        table.remove(key.as_slice())?;
+                    }
+                }
+            }
+        }
+
+        let _timer = metrics::start_timer(&metrics::DISK_DB_WRITE_TIMES);
+
+        txn.commit()?;
+        Ok(())
+    }
+
+    fn begin_rw_transaction(&self) -> MutexGuard<()> {
+        self.transaction_mutex.lock()
+    }
+
+    /// redb has no notion of compacting a key range, so the whole database is compacted by
+    /// `Self::compact` instead.
+    fn compact_column(&self, _column: DBColumn) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Compact the whole database.
+    ///
+    /// Compaction is skipped whilst a transaction is in progress (e.g. a long-lived iterator), and
+    /// will be attempted again the next time the database is compacted.
+    fn compact(&self) -> Result<(), Error> {
+        match self.db.write().compact() {
+            Ok(_) | Err(redb::CompactionError::TransactionInProgress) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn iter_column_from<K: Key>(&self, column: DBColumn, from: &[u8]) -> ColumnIter<K> {
+        let start_key = get_key_for_col(column.into(), from);
+
+        Box::new(
+            self.iter_from_while(start_key, move |key| key.starts_with(column.as_bytes()))
+                .map(move |res| {
+                    let (key, value) = res?;
+                    let bytes_key = BytesKey::from_vec(key);
+                    let key = bytes_key.remove_column_variable(column).ok_or_else(|| {
+                        HotColdDBError::IterationError {
+                            unexpected_key: bytes_key.clone(),
+                        }
+                    })?;
+                    Ok((K::from_bytes(key)?, value))
+                }),
+        )
+    }
+
+    fn iter_raw_entries(&self, column: DBColumn, prefix: &[u8]) -> RawEntryIter {
+        let start_key = get_key_for_col(column.into(), prefix);
+        let match_prefix = start_key.clone();
+
+        Box::new(
+            self.iter_from_while(start_key, move |key| key.starts_with(&match_prefix))
+                .map(move |res| {
+                    let (key, value) = res?;
+                    Ok((Vec::from(&key[column.as_bytes().len()..]), value))
+                }),
+        )
+    }
+
+    fn iter_raw_keys(&self, column: DBColumn, prefix: &[u8]) -> RawKeyIter {
+        Box::new(
+            self.iter_raw_entries(column, prefix)
+                .map(|res| res.map(|(key, _)| key)),
+        )
+    }
+
+    /// Iterate through all keys in a particular column.
+    fn iter_column_keys<K: Key>(&self, column: DBColumn) -> ColumnKeyIter<K> {
+        Box::new(self.iter_column(column).map(|res| res.map(|(k, _)| k)))
+    }
+}
+
+impl<E: EthSpec> ItemStore<E> for Redb<E> {}
+
+impl From<redb::Error> for Error {
+    fn from(e: redb::Error) -> Error {
+        Error::DBError {
+            message: format!("{:?}", e),
+        }
+    }
+}
+
+impl From<redb::DatabaseError> for Error {
+    fn from(e: redb::DatabaseError) -> Error {
+        redb::Error::from(e).into()
+    }
+}
+
+impl From<redb::TransactionError> for Error {
+    fn from(e: redb::TransactionError) -> Error {
+        redb::Error::from(e).into()
+    }
+}
+
+impl From<redb::TableError> for Error {
+    fn from(e: redb::TableError) -> Error {
+        redb::Error::from(e).into()
+    }
+}
+
+impl From<redb::StorageError> for Error {
+    fn from(e: redb::StorageError) -> Error {
+        redb::Error::from(e).into()
+    }
+}
+
+impl From<redb::CommitError> for Error {
+    fn from(e: redb::CommitError) -> Error {
+        redb::Error::from(e).into()
+    }
+}
+
+impl From<redb::CompactionError> for Error {
+    fn from(e: redb::CompactionError) -> Error {
+        redb::Error::from(e).into()
+    }
+}
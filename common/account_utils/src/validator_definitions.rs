@@ -82,6 +82,62 @@ pub struct Web3SignerDefinition {
     pub client_identity_password: Option<String>,
 }
 
+/// Configuration of a remote signer that is not Web3Signer (e.g., a signing service fronting an
+/// HSM), reached over HTTPS.
+#[derive(Clone, PartialEq, Serialize, Deserialize, Hash, Eq)]
+pub struct RemoteSignerDefinition {
+    /// The signing endpoint. Requests for all validators using this signer are POSTed here.
+    pub url: String,
+    /// Path to a .pem file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_certificate_path: Option<PathBuf>,
+    /// Specifies a request timeout.
+    ///
+    /// The timeout is applied from when the request starts connecting until the response body has finished.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_timeout_ms: Option<u64>,
+
+    /// Path to a PKCS12 file, presented to the remote signer for mutual TLS.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_identity_path: Option<PathBuf>,
+
+    /// Password for the PKCS12 file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_identity_password: Option<String>,
+}
+
+/// Configuration of a validator whose key is split into threshold shares, one of which is held in
+/// a local keystore.
+///
+/// Signatures are produced by combining the partial signature of the local share with those of
+/// the peer validator clients holding the other shares.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct DistributedDefinition {
+    /// The index of the share held in the local keystore.
+    pub share_index: u64,
+    /// The number of partial signatures required to produce a signature.
+    pub threshold: usize,
+    /// The validator clients holding the other shares.
+    pub peers: Vec<DistributedPeerDefinition>,
+}
+
+/// A validator client holding a share of a distributed validator.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct DistributedPeerDefinition {
+    /// The URL of the peer's validator client HTTP API.
+    pub url: String,
+    /// Path to the API token of the peer's validator client HTTP API.
+    pub api_token_path: PathBuf,
+    pub share_index: u64,
+    pub share_public_key: PublicKey,
+    /// Path to a .pem file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_certificate_path: Option<PathBuf>,
+    /// Specifies a request timeout.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_timeout_ms: Option<u64>,
+}
+
 /// Defines how the validator client should attempt to sign messages for this validator.
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -94,12 +150,19 @@ pub enum SigningDefinition {
         voting_keystore_password_path: Option<PathBuf>,
         #[serde(skip_serializing_if = "Option::is_none")]
         voting_keystore_password: Option<ZeroizeString>,
+        /// Present if the keystore holds a share of a distributed validator, rather than the
+        /// validator's key.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        distributed: Option<DistributedDefinition>,
     },
     /// A validator that defers to a Web3Signer HTTP server for signing.
     ///
     /// https://github.com/ConsenSys/web3signer
     #[serde(rename = "web3signer")]
     Web3Signer(Web3SignerDefinition),
+    /// A validator that defers to some other remote signer for signing.
+    #[serde(rename = "remote_signer")]
+    RemoteSigner(RemoteSignerDefinition),
 }
 
 impl SigningDefinition {
@@ -121,7 +184,7 @@ impl SigningDefinition {
                 .map(Option::Some)
                 .map_err(Error::UnableToReadKeystorePassword),
             SigningDefinition::LocalKeystore { .. } => Err(Error::KeystoreWithoutPassword),
-            SigningDefinition::Web3Signer(_) => Ok(None),
+            SigningDefinition::Web3Signer(_) | SigningDefinition::RemoteSigner(_) => Ok(None),
         }
     }
 
@@ -161,6 +224,9 @@ pub struct ValidatorDefinition {
     pub builder_boost_factor: Option<u64>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_min_bid_gwei: Option<u64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub prefer_builder_proposals: Option<bool>,
     #[serde(default)]
     pub description: String,
@@ -206,11 +272,13 @@ impl ValidatorDefinition {
             gas_limit,
             builder_proposals,
             builder_boost_factor,
+            builder_min_bid_gwei: None,
             prefer_builder_proposals,
             signing_definition: SigningDefinition::LocalKeystore {
                 voting_keystore_path,
                 voting_keystore_password_path,
                 voting_keystore_password,
+                distributed: None,
             },
         })
     }
@@ -283,8 +351,8 @@ impl ValidatorDefinitions {
                     voting_keystore_path,
                     ..
                 } => Some(voting_keystore_path),
-                // A Web3Signer validator does not use a local keystore file.
-                SigningDefinition::Web3Signer { .. } => None,
+                // A remote signer validator does not use a local keystore file.
+                SigningDefinition::Web3Signer { .. } | SigningDefinition::RemoteSigner(_) => None,
             })
             .collect();
 
@@ -356,11 +424,13 @@ impl ValidatorDefinitions {
                     gas_limit: None,
                     builder_proposals: None,
                     builder_boost_factor: None,
+                    builder_min_bid_gwei: None,
                     prefer_builder_proposals: None,
                     signing_definition: SigningDefinition::LocalKeystore {
                         voting_keystore_path,
                         voting_keystore_password_path,
                         voting_keystore_password: None,
+                        distributed: None,
                     },
                 })
             })
@@ -567,6 +637,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn remote_signer_checks() {
+        let remote_signer = r#"---
+        description: ""
+        enabled: true
+        type: remote_signer
+        url: "https://signer.example.com:9000/sign"
+        root_certificate_path: "/certs/ca.pem"
+        client_identity_path: "/certs/client.p12"
+        client_identity_password: "hunter2"
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(remote_signer).unwrap();
+        assert!(
+            def.signing_definition
+                == SigningDefinition::RemoteSigner(RemoteSignerDefinition {
+                    url: "https://signer.example.com:9000/sign".to_string(),
+                    root_certificate_path: Some(PathBuf::from("/certs/ca.pem")),
+                    request_timeout_ms: None,
+                    client_identity_path: Some(PathBuf::from("/certs/client.p12")),
+                    client_identity_password: Some("hunter2".to_string()),
+                })
+        );
+        assert!(def
+            .signing_definition
+            .voting_keystore_password()
+            .unwrap()
+            .is_none());
+
+        let round_trip: ValidatorDefinition =
+            serde_yaml::from_str(&serde_yaml::to_string(&def).unwrap()).unwrap();
+        assert!(round_trip == def);
+    }
+
     #[test]
     fn suggested_fee_recipient_checks() {
         let no_suggested_fee_recipient = r#"---
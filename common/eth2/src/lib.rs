@@ -35,6 +35,7 @@ use std::path::PathBuf;
 use std::time::Duration;
 use store::fork_versioned_response::ExecutionOptimisticFinalizedForkVersionedResponse;
 
+pub const V0: EndpointVersion = EndpointVersion(0);
 pub const V1: EndpointVersion = EndpointVersion(1);
 pub const V2: EndpointVersion = EndpointVersion(2);
 pub const V3: EndpointVersion = EndpointVersion(3);
@@ -818,6 +819,29 @@ impl BeaconNodeHttpClient {
         self.get_opt(path).await
     }
 
+    /// `GET beacon/light_client/updates?start_period,count`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_light_client_updates<E: EthSpec>(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Option<Vec<ForkVersionedResponse<LightClientUpdate<E>>>>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("light_client")
+            .push("updates");
+
+        path.query_pairs_mut()
+            .append_pair("start_period", &start_period.to_string())
+            .append_pair("count", &count.to_string());
+
+        self.get_opt(path).await
+    }
+
     /// `GET beacon/headers?slot,parent_root`
     ///
     /// Returns `Ok(None)` on a 404 error.
@@ -1224,6 +1248,52 @@ impl BeaconNodeHttpClient {
         self.get_opt(path).await
     }
 
+    /// `GET v0/beacon/proof/state/{state_id}?paths`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_proof_state(
+        &self,
+        state_id: StateId,
+        paths: &[String],
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<MerkleProofsData>>, Error> {
+        let mut path = self.eth_path(V0)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("proof")
+            .push("state")
+            .push(&state_id.to_string());
+
+        path.query_pairs_mut()
+            .append_pair("paths", &paths.join(","));
+
+        self.get_opt(path).await
+    }
+
+    /// `GET v0/beacon/proof/block/{block_id}?paths`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_proof_block(
+        &self,
+        block_id: BlockId,
+        paths: &[String],
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<MerkleProofsData>>, Error> {
+        let mut path = self.eth_path(V0)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("proof")
+            .push("block")
+            .push(&block_id.to_string());
+
+        path.query_pairs_mut()
+            .append_pair("paths", &paths.join(","));
+
+        self.get_opt(path).await
+    }
+
     /// `GET v1/beacon/blocks/{block_id}/attestations`
     ///
     /// Returns `Ok(None)` on a 404 error.
@@ -1987,6 +2057,7 @@ impl BeaconNodeHttpClient {
         graffiti: Option<&Graffiti>,
         skip_randao_verification: SkipRandaoVerification,
         builder_booster_factor: Option<u64>,
+        builder_min_bid_gwei: Option<u64>,
     ) -> Result<Url, Error> {
         let mut path = self.eth_path(V3)?;
 
@@ -2014,6 +2085,11 @@ impl BeaconNodeHttpClient {
                 .append_pair("builder_boost_factor", &builder_booster_factor.to_string());
         }
 
+        if let Some(builder_min_bid_gwei) = builder_min_bid_gwei {
+            path.query_pairs_mut()
+                .append_pair("builder_min_bid_gwei", &builder_min_bid_gwei.to_string());
+        }
+
         Ok(path)
     }
 
@@ -2024,6 +2100,7 @@ impl BeaconNodeHttpClient {
         randao_reveal: &SignatureBytes,
         graffiti: Option<&Graffiti>,
         builder_booster_factor: Option<u64>,
+        builder_min_bid_gwei: Option<u64>,
     ) -> Result<(JsonProduceBlockV3Response<E>, ProduceBlockV3Metadata), Error> {
         self.get_validator_blocks_v3_modular(
             slot,
@@ -2031,6 +2108,7 @@ impl BeaconNodeHttpClient {
             graffiti,
             SkipRandaoVerification::No,
             builder_booster_factor,
+            builder_min_bid_gwei,
         )
         .await
     }
@@ -2043,6 +2121,7 @@ impl BeaconNodeHttpClient {
         graffiti: Option<&Graffiti>,
         skip_randao_verification: SkipRandaoVerification,
         builder_booster_factor: Option<u64>,
+        builder_min_bid_gwei: Option<u64>,
     ) -> Result<(JsonProduceBlockV3Response<E>, ProduceBlockV3Metadata), Error> {
         let path = self
             .get_validator_blocks_v3_path(
@@ -2051,6 +2130,7 @@ impl BeaconNodeHttpClient {
                 graffiti,
                 skip_randao_verification,
                 builder_booster_factor,
+                builder_min_bid_gwei,
             )
             .await?;
 
@@ -2093,6 +2173,7 @@ impl BeaconNodeHttpClient {
         randao_reveal: &SignatureBytes,
         graffiti: Option<&Graffiti>,
         builder_booster_factor: Option<u64>,
+        builder_min_bid_gwei: Option<u64>,
     ) -> Result<(ProduceBlockV3Response<E>, ProduceBlockV3Metadata), Error> {
         self.get_validator_blocks_v3_modular_ssz::<E>(
             slot,
@@ -2100,6 +2181,7 @@ impl BeaconNodeHttpClient {
             graffiti,
             SkipRandaoVerification::No,
             builder_booster_factor,
+            builder_min_bid_gwei,
         )
         .await
     }
@@ -2112,6 +2194,7 @@ impl BeaconNodeHttpClient {
         graffiti: Option<&Graffiti>,
         skip_randao_verification: SkipRandaoVerification,
         builder_booster_factor: Option<u64>,
+        builder_min_bid_gwei: Option<u64>,
     ) -> Result<(ProduceBlockV3Response<E>, ProduceBlockV3Metadata), Error> {
         let path = self
             .get_validator_blocks_v3_path(
@@ -2120,6 +2203,7 @@ impl BeaconNodeHttpClient {
                 graffiti,
                 skip_randao_verification,
                 builder_booster_factor,
+                builder_min_bid_gwei,
             )
             .await?;
 
@@ -4,6 +4,7 @@ mod attestation_performance;
 pub mod attestation_rewards;
 mod block_packing_efficiency;
 mod block_rewards;
+mod operation_pool;
 mod standard_block_rewards;
 mod sync_committee_rewards;
 
@@ -17,6 +18,7 @@ use proto_array::core::ProtoArray;
 use serde::{Deserialize, Serialize};
 use ssz::four_byte_option_impl;
 use ssz_derive::{Decode, Encode};
+use std::net::IpAddr;
 use store::{AnchorInfo, BlobInfo, Split, StoreConfig};
 
 pub use attestation_performance::{
@@ -27,7 +29,10 @@ pub use block_packing_efficiency::{
     BlockPackingEfficiency, BlockPackingEfficiencyQuery, ProposerInfo, UniqueAttestation,
 };
 pub use block_rewards::{AttestationRewards, BlockReward, BlockRewardMeta, BlockRewardsQuery};
-pub use lighthouse_network::{types::SyncState, PeerInfo};
+pub use lighthouse_network::{types::SyncState, PeerInfo, PeerPolicy};
+pub use operation_pool::{
+    OperationKind, OperationPoolContents, OperationPoolQuery, OperationPoolStats,
+};
 pub use standard_block_rewards::StandardBlockReward;
 pub use sync_committee_rewards::SyncCommitteeReward;
 
@@ -47,6 +52,68 @@ pub struct Peer<E: EthSpec> {
     pub peer_info: PeerInfo<E>,
 }
 
+/// A banned peer, as returned by `lighthouse/peers/bans`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerBan {
+    pub peer_id: String,
+    pub score: f64,
+    pub client: String,
+    /// The IP addresses the peer has been seen using.
+    pub ip_addresses: Vec<IpAddr>,
+    /// The number of seconds since the peer was banned.
+    pub banned_for: u64,
+    /// The estimated number of seconds until the peer's score decays enough for it to be unbanned.
+    pub expires_in: Option<u64>,
+}
+
+/// The inbound quota of an RPC protocol, as used by `lighthouse/rpc/rate_limits`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcQuota {
+    /// The protocol name, e.g. `beacon_blocks_by_range`.
+    pub protocol: String,
+    /// The maximum number of tokens that can be used in an instantaneous batch.
+    pub max_tokens: u64,
+    /// The number of seconds in which `max_tokens` are fully replenished.
+    pub replenish_all_every: u64,
+}
+
+/// The multiplier applied to the inbound RPC quotas of a peer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcPeerMultiplier {
+    pub peer_id: String,
+    /// The multiplier, or `None` to remove it when updating.
+    pub multiplier: Option<f64>,
+}
+
+/// The number of inbound requests from a connected peer rejected by the rate limiter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcRejectedRequests {
+    pub peer_id: String,
+    pub protocol: String,
+    pub count: u64,
+}
+
+/// The inbound RPC rate limits, as returned by `lighthouse/rpc/rate_limits`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcRateLimits {
+    /// Whether inbound rate limiting is enabled.
+    pub enabled: bool,
+    pub quotas: Vec<RpcQuota>,
+    pub peer_multipliers: Vec<RpcPeerMultiplier>,
+    pub rejected_requests: Vec<RpcRejectedRequests>,
+}
+
+/// An update of the inbound RPC rate limits, as accepted by `POST lighthouse/rpc/rate_limits`.
+///
+/// Protocols and peers which are not listed are left unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RpcRateLimitsUpdate {
+    #[serde(default)]
+    pub quotas: Vec<RpcQuota>,
+    #[serde(default)]
+    pub peer_multipliers: Vec<RpcPeerMultiplier>,
+}
+
 /// The results of validators voting during an epoch.
 ///
 /// Provides information about the current and previous epochs.
@@ -398,6 +465,107 @@ impl BeaconNodeHttpClient {
      * fairly simply achieved, if desired.
      */
 
+    /// `GET lighthouse/peers/bans`
+    pub async fn get_lighthouse_peers_bans(&self) -> Result<GenericResponse<Vec<PeerBan>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("bans");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/peers/bans/clear`
+    pub async fn post_lighthouse_peers_bans_clear(&self) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("bans")
+            .push("clear");
+
+        self.post(path, &()).await
+    }
+
+    /// `GET lighthouse/peers/policy`
+    pub async fn get_lighthouse_peers_policy(&self) -> Result<GenericResponse<PeerPolicy>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("policy");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/peers/policy`
+    pub async fn post_lighthouse_peers_policy(
+        &self,
+        peer_policy: &PeerPolicy,
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("policy");
+
+        self.post(path, peer_policy).await
+    }
+
+    /// `POST lighthouse/peers/policy/reload`
+    pub async fn post_lighthouse_peers_policy_reload(&self) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("policy")
+            .push("reload");
+
+        self.post(path, &()).await
+    }
+
+    /// `GET lighthouse/rpc/rate_limits`
+    pub async fn get_lighthouse_rpc_rate_limits(
+        &self,
+    ) -> Result<GenericResponse<RpcRateLimits>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("rpc")
+            .push("rate_limits");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/rpc/rate_limits`
+    pub async fn post_lighthouse_rpc_rate_limits(
+        &self,
+        update: &RpcRateLimitsUpdate,
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("rpc")
+            .push("rate_limits");
+
+        self.post(path, update).await
+    }
+
     /// `GET lighthouse/proto_array`
     pub async fn get_lighthouse_proto_array(&self) -> Result<GenericResponse<ProtoArray>, Error> {
         let mut path = self.server.full.clone();
@@ -574,6 +742,40 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/operation_pool?kind,slot,validator_index`
+    pub async fn get_lighthouse_operation_pool<E: EthSpec>(
+        &self,
+        kinds: Option<&[OperationKind]>,
+        slot: Option<Slot>,
+        validator_index: Option<u64>,
+    ) -> Result<OperationPoolContents<E>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("operation_pool");
+
+        if let Some(kinds) = kinds {
+            let kinds = kinds
+                .iter()
+                .map(OperationKind::as_str)
+                .collect::<Vec<_>>()
+                .join(",");
+            path.query_pairs_mut().append_pair("kind", &kinds);
+        }
+        if let Some(slot) = slot {
+            path.query_pairs_mut()
+                .append_pair("slot", &slot.to_string());
+        }
+        if let Some(validator_index) = validator_index {
+            path.query_pairs_mut()
+                .append_pair("validator_index", &validator_index.to_string());
+        }
+
+        self.get(path).await
+    }
+
     /// `GET` lighthouse/analysis/attestation_performance/{index}?start_epoch,end_epoch
     pub async fn get_lighthouse_analysis_attestation_performance(
         &self,
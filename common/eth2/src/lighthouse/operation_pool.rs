@@ -0,0 +1,109 @@
+use crate::types::option_query_vec;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use types::{
+    Attestation, AttesterSlashing, EthSpec, ProposerSlashing, SignedBlsToExecutionChange,
+    SignedVoluntaryExit, Slot, SyncCommitteeContribution,
+};
+
+/// A kind of operation held by the operation pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationKind {
+    Attestations,
+    SyncContributions,
+    AttesterSlashings,
+    ProposerSlashings,
+    VoluntaryExits,
+    BlsToExecutionChanges,
+}
+
+impl OperationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OperationKind::Attestations => "attestations",
+            OperationKind::SyncContributions => "sync_contributions",
+            OperationKind::AttesterSlashings => "attester_slashings",
+            OperationKind::ProposerSlashings => "proposer_slashings",
+            OperationKind::VoluntaryExits => "voluntary_exits",
+            OperationKind::BlsToExecutionChanges => "bls_to_execution_changes",
+        }
+    }
+}
+
+impl FromStr for OperationKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "attestations" => Ok(OperationKind::Attestations),
+            "sync_contributions" => Ok(OperationKind::SyncContributions),
+            "attester_slashings" => Ok(OperationKind::AttesterSlashings),
+            "proposer_slashings" => Ok(OperationKind::ProposerSlashings),
+            "voluntary_exits" => Ok(OperationKind::VoluntaryExits),
+            "bls_to_execution_changes" => Ok(OperationKind::BlsToExecutionChanges),
+            other => Err(format!("{} is not a valid operation kind", other)),
+        }
+    }
+}
+
+impl fmt::Display for OperationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Filters for the contents returned by `GET lighthouse/operation_pool`.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+pub struct OperationPoolQuery {
+    /// Only return operations of these kinds, or all kinds if `None`.
+    #[serde(default, deserialize_with = "option_query_vec")]
+    pub kind: Option<Vec<OperationKind>>,
+    /// Only return attestations and sync contributions for this slot.
+    pub slot: Option<Slot>,
+    /// Only return slashings, exits and BLS to execution changes for this validator.
+    pub validator_index: Option<u64>,
+}
+
+impl OperationPoolQuery {
+    pub fn includes(&self, kind: OperationKind) -> bool {
+        self.kind
+            .as_ref()
+            .map_or(true, |kinds| kinds.contains(&kind))
+    }
+}
+
+/// The number of operations of each kind held by the operation pool, regardless of any filters.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationPoolStats {
+    /// Aggregated attestations held for block inclusion.
+    pub attestations: usize,
+    /// Unique `AttestationData` of the aggregated attestations.
+    pub attestation_data: usize,
+    /// Unaggregated attestations, aggregated by `AttestationData`.
+    pub naive_attestations: usize,
+    /// Sync contributions held for block inclusion.
+    pub sync_contributions: usize,
+    /// Unaggregated sync committee messages, aggregated by subcommittee.
+    pub naive_sync_contributions: usize,
+    pub attester_slashings: usize,
+    pub proposer_slashings: usize,
+    pub voluntary_exits: usize,
+    pub bls_to_execution_changes: usize,
+}
+
+/// The contents of the operation pool, as returned by `GET lighthouse/operation_pool`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "E: EthSpec")]
+pub struct OperationPoolContents<E: EthSpec> {
+    pub stats: OperationPoolStats,
+    /// Both aggregated and unaggregated attestations.
+    pub attestations: Vec<Attestation<E>>,
+    /// Both aggregated and unaggregated sync contributions.
+    pub sync_contributions: Vec<SyncCommitteeContribution<E>>,
+    pub attester_slashings: Vec<AttesterSlashing<E>>,
+    pub proposer_slashings: Vec<ProposerSlashing>,
+    pub voluntary_exits: Vec<SignedVoluntaryExit>,
+    pub bls_to_execution_changes: Vec<SignedBlsToExecutionChange>,
+}
@@ -412,6 +412,74 @@ impl ValidatorClientHttpClient {
         self.post(path, &request).await
     }
 
+    /// `POST lighthouse/distributed/sign`
+    ///
+    /// `request` is a `PartialSignatureRequest`, which may already be encoded as JSON.
+    pub async fn post_lighthouse_distributed_sign<T: Serialize>(
+        &self,
+        request: &T,
+    ) -> Result<GenericResponse<PartialSignatureResponse>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("distributed")
+            .push("sign");
+
+        self.post(path, &request).await
+    }
+
+    /// `GET lighthouse/beacon/health`
+    pub async fn get_lighthouse_beacon_health(
+        &self,
+    ) -> Result<GenericResponse<BeaconNodeHealthResponse>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon")
+            .push("health");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/signing_audit_log`
+    pub async fn get_lighthouse_signing_audit_log(
+        &self,
+        query: &SigningAuditLogQuery,
+    ) -> Result<GenericResponse<SigningAuditLogResponse>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("signing_audit_log");
+
+        if let Some(pubkey) = &query.pubkey {
+            path.query_pairs_mut()
+                .append_pair("pubkey", &pubkey.to_string());
+        }
+        if let Some(message_type) = &query.message_type {
+            let message_type = serde_json::to_value(message_type).map_err(Error::InvalidJson)?;
+            if let Some(message_type) = message_type.as_str() {
+                path.query_pairs_mut()
+                    .append_pair("message_type", message_type);
+            }
+        }
+        if let Some(start_index) = query.start_index {
+            path.query_pairs_mut()
+                .append_pair("start_index", &start_index.to_string());
+        }
+        if let Some(limit) = query.limit {
+            path.query_pairs_mut()
+                .append_pair("limit", &limit.to_string());
+        }
+
+        self.get(path).await
+    }
+
     /// `PATCH lighthouse/validators/{validator_pubkey}`
     #[allow(clippy::too_many_arguments)]
     pub async fn patch_lighthouse_validators(
@@ -421,6 +489,7 @@ impl ValidatorClientHttpClient {
         gas_limit: Option<u64>,
         builder_proposals: Option<bool>,
         builder_boost_factor: Option<u64>,
+        builder_min_bid_gwei: Option<u64>,
         prefer_builder_proposals: Option<bool>,
         graffiti: Option<GraffitiString>,
     ) -> Result<(), Error> {
@@ -439,6 +508,7 @@ impl ValidatorClientHttpClient {
                 gas_limit,
                 builder_proposals,
                 builder_boost_factor,
+                builder_min_bid_gwei,
                 prefer_builder_proposals,
                 graffiti,
             },
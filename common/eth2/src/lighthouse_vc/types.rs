@@ -7,6 +7,10 @@ use std::path::PathBuf;
 pub use crate::lighthouse::Health;
 pub use crate::lighthouse_vc::std_types::*;
 pub use crate::types::{GenericResponse, VersionData};
+pub use signing_audit_log::{
+    Head as SigningAuditLogHead, MessageType as SigningAuditLogMessageType,
+    Query as SigningAuditLogQuery, Record as SigningAuditLogRecord,
+};
 pub use types::*;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -97,6 +101,9 @@ pub struct ValidatorPatchRequest {
     pub builder_boost_factor: Option<u64>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_min_bid_gwei: Option<u64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub prefer_builder_proposals: Option<bool>,
 }
 
@@ -197,3 +204,90 @@ pub struct SingleExportKeystoresResponse {
 pub struct SetGraffitiRequest {
     pub graffiti: GraffitiString,
 }
+
+/// A message to be signed by a share of a distributed validator.
+///
+/// The full message is sent, rather than its signing root, so that the validator client holding
+/// the share can check it against its own slashing protection and compute the signing root itself.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "E: EthSpec", rename_all = "snake_case")]
+pub enum PartialSignatureMessage<E: EthSpec> {
+    AggregationSlot {
+        slot: Slot,
+    },
+    AggregateAndProof(AggregateAndProof<E>),
+    Attestation(AttestationData),
+    /// A block is signed via its header, which has the same signing root.
+    BeaconBlock {
+        block_header: BeaconBlockHeader,
+    },
+    RandaoReveal {
+        epoch: Epoch,
+    },
+    VoluntaryExit(VoluntaryExit),
+    SyncCommitteeMessage {
+        beacon_block_root: Hash256,
+        slot: Slot,
+    },
+    SyncAggregatorSelectionData(SyncAggregatorSelectionData),
+    ContributionAndProof(ContributionAndProof<E>),
+    ValidatorRegistration(ValidatorRegistrationData),
+}
+
+/// The fork a `PartialSignatureMessage` is signed for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartialSignatureForkInfo {
+    pub fork: Fork,
+    pub genesis_validators_root: Hash256,
+}
+
+/// A request for a partial signature over `message` by the share of the distributed validator
+/// `pubkey` held by a validator client.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "E: EthSpec")]
+pub struct PartialSignatureRequest<E: EthSpec> {
+    pub pubkey: PublicKeyBytes,
+    /// The fork the message is signed for, which must match that of the validator client. Omitted
+    /// for validator registrations, which are not signed for any fork.
+    pub fork_info: Option<PartialSignatureForkInfo>,
+    pub message: PartialSignatureMessage<E>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartialSignatureResponse {
+    pub signing_root: Hash256,
+    pub signature: Signature,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SigningAuditLogResponse {
+    /// The latest record in the log, which may be stored elsewhere for use as a verification
+    /// anchor.
+    pub head: Option<SigningAuditLogHead>,
+    pub records: Vec<SigningAuditLogRecord>,
+}
+
+/// A beacon node as ranked by the validator client, most preferred first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeaconNodeHealthData {
+    /// The position of the node in the order it was supplied on the command line.
+    pub index: usize,
+    pub endpoint: String,
+    /// The status of the node, e.g. `synced`, `not_synced` or `offline`.
+    pub status: String,
+    /// The health score of the node, lower is better. `None` if the node has not been ranked yet.
+    pub score: Option<u64>,
+    pub head_slot: Option<Slot>,
+    pub sync_distance: Option<Slot>,
+    pub is_optimistic: Option<bool>,
+    pub el_offline: Option<bool>,
+    pub latency_millis: Option<u64>,
+    /// The fraction of recent requests to the node which failed.
+    pub error_rate: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeaconNodeHealthResponse {
+    pub beacon_nodes: Vec<BeaconNodeHealthData>,
+    pub proposer_nodes: Vec<BeaconNodeHealthData>,
+}
@@ -488,6 +488,12 @@ pub struct RandaoQuery {
     pub epoch: Option<Epoch>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct LightClientUpdatesQuery {
+    pub start_period: u64,
+    pub count: u64,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct AttestationPoolQuery {
     pub slot: Option<Slot>,
@@ -610,6 +616,50 @@ pub struct ExpectedWithdrawalsQuery {
     pub proposal_slot: Option<Slot>,
 }
 
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MerkleProofQuery {
+    #[serde(deserialize_with = "query_vec")]
+    pub paths: Vec<String>,
+}
+
+/// A proof that `leaf` is the node at generalized index `gindex` in the tree of some root.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// The path of the proven field, e.g. `validators.5.effective_balance`.
+    pub path: String,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub gindex: u64,
+    pub leaf: Hash256,
+    /// Sibling nodes from the leaf up to the root.
+    pub branch: Vec<Hash256>,
+}
+
+impl MerkleProof {
+    /// Returns `true` if the proof is valid for the tree rooted at `root`.
+    pub fn verify(&self, root: Hash256) -> bool {
+        if self.gindex == 0 {
+            return false;
+        }
+        let depth = self.gindex.ilog2();
+        let index = self.gindex - (1 << depth);
+        merkle_proof::verify_merkle_proof(
+            self.leaf,
+            &self.branch,
+            depth as usize,
+            index as usize,
+            root,
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MerkleProofsData {
+    /// The state or block root which all of the proofs are against.
+    pub root: Hash256,
+    pub proofs: Vec<MerkleProof>,
+}
+
 #[derive(Clone, PartialEq, Debug, Deserialize)]
 #[serde(try_from = "String", bound = "T: FromStr")]
 pub struct QueryVec<T: FromStr> {
@@ -625,7 +675,7 @@ where
     Ok(Vec::from(QueryVec::from(vec)))
 }
 
-fn option_query_vec<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+pub(crate) fn option_query_vec<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
 where
     D: serde::Deserializer<'de>,
     T: FromStr,
@@ -745,6 +795,7 @@ pub struct ValidatorBlocksQuery {
     pub graffiti: Option<Graffiti>,
     pub skip_randao_verification: SkipRandaoVerification,
     pub builder_boost_factor: Option<u64>,
+    pub builder_min_bid_gwei: Option<u64>,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
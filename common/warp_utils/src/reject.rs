@@ -136,6 +136,15 @@ pub fn invalid_auth(msg: String) -> warp::reject::Rejection {
     warp::reject::custom(InvalidAuthorization(msg))
 }
 
+#[derive(Debug)]
+pub struct TooManyRequests(pub String);
+
+impl Reject for TooManyRequests {}
+
+pub fn too_many_requests(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(TooManyRequests(msg))
+}
+
 #[derive(Debug)]
 pub struct UnsupportedMediaType(pub String);
 
@@ -228,6 +237,9 @@ pub async fn handle_rejection(err: warp::Rejection) -> Result<impl warp::Reply,
     } else if let Some(e) = err.find::<crate::reject::InvalidAuthorization>() {
         code = StatusCode::FORBIDDEN;
         message = format!("FORBIDDEN: Invalid auth token: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::TooManyRequests>() {
+        code = StatusCode::TOO_MANY_REQUESTS;
+        message = format!("TOO_MANY_REQUESTS: {}", e.0);
     } else if let Some(e) = err.find::<warp::reject::MissingHeader>() {
         if e.name().eq("Authorization") {
             code = StatusCode::UNAUTHORIZED;
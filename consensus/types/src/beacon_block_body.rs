@@ -143,31 +143,41 @@ impl<'a, E: EthSpec, Payload: AbstractExecPayload<E>> BeaconBlockBodyRef<'a, E,
     }
 
     fn body_merkle_leaves(&self) -> Vec<Hash256> {
+        self.field_roots()
+            .into_iter()
+            .map(|(_, root)| root)
+            .collect()
+    }
+
+    /// Returns the name and tree hash root of each field of the body, in order.
+    ///
+    /// These are the leaves of the tree whose root is the body root.
+    pub fn field_roots(&self) -> Vec<(&'static str, Hash256)> {
         let mut leaves = vec![];
         match self {
             Self::Base(body) => {
-                beacon_block_body_base_fields!(body, |_, field| leaves
-                    .push(field.tree_hash_root()));
+                beacon_block_body_base_fields!(body, |name, field| leaves
+                    .push((name, field.tree_hash_root())));
             }
             Self::Altair(body) => {
-                beacon_block_body_altair_fields!(body, |_, field| leaves
-                    .push(field.tree_hash_root()));
+                beacon_block_body_altair_fields!(body, |name, field| leaves
+                    .push((name, field.tree_hash_root())));
             }
             Self::Bellatrix(body) => {
-                beacon_block_body_bellatrix_fields!(body, |_, field| leaves
-                    .push(field.tree_hash_root()));
+                beacon_block_body_bellatrix_fields!(body, |name, field| leaves
+                    .push((name, field.tree_hash_root())));
             }
             Self::Capella(body) => {
-                beacon_block_body_capella_fields!(body, |_, field| leaves
-                    .push(field.tree_hash_root()));
+                beacon_block_body_capella_fields!(body, |name, field| leaves
+                    .push((name, field.tree_hash_root())));
             }
             Self::Deneb(body) => {
-                beacon_block_body_deneb_fields!(body, |_, field| leaves
-                    .push(field.tree_hash_root()));
+                beacon_block_body_deneb_fields!(body, |name, field| leaves
+                    .push((name, field.tree_hash_root())));
             }
             Self::Electra(body) => {
-                beacon_block_body_electra_fields!(body, |_, field| leaves
-                    .push(field.tree_hash_root()));
+                beacon_block_body_electra_fields!(body, |name, field| leaves
+                    .push((name, field.tree_hash_root())));
             }
         }
         leaves
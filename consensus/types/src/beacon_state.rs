@@ -2480,6 +2480,47 @@ impl<E: EthSpec> BeaconState<E> {
         Ok(())
     }
 
+    /// Returns the name and tree hash root of each top-level field of the state, in order.
+    ///
+    /// These are the leaves of the tree whose root is the state root.
+    pub fn field_roots(&self) -> Vec<(&'static str, Hash256)> {
+        let mut leaves = vec![];
+        #[allow(clippy::arithmetic_side_effects)]
+        match self {
+            BeaconState::Base(state) => {
+                map_beacon_state_base_fields!(state, |name, field| {
+                    leaves.push((name, field.tree_hash_root()));
+                });
+            }
+            BeaconState::Altair(state) => {
+                map_beacon_state_altair_fields!(state, |name, field| {
+                    leaves.push((name, field.tree_hash_root()));
+                });
+            }
+            BeaconState::Bellatrix(state) => {
+                map_beacon_state_bellatrix_fields!(state, |name, field| {
+                    leaves.push((name, field.tree_hash_root()));
+                });
+            }
+            BeaconState::Capella(state) => {
+                map_beacon_state_capella_fields!(state, |name, field| {
+                    leaves.push((name, field.tree_hash_root()));
+                });
+            }
+            BeaconState::Deneb(state) => {
+                map_beacon_state_deneb_fields!(state, |name, field| {
+                    leaves.push((name, field.tree_hash_root()));
+                });
+            }
+            BeaconState::Electra(state) => {
+                map_beacon_state_electra_fields!(state, |name, field| {
+                    leaves.push((name, field.tree_hash_root()));
+                });
+            }
+        };
+        leaves
+    }
+
     pub fn compute_merkle_proof(&self, generalized_index: usize) -> Result<Vec<Hash256>, Error> {
         // 1. Convert generalized index to field index.
         let field_index = match generalized_index {
@@ -2507,40 +2548,11 @@ impl<E: EthSpec> BeaconState<E> {
         };
 
         // 2. Get all `BeaconState` leaves.
-        let mut leaves = vec![];
-        #[allow(clippy::arithmetic_side_effects)]
-        match self {
-            BeaconState::Base(state) => {
-                map_beacon_state_base_fields!(state, |_, field| {
-                    leaves.push(field.tree_hash_root());
-                });
-            }
-            BeaconState::Altair(state) => {
-                map_beacon_state_altair_fields!(state, |_, field| {
-                    leaves.push(field.tree_hash_root());
-                });
-            }
-            BeaconState::Bellatrix(state) => {
-                map_beacon_state_bellatrix_fields!(state, |_, field| {
-                    leaves.push(field.tree_hash_root());
-                });
-            }
-            BeaconState::Capella(state) => {
-                map_beacon_state_capella_fields!(state, |_, field| {
-                    leaves.push(field.tree_hash_root());
-                });
-            }
-            BeaconState::Deneb(state) => {
-                map_beacon_state_deneb_fields!(state, |_, field| {
-                    leaves.push(field.tree_hash_root());
-                });
-            }
-            BeaconState::Electra(state) => {
-                map_beacon_state_electra_fields!(state, |_, field| {
-                    leaves.push(field.tree_hash_root());
-                });
-            }
-        };
+        let leaves = self
+            .field_roots()
+            .into_iter()
+            .map(|(_, root)| root)
+            .collect::<Vec<_>>();
 
         // 3. Make deposit tree.
         // Use the depth of the `BeaconState` fields (i.e. `log2(32) = 5`).
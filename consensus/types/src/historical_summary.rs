@@ -39,4 +39,12 @@ impl HistoricalSummary {
             state_summary_root: state.state_roots().tree_hash_root(),
         }
     }
+
+    pub fn block_summary_root(&self) -> Hash256 {
+        self.block_summary_root
+    }
+
+    pub fn state_summary_root(&self) -> Hash256 {
+        self.state_summary_root
+    }
 }
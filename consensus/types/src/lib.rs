@@ -203,6 +203,7 @@ pub use crate::light_client_optimistic_update::{
 pub use crate::light_client_update::{
     Error as LightClientError, LightClientUpdate, LightClientUpdateAltair,
     LightClientUpdateCapella, LightClientUpdateDeneb, LightClientUpdateElectra,
+    MAX_REQUEST_LIGHT_CLIENT_UPDATES,
 };
 pub use crate::participation_flags::ParticipationFlags;
 pub use crate::payload::{
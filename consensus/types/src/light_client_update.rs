@@ -1,22 +1,21 @@
 use super::{EthSpec, FixedVector, Hash256, Slot, SyncAggregate, SyncCommittee};
 use crate::light_client_header::LightClientHeaderElectra;
 use crate::{
-    beacon_state, test_utils::TestRandom, BeaconBlock, BeaconBlockHeader, BeaconState, ChainSpec,
-    ForkName, ForkVersionDeserialize, LightClientHeaderAltair, LightClientHeaderCapella,
-    LightClientHeaderDeneb, SignedBeaconBlock,
+    beacon_state, test_utils::TestRandom, ChainSpec, ForkName, ForkVersionDeserialize,
+    LightClientHeader, LightClientHeaderAltair, LightClientHeaderCapella, LightClientHeaderDeneb,
+    SignedBeaconBlock,
 };
 use derivative::Derivative;
-use safe_arith::ArithError;
+use safe_arith::{ArithError, SafeArith};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
-use ssz::Decode;
+use ssz::{Decode, Encode};
 use ssz_derive::Decode;
 use ssz_derive::Encode;
 use ssz_types::typenum::{U4, U5, U6};
 use std::sync::Arc;
 use superstruct::superstruct;
 use test_random_derive::TestRandom;
-use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
 
 pub const FINALIZED_ROOT_INDEX: usize = 105;
@@ -35,6 +34,10 @@ pub const CURRENT_SYNC_COMMITTEE_PROOF_LEN: usize = 5;
 pub const NEXT_SYNC_COMMITTEE_PROOF_LEN: usize = 5;
 pub const EXECUTION_PAYLOAD_PROOF_LEN: usize = 4;
 
+/// The maximum number of updates that may be requested in a single `LightClientUpdatesByRange`
+/// request.
+pub const MAX_REQUEST_LIGHT_CLIENT_UPDATES: u64 = 128;
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Error {
     SszTypesError(ssz_types::Error),
@@ -152,44 +155,35 @@ impl<E: EthSpec> ForkVersionDeserialize for LightClientUpdate<E> {
 }
 
 impl<E: EthSpec> LightClientUpdate<E> {
+    /// Construct an update from the block at `signature_slot`, its parent (the attested block)
+    /// and the block finalized by the attested block's post-state.
+    ///
+    /// The sync committee and proofs are taken from the attested block's post-state.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        beacon_state: BeaconState<E>,
-        block: BeaconBlock<E>,
-        attested_state: &mut BeaconState<E>,
+        sync_aggregate: &SyncAggregate<E>,
+        signature_slot: Slot,
+        next_sync_committee: Arc<SyncCommittee<E>>,
+        next_sync_committee_branch: FixedVector<Hash256, NextSyncCommitteeProofLen>,
+        finality_branch: FixedVector<Hash256, FinalizedRootProofLen>,
         attested_block: &SignedBeaconBlock<E>,
         finalized_block: &SignedBeaconBlock<E>,
         chain_spec: &ChainSpec,
     ) -> Result<Self, Error> {
-        let sync_aggregate = block.body().sync_aggregate()?;
         if sync_aggregate.num_set_bits() < chain_spec.min_sync_committee_participants as usize {
             return Err(Error::NotEnoughSyncCommitteeParticipants);
         }
 
-        let signature_period = block.epoch().sync_committee_period(chain_spec)?;
-        // Compute and validate attested header.
-        let mut attested_header = attested_state.latest_block_header().clone();
-        attested_header.state_root = attested_state.update_tree_hash_cache()?;
-        let attested_period = attested_header
-            .slot
+        let signature_period = signature_slot
+            .epoch(E::slots_per_epoch())
+            .sync_committee_period(chain_spec)?;
+        let attested_period = attested_block
+            .slot()
             .epoch(E::slots_per_epoch())
             .sync_committee_period(chain_spec)?;
         if attested_period != signature_period {
             return Err(Error::MismatchingPeriods);
         }
-        // Build finalized header from finalized block
-        let finalized_header = BeaconBlockHeader {
-            slot: finalized_block.slot(),
-            proposer_index: finalized_block.message().proposer_index(),
-            parent_root: finalized_block.parent_root(),
-            state_root: finalized_block.state_root(),
-            body_root: finalized_block.message().body_root(),
-        };
-        if finalized_header.tree_hash_root() != beacon_state.finalized_checkpoint().root {
-            return Err(Error::InvalidFinalizedBlock);
-        }
-        let next_sync_committee_branch =
-            attested_state.compute_merkle_proof(NEXT_SYNC_COMMITTEE_INDEX)?;
-        let finality_branch = attested_state.compute_merkle_proof(FINALIZED_ROOT_INDEX)?;
 
         let light_client_update = match attested_block
             .fork_name(chain_spec)
@@ -203,12 +197,12 @@ impl<E: EthSpec> LightClientUpdate<E> {
                     LightClientHeaderAltair::block_to_light_client_header(finalized_block)?;
                 Self::Altair(LightClientUpdateAltair {
                     attested_header,
-                    next_sync_committee: attested_state.next_sync_committee()?.clone(),
-                    next_sync_committee_branch: FixedVector::new(next_sync_committee_branch)?,
+                    next_sync_committee,
+                    next_sync_committee_branch,
                     finalized_header,
-                    finality_branch: FixedVector::new(finality_branch)?,
+                    finality_branch,
                     sync_aggregate: sync_aggregate.clone(),
-                    signature_slot: block.slot(),
+                    signature_slot,
                 })
             }
             ForkName::Capella => {
@@ -218,12 +212,12 @@ impl<E: EthSpec> LightClientUpdate<E> {
                     LightClientHeaderCapella::block_to_light_client_header(finalized_block)?;
                 Self::Capella(LightClientUpdateCapella {
                     attested_header,
-                    next_sync_committee: attested_state.next_sync_committee()?.clone(),
-                    next_sync_committee_branch: FixedVector::new(next_sync_committee_branch)?,
+                    next_sync_committee,
+                    next_sync_committee_branch,
                     finalized_header,
-                    finality_branch: FixedVector::new(finality_branch)?,
+                    finality_branch,
                     sync_aggregate: sync_aggregate.clone(),
-                    signature_slot: block.slot(),
+                    signature_slot,
                 })
             }
             ForkName::Deneb => {
@@ -233,12 +227,12 @@ impl<E: EthSpec> LightClientUpdate<E> {
                     LightClientHeaderDeneb::block_to_light_client_header(finalized_block)?;
                 Self::Deneb(LightClientUpdateDeneb {
                     attested_header,
-                    next_sync_committee: attested_state.next_sync_committee()?.clone(),
-                    next_sync_committee_branch: FixedVector::new(next_sync_committee_branch)?,
+                    next_sync_committee,
+                    next_sync_committee_branch,
                     finalized_header,
-                    finality_branch: FixedVector::new(finality_branch)?,
+                    finality_branch,
                     sync_aggregate: sync_aggregate.clone(),
-                    signature_slot: block.slot(),
+                    signature_slot,
                 })
             }
             ForkName::Electra => {
@@ -248,12 +242,12 @@ impl<E: EthSpec> LightClientUpdate<E> {
                     LightClientHeaderElectra::block_to_light_client_header(finalized_block)?;
                 Self::Electra(LightClientUpdateElectra {
                     attested_header,
-                    next_sync_committee: attested_state.next_sync_committee()?.clone(),
-                    next_sync_committee_branch: FixedVector::new(next_sync_committee_branch)?,
+                    next_sync_committee,
+                    next_sync_committee_branch,
                     finalized_header,
-                    finality_branch: FixedVector::new(finality_branch)?,
+                    finality_branch,
                     sync_aggregate: sync_aggregate.clone(),
-                    signature_slot: block.slot(),
+                    signature_slot,
                 })
             } // To add a new fork, just append the new fork variant on the latest fork. Forks that
               // have a distinct execution header will need a new LightClientUdpate variant only
@@ -263,6 +257,113 @@ impl<E: EthSpec> LightClientUpdate<E> {
         Ok(light_client_update)
     }
 
+    pub fn map_with_fork_name<F, R>(&self, func: F) -> R
+    where
+        F: Fn(ForkName) -> R,
+    {
+        match self {
+            Self::Altair(_) => func(ForkName::Altair),
+            Self::Capella(_) => func(ForkName::Capella),
+            Self::Deneb(_) => func(ForkName::Deneb),
+            Self::Electra(_) => func(ForkName::Electra),
+        }
+    }
+
+    pub fn attested_header_slot<'a>(&'a self) -> Slot {
+        map_light_client_update_ref!(&'a _, self.to_ref(), |inner, cons| {
+            cons(inner);
+            inner.attested_header.beacon.slot
+        })
+    }
+
+    pub fn finalized_header_slot<'a>(&'a self) -> Slot {
+        map_light_client_update_ref!(&'a _, self.to_ref(), |inner, cons| {
+            cons(inner);
+            inner.finalized_header.beacon.slot
+        })
+    }
+
+    /// Return `true` if `new` is a better update than `self`, according to the spec's
+    /// `is_better_update`.
+    ///
+    /// ref: https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/light-client/sync-protocol.md#is_better_update
+    pub fn is_better_light_client_update(
+        &self,
+        new: &Self,
+        chain_spec: &ChainSpec,
+    ) -> Result<bool, Error> {
+        let period_at_slot = |slot: Slot| {
+            slot.epoch(E::slots_per_epoch())
+                .sync_committee_period(chain_spec)
+        };
+
+        // Compare super majority (> 2/3) sync committee participation.
+        let max_active_participants = new.sync_aggregate().sync_committee_bits.len();
+        let new_active_participants = new.sync_aggregate().num_set_bits();
+        let old_active_participants = self.sync_aggregate().num_set_bits();
+        let new_has_supermajority =
+            new_active_participants.safe_mul(3)? >= max_active_participants.safe_mul(2)?;
+        let old_has_supermajority =
+            old_active_participants.safe_mul(3)? >= max_active_participants.safe_mul(2)?;
+        if new_has_supermajority != old_has_supermajority {
+            return Ok(new_has_supermajority);
+        }
+        if !new_has_supermajority && new_active_participants != old_active_participants {
+            return Ok(new_active_participants > old_active_participants);
+        }
+
+        // Compare presence of relevant sync committee.
+        let new_has_relevant_sync_committee = new.is_sync_committee_update()
+            && period_at_slot(new.attested_header_slot())?
+                == period_at_slot(*new.signature_slot())?;
+        let old_has_relevant_sync_committee = self.is_sync_committee_update()
+            && period_at_slot(self.attested_header_slot())?
+                == period_at_slot(*self.signature_slot())?;
+        if new_has_relevant_sync_committee != old_has_relevant_sync_committee {
+            return Ok(new_has_relevant_sync_committee);
+        }
+
+        // Compare indication of any finality.
+        let new_has_finality = new.is_finality_update();
+        let old_has_finality = self.is_finality_update();
+        if new_has_finality != old_has_finality {
+            return Ok(new_has_finality);
+        }
+
+        // Compare sync committee finality.
+        if new_has_finality {
+            let new_has_sync_committee_finality = period_at_slot(new.finalized_header_slot())?
+                == period_at_slot(new.attested_header_slot())?;
+            let old_has_sync_committee_finality = period_at_slot(self.finalized_header_slot())?
+                == period_at_slot(self.attested_header_slot())?;
+            if new_has_sync_committee_finality != old_has_sync_committee_finality {
+                return Ok(new_has_sync_committee_finality);
+            }
+        }
+
+        // Tiebreaker 1: Sync committee participation beyond super majority.
+        if new_active_participants != old_active_participants {
+            return Ok(new_active_participants > old_active_participants);
+        }
+
+        // Tiebreaker 2: Prefer older data (fewer changes to best).
+        if new.attested_header_slot() != self.attested_header_slot() {
+            return Ok(new.attested_header_slot() < self.attested_header_slot());
+        }
+        Ok(new.signature_slot() < self.signature_slot())
+    }
+
+    fn is_sync_committee_update(&self) -> bool {
+        !self
+            .next_sync_committee_branch()
+            .iter()
+            .all(|node| node.is_zero())
+    }
+
+    fn is_finality_update(&self) -> bool {
+        !self.finality_branch().iter().all(|node| node.is_zero())
+    }
+
     pub fn from_ssz_bytes(bytes: &[u8], fork_name: ForkName) -> Result<Self, ssz::DecodeError> {
         let update = match fork_name {
             ForkName::Altair | ForkName::Bellatrix => {
@@ -280,16 +381,76 @@ impl<E: EthSpec> LightClientUpdate<E> {
 
         Ok(update)
     }
+
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn ssz_max_len_for_fork(fork_name: ForkName) -> usize {
+        let fixed_size = match fork_name {
+            ForkName::Base => 0,
+            ForkName::Altair | ForkName::Bellatrix => {
+                <LightClientUpdateAltair<E> as Encode>::ssz_fixed_len()
+            }
+            ForkName::Capella => <LightClientUpdateCapella<E> as Encode>::ssz_fixed_len(),
+            ForkName::Deneb => <LightClientUpdateDeneb<E> as Encode>::ssz_fixed_len(),
+            ForkName::Electra => <LightClientUpdateElectra<E> as Encode>::ssz_fixed_len(),
+        };
+        // `2 *` because there are two headers in the update
+        fixed_size + 2 * LightClientHeader::<E>::ssz_max_var_len_for_fork(fork_name)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{SeedableRng, XorShiftRng};
     use crate::MainnetEthSpec;
     use ssz_types::typenum::Unsigned;
 
     ssz_tests!(LightClientUpdateDeneb<MainnetEthSpec>);
 
+    fn update_with_participants(
+        rng: &mut XorShiftRng,
+        participants: usize,
+    ) -> LightClientUpdate<MainnetEthSpec> {
+        let mut update = LightClientUpdateDeneb::<MainnetEthSpec>::random_for_test(rng);
+        update.attested_header.beacon.slot = Slot::new(100);
+        update.finalized_header.beacon.slot = Slot::new(64);
+        update.signature_slot = Slot::new(101);
+        for i in 0..update.sync_aggregate.sync_committee_bits.len() {
+            update
+                .sync_aggregate
+                .sync_committee_bits
+                .set(i, i < participants)
+                .unwrap();
+        }
+        LightClientUpdate::Deneb(update)
+    }
+
+    #[test]
+    fn better_update_prefers_participation() {
+        let spec = MainnetEthSpec::default_spec();
+        let rng = &mut XorShiftRng::from_seed([42; 16]);
+        let max_participants = <MainnetEthSpec as EthSpec>::SyncCommitteeSize::to_usize();
+
+        let supermajority = update_with_participants(rng, max_participants);
+        let minority = update_with_participants(rng, max_participants / 2);
+        assert!(minority
+            .is_better_light_client_update(&supermajority, &spec)
+            .unwrap());
+        assert!(!supermajority
+            .is_better_light_client_update(&minority, &spec)
+            .unwrap());
+
+        // With equal participation the older update is preferred.
+        let mut newer = update_with_participants(rng, max_participants);
+        *newer.signature_slot_mut() = Slot::new(102);
+        assert!(!supermajority
+            .is_better_light_client_update(&newer, &spec)
+            .unwrap());
+        assert!(newer
+            .is_better_light_client_update(&supermajority, &spec)
+            .unwrap());
+    }
+
     #[test]
     fn finalized_root_params() {
         assert!(2usize.pow(FINALIZED_ROOT_PROOF_LEN as u32) <= FINALIZED_ROOT_INDEX);
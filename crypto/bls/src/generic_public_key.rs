@@ -1,5 +1,5 @@
 use crate::generic_public_key_bytes::GenericPublicKeyBytes;
-use crate::Error;
+use crate::{threshold, Error};
 use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
 use serde_utils::hex::encode as hex_encode;
@@ -37,6 +37,12 @@ pub trait TPublicKey: Sized + Clone {
     /// This function *does not* perform thorough checks of the input bytes and should only be
     /// used with bytes output from `Self::serialize_uncompressed`.
     fn deserialize_uncompressed(bytes: &[u8]) -> Result<Self, Error>;
+
+    /// Combines the public keys of secret key shares into the public key of the secret key that
+    /// was split, where each item of `shares` is `(share_index, share_public_key)`.
+    ///
+    /// The share indices have already been checked to be non-zero and distinct.
+    fn combine_shares(shares: &[(u64, &Self)]) -> Result<Self, Error>;
 }
 
 /// A BLS public key that is generic across some BLS point (`Pub`).
@@ -99,6 +105,22 @@ where
             point: Pub::deserialize_uncompressed(bytes)?,
         })
     }
+
+    /// Combines the public keys of at least `threshold` shares produced by
+    /// `GenericSecretKey::split` into the public key of the secret key that was split.
+    ///
+    /// Each item of `shares` is `(share_index, share_public_key)`. Combining fewer than
+    /// `threshold` shares returns an unrelated public key.
+    pub fn combine_shares(shares: &[(u64, Self)]) -> Result<Self, Error> {
+        threshold::verify_share_indices(shares.iter().map(|(index, _)| *index))?;
+        let points = shares
+            .iter()
+            .map(|(index, pubkey)| (*index, pubkey.point()))
+            .collect::<Vec<_>>();
+        Ok(Self {
+            point: Pub::combine_shares(&points)?,
+        })
+    }
 }
 
 impl<Pub: TPublicKey> Eq for GenericPublicKey<Pub> {}
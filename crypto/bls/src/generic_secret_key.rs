@@ -1,7 +1,7 @@
 use crate::{
     generic_public_key::{GenericPublicKey, TPublicKey},
     generic_signature::{GenericSignature, TSignature},
-    Error, Hash256, ZeroizeHash,
+    threshold, Error, Hash256, ZeroizeHash,
 };
 use std::marker::PhantomData;
 
@@ -25,6 +25,12 @@ pub trait TSecretKey<SignaturePoint, PublicKeyPoint>: Sized {
 
     /// Deserialize `self` from compressed bytes.
     fn deserialize(bytes: &[u8]) -> Result<Self, Error>;
+
+    /// Split `self` into `share_count` shares, any `threshold` of which can sign on its behalf.
+    ///
+    /// The share at position `i` of the result has the share index `i + 1`. The parameters have
+    /// already been checked to satisfy `0 < threshold <= share_count`.
+    fn split(&self, threshold: usize, share_count: usize) -> Result<Vec<Self>, Error>;
 }
 
 #[derive(Clone)]
@@ -88,4 +94,33 @@ where
             })
         }
     }
+
+    /// Split `self` into `share_count` shares using Shamir's secret sharing, such that partial
+    /// signatures from any `threshold` of the shares combine into a signature by `self`.
+    ///
+    /// Returns `(share_index, share)` pairs, with indices counting up from `1`.
+    ///
+    /// ## Note
+    ///
+    /// The shares that are returned are sensitive cryptographic material. Fewer than `threshold`
+    /// of them reveal nothing about `self`.
+    pub fn split(&self, threshold: usize, share_count: usize) -> Result<Vec<(u64, Self)>, Error> {
+        threshold::verify_threshold(threshold, share_count)?;
+        Ok(self
+            .point
+            .split(threshold, share_count)?
+            .into_iter()
+            .zip(1..)
+            .map(|(point, index)| {
+                (
+                    index,
+                    Self {
+                        point,
+                        _phantom_signature: PhantomData,
+                        _phantom_public_key: PhantomData,
+                    },
+                )
+            })
+            .collect())
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
     generic_public_key::{GenericPublicKey, TPublicKey},
-    Error, Hash256,
+    threshold, Error, Hash256,
 };
 use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
@@ -36,6 +36,12 @@ pub trait TSignature<GenericPublicKey>: Sized + Clone {
 
     /// Returns `true` if `self` is a signature across `msg` by `pubkey`.
     fn verify(&self, pubkey: &GenericPublicKey, msg: Hash256) -> bool;
+
+    /// Combines partial signatures from secret key shares into a signature by the secret key that
+    /// was split, where each item of `partials` is `(share_index, partial_signature)`.
+    ///
+    /// The share indices have already been checked to be non-zero and distinct.
+    fn combine_partials(partials: &[(u64, &Self)]) -> Result<Self, Error>;
 }
 
 /// A BLS signature that is generic across:
@@ -129,6 +135,28 @@ where
             _phantom: PhantomData,
         })
     }
+
+    /// Combines the partial signatures of at least `threshold` shares produced by
+    /// `GenericSecretKey::split` into a signature by the secret key that was split.
+    ///
+    /// Each item of `partials` is `(share_index, partial_signature)`, all across the same message.
+    /// The partial signatures are not verified, callers should verify each of them against the
+    /// public key of its share beforehand so that an invalid partial can be attributed.
+    pub fn combine_partials(partials: &[(u64, Self)]) -> Result<Self, Error> {
+        threshold::verify_share_indices(partials.iter().map(|(index, _)| *index))?;
+        let points = partials
+            .iter()
+            .map(|(index, signature)| {
+                signature
+                    .point()
+                    .map(|point| (*index, point))
+                    .ok_or(Error::EmptyPartialSignature)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let point = Sig::combine_partials(&points)?;
+        let is_infinity = point.serialize() == INFINITY_SIGNATURE;
+        Ok(Self::from_point(point, is_infinity))
+    }
 }
 
 impl<Pub, Sig> GenericSignature<Pub, Sig>
@@ -9,11 +9,14 @@ use crate::{
     BlstError, Error, Hash256, ZeroizeHash, INFINITY_SIGNATURE,
 };
 pub use blst::min_pk as blst_core;
-use blst::{blst_scalar, BLST_ERROR};
+use blst::{blst_fr, blst_p1, blst_p1_affine, blst_p2, blst_p2_affine, blst_scalar, BLST_ERROR};
 use rand::Rng;
+use zeroize::Zeroize;
 
 pub const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
 pub const RAND_BITS: usize = 64;
+/// The bit-length of the order of the BLS12-381 scalar field.
+const SCALAR_BITS: usize = 255;
 
 /// Provides the externally-facing, core BLS types.
 pub mod types {
@@ -151,6 +154,38 @@ impl TPublicKey for blst_core::PublicKey {
         let key = result?;
         Ok(key)
     }
+
+    fn combine_shares(shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        let coefficients = lagrange_coefficients(shares.iter().map(|(index, _)| *index));
+        let mut sum: Option<blst_p1> = None;
+
+        for ((_, pubkey), coefficient) in shares.iter().zip(coefficients.iter()) {
+            let mut affine = blst_p1_affine::default();
+            let mut point = blst_p1::default();
+            unsafe {
+                let err = blst::blst_p1_uncompress(&mut affine, pubkey.compress().as_ptr());
+                if err != BLST_ERROR::BLST_SUCCESS {
+                    return Err(err.into());
+                }
+                blst::blst_p1_from_affine(&mut point, &affine);
+                let point_ptr: *mut blst_p1 = &mut point;
+                blst::blst_p1_mult(point_ptr, point_ptr, coefficient.b.as_ptr(), SCALAR_BITS);
+                if let Some(sum) = sum.as_mut() {
+                    let sum_ptr: *mut blst_p1 = sum;
+                    blst::blst_p1_add_or_double(sum_ptr, sum_ptr, &point);
+                    continue;
+                }
+            }
+            sum = Some(point);
+        }
+
+        let sum = sum.ok_or(Error::NoShares)?;
+        let mut bytes = [0; PUBLIC_KEY_BYTES_LEN];
+        unsafe {
+            blst::blst_p1_compress(bytes.as_mut_ptr(), &sum);
+        }
+        Self::key_validate(&bytes).map_err(Into::into)
+    }
 }
 
 /// A wrapper that allows for `PartialEq` and `Clone` impls.
@@ -198,6 +233,38 @@ impl TSignature<blst_core::PublicKey> for blst_core::Signature {
         // Check Signature inside function for subgroup
         self.verify(true, msg.as_bytes(), DST, &[], pubkey, false) == BLST_ERROR::BLST_SUCCESS
     }
+
+    fn combine_partials(partials: &[(u64, &Self)]) -> Result<Self, Error> {
+        let coefficients = lagrange_coefficients(partials.iter().map(|(index, _)| *index));
+        let mut sum: Option<blst_p2> = None;
+
+        for ((_, signature), coefficient) in partials.iter().zip(coefficients.iter()) {
+            let mut affine = blst_p2_affine::default();
+            let mut point = blst_p2::default();
+            unsafe {
+                let err = blst::blst_p2_uncompress(&mut affine, signature.to_bytes().as_ptr());
+                if err != BLST_ERROR::BLST_SUCCESS {
+                    return Err(err.into());
+                }
+                blst::blst_p2_from_affine(&mut point, &affine);
+                let point_ptr: *mut blst_p2 = &mut point;
+                blst::blst_p2_mult(point_ptr, point_ptr, coefficient.b.as_ptr(), SCALAR_BITS);
+                if let Some(sum) = sum.as_mut() {
+                    let sum_ptr: *mut blst_p2 = sum;
+                    blst::blst_p2_add_or_double(sum_ptr, sum_ptr, &point);
+                    continue;
+                }
+            }
+            sum = Some(point);
+        }
+
+        let sum = sum.ok_or(Error::NoShares)?;
+        let mut bytes = [0; SIGNATURE_BYTES_LEN];
+        unsafe {
+            blst::blst_p2_compress(bytes.as_mut_ptr(), &sum);
+        }
+        Self::from_bytes(&bytes).map_err(Into::into)
+    }
 }
 
 /// A wrapper that allows for `PartialEq` and `Clone` impls.
@@ -297,4 +364,121 @@ impl TSecretKey<blst_core::Signature, blst_core::PublicKey> for blst_core::Secre
     fn deserialize(bytes: &[u8]) -> Result<Self, Error> {
         Self::from_bytes(bytes).map_err(Into::into)
     }
+
+    fn split(&self, threshold: usize, share_count: usize) -> Result<Vec<Self>, Error> {
+        // The polynomial `f(x) = self + a_1 * x + ... + a_{threshold - 1} * x^{threshold - 1}`,
+        // with random coefficients. The coefficients are generated as secret keys, and they and
+        // every evaluation of `f` are zeroized when dropped.
+        let random_coefficients = (1..threshold)
+            .map(|_| <Self as TSecretKey<blst_core::Signature, blst_core::PublicKey>>::random())
+            .collect::<Vec<_>>();
+        let coefficients = std::iter::once(self)
+            .chain(random_coefficients.iter())
+            .map(fr_from_secret_key)
+            .collect::<Vec<_>>();
+
+        (1..=share_count as u64)
+            .map(|index| {
+                let x = fr_from_u64(index);
+                let mut y = SecretFr::default();
+                let y_ptr: *mut blst_fr = &mut y.0;
+                // Evaluate `f(index)` using Horner's method.
+                for coefficient in coefficients.iter().rev() {
+                    unsafe {
+                        blst::blst_fr_mul(y_ptr, y_ptr, &x);
+                        blst::blst_fr_add(y_ptr, y_ptr, &coefficient.0);
+                    }
+                }
+                secret_key_from_fr(&y)
+            })
+            .collect()
+    }
+}
+
+/// An element of the scalar field derived from a secret key, which is zeroized when dropped.
+#[derive(Default)]
+struct SecretFr(blst_fr);
+
+impl Drop for SecretFr {
+    fn drop(&mut self) {
+        self.0.l.zeroize();
+    }
+}
+
+/// A scalar derived from a secret key, which is zeroized when dropped.
+#[derive(Default)]
+struct SecretScalar(blst_scalar);
+
+impl Drop for SecretScalar {
+    fn drop(&mut self) {
+        self.0.b.zeroize();
+    }
+}
+
+/// Returns `value` as an element of the scalar field.
+fn fr_from_u64(value: u64) -> blst_fr {
+    let limbs = [value, 0, 0, 0];
+    let mut fr = blst_fr::default();
+    unsafe {
+        blst::blst_fr_from_uint64(&mut fr, limbs.as_ptr());
+    }
+    fr
+}
+
+/// Returns the scalar of `secret_key` as an element of the scalar field.
+fn fr_from_secret_key(secret_key: &blst_core::SecretKey) -> SecretFr {
+    let mut scalar = SecretScalar::default();
+    let mut fr = SecretFr::default();
+    let bytes = ZeroizeHash::from(secret_key.to_bytes());
+    unsafe {
+        blst::blst_scalar_from_bendian(&mut scalar.0, bytes.as_bytes().as_ptr());
+        blst::blst_fr_from_scalar(&mut fr.0, &scalar.0);
+    }
+    fr
+}
+
+/// Returns the secret key with the scalar `fr`, which must be non-zero.
+fn secret_key_from_fr(fr: &SecretFr) -> Result<blst_core::SecretKey, Error> {
+    let mut scalar = SecretScalar::default();
+    let mut bytes = ZeroizeHash::zero();
+    unsafe {
+        blst::blst_scalar_from_fr(&mut scalar.0, &fr.0);
+        blst::blst_bendian_from_scalar(bytes.as_mut_bytes().as_mut_ptr(), &scalar.0);
+    }
+    blst_core::SecretKey::from_bytes(bytes.as_bytes()).map_err(Into::into)
+}
+
+/// Returns the Lagrange coefficients for interpolating a polynomial at `x = 0` from its evaluations
+/// at `indices`, which must be non-zero and distinct.
+fn lagrange_coefficients(indices: impl Iterator<Item = u64>) -> Vec<blst_scalar> {
+    let xs = indices.map(fr_from_u64).collect::<Vec<_>>();
+
+    xs.iter()
+        .enumerate()
+        .map(|(i, x_i)| {
+            // The coefficient for `x_i` is the product of `x_j / (x_j - x_i)` for all `j != i`.
+            let mut numerator = fr_from_u64(1);
+            let mut denominator = fr_from_u64(1);
+            let numerator_ptr: *mut blst_fr = &mut numerator;
+            let denominator_ptr: *mut blst_fr = &mut denominator;
+            for (_, x_j) in xs.iter().enumerate().filter(|(j, _)| *j != i) {
+                let mut difference = blst_fr::default();
+                unsafe {
+                    blst::blst_fr_sub(&mut difference, x_j, x_i);
+                    blst::blst_fr_mul(numerator_ptr, numerator_ptr, x_j);
+                    blst::blst_fr_mul(denominator_ptr, denominator_ptr, &difference);
+                }
+            }
+
+            let mut coefficient = blst_fr::default();
+            let mut scalar = blst_scalar::default();
+            unsafe {
+                blst::blst_fr_inverse(&mut coefficient, denominator_ptr);
+                let coefficient_ptr: *mut blst_fr = &mut coefficient;
+                blst::blst_fr_mul(coefficient_ptr, coefficient_ptr, numerator_ptr);
+                blst::blst_scalar_from_fr(&mut scalar, coefficient_ptr);
+            }
+            scalar
+        })
+        .collect()
 }
@@ -61,6 +61,13 @@ impl TPublicKey for PublicKey {
     fn deserialize_uncompressed(_: &[u8]) -> Result<Self, Error> {
         panic!("fake_crypto does not support uncompressed keys")
     }
+
+    fn combine_shares(shares: &[(u64, &Self)]) -> Result<Self, Error> {
+        shares
+            .first()
+            .map(|(_, pubkey)| (*pubkey).clone())
+            .ok_or(Error::NoShares)
+    }
 }
 
 impl Eq for PublicKey {}
@@ -115,6 +122,10 @@ impl TSignature<PublicKey> for Signature {
     fn verify(&self, _pubkey: &PublicKey, _msg: Hash256) -> bool {
         true
     }
+
+    fn combine_partials(_partials: &[(u64, &Self)]) -> Result<Self, Error> {
+        Ok(Self::infinity())
+    }
 }
 
 impl PartialEq for Signature {
@@ -221,4 +232,8 @@ impl TSecretKey<Signature, PublicKey> for SecretKey {
         sk.0[..].copy_from_slice(&bytes[0..SECRET_KEY_BYTES_LEN]);
         Ok(sk)
     }
+
+    fn split(&self, _threshold: usize, share_count: usize) -> Result<Vec<Self>, Error> {
+        Ok(vec![self.clone(); share_count])
+    }
 }
@@ -29,6 +29,7 @@ mod generic_signature;
 mod generic_signature_bytes;
 mod generic_signature_set;
 mod get_withdrawal_credentials;
+mod threshold;
 mod zeroize_hash;
 
 pub mod impls;
@@ -59,6 +60,17 @@ pub enum Error {
     InvalidInfinityPublicKey,
     /// The secret key is all zero bytes, which is invalid.
     InvalidZeroSecretKey,
+    /// A secret key cannot be split into `share_count` shares with the given `threshold`.
+    InvalidThreshold {
+        threshold: usize,
+        share_count: usize,
+    },
+    /// A share index was zero or appeared more than once.
+    InvalidShareIndex(u64),
+    /// At least one share is required to combine partial signatures or public keys.
+    NoShares,
+    /// A partial signature was the "empty" signature.
+    EmptyPartialSignature,
 }
 
 #[cfg(feature = "supranational")]
@@ -0,0 +1,37 @@
+//! Provides validation shared by the threshold signing functions.
+//!
+//! A secret key is split into shares using Shamir's secret sharing, where the share with index `i`
+//! is the evaluation of a random polynomial at `x = i`. Any `threshold` partial signatures (or
+//! public keys) from distinct shares can be combined via Lagrange interpolation at `x = 0`.
+
+use crate::Error;
+use std::collections::HashSet;
+
+/// Returns an error if a secret key cannot be split into `share_count` shares, any `threshold` of
+/// which are able to sign.
+pub(crate) fn verify_threshold(threshold: usize, share_count: usize) -> Result<(), Error> {
+    if threshold == 0 || threshold > share_count || u64::try_from(share_count).is_err() {
+        Err(Error::InvalidThreshold {
+            threshold,
+            share_count,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns an error if `indices` is empty or contains a zero or duplicate index.
+pub(crate) fn verify_share_indices(indices: impl Iterator<Item = u64>) -> Result<(), Error> {
+    let mut seen = HashSet::new();
+    for index in indices {
+        if index == 0 || !seen.insert(index) {
+            return Err(Error::InvalidShareIndex(index));
+        }
+    }
+
+    if seen.is_empty() {
+        Err(Error::NoShares)
+    } else {
+        Ok(())
+    }
+}
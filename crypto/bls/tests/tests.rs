@@ -508,6 +508,121 @@ macro_rules! test_suite {
                 .push_valid_set(2)
                 .run_checks()
         }
+
+        fn partial_signatures(shares: &[(u64, SecretKey)], msg: Hash256) -> Vec<(u64, Signature)> {
+            shares
+                .iter()
+                .map(|(index, share)| (*index, share.sign(msg)))
+                .collect()
+        }
+
+        #[test]
+        fn threshold_signature_from_any_threshold_shares() {
+            let secret_key = secret_from_u64(42);
+            let msg = Hash256::from_low_u64_be(1);
+            let shares = secret_key.split(3, 5).unwrap();
+            assert_eq!(
+                shares.iter().map(|(index, _)| *index).collect::<Vec<_>>(),
+                vec![1, 2, 3, 4, 5]
+            );
+
+            let partials = partial_signatures(&shares, msg);
+            let expected = secret_key.sign(msg);
+            for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+                let subset = subset
+                    .iter()
+                    .map(|i| partials[*i].clone())
+                    .collect::<Vec<_>>();
+                let signature = Signature::combine_partials(&subset).unwrap();
+                assert_eq!(signature, expected);
+                assert!(signature.verify(&secret_key.public_key(), msg));
+            }
+
+            // Combining more partials than the threshold yields the same signature.
+            assert_eq!(Signature::combine_partials(&partials).unwrap(), expected);
+        }
+
+        #[test]
+        fn threshold_signature_below_threshold_is_invalid() {
+            let secret_key = secret_from_u64(42);
+            let msg = Hash256::from_low_u64_be(1);
+            let shares = secret_key.split(3, 5).unwrap();
+            let partials = partial_signatures(&shares[..2], msg);
+
+            let signature = Signature::combine_partials(&partials).unwrap();
+            assert!(!signature.verify(&secret_key.public_key(), msg));
+        }
+
+        #[test]
+        fn threshold_public_key_from_shares() {
+            let secret_key = secret_from_u64(42);
+            let shares = secret_key.split(2, 3).unwrap();
+            let share_pubkeys = shares
+                .iter()
+                .map(|(index, share)| (*index, share.public_key()))
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                PublicKey::combine_shares(&share_pubkeys[1..]).unwrap(),
+                secret_key.public_key()
+            );
+            assert!(share_pubkeys
+                .iter()
+                .all(|(_, pubkey)| *pubkey != secret_key.public_key()));
+        }
+
+        #[test]
+        fn threshold_one_of_one() {
+            let secret_key = secret_from_u64(42);
+            let shares = secret_key.split(1, 1).unwrap();
+            assert_eq!(
+                shares[0].1.serialize().as_bytes(),
+                secret_key.serialize().as_bytes()
+            );
+        }
+
+        #[test]
+        fn threshold_invalid_parameters() {
+            let secret_key = secret_from_u64(42);
+            for (threshold, share_count) in [(0, 3), (4, 3), (0, 0)] {
+                assert_eq!(
+                    secret_key.split(threshold, share_count).err(),
+                    Some(bls::Error::InvalidThreshold {
+                        threshold,
+                        share_count
+                    })
+                );
+            }
+        }
+
+        #[test]
+        fn threshold_invalid_share_indices() {
+            let msg = Hash256::from_low_u64_be(1);
+            let shares = secret_from_u64(42).split(2, 3).unwrap();
+            let mut partials = partial_signatures(&shares, msg);
+
+            assert_eq!(
+                Signature::combine_partials(&[]).err(),
+                Some(bls::Error::NoShares)
+            );
+
+            partials[1].0 = partials[0].0;
+            assert_eq!(
+                Signature::combine_partials(&partials).err(),
+                Some(bls::Error::InvalidShareIndex(partials[0].0))
+            );
+
+            partials[1].0 = 0;
+            assert_eq!(
+                Signature::combine_partials(&partials).err(),
+                Some(bls::Error::InvalidShareIndex(0))
+            );
+
+            assert_eq!(
+                Signature::combine_partials(&[(1, Signature::empty())]).err(),
+                Some(bls::Error::EmptyPartialSignature)
+            );
+        }
     };
 }
 
@@ -2,7 +2,10 @@ pub use clap::{Arg, ArgAction, Args, Command, FromArgMatches, Parser};
 use clap_utils::get_color_style;
 use clap_utils::FLAG_HEADER;
 use serde::{Deserialize, Serialize};
+use slasher::DatabaseBackend as SlasherDatabaseBackend;
 use std::path::PathBuf;
+use store::hdiff::HierarchyConfig;
+use store::DatabaseBackend;
 
 use crate::InspectTarget;
 
@@ -55,6 +58,24 @@ pub struct DatabaseManager {
     )]
     pub blobs_dir: Option<PathBuf>,
 
+    #[clap(
+        long = "beacon-node-backend",
+        value_name = "DATABASE",
+        help = "Set the database backend used by the beacon node. Must match the backend the \
+                database was created with. [default: leveldb]",
+        display_order = 0
+    )]
+    pub backend: Option<DatabaseBackend>,
+
+    #[clap(
+        long,
+        value_name = "EXPONENTS",
+        help = "Exponents of the state diff layers in the freezer database, as passed to the \
+                beacon node. Must match the exponents the database is currently using.",
+        display_order = 0
+    )]
+    pub hierarchy_exponents: Option<HierarchyConfig>,
+
     #[clap(
         long,
         global = true,
@@ -79,6 +100,11 @@ pub enum DatabaseManagerSubcommand {
     PruneBlobs(PruneBlobs),
     PruneStates(PruneStates),
     Compact(Compact),
+    ConvertFreezer(ConvertFreezer),
+    ExportEra(ExportEra),
+    ImportEra(ImportEra),
+    Verify(Verify),
+    MigrateSlasher(MigrateSlasher),
 }
 
 #[derive(Parser, Clone, Deserialize, Serialize, Debug)]
@@ -227,3 +253,122 @@ pub struct Compact {
     )]
     pub output_dir: Option<PathBuf>,
 }
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Re-encode the frozen states between restore points using new hierarchy exponents.",
+    alias = "convert_freezer"
+)]
+pub struct ConvertFreezer {
+    #[clap(
+        long,
+        value_name = "EXPONENTS",
+        help = "Exponents of the state diff layers to convert to. If omitted, all state diffs \
+                are deleted and only restore points are kept.",
+        display_order = 0
+    )]
+    pub target_exponents: Option<HierarchyConfig>,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Export finalized blocks, blobs and states to era files.",
+    alias = "export_era"
+)]
+pub struct ExportEra {
+    #[clap(
+        long,
+        value_name = "DIR",
+        help = "Directory to write the era files to.",
+        display_order = 0
+    )]
+    pub output_dir: PathBuf,
+
+    #[clap(
+        long,
+        value_name = "ERA",
+        help = "First era to export. Defaults to the earliest era with all of its blocks in \
+                the database.",
+        display_order = 0
+    )]
+    pub start_era: Option<u64>,
+
+    #[clap(
+        long,
+        value_name = "ERA",
+        help = "Last era to export. Defaults to the latest finalized era.",
+        display_order = 0
+    )]
+    pub end_era: Option<u64>,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Import historic blocks, blobs and states from era files.",
+    alias = "import_era"
+)]
+pub struct ImportEra {
+    #[clap(
+        long,
+        value_name = "DIR",
+        help = "Directory containing the era files to import. Blocks must be contiguous with \
+                the oldest block in the database.",
+        display_order = 0
+    )]
+    pub input_dir: PathBuf,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(about = "Check the database for inconsistencies, optionally repairing them.")]
+pub struct Verify {
+    #[clap(
+        long,
+        help = "Repair the inconsistencies that can be fixed locally. Orphaned finalized blocks \
+                are deleted and the frozen block roots are rebuilt from the split block.",
+        display_order = 0,
+        help_heading = FLAG_HEADER
+    )]
+    pub repair: bool,
+}
+
+#[derive(Parser, Clone, Deserialize, Serialize, Debug)]
+#[clap(
+    about = "Copy the slasher database into a new directory, converting it to another backend.",
+    alias = "migrate_slasher"
+)]
+pub struct MigrateSlasher {
+    #[clap(
+        long,
+        value_name = "DIR",
+        help = "Directory of the existing slasher database. Defaults to the slasher_db \
+                directory within the data directory.",
+        display_order = 0
+    )]
+    pub slasher_dir: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "DATABASE",
+        help = "Backend of the existing slasher database.",
+        display_order = 0
+    )]
+    pub from: SlasherDatabaseBackend,
+
+    #[clap(
+        long,
+        value_name = "DATABASE",
+        help = "Backend to convert the slasher database to.",
+        display_order = 0
+    )]
+    pub to: SlasherDatabaseBackend,
+
+    #[clap(
+        long,
+        value_name = "DIR",
+        help = "Directory to write the converted slasher database to. Must be empty or not \
+                exist. Once complete, start the beacon node with --slasher-dir set to this \
+                directory and --slasher-backend set to the new backend.",
+        display_order = 0
+    )]
+    pub output_dir: PathBuf,
+}
@@ -0,0 +1,832 @@
+//! Export and import of finalized chain data as era-style archive files.
+//!
+//! Each era file covers `SLOTS_PER_HISTORICAL_ROOT` slots. Era `N` contains the blocks from slots
+//! `(N - 1) * SLOTS_PER_HISTORICAL_ROOT .. N * SLOTS_PER_HISTORICAL_ROOT`, followed by the state
+//! at slot `N * SLOTS_PER_HISTORICAL_ROOT`. Era 0 contains just the genesis state.
+//!
+//! Files are sequences of [e2store] records, each an 8-byte header (2-byte type, 4-byte
+//! little-endian length, 2 reserved bytes) followed by the data:
+//!
+//! ```text
+//! Version | (block blobs?)* | state | block-index? | state-index
+//! ```
+//!
+//! Blocks and states are SSZ-encoded and snappy-framed, as in the era format used by other
+//! clients. Lighthouse prunes the execution payloads of finalized blocks by default, so blocks
+//! without a payload are written as blinded blocks using a Lighthouse-specific record type.
+//! Blobs are also written using a Lighthouse-specific record type, following their block. Other
+//! readers skip record types they don't know about.
+//!
+//! [e2store]: https://github.com/status-im/nimbus-eth2/blob/stable/docs/e2store.md
+use slog::{info, warn, Logger};
+use ssz::{Decode, Encode};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use store::chunked_vector::BlockRoots;
+use store::metadata::STATE_UPPER_LIMIT_NO_RETAIN;
+use store::{
+    AnchorInfo, BlobInfo, ChunkWriter, DatabaseBlock, HotColdDB, ItemStore, KeyValueStore,
+};
+use types::{
+    BeaconState, BlobSidecarList, ChainSpec, EthSpec, Hash256, SignedBeaconBlock,
+    SignedBlindedBeaconBlock, Slot,
+};
+
+const VERSION: [u8; 2] = [0x65, 0x32];
+const COMPRESSED_SIGNED_BEACON_BLOCK: [u8; 2] = [0x01, 0x00];
+const COMPRESSED_BEACON_STATE: [u8; 2] = [0x02, 0x00];
+const SLOT_INDEX: [u8; 2] = [0x69, 0x32];
+/// Lighthouse-specific: a block whose execution payload has been pruned.
+const COMPRESSED_SIGNED_BLINDED_BEACON_BLOCK: [u8; 2] = [0x01, 0x4c];
+/// Lighthouse-specific: the blob sidecars of the preceding block.
+const COMPRESSED_BLOB_SIDECAR_LIST: [u8; 2] = [0x03, 0x4c];
+
+const HEADER_LEN: u64 = 8;
+/// Records longer than this are considered corrupt when reading an era file. The largest records
+/// are compressed states, which are far smaller than this on every network.
+const MAX_RECORD_LEN: u64 = 1 << 30;
+const ERA_FILE_EXTENSION: &str = "era";
+
+/// Writes e2store records, keeping track of the offset of each record.
+struct EraWriter<W: Write> {
+    inner: W,
+    position: u64,
+}
+
+impl<W: Write> EraWriter<W> {
+    fn new(inner: W) -> Self {
+        Self { inner, position: 0 }
+    }
+
+    /// Write a record, returning its offset from the start of the file.
+    fn write_record(&mut self, record_type: [u8; 2], data: &[u8]) -> io::Result<u64> {
+        let offset = self.position;
+        let len = u32::try_from(data.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "record too large"))?;
+
+        self.inner.write_all(&record_type)?;
+        self.inner.write_all(&len.to_le_bytes())?;
+        self.inner.write_all(&[0, 0])?;
+        self.inner.write_all(data)?;
+
+        self.position += HEADER_LEN + data.len() as u64;
+        Ok(offset)
+    }
+
+    /// Write a record containing snappy-framed `ssz_bytes`.
+    fn write_compressed(&mut self, record_type: [u8; 2], ssz_bytes: &[u8]) -> io::Result<u64> {
+        let mut encoder = snap::write::FrameEncoder::new(vec![]);
+        encoder.write_all(ssz_bytes)?;
+        let compressed = encoder.into_inner().map_err(|e| e.into_error())?;
+        self.write_record(record_type, &compressed)
+    }
+
+    /// Write a slot index for the records at `offsets`, starting from `start_slot`.
+    ///
+    /// Offsets are stored relative to the start of the index record, with 0 indicating a slot
+    /// without a record.
+    fn write_slot_index(&mut self, start_slot: Slot, offsets: &[Option<u64>]) -> io::Result<()> {
+        let index_position = self.position as i64;
+
+        let mut data = Vec::with_capacity((offsets.len() + 2) * 8);
+        data.extend_from_slice(&start_slot.as_u64().to_le_bytes());
+        for offset in offsets {
+            let relative_offset = offset.map_or(0, |offset| offset as i64 - index_position);
+            data.extend_from_slice(&relative_offset.to_le_bytes());
+        }
+        data.extend_from_slice(&(offsets.len() as i64).to_le_bytes());
+
+        self.write_record(SLOT_INDEX, &data).map(|_| ())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Reads e2store records one at a time.
+struct EraReader<R: Read> {
+    inner: R,
+}
+
+impl<R: Read> EraReader<R> {
+    /// Start reading an era file, checking that it starts with a version record.
+    fn new(inner: R) -> Result<Self, String> {
+        let mut reader = Self { inner };
+        match reader.read_record()? {
+            Some((record_type, _)) if record_type == VERSION => Ok(reader),
+            _ => Err("Not an era file: missing version record".to_string()),
+        }
+    }
+
+    /// Read the next record, returning `None` at the end of the file.
+    fn read_record(&mut self) -> Result<Option<([u8; 2], Vec<u8>)>, String> {
+        let mut header = [0; HEADER_LEN as usize];
+        match self.inner.read_exact(&mut header) {
+            Ok(()) => (),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(format!("Unable to read record header: {e:?}")),
+        }
+        let record_type = [header[0], header[1]];
+        let len = u64::from(u32::from_le_bytes([
+            header[2], header[3], header[4], header[5],
+        ]));
+        if len > MAX_RECORD_LEN {
+            return Err(format!(
+                "Record length {len} exceeds the maximum of {MAX_RECORD_LEN}"
+            ));
+        }
+
+        // Read through `take` rather than into a buffer of `len` bytes, so that a corrupt length
+        // can't allocate more memory than the file contains.
+        let mut data = vec![];
+        (&mut self.inner)
+            .take(len)
+            .read_to_end(&mut data)
+            .map_err(|e| format!("Unable to read record data: {e:?}"))?;
+        if data.len() as u64 != len {
+            return Err(format!(
+                "Unable to read record data: expected {len} bytes, found {}",
+                data.len()
+            ));
+        }
+        Ok(Some((record_type, data)))
+    }
+}
+
+fn decompress(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decompressed = vec![];
+    snap::read::FrameDecoder::new(data)
+        .read_to_end(&mut decompressed)
+        .map_err(|e| format!("Unable to decompress record: {e:?}"))?;
+    Ok(decompressed)
+}
+
+/// The decoded contents of an era file.
+struct Era<E: EthSpec> {
+    /// Blocks with their roots, in ascending slot order.
+    blocks: Vec<(Hash256, DatabaseBlock<E>)>,
+    blobs: HashMap<Hash256, BlobSidecarList<E>>,
+    state: BeaconState<E>,
+}
+
+impl<E: EthSpec> Era<E> {
+    fn read(path: &Path, spec: &ChainSpec) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("Unable to open {path:?}: {e:?}"))?;
+        let mut reader = EraReader::new(BufReader::new(file))?;
+
+        // Records are decoded as they are read, so only one compressed record is held at a time.
+        let mut blocks = vec![];
+        let mut blobs = HashMap::new();
+        let mut state = None;
+        while let Some((record_type, data)) = reader.read_record()? {
+            match record_type {
+                COMPRESSED_SIGNED_BEACON_BLOCK => {
+                    let block = SignedBeaconBlock::from_ssz_bytes(&decompress(&data)?, spec)
+                        .map_err(|e| format!("Invalid block: {e:?}"))?;
+                    blocks.push((block.canonical_root(), DatabaseBlock::Full(block)));
+                }
+                COMPRESSED_SIGNED_BLINDED_BEACON_BLOCK => {
+                    let block = SignedBlindedBeaconBlock::from_ssz_bytes(&decompress(&data)?, spec)
+                        .map_err(|e| format!("Invalid blinded block: {e:?}"))?;
+                    blocks.push((block.canonical_root(), DatabaseBlock::Blinded(block)));
+                }
+                COMPRESSED_BLOB_SIDECAR_LIST => {
+                    let list = BlobSidecarList::<E>::from_ssz_bytes(&decompress(&data)?)
+                        .map_err(|e| format!("Invalid blob sidecars: {e:?}"))?;
+                    if let Some(sidecar) = list.first() {
+                        blobs.insert(sidecar.block_root(), list);
+                    }
+                }
+                COMPRESSED_BEACON_STATE => {
+                    let decoded = BeaconState::from_ssz_bytes(&decompress(&data)?, spec)
+                        .map_err(|e| format!("Invalid state: {e:?}"))?;
+                    state = Some(decoded);
+                }
+                // The slot indices are only needed for random access, and unknown records are
+                // skipped.
+                _ => (),
+            }
+        }
+
+        Ok(Self {
+            blocks,
+            blobs,
+            state: state.ok_or_else(|| format!("Era file {path:?} has no state"))?,
+        })
+    }
+}
+
+/// Return the first era that can be exported, given the oldest block in the database.
+fn first_complete_era<E: EthSpec>(oldest_block_slot: Slot) -> u64 {
+    let slots_per_era = E::slots_per_historical_root() as u64;
+    if oldest_block_slot == 0 {
+        0
+    } else {
+        oldest_block_slot.as_u64().div_ceil(slots_per_era) + 1
+    }
+}
+
+/// Write era files for every era in `start_era..=end_era` to `output_dir`.
+///
+/// If unspecified, the range defaults to every era whose blocks and state are in the freezer.
+pub fn export_eras<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    db: &HotColdDB<E, Hot, Cold>,
+    output_dir: &Path,
+    start_era: Option<u64>,
+    end_era: Option<u64>,
+    log: &Logger,
+) -> Result<(), String> {
+    let slots_per_era = E::slots_per_historical_root() as u64;
+    let split_slot = db.get_split_slot();
+    let oldest_block_slot = db.get_oldest_block_slot();
+
+    // The state at the end of each era must be in the freezer.
+    let last_era = split_slot.as_u64().saturating_sub(1) / slots_per_era;
+    let first_era = first_complete_era::<E>(oldest_block_slot);
+    let start_era = start_era.unwrap_or(first_era);
+    let end_era = end_era.unwrap_or(last_era);
+
+    if start_era < first_era {
+        return Err(format!(
+            "Era {start_era} is unavailable, blocks prior to slot {oldest_block_slot} are \
+             missing. The earliest era available is {first_era}"
+        ));
+    }
+    if end_era > last_era {
+        return Err(format!(
+            "Era {end_era} is not yet finalized, the latest era available is {last_era}"
+        ));
+    }
+
+    fs::create_dir_all(output_dir)
+        .map_err(|e| format!("Unable to create {output_dir:?}: {e:?}"))?;
+
+    let config_name = db
+        .get_chain_spec()
+        .config_name
+        .clone()
+        .unwrap_or_else(|| "unknown".to_string());
+    for era in start_era..=end_era {
+        export_era(db, era, &config_name, output_dir, log)?;
+    }
+
+    info!(
+        log,
+        "Export complete";
+        "start_era" => start_era,
+        "end_era" => end_era,
+    );
+    Ok(())
+}
+
+fn export_era<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    db: &HotColdDB<E, Hot, Cold>,
+    era: u64,
+    config_name: &str,
+    output_dir: &Path,
+    log: &Logger,
+) -> Result<(), String> {
+    let slots_per_era = E::slots_per_historical_root() as u64;
+    let state_slot = Slot::new(era * slots_per_era);
+
+    let mut state = db
+        .load_cold_state_by_slot(state_slot)
+        .map_err(|e| format!("Unable to load state at slot {state_slot}: {e:?}"))?
+        .ok_or_else(|| {
+            format!(
+                "State at slot {state_slot} is unavailable, historic states must be \
+                 reconstructed before they can be exported"
+            )
+        })?;
+    let state_root = state
+        .update_tree_hash_cache()
+        .map_err(|e| format!("Unable to compute state root: {e:?}"))?;
+
+    let path = output_dir.join(format!(
+        "{config_name}-{era:05}-{}.{ERA_FILE_EXTENSION}",
+        hex::encode(&state_root.as_bytes()[..4])
+    ));
+    let file = File::create(&path).map_err(|e| format!("Unable to create {path:?}: {e:?}"))?;
+    let mut writer = EraWriter::new(BufWriter::new(file));
+    let io_err = |e: io::Error| format!("Unable to write {path:?}: {e:?}");
+
+    writer.write_record(VERSION, &[]).map_err(io_err)?;
+
+    // Write the blocks of the era, using the state's block roots to find them.
+    let mut block_offsets = vec![];
+    if era > 0 {
+        let start_slot = state_slot - slots_per_era;
+        for slot in (start_slot.as_u64()..state_slot.as_u64()).map(Slot::new) {
+            let block_root = *state
+                .get_block_root(slot)
+                .map_err(|e| format!("Missing block root at slot {slot}: {e:?}"))?;
+            let block = db
+                .try_get_full_block(&block_root)
+                .map_err(|e| format!("Unable to load block {block_root:?}: {e:?}"))?
+                .ok_or_else(|| format!("Missing block {block_root:?} at slot {slot}"))?;
+
+            // Skipped slots repeat the root of an earlier block.
+            let offset = match block {
+                DatabaseBlock::Full(block) if block.slot() == slot => {
+                    writer.write_compressed(COMPRESSED_SIGNED_BEACON_BLOCK, &block.as_ssz_bytes())
+                }
+                DatabaseBlock::Blinded(block) if block.slot() == slot => writer.write_compressed(
+                    COMPRESSED_SIGNED_BLINDED_BEACON_BLOCK,
+                    &block.as_ssz_bytes(),
+                ),
+                _ => {
+                    block_offsets.push(None);
+                    continue;
+                }
+            }
+            .map_err(io_err)?;
+            block_offsets.push(Some(offset));
+
+            let blobs = db
+                .get_blobs(&block_root)
+                .map_err(|e| format!("Unable to load blobs for {block_root:?}: {e:?}"))?;
+            if let Some(blobs) = blobs.filter(|blobs| !blobs.is_empty()) {
+                writer
+                    .write_compressed(COMPRESSED_BLOB_SIDECAR_LIST, &blobs.as_ssz_bytes())
+                    .map_err(io_err)?;
+            }
+        }
+    }
+
+    let state_offset = writer
+        .write_compressed(COMPRESSED_BEACON_STATE, &state.as_ssz_bytes())
+        .map_err(io_err)?;
+
+    if era > 0 {
+        writer
+            .write_slot_index(state_slot - slots_per_era, &block_offsets)
+            .map_err(io_err)?;
+    }
+    writer
+        .write_slot_index(state_slot, &[Some(state_offset)])
+        .map_err(io_err)?;
+    writer.flush().map_err(io_err)?;
+
+    info!(
+        log,
+        "Exported era";
+        "era" => era,
+        "blocks" => block_offsets.iter().flatten().count(),
+        "path" => ?path,
+    );
+    Ok(())
+}
+
+/// Find the era files in `input_dir`, ordered from the most to the least recent era.
+fn era_files_descending(input_dir: &Path) -> Result<Vec<(u64, PathBuf)>, String> {
+    let mut files = vec![];
+    for entry in
+        fs::read_dir(input_dir).map_err(|e| format!("Unable to read {input_dir:?}: {e:?}"))?
+    {
+        let path = entry
+            .map_err(|e| format!("Unable to read {input_dir:?}: {e:?}"))?
+            .path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(ERA_FILE_EXTENSION) {
+            continue;
+        }
+        // File names have the form `<config-name>-<era>-<short-state-root>.era`.
+        let era = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.rsplit('-').nth(1))
+            .and_then(|era| era.parse::<u64>().ok())
+            .ok_or_else(|| format!("Unable to determine the era of {path:?}"))?;
+        files.push((era, path));
+    }
+    files.sort_by(|(a, _), (b, _)| b.cmp(a));
+    Ok(files)
+}
+
+/// Import the blocks, blobs and states from the era files in `input_dir`.
+///
+/// Blocks are imported backwards from the oldest block in the database, like backfill sync, so
+/// each block must be the parent of the previously imported block. Blocks in the database
+/// already are skipped. States are only imported if they are restore points (which requires the
+/// slots per restore point to equal the era length). Each state is verified against the state
+/// roots of the state imported after it, beginning with the oldest state in the database.
+pub fn import_eras<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    db: &HotColdDB<E, Hot, Cold>,
+    input_dir: &Path,
+    log: &Logger,
+) -> Result<(), String> {
+    let slots_per_era = E::slots_per_historical_root() as u64;
+    let spec = db.get_chain_spec().clone();
+
+    let Some(anchor) = db.get_anchor_info() else {
+        info!(
+            log,
+            "Database already contains all historic blocks and states"
+        );
+        return Ok(());
+    };
+
+    // The state that the next imported state is verified against.
+    let mut trusted_state = if db.get_config().slots_per_restore_point != slots_per_era {
+        warn!(
+            log,
+            "Skipping import of states";
+            "reason" => "slots per restore point differs from era length",
+            "slots_per_restore_point" => db.get_config().slots_per_restore_point,
+        );
+        None
+    } else if anchor.state_upper_limit == STATE_UPPER_LIMIT_NO_RETAIN {
+        warn!(
+            log,
+            "Skipping import of states";
+            "reason" => "historic states are not retained",
+        );
+        None
+    } else if anchor.state_upper_limit <= db.get_split_slot() {
+        db.load_cold_state_by_slot(anchor.state_upper_limit)
+            .map_err(|e| format!("Unable to load state upper limit: {e:?}"))?
+    } else {
+        let split = db.get_split_info();
+        db.get_state(&split.state_root, Some(split.slot))
+            .map_err(|e| format!("Unable to load split state: {e:?}"))?
+    };
+    let mut highest_state_slot = None;
+    let mut lowest_state_slot = None;
+
+    for (era, path) in era_files_descending(input_dir)? {
+        let mut era_contents = Era::<E>::read(&path, &spec)?;
+
+        let num_blocks = import_blocks(db, &mut era_contents, log)?;
+
+        let state_slot = era_contents.state.slot();
+        let state_in_range = state_slot > anchor.state_lower_limit
+            && state_slot < anchor.state_upper_limit
+            && state_slot % slots_per_era == 0;
+        let mut imported_state = false;
+        if let Some(trusted) = trusted_state.as_ref().filter(|_| state_in_range) {
+            let mut state = era_contents.state;
+            let state_root = state
+                .update_tree_hash_cache()
+                .map_err(|e| format!("Unable to compute state root: {e:?}"))?;
+
+            match trusted.get_state_root(state_slot) {
+                Ok(expected_state_root) if *expected_state_root == state_root => {
+                    let mut ops = vec![];
+                    db.store_cold_state(&state_root, &state, &mut ops)
+                        .and_then(|()| db.cold_db.do_atomically(ops))
+                        .map_err(|e| format!("Unable to store state: {e:?}"))?;
+                    highest_state_slot.get_or_insert(state_slot);
+                    lowest_state_slot = Some(state_slot);
+                    trusted_state = Some(state);
+                    imported_state = true;
+                }
+                Ok(expected_state_root) => {
+                    return Err(format!(
+                        "State root mismatch at slot {state_slot}: era file has {state_root:?}, \
+                         expected {expected_state_root:?}"
+                    ));
+                }
+                Err(_) => {
+                    warn!(
+                        log,
+                        "Skipping import of states";
+                        "reason" => "era files are not contiguous with the database",
+                        "slot" => state_slot,
+                    );
+                    trusted_state = None;
+                }
+            }
+        }
+
+        info!(
+            log,
+            "Imported era";
+            "era" => era,
+            "blocks" => num_blocks,
+            "state" => imported_state,
+        );
+    }
+
+    // If every restore point between the old and new lower limit has been imported, raise the
+    // lower limit so that the states are available without reconstruction.
+    let anchor = db
+        .get_anchor_info()
+        .ok_or("Anchor info was removed during import")?;
+    if let (Some(highest_state_slot), Some(lowest_state_slot)) =
+        (highest_state_slot, lowest_state_slot)
+    {
+        let contiguous = lowest_state_slot <= anchor.state_lower_limit + slots_per_era
+            && anchor.oldest_block_slot == 0;
+        if contiguous {
+            let new_anchor = AnchorInfo {
+                state_lower_limit: highest_state_slot,
+                ..anchor.clone()
+            };
+            db.compare_and_set_anchor_info_with_write(Some(anchor), Some(new_anchor))
+                .map_err(|e| format!("Unable to update anchor: {e:?}"))?;
+        } else {
+            warn!(
+                log,
+                "Imported states are not contiguous with genesis";
+                "info" => "states will be available once historic state reconstruction completes",
+            );
+        }
+    }
+
+    info!(log, "Import complete");
+    Ok(())
+}
+
+/// Import the blocks of an era which are older than the oldest block in the database.
+fn import_blocks<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    db: &HotColdDB<E, Hot, Cold>,
+    era: &mut Era<E>,
+    log: &Logger,
+) -> Result<usize, String> {
+    let anchor_info = db
+        .get_anchor_info()
+        .ok_or("Anchor info was removed during import")?;
+    let blob_info = db.get_blob_info();
+
+    let mut expected_block_root = anchor_info.oldest_block_parent;
+    let mut prev_block_slot = anchor_info.oldest_block_slot;
+    let mut new_oldest_blob_slot = blob_info.oldest_blob_slot;
+    let mut chunk_writer =
+        ChunkWriter::<BlockRoots, _, _>::new(&db.cold_db, prev_block_slot.as_usize())
+            .map_err(|e| format!("Unable to read block roots: {e:?}"))?;
+
+    let mut blob_batch = vec![];
+    let mut hot_batch = vec![];
+    let mut cold_batch = vec![];
+    let mut num_blocks = 0;
+
+    for (block_root, block) in std::mem::take(&mut era.blocks).into_iter().rev() {
+        let slot = match &block {
+            DatabaseBlock::Full(block) => block.slot(),
+            DatabaseBlock::Blinded(block) => block.slot(),
+        };
+        if slot >= prev_block_slot {
+            continue;
+        }
+        if block_root != expected_block_root {
+            return Err(format!(
+                "Block root mismatch at slot {slot}: era file has {block_root:?}, expected \
+                 {expected_block_root:?}. Era files must be contiguous with the oldest block \
+                 in the database"
+            ));
+        }
+
+        let parent_root = match block {
+            DatabaseBlock::Full(block) => db
+                .block_as_kv_store_ops(&block_root, block, &mut hot_batch)
+                .map_err(|e| format!("Unable to store block: {e:?}"))?
+                .parent_root(),
+            DatabaseBlock::Blinded(block) => {
+                db.blinded_block_as_kv_store_ops(&block_root, &block, &mut hot_batch);
+                block.parent_root()
+            }
+        };
+        if let Some(blobs) = era.blobs.remove(&block_root) {
+            new_oldest_blob_slot = Some(slot);
+            db.blobs_as_kv_store_ops(&block_root, blobs, &mut blob_batch);
+        }
+
+        // Store block roots, including at all skip slots in the freezer DB.
+        for slot in (slot.as_usize()..prev_block_slot.as_usize()).rev() {
+            chunk_writer
+                .set(slot, block_root, &mut cold_batch)
+                .map_err(|e| format!("Unable to store block root: {e:?}"))?;
+        }
+
+        prev_block_slot = slot;
+        expected_block_root = parent_root;
+        num_blocks += 1;
+    }
+
+    if num_blocks == 0 {
+        return Ok(0);
+    }
+
+    chunk_writer
+        .write(&mut cold_batch)
+        .map_err(|e| format!("Unable to store block roots: {e:?}"))?;
+
+    // Write the blocks first, as it's better for the hot DB to contain extra blocks than for the
+    // cold DB to point to blocks that do not exist.
+    db.blobs_db
+        .do_atomically(blob_batch)
+        .and_then(|()| db.hot_db.do_atomically(hot_batch))
+        .and_then(|()| db.cold_db.do_atomically(cold_batch))
+        .map_err(|e| format!("Unable to write blocks: {e:?}"))?;
+
+    let mut anchor_and_blob_batch = vec![];
+    if new_oldest_blob_slot != blob_info.oldest_blob_slot {
+        let new_blob_info = BlobInfo {
+            oldest_blob_slot: new_oldest_blob_slot,
+            ..blob_info.clone()
+        };
+        anchor_and_blob_batch.push(
+            db.compare_and_set_blob_info(blob_info, new_blob_info)
+                .map_err(|e| format!("Unable to update blob info: {e:?}"))?,
+        );
+    }
+    let new_anchor = AnchorInfo {
+        oldest_block_slot: prev_block_slot,
+        oldest_block_parent: expected_block_root,
+        ..anchor_info.clone()
+    };
+    anchor_and_blob_batch.push(
+        db.compare_and_set_anchor_info(Some(anchor_info), Some(new_anchor))
+            .map_err(|e| format!("Unable to update anchor: {e:?}"))?,
+    );
+    db.hot_db
+        .do_atomically(anchor_and_blob_batch)
+        .map_err(|e| format!("Unable to update anchor: {e:?}"))?;
+
+    if prev_block_slot == 0 {
+        info!(log, "All blocks back to genesis have been imported");
+    }
+    Ok(num_blocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::{
+        AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
+    };
+    use beacon_chain::{ChainConfig, WhenSlotSkipped};
+    use store::StoreConfig;
+    use types::MinimalEthSpec;
+
+    type E = MinimalEthSpec;
+
+    #[test]
+    fn records_roundtrip() {
+        let mut writer = EraWriter::new(vec![]);
+        writer.write_record(VERSION, &[]).unwrap();
+        let block_offset = writer
+            .write_compressed(COMPRESSED_SIGNED_BEACON_BLOCK, &[1, 2, 3])
+            .unwrap();
+        writer
+            .write_slot_index(Slot::new(64), &[Some(block_offset), None])
+            .unwrap();
+        let bytes = writer.inner;
+
+        let mut reader = EraReader::new(bytes.as_slice()).unwrap();
+        let mut records = vec![];
+        while let Some(record) = reader.read_record().unwrap() {
+            records.push(record);
+        }
+        // The version record is consumed by `EraReader::new`.
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].0, COMPRESSED_SIGNED_BEACON_BLOCK);
+        assert_eq!(decompress(&records[0].1).unwrap(), vec![1, 2, 3]);
+
+        // Index: start slot, one offset per slot relative to the index record, then the count.
+        let (record_type, index) = &records[1];
+        assert_eq!(*record_type, SLOT_INDEX);
+        let index_position = bytes.len() as i64 - HEADER_LEN as i64 - index.len() as i64;
+        let read_i64 = |i: usize| i64::from_le_bytes(index[i * 8..(i + 1) * 8].try_into().unwrap());
+        assert_eq!(read_i64(0), 64);
+        assert_eq!(read_i64(1), block_offset as i64 - index_position);
+        assert_eq!(read_i64(2), 0);
+        assert_eq!(read_i64(3), 2);
+    }
+
+    #[test]
+    fn missing_version() {
+        let mut writer = EraWriter::new(vec![]);
+        writer.write_record(COMPRESSED_BEACON_STATE, &[]).unwrap();
+        assert!(EraReader::new(writer.inner.as_slice()).is_err());
+    }
+
+    #[test]
+    fn invalid_record_length() {
+        let mut writer = EraWriter::new(vec![]);
+        writer.write_record(VERSION, &[]).unwrap();
+        let mut bytes = writer.inner;
+
+        // A header claiming more data than the file contains.
+        let mut truncated = bytes.clone();
+        truncated.extend_from_slice(&COMPRESSED_BEACON_STATE);
+        truncated.extend_from_slice(&100u32.to_le_bytes());
+        truncated.extend_from_slice(&[0, 0, 1, 2, 3]);
+        let mut reader = EraReader::new(truncated.as_slice()).unwrap();
+        assert!(reader.read_record().is_err());
+
+        // A header claiming more data than any valid record.
+        bytes.extend_from_slice(&COMPRESSED_BEACON_STATE);
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        bytes.extend_from_slice(&[0, 0]);
+        let mut reader = EraReader::new(bytes.as_slice()).unwrap();
+        assert!(reader.read_record().is_err());
+    }
+
+    #[tokio::test]
+    async fn export_import_roundtrip() {
+        let log = Logger::root(slog::Discard, slog::o!());
+        let slots_per_era = E::slots_per_historical_root() as u64;
+
+        // An archive node with a finalized chain spanning more than three eras.
+        let harness = BeaconChainHarness::<EphemeralHarnessType<E>>::builder(E::default())
+            .default_spec()
+            .deterministic_keypairs(24)
+            .fresh_ephemeral_store()
+            .mock_execution_layer()
+            .chain_config(ChainConfig {
+                reconstruct_historic_states: true,
+                ..ChainConfig::default()
+            })
+            .build();
+        harness.advance_slot();
+        harness
+            .extend_chain(
+                5 * slots_per_era as usize,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::AllValidators,
+            )
+            .await;
+        let source = harness.chain.store.clone();
+        let checkpoint_slot = Slot::new(3 * slots_per_era);
+        assert!(source.get_split_slot() > checkpoint_slot);
+
+        let dir = tempfile::tempdir().unwrap();
+        export_eras(&source, dir.path(), Some(1), Some(3), &log).unwrap();
+
+        // A database checkpoint synced to the end of era 3, with the checkpoint state in the
+        // freezer so that imported states can be verified against it.
+        let target =
+            HotColdDB::open_ephemeral(StoreConfig::default(), harness.spec.clone(), log.clone())
+                .unwrap();
+        let mut checkpoint_state = source
+            .load_cold_state_by_slot(checkpoint_slot)
+            .unwrap()
+            .unwrap();
+        let checkpoint_state_root = checkpoint_state.update_tree_hash_cache().unwrap();
+        let checkpoint_block_root = harness
+            .chain
+            .block_root_at_slot(checkpoint_slot, WhenSlotSkipped::None)
+            .unwrap()
+            .unwrap();
+        let checkpoint_block = source
+            .get_blinded_block(&checkpoint_block_root)
+            .unwrap()
+            .unwrap();
+        target.set_split(
+            checkpoint_slot,
+            checkpoint_state_root,
+            checkpoint_block_root,
+        );
+        let mut cold_ops = vec![];
+        target
+            .store_cold_state(&checkpoint_state_root, &checkpoint_state, &mut cold_ops)
+            .unwrap();
+        target.cold_db.do_atomically(cold_ops).unwrap();
+        let anchor = AnchorInfo {
+            anchor_slot: checkpoint_slot,
+            oldest_block_slot: checkpoint_slot,
+            oldest_block_parent: checkpoint_block.parent_root(),
+            state_upper_limit: checkpoint_slot,
+            state_lower_limit: Slot::new(0),
+        };
+        let anchor_op = target
+            .compare_and_set_anchor_info(None, Some(anchor))
+            .unwrap();
+        target.hot_db.do_atomically(vec![anchor_op]).unwrap();
+
+        import_eras(&target, dir.path(), &log).unwrap();
+
+        // Every block back to genesis has been imported, along with the states at the start of
+        // eras 1 and 2.
+        let anchor = target.get_anchor_info().unwrap();
+        assert_eq!(anchor.oldest_block_slot, 0);
+        assert_eq!(anchor.state_lower_limit, Slot::new(2 * slots_per_era));
+        for slot in (0..checkpoint_slot.as_u64()).map(Slot::new) {
+            let block_root = harness
+                .chain
+                .block_root_at_slot(slot, WhenSlotSkipped::None)
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                target.get_blinded_block(&block_root).unwrap(),
+                source.get_blinded_block(&block_root).unwrap(),
+                "block at slot {slot}"
+            );
+        }
+        for slot in [slots_per_era, 2 * slots_per_era].map(Slot::new) {
+            let imported_root = target
+                .load_cold_state_by_slot(slot)
+                .unwrap()
+                .map(|mut state| state.update_tree_hash_cache().unwrap());
+            let expected_root = source
+                .load_cold_state_by_slot(slot)
+                .unwrap()
+                .map(|mut state| state.update_tree_hash_cache().unwrap());
+            assert!(imported_root.is_some(), "state at slot {slot}");
+            assert_eq!(imported_root, expected_root, "state at slot {slot}");
+        }
+    }
+}
@@ -1,12 +1,20 @@
 pub mod cli;
+mod era;
+mod verify;
+
+use crate::cli::ConvertFreezer;
 use crate::cli::DatabaseManager;
+use crate::cli::ExportEra;
+use crate::cli::ImportEra;
 use crate::cli::Migrate;
+use crate::cli::MigrateSlasher;
 use crate::cli::PruneStates;
+use crate::cli::Verify;
 use beacon_chain::{
     builder::Witness, eth1_chain::CachingEth1Backend, schema_change::migrate_schema,
     slot_clock::SystemTimeSlotClock,
 };
-use beacon_node::{get_data_dir, get_slots_per_restore_point, ClientConfig};
+use beacon_node::{get_data_dir, get_slots_per_restore_point, ClientConfig, DEFAULT_SLASHER_DIR};
 use clap::ArgMatches;
 use clap::ValueEnum;
 use cli::{Compact, Inspect};
@@ -20,7 +28,7 @@ use store::metadata::STATE_UPPER_LIMIT_NO_RETAIN;
 use store::{
     errors::Error,
     metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION},
-    DBColumn, HotColdDB, KeyValueStore, LevelDB,
+    BeaconNodeBackend, DBColumn, DatabaseBackend, HotColdDB, KeyValueStore,
 };
 use strum::{EnumString, EnumVariantNames};
 use types::{BeaconState, EthSpec, Slot};
@@ -46,6 +54,13 @@ fn parse_client_config<E: EthSpec>(
     client_config.store.slots_per_restore_point = sprp;
     client_config.store.slots_per_restore_point_set_explicitly = sprp_explicit;
     client_config.store.blob_prune_margin_epochs = database_manager_config.blob_prune_margin_epochs;
+    if let Some(backend) = database_manager_config.backend {
+        client_config.store.backend = backend;
+    }
+    client_config
+        .store
+        .hierarchy_config
+        .clone_from(&database_manager_config.hierarchy_exponents);
 
     Ok(client_config)
 }
@@ -61,7 +76,7 @@ pub fn display_db_version<E: EthSpec>(
     let blobs_path = client_config.get_blobs_db_path();
 
     let mut version = CURRENT_SCHEMA_VERSION;
-    HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -150,12 +165,16 @@ pub fn inspect_db<E: EthSpec>(
     let mut total = 0;
     let mut num_keys = 0;
 
+    let backend = client_config.store.backend;
     let sub_db = if inspect_config.freezer {
-        LevelDB::<E>::open(&cold_path).map_err(|e| format!("Unable to open freezer DB: {e:?}"))?
+        BeaconNodeBackend::<E>::open(backend, &cold_path)
+            .map_err(|e| format!("Unable to open freezer DB: {e:?}"))?
     } else if inspect_config.blobs_db {
-        LevelDB::<E>::open(&blobs_path).map_err(|e| format!("Unable to open blobs DB: {e:?}"))?
+        BeaconNodeBackend::<E>::open(backend, &blobs_path)
+            .map_err(|e| format!("Unable to open blobs DB: {e:?}"))?
     } else {
-        LevelDB::<E>::open(&hot_path).map_err(|e| format!("Unable to open hot DB: {e:?}"))?
+        BeaconNodeBackend::<E>::open(backend, &hot_path)
+            .map_err(|e| format!("Unable to open hot DB: {e:?}"))?
     };
 
     let skip = inspect_config.skip.unwrap_or(0);
@@ -268,20 +287,38 @@ pub fn compact_db<E: EthSpec>(
     let blobs_path = client_config.get_blobs_db_path();
     let column = compact_config.column;
 
+    let backend = client_config.store.backend;
+
     let (sub_db, db_name) = if compact_config.freezer {
-        (LevelDB::<E>::open(&cold_path)?, "freezer_db")
+        (
+            BeaconNodeBackend::<E>::open(backend, &cold_path)?,
+            "freezer_db",
+        )
     } else if compact_config.blobs_db {
-        (LevelDB::<E>::open(&blobs_path)?, "blobs_db")
+        (
+            BeaconNodeBackend::<E>::open(backend, &blobs_path)?,
+            "blobs_db",
+        )
     } else {
-        (LevelDB::<E>::open(&hot_path)?, "hot_db")
+        (BeaconNodeBackend::<E>::open(backend, &hot_path)?, "hot_db")
     };
-    info!(
-        log,
-        "Compacting database";
-        "db" => db_name,
-        "column" => ?column
-    );
-    sub_db.compact_column(column)?;
+    if backend == DatabaseBackend::LevelDb {
+        info!(
+            log,
+            "Compacting database";
+            "db" => db_name,
+            "column" => ?column
+        );
+        sub_db.compact_column(column)?;
+    } else {
+        // Other backends can't compact a single column, so compact the whole database.
+        info!(
+            log,
+            "Compacting database";
+            "db" => db_name,
+        );
+        sub_db.compact()?;
+    }
     Ok(())
 }
 
@@ -308,7 +345,7 @@ pub fn migrate_db<E: EthSpec>(
 
     let mut from = CURRENT_SCHEMA_VERSION;
     let to = migrate_config.to;
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -348,7 +385,7 @@ pub fn prune_payloads<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -374,7 +411,7 @@ pub fn prune_blobs<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -411,7 +448,7 @@ pub fn prune_states<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -473,6 +510,189 @@ pub fn prune_states<E: EthSpec>(
     Ok(())
 }
 
+pub fn convert_freezer<E: EthSpec>(
+    client_config: ClientConfig,
+    convert_config: &ConvertFreezer,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), Error> {
+    let spec = &runtime_context.eth2_config.spec;
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )?;
+
+    let target = convert_config.target_exponents.clone();
+    info!(
+        log,
+        "Converting freezer database";
+        "target_exponents" => target.as_ref().map_or("none".to_string(), ToString::to_string),
+    );
+    db.convert_freezer_hierarchy(target)?;
+
+    info!(
+        log,
+        "Freezer database converted, restart the beacon node with matching --hierarchy-exponents"
+    );
+    Ok(())
+}
+
+pub fn export_era<E: EthSpec>(
+    client_config: ClientConfig,
+    export_config: &ExportEra,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    let spec = &runtime_context.eth2_config.spec;
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to open database: {e:?}"))?;
+
+    era::export_eras(
+        &db,
+        &export_config.output_dir,
+        export_config.start_era,
+        export_config.end_era,
+        &log,
+    )
+}
+
+pub fn import_era<E: EthSpec>(
+    client_config: ClientConfig,
+    import_config: &ImportEra,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    let spec = &runtime_context.eth2_config.spec;
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to open database: {e:?}"))?;
+
+    era::import_eras(&db, &import_config.input_dir, &log)
+}
+
+pub fn verify_db<E: EthSpec>(
+    client_config: ClientConfig,
+    verify_config: &Verify,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), String> {
+    let spec = &runtime_context.eth2_config.spec;
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    let blobs_path = client_config.get_blobs_db_path();
+
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
+        &hot_path,
+        &cold_path,
+        &blobs_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to open database: {e:?}"))?;
+
+    let inconsistencies =
+        verify::verify_db(&db, &log).map_err(|e| format!("Unable to verify database: {e:?}"))?;
+    if inconsistencies.is_empty() {
+        info!(log, "No inconsistencies found");
+        return Ok(());
+    }
+
+    let num_unrepairable = verify::report_inconsistencies(&inconsistencies, &log);
+    let num_repairable = inconsistencies.len() - num_unrepairable;
+    if verify_config.repair && num_repairable > 0 {
+        verify::repair_inconsistencies(&db, &inconsistencies, &log)
+            .map_err(|e| format!("Unable to repair database: {e:?}"))?;
+        info!(log, "Repaired inconsistencies"; "count" => num_repairable);
+    } else if num_repairable > 0 {
+        warn!(
+            log,
+            "Some inconsistencies can be repaired";
+            "count" => num_repairable,
+            "info" => "re-run this command with --repair",
+        );
+    }
+
+    if num_unrepairable > 0 || !verify_config.repair {
+        Err(format!(
+            "Database has {} inconsistencies, of which {num_unrepairable} cannot be repaired \
+             automatically",
+            inconsistencies.len()
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn migrate_slasher(
+    client_config: ClientConfig,
+    migrate_config: &MigrateSlasher,
+    log: Logger,
+) -> Result<(), String> {
+    let source_dir = migrate_config
+        .slasher_dir
+        .clone()
+        .unwrap_or_else(|| client_config.data_dir().join(DEFAULT_SLASHER_DIR));
+
+    let mut source = slasher::Config::new(source_dir);
+    source.backend = migrate_config.from;
+    let mut target = slasher::Config::new(migrate_config.output_dir.clone());
+    target.backend = migrate_config.to;
+
+    info!(
+        log,
+        "Migrating slasher database";
+        "from" => %source.backend,
+        "to" => %target.backend,
+        "source_dir" => source.database_path.display(),
+        "output_dir" => target.database_path.display(),
+    );
+
+    let counts = slasher::migrate_backend(&source, &target, &log)
+        .map_err(|e| format!("Unable to migrate slasher database: {e:?}"))?;
+
+    info!(
+        log,
+        "Slasher database migrated";
+        "entries" => counts.iter().map(|(_, count)| count).sum::<usize>(),
+        "info" => "start the beacon node with the new --slasher-dir and --slasher-backend",
+    );
+    Ok(())
+}
+
 /// Run the database manager, returning an error string if the operation did not succeed.
 pub fn run<E: EthSpec>(
     cli_args: &ArgMatches,
@@ -530,5 +750,20 @@ pub fn run<E: EthSpec>(
             let compact_config = parse_compact_config(compact_config)?;
             compact_db::<E>(compact_config, client_config, log).map_err(format_err)
         }
+        cli::DatabaseManagerSubcommand::ConvertFreezer(convert_config) => {
+            convert_freezer(client_config, convert_config, &context, log).map_err(format_err)
+        }
+        cli::DatabaseManagerSubcommand::ExportEra(export_config) => {
+            export_era(client_config, export_config, &context, log)
+        }
+        cli::DatabaseManagerSubcommand::ImportEra(import_config) => {
+            import_era(client_config, import_config, &context, log)
+        }
+        cli::DatabaseManagerSubcommand::Verify(verify_config) => {
+            verify_db(client_config, verify_config, &context, log)
+        }
+        cli::DatabaseManagerSubcommand::MigrateSlasher(migrate_config) => {
+            migrate_slasher(client_config, migrate_config, log)
+        }
     }
 }
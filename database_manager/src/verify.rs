@@ -0,0 +1,610 @@
+//! Consistency checks for a `HotColdDB`, used to diagnose databases corrupted by crashes or
+//! power loss.
+use slog::{info, warn, Logger};
+use std::time::{SystemTime, UNIX_EPOCH};
+use store::metadata::STATE_UPPER_LIMIT_NO_RETAIN;
+use store::{DBColumn, Error, HotColdDB, ItemStore, KeyValueStore};
+use types::{ChainSpec, EthSpec, Hash256, SignedBlindedBeaconBlock, Slot};
+
+/// An inconsistency found in the database.
+#[derive(Debug, PartialEq)]
+pub enum Inconsistency {
+    /// A block could not be decoded.
+    InvalidBlock { block_root: Hash256, error: String },
+    /// A block's parent is not in the database, and the block is not the oldest block.
+    ///
+    /// The block is `orphaned` if it is prior to the split and not canonical, in which case it
+    /// can be deleted.
+    MissingParent {
+        block_root: Hash256,
+        slot: Slot,
+        parent_root: Hash256,
+        orphaned: bool,
+    },
+    /// The frozen block root at `slot` refers to a block that is not in the database.
+    MissingFrozenBlock { slot: Slot, block_root: Hash256 },
+    /// The frozen block root at `slot` does not match the chain of blocks.
+    FrozenBlockRootMismatch { slot: Slot, block_root: Hash256 },
+    /// The frozen state root at `slot` is missing or refers to a state from a different slot.
+    FrozenStateRootMismatch {
+        slot: Slot,
+        state_root: Hash256,
+        summary_slot: Option<Slot>,
+    },
+    /// The split state or block is missing, or doesn't match the split.
+    InvalidSplit(String),
+    /// The anchor or blob info is inconsistent with the split or with itself.
+    InvalidAnchor(String),
+    /// A block within the data availability window is missing some of its blobs.
+    MissingBlobs {
+        block_root: Hash256,
+        slot: Slot,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Inconsistency {
+    /// Whether `repair_inconsistencies` is able to fix this inconsistency.
+    fn is_repairable(&self) -> bool {
+        matches!(
+            self,
+            Inconsistency::MissingParent { orphaned: true, .. }
+                | Inconsistency::FrozenBlockRootMismatch { .. }
+        )
+    }
+}
+
+/// Check the consistency of the database, returning every inconsistency found.
+pub fn verify_db<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    db: &HotColdDB<E, Hot, Cold>,
+    log: &Logger,
+) -> Result<Vec<Inconsistency>, Error> {
+    let mut inconsistencies = vec![];
+
+    info!(log, "Checking split and anchor");
+    let current_slot = verify_split_and_anchor(db, &mut inconsistencies)?;
+
+    info!(log, "Checking blocks and blobs");
+    let da_start_slot =
+        current_slot.and_then(|current_slot| data_availability_start(db, current_slot));
+    verify_blocks(db, da_start_slot, &mut inconsistencies)?;
+
+    info!(log, "Checking frozen block roots");
+    verify_frozen_block_roots(db, &mut inconsistencies)?;
+
+    info!(log, "Checking frozen state roots");
+    verify_frozen_state_roots(db, &mut inconsistencies)?;
+
+    Ok(inconsistencies)
+}
+
+/// Check the split state and block, and that the anchor and blob info are consistent with them.
+///
+/// Returns the current slot according to the genesis time of the split state, if available.
+fn verify_split_and_anchor<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    db: &HotColdDB<E, Hot, Cold>,
+    inconsistencies: &mut Vec<Inconsistency>,
+) -> Result<Option<Slot>, Error> {
+    let split = db.get_split_info();
+    let spec = db.get_chain_spec();
+    let mut current_slot = None;
+
+    match db.load_hot_state_summary(&split.state_root)? {
+        Some(summary) if summary.slot == split.slot => (),
+        Some(summary) => inconsistencies.push(Inconsistency::InvalidSplit(format!(
+            "split state {:?} has slot {}, expected {}",
+            split.state_root, summary.slot, split.slot
+        ))),
+        None => inconsistencies.push(Inconsistency::InvalidSplit(format!(
+            "split state {:?} has no summary",
+            split.state_root
+        ))),
+    }
+
+    match db.get_blinded_block(&split.block_root)? {
+        Some(block) if block.slot() <= split.slot => (),
+        Some(block) => inconsistencies.push(Inconsistency::InvalidSplit(format!(
+            "split block {:?} has slot {}, which is after the split slot {}",
+            split.block_root,
+            block.slot(),
+            split.slot
+        ))),
+        None => inconsistencies.push(Inconsistency::InvalidSplit(format!(
+            "split block {:?} is missing",
+            split.block_root
+        ))),
+    }
+
+    if let Some(state) = db.get_state(&split.state_root, Some(split.slot))? {
+        let seconds_since_genesis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| now.as_secs().saturating_sub(state.genesis_time()));
+        current_slot = Some(Slot::new(seconds_since_genesis / spec.seconds_per_slot));
+
+        // The last frozen block root should match the split state.
+        if split.slot > 0 && split.slot > db.get_oldest_block_slot() {
+            let slot = split.slot - 1;
+            let expected = *state.get_block_root(slot)?;
+            let frozen = frozen_block_root(db, slot, spec)?;
+            if frozen != Some(expected) {
+                inconsistencies.push(Inconsistency::FrozenBlockRootMismatch {
+                    slot,
+                    block_root: frozen.unwrap_or_default(),
+                });
+            }
+        }
+    } else {
+        inconsistencies.push(Inconsistency::InvalidSplit(format!(
+            "split state {:?} is missing",
+            split.state_root
+        )));
+    }
+
+    let sprp = db.get_config().slots_per_restore_point;
+    if let Some(anchor) = db.get_anchor_info() {
+        if anchor.oldest_block_slot > split.slot {
+            inconsistencies.push(Inconsistency::InvalidAnchor(format!(
+                "oldest block slot {} is after the split slot {}",
+                anchor.oldest_block_slot, split.slot
+            )));
+        }
+        if anchor.state_upper_limit != STATE_UPPER_LIMIT_NO_RETAIN {
+            if anchor.state_upper_limit % sprp != 0 {
+                inconsistencies.push(Inconsistency::InvalidAnchor(format!(
+                    "state upper limit {} is not a multiple of the slots per restore point {}",
+                    anchor.state_upper_limit, sprp
+                )));
+            }
+            if anchor.state_lower_limit > split.slot {
+                inconsistencies.push(Inconsistency::InvalidAnchor(format!(
+                    "state lower limit {} is after the split slot {}",
+                    anchor.state_lower_limit, split.slot
+                )));
+            }
+        }
+    }
+
+    let blob_info = db.get_blob_info();
+    if let Some(oldest_blob_slot) = blob_info.oldest_blob_slot {
+        if oldest_blob_slot < db.get_oldest_block_slot() {
+            inconsistencies.push(Inconsistency::InvalidAnchor(format!(
+                "oldest blob slot {} is before the oldest block slot {}",
+                oldest_blob_slot,
+                db.get_oldest_block_slot()
+            )));
+        }
+    }
+
+    Ok(current_slot)
+}
+
+/// Return the first slot for which blobs must be retained, or `None` prior to Deneb.
+fn data_availability_start<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    db: &HotColdDB<E, Hot, Cold>,
+    current_slot: Slot,
+) -> Option<Slot> {
+    let spec = db.get_chain_spec();
+    let deneb_fork_epoch = spec.deneb_fork_epoch?;
+    let boundary_epoch = current_slot
+        .epoch(E::slots_per_epoch())
+        .saturating_sub(spec.min_epochs_for_blob_sidecars_requests);
+    Some(std::cmp::max(deneb_fork_epoch, boundary_epoch).start_slot(E::slots_per_epoch()))
+}
+
+/// Check the parent link of every block, and that blocks within the data availability window
+/// have all of their blobs.
+fn verify_blocks<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    db: &HotColdDB<E, Hot, Cold>,
+    da_start_slot: Option<Slot>,
+    inconsistencies: &mut Vec<Inconsistency>,
+) -> Result<(), Error> {
+    let spec = db.get_chain_spec();
+    let split_slot = db.get_split_slot();
+    let oldest_block_parent = db
+        .get_anchor_info()
+        .map_or(Hash256::zero(), |anchor| anchor.oldest_block_parent);
+
+    for res in db.hot_db.iter_column::<Hash256>(DBColumn::BeaconBlock) {
+        let (block_root, bytes) = res?;
+        let block = match SignedBlindedBeaconBlock::<E>::from_ssz_bytes(&bytes, spec) {
+            Ok(block) => block,
+            Err(e) => {
+                inconsistencies.push(Inconsistency::InvalidBlock {
+                    block_root,
+                    error: format!("{e:?}"),
+                });
+                continue;
+            }
+        };
+        let slot = block.slot();
+        let parent_root = block.parent_root();
+
+        if !parent_root.is_zero()
+            && parent_root != oldest_block_parent
+            && !db.block_exists(&parent_root)?
+        {
+            let orphaned = slot < split_slot
+                && slot >= db.get_oldest_block_slot()
+                && frozen_block_root(db, slot, spec)? != Some(block_root);
+            inconsistencies.push(Inconsistency::MissingParent {
+                block_root,
+                slot,
+                parent_root,
+                orphaned,
+            });
+        }
+
+        if da_start_slot.map_or(true, |da_start_slot| slot < da_start_slot) {
+            continue;
+        }
+        let expected = block
+            .message()
+            .body()
+            .blob_kzg_commitments()
+            .map_or(0, |commitments| commitments.len());
+        if expected > 0 {
+            let found = db.get_blobs(&block_root)?.map_or(0, |blobs| blobs.len());
+            if found != expected {
+                inconsistencies.push(Inconsistency::MissingBlobs {
+                    block_root,
+                    slot,
+                    expected,
+                    found,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check that the frozen block roots refer to blocks in the database, and that each block root
+/// is either the block at that slot or repeats the previous block root at a skipped slot.
+fn verify_frozen_block_roots<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    db: &HotColdDB<E, Hot, Cold>,
+    inconsistencies: &mut Vec<Inconsistency>,
+) -> Result<(), Error> {
+    let split_slot = db.get_split_slot();
+    let oldest_block_slot = db.get_oldest_block_slot();
+    if split_slot <= oldest_block_slot {
+        return Ok(());
+    }
+
+    let block_roots = db.forwards_block_roots_iterator_until(
+        oldest_block_slot,
+        split_slot - 1,
+        || Err(frozen_only_error()),
+        db.get_chain_spec(),
+    )?;
+
+    // The previous block root along with the slot of its block.
+    let mut prev: Option<(Hash256, Slot)> = None;
+    for res in block_roots {
+        let (block_root, slot) = res?;
+
+        if let Some((prev_root, prev_block_slot)) = prev {
+            if block_root == prev_root && prev_block_slot < slot {
+                // Skipped slot.
+                continue;
+            }
+        }
+
+        let Some(block) = db.get_blinded_block(&block_root)? else {
+            inconsistencies.push(Inconsistency::MissingFrozenBlock { slot, block_root });
+            prev = None;
+            continue;
+        };
+
+        let links_to_prev = prev.map_or(true, |(prev_root, _)| block.parent_root() == prev_root);
+        if block.slot() != slot || !links_to_prev {
+            inconsistencies.push(Inconsistency::FrozenBlockRootMismatch { slot, block_root });
+        }
+        prev = Some((block_root, block.slot()));
+    }
+    Ok(())
+}
+
+/// Check that every available frozen state root refers to a state summary for the same slot.
+fn verify_frozen_state_roots<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    db: &HotColdDB<E, Hot, Cold>,
+    inconsistencies: &mut Vec<Inconsistency>,
+) -> Result<(), Error> {
+    let split_slot = db.get_split_slot();
+    if split_slot == 0 {
+        return Ok(());
+    }
+    let (lower_limit, upper_limit) = db.get_historic_state_limits();
+
+    // States are available up to and including the lower limit, and from the upper limit to the
+    // split.
+    let mut ranges = vec![];
+    if lower_limit >= upper_limit {
+        ranges.push((Slot::new(0), split_slot - 1));
+    } else {
+        ranges.push((Slot::new(0), std::cmp::min(lower_limit, split_slot - 1)));
+        if upper_limit < split_slot {
+            ranges.push((upper_limit, split_slot - 1));
+        }
+    }
+
+    for (start_slot, end_slot) in ranges {
+        let state_roots = db.forwards_state_roots_iterator_until(
+            start_slot,
+            end_slot,
+            || Err(frozen_only_error()),
+            db.get_chain_spec(),
+        )?;
+        for res in state_roots {
+            let (state_root, slot) = res?;
+            let summary_slot = db.load_cold_state_slot(&state_root)?;
+            if summary_slot != Some(slot) {
+                inconsistencies.push(Inconsistency::FrozenStateRootMismatch {
+                    slot,
+                    state_root,
+                    summary_slot,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn frozen_block_root<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    db: &HotColdDB<E, Hot, Cold>,
+    slot: Slot,
+    spec: &ChainSpec,
+) -> Result<Option<Hash256>, Error> {
+    db.forwards_block_roots_iterator_until(slot, slot, || Err(frozen_only_error()), spec)?
+        .next()
+        .transpose()
+        .map(|res| res.map(|(block_root, _)| block_root))
+}
+
+fn frozen_only_error() -> Error {
+    Error::DBError {
+        message: "Should not require end state".to_string(),
+    }
+}
+
+/// Repair the inconsistencies that can be fixed without data from the network.
+///
+/// Orphaned blocks prior to the split are deleted, and the frozen block roots are rewritten from the parent links of
+/// the split block.
+pub fn repair_inconsistencies<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    db: &HotColdDB<E, Hot, Cold>,
+    inconsistencies: &[Inconsistency],
+    log: &Logger,
+) -> Result<(), Error> {
+    let mut repair_block_roots = false;
+    for inconsistency in inconsistencies {
+        match inconsistency {
+            Inconsistency::MissingParent {
+                block_root,
+                slot,
+                orphaned: true,
+                ..
+            } => {
+                info!(
+                    log,
+                    "Deleting orphaned block";
+                    "block_root" => ?block_root,
+                    "slot" => slot,
+                );
+                db.delete_block(block_root)?;
+            }
+            Inconsistency::FrozenBlockRootMismatch { .. } => repair_block_roots = true,
+            _ => (),
+        }
+    }
+
+    if repair_block_roots {
+        let num_repaired = db.repair_frozen_block_roots()?;
+        info!(log, "Repaired frozen block roots"; "slots" => num_repaired);
+    }
+    Ok(())
+}
+
+/// Log each inconsistency, returning the number that can't be repaired.
+pub fn report_inconsistencies(inconsistencies: &[Inconsistency], log: &Logger) -> usize {
+    for inconsistency in inconsistencies {
+        warn!(
+            log,
+            "Database inconsistency";
+            "repairable" => inconsistency.is_repairable(),
+            "details" => ?inconsistency,
+        );
+    }
+    inconsistencies
+        .iter()
+        .filter(|inconsistency| !inconsistency.is_repairable())
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::{
+        AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
+    };
+    use beacon_chain::WhenSlotSkipped;
+    use std::sync::Arc;
+    use store::MemoryStore;
+    use types::MinimalEthSpec;
+
+    type E = MinimalEthSpec;
+    type Harness = BeaconChainHarness<EphemeralHarnessType<E>>;
+    type Db = HotColdDB<E, MemoryStore<E>, MemoryStore<E>>;
+
+    /// A frozen slot at which the database is corrupted.
+    const SLOT: Slot = Slot::new(10);
+
+    /// A harness with a finalized chain and a block at every slot.
+    async fn harness() -> Harness {
+        let harness = BeaconChainHarness::<EphemeralHarnessType<E>>::builder(E::default())
+            .default_spec()
+            .deterministic_keypairs(24)
+            .fresh_ephemeral_store()
+            .mock_execution_layer()
+            .build();
+        harness.advance_slot();
+        harness
+            .extend_chain(
+                5 * E::slots_per_epoch() as usize,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::AllValidators,
+            )
+            .await;
+        assert!(harness.chain.store.get_split_slot() > SLOT + 2);
+        harness
+    }
+
+    fn logger() -> Logger {
+        Logger::root(slog::Discard, slog::o!())
+    }
+
+    fn block_root_at(harness: &Harness, slot: Slot) -> Hash256 {
+        harness
+            .chain
+            .block_root_at_slot(slot, WhenSlotSkipped::None)
+            .unwrap()
+            .unwrap()
+    }
+
+    fn overwrite_frozen_block_root(db: &Arc<Db>, slot: Slot, block_root: Hash256) {
+        let ops = db
+            .store_frozen_block_root_at_skip_slots(slot, slot + 1, block_root)
+            .unwrap();
+        db.cold_db.do_atomically(ops).unwrap();
+    }
+
+    #[tokio::test]
+    async fn consistent_db() {
+        let harness = harness().await;
+        let db = &harness.chain.store;
+
+        assert_eq!(verify_db(db, &logger()).unwrap(), vec![]);
+    }
+
+    #[tokio::test]
+    async fn missing_frozen_block() {
+        let harness = harness().await;
+        let db = &harness.chain.store;
+        let block_root = block_root_at(&harness, SLOT);
+        let child_root = block_root_at(&harness, SLOT + 1);
+        db.delete_block(&block_root).unwrap();
+
+        let mut inconsistencies = vec![];
+        verify_blocks(db, None, &mut inconsistencies).unwrap();
+        assert_eq!(
+            inconsistencies,
+            vec![Inconsistency::MissingParent {
+                block_root: child_root,
+                slot: SLOT + 1,
+                parent_root: block_root,
+                orphaned: false,
+            }]
+        );
+
+        let mut inconsistencies = vec![];
+        verify_frozen_block_roots(db, &mut inconsistencies).unwrap();
+        assert_eq!(
+            inconsistencies,
+            vec![Inconsistency::MissingFrozenBlock {
+                slot: SLOT,
+                block_root,
+            }]
+        );
+
+        // The block can't be recovered without the network, so repairing changes nothing.
+        let inconsistencies = verify_db(db, &logger()).unwrap();
+        assert_eq!(report_inconsistencies(&inconsistencies, &logger()), 2);
+        repair_inconsistencies(db, &inconsistencies, &logger()).unwrap();
+        assert_eq!(verify_db(db, &logger()).unwrap(), inconsistencies);
+    }
+
+    #[tokio::test]
+    async fn repair_frozen_block_root_mismatch() {
+        let harness = harness().await;
+        let db = &harness.chain.store;
+        let block_root = block_root_at(&harness, SLOT);
+        let next_root = block_root_at(&harness, SLOT + 1);
+        let wrong_root = block_root_at(&harness, SLOT + 2);
+        overwrite_frozen_block_root(db, SLOT, wrong_root);
+
+        let mut inconsistencies = vec![];
+        verify_frozen_block_roots(db, &mut inconsistencies).unwrap();
+        // The wrong block is at a later slot, and the next block doesn't descend from it.
+        let expected = vec![
+            Inconsistency::FrozenBlockRootMismatch {
+                slot: SLOT,
+                block_root: wrong_root,
+            },
+            Inconsistency::FrozenBlockRootMismatch {
+                slot: SLOT + 1,
+                block_root: next_root,
+            },
+        ];
+        assert_eq!(inconsistencies, expected);
+        assert_eq!(verify_db(db, &logger()).unwrap(), expected);
+        assert_eq!(report_inconsistencies(&expected, &logger()), 0);
+
+        repair_inconsistencies(db, &expected, &logger()).unwrap();
+        assert_eq!(
+            frozen_block_root(db, SLOT, &harness.spec).unwrap(),
+            Some(block_root)
+        );
+        assert_eq!(verify_db(db, &logger()).unwrap(), vec![]);
+    }
+
+    #[tokio::test]
+    async fn missing_frozen_state() {
+        let harness = harness().await;
+        let db = &harness.chain.store;
+        let state_root = harness.chain.state_root_at_slot(SLOT).unwrap().unwrap();
+        db.cold_db
+            .key_delete(DBColumn::BeaconStateSummary.into(), state_root.as_bytes())
+            .unwrap();
+
+        let mut inconsistencies = vec![];
+        verify_frozen_state_roots(db, &mut inconsistencies).unwrap();
+        let expected = vec![Inconsistency::FrozenStateRootMismatch {
+            slot: SLOT,
+            state_root,
+            summary_slot: None,
+        }];
+        assert_eq!(inconsistencies, expected);
+        assert_eq!(verify_db(db, &logger()).unwrap(), expected);
+        assert_eq!(report_inconsistencies(&expected, &logger()), 1);
+    }
+
+    #[tokio::test]
+    async fn repair_orphaned_block() {
+        let harness = harness().await;
+        let db = &harness.chain.store;
+
+        // A block prior to the split which isn't canonical and whose parent is missing.
+        let mut block = db
+            .get_blinded_block(&block_root_at(&harness, SLOT))
+            .unwrap()
+            .unwrap();
+        let parent_root = Hash256::repeat_byte(1);
+        *block.message_mut().parent_root_mut() = parent_root;
+        let block_root = block.canonical_root();
+        let mut ops = vec![];
+        db.blinded_block_as_kv_store_ops(&block_root, &block, &mut ops);
+        db.hot_db.do_atomically(ops).unwrap();
+
+        let expected = vec![Inconsistency::MissingParent {
+            block_root,
+            slot: SLOT,
+            parent_root,
+            orphaned: true,
+        }];
+        assert_eq!(verify_db(db, &logger()).unwrap(), expected);
+        assert_eq!(report_inconsistencies(&expected, &logger()), 0);
+
+        repair_inconsistencies(db, &expected, &logger()).unwrap();
+        assert!(!db.block_exists(&block_root).unwrap());
+        assert_eq!(verify_db(db, &logger()).unwrap(), vec![]);
+    }
+}
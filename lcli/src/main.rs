@@ -4,6 +4,7 @@ mod generate_bootnode_enr;
 mod indexed_attestations;
 mod mnemonic_validators;
 mod mock_el;
+mod pack_attestations;
 mod parse_ssz;
 mod skip_slots;
 mod state_root;
@@ -230,6 +231,57 @@ fn main() {
                         .display_order(0)
                 )
         )
+        .subcommand(
+            Command::new("pack-attestations")
+                .about("Compares the attestations packed by each operation pool strategy for \
+                    historical blocks")
+                .arg(
+                    Arg::new("beacon-url")
+                        .long("beacon-url")
+                        .value_name("URL")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("URL to a beacon-API provider.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("start-slot")
+                        .long("start-slot")
+                        .value_name("SLOT")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Slot of the first block to pack.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("end-slot")
+                        .long("end-slot")
+                        .value_name("SLOT")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Slot of the last block to pack.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("lookahead")
+                        .long("lookahead")
+                        .value_name("BLOCKS")
+                        .action(ArgAction::Set)
+                        .default_value("1")
+                        .help("Number of later blocks whose attestations are also available \
+                            to each block, if they are old enough to be included.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("time-budget")
+                        .long("time-budget")
+                        .value_name("MILLISECONDS")
+                        .action(ArgAction::Set)
+                        .default_value("100")
+                        .help("Time allowed for the reward-optimised strategy to pack each block.")
+                        .display_order(0)
+                )
+        )
         .subcommand(
             Command::new("pretty-ssz")
                 .about("Parses SSZ-encoded data from a file")
@@ -631,6 +683,11 @@ fn run<E: EthSpec>(env_builder: EnvironmentBuilder<E>, matches: &ArgMatches) ->
             skip_slots::run::<E>(env, network_config, matches)
                 .map_err(|e| format!("Failed to skip slots: {}", e))
         }
+        Some(("pack-attestations", matches)) => {
+            let network_config = get_network_config()?;
+            pack_attestations::run::<E>(env, network_config, matches)
+                .map_err(|e| format!("Failed to pack attestations: {}", e))
+        }
         Some(("pretty-ssz", matches)) => {
             let network_config = get_network_config()?;
             run_parse_ssz::<E>(network_config, matches)
@@ -0,0 +1,356 @@
+//! # Pack Attestations
+//!
+//! Use this tool to compare the attestations packed by the greedy and reward-optimised operation
+//! pool strategies against the attestations of historical blocks. Useful for benchmarking changes
+//! to attestation packing.
+//!
+//! For each block between the start and end slots, the attestations that were available to its
+//! proposer are approximated by those included in the block and in the following `--lookahead`
+//! blocks which were old enough to be included at the block's slot. Electra on-chain aggregates are
+//! split back into one attestation per committee. The pools are packed against the block's
+//! pre-state and the proposer reward of each selection is logged alongside the reward of the
+//! attestations which were actually included.
+//!
+//! Logging output is controlled via the `RUST_LOG` environment variable. For example, `export
+//! RUST_LOG=info`.
+//!
+//! ## Example
+//!
+//! Compare the packing of the blocks of an epoch, giving the reward-optimised strategy 200ms for
+//! each block:
+//!
+//! ```ignore
+//! lcli pack-attestations \
+//!     --beacon-url http://localhost:5052 \
+//!     --start-slot 9000000 \
+//!     --end-slot 9000031 \
+//!     --lookahead 2 \
+//!     --time-budget 200
+//! ```
+use clap::ArgMatches;
+use clap_utils::parse_required;
+use environment::Environment;
+use eth2::{
+    types::{BlockId, StateId},
+    BeaconNodeHttpClient, SensitiveUrl, Timeouts,
+};
+use eth2_network_config::Eth2NetworkConfig;
+use log::{debug, info};
+use operation_pool::{
+    AttMaxCover, CompactAttestationRef, OperationPool, PackingStrategy, RewardCache,
+    SplitAttestation,
+};
+use state_processing::common::get_attesting_indices_from_state;
+use state_processing::epoch_cache::initialize_epoch_cache;
+use state_processing::state_advance::complete_state_advance;
+use state_processing::{
+    per_block_processing, AllCaches, BlockSignatureStrategy, ConsensusContext, VerifyBlockRoot,
+};
+use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
+use types::{
+    AggregateSignature, Attestation, AttestationElectra, BeaconState, BitList, BitVector,
+    ChainSpec, EthSpec, SignedBeaconBlock, Slot,
+};
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The rewards and packing times for one block.
+#[derive(Debug, Default)]
+struct Comparison {
+    actual_reward: u64,
+    greedy_reward: u64,
+    optimised_reward: u64,
+    greedy_time: Duration,
+    optimised_time: Duration,
+}
+
+pub fn run<E: EthSpec>(
+    env: Environment<E>,
+    network_config: Eth2NetworkConfig,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let spec = &network_config.chain_spec::<E>()?;
+    let executor = env.core_context().executor;
+
+    let beacon_url: SensitiveUrl = parse_required(matches, "beacon-url")?;
+    let start_slot: Slot = parse_required(matches, "start-slot")?;
+    let end_slot: Slot = parse_required(matches, "end-slot")?;
+    let lookahead: usize = parse_required(matches, "lookahead")?;
+    let time_budget = Duration::from_millis(parse_required(matches, "time-budget")?);
+
+    if start_slot <= spec.genesis_slot {
+        return Err("Cannot run on the genesis block".to_string());
+    }
+    if end_slot < start_slot {
+        return Err("--end-slot must not be less than --start-slot".to_string());
+    }
+
+    info!("Using {} spec", E::spec_name());
+    info!("Time budget: {:?}", time_budget);
+
+    /*
+     * Download the blocks and the pre-state of the first block.
+     */
+
+    let client = BeaconNodeHttpClient::new(beacon_url, Timeouts::set_all(HTTP_TIMEOUT));
+    let (mut state, blocks) = executor
+        .handle()
+        .ok_or("shutdown in progress")?
+        .block_on(async move {
+            // Fetch enough slots beyond the end slot to find `lookahead` more blocks, allowing for
+            // a missed slot between each.
+            let last_slot = end_slot + (lookahead as u64) * 2;
+            let mut blocks: Vec<SignedBeaconBlock<E>> = vec![];
+            for slot in start_slot.as_u64()..=last_slot.as_u64() {
+                let block_id = BlockId::Slot(Slot::new(slot));
+                if let Some(response) = client
+                    .get_beacon_blocks(block_id)
+                    .await
+                    .map_err(|e| format!("Failed to download block: {:?}", e))?
+                {
+                    blocks.push(response.data);
+                }
+            }
+
+            let first_block = blocks
+                .first()
+                .ok_or_else(|| format!("No blocks between slots {start_slot} and {last_slot}"))?;
+            let parent_block: SignedBeaconBlock<E> = client
+                .get_beacon_blocks(BlockId::Root(first_block.parent_root()))
+                .await
+                .map_err(|e| format!("Failed to download parent block: {:?}", e))?
+                .ok_or("Unable to locate the parent of the first block")?
+                .data;
+
+            let state_id = StateId::Root(parent_block.state_root());
+            let pre_state = client
+                .get_debug_beacon_states::<E>(state_id)
+                .await
+                .map_err(|e| format!("Failed to download state: {:?}", e))?
+                .ok_or_else(|| format!("Unable to locate state at {:?}", state_id))?
+                .data;
+
+            Ok::<_, String>((pre_state, blocks))
+        })
+        .map_err(|e| format!("Failed to complete task: {:?}", e))?;
+
+    /*
+     * Pack the attestations of each block with both strategies.
+     */
+
+    let mut total = Comparison::default();
+    let mut num_blocks = 0;
+    for (i, block) in blocks.iter().enumerate() {
+        if block.slot() > end_slot {
+            break;
+        }
+
+        complete_state_advance(&mut state, None, block.slot(), spec)
+            .map_err(|e| format!("Unable to perform complete advance: {e:?}"))?;
+        state
+            .build_all_caches(spec)
+            .map_err(|e| format!("Unable to build caches: {:?}", e))?;
+        initialize_epoch_cache(&mut state, spec)
+            .map_err(|e| format!("Unable to initialize epoch cache: {:?}", e))?;
+
+        let available_attestations = blocks[i..]
+            .iter()
+            .take(lookahead + 1)
+            .flat_map(|later_block| later_block.message().body().attestations())
+            .filter(|att| att.data().slot + spec.min_attestation_inclusion_delay <= block.slot())
+            .map(|att| att.clone_as_attestation());
+        let comparison = compare_packing(&state, block, available_attestations, time_budget, spec)?;
+
+        info!(
+            "Slot {}: actual reward {}, greedy reward {} in {:?}, optimised reward {} in {:?}",
+            block.slot(),
+            comparison.actual_reward,
+            comparison.greedy_reward,
+            comparison.greedy_time,
+            comparison.optimised_reward,
+            comparison.optimised_time,
+        );
+        total.actual_reward += comparison.actual_reward;
+        total.greedy_reward += comparison.greedy_reward;
+        total.optimised_reward += comparison.optimised_reward;
+        total.greedy_time += comparison.greedy_time;
+        total.optimised_time += comparison.optimised_time;
+        num_blocks += 1;
+
+        per_block_processing(
+            &mut state,
+            block,
+            BlockSignatureStrategy::NoVerification,
+            VerifyBlockRoot::True,
+            &mut ConsensusContext::new(block.slot()),
+            spec,
+        )
+        .map_err(|e| format!("Failed to process block at slot {}: {:?}", block.slot(), e))?;
+    }
+
+    info!(
+        "{} blocks: actual reward {}, greedy reward {} in {:?}, optimised reward {} in {:?}",
+        num_blocks,
+        total.actual_reward,
+        total.greedy_reward,
+        total.greedy_time,
+        total.optimised_reward,
+        total.optimised_time,
+    );
+
+    Ok(())
+}
+
+/// Pack `attestations` with each strategy, comparing them to the attestations of `block`.
+fn compare_packing<E: EthSpec>(
+    state: &BeaconState<E>,
+    block: &SignedBeaconBlock<E>,
+    attestations: impl Iterator<Item = Attestation<E>>,
+    time_budget: Duration,
+    spec: &ChainSpec,
+) -> Result<Comparison, String> {
+    let greedy_op_pool = OperationPool::<E>::new();
+    let mut optimised_op_pool = OperationPool::<E>::new();
+    optimised_op_pool.set_packing_strategy(PackingStrategy::RewardOptimised { time_budget });
+
+    for attestation in attestations {
+        for attestation in split_by_committee(state, attestation)? {
+            let attesting_indices =
+                match get_attesting_indices_from_state(state, attestation.to_ref()) {
+                    Ok(attesting_indices) => attesting_indices,
+                    Err(e) => {
+                        debug!("Skipping attestation: {:?}", e);
+                        continue;
+                    }
+                };
+            greedy_op_pool
+                .insert_attestation(attestation.clone(), attesting_indices.clone())
+                .map_err(|e| format!("Unable to insert attestation: {:?}", e))?;
+            optimised_op_pool
+                .insert_attestation(attestation, attesting_indices)
+                .map_err(|e| format!("Unable to insert attestation: {:?}", e))?;
+        }
+    }
+
+    let pack = |op_pool: &OperationPool<E>| {
+        let start = Instant::now();
+        let attestations = op_pool
+            .get_attestations(
+                state,
+                |_: &CompactAttestationRef<E>| true,
+                |_: &CompactAttestationRef<E>| true,
+                spec,
+            )
+            .map_err(|e| format!("Unable to pack attestations: {:?}", e))?;
+        Ok::<_, String>((attestations, start.elapsed()))
+    };
+    let (greedy_attestations, greedy_time) = pack(&greedy_op_pool)?;
+    let (optimised_attestations, optimised_time) = pack(&optimised_op_pool)?;
+
+    let actual_attestations = block
+        .message()
+        .body()
+        .attestations()
+        .map(|att| att.clone_as_attestation());
+
+    Ok(Comparison {
+        actual_reward: attestation_reward(state, actual_attestations, spec)?,
+        greedy_reward: attestation_reward(state, greedy_attestations, spec)?,
+        optimised_reward: attestation_reward(state, optimised_attestations, spec)?,
+        greedy_time,
+        optimised_time,
+    })
+}
+
+/// Split an Electra on-chain aggregate into one attestation for each of its committees, so that
+/// the operation pool can aggregate them as it would attestations from the network.
+///
+/// The attestations are given an empty signature, as their signatures are never verified.
+fn split_by_committee<E: EthSpec>(
+    state: &BeaconState<E>,
+    attestation: Attestation<E>,
+) -> Result<Vec<Attestation<E>>, String> {
+    let Attestation::Electra(attestation) = attestation else {
+        return Ok(vec![attestation]);
+    };
+
+    let mut split = vec![];
+    let mut offset = 0;
+    for committee_index in attestation.get_committee_indices() {
+        let committee_len = match state.get_beacon_committee(attestation.data.slot, committee_index)
+        {
+            Ok(committee) => committee.committee.len(),
+            Err(e) => {
+                debug!("Skipping attestation: {:?}", e);
+                return Ok(vec![]);
+            }
+        };
+
+        let mut aggregation_bits = BitList::with_capacity(committee_len)
+            .map_err(|e| format!("Invalid committee length: {:?}", e))?;
+        for i in 0..committee_len {
+            if attestation
+                .aggregation_bits
+                .get(offset + i)
+                .unwrap_or(false)
+            {
+                aggregation_bits
+                    .set(i, true)
+                    .map_err(|e| format!("Invalid aggregation bits: {:?}", e))?;
+            }
+        }
+        offset += committee_len;
+
+        let mut committee_bits = BitVector::default();
+        committee_bits
+            .set(committee_index as usize, true)
+            .map_err(|e| format!("Invalid committee index: {:?}", e))?;
+
+        split.push(Attestation::Electra(AttestationElectra {
+            aggregation_bits,
+            data: attestation.data.clone(),
+            committee_bits,
+            signature: AggregateSignature::infinity(),
+        }));
+    }
+    Ok(split)
+}
+
+/// The proposer reward for including `attestations` in a block at the slot of `state`.
+fn attestation_reward<E: EthSpec>(
+    state: &BeaconState<E>,
+    attestations: impl IntoIterator<Item = Attestation<E>>,
+    spec: &ChainSpec,
+) -> Result<u64, String> {
+    let total_active_balance = state
+        .get_total_active_balance()
+        .map_err(|e| format!("Unable to get total active balance: {:?}", e))?;
+    let mut reward_cache = RewardCache::default();
+    reward_cache
+        .update(state)
+        .map_err(|e| format!("Unable to update reward cache: {:?}", e))?;
+
+    let mut seen_indices = BTreeSet::new();
+    let mut total_reward = 0;
+    for attestation in attestations {
+        let attesting_indices = get_attesting_indices_from_state(state, attestation.to_ref())
+            .map_err(|e| format!("Unable to get attesting indices: {:?}", e))?;
+        let split_attestation = SplitAttestation::new(attestation, attesting_indices);
+        let Some(att_max_cover) = AttMaxCover::new(
+            split_attestation.as_ref(),
+            state,
+            &reward_cache,
+            total_active_balance,
+            spec,
+        ) else {
+            continue;
+        };
+        for (validator_index, reward) in att_max_cover.fresh_validators_rewards {
+            if seen_indices.insert(validator_index) {
+                total_reward += reward;
+            }
+        }
+    }
+    Ok(total_reward)
+}
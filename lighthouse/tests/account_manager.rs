@@ -493,12 +493,14 @@ fn validator_import_launchpad() {
         gas_limit: None,
         builder_proposals: None,
         builder_boost_factor: None,
+        builder_min_bid_gwei: None,
         prefer_builder_proposals: None,
         voting_public_key: keystore.public_key().unwrap(),
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path,
             voting_keystore_password_path: None,
             voting_keystore_password: Some(ZeroizeString::from(PASSWORD.to_string())),
+            distributed: None,
         },
     };
 
@@ -617,12 +619,14 @@ fn validator_import_launchpad_no_password_then_add_password() {
         gas_limit: None,
         builder_proposals: None,
         builder_boost_factor: None,
+        builder_min_bid_gwei: None,
         prefer_builder_proposals: None,
         voting_public_key: keystore.public_key().unwrap(),
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path,
             voting_keystore_password_path: None,
             voting_keystore_password: None,
+            distributed: None,
         },
     };
 
@@ -645,12 +649,14 @@ fn validator_import_launchpad_no_password_then_add_password() {
         gas_limit: None,
         builder_proposals: None,
         builder_boost_factor: None,
+        builder_min_bid_gwei: None,
         prefer_builder_proposals: None,
         voting_public_key: keystore.public_key().unwrap(),
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path: dst_keystore_dir.join(KEYSTORE_NAME),
             voting_keystore_password_path: None,
             voting_keystore_password: Some(ZeroizeString::from(PASSWORD.to_string())),
+            distributed: None,
         },
     };
 
@@ -749,11 +755,13 @@ fn validator_import_launchpad_password_file() {
         gas_limit: None,
         builder_proposals: None,
         builder_boost_factor: None,
+        builder_min_bid_gwei: None,
         prefer_builder_proposals: None,
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path,
             voting_keystore_password_path: None,
             voting_keystore_password: Some(ZeroizeString::from(PASSWORD.to_string())),
+            distributed: None,
         },
     };
 
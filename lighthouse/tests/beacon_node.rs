@@ -2,12 +2,15 @@ use beacon_node::ClientConfig as Config;
 
 use crate::exec::{CommandLineTestExec, CompletedTest};
 use beacon_node::beacon_chain::chain_config::{
-    DisallowedReOrgOffsets, DEFAULT_RE_ORG_CUTOFF_DENOMINATOR, DEFAULT_RE_ORG_HEAD_THRESHOLD,
-    DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION,
+    DisallowedReOrgOffsets, PackingStrategy, DEFAULT_RE_ORG_CUTOFF_DENOMINATOR,
+    DEFAULT_RE_ORG_HEAD_THRESHOLD, DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION,
 };
 use beacon_node::beacon_chain::graffiti_calculator::GraffitiOrigin;
+use beacon_node::beacon_chain::store::DatabaseBackend;
+use beacon_processor::scheduler::{QueueId, QueueOrder, QueuePolicy, SchedulingPolicy};
 use beacon_processor::BeaconProcessorConfig;
 use eth1::Eth1Endpoint;
+use http_api::auth::{ApiScope, ApiToken};
 use lighthouse_network::PeerId;
 use lighthouse_version;
 use std::fs::File;
@@ -250,6 +253,56 @@ fn prepare_payload_lookahead_shorter() {
         });
 }
 
+#[test]
+fn op_pool_packing_time_budget_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.op_pool_packing_strategy,
+                PackingStrategy::Greedy
+            )
+        });
+}
+
+#[test]
+fn op_pool_packing_time_budget_flag() {
+    CommandLineTest::new()
+        .flag("op-pool-packing-time-budget", Some("200"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.op_pool_packing_strategy,
+                PackingStrategy::RewardOptimised {
+                    time_budget: Duration::from_millis(200)
+                }
+            )
+        });
+}
+
+#[test]
+fn op_pool_persistence_epochs_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.op_pool_persistence_epochs, Some(4)));
+}
+
+#[test]
+fn op_pool_persistence_epochs_flag() {
+    CommandLineTest::new()
+        .flag("op-pool-persistence-epochs", Some("16"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.op_pool_persistence_epochs, Some(16)));
+}
+
+#[test]
+fn op_pool_persistence_epochs_zero() {
+    CommandLineTest::new()
+        .flag("op-pool-persistence-epochs", Some("0"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.op_pool_persistence_epochs, None));
+}
+
 #[test]
 fn always_prepare_payload_default() {
     CommandLineTest::new()
@@ -371,6 +424,63 @@ fn trusted_peers_flag() {
         });
 }
 
+#[test]
+fn peer_policy_file_flag() {
+    CommandLineTest::new()
+        .flag("peer-policy-file", Some("/tmp/peer-policy.yaml"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.peer_policy_file,
+                Some(PathBuf::from("/tmp/peer-policy.yaml"))
+            )
+        });
+}
+
+#[test]
+fn peer_policy_file_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.peer_policy_file, None));
+}
+
+#[test]
+fn network_recording_file_flag() {
+    CommandLineTest::new()
+        .flag("network-recording-file", Some("/tmp/network.rec"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.network_recording_file,
+                Some(PathBuf::from("/tmp/network.rec"))
+            )
+        });
+}
+
+#[test]
+fn network_recording_max_size_flag() {
+    CommandLineTest::new()
+        .flag("network-recording-file", Some("/tmp/network.rec"))
+        .flag("network-recording-max-size", Some("100"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.network_recording_max_size, 100 * 1024 * 1024)
+        });
+}
+
+#[test]
+fn network_recording_max_size_default() {
+    CommandLineTest::new()
+        .flag("network-recording-file", Some("/tmp/network.rec"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.network_recording_max_size,
+                4096 * 1024 * 1024
+            )
+        });
+}
+
 #[test]
 fn genesis_backfill_flag() {
     CommandLineTest::new()
@@ -441,8 +551,7 @@ fn eth1_cache_follow_distance_manual() {
 fn run_bellatrix_execution_endpoints_flag_test(flag: &str) {
     use sensitive_url::SensitiveUrl;
     let urls = vec!["http://sigp.io/no-way:1337", "http://infura.not_real:4242"];
-    // we don't support redundancy for execution-endpoints
-    // only the first provided endpoint is parsed.
+    // The first endpoint is the primary, the rest are redundant engines.
 
     let mut endpoint_arg = urls[0].to_string();
     for url in urls.iter().skip(1) {
@@ -477,11 +586,37 @@ fn run_bellatrix_execution_endpoints_flag_test(flag: &str) {
                 config.execution_endpoint.as_ref().unwrap().clone(),
                 SensitiveUrl::parse(&urls[0]).unwrap()
             );
-            // Only the first secret file should be used.
+            assert_eq!(
+                config.redundant_execution_endpoints,
+                vec![SensitiveUrl::parse(&urls[1]).unwrap()]
+            );
+            // Each endpoint uses its own secret file.
             assert_eq!(
                 config.secret_file.as_ref().unwrap().clone(),
                 jwts[0].clone()
             );
+            assert_eq!(config.redundant_secret_files, vec![jwts[1].clone()]);
+        });
+}
+#[test]
+fn execution_endpoints_share_single_jwt() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag(
+            "execution-endpoint",
+            Some("http://meow.cats,http://woof.dogs"),
+        )
+        .flag(
+            "execution-jwt",
+            dir.path().join("jwt-file").as_os_str().to_str(),
+        )
+        .flag("execution-invalid-quorum", Some("1"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let config = config.execution_layer.as_ref().unwrap();
+            assert_eq!(config.redundant_execution_endpoints.len(), 1);
+            assert!(config.redundant_secret_files.is_empty());
+            assert_eq!(config.invalid_payload_quorum, Some(1));
         });
 }
 #[test]
@@ -585,9 +720,8 @@ fn run_payload_builder_flag_test(flag: &str, builders: &str) {
         .collect();
     run_payload_builder_flag_test_with_config(flag, builders, None, None, |config| {
         let config = config.execution_layer.as_ref().unwrap();
-        // Only first provided endpoint is parsed as we don't support
-        // redundancy.
-        assert_eq!(config.builder_url, all_builders.get(0).cloned());
+        // Every provided endpoint is used as a relay in the bid auction.
+        assert_eq!(config.builder_urls, all_builders);
     })
 }
 fn run_payload_builder_flag_test_with_config<F: Fn(&Config)>(
@@ -712,6 +846,42 @@ fn builder_user_agent() {
     );
 }
 
+#[test]
+fn builder_min_bid_gwei() {
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        None,
+        None,
+        |config| {
+            assert_eq!(
+                config
+                    .execution_layer
+                    .as_ref()
+                    .unwrap()
+                    .builder_min_bid_gwei,
+                None
+            );
+        },
+    );
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-min-bid-gwei"),
+        Some("50000000"),
+        |config| {
+            assert_eq!(
+                config
+                    .execution_layer
+                    .as_ref()
+                    .unwrap()
+                    .builder_min_bid_gwei,
+                Some(50_000_000)
+            );
+        },
+    );
+}
+
 fn run_jwt_optional_flags_test(jwt_flag: &str, jwt_id_flag: &str, jwt_version_flag: &str) {
     use sensitive_url::SensitiveUrl;
 
@@ -1298,6 +1468,40 @@ fn network_load_flag() {
             assert_eq!(config.network.network_load, 4);
         });
 }
+#[test]
+fn operation_rebroadcast_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.operation_rebroadcast_epochs, Some(4));
+            assert_eq!(config.network.operation_rebroadcast_limit, 64);
+        });
+}
+#[test]
+fn operation_rebroadcast_flags() {
+    CommandLineTest::new()
+        .flag("operation-rebroadcast-epochs", Some("8"))
+        .flag("operation-rebroadcast-limit", Some("16"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.operation_rebroadcast_epochs, Some(8));
+            assert_eq!(config.network.operation_rebroadcast_limit, 16);
+        });
+}
+#[test]
+fn operation_rebroadcast_epochs_zero() {
+    CommandLineTest::new()
+        .flag("operation-rebroadcast-epochs", Some("0"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.operation_rebroadcast_epochs, None));
+}
+#[test]
+#[should_panic]
+fn operation_rebroadcast_epochs_too_low() {
+    CommandLineTest::new()
+        .flag("operation-rebroadcast-epochs", Some("2"))
+        .run_with_zero_port();
+}
 
 // Tests for ENR flags.
 #[test]
@@ -1992,6 +2196,32 @@ fn blob_prune_margin_epochs_on_startup_ten() {
         .with_config(|config| assert!(config.store.blob_prune_margin_epochs == 10));
 }
 #[test]
+fn beacon_node_backend_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.backend, DatabaseBackend::LevelDb));
+}
+#[test]
+fn beacon_node_backend_leveldb() {
+    CommandLineTest::new()
+        .flag("beacon-node-backend", Some("leveldb"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.backend, DatabaseBackend::LevelDb));
+}
+#[test]
+fn hierarchy_exponents_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.hierarchy_config, None));
+}
+#[test]
+fn hierarchy_exponents_flag() {
+    CommandLineTest::new()
+        .flag("hierarchy-exponents", Some("5,7,11"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.hierarchy_exponents(), vec![5, 7, 11]));
+}
+#[test]
 fn reconstruct_historic_states_flag() {
     CommandLineTest::new()
         .flag("reconstruct-historic-states", None)
@@ -2635,12 +2865,65 @@ fn beacon_processor() {
                     max_scheduled_work_queue_len: 3,
                     max_gossip_attestation_batch_size: 4,
                     max_gossip_aggregate_batch_size: 5,
-                    enable_backfill_rate_limiting: false
+                    enable_backfill_rate_limiting: false,
+                    scheduling_policy: SchedulingPolicy::default(),
                 }
             )
         });
 }
 
+#[test]
+fn beacon_processor_scheduling_policy() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("scheduling-policy.json");
+    let mut file = File::create(&path).expect("Unable to create policy file");
+    file.write_all(
+        br#"{"queues": {"api_request_p1": {"priority": 5, "max_workers": 2, "order": "lifo"}}}"#,
+    )
+    .expect("Unable to write policy file");
+
+    CommandLineTest::new()
+        .flag(
+            "beacon-processor-scheduling-policy",
+            path.as_os_str().to_str(),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            let policy = config
+                .beacon_processor
+                .scheduling_policy
+                .queues
+                .get(&QueueId::ApiRequestP1)
+                .expect("policy should be set for api_request_p1");
+            assert_eq!(
+                *policy,
+                QueuePolicy {
+                    capacity: None,
+                    priority: Some(5),
+                    max_workers: Some(2),
+                    order: Some(QueueOrder::Lifo),
+                }
+            );
+        });
+}
+
+#[test]
+#[should_panic]
+fn beacon_processor_scheduling_policy_zero_workers() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("scheduling-policy.json");
+    let mut file = File::create(&path).expect("Unable to create policy file");
+    file.write_all(br#"{"queues": {"status": {"max_workers": 0}}}"#)
+        .expect("Unable to write policy file");
+
+    CommandLineTest::new()
+        .flag(
+            "beacon-processor-scheduling-policy",
+            path.as_os_str().to_str(),
+        )
+        .run_with_zero_port();
+}
+
 #[test]
 #[should_panic]
 fn beacon_processor_zero_workers() {
@@ -2649,6 +2932,63 @@ fn beacon_processor_zero_workers() {
         .run_with_zero_port();
 }
 
+#[test]
+fn http_api_tokens_file_default() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.auth, None));
+}
+
+#[test]
+fn http_api_tokens_file() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("api-tokens.json");
+    let mut file = File::create(&path).expect("Unable to create tokens file");
+    file.write_all(
+        br#"{"tokens": [{"name": "vc", "token": "secret", "scopes": ["read_only", "validator"], "requests_per_minute": 100}]}"#,
+    )
+    .expect("Unable to write tokens file");
+
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-api-tokens-file", path.as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| {
+            let auth = config
+                .http_api
+                .auth
+                .as_ref()
+                .expect("auth should be enabled");
+            assert_eq!(
+                auth.tokens,
+                vec![ApiToken {
+                    name: "vc".to_string(),
+                    token: "secret".to_string(),
+                    scopes: vec![ApiScope::ReadOnly, ApiScope::Validator],
+                    requests_per_minute: Some(100),
+                }]
+            );
+        });
+}
+
+#[test]
+#[should_panic]
+fn http_api_tokens_file_duplicate_names() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("api-tokens.json");
+    let mut file = File::create(&path).expect("Unable to create tokens file");
+    file.write_all(
+        br#"{"tokens": [{"name": "vc", "token": "a", "scopes": []}, {"name": "vc", "token": "b", "scopes": []}]}"#,
+    )
+    .expect("Unable to write tokens file");
+
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-api-tokens-file", path.as_os_str().to_str())
+        .run_with_zero_port();
+}
+
 #[test]
 fn http_sse_capacity_multiplier_default() {
     CommandLineTest::new()
@@ -154,6 +154,23 @@ fn beacon_nodes_tls_certs_flag() {
         });
 }
 
+#[test]
+fn beacon_nodes_api_token_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag(
+            "beacon-nodes-api-token-file",
+            dir.path().join("api-token.txt").to_str(),
+        )
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.beacon_nodes_api_token_file,
+                Some(dir.path().join("api-token.txt"))
+            )
+        });
+}
+
 // Tests for Graffiti flags.
 #[test]
 fn graffiti_flag() {
@@ -644,6 +661,48 @@ fn validator_disable_web3_signer_slashing_protection() {
         });
 }
 
+#[test]
+fn signing_audit_log_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.signing_audit_log, None);
+    });
+}
+
+#[test]
+fn signing_audit_log_flag() {
+    CommandLineTest::new()
+        .flag("signing-audit-log", None)
+        .run()
+        .with_config(|config| {
+            let audit_config = config.signing_audit_log.as_ref().unwrap();
+            assert_eq!(audit_config.max_file_size, 64 * 1024 * 1024);
+            assert_eq!(audit_config.max_files, None);
+        });
+}
+
+#[test]
+fn signing_audit_log_rotation_flags() {
+    CommandLineTest::new()
+        .flag("signing-audit-log", None)
+        .flag("signing-audit-log-max-file-size", Some("16"))
+        .flag("signing-audit-log-max-files", Some("10"))
+        .run()
+        .with_config(|config| {
+            let audit_config = config.signing_audit_log.as_ref().unwrap();
+            assert_eq!(audit_config.max_file_size, 16 * 1024 * 1024);
+            assert_eq!(audit_config.max_files, Some(10));
+        });
+}
+
+#[test]
+#[should_panic]
+fn signing_audit_log_max_file_size_zero_value() {
+    CommandLineTest::new()
+        .flag("signing-audit-log", None)
+        .flag("signing-audit-log-max-file-size", Some("0"))
+        .run();
+}
+
 #[test]
 fn validator_web3_signer_keep_alive_default() {
     CommandLineTest::new().run().with_config(|config| {
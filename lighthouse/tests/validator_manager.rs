@@ -11,6 +11,7 @@ use validator_manager::{
     create_validators::CreateConfig,
     import_validators::ImportConfig,
     move_validators::{MoveConfig, PasswordSource, Validators},
+    split_keystore::SplitConfig,
 };
 
 const EXAMPLE_ETH1_ADDRESS: &str = "0x00000000219ab540356cBB839Cbe05303d7705Fa";
@@ -105,6 +106,12 @@ impl CommandLineTest<MoveConfig> {
     }
 }
 
+impl CommandLineTest<SplitConfig> {
+    fn split_keystore() -> Self {
+        Self::default().flag("split-keystore", None)
+    }
+}
+
 #[test]
 pub fn validator_create_without_output_path() {
     CommandLineTest::validators_create().assert_failed();
@@ -394,3 +401,54 @@ pub fn validator_move_count() {
             assert_eq!(expected, config);
         });
 }
+
+#[test]
+pub fn split_keystore_without_threshold() {
+    CommandLineTest::split_keystore()
+        .flag("--keystore-path", Some("./keystore.json"))
+        .flag("--share-count", Some("3"))
+        .flag("--output-path", Some("./shares"))
+        .assert_failed();
+}
+
+#[test]
+pub fn split_keystore_defaults() {
+    CommandLineTest::split_keystore()
+        .flag("--keystore-path", Some("./keystore.json"))
+        .flag("--threshold", Some("2"))
+        .flag("--share-count", Some("3"))
+        .flag("--output-path", Some("./shares"))
+        .assert_success(|config| {
+            let expected = SplitConfig {
+                keystore_path: PathBuf::from("./keystore.json"),
+                password_file: None,
+                threshold: 2,
+                share_count: 3,
+                output_path: PathBuf::from("./shares"),
+                stdin_inputs: cfg!(windows) || false,
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn split_keystore_misc_flags() {
+    CommandLineTest::split_keystore()
+        .flag("--keystore-path", Some("./keystore.json"))
+        .flag("--password-file", Some("./password.txt"))
+        .flag("--threshold", Some("3"))
+        .flag("--share-count", Some("4"))
+        .flag("--output-path", Some("./shares"))
+        .flag("--stdin-inputs", None)
+        .assert_success(|config| {
+            let expected = SplitConfig {
+                keystore_path: PathBuf::from("./keystore.json"),
+                password_file: Some(PathBuf::from("./password.txt")),
+                threshold: 3,
+                share_count: 4,
+                output_path: PathBuf::from("./shares"),
+                stdin_inputs: true,
+            };
+            assert_eq!(expected, config);
+        });
+}
@@ -8,7 +8,7 @@ use crate::{
     ProposerSlashingStatus,
 };
 use byteorder::{BigEndian, ByteOrder};
-use interface::{Environment, OpenDatabases, RwTransaction};
+use interface::{Database, Environment, OpenDatabases, RwTransaction};
 use lru::LruCache;
 use parking_lot::Mutex;
 use serde::de::DeserializeOwned;
@@ -286,6 +286,23 @@ fn ssz_decode<T: Decode>(bytes: Cow<[u8]>) -> Result<T, Error> {
     Ok(T::from_ssz_bytes(bytes.borrow())?)
 }
 
+impl<'env> OpenDatabases<'env> {
+    /// All of the databases, along with their names.
+    pub(crate) fn named(&self) -> [(&'static str, &Database<'env>); MAX_NUM_DBS] {
+        [
+            (METADATA_DB, &self.metadata_db),
+            (ATTESTERS_DB, &self.attesters_db),
+            (ATTESTERS_MAX_TARGETS_DB, &self.attesters_max_targets_db),
+            (INDEXED_ATTESTATION_DB, &self.indexed_attestation_db),
+            (INDEXED_ATTESTATION_ID_DB, &self.indexed_attestation_id_db),
+            (MIN_TARGETS_DB, &self.min_targets_db),
+            (MAX_TARGETS_DB, &self.max_targets_db),
+            (CURRENT_EPOCHS_DB, &self.current_epochs_db),
+            (PROPOSERS_DB, &self.proposers_db),
+        ]
+    }
+}
+
 impl<E: EthSpec> SlasherDB<E> {
     pub fn open(config: Arc<Config>, spec: Arc<ChainSpec>, log: Logger) -> Result<Self, Error> {
         info!(log, "Opening slasher database"; "backend" => %config.backend);
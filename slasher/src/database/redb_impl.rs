@@ -9,7 +9,7 @@ use crate::{
 };
 use derivative::Derivative;
 use redb::{ReadableTable, TableDefinition};
-use std::{borrow::Cow, path::PathBuf};
+use std::{borrow::Cow, ops::Bound, path::PathBuf};
 
 #[derive(Debug)]
 pub struct Environment {
@@ -215,7 +215,10 @@ impl<'env> Cursor<'env> {
             TableDefinition::new(&self.db.table_name);
         let table = self.txn.open_table(table_definition)?;
         if let Some(current_key) = &self.current_key {
-            let range: std::ops::RangeFrom<&[u8]> = current_key..;
+            // Exclude the current key so that the cursor advances, consistent with the other
+            // backends.
+            let range: (Bound<&[u8]>, Bound<&[u8]>) =
+                (Bound::Excluded(current_key.as_ref()), Bound::Unbounded);
 
             let next = table
                 .range(range)?
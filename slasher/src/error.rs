@@ -1,5 +1,6 @@
 use crate::config::{Config, DiskConfig};
 use std::io;
+use std::path::PathBuf;
 use types::Epoch;
 
 #[derive(Debug)]
@@ -70,6 +71,16 @@ pub enum Error {
     MissingIndexedAttestationIdKey,
     InconsistentAttestationDataRoot,
     MissingKey,
+    /// The database to migrate from does not exist or has no schema version.
+    MigrationSourceMissing(PathBuf),
+    /// The directory to migrate into already exists and is not empty.
+    MigrationTargetExists(PathBuf),
+    /// A table of the migrated database does not contain the expected number of entries.
+    MigrationCountMismatch {
+        table: &'static str,
+        expected: usize,
+        found: usize,
+    },
 }
 
 #[cfg(feature = "mdbx")]
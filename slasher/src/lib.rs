@@ -27,6 +27,7 @@ pub use database::{
     IndexedAttestationId, SlasherDB,
 };
 pub use error::Error;
+pub use migrate::migrate_backend;
 
 use types::{AttesterSlashing, AttesterSlashingBase, AttesterSlashingElectra};
 use types::{EthSpec, IndexedAttestation, ProposerSlashing};
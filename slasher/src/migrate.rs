@@ -1,4 +1,9 @@
-use crate::{database::CURRENT_SCHEMA_VERSION, Error, SlasherDB};
+use crate::database::interface::{Database, Environment};
+use crate::{database::CURRENT_SCHEMA_VERSION, Config, Error, SlasherDB};
+use slog::{info, Logger};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
 use types::EthSpec;
 
 impl<E: EthSpec> SlasherDB<E> {
@@ -27,3 +32,274 @@ impl<E: EthSpec> SlasherDB<E> {
         }
     }
 }
+
+/// The number of entries to copy before committing the transaction on the target database.
+const MIGRATION_BATCH_SIZE: usize = 100_000;
+
+/// Copy every table of the slasher database described by `source` into a new database described
+/// by `target`, which will typically use a different backend.
+///
+/// The target directory must be empty or not exist. Once copied, the number of entries in each
+/// table of the target database is checked against the source database. Returns the number of
+/// entries copied from each table.
+pub fn migrate_backend(
+    source: &Config,
+    target: &Config,
+    log: &Logger,
+) -> Result<Vec<(&'static str, usize)>, Error> {
+    let source_files = dir_entries(&source.database_path)?;
+    if source_files.is_empty() {
+        return Err(Error::MigrationSourceMissing(source.database_path.clone()));
+    }
+    if !dir_entries(&target.database_path)?.is_empty() {
+        return Err(Error::MigrationTargetExists(target.database_path.clone()));
+    }
+
+    let source_env = Environment::new(source)?;
+    let source_dbs = source_env.create_databases()?;
+
+    // A database without metadata was either never written, or was written by another backend.
+    if count_entries(&source_env, &source_dbs.metadata_db)? == 0 {
+        // Remove any files created by opening the database above.
+        let created_files = source_env
+            .filenames(source)
+            .into_iter()
+            .filter(|path| !source_files.contains(path))
+            .collect::<Vec<_>>();
+        drop(source_dbs);
+        drop(source_env);
+        for path in created_files {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+        }
+        return Err(Error::MigrationSourceMissing(source.database_path.clone()));
+    }
+
+    fs::create_dir_all(&target.database_path)?;
+    let target_env = Environment::new(target)?;
+    let target_dbs = target_env.create_databases()?;
+
+    #[cfg(windows)]
+    {
+        for database_file in target_env.filenames(target) {
+            filesystem::restrict_file_permissions(database_file)
+                .map_err(Error::DatabasePermissionsError)?;
+        }
+    }
+
+    let source_tables = source_dbs.named();
+    let target_tables = target_dbs.named();
+
+    let mut counts = Vec::with_capacity(source_tables.len());
+    for (&(name, source_db), &(_, target_db)) in source_tables.iter().zip(&target_tables) {
+        let count = copy_entries(&source_env, source_db, &target_env, target_db)?;
+        info!(log, "Copied slasher table"; "table" => name, "entries" => count);
+        counts.push((name, count));
+    }
+
+    // Re-count both databases, which also detects writes to the source during the migration.
+    for (&(name, source_db), &(_, target_db)) in source_tables.iter().zip(&target_tables) {
+        let expected = count_entries(&source_env, source_db)?;
+        let found = count_entries(&target_env, target_db)?;
+        if expected != found {
+            return Err(Error::MigrationCountMismatch {
+                table: name,
+                expected,
+                found,
+            });
+        }
+    }
+
+    Ok(counts)
+}
+
+/// Copy all entries of `source_db` into `target_db`, returning the number copied.
+fn copy_entries(
+    source_env: &Environment,
+    source_db: &Database,
+    target_env: &Environment,
+    target_db: &Database,
+) -> Result<usize, Error> {
+    let mut source_txn = source_env.begin_rw_txn()?;
+    let mut cursor = source_txn.cursor(source_db)?;
+    let mut target_txn = target_env.begin_rw_txn()?;
+    let mut count = 0;
+
+    if cursor.first_key()?.is_some() {
+        loop {
+            let (key, value) = cursor.get_current()?.ok_or(Error::MissingKey)?;
+            target_txn.put(target_db, key, value)?;
+            count += 1;
+
+            if count % MIGRATION_BATCH_SIZE == 0 {
+                target_txn.commit()?;
+                target_txn = target_env.begin_rw_txn()?;
+            }
+
+            if cursor.next_key()?.is_none() {
+                break;
+            }
+        }
+    }
+    target_txn.commit()?;
+
+    Ok(count)
+}
+
+fn count_entries(env: &Environment, db: &Database) -> Result<usize, Error> {
+    let mut txn = env.begin_rw_txn()?;
+    let mut cursor = txn.cursor(db)?;
+    let mut count = 0;
+
+    if cursor.first_key()?.is_some() {
+        count += 1;
+        while cursor.next_key()?.is_some() {
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
+/// Returns the paths of the entries in `dir`, which are empty if it does not exist.
+fn dir_entries(dir: &Path) -> Result<HashSet<PathBuf>, Error> {
+    if !dir.exists() {
+        return Ok(HashSet::new());
+    }
+    fs::read_dir(dir)?.map(|entry| Ok(entry?.path())).collect()
+}
+
+#[cfg(all(test, any(feature = "mdbx", feature = "lmdb", feature = "redb")))]
+mod test {
+    use super::*;
+    use crate::config::DEFAULT_BACKEND;
+    use crate::DatabaseBackend;
+    use logging::test_logger;
+    use tempfile::tempdir;
+
+    type Entries = Vec<Vec<(Vec<u8>, Vec<u8>)>>;
+
+    /// Write a different number of entries to each table of the database.
+    fn populate(config: &Config) -> Entries {
+        let env = Environment::new(config).unwrap();
+        let dbs = env.create_databases().unwrap();
+        let mut txn = env.begin_rw_txn().unwrap();
+        for (i, (_, db)) in dbs.named().into_iter().enumerate() {
+            for j in 0..=i as u8 {
+                txn.put(db, [i as u8, j], [j; 32]).unwrap();
+            }
+        }
+        txn.commit().unwrap();
+        drop(dbs);
+        drop(env);
+        read_all(config)
+    }
+
+    fn read_all(config: &Config) -> Entries {
+        let env = Environment::new(config).unwrap();
+        let dbs = env.create_databases().unwrap();
+        let mut tables = vec![];
+        for (_, db) in dbs.named() {
+            let mut txn = env.begin_rw_txn().unwrap();
+            let mut cursor = txn.cursor(db).unwrap();
+            let mut entries = vec![];
+            if cursor.first_key().unwrap().is_some() {
+                loop {
+                    let (key, value) = cursor.get_current().unwrap().unwrap();
+                    entries.push((key.to_vec(), value.to_vec()));
+                    if cursor.next_key().unwrap().is_none() {
+                        break;
+                    }
+                }
+            }
+            tables.push(entries);
+        }
+        tables
+    }
+
+    fn migrate_test(source_backend: DatabaseBackend, target_backend: DatabaseBackend) {
+        let source_dir = tempdir().unwrap();
+        let target_dir = tempdir().unwrap();
+        let mut source = Config::new(source_dir.path().into());
+        source.backend = source_backend;
+        let mut target = Config::new(target_dir.path().join("slasher_db"));
+        target.backend = target_backend;
+
+        let entries = populate(&source);
+        let counts = migrate_backend(&source, &target, &test_logger()).unwrap();
+
+        assert_eq!(
+            counts.iter().map(|(_, count)| *count).collect::<Vec<_>>(),
+            entries.iter().map(Vec::len).collect::<Vec<_>>()
+        );
+        assert_eq!(read_all(&target), entries);
+    }
+
+    #[test]
+    fn migrate_same_backend() {
+        migrate_test(DEFAULT_BACKEND, DEFAULT_BACKEND);
+    }
+
+    #[test]
+    #[cfg(all(feature = "lmdb", feature = "mdbx"))]
+    fn migrate_lmdb_to_mdbx() {
+        migrate_test(DatabaseBackend::Lmdb, DatabaseBackend::Mdbx);
+    }
+
+    #[test]
+    #[cfg(all(feature = "lmdb", feature = "redb"))]
+    fn migrate_lmdb_to_redb() {
+        migrate_test(DatabaseBackend::Lmdb, DatabaseBackend::Redb);
+    }
+
+    #[test]
+    #[cfg(all(feature = "mdbx", feature = "redb"))]
+    fn migrate_redb_to_mdbx() {
+        migrate_test(DatabaseBackend::Redb, DatabaseBackend::Mdbx);
+    }
+
+    #[test]
+    fn migrate_missing_source() {
+        let source_dir = tempdir().unwrap();
+        let target_dir = tempdir().unwrap();
+        let source = Config::new(source_dir.path().join("slasher_db"));
+        let target = Config::new(target_dir.path().into());
+
+        assert!(matches!(
+            migrate_backend(&source, &target, &test_logger()),
+            Err(Error::MigrationSourceMissing(_))
+        ));
+    }
+
+    #[test]
+    fn migrate_empty_source() {
+        let source_dir = tempdir().unwrap();
+        let target_dir = tempdir().unwrap();
+        let source = Config::new(source_dir.path().into());
+        let target = Config::new(target_dir.path().into());
+        fs::write(source_dir.path().join("unrelated"), []).unwrap();
+
+        assert!(matches!(
+            migrate_backend(&source, &target, &test_logger()),
+            Err(Error::MigrationSourceMissing(_))
+        ));
+        // The files created when opening the source database are removed.
+        assert_eq!(dir_entries(source_dir.path()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn migrate_into_existing_database() {
+        let source_dir = tempdir().unwrap();
+        let target_dir = tempdir().unwrap();
+        let source = Config::new(source_dir.path().into());
+        let target = Config::new(target_dir.path().into());
+        populate(&source);
+        populate(&target);
+
+        assert!(matches!(
+            migrate_backend(&source, &target, &test_logger()),
+            Err(Error::MigrationTargetExists(_))
+        ));
+    }
+}
@@ -311,6 +311,7 @@ impl<Engine: GenericExecutionEngine> TestRig<Engine> {
             pubkey: PublicKeyBytes::empty(),
             slot: Slot::new(0),
             chain_health: ChainHealth::Healthy,
+            min_bid_gwei: None,
         };
         let suggested_fee_recipient = self
             .ee_a
@@ -463,6 +464,7 @@ impl<Engine: GenericExecutionEngine> TestRig<Engine> {
             pubkey: PublicKeyBytes::empty(),
             slot: Slot::new(0),
             chain_health: ChainHealth::Healthy,
+            min_bid_gwei: None,
         };
         let suggested_fee_recipient = self
             .ee_a
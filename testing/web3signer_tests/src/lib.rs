@@ -361,6 +361,7 @@ mod tests {
             let validator_store = ValidatorStore::<_, E>::new(
                 initialized_validators,
                 slashing_protection,
+                None,
                 Hash256::repeat_byte(42),
                 spec,
                 None,
@@ -423,12 +424,14 @@ mod tests {
                     gas_limit: None,
                     builder_proposals: None,
                     builder_boost_factor: None,
+                    builder_min_bid_gwei: None,
                     prefer_builder_proposals: None,
                     description: String::default(),
                     signing_definition: SigningDefinition::LocalKeystore {
                         voting_keystore_path: signer_rig.keystore_path.clone(),
                         voting_keystore_password_path: None,
                         voting_keystore_password: Some(KEYSTORE_PASSWORD.to_string().into()),
+                        distributed: None,
                     },
                 };
                 ValidatorStoreRig::new(
@@ -449,6 +452,7 @@ mod tests {
                     gas_limit: None,
                     builder_proposals: None,
                     builder_boost_factor: None,
+                    builder_min_bid_gwei: None,
                     prefer_builder_proposals: None,
                     description: String::default(),
                     signing_definition: SigningDefinition::Web3Signer(Web3SignerDefinition {
@@ -594,7 +598,7 @@ mod tests {
             let block = BeaconBlock::Base(BeaconBlockBase::empty(spec));
             let block_slot = block.slot();
             validator_store
-                .sign_block(pubkey, block, block_slot)
+                .sign_block(pubkey, block, block_slot, None)
                 .await
                 .unwrap()
         })
@@ -602,7 +606,7 @@ mod tests {
         .assert_signatures_match("attestation", |pubkey, validator_store| async move {
             let mut attestation = get_attestation();
             validator_store
-                .sign_attestation(pubkey, 0, &mut attestation, Epoch::new(0))
+                .sign_attestation(pubkey, 0, &mut attestation, Epoch::new(0), None)
                 .await
                 .unwrap();
             attestation
@@ -616,6 +620,7 @@ mod tests {
                     0,
                     attestation,
                     SelectionProof::from(Signature::empty()),
+                    None,
                 )
                 .await
                 .unwrap()
@@ -663,7 +668,12 @@ mod tests {
                 let mut altair_block = BeaconBlockAltair::empty(spec);
                 altair_block.slot = altair_fork_slot;
                 validator_store
-                    .sign_block(pubkey, BeaconBlock::Altair(altair_block), altair_fork_slot)
+                    .sign_block(
+                        pubkey,
+                        BeaconBlock::Altair(altair_block),
+                        altair_fork_slot,
+                        None,
+                    )
                     .await
                     .unwrap()
             },
@@ -683,7 +693,13 @@ mod tests {
             "sync_committee_signature",
             |pubkey, validator_store| async move {
                 validator_store
-                    .produce_sync_committee_signature(altair_fork_slot, Hash256::zero(), 0, &pubkey)
+                    .produce_sync_committee_signature(
+                        altair_fork_slot,
+                        Hash256::zero(),
+                        0,
+                        &pubkey,
+                        None,
+                    )
                     .await
                     .unwrap()
             },
@@ -705,6 +721,7 @@ mod tests {
                         pubkey,
                         contribution,
                         SyncSelectionProof::from(Signature::empty()),
+                        None,
                     )
                     .await
                     .unwrap()
@@ -750,6 +767,7 @@ mod tests {
                         pubkey,
                         BeaconBlock::Bellatrix(bellatrix_block),
                         bellatrix_fork_slot,
+                        None,
                     )
                     .await
                     .unwrap()
@@ -827,7 +845,7 @@ mod tests {
         .assert_signatures_match("first_attestation", |pubkey, validator_store| async move {
             let mut attestation = first_attestation();
             validator_store
-                .sign_attestation(pubkey, 0, &mut attestation, current_epoch)
+                .sign_attestation(pubkey, 0, &mut attestation, current_epoch, None)
                 .await
                 .unwrap();
             attestation
@@ -838,7 +856,7 @@ mod tests {
             move |pubkey, validator_store| async move {
                 let mut attestation = double_vote_attestation();
                 validator_store
-                    .sign_attestation(pubkey, 0, &mut attestation, current_epoch)
+                    .sign_attestation(pubkey, 0, &mut attestation, current_epoch, None)
                     .await
             },
             slashable_message_should_sign,
@@ -849,7 +867,7 @@ mod tests {
             move |pubkey, validator_store| async move {
                 let mut attestation = surrounding_attestation();
                 validator_store
-                    .sign_attestation(pubkey, 0, &mut attestation, current_epoch)
+                    .sign_attestation(pubkey, 0, &mut attestation, current_epoch, None)
                     .await
             },
             slashable_message_should_sign,
@@ -860,7 +878,7 @@ mod tests {
             move |pubkey, validator_store| async move {
                 let mut attestation = surrounded_attestation();
                 validator_store
-                    .sign_attestation(pubkey, 0, &mut attestation, current_epoch)
+                    .sign_attestation(pubkey, 0, &mut attestation, current_epoch, None)
                     .await
             },
             slashable_message_should_sign,
@@ -870,7 +888,7 @@ mod tests {
             let block = first_block();
             let slot = block.slot();
             validator_store
-                .sign_block(pubkey, block, slot)
+                .sign_block(pubkey, block, slot, None)
                 .await
                 .unwrap()
         })
@@ -881,7 +899,7 @@ mod tests {
                 let block = double_vote_block();
                 let slot = block.slot();
                 validator_store
-                    .sign_block(pubkey, block, slot)
+                    .sign_block(pubkey, block, slot, None)
                     .await
                     .map(|_| ())
             },
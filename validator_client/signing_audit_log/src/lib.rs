@@ -0,0 +1,232 @@
+//! An append-only, hash-chained log of every signing request handled by the validator client.
+//!
+//! Each record commits to the hash of the record before it, so modifying, re-ordering or removing
+//! a record breaks the chain from that point onwards. The log is written as JSON lines across a
+//! sequence of numbered files, starting a new file once the current one grows beyond a configured
+//! size. Older files may optionally be deleted, in which case verification starts from the oldest
+//! remaining record.
+//!
+//! Removing records from the *end* of the log cannot be detected from the log alone. Operators
+//! wishing to detect this should periodically record the hash of the latest record elsewhere and
+//! provide it as an anchor during verification.
+
+mod signing_audit_log;
+mod verification;
+
+pub use crate::signing_audit_log::SigningAuditLog;
+pub use crate::verification::{verify, VerificationSummary};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::PathBuf;
+use types::{Epoch, Hash256, PublicKeyBytes, Slot};
+
+/// The directory within the `validators` directory that contains the signing audit log.
+pub const SIGNING_AUDIT_LOG_DIR: &str = "signing_audit_log";
+
+/// The default size, in bytes, after which a new log file is started.
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 64 * 1024 * 1024;
+
+/// The `prev_hash` of the first record in the log.
+pub const GENESIS_PREV_HASH: Hash256 = Hash256::zero();
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    /// The size, in bytes, after which a new log file is started.
+    pub max_file_size: u64,
+    /// The maximum number of log files to keep, deleting the oldest. All files are kept if `None`.
+    pub max_files: Option<usize>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            max_files: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    PermissionsError,
+    Json(serde_json::Error),
+    /// A line of a log file could not be parsed.
+    Malformed {
+        path: PathBuf,
+        line: usize,
+        error: String,
+    },
+    /// A log file is missing from the middle of the sequence.
+    MissingFile(u64),
+    /// A record does not have the index following that of the previous record.
+    UnexpectedIndex {
+        path: PathBuf,
+        line: usize,
+        expected: u64,
+        found: u64,
+    },
+    /// The `prev_hash` of a record does not match the hash of the previous record.
+    BrokenChain {
+        path: PathBuf,
+        line: usize,
+        index: u64,
+    },
+    /// The `hash` of a record does not match its contents.
+    InvalidHash {
+        path: PathBuf,
+        line: usize,
+        index: u64,
+    },
+    /// No record with the given hash exists in the log.
+    AnchorNotFound(Hash256),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Json(e)
+    }
+}
+
+/// The type of message for which a signature was requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageType {
+    RandaoReveal,
+    BeaconBlock,
+    Attestation,
+    AggregateAndProof,
+    SelectionProof,
+    SyncSelectionProof,
+    SyncCommitteeMessage,
+    ContributionAndProof,
+    ValidatorRegistration,
+    VoluntaryExit,
+}
+
+/// What became of a signing request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// A signature was produced.
+    Signed,
+    /// Signing was refused by slashing protection.
+    Refused,
+    /// The signing method failed to produce a signature.
+    Failed,
+}
+
+/// A signing request, as provided by the validator client.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub pubkey: PublicKeyBytes,
+    pub message_type: MessageType,
+    pub signing_root: Hash256,
+    pub slot: Option<Slot>,
+    pub epoch: Option<Epoch>,
+    /// The method used to sign the message (e.g., `local_keystore`).
+    pub signing_method: String,
+    /// The beacon node which provided the message, if any.
+    pub beacon_node: Option<String>,
+    pub outcome: Outcome,
+}
+
+/// An `Entry` once it has been appended to the log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub index: u64,
+    /// Seconds since the UNIX epoch at which the record was created.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub timestamp: u64,
+    pub entry: Entry,
+    pub prev_hash: Hash256,
+    pub hash: Hash256,
+}
+
+/// The fields of a `Record` which are committed to by its `hash`.
+#[derive(Serialize)]
+struct HashedContents<'a> {
+    #[serde(with = "serde_utils::quoted_u64")]
+    index: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    timestamp: u64,
+    entry: &'a Entry,
+    prev_hash: Hash256,
+}
+
+impl Record {
+    /// Returns a record for `entry`, committing to `prev_hash`.
+    pub fn new(
+        index: u64,
+        timestamp: u64,
+        entry: Entry,
+        prev_hash: Hash256,
+    ) -> Result<Self, Error> {
+        let hash = Self::hash_contents(index, timestamp, &entry, prev_hash)?;
+        Ok(Self {
+            index,
+            timestamp,
+            entry,
+            prev_hash,
+            hash,
+        })
+    }
+
+    /// Returns the hash of the contents of this record, which should equal `self.hash`.
+    pub fn compute_hash(&self) -> Result<Hash256, Error> {
+        Self::hash_contents(self.index, self.timestamp, &self.entry, self.prev_hash)
+    }
+
+    fn hash_contents(
+        index: u64,
+        timestamp: u64,
+        entry: &Entry,
+        prev_hash: Hash256,
+    ) -> Result<Hash256, Error> {
+        let bytes = serde_json::to_vec(&HashedContents {
+            index,
+            timestamp,
+            entry,
+            prev_hash,
+        })?;
+        Ok(Hash256::from_slice(&ethereum_hashing::hash(&bytes)))
+    }
+}
+
+/// Selects records from the log.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Query {
+    pub pubkey: Option<PublicKeyBytes>,
+    pub message_type: Option<MessageType>,
+    /// Only return records with an index greater than or equal to this value.
+    pub start_index: Option<u64>,
+    /// The maximum number of records to return.
+    pub limit: Option<usize>,
+}
+
+impl Query {
+    fn matches(&self, record: &Record) -> bool {
+        self.start_index.map_or(true, |start| record.index >= start)
+            && self
+                .pubkey
+                .map_or(true, |pubkey| record.entry.pubkey == pubkey)
+            && self.message_type.map_or(true, |message_type| {
+                record.entry.message_type == message_type
+            })
+    }
+}
+
+/// The index and hash of the latest record in the log.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Head {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub index: u64,
+    pub hash: Hash256,
+}
@@ -0,0 +1,439 @@
+use crate::{Config, Entry, Error, Head, Query, Record, GENESIS_PREV_HASH};
+use filesystem::restrict_file_permissions;
+use parking_lot::Mutex;
+use slog::{warn, Logger};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const FILE_PREFIX: &str = "audit_";
+const FILE_EXTENSION: &str = "jsonl";
+
+/// The number of records returned by a query which does not specify a limit.
+pub const DEFAULT_QUERY_LIMIT: usize = 1_000;
+
+/// The file currently being appended to, and the latest record in the log.
+struct State {
+    file: File,
+    file_number: u64,
+    file_size: u64,
+    head: Option<Head>,
+}
+
+/// A writer for the signing audit log in some directory.
+///
+/// Only a single `SigningAuditLog` should exist for a directory at any time.
+pub struct SigningAuditLog {
+    dir: PathBuf,
+    config: Config,
+    state: Mutex<State>,
+}
+
+impl SigningAuditLog {
+    /// Open the log in `dir`, creating it if it does not exist.
+    ///
+    /// Only the latest record is checked, use `verify` to check the entire log. A partially
+    /// written record at the end of the log, as left by a crash whilst appending, is removed.
+    pub fn open_or_create(dir: &Path, config: Config, log: &Logger) -> Result<Self, Error> {
+        fs::create_dir_all(dir)?;
+
+        let files = list_files(dir)?;
+        if let Some((_, path)) = files.last() {
+            let removed = truncate_partial_line(path)?;
+            if removed > 0 {
+                warn!(
+                    log,
+                    "Removed partially written record from signing audit log";
+                    "path" => ?path,
+                    "bytes" => removed,
+                );
+            }
+        }
+
+        let mut head = None;
+        for (_, path) in files.iter().rev() {
+            if let Some((line, record)) = last_record(path)? {
+                if record.compute_hash()? != record.hash {
+                    return Err(Error::InvalidHash {
+                        path: path.clone(),
+                        line,
+                        index: record.index,
+                    });
+                }
+                head = Some(Head {
+                    index: record.index,
+                    hash: record.hash,
+                });
+                break;
+            }
+        }
+
+        let file_number = files.last().map_or(0, |(number, _)| *number);
+        let file = open_file(&file_path(dir, file_number))?;
+        let file_size = file.metadata()?.len();
+
+        Ok(Self {
+            dir: dir.into(),
+            config,
+            state: Mutex::new(State {
+                file,
+                file_number,
+                file_size,
+                head,
+            }),
+        })
+    }
+
+    /// Append `entry` to the log, returning the resulting record.
+    ///
+    /// The record is synced to disk before this function returns, so a returned record survives a
+    /// crash or power loss. This blocks on disk IO and should not be called from async tasks.
+    pub fn append(&self, entry: Entry) -> Result<Record, Error> {
+        let mut state = self.state.lock();
+
+        let (index, prev_hash) = state
+            .head
+            .map_or((0, GENESIS_PREV_HASH), |head| (head.index + 1, head.hash));
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        let record = Record::new(index, timestamp, entry, prev_hash)?;
+
+        let mut line = serde_json::to_vec(&record)?;
+        line.push(b'\n');
+
+        if state.file_size > 0 && state.file_size + line.len() as u64 > self.config.max_file_size {
+            self.rotate(&mut state)?;
+        }
+
+        // Write the line with a single call so that concurrent readers never observe a partial
+        // record before the end of the file.
+        state.file.write_all(&line)?;
+        state.file.sync_data()?;
+        state.file_size += line.len() as u64;
+        state.head = Some(Head {
+            index: record.index,
+            hash: record.hash,
+        });
+
+        Ok(record)
+    }
+
+    /// Returns the latest record in the log, if any.
+    pub fn head(&self) -> Option<Head> {
+        self.state.lock().head
+    }
+
+    /// Returns the records matching `query`, in ascending order of index.
+    ///
+    /// Records appended whilst the query is running are not returned.
+    pub fn query(&self, query: &Query) -> Result<Vec<Record>, Error> {
+        // Avoid holding the lock whilst reading, since it would block signing.
+        let Some(head) = self.head() else {
+            return Ok(vec![]);
+        };
+        let limit = query.limit.unwrap_or(DEFAULT_QUERY_LIMIT);
+        if limit == 0 {
+            return Ok(vec![]);
+        }
+
+        let mut records = vec![];
+        for (_, path) in list_files(&self.dir)? {
+            for result in read_records(&path)? {
+                let (_, record) = result?;
+                let index = record.index;
+                if query.matches(&record) {
+                    records.push(record);
+                    if records.len() >= limit {
+                        return Ok(records);
+                    }
+                }
+                // Stop at the head, since a record which is being appended may be incomplete.
+                if index >= head.index {
+                    return Ok(records);
+                }
+            }
+        }
+        Ok(records)
+    }
+
+    /// Start a new log file, deleting the oldest files if there are too many.
+    fn rotate(&self, state: &mut State) -> Result<(), Error> {
+        let file_number = state.file_number + 1;
+        state.file = open_file(&file_path(&self.dir, file_number))?;
+        state.file_number = file_number;
+        state.file_size = 0;
+
+        if let Some(max_files) = self.config.max_files {
+            let files = list_files(&self.dir)?;
+            let excess = files.len().saturating_sub(max_files.max(1));
+            for (_, path) in files.into_iter().take(excess) {
+                fs::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn file_path(dir: &Path, file_number: u64) -> PathBuf {
+    dir.join(format!(
+        "{}{:08}.{}",
+        FILE_PREFIX, file_number, FILE_EXTENSION
+    ))
+}
+
+/// Open the log file at `path` for appending, creating it with restricted permissions if it does
+/// not exist.
+fn open_file(path: &Path) -> Result<File, Error> {
+    let exists = path.exists();
+    let file = File::options().append(true).create(true).open(path)?;
+    if !exists {
+        restrict_file_permissions(path).map_err(|_| Error::PermissionsError)?;
+    }
+    Ok(file)
+}
+
+/// Returns the numbers and paths of the log files in `dir`, in ascending order.
+pub(crate) fn list_files(dir: &Path) -> Result<Vec<(u64, PathBuf)>, Error> {
+    let mut files = vec![];
+    for dir_entry in fs::read_dir(dir)? {
+        let path = dir_entry?.path();
+        let number = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(FILE_PREFIX))
+            .and_then(|name| name.strip_suffix(FILE_EXTENSION))
+            .and_then(|name| name.strip_suffix('.'))
+            .and_then(|number| number.parse::<u64>().ok());
+        if let Some(number) = number {
+            files.push((number, path));
+        }
+    }
+    files.sort_unstable_by_key(|(number, _)| *number);
+    Ok(files)
+}
+
+/// Returns an iterator over the records in the file at `path`, along with their line numbers
+/// (starting from 1).
+pub(crate) fn read_records(
+    path: &Path,
+) -> Result<impl Iterator<Item = Result<(usize, Record), Error>> + '_, Error> {
+    let reader = BufReader::new(File::open(path)?);
+    Ok(reader.lines().enumerate().map(move |(i, line)| {
+        let line_number = i + 1;
+        let record = serde_json::from_str(&line?).map_err(|e| Error::Malformed {
+            path: path.into(),
+            line: line_number,
+            error: e.to_string(),
+        })?;
+        Ok((line_number, record))
+    }))
+}
+
+/// Truncate the file at `path` to the end of its last complete line, returning the number of bytes
+/// removed.
+fn truncate_partial_line(path: &Path) -> Result<u64, Error> {
+    let mut file = File::options().read(true).write(true).open(path)?;
+    let len = file.metadata()?.len();
+
+    // Search backwards from the end of the file for the last newline.
+    let mut end = len;
+    let mut buf = [0; 4096];
+    while end > 0 {
+        let chunk_start = end.saturating_sub(buf.len() as u64);
+        let chunk = &mut buf[..(end - chunk_start) as usize];
+        file.seek(SeekFrom::Start(chunk_start))?;
+        file.read_exact(chunk)?;
+        if let Some(position) = chunk.iter().rposition(|byte| *byte == b'\n') {
+            end = chunk_start + position as u64 + 1;
+            break;
+        }
+        end = chunk_start;
+    }
+
+    let removed = len - end;
+    if removed > 0 {
+        file.set_len(end)?;
+        file.sync_all()?;
+    }
+    Ok(removed)
+}
+
+fn last_record(path: &Path) -> Result<Option<(usize, Record)>, Error> {
+    let mut last = None;
+    for result in read_records(path)? {
+        last = Some(result?);
+    }
+    Ok(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{verify, MessageType, Outcome};
+    use slog::o;
+    use tempfile::tempdir;
+    use types::{Epoch, Hash256, PublicKeyBytes, Slot};
+
+    fn open(dir: &Path, config: Config) -> SigningAuditLog {
+        let log = Logger::root(slog::Discard, o!());
+        SigningAuditLog::open_or_create(dir, config, &log).unwrap()
+    }
+
+    fn entry(slot: u64) -> Entry {
+        Entry {
+            pubkey: PublicKeyBytes::empty(),
+            message_type: MessageType::BeaconBlock,
+            signing_root: Hash256::from_low_u64_be(slot),
+            slot: Some(Slot::new(slot)),
+            epoch: Some(Epoch::new(slot / 32)),
+            signing_method: "local_keystore".into(),
+            beacon_node: Some("http://localhost:5052/".into()),
+            outcome: Outcome::Signed,
+        }
+    }
+
+    #[test]
+    fn append_and_reopen() {
+        let dir = tempdir().unwrap();
+        let log = open(dir.path(), Config::default());
+        assert_eq!(log.head(), None);
+
+        let first = log.append(entry(1)).unwrap();
+        assert_eq!(first.index, 0);
+        assert_eq!(first.prev_hash, GENESIS_PREV_HASH);
+        let second = log.append(entry(2)).unwrap();
+        assert_eq!(second.index, 1);
+        assert_eq!(second.prev_hash, first.hash);
+        drop(log);
+
+        let log = open(dir.path(), Config::default());
+        assert_eq!(
+            log.head(),
+            Some(Head {
+                index: 1,
+                hash: second.hash
+            })
+        );
+        let third = log.append(entry(3)).unwrap();
+        assert_eq!(third.index, 2);
+        assert_eq!(third.prev_hash, second.hash);
+
+        let summary = verify(dir.path(), None).unwrap();
+        assert_eq!(summary.record_count, 3);
+        assert_eq!(summary.first_index, Some(0));
+        assert_eq!(summary.head.map(|head| head.hash), Some(third.hash));
+    }
+
+    #[test]
+    fn rotation_preserves_chain() {
+        let dir = tempdir().unwrap();
+        let config = Config {
+            max_file_size: 1,
+            max_files: None,
+        };
+        let log = open(dir.path(), config);
+        for slot in 0..4 {
+            log.append(entry(slot)).unwrap();
+        }
+
+        // Each record exceeds the maximum size, so is written to its own file.
+        assert_eq!(list_files(dir.path()).unwrap().len(), 4);
+        let summary = verify(dir.path(), None).unwrap();
+        assert_eq!(summary.record_count, 4);
+        assert_eq!(summary.files, 4);
+        assert!(!summary.pruned);
+    }
+
+    #[test]
+    fn rotation_prunes_old_files() {
+        let dir = tempdir().unwrap();
+        let config = Config {
+            max_file_size: 1,
+            max_files: Some(2),
+        };
+        let log = open(dir.path(), config);
+        for slot in 0..4 {
+            log.append(entry(slot)).unwrap();
+        }
+
+        assert_eq!(list_files(dir.path()).unwrap().len(), 2);
+        let summary = verify(dir.path(), None).unwrap();
+        assert_eq!(summary.record_count, 2);
+        assert_eq!(summary.first_index, Some(2));
+        assert!(summary.pruned);
+    }
+
+    #[test]
+    fn query() {
+        let dir = tempdir().unwrap();
+        let log = open(dir.path(), Config::default());
+        let other_pubkey = PublicKeyBytes::deserialize(&[1; 48]).unwrap();
+        for slot in 0..10 {
+            let mut entry = entry(slot);
+            if slot % 2 == 0 {
+                entry.pubkey = other_pubkey;
+                entry.message_type = MessageType::Attestation;
+            }
+            log.append(entry).unwrap();
+        }
+
+        let records = log
+            .query(&Query {
+                pubkey: Some(other_pubkey),
+                ..Query::default()
+            })
+            .unwrap();
+        assert_eq!(
+            records.iter().map(|r| r.index).collect::<Vec<_>>(),
+            vec![0, 2, 4, 6, 8]
+        );
+
+        let records = log
+            .query(&Query {
+                message_type: Some(MessageType::BeaconBlock),
+                start_index: Some(4),
+                limit: Some(2),
+                ..Query::default()
+            })
+            .unwrap();
+        assert_eq!(
+            records.iter().map(|r| r.index).collect::<Vec<_>>(),
+            vec![5, 7]
+        );
+    }
+
+    #[test]
+    fn reopen_after_partial_append() {
+        let dir = tempdir().unwrap();
+        let log = open(dir.path(), Config::default());
+        log.append(entry(1)).unwrap();
+        let second = log.append(entry(2)).unwrap();
+        drop(log);
+
+        // Simulate a crash part way through appending a record.
+        let (_, path) = list_files(dir.path()).unwrap().pop().unwrap();
+        let complete_len = fs::metadata(&path).unwrap().len();
+        let mut file = File::options().append(true).open(&path).unwrap();
+        file.write_all(br#"{"index":2,"timestamp":17"#).unwrap();
+        drop(file);
+
+        let log = open(dir.path(), Config::default());
+        assert_eq!(fs::metadata(&path).unwrap().len(), complete_len);
+        assert_eq!(
+            log.head(),
+            Some(Head {
+                index: 1,
+                hash: second.hash
+            })
+        );
+        let third = log.append(entry(3)).unwrap();
+        assert_eq!(third.index, 2);
+        assert_eq!(third.prev_hash, second.hash);
+
+        let summary = verify(dir.path(), None).unwrap();
+        assert_eq!(summary.record_count, 3);
+    }
+}
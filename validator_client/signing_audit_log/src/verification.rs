@@ -0,0 +1,279 @@
+use crate::signing_audit_log::{list_files, read_records};
+use crate::{Error, Head, GENESIS_PREV_HASH};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use types::Hash256;
+
+/// The result of successfully verifying the log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerificationSummary {
+    /// The number of log files.
+    pub files: usize,
+    /// The number of records across all files.
+    pub record_count: u64,
+    /// The index of the oldest record.
+    pub first_index: Option<u64>,
+    /// The latest record.
+    pub head: Option<Head>,
+    /// True if older records have been deleted, such that the oldest remaining record could not
+    /// be checked against its predecessor.
+    pub pruned: bool,
+}
+
+/// Check that every record in the log in `dir` is intact and correctly chained to the one before
+/// it, returning the first inconsistency found.
+///
+/// If `anchor` is provided, also check that a record with that hash is present. Providing the hash
+/// of a previously observed head detects removal of records from the end of the log.
+pub fn verify(dir: &Path, anchor: Option<Hash256>) -> Result<VerificationSummary, Error> {
+    let files = list_files(dir)?;
+
+    let mut record_count = 0;
+    let mut first_index = None;
+    let mut head: Option<Head> = None;
+    let mut pruned = false;
+    let mut anchor_found = false;
+    let mut prev_file_number = None;
+
+    for (file_number, path) in &files {
+        if let Some(prev_file_number) = prev_file_number {
+            if *file_number != prev_file_number + 1 {
+                return Err(Error::MissingFile(prev_file_number + 1));
+            }
+        }
+        prev_file_number = Some(*file_number);
+
+        for result in read_records(path)? {
+            let (line, record) = result?;
+
+            match head {
+                Some(prev) => {
+                    if record.index != prev.index + 1 {
+                        return Err(Error::UnexpectedIndex {
+                            path: path.clone(),
+                            line,
+                            expected: prev.index + 1,
+                            found: record.index,
+                        });
+                    }
+                    if record.prev_hash != prev.hash {
+                        return Err(Error::BrokenChain {
+                            path: path.clone(),
+                            line,
+                            index: record.index,
+                        });
+                    }
+                }
+                // The oldest record can only be checked against the genesis if it is the first
+                // record ever written.
+                None => {
+                    if record.index == 0 && record.prev_hash != GENESIS_PREV_HASH {
+                        return Err(Error::BrokenChain {
+                            path: path.clone(),
+                            line,
+                            index: record.index,
+                        });
+                    }
+                    first_index = Some(record.index);
+                    pruned = record.index != 0;
+                }
+            }
+
+            if record.compute_hash()? != record.hash {
+                return Err(Error::InvalidHash {
+                    path: path.clone(),
+                    line,
+                    index: record.index,
+                });
+            }
+
+            anchor_found |= anchor == Some(record.hash);
+            record_count += 1;
+            head = Some(Head {
+                index: record.index,
+                hash: record.hash,
+            });
+        }
+    }
+
+    if let Some(anchor) = anchor {
+        if !anchor_found {
+            return Err(Error::AnchorNotFound(anchor));
+        }
+    }
+
+    Ok(VerificationSummary {
+        files: files.len(),
+        record_count,
+        first_index,
+        head,
+        pruned,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Config, Entry, MessageType, Outcome, Record, SigningAuditLog};
+    use std::fs;
+    use tempfile::{tempdir, TempDir};
+    use types::{Epoch, PublicKeyBytes};
+
+    fn null_logger() -> slog::Logger {
+        slog::Logger::root(slog::Discard, slog::o!())
+    }
+
+    fn entry(epoch: u64) -> Entry {
+        Entry {
+            pubkey: PublicKeyBytes::empty(),
+            message_type: MessageType::Attestation,
+            signing_root: Hash256::from_low_u64_be(epoch),
+            slot: None,
+            epoch: Some(Epoch::new(epoch)),
+            signing_method: "web3signer".into(),
+            beacon_node: None,
+            outcome: Outcome::Signed,
+        }
+    }
+
+    /// Returns a directory containing a log of `n` records in a single file, and those records.
+    fn log_with_records(n: u64) -> (TempDir, Vec<Record>) {
+        let dir = tempdir().unwrap();
+        let log =
+            SigningAuditLog::open_or_create(dir.path(), Config::default(), &null_logger()).unwrap();
+        let records = (0..n).map(|i| log.append(entry(i)).unwrap()).collect();
+        (dir, records)
+    }
+
+    fn log_file(dir: &TempDir) -> std::path::PathBuf {
+        list_files(dir.path()).unwrap().remove(0).1
+    }
+
+    /// Replace the lines of the single log file in `dir` with `records`.
+    fn rewrite(dir: &TempDir, records: &[Record]) {
+        let contents = records
+            .iter()
+            .map(|record| serde_json::to_string(record).unwrap() + "\n")
+            .collect::<String>();
+        fs::write(log_file(dir), contents).unwrap();
+    }
+
+    #[test]
+    fn empty_log() {
+        let dir = tempdir().unwrap();
+        let summary = verify(dir.path(), None).unwrap();
+        assert_eq!(summary.record_count, 0);
+        assert_eq!(summary.head, None);
+    }
+
+    #[test]
+    fn intact_log_with_anchor() {
+        let (dir, records) = log_with_records(5);
+        let summary = verify(dir.path(), Some(records[2].hash)).unwrap();
+        assert_eq!(summary.record_count, 5);
+        assert_eq!(summary.head.map(|head| head.hash), Some(records[4].hash));
+    }
+
+    #[test]
+    fn modified_record() {
+        let (dir, mut records) = log_with_records(5);
+        records[2].entry.signing_root = Hash256::repeat_byte(0xff);
+        rewrite(&dir, &records);
+
+        assert!(matches!(
+            verify(dir.path(), None),
+            Err(Error::InvalidHash {
+                index: 2,
+                line: 3,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn modified_and_rehashed_record() {
+        let (dir, mut records) = log_with_records(5);
+        records[2].entry.signing_root = Hash256::repeat_byte(0xff);
+        records[2].hash = records[2].compute_hash().unwrap();
+        rewrite(&dir, &records);
+
+        assert!(matches!(
+            verify(dir.path(), None),
+            Err(Error::BrokenChain { index: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn removed_record() {
+        let (dir, mut records) = log_with_records(5);
+        records.remove(2);
+        rewrite(&dir, &records);
+
+        assert!(matches!(
+            verify(dir.path(), None),
+            Err(Error::UnexpectedIndex {
+                expected: 2,
+                found: 3,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn removed_first_record() {
+        let (dir, mut records) = log_with_records(5);
+        records.remove(0);
+        rewrite(&dir, &records);
+
+        // Indistinguishable from pruning, but reported as such.
+        let summary = verify(dir.path(), None).unwrap();
+        assert!(summary.pruned);
+        assert_eq!(summary.first_index, Some(1));
+    }
+
+    #[test]
+    fn removed_last_records_detected_by_anchor() {
+        let (dir, mut records) = log_with_records(5);
+        let anchor = records[4].hash;
+        records.truncate(3);
+        rewrite(&dir, &records);
+
+        assert!(verify(dir.path(), None).is_ok());
+        assert!(matches!(
+            verify(dir.path(), Some(anchor)),
+            Err(Error::AnchorNotFound(hash)) if hash == anchor
+        ));
+    }
+
+    #[test]
+    fn malformed_record() {
+        let (dir, _) = log_with_records(2);
+        let mut contents = fs::read_to_string(log_file(&dir)).unwrap();
+        contents.push_str("{\"index\":\n");
+        fs::write(log_file(&dir), contents).unwrap();
+
+        assert!(matches!(
+            verify(dir.path(), None),
+            Err(Error::Malformed { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn missing_file() {
+        let dir = tempdir().unwrap();
+        let config = Config {
+            max_file_size: 1,
+            max_files: None,
+        };
+        let log = SigningAuditLog::open_or_create(dir.path(), config, &null_logger()).unwrap();
+        for i in 0..3 {
+            log.append(entry(i)).unwrap();
+        }
+        fs::remove_file(&list_files(dir.path()).unwrap()[1].1).unwrap();
+
+        assert!(matches!(
+            verify(dir.path(), None),
+            Err(Error::MissingFile(1))
+        ));
+    }
+}
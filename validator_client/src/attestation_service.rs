@@ -337,7 +337,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             .ok_or("Unable to determine current slot from clock")?
             .epoch(E::slots_per_epoch());
 
-        let attestation_data = self
+        let (attestation_data, beacon_node) = self
             .beacon_nodes
             .first_success(
                 RequireSynced::No,
@@ -351,7 +351,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                         .get_validator_attestation_data(slot, committee_index)
                         .await
                         .map_err(|e| format!("Failed to produce attestation data: {:?}", e))
-                        .map(|result| result.data)
+                        .map(|result| (result.data, beacon_node.to_string()))
                 },
             )
             .await
@@ -359,6 +359,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
 
         // Create futures to produce signed `Attestation` objects.
         let attestation_data_ref = &attestation_data;
+        let beacon_node = beacon_node.as_str();
         let signing_futures = validator_duties.iter().map(|duty_and_proof| async move {
             let duty = &duty_and_proof.duty;
             let attestation_data = attestation_data_ref;
@@ -406,6 +407,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                     duty.validator_committee_index as usize,
                     &mut attestation,
                     current_epoch,
+                    Some(beacon_node),
                 )
                 .await
             {
@@ -439,11 +441,13 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         });
 
         // Execute all the futures in parallel, collecting any successful results.
-        let (ref attestations, ref validator_indices): (Vec<_>, Vec<_>) = join_all(signing_futures)
+        let (attestations, ref validator_indices): (Vec<_>, Vec<_>) = join_all(signing_futures)
             .await
             .into_iter()
             .flatten()
             .unzip();
+        // Shared with the requests to each beacon node, which may outlive this function.
+        let attestations = Arc::new(attestations);
 
         if attestations.is_empty() {
             warn!(log, "No attestations were published");
@@ -462,19 +466,25 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
                 ApiTopic::Attestations,
-                |beacon_node| async move {
-                    let _timer = metrics::start_timer_vec(
-                        &metrics::ATTESTATION_SERVICE_TIMES,
-                        &[metrics::ATTESTATIONS_HTTP_POST],
-                    );
-                    if fork_name.electra_enabled() {
-                        beacon_node
-                            .post_beacon_pool_attestations_v2(attestations, fork_name)
-                            .await
-                    } else {
-                        beacon_node
-                            .post_beacon_pool_attestations_v1(attestations)
-                            .await
+                {
+                    let attestations = attestations.clone();
+                    move |beacon_node| {
+                        let attestations = attestations.clone();
+                        async move {
+                            let _timer = metrics::start_timer_vec(
+                                &metrics::ATTESTATION_SERVICE_TIMES,
+                                &[metrics::ATTESTATIONS_HTTP_POST],
+                            );
+                            if fork_name.electra_enabled() {
+                                beacon_node
+                                    .post_beacon_pool_attestations_v2(&attestations, fork_name)
+                                    .await
+                            } else {
+                                beacon_node
+                                    .post_beacon_pool_attestations_v1(&attestations)
+                                    .await
+                            }
+                        }
                     }
                 },
             )
@@ -538,7 +548,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             .spec
             .fork_name_at_slot::<E>(attestation_data.slot);
 
-        let aggregated_attestation = &self
+        let (aggregated_attestation, beacon_node) = &self
             .beacon_nodes
             .first_success(
                 RequireSynced::No,
@@ -562,7 +572,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                             .ok_or_else(|| {
                                 format!("No aggregate available for {:?}", attestation_data)
                             })
-                            .map(|result| result.data)
+                            .map(|result| (result.data, beacon_node.to_string()))
                     } else {
                         beacon_node
                             .get_validator_aggregate_attestation_v1(
@@ -576,7 +586,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                             .ok_or_else(|| {
                                 format!("No aggregate available for {:?}", attestation_data)
                             })
-                            .map(|result| result.data)
+                            .map(|result| (result.data, beacon_node.to_string()))
                     }
                 },
             )
@@ -600,6 +610,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                     duty.validator_index,
                     aggregated_attestation.clone(),
                     selection_proof.clone(),
+                    Some(beacon_node.as_str()),
                 )
                 .await
             {
@@ -2,11 +2,17 @@
 //! "fallback" behaviour; it will try a request on all of the nodes until one or none of them
 //! succeed.
 
-use crate::check_synced::check_synced;
-use crate::http_metrics::metrics::{inc_counter_vec, ENDPOINT_ERRORS, ENDPOINT_REQUESTS};
+use crate::beacon_node_health::{self, RequestStats, SyncHealth};
+use crate::check_synced::{check_synced, get_sync_status};
+use crate::http_metrics::metrics::{
+    inc_counter_vec, set_int_gauge, start_timer_vec, ENDPOINT_ERRORS, ENDPOINT_REQUESTS,
+    VC_BEACON_NODE_HEALTH_SCORE, VC_BEACON_NODE_PUBLISH_TIMES, VC_BEACON_NODE_RANK,
+};
 use environment::RuntimeContext;
+use eth2::lighthouse_vc::types::BeaconNodeHealthData;
 use eth2::BeaconNodeHttpClient;
 use futures::future;
+use futures::stream::{FuturesUnordered, StreamExt};
 use serde::{Deserialize, Serialize};
 use slog::{debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
@@ -16,9 +22,10 @@ use std::future::Future;
 use std::marker::PhantomData;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use strum::{EnumString, EnumVariantNames};
+use strum::{EnumString, EnumVariantNames, IntoStaticStr};
+use task_executor::TaskExecutor;
 use tokio::{sync::RwLock, time::sleep};
-use types::{ChainSpec, Config, EthSpec};
+use types::{ChainSpec, Config, EthSpec, Slot};
 
 /// Message emitted when the VC detects the BN is using a different spec.
 const UPDATE_REQUIRED_LOG_HINT: &str = "this VC or the remote BN may need updating";
@@ -149,11 +156,34 @@ pub enum CandidateError {
     NotSynced,
 }
 
+impl CandidateError {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CandidateError::Uninitialized => "uninitialized",
+            CandidateError::Offline => "offline",
+            CandidateError::Incompatible => "incompatible",
+            CandidateError::NotSynced => "not_synced",
+        }
+    }
+}
+
+/// The position of a candidate in the ranking maintained by `BeaconNodeFallback`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CandidateRank {
+    /// The index of the candidate in `BeaconNodeFallback::candidates`.
+    pub index: usize,
+    /// The health score of the candidate, lower is better.
+    pub score: u64,
+}
+
 /// Represents a `BeaconNodeHttpClient` inside a `BeaconNodeFallback` that may or may not be used
 /// for a query.
 pub struct CandidateBeaconNode<E> {
     beacon_node: BeaconNodeHttpClient,
     status: RwLock<Result<(), CandidateError>>,
+    sync_health: RwLock<Option<SyncHealth>>,
+    latency: RwLock<Option<Duration>>,
+    request_stats: RwLock<RequestStats>,
     _phantom: PhantomData<E>,
 }
 
@@ -163,6 +193,9 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         Self {
             beacon_node,
             status: RwLock::new(Err(CandidateError::Uninitialized)),
+            sync_health: RwLock::new(None),
+            latency: RwLock::new(None),
+            request_stats: RwLock::new(RequestStats::default()),
             _phantom: PhantomData,
         }
     }
@@ -182,6 +215,11 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         *self.status.write().await = Err(CandidateError::Offline)
     }
 
+    /// Record the outcome of a request, for use in the candidate's error rate.
+    async fn record_request(&self, success: bool) {
+        self.request_stats.write().await.record(success)
+    }
+
     /// Perform some queries against the node to determine if it is a good candidate, updating
     /// `self.status` and returning that result.
     pub async fn refresh_status<T: SlotClock>(
@@ -203,6 +241,11 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
             Ok(())
         };
 
+        // Don't rank an unreachable node on the basis of its last response.
+        if matches!(new_status, Err(CandidateError::Offline)) {
+            *self.sync_health.write().await = None;
+        }
+
         // In case of concurrent use, the latest value will always be used. It's possible that a
         // long time out might over-ride a recent successful response, leading to a falsely-offline
         // status. I deem this edge-case acceptable in return for the concurrency benefits of not
@@ -332,7 +375,9 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         log: &Logger,
     ) -> Result<(), CandidateError> {
         if let Some(slot_clock) = slot_clock {
-            check_synced(&self.beacon_node, slot_clock, Some(log)).await
+            let sync_status = get_sync_status(&self.beacon_node, Some(log)).await?;
+            *self.sync_health.write().await = Some(SyncHealth::from(&sync_status));
+            check_synced(&sync_status, &self.beacon_node, slot_clock, Some(log))
         } else {
             // Skip this check if we don't supply a slot clock.
             Ok(())
@@ -344,10 +389,13 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
 /// behaviour, where the failure of one candidate results in the next candidate receiving an
 /// identical query.
 pub struct BeaconNodeFallback<T, E> {
-    candidates: Vec<CandidateBeaconNode<E>>,
+    candidates: Vec<Arc<CandidateBeaconNode<E>>>,
+    /// The order in which `candidates` are tried by `Self::first_success`, best first.
+    ranking: RwLock<Vec<CandidateRank>>,
     slot_clock: Option<T>,
     broadcast_topics: Vec<ApiTopic>,
     spec: ChainSpec,
+    executor: TaskExecutor,
     log: Logger,
 }
 
@@ -356,13 +404,20 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         candidates: Vec<CandidateBeaconNode<E>>,
         broadcast_topics: Vec<ApiTopic>,
         spec: ChainSpec,
+        executor: TaskExecutor,
         log: Logger,
     ) -> Self {
+        // Until the first ranking, candidates are tried in the order they were supplied.
+        let ranking = (0..candidates.len())
+            .map(|index| CandidateRank { index, score: 0 })
+            .collect();
         Self {
-            candidates,
+            candidates: candidates.into_iter().map(Arc::new).collect(),
+            ranking: RwLock::new(ranking),
             slot_clock: None,
             broadcast_topics,
             spec,
+            executor,
             log,
         }
     }
@@ -430,6 +485,120 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
 
         // run all updates concurrently and ignore errors
         let _ = future::join_all(futures).await;
+
+        self.update_ranking().await;
+    }
+
+    /// Score each candidate and re-order `self.ranking` so the healthiest candidates are tried
+    /// first.
+    ///
+    /// Candidates are ordered by their status (synced, then unsynced, then unavailable) and then by
+    /// their score. Ties are broken by the order the candidates were supplied in.
+    ///
+    /// The request statistics of each candidate are decayed each time this is called, so it should
+    /// be called once per slot.
+    pub async fn update_ranking(&self) {
+        let mut sync_healths = Vec::with_capacity(self.candidates.len());
+        for candidate in &self.candidates {
+            sync_healths.push(*candidate.sync_health.read().await);
+        }
+        let best_head = sync_healths
+            .iter()
+            .flatten()
+            .map(|health| health.head_slot)
+            .max()
+            .unwrap_or_else(|| Slot::new(0));
+
+        let mut ranked = Vec::with_capacity(self.candidates.len());
+        for (index, (candidate, sync_health)) in
+            self.candidates.iter().zip(sync_healths).enumerate()
+        {
+            let status_tier = match candidate.status(RequireSynced::Yes).await {
+                Ok(()) => 0,
+                Err(CandidateError::NotSynced) => 1,
+                Err(_) => 2,
+            };
+            let latency = *candidate.latency.read().await;
+            let error_rate = {
+                let mut request_stats = candidate.request_stats.write().await;
+                let error_rate = request_stats.error_rate();
+                request_stats.decay();
+                error_rate
+            };
+            let score =
+                beacon_node_health::score(sync_health.as_ref(), best_head, latency, error_rate);
+
+            set_int_gauge(
+                &VC_BEACON_NODE_HEALTH_SCORE,
+                &[candidate.beacon_node.as_ref()],
+                score as i64,
+            );
+
+            ranked.push((status_tier, CandidateRank { index, score }));
+        }
+
+        // The sort is stable, so equal candidates retain their original order.
+        ranked.sort_by_key(|(status_tier, rank)| (*status_tier, rank.score));
+        let ranking: Vec<_> = ranked.into_iter().map(|(_, rank)| rank).collect();
+
+        for (position, rank) in ranking.iter().enumerate() {
+            set_int_gauge(
+                &VC_BEACON_NODE_RANK,
+                &[self.candidates[rank.index].beacon_node.as_ref()],
+                position as i64,
+            );
+        }
+
+        let mut current_ranking = self.ranking.write().await;
+        let previous_best = current_ranking.first().map(|rank| rank.index);
+        let new_best = ranking.first().map(|rank| rank.index);
+        if previous_best != new_best {
+            if let Some(candidate) = new_best.and_then(|index| self.candidates.get(index)) {
+                info!(
+                    self.log,
+                    "Preferred beacon node changed";
+                    "endpoint" => %candidate.beacon_node,
+                );
+            }
+        }
+        debug!(
+            self.log,
+            "Ranked beacon nodes";
+            "ranking" => ?ranking,
+        );
+        *current_ranking = ranking;
+    }
+
+    /// Returns the health of each candidate, in the order they are currently ranked.
+    pub async fn health(&self) -> Vec<BeaconNodeHealthData> {
+        let ranking = self.ranking.read().await.clone();
+        let mut health = Vec::with_capacity(ranking.len());
+        for rank in ranking {
+            let Some(candidate) = self.candidates.get(rank.index) else {
+                continue;
+            };
+            let status = match *candidate.status.read().await {
+                Ok(()) => "synced",
+                Err(e) => e.as_str(),
+            };
+            let sync_health = *candidate.sync_health.read().await;
+            let latency = *candidate.latency.read().await;
+            let error_rate = candidate.request_stats.read().await.error_rate();
+
+            health.push(BeaconNodeHealthData {
+                index: rank.index,
+                endpoint: candidate.beacon_node.to_string(),
+                status: status.to_string(),
+                score: sync_health.map(|_| rank.score),
+                head_slot: sync_health.map(|health| health.head_slot),
+                sync_distance: sync_health.map(|health| health.sync_distance),
+                is_optimistic: sync_health.map(|health| health.is_optimistic),
+                el_offline: sync_health.map(|health| health.el_offline),
+                latency_millis: latency.map(|latency| latency.as_millis() as u64),
+                error_rate,
+            });
+        }
+        health
     }
 
     /// Concurrently send a request to all candidates (regardless of
@@ -439,7 +608,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         let futures: Vec<_> = self
             .candidates
             .iter()
-            .map(|candidate| async {
+            .map(|candidate| async move {
                 let beacon_node_id = candidate.beacon_node.to_string();
                 // The `node/version` endpoint is used since I imagine it would
                 // require the least processing in the BN and therefore measure
@@ -453,7 +622,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
                     .await
                     .ok()
                     .map(|_| Instant::now());
-                (beacon_node_id, response_instant)
+                (candidate, beacon_node_id, response_instant)
             })
             .collect();
 
@@ -462,15 +631,20 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         // Send the request to all BNs at the same time. This might involve some
         // queueing on the sending host, however I hope it will avoid bias
         // caused by sending requests at different times.
-        future::join_all(futures)
-            .await
-            .into_iter()
-            .map(|(beacon_node_id, response_instant)| LatencyMeasurement {
+        let mut measurements = Vec::with_capacity(self.candidates.len());
+        for (candidate, beacon_node_id, response_instant) in future::join_all(futures).await {
+            let latency = response_instant
+                .and_then(|response| response.checked_duration_since(request_instant));
+
+            // Retain the measurement for use in the candidate's health score.
+            *candidate.latency.write().await = latency;
+
+            measurements.push(LatencyMeasurement {
                 beacon_node_id,
-                latency: response_instant
-                    .and_then(|response| response.checked_duration_since(request_instant)),
-            })
-            .collect()
+                latency,
+            });
+        }
+        measurements
     }
 
     /// Run `func` against each candidate in `self`, returning immediately if a result is found.
@@ -506,8 +680,12 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
                 // There exists a race condition where `func` may be called when the candidate is
                 // actually not ready. We deem this an acceptable inefficiency.
                 match func(&$candidate.beacon_node).await {
-                    Ok(val) => return Ok(val),
+                    Ok(val) => {
+                        $candidate.record_request(true).await;
+                        return Ok(val);
+                    }
                     Err(e) => {
+                        $candidate.record_request(false).await;
                         debug!(
                             log,
                             "Request to beacon node failed";
@@ -529,10 +707,16 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
             }};
         }
 
+        // Candidates are tried in order of their health, see `Self::update_ranking`.
+        let ranking = self.ranking.read().await.clone();
+        let ranked_candidates = ranking
+            .iter()
+            .filter_map(|rank| self.candidates.get(rank.index));
+
         // First pass: try `func` on all synced and ready candidates.
         //
         // This ensures that we always choose a synced node if it is available.
-        for candidate in &self.candidates {
+        for candidate in ranked_candidates {
             match candidate.status(RequireSynced::Yes).await {
                 Err(e @ CandidateError::NotSynced) if require_synced == false => {
                     // This client is unsynced we will try it after trying all synced clients
@@ -615,8 +799,12 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
             // There exists a race condition where `func` may be called when the candidate is
             // actually not ready. We deem this an acceptable inefficiency.
             match func(&candidate.beacon_node).await {
-                Ok(val) => Ok(val),
+                Ok(val) => {
+                    candidate.record_request(true).await;
+                    Ok(val)
+                }
                 Err(e) => {
+                    candidate.record_request(false).await;
                     // If we have an error on this function, mark the client as not-ready.
                     //
                     // There exists a race condition where the candidate may have been marked
@@ -635,7 +823,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         //
         // This ensures that we always choose a synced node if it is available.
         let mut first_batch_futures = vec![];
-        for candidate in &self.candidates {
+        for candidate in self.candidates.iter().map(|candidate| &**candidate) {
             match candidate.status(RequireSynced::Yes).await {
                 Ok(_) => {
                     first_batch_futures.push(run_on_candidate(candidate));
@@ -704,33 +892,183 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         }
     }
 
+    /// Concurrently publish using `func` on all ready candidates, returning as soon as one of them
+    /// succeeds.
+    ///
+    /// Each request is spawned as a separate task, so the requests to the remaining candidates
+    /// continue in the background after this function returns. The time taken by each candidate
+    /// is recorded in the `VC_BEACON_NODE_PUBLISH_TIMES` metric.
+    ///
+    /// If none of the ready candidates succeed, the remaining candidates are tried one at a time
+    /// after attempting to make them ready, as per `Self::first_success`.
+    pub async fn broadcast_first_success<F, Err, R>(
+        &self,
+        require_synced: RequireSynced,
+        offline_on_failure: OfflineOnFailure,
+        topic: ApiTopic,
+        func: F,
+    ) -> Result<(), Errors<Err>>
+    where
+        F: Fn(BeaconNodeHttpClient) -> R + Send + Sync + 'static,
+        R: Future<Output = Result<(), Err>> + Send + 'static,
+        Err: Debug + Send + 'static,
+    {
+        let func = Arc::new(func);
+        let mut errors = vec![];
+        let mut to_retry = vec![];
+        let mut pending = FuturesUnordered::new();
+
+        for candidate in &self.candidates {
+            match candidate.status(require_synced).await {
+                Ok(()) => {
+                    let publication = publish_on_candidate(
+                        candidate.clone(),
+                        func.clone(),
+                        offline_on_failure,
+                        topic,
+                        self.log.clone(),
+                    );
+                    if let Some(handle) = self.executor.spawn_handle(publication, "broadcast") {
+                        pending.push(handle);
+                    }
+                }
+                Err(_) => to_retry.push(candidate),
+            }
+        }
+
+        while let Some(result) = pending.next().await {
+            match result {
+                Ok(Some((_, Ok(())))) => return Ok(()),
+                Ok(Some((beacon_node_id, Err(e)))) => errors.push((beacon_node_id, e)),
+                // The task was cancelled due to shutdown.
+                Ok(None) | Err(_) => (),
+            }
+        }
+
+        // None of the ready candidates succeeded, try to make the others ready.
+        for candidate in to_retry {
+            let new_status = candidate
+                .refresh_status(self.slot_clock.as_ref(), &self.spec, &self.log)
+                .await;
+
+            match new_status {
+                Ok(()) => (),
+                Err(CandidateError::NotSynced) if require_synced == false => (),
+                Err(e) => {
+                    errors.push((candidate.beacon_node.to_string(), Error::Unavailable(e)));
+                    continue;
+                }
+            }
+
+            match publish_on_candidate(
+                candidate.clone(),
+                func.clone(),
+                offline_on_failure,
+                topic,
+                self.log.clone(),
+            )
+            .await
+            {
+                (_, Ok(())) => return Ok(()),
+                (beacon_node_id, Err(e)) => errors.push((beacon_node_id, e)),
+            }
+        }
+
+        Err(Errors(errors))
+    }
+
     /// Call `func` on first beacon node that returns success or on all beacon nodes
     /// depending on the `topic` and configuration.
-    pub async fn request<'a, F, Err, R>(
-        &'a self,
+    ///
+    /// When the `topic` is broadcast this function returns as soon as one beacon node succeeds,
+    /// see `Self::broadcast_first_success`.
+    pub async fn request<F, Err, R>(
+        &self,
         require_synced: RequireSynced,
         offline_on_failure: OfflineOnFailure,
         topic: ApiTopic,
         func: F,
     ) -> Result<(), Errors<Err>>
     where
-        F: Fn(&'a BeaconNodeHttpClient) -> R,
-        R: Future<Output = Result<(), Err>>,
-        Err: Debug,
+        F: Fn(BeaconNodeHttpClient) -> R + Send + Sync + 'static,
+        R: Future<Output = Result<(), Err>> + Send + 'static,
+        Err: Debug + Send + 'static,
     {
         if self.broadcast_topics.contains(&topic) {
-            self.broadcast(require_synced, offline_on_failure, func)
+            self.broadcast_first_success(require_synced, offline_on_failure, topic, func)
                 .await
         } else {
-            self.first_success(require_synced, offline_on_failure, func)
-                .await?;
+            self.first_success(require_synced, offline_on_failure, |beacon_node| {
+                func(beacon_node.clone())
+            })
+            .await?;
             Ok(())
         }
     }
 }
 
+/// Publish using `func` on a single `candidate`, returning the id of the candidate and the result.
+///
+/// This function owns all of its arguments so that it may be spawned and outlive the caller.
+async fn publish_on_candidate<E, F, Err, R>(
+    candidate: Arc<CandidateBeaconNode<E>>,
+    func: Arc<F>,
+    offline_on_failure: OfflineOnFailure,
+    topic: ApiTopic,
+    log: Logger,
+) -> (String, Result<(), Error<Err>>)
+where
+    E: EthSpec,
+    F: Fn(BeaconNodeHttpClient) -> R,
+    R: Future<Output = Result<(), Err>>,
+    Err: Debug,
+{
+    let beacon_node_id = candidate.beacon_node.to_string();
+    let topic_name: &'static str = topic.into();
+    inc_counter_vec(&ENDPOINT_REQUESTS, &[candidate.beacon_node.as_ref()]);
+
+    let timer = start_timer_vec(
+        &VC_BEACON_NODE_PUBLISH_TIMES,
+        &[topic_name, candidate.beacon_node.as_ref()],
+    );
+    let result = func(candidate.beacon_node.clone()).await;
+    drop(timer);
+
+    match result {
+        Ok(()) => {
+            candidate.record_request(true).await;
+            (beacon_node_id, Ok(()))
+        }
+        Err(e) => {
+            candidate.record_request(false).await;
+            debug!(
+                log,
+                "Request to beacon node failed";
+                "node" => &beacon_node_id,
+                "topic" => topic_name,
+                "error" => ?e,
+            );
+            if matches!(offline_on_failure, OfflineOnFailure::Yes) {
+                candidate.set_offline().await;
+            }
+            inc_counter_vec(&ENDPOINT_ERRORS, &[candidate.beacon_node.as_ref()]);
+            (beacon_node_id, Err(Error::RequestFailed(e)))
+        }
+    }
+}
+
 /// Serves as a cue for `BeaconNodeFallback` to tell which requests need to be broadcasted.
-#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize, EnumString, EnumVariantNames)]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Deserialize,
+    Serialize,
+    EnumString,
+    EnumVariantNames,
+    IntoStaticStr,
+)]
 #[strum(serialize_all = "kebab-case")]
 pub enum ApiTopic {
     Attestations,
@@ -749,8 +1087,203 @@ impl ApiTopic {
 #[cfg(test)]
 mod test {
     use super::*;
+    use eth2::Timeouts;
+    use logging::test_logger;
+    use sensitive_url::SensitiveUrl;
+    use slot_clock::TestingSlotClock;
     use std::str::FromStr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use strum::VariantNames;
+    use task_executor::test_utils::TestRuntime;
+    use tokio::sync::Notify;
+    use types::MainnetEthSpec;
+
+    type E = MainnetEthSpec;
+
+    fn fallback(
+        num_candidates: usize,
+        executor: TaskExecutor,
+    ) -> BeaconNodeFallback<TestingSlotClock, E> {
+        let candidates = (0..num_candidates)
+            .map(|i| {
+                let url = SensitiveUrl::parse(&format!("http://127.0.0.1:{}", 5052 + i)).unwrap();
+                CandidateBeaconNode::new(BeaconNodeHttpClient::new(
+                    url,
+                    Timeouts::set_all(Duration::from_secs(1)),
+                ))
+            })
+            .collect();
+        BeaconNodeFallback::new(
+            candidates,
+            ApiTopic::all(),
+            E::default_spec(),
+            executor,
+            test_logger(),
+        )
+    }
+
+    async fn set_health(
+        candidate: &CandidateBeaconNode<E>,
+        status: Result<(), CandidateError>,
+        head_slot: u64,
+        latency_millis: u64,
+    ) {
+        *candidate.status.write().await = status;
+        *candidate.sync_health.write().await = Some(SyncHealth {
+            head_slot: Slot::new(head_slot),
+            sync_distance: Slot::new(0),
+            is_optimistic: false,
+            el_offline: false,
+        });
+        *candidate.latency.write().await = Some(Duration::from_millis(latency_millis));
+    }
+
+    async fn ranked_indices(fallback: &BeaconNodeFallback<TestingSlotClock, E>) -> Vec<usize> {
+        fallback
+            .ranking
+            .read()
+            .await
+            .iter()
+            .map(|rank| rank.index)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn ranking_prefers_healthy_candidates() {
+        let runtime = TestRuntime::default();
+        let fallback = fallback(4, runtime.task_executor.clone());
+        assert_eq!(ranked_indices(&fallback).await, vec![0, 1, 2, 3]);
+
+        // The primary is behind the other candidates.
+        set_health(&fallback.candidates[0], Ok(()), 60, 10).await;
+        // The fastest synced candidate.
+        set_health(&fallback.candidates[1], Ok(()), 64, 10).await;
+        // Synced, but slower.
+        set_health(&fallback.candidates[2], Ok(()), 64, 100).await;
+        // Fastest of all, but not synced.
+        set_health(
+            &fallback.candidates[3],
+            Err(CandidateError::NotSynced),
+            64,
+            0,
+        )
+        .await;
+
+        fallback.update_ranking().await;
+        assert_eq!(ranked_indices(&fallback).await, vec![1, 2, 0, 3]);
+
+        // Errors on the best candidate push it below the slower one.
+        for _ in 0..4 {
+            fallback.candidates[1].record_request(false).await;
+        }
+        fallback.update_ranking().await;
+        assert_eq!(ranked_indices(&fallback).await, vec![2, 0, 1, 3]);
+
+        let health = fallback.health().await;
+        assert_eq!(
+            health.iter().map(|health| health.index).collect::<Vec<_>>(),
+            vec![2, 0, 1, 3]
+        );
+        assert_eq!(health[2].error_rate, 1.0);
+        assert_eq!(health[3].status, "not_synced");
+    }
+
+    #[tokio::test]
+    async fn first_success_uses_ranking() {
+        let runtime = TestRuntime::default();
+        let fallback = fallback(2, runtime.task_executor.clone());
+        set_health(&fallback.candidates[0], Ok(()), 64, 500).await;
+        set_health(&fallback.candidates[1], Ok(()), 64, 10).await;
+        fallback.update_ranking().await;
+
+        let first = fallback
+            .first_success(
+                RequireSynced::Yes,
+                OfflineOnFailure::No,
+                |node| async move { Ok::<_, ()>(node.to_string()) },
+            )
+            .await
+            .unwrap();
+        assert_eq!(first, fallback.candidates[1].beacon_node.to_string());
+    }
+
+    #[tokio::test]
+    async fn broadcast_returns_on_first_success() {
+        let runtime = TestRuntime::default();
+        let fallback = fallback(3, runtime.task_executor.clone());
+        for candidate in &fallback.candidates {
+            set_health(candidate, Ok(()), 64, 10).await;
+        }
+        let failing_node = fallback.candidates[0].beacon_node.to_string();
+        let slow_node = fallback.candidates[1].beacon_node.to_string();
+
+        // The slow node only completes once the gate is opened, after the broadcast has returned.
+        let gate = Arc::new(Notify::new());
+        let slow_completions = Arc::new(AtomicUsize::new(0));
+        let (inner_gate, inner_completions) = (gate.clone(), slow_completions.clone());
+
+        fallback
+            .request(
+                RequireSynced::Yes,
+                OfflineOnFailure::Yes,
+                ApiTopic::Blocks,
+                move |beacon_node| {
+                    let node = beacon_node.to_string();
+                    let (failing_node, slow_node) = (failing_node.clone(), slow_node.clone());
+                    let (gate, completions) = (inner_gate.clone(), inner_completions.clone());
+                    async move {
+                        if node == failing_node {
+                            Err(())
+                        } else if node == slow_node {
+                            gate.notified().await;
+                            completions.fetch_add(1, Ordering::SeqCst);
+                            Ok(())
+                        } else {
+                            Ok(())
+                        }
+                    }
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(slow_completions.load(Ordering::SeqCst), 0);
+
+        // The request to the slow node continues in the background.
+        gate.notify_one();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while slow_completions.load(Ordering::SeqCst) == 0 {
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("slow node should complete");
+
+        // The failing node is marked offline.
+        assert!(matches!(
+            fallback.candidates[0].status(RequireSynced::No).await,
+            Err(CandidateError::Offline)
+        ));
+    }
+
+    #[tokio::test]
+    async fn broadcast_fails_if_all_candidates_fail() {
+        let runtime = TestRuntime::default();
+        let fallback = fallback(2, runtime.task_executor.clone());
+        for candidate in &fallback.candidates {
+            set_health(candidate, Ok(()), 64, 10).await;
+        }
+
+        let errors = fallback
+            .request(
+                RequireSynced::Yes,
+                OfflineOnFailure::No,
+                ApiTopic::Attestations,
+                |_| async { Err::<(), _>(()) },
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(errors.num_errors(), 2);
+    }
 
     #[test]
     fn api_topic_all() {
@@ -0,0 +1,199 @@
+//! Provides a score for each beacon node which is used by `BeaconNodeFallback` to decide the order
+//! in which candidates are tried.
+//!
+//! The score is a penalty: a perfectly healthy node scores zero and a *lower* score is better.
+//! Each component of the penalty is capped so that a single bad reading (e.g. a latency spike)
+//! cannot outweigh a more fundamental problem (e.g. an offline execution engine).
+
+use eth2::types::SyncingData;
+use std::time::Duration;
+use types::Slot;
+
+/// Added to the score of a node which reports that its execution engine is offline.
+pub const EL_OFFLINE_PENALTY: u64 = 10_000;
+/// Added to the score of a node which reports that its head is optimistic.
+pub const OPTIMISTIC_PENALTY: u64 = 5_000;
+/// Added to the score of a node for each slot it is behind the wall-clock slot.
+pub const SYNC_DISTANCE_PENALTY_PER_SLOT: u64 = 200;
+/// Added to the score of a node for each slot its head is behind the best head of all candidates.
+pub const HEAD_LAG_PENALTY_PER_SLOT: u64 = 100;
+/// The maximum number of slots which will be penalised by either of the per-slot penalties.
+pub const MAX_PENALISED_SLOTS: u64 = 32;
+/// Latency is penalised at one point per millisecond, up to this many milliseconds.
+pub const MAX_PENALISED_LATENCY_MILLIS: u64 = 2_000;
+/// The penalty applied to a node for which every recent request has failed. Nodes with a lower
+/// error rate are penalised proportionally.
+pub const ERROR_RATE_PENALTY: u64 = 5_000;
+
+/// The status reported by a beacon node on the `/eth/v1/node/syncing` endpoint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SyncHealth {
+    pub head_slot: Slot,
+    pub sync_distance: Slot,
+    pub is_optimistic: bool,
+    pub el_offline: bool,
+}
+
+impl From<&SyncingData> for SyncHealth {
+    fn from(data: &SyncingData) -> Self {
+        Self {
+            head_slot: data.head_slot,
+            sync_distance: data.sync_distance,
+            is_optimistic: data.is_optimistic,
+            el_offline: data.el_offline,
+        }
+    }
+}
+
+/// Counts the requests made to a beacon node and how many of them failed.
+///
+/// The counts are halved each time `Self::decay` is called (once per slot), so the error rate
+/// reflects recent behaviour and a node can recover from a burst of failures.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RequestStats {
+    requests: u64,
+    errors: u64,
+}
+
+impl RequestStats {
+    pub fn record(&mut self, success: bool) {
+        self.requests = self.requests.saturating_add(1);
+        if !success {
+            self.errors = self.errors.saturating_add(1);
+        }
+    }
+
+    pub fn decay(&mut self) {
+        self.requests /= 2;
+        self.errors /= 2;
+    }
+
+    /// Returns the fraction of recent requests which failed, or zero if there have been none.
+    pub fn error_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.requests as f64
+        }
+    }
+}
+
+/// Compute the score of a node, where a lower score is better.
+///
+/// - `sync_health` is the most recent response from the node, if any. A node which has never
+///   responded receives the maximum sync-distance and head-lag penalties.
+/// - `best_head` is the highest head slot reported by any candidate.
+/// - `latency` is the most recent measurement from the latency service, if any. Unmeasured
+///   latency is not penalised since failing requests are already penalised via `error_rate`.
+pub fn score(
+    sync_health: Option<&SyncHealth>,
+    best_head: Slot,
+    latency: Option<Duration>,
+    error_rate: f64,
+) -> u64 {
+    let sync_penalty = match sync_health {
+        Some(health) => {
+            let sync_distance = std::cmp::min(health.sync_distance.as_u64(), MAX_PENALISED_SLOTS);
+            let head_lag = std::cmp::min(
+                best_head.saturating_sub(health.head_slot).as_u64(),
+                MAX_PENALISED_SLOTS,
+            );
+
+            let mut penalty = sync_distance * SYNC_DISTANCE_PENALTY_PER_SLOT
+                + head_lag * HEAD_LAG_PENALTY_PER_SLOT;
+            if health.el_offline {
+                penalty += EL_OFFLINE_PENALTY;
+            }
+            if health.is_optimistic {
+                penalty += OPTIMISTIC_PENALTY;
+            }
+            penalty
+        }
+        None => MAX_PENALISED_SLOTS * (SYNC_DISTANCE_PENALTY_PER_SLOT + HEAD_LAG_PENALTY_PER_SLOT),
+    };
+
+    let latency_penalty = latency.map_or(0, |latency| {
+        std::cmp::min(latency.as_millis() as u64, MAX_PENALISED_LATENCY_MILLIS)
+    });
+
+    let error_penalty = (error_rate.clamp(0.0, 1.0) * ERROR_RATE_PENALTY as f64) as u64;
+
+    sync_penalty + latency_penalty + error_penalty
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health(head_slot: u64, sync_distance: u64) -> SyncHealth {
+        SyncHealth {
+            head_slot: Slot::new(head_slot),
+            sync_distance: Slot::new(sync_distance),
+            is_optimistic: false,
+            el_offline: false,
+        }
+    }
+
+    #[test]
+    fn healthy_node_scores_zero() {
+        assert_eq!(score(Some(&health(64, 0)), Slot::new(64), None, 0.0), 0);
+    }
+
+    #[test]
+    fn penalties_are_capped() {
+        let far_behind = health(0, 10_000);
+        assert_eq!(
+            score(
+                Some(&far_behind),
+                Slot::new(10_000),
+                Some(Duration::from_secs(60)),
+                2.0
+            ),
+            MAX_PENALISED_SLOTS * (SYNC_DISTANCE_PENALTY_PER_SLOT + HEAD_LAG_PENALTY_PER_SLOT)
+                + MAX_PENALISED_LATENCY_MILLIS
+                + ERROR_RATE_PENALTY
+        );
+    }
+
+    #[test]
+    fn el_offline_outweighs_latency() {
+        let mut el_offline = health(64, 0);
+        el_offline.el_offline = true;
+        let slow = health(64, 0);
+
+        let el_offline_score = score(Some(&el_offline), Slot::new(64), Some(Duration::ZERO), 0.0);
+        let slow_score = score(
+            Some(&slow),
+            Slot::new(64),
+            Some(Duration::from_secs(60)),
+            0.0,
+        );
+        assert!(el_offline_score > slow_score);
+    }
+
+    #[test]
+    fn head_lag_is_relative_to_best_head() {
+        let behind = health(62, 0);
+        assert_eq!(
+            score(Some(&behind), Slot::new(64), None, 0.0),
+            2 * HEAD_LAG_PENALTY_PER_SLOT
+        );
+    }
+
+    #[test]
+    fn request_stats_decay() {
+        let mut stats = RequestStats::default();
+        assert_eq!(stats.error_rate(), 0.0);
+
+        for success in [true, false, false, true] {
+            stats.record(success);
+        }
+        assert_eq!(stats.error_rate(), 0.5);
+
+        stats.decay();
+        assert_eq!(stats.error_rate(), 0.5);
+        stats.decay();
+        stats.decay();
+        assert_eq!(stats, RequestStats::default());
+    }
+}
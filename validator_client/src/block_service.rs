@@ -141,16 +141,16 @@ pub struct ProposerFallback<T, E: EthSpec> {
 
 impl<T: SlotClock, E: EthSpec> ProposerFallback<T, E> {
     // Try `func` on `self.proposer_nodes` first. If that doesn't work, try `self.beacon_nodes`.
-    pub async fn request_proposers_first<'a, F, Err, R>(
-        &'a self,
+    pub async fn request_proposers_first<F, Err, R>(
+        &self,
         require_synced: RequireSynced,
         offline_on_failure: OfflineOnFailure,
         func: F,
     ) -> Result<(), Errors<Err>>
     where
-        F: Fn(&'a BeaconNodeHttpClient) -> R + Clone,
-        R: Future<Output = Result<(), Err>>,
-        Err: Debug,
+        F: Fn(BeaconNodeHttpClient) -> R + Clone + Send + Sync + 'static,
+        R: Future<Output = Result<(), Err>> + Send + 'static,
+        Err: Debug + Send + 'static,
     {
         // If there are proposer nodes, try calling `func` on them and return early if they are successful.
         if let Some(proposer_nodes) = &self.proposer_nodes {
@@ -360,6 +360,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         graffiti: Option<Graffiti>,
         validator_pubkey: &PublicKeyBytes,
         unsigned_block: UnsignedBlock<E>,
+        beacon_node: &str,
     ) -> Result<(), BlockError> {
         let log = self.context.log();
         let signing_timer = metrics::start_timer(&metrics::BLOCK_SIGNING_TIMES);
@@ -368,13 +369,13 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
             UnsignedBlock::Full(block_contents) => {
                 let (block, maybe_blobs) = block_contents.deconstruct();
                 self.validator_store
-                    .sign_block(*validator_pubkey, block, slot)
+                    .sign_block(*validator_pubkey, block, slot, Some(beacon_node))
                     .await
                     .map(|b| SignedBlock::Full(PublishBlockRequest::new(Arc::new(b), maybe_blobs)))
             }
             UnsignedBlock::Blinded(block) => self
                 .validator_store
-                .sign_block(*validator_pubkey, block, slot)
+                .sign_block(*validator_pubkey, block, slot, Some(beacon_node))
                 .await
                 .map(Arc::new)
                 .map(SignedBlock::Blinded),
@@ -417,15 +418,24 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         // Try the proposer nodes first, since we've likely gone to efforts to
         // protect them from DoS attacks and they're most likely to successfully
         // publish a block.
+        //
+        // The block is shared with the request to each beacon node, which may outlive this
+        // function if blocks are broadcast.
+        let signed_block = Arc::new(signed_block);
         proposer_fallback
-            .request_proposers_first(
-                RequireSynced::No,
-                OfflineOnFailure::Yes,
-                |beacon_node| async {
-                    self.publish_signed_block_contents(&signed_block, beacon_node)
-                        .await
-                },
-            )
+            .request_proposers_first(RequireSynced::No, OfflineOnFailure::Yes, {
+                let block_service = self.clone();
+                let signed_block = signed_block.clone();
+                move |beacon_node| {
+                    let block_service = block_service.clone();
+                    let signed_block = signed_block.clone();
+                    async move {
+                        block_service
+                            .publish_signed_block_contents(&signed_block, &beacon_node)
+                            .await
+                    }
+                }
+            })
             .await?;
 
         info!(
@@ -483,6 +493,9 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
             self.validator_store.graffiti(&validator_pubkey),
             self.graffiti,
         );
+        let builder_min_bid_gwei = self
+            .validator_store
+            .get_builder_min_bid_gwei(&validator_pubkey);
 
         let randao_reveal_ref = &randao_reveal;
         let self_ref = &self;
@@ -502,7 +515,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         //
         // Try the proposer nodes last, since it's likely that they don't have a
         // great view of attestations on the network.
-        let unsigned_block = proposer_fallback
+        let (unsigned_block, beacon_node) = proposer_fallback
             .request_proposers_last(
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
@@ -518,9 +531,11 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                         graffiti,
                         proposer_index,
                         builder_boost_factor,
+                        builder_min_bid_gwei,
                         log,
                     )
                     .await
+                    .map(|block| (block, beacon_node.to_string()))
                     .map_err(|e| {
                         BlockError::Recoverable(format!(
                             "Error from beacon node when producing block: {:?}",
@@ -538,6 +553,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                 graffiti,
                 &validator_pubkey,
                 unsigned_block,
+                &beacon_node,
             )
             .await?;
 
@@ -576,6 +592,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         Ok::<_, BlockError>(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn get_validator_block(
         beacon_node: &BeaconNodeHttpClient,
         slot: Slot,
@@ -583,6 +600,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         graffiti: Option<Graffiti>,
         proposer_index: Option<u64>,
         builder_boost_factor: Option<u64>,
+        builder_min_bid_gwei: Option<u64>,
         log: &Logger,
     ) -> Result<UnsignedBlock<E>, BlockError> {
         let (block_response, _) = beacon_node
@@ -591,6 +609,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                 randao_reveal_ref,
                 graffiti.as_ref(),
                 builder_boost_factor,
+                builder_min_bid_gwei,
             )
             .await
             .map_err(|e| {
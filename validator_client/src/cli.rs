@@ -139,6 +139,17 @@ pub fn cli_app() -> Command {
                         delimiter, and must not be part of the certificate path.")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("beacon-nodes-api-token-file")
+                .long("beacon-nodes-api-token-file")
+                .value_name("FILE")
+                .action(ArgAction::Set)
+                .help("Path to a file containing a bearer token which is sent in the \
+                        Authorization header of every request to a beacon node (and/or proposer \
+                        node). Required when the beacon node restricts its HTTP API with \
+                        --http-api-tokens-file.")
+                .display_order(0)
+        )
         // This overwrites the graffiti configured in the beacon node.
         .arg(
             Arg::new("graffiti")
@@ -457,6 +468,36 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("signing-audit-log")
+                .long("signing-audit-log")
+                .help("If present, record every signing request in an append-only, hash-chained \
+                       log within the validators directory. The log can be checked for tampering \
+                       with `lighthouse account validator signing-audit-log verify`.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("signing-audit-log-max-file-size")
+                .long("signing-audit-log-max-file-size")
+                .value_name("MEGABYTES")
+                .help("The size after which the signing audit log starts a new file.")
+                .default_value("64")
+                .requires("signing-audit-log")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("signing-audit-log-max-files")
+                .long("signing-audit-log-max-files")
+                .value_name("COUNT")
+                .help("The maximum number of signing audit log files to keep, deleting the \
+                       oldest. All files are kept if this flag is not provided.")
+                .requires("signing-audit-log")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /*
          * Experimental/development options.
          */
@@ -71,6 +71,8 @@ pub struct Config {
     /// A list of custom certificates that the validator client will additionally use when
     /// connecting to a beacon node over SSL/TLS.
     pub beacon_nodes_tls_certs: Option<Vec<PathBuf>>,
+    /// A file containing the bearer token sent to beacon nodes which require authentication.
+    pub beacon_nodes_api_token_file: Option<PathBuf>,
     /// Enables broadcasting of various requests (by topic) to all beacon nodes.
     pub broadcast_topics: Vec<ApiTopic>,
     /// Enables a service which attempts to measure latency between the VC and BNs.
@@ -79,6 +81,8 @@ pub struct Config {
     pub validator_registration_batch_size: usize,
     /// Enable slashing protection even while using web3signer keys.
     pub enable_web3signer_slashing_protection: bool,
+    /// Configuration for the signing audit log, which is disabled if `None`.
+    pub signing_audit_log: Option<signing_audit_log::Config>,
     /// Specifies the boost factor, a percentage multiplier to apply to the builder's payload value.
     pub builder_boost_factor: Option<u64>,
     /// If true, Lighthouse will prefer builder proposals, if available.
@@ -121,6 +125,7 @@ impl Default for Config {
             enable_doppelganger_protection: false,
             enable_high_validator_count_metrics: false,
             beacon_nodes_tls_certs: None,
+            beacon_nodes_api_token_file: None,
             builder_proposals: false,
             builder_registration_timestamp_override: None,
             gas_limit: None,
@@ -128,6 +133,7 @@ impl Default for Config {
             enable_latency_measurement_service: true,
             validator_registration_batch_size: 500,
             enable_web3signer_slashing_protection: true,
+            signing_audit_log: None,
             builder_boost_factor: None,
             prefer_builder_proposals: false,
             distributed: false,
@@ -234,6 +240,9 @@ impl Config {
             config.beacon_nodes_tls_certs = Some(tls_certs.split(',').map(PathBuf::from).collect());
         }
 
+        config.beacon_nodes_api_token_file =
+            parse_optional(cli_args, "beacon-nodes-api-token-file")?;
+
         if cli_args.get_flag("distributed") {
             config.distributed = true;
         }
@@ -443,6 +452,18 @@ impl Config {
                 true
             };
 
+        if cli_args.get_flag("signing-audit-log") {
+            let max_file_size_mb: u64 =
+                parse_required(cli_args, "signing-audit-log-max-file-size")?;
+            if max_file_size_mb == 0 {
+                return Err("signing-audit-log-max-file-size cannot be 0".to_string());
+            }
+            config.signing_audit_log = Some(signing_audit_log::Config {
+                max_file_size: max_file_size_mb * 1024 * 1024,
+                max_files: parse_optional(cli_args, "signing-audit-log-max-files")?,
+            });
+        }
+
         Ok(config)
     }
 }
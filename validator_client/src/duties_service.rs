@@ -741,21 +741,25 @@ async fn poll_beacon_attesters<T: SlotClock + 'static, E: EthSpec>(
 
     // If there are any subscriptions, push them out to beacon nodes
     if !subscriptions.is_empty() {
-        let subscriptions_ref = &subscriptions;
+        let subscriptions = Arc::new(subscriptions);
+        let subscriptions_ref = subscriptions.clone();
         let subscription_result = duties_service
             .beacon_nodes
             .request(
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
                 ApiTopic::Subscriptions,
-                |beacon_node| async move {
-                    let _timer = metrics::start_timer_vec(
-                        &metrics::DUTIES_SERVICE_TIMES,
-                        &[metrics::SUBSCRIPTIONS_HTTP_POST],
-                    );
-                    beacon_node
-                        .post_validator_beacon_committee_subscriptions(subscriptions_ref)
-                        .await
+                move |beacon_node| {
+                    let subscriptions = subscriptions_ref.clone();
+                    async move {
+                        let _timer = metrics::start_timer_vec(
+                            &metrics::DUTIES_SERVICE_TIMES,
+                            &[metrics::SUBSCRIPTIONS_HTTP_POST],
+                        );
+                        beacon_node
+                            .post_validator_beacon_committee_subscriptions(&subscriptions)
+                            .await
+                    }
                 },
             )
             .await;
@@ -10,7 +10,9 @@ pub mod test_utils;
 
 use crate::http_api::graffiti::{delete_graffiti, get_graffiti, set_graffiti};
 
+use crate::beacon_node_fallback::BeaconNodeFallback;
 use crate::http_api::create_signed_voluntary_exit::create_signed_voluntary_exit;
+use crate::validator_store::Error as ValidatorStoreError;
 use crate::{determine_graffiti, GraffitiFile, ValidatorStore};
 use account_utils::{
     mnemonic_from_phrase,
@@ -73,6 +75,8 @@ pub struct Context<T: SlotClock, E: EthSpec> {
     pub task_executor: TaskExecutor,
     pub api_secret: ApiSecret,
     pub validator_store: Option<Arc<ValidatorStore<T, E>>>,
+    pub beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
+    pub proposer_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     pub validator_dir: Option<PathBuf>,
     pub secrets_dir: Option<PathBuf>,
     pub graffiti_file: Option<GraffitiFile>,
@@ -180,6 +184,23 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    let inner_beacon_nodes = ctx.beacon_nodes.clone();
+    let inner_proposer_nodes = ctx.proposer_nodes.clone();
+    let beacon_nodes_filter = warp::any()
+        .map(move || (inner_beacon_nodes.clone(), inner_proposer_nodes.clone()))
+        .and_then(
+            |(beacon_nodes, proposer_nodes): (Option<_>, Option<_>)| async move {
+                beacon_nodes
+                    .map(|beacon_nodes| (beacon_nodes, proposer_nodes))
+                    .ok_or_else(|| {
+                        warp_utils::reject::custom_not_found(
+                            "beacon nodes are not initialized.".to_string(),
+                        )
+                    })
+            },
+        )
+        .untuple_one();
+
     let inner_task_executor = ctx.task_executor.clone();
     let task_executor_filter = warp::any().map(move || inner_task_executor.clone());
 
@@ -347,6 +368,69 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // GET lighthouse/signing_audit_log
+    let get_lighthouse_signing_audit_log = warp::path("lighthouse")
+        .and(warp::path("signing_audit_log"))
+        .and(warp::path::end())
+        .and(warp::query::<api_types::SigningAuditLogQuery>())
+        .and(validator_store_filter.clone())
+        .then(
+            |query: api_types::SigningAuditLogQuery, validator_store: Arc<ValidatorStore<T, E>>| {
+                blocking_json_task(move || {
+                    let signing_audit_log =
+                        validator_store.signing_audit_log().ok_or_else(|| {
+                            warp_utils::reject::custom_not_found(
+                                "signing audit log is disabled".to_string(),
+                            )
+                        })?;
+                    let head = signing_audit_log.head();
+                    let records = signing_audit_log.query(&query).map_err(|e| {
+                        warp_utils::reject::custom_server_error(format!(
+                            "unable to read signing audit log: {:?}",
+                            e
+                        ))
+                    })?;
+
+                    Ok(api_types::GenericResponse::from(
+                        api_types::SigningAuditLogResponse { head, records },
+                    ))
+                })
+            },
+        );
+
+    // GET lighthouse/beacon/health
+    let get_lighthouse_beacon_health = warp::path("lighthouse")
+        .and(warp::path("beacon"))
+        .and(warp::path("health"))
+        .and(warp::path::end())
+        .and(beacon_nodes_filter)
+        .and(task_executor_filter.clone())
+        .then(
+            |beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+             proposer_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
+             task_executor: TaskExecutor| {
+                blocking_json_task(move || {
+                    if let Some(handle) = task_executor.handle() {
+                        let beacon_nodes = handle.block_on(beacon_nodes.health());
+                        let proposer_nodes = match proposer_nodes {
+                            Some(proposer_nodes) => handle.block_on(proposer_nodes.health()),
+                            None => vec![],
+                        };
+                        Ok(api_types::GenericResponse::from(
+                            api_types::BeaconNodeHealthResponse {
+                                beacon_nodes,
+                                proposer_nodes,
+                            },
+                        ))
+                    } else {
+                        Err(warp_utils::reject::custom_server_error(
+                            "Lighthouse shutting down".into(),
+                        ))
+                    }
+                })
+            },
+        );
+
     // GET lighthouse/ui/health
     let get_lighthouse_ui_health = warp::path("lighthouse")
         .and(warp::path("ui"))
@@ -604,6 +688,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                 gas_limit: web3signer.gas_limit,
                                 builder_proposals: web3signer.builder_proposals,
                                 builder_boost_factor: web3signer.builder_boost_factor,
+                                builder_min_bid_gwei: None,
                                 prefer_builder_proposals: web3signer.prefer_builder_proposals,
                                 description: web3signer.description,
                                 signing_definition: SigningDefinition::Web3Signer(
@@ -632,6 +717,50 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // POST lighthouse/distributed/sign
+    let post_distributed_sign = warp::path("lighthouse")
+        .and(warp::path("distributed"))
+        .and(warp::path("sign"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .and(task_executor_filter.clone())
+        .then(
+            |body: api_types::PartialSignatureRequest<E>,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             task_executor: TaskExecutor| {
+                blocking_json_task(move || {
+                    if let Some(handle) = task_executor.handle() {
+                        let response = handle
+                            .block_on(validator_store.sign_partial(body))
+                            .map_err(|e| match e {
+                                ValidatorStoreError::UnknownPubkey(pubkey) => {
+                                    warp_utils::reject::custom_not_found(format!(
+                                        "no validator for {:?}",
+                                        pubkey
+                                    ))
+                                }
+                                ValidatorStoreError::UnableToSign(e) => {
+                                    warp_utils::reject::custom_server_error(format!(
+                                        "unable to sign: {:?}",
+                                        e
+                                    ))
+                                }
+                                e => warp_utils::reject::custom_bad_request(format!(
+                                    "refusing to sign: {:?}",
+                                    e
+                                )),
+                            })?;
+                        Ok(api_types::GenericResponse::from(response))
+                    } else {
+                        Err(warp_utils::reject::custom_server_error(
+                            "Lighthouse shutting down".into(),
+                        ))
+                    }
+                })
+            },
+        );
+
     // PATCH lighthouse/validators/{validator_pubkey}
     let patch_validators = warp::path("lighthouse")
         .and(warp::path("validators"))
@@ -687,6 +816,10 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                     initialized_validator.get_builder_boost_factor(),
                                     body.builder_boost_factor,
                                 )
+                                && equal_or_none(
+                                    initialized_validator.get_builder_min_bid_gwei(),
+                                    body.builder_min_bid_gwei,
+                                )
                                 && equal_or_none(
                                     initialized_validator.get_builder_proposals(),
                                     body.builder_proposals,
@@ -708,6 +841,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                             if body.enabled.map_or(true, |enabled| !enabled)
                                 && body.gas_limit.is_none()
                                 && body.builder_boost_factor.is_none()
+                                && body.builder_min_bid_gwei.is_none()
                                 && body.builder_proposals.is_none()
                                 && body.prefer_builder_proposals.is_none()
                                 && maybe_graffiti.is_none() =>
@@ -731,6 +865,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                                 body.gas_limit,
                                                 body.builder_proposals,
                                                 body.builder_boost_factor,
+                                                body.builder_min_bid_gwei,
                                                 body.prefer_builder_proposals,
                                                 body.graffiti,
                                             ),
@@ -1251,6 +1386,8 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(get_lighthouse_spec)
                         .or(get_lighthouse_validators)
                         .or(get_lighthouse_validators_pubkey)
+                        .or(get_lighthouse_signing_audit_log)
+                        .or(get_lighthouse_beacon_health)
                         .or(get_lighthouse_ui_health)
                         .or(get_lighthouse_ui_graffiti)
                         .or(get_fee_recipient)
@@ -1265,6 +1402,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(post_validators_keystore)
                         .or(post_validators_mnemonic)
                         .or(post_validators_web3signer)
+                        .or(post_distributed_sign)
                         .or(post_validators_voluntary_exits)
                         .or(post_fee_recipient)
                         .or(post_gas_limit)
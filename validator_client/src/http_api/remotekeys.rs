@@ -1,7 +1,7 @@
 //! Implementation of the standard remotekey management API.
 use crate::{initialized_validators::Error, InitializedValidators, ValidatorStore};
 use account_utils::validator_definitions::{
-    SigningDefinition, ValidatorDefinition, Web3SignerDefinition,
+    RemoteSignerDefinition, SigningDefinition, ValidatorDefinition, Web3SignerDefinition,
 };
 use eth2::lighthouse_vc::std_types::{
     DeleteRemotekeyStatus, DeleteRemotekeysRequest, DeleteRemotekeysResponse,
@@ -33,7 +33,8 @@ pub fn list<T: SlotClock + 'static, E: EthSpec>(
 
             match &def.signing_definition {
                 SigningDefinition::LocalKeystore { .. } => None,
-                SigningDefinition::Web3Signer(Web3SignerDefinition { url, .. }) => {
+                SigningDefinition::Web3Signer(Web3SignerDefinition { url, .. })
+                | SigningDefinition::RemoteSigner(RemoteSignerDefinition { url, .. }) => {
                     Some(SingleListRemotekeysResponse {
                         pubkey: validating_pubkey,
                         url: url.clone(),
@@ -126,6 +127,7 @@ fn import_single_remotekey<T: SlotClock + 'static, E: EthSpec>(
         gas_limit: None,
         builder_proposals: None,
         builder_boost_factor: None,
+        builder_min_bid_gwei: None,
         prefer_builder_proposals: None,
         description: String::from("Added by remotekey API"),
         signing_definition: SigningDefinition::Web3Signer(Web3SignerDefinition {
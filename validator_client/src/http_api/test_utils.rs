@@ -109,6 +109,7 @@ impl ApiTester {
         let validator_store = Arc::new(ValidatorStore::<_, E>::new(
             initialized_validators,
             slashing_protection,
+            None,
             Hash256::repeat_byte(42),
             spec,
             Some(Arc::new(DoppelgangerService::new(log.clone()))),
@@ -130,6 +131,8 @@ impl ApiTester {
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
+            beacon_nodes: None,
+            proposer_nodes: None,
             graffiti_file: None,
             graffiti_flag: Some(Graffiti::default()),
             spec: E::default_spec(),
@@ -551,6 +554,7 @@ impl ApiTester {
                 None,
                 None,
                 None,
+                None,
             )
             .await
             .unwrap();
@@ -601,6 +605,7 @@ impl ApiTester {
                 None,
                 None,
                 None,
+                None,
             )
             .await
             .unwrap();
@@ -631,6 +636,7 @@ impl ApiTester {
                 None,
                 None,
                 None,
+                None,
             )
             .await
             .unwrap();
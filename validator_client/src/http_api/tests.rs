@@ -97,6 +97,7 @@ impl ApiTester {
         let validator_store = Arc::new(ValidatorStore::<_, E>::new(
             initialized_validators,
             slashing_protection,
+            None,
             Hash256::repeat_byte(42),
             spec.clone(),
             Some(Arc::new(DoppelgangerService::new(log.clone()))),
@@ -118,6 +119,8 @@ impl ApiTester {
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
+            beacon_nodes: None,
+            proposer_nodes: None,
             graffiti_file: None,
             graffiti_flag: Some(Graffiti::default()),
             spec: E::default_spec(),
@@ -539,6 +542,7 @@ impl ApiTester {
                 None,
                 None,
                 None,
+                None,
             )
             .await
             .unwrap();
@@ -589,6 +593,7 @@ impl ApiTester {
                 None,
                 None,
                 None,
+                None,
             )
             .await
             .unwrap();
@@ -619,6 +624,7 @@ impl ApiTester {
                 None,
                 None,
                 None,
+                None,
             )
             .await
             .unwrap();
@@ -638,6 +644,27 @@ impl ApiTester {
                 Some(builder_boost_factor),
                 None,
                 None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        self
+    }
+
+    pub async fn set_builder_min_bid_gwei(self, index: usize, builder_min_bid_gwei: u64) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+
+        self.client
+            .patch_lighthouse_validators(
+                &validator.voting_pubkey,
+                None,
+                None,
+                None,
+                None,
+                Some(builder_min_bid_gwei),
+                None,
+                None,
             )
             .await
             .unwrap();
@@ -659,6 +686,7 @@ impl ApiTester {
                 None,
                 None,
                 None,
+                None,
                 Some(prefer_builder_proposals),
                 None,
             )
@@ -696,6 +724,22 @@ impl ApiTester {
         self
     }
 
+    pub async fn assert_builder_min_bid_gwei(
+        self,
+        index: usize,
+        builder_min_bid_gwei: Option<u64>,
+    ) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+
+        assert_eq!(
+            self.validator_store
+                .get_builder_min_bid_gwei(&validator.voting_pubkey),
+            builder_min_bid_gwei
+        );
+
+        self
+    }
+
     pub async fn assert_validator_derived_builder_boost_factor(
         self,
         index: usize,
@@ -749,6 +793,7 @@ impl ApiTester {
                 None,
                 None,
                 None,
+                None,
                 Some(graffiti_str),
             )
             .await
@@ -858,6 +903,12 @@ async fn routes_with_invalid_auth() {
                 .await
         })
         .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .get_lighthouse_signing_audit_log(&<_>::default())
+                .await
+        })
+        .await
         .test_with_invalid_auth(|client| async move {
             client
                 .post_lighthouse_validators(vec![ValidatorRequest {
@@ -916,6 +967,7 @@ async fn routes_with_invalid_auth() {
                     None,
                     None,
                     None,
+                    None,
                 )
                 .await
         })
@@ -1142,6 +1194,29 @@ async fn validator_builder_boost_factor() {
         .await;
 }
 
+#[tokio::test]
+async fn validator_builder_min_bid_gwei() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .assert_enabled_validators_count(2)
+        .assert_validators_count(2)
+        .assert_builder_min_bid_gwei(0, None)
+        .await
+        .set_builder_min_bid_gwei(0, 50_000_000)
+        .await
+        .assert_builder_min_bid_gwei(0, Some(50_000_000))
+        .await
+        .assert_builder_min_bid_gwei(1, None)
+        .await;
+}
+
 /// Verifies the builder boost factors translated from the `builder_proposals`,
 /// `prefer_builder_proposals` and `builder_boost_factor` values.
 #[tokio::test]
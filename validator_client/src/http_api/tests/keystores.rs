@@ -470,7 +470,16 @@ async fn import_and_delete_conflicting_web3_signer_keystores() {
         for pubkey in &pubkeys {
             tester
                 .client
-                .patch_lighthouse_validators(pubkey, Some(false), None, None, None, None, None)
+                .patch_lighthouse_validators(
+                    pubkey,
+                    Some(false),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
                 .await
                 .unwrap();
         }
@@ -1097,7 +1106,7 @@ async fn generic_migration_test(
             let current_epoch = attestation.data().target.epoch;
             tester1
                 .validator_store
-                .sign_attestation(public_key, 0, &mut attestation, current_epoch)
+                .sign_attestation(public_key, 0, &mut attestation, current_epoch, None)
                 .await
                 .unwrap();
         }
@@ -1173,7 +1182,7 @@ async fn generic_migration_test(
             let current_epoch = attestation.data().target.epoch;
             match tester2
                 .validator_store
-                .sign_attestation(public_key, 0, &mut attestation, current_epoch)
+                .sign_attestation(public_key, 0, &mut attestation, current_epoch, None)
                 .await
             {
                 Ok(()) => assert!(should_succeed),
@@ -1306,7 +1315,7 @@ async fn delete_concurrent_with_signing() {
                 let mut att = make_attestation(j, j + 1);
                 for (_validator_id, public_key) in thread_pubkeys.iter().enumerate() {
                     let _ = validator_store
-                        .sign_attestation(*public_key, 0, &mut att, Epoch::new(j + 1))
+                        .sign_attestation(*public_key, 0, &mut att, Epoch::new(j + 1), None)
                         .await;
                 }
             }
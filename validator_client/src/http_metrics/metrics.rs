@@ -36,6 +36,9 @@ pub const ATTESTATION_SELECTION_PROOFS: &str = "attestation_selection_proofs";
 pub const SUBSCRIPTIONS: &str = "subscriptions";
 pub const LOCAL_KEYSTORE: &str = "local_keystore";
 pub const WEB3SIGNER: &str = "web3signer";
+pub const REMOTE_SIGNER: &str = "remote_signer";
+pub const DISTRIBUTED_KEYSTORE: &str = "distributed_keystore";
+pub const DISTRIBUTED_KEYSTORE_PARTIAL: &str = "distributed_keystore_partial";
 
 pub use lighthouse_metrics::*;
 
@@ -178,6 +181,10 @@ lazy_static::lazy_static! {
         "Duration to obtain a signature",
         &["type"]
     );
+    pub static ref SIGNING_AUDIT_LOG_ERRORS_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "vc_signing_audit_log_errors_total",
+        "Total count of signing requests which could not be written to the signing audit log",
+    );
     pub static ref BLOCK_SIGNING_TIMES: Result<Histogram> = try_create_histogram(
         "vc_block_signing_times_seconds",
         "Duration to obtain a signature for a block",
@@ -200,6 +207,24 @@ lazy_static::lazy_static! {
         "vc_beacon_node_latency_primary_endpoint",
         "Round-trip latency for the primary BN endpoint",
     );
+    pub static ref VC_BEACON_NODE_PUBLISH_TIMES: Result<HistogramVec> = try_create_histogram_vec(
+        "vc_beacon_node_publish_times",
+        "Time taken by each BN to accept a message broadcast to all BNs",
+        &["topic", "endpoint"]
+    );
+    /*
+     * BN health
+     */
+    pub static ref VC_BEACON_NODE_HEALTH_SCORE: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "vc_beacon_node_health_score",
+        "Health score of each BN, lower is better",
+        &["endpoint"]
+    );
+    pub static ref VC_BEACON_NODE_RANK: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "vc_beacon_node_rank",
+        "Position of each BN in the fallback order, starting at 0",
+        &["endpoint"]
+    );
 }
 
 pub fn gather_prometheus_metrics<E: EthSpec>(
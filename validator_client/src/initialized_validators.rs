@@ -6,20 +6,24 @@
 //! The `InitializedValidators` struct in this file serves as the source-of-truth of which
 //! validators are managed by this validator client.
 
-use crate::signing_method::SigningMethod;
+use crate::signing_method::{
+    DistributedPeer, HttpDistributedPeer, HttpRemoteSigner, RemoteSigner, SigningMethod,
+};
 use account_utils::{
     read_password, read_password_from_user, read_password_string,
     validator_definitions::{
-        self, SigningDefinition, ValidatorDefinition, ValidatorDefinitions, Web3SignerDefinition,
-        CONFIG_FILENAME,
+        self, DistributedDefinition, RemoteSignerDefinition, SigningDefinition,
+        ValidatorDefinition, ValidatorDefinitions, Web3SignerDefinition, CONFIG_FILENAME,
     },
     ZeroizeString,
 };
+use eth2::lighthouse_vc::http_client::ValidatorClientHttpClient;
 use eth2_keystore::Keystore;
 use lighthouse_metrics::set_gauge;
 use lockfile::{Lockfile, LockfileError};
 use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
 use reqwest::{Certificate, Client, Error as ReqwestError, Identity};
+use sensitive_url::SensitiveUrl;
 use slog::{debug, error, info, warn, Logger};
 use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
@@ -110,6 +114,15 @@ pub enum Error {
     InvalidWeb3SignerClientIdentityCertificateFile(io::Error),
     InvalidWeb3SignerClientIdentityCertificate(ReqwestError),
     UnableToBuildWeb3SignerClient(ReqwestError),
+    /// The URL for a remote signer other than Web3Signer cannot be parsed.
+    InvalidRemoteSignerUrl(String),
+    /// The URL for a peer of a distributed validator cannot be parsed.
+    InvalidDistributedPeerUrl(String),
+    UnableToReadDistributedPeerApiToken(String),
+    /// The shares of a distributed validator do not combine into its voting public key.
+    InvalidDistributedShares(String),
+    /// The keystore does not contain a valid public key.
+    InvalidKeystorePublicKey(PathBuf),
     /// Unable to apply an action to a validator.
     InvalidActionOnValidator,
     UnableToReadValidatorPassword(String),
@@ -133,6 +146,7 @@ pub struct InitializedValidator {
     gas_limit: Option<u64>,
     builder_proposals: Option<bool>,
     builder_boost_factor: Option<u64>,
+    builder_min_bid_gwei: Option<u64>,
     prefer_builder_proposals: Option<bool>,
     /// The validators index in `state.validators`, to be updated by an external service.
     index: Option<u64>,
@@ -145,12 +159,16 @@ impl InitializedValidator {
             SigningMethod::LocalKeystore {
                 ref voting_keystore_lockfile,
                 ..
+            }
+            | SigningMethod::DistributedKeystore {
+                ref voting_keystore_lockfile,
+                ..
             } => MutexGuard::try_map(voting_keystore_lockfile.lock(), |option_lockfile| {
                 option_lockfile.as_mut()
             })
             .ok(),
-            // Web3Signer validators do not have any lockfiles.
-            SigningMethod::Web3Signer { .. } => None,
+            // Remote signer validators do not have any lockfiles.
+            SigningMethod::Web3Signer { .. } | SigningMethod::RemoteSigner { .. } => None,
         }
     }
 
@@ -166,6 +184,10 @@ impl InitializedValidator {
         self.builder_boost_factor
     }
 
+    pub fn get_builder_min_bid_gwei(&self) -> Option<u64> {
+        self.builder_min_bid_gwei
+    }
+
     pub fn get_prefer_builder_proposals(&self) -> Option<bool> {
         self.prefer_builder_proposals
     }
@@ -209,6 +231,7 @@ impl InitializedValidator {
         key_cache: &mut KeyCache,
         key_stores: &mut HashMap<PathBuf, Keystore>,
         web3_signer_client_map: &mut Option<HashMap<Web3SignerDefinition, Client>>,
+        remote_signer_map: &mut HashMap<RemoteSignerDefinition, Arc<dyn RemoteSigner>>,
         config: &Config,
     ) -> Result<Self, Error> {
         if !def.enabled {
@@ -222,6 +245,7 @@ impl InitializedValidator {
                 voting_keystore_path,
                 voting_keystore_password_path,
                 voting_keystore_password,
+                distributed,
             } => {
                 use std::collections::hash_map::Entry::*;
                 let voting_keystore = match key_stores.entry(voting_keystore_path.clone()) {
@@ -274,12 +298,22 @@ impl InitializedValidator {
                     keypair
                 };
 
-                if voting_keypair.pk != def.voting_public_key {
+                // The keystore of a distributed validator holds a share of the voting keypair.
+                let peers = if let Some(distributed) = &distributed {
+                    Some(build_distributed_peers(
+                        distributed,
+                        &voting_keypair.pk,
+                        &def.voting_public_key,
+                        config,
+                    )?)
+                } else if voting_keypair.pk != def.voting_public_key {
                     return Err(Error::VotingPublicKeyMismatch {
                         definition: Box::new(def.voting_public_key),
                         keystore: Box::new(voting_keypair.pk),
                     });
-                }
+                } else {
+                    None
+                };
 
                 // Append a `.lock` suffix to the voting keystore.
                 let lockfile_path = get_lockfile_path(&voting_keystore_path)
@@ -287,11 +321,23 @@ impl InitializedValidator {
 
                 let voting_keystore_lockfile = Mutex::new(Some(Lockfile::new(lockfile_path)?));
 
-                SigningMethod::LocalKeystore {
-                    voting_keystore_path,
-                    voting_keystore_lockfile,
-                    voting_keystore: voting_keystore.clone(),
-                    voting_keypair: Arc::new(voting_keypair),
+                match (distributed, peers) {
+                    (Some(distributed), Some(peers)) => SigningMethod::DistributedKeystore {
+                        voting_keystore_path,
+                        voting_keystore_lockfile,
+                        voting_keystore: voting_keystore.clone(),
+                        share_keypair: Arc::new(voting_keypair),
+                        share_index: distributed.share_index,
+                        threshold: distributed.threshold,
+                        peers,
+                        voting_public_key: def.voting_public_key,
+                    },
+                    _ => SigningMethod::LocalKeystore {
+                        voting_keystore_path,
+                        voting_keystore_lockfile,
+                        voting_keystore: voting_keystore.clone(),
+                        voting_keypair: Arc::new(voting_keypair),
+                    },
                 }
             }
             SigningDefinition::Web3Signer(web3_signer) => {
@@ -343,6 +389,37 @@ impl InitializedValidator {
                     voting_public_key: def.voting_public_key,
                 }
             }
+            SigningDefinition::RemoteSigner(remote_signer) => {
+                // Share a single signer between all the validators using the same remote signer.
+                let signer = match remote_signer_map.get(&remote_signer) {
+                    Some(signer) => signer.clone(),
+                    None => {
+                        let signing_url = Url::parse(&remote_signer.url)
+                            .map_err(|e| Error::InvalidRemoteSignerUrl(e.to_string()))?;
+                        let request_timeout = remote_signer
+                            .request_timeout_ms
+                            .map(Duration::from_millis)
+                            .unwrap_or(DEFAULT_REMOTE_SIGNER_REQUEST_TIMEOUT);
+                        let http_client = build_web3_signer_client(
+                            remote_signer.root_certificate_path.clone(),
+                            remote_signer.client_identity_path.clone(),
+                            remote_signer.client_identity_password.clone(),
+                            request_timeout,
+                            config.web3_signer_keep_alive_timeout,
+                            config.web3_signer_max_idle_connections,
+                        )?;
+                        let signer: Arc<dyn RemoteSigner> =
+                            Arc::new(HttpRemoteSigner::new(signing_url, http_client));
+                        remote_signer_map.insert(remote_signer, signer.clone());
+                        signer
+                    }
+                };
+
+                SigningMethod::RemoteSigner {
+                    signer,
+                    voting_public_key: def.voting_public_key,
+                }
+            }
         };
 
         Ok(Self {
@@ -352,6 +429,7 @@ impl InitializedValidator {
             gas_limit: def.gas_limit,
             builder_proposals: def.builder_proposals,
             builder_boost_factor: def.builder_boost_factor,
+            builder_min_bid_gwei: def.builder_min_bid_gwei,
             prefer_builder_proposals: def.prefer_builder_proposals,
             index: None,
         })
@@ -363,6 +441,12 @@ impl InitializedValidator {
             SigningMethod::LocalKeystore { voting_keypair, .. } => &voting_keypair.pk,
             SigningMethod::Web3Signer {
                 voting_public_key, ..
+            }
+            | SigningMethod::RemoteSigner {
+                voting_public_key, ..
+            }
+            | SigningMethod::DistributedKeystore {
+                voting_public_key, ..
             } => voting_public_key,
         }
     }
@@ -394,6 +478,79 @@ fn build_web3_signer_url(base_url: &str, voting_public_key: &PublicKey) -> Resul
     Url::parse(base_url)?.join(&format!("api/v1/eth2/sign/{}", voting_public_key))
 }
 
+/// Builds clients for the peers of a distributed validator, after checking that the shares are
+/// consistent with `voting_public_key`.
+fn build_distributed_peers(
+    distributed: &DistributedDefinition,
+    share_public_key: &PublicKey,
+    voting_public_key: &PublicKey,
+    config: &Config,
+) -> Result<Vec<DistributedPeer>, Error> {
+    let peers = distributed
+        .peers
+        .iter()
+        .map(|peer| {
+            let url = SensitiveUrl::parse(&peer.url)
+                .map_err(|e| Error::InvalidDistributedPeerUrl(format!("{:?}", e)))?;
+            let api_token =
+                ValidatorClientHttpClient::load_api_token_from_file(&peer.api_token_path)
+                    .map_err(|e| Error::UnableToReadDistributedPeerApiToken(format!("{:?}", e)))?;
+            let request_timeout = peer
+                .request_timeout_ms
+                .map(Duration::from_millis)
+                .unwrap_or(DEFAULT_REMOTE_SIGNER_REQUEST_TIMEOUT);
+            let http_client = build_web3_signer_client(
+                peer.root_certificate_path.clone(),
+                None,
+                None,
+                request_timeout,
+                config.web3_signer_keep_alive_timeout,
+                config.web3_signer_max_idle_connections,
+            )?;
+            let client = ValidatorClientHttpClient::from_components(
+                url,
+                http_client,
+                api_token.as_str().to_string(),
+            )
+            .map_err(|e| Error::InvalidDistributedPeerUrl(format!("{:?}", e)))?;
+
+            Ok(DistributedPeer {
+                share_index: peer.share_index,
+                share_public_key: peer.share_public_key.clone(),
+                signer: Arc::new(HttpDistributedPeer::new(client)),
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    // Any `threshold` of the shares must combine into the voting public key, so check the first
+    // `threshold` of them. This also detects a threshold that is too low.
+    let shares = std::iter::once((distributed.share_index, share_public_key.clone()))
+        .chain(
+            peers
+                .iter()
+                .map(|peer| (peer.share_index, peer.share_public_key.clone())),
+        )
+        .take(distributed.threshold)
+        .collect::<Vec<_>>();
+    if distributed.threshold == 0 || shares.len() < distributed.threshold {
+        return Err(Error::InvalidDistributedShares(format!(
+            "threshold {} with {} shares",
+            distributed.threshold,
+            peers.len() + 1
+        )));
+    }
+    let combined_public_key = PublicKey::combine_shares(&shares)
+        .map_err(|e| Error::InvalidDistributedShares(format!("{:?}", e)))?;
+    if combined_public_key != *voting_public_key {
+        return Err(Error::InvalidDistributedShares(format!(
+            "shares combine into {:?}",
+            combined_public_key
+        )));
+    }
+
+    Ok(peers)
+}
+
 fn build_web3_signer_client(
     root_certificate_path: Option<PathBuf>,
     client_identity_path: Option<PathBuf>,
@@ -484,6 +641,8 @@ pub struct InitializedValidators {
     validators: HashMap<PublicKeyBytes, InitializedValidator>,
     /// The clients used for communications with a remote signer.
     web3_signer_client_map: Option<HashMap<Web3SignerDefinition, Client>>,
+    /// The signers used for remote signers other than Web3Signer.
+    remote_signer_map: HashMap<RemoteSignerDefinition, Arc<dyn RemoteSigner>>,
     /// For logging via `slog`.
     log: Logger,
     config: Config,
@@ -502,6 +661,7 @@ impl InitializedValidators {
             definitions,
             validators: HashMap::default(),
             web3_signer_client_map: None,
+            remote_signer_map: HashMap::new(),
             config,
             log,
         };
@@ -624,7 +784,9 @@ impl InitializedValidators {
 
                     Some(KeystoreAndPassword { keystore, password })
                 }
-                SigningDefinition::Web3Signer(_) if !is_local_keystore => {
+                SigningDefinition::Web3Signer(_) | SigningDefinition::RemoteSigner(_)
+                    if !is_local_keystore =>
+                {
                     def.enabled = false;
                     None
                 }
@@ -662,6 +824,12 @@ impl InitializedValidators {
                 ref voting_keystore_lockfile,
                 ref voting_keystore,
                 ..
+            }
+            | SigningMethod::DistributedKeystore {
+                ref voting_keystore_path,
+                ref voting_keystore_lockfile,
+                ref voting_keystore,
+                ..
             } = *initialized_validator.signing_method
             {
                 // Drop the lock file so that it may be deleted. This is particularly important on
@@ -849,6 +1017,14 @@ impl InitializedValidators {
             .and_then(|v| v.builder_boost_factor)
     }
 
+    /// Returns the `builder_min_bid_gwei` for a given public key specified in the
+    /// `ValidatorDefinitions`.
+    pub fn builder_min_bid_gwei(&self, public_key: &PublicKeyBytes) -> Option<u64> {
+        self.validators
+            .get(public_key)
+            .and_then(|v| v.builder_min_bid_gwei)
+    }
+
     /// Returns the `prefer_builder_proposals` for a given public key specified in the
     /// `ValidatorDefinitions`.
     pub fn prefer_builder_proposals(&self, public_key: &PublicKeyBytes) -> Option<bool> {
@@ -885,6 +1061,7 @@ impl InitializedValidators {
         gas_limit: Option<u64>,
         builder_proposals: Option<bool>,
         builder_boost_factor: Option<u64>,
+        builder_min_bid_gwei: Option<u64>,
         prefer_builder_proposals: Option<bool>,
         graffiti: Option<GraffitiString>,
     ) -> Result<(), Error> {
@@ -910,6 +1087,9 @@ impl InitializedValidators {
             if let Some(builder_boost_factor) = builder_boost_factor {
                 def.builder_boost_factor = Some(builder_boost_factor);
             }
+            if let Some(builder_min_bid_gwei) = builder_min_bid_gwei {
+                def.builder_min_bid_gwei = Some(builder_min_bid_gwei);
+            }
             if let Some(prefer_builder_proposals) = prefer_builder_proposals {
                 def.prefer_builder_proposals = Some(prefer_builder_proposals);
             }
@@ -934,6 +1114,9 @@ impl InitializedValidators {
             if let Some(builder_boost_factor) = builder_boost_factor {
                 val.builder_boost_factor = Some(builder_boost_factor);
             }
+            if let Some(builder_min_bid_gwei) = builder_min_bid_gwei {
+                val.builder_min_bid_gwei = Some(builder_min_bid_gwei);
+            }
             if let Some(prefer_builder_proposals) = prefer_builder_proposals {
                 val.prefer_builder_proposals = Some(prefer_builder_proposals);
             }
@@ -1124,7 +1307,7 @@ impl InitializedValidators {
                     definitions_map.insert(*key_store.uuid(), def);
                 }
                 // Remote signer validators don't interact with the key cache.
-                SigningDefinition::Web3Signer { .. } => (),
+                SigningDefinition::Web3Signer { .. } | SigningDefinition::RemoteSigner(_) => (),
             }
         }
 
@@ -1151,6 +1334,7 @@ impl InitializedValidators {
                     voting_keystore_password_path,
                     voting_keystore_password,
                     voting_keystore_path,
+                    distributed,
                 } => {
                     let pw = if let Some(p) = voting_keystore_password {
                         p.as_ref().to_vec().into()
@@ -1164,11 +1348,23 @@ impl InitializedValidators {
                             .to_vec()
                             .into()
                     };
+                    // The keystore of a distributed validator holds a share of the voting
+                    // keypair.
+                    let public_key = if distributed.is_some() {
+                        key_stores
+                            .get(voting_keystore_path)
+                            .and_then(Keystore::public_key)
+                            .ok_or_else(|| {
+                                Error::InvalidKeystorePublicKey(voting_keystore_path.clone())
+                            })?
+                    } else {
+                        def.voting_public_key.clone()
+                    };
                     passwords.push(pw);
-                    public_keys.push(def.voting_public_key.clone());
+                    public_keys.push(public_key);
                 }
                 // Remote signer validators don't interact with the key cache.
-                SigningDefinition::Web3Signer { .. } => (),
+                SigningDefinition::Web3Signer { .. } | SigningDefinition::RemoteSigner(_) => (),
             };
         }
 
@@ -1248,6 +1444,7 @@ impl InitializedValidators {
                             &mut key_cache,
                             &mut key_stores,
                             &mut None,
+                            &mut HashMap::new(),
                             &self.config,
                         )
                         .await
@@ -1293,12 +1490,14 @@ impl InitializedValidators {
                             }
                         }
                     }
-                    SigningDefinition::Web3Signer(Web3SignerDefinition { .. }) => {
+                    SigningDefinition::Web3Signer(Web3SignerDefinition { .. })
+                    | SigningDefinition::RemoteSigner(_) => {
                         match InitializedValidator::from_definition(
                             def.clone(),
                             &mut key_cache,
                             &mut key_stores,
                             &mut self.web3_signer_client_map,
+                            &mut self.remote_signer_map,
                             &self.config,
                         )
                         .await
@@ -1341,7 +1540,7 @@ impl InitializedValidators {
                         }
                     }
                     // Remote signers do not interact with the key cache.
-                    SigningDefinition::Web3Signer { .. } => (),
+                    SigningDefinition::Web3Signer { .. } | SigningDefinition::RemoteSigner(_) => (),
                 }
 
                 info!(
@@ -1420,7 +1619,7 @@ impl InitializedValidators {
                     }
                 }
                 // Remote signers don't have passwords.
-                SigningDefinition::Web3Signer { .. } => (),
+                SigningDefinition::Web3Signer { .. } | SigningDefinition::RemoteSigner(_) => (),
             };
         }
 
@@ -1,5 +1,6 @@
 mod attestation_service;
 mod beacon_node_fallback;
+mod beacon_node_health;
 mod block_service;
 mod check_synced;
 mod cli;
@@ -26,6 +27,7 @@ use initialized_validators::InitializedValidators;
 use lighthouse_metrics::set_gauge;
 use monitoring_api::{MonitoringHttpClient, ProcessType};
 use sensitive_url::SensitiveUrl;
+use signing_audit_log::{SigningAuditLog, SIGNING_AUDIT_LOG_DIR};
 pub use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
 
 use crate::beacon_node_fallback::{
@@ -46,6 +48,7 @@ use http_api::ApiSecret;
 use notifier::spawn_notifier;
 use parking_lot::RwLock;
 use preparation_service::{PreparationService, PreparationServiceBuilder};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
 use reqwest::Certificate;
 use slog::{debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
@@ -102,6 +105,7 @@ pub struct ProductionValidatorClient<E: EthSpec> {
     http_api_listen_addr: Option<SocketAddr>,
     config: Config,
     beacon_nodes: Arc<BeaconNodeFallback<SystemTimeSlotClock, E>>,
+    proposer_nodes: Arc<BeaconNodeFallback<SystemTimeSlotClock, E>>,
     genesis_time: u64,
 }
 
@@ -294,6 +298,12 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             .checked_sub(1)
             .ok_or_else(|| "No beacon nodes defined.".to_string())?;
 
+        let beacon_nodes_auth_header = config
+            .beacon_nodes_api_token_file
+            .as_ref()
+            .map(load_api_token_header)
+            .transpose()?;
+
         let beacon_node_setup = |x: (usize, &SensitiveUrl)| {
             let i = x.0;
             let url = x.1;
@@ -309,6 +319,14 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
                 }
             }
 
+            // Authenticate with beacon nodes which require a bearer token.
+            if let Some(auth_header) = &beacon_nodes_auth_header {
+                let mut headers = HeaderMap::new();
+                headers.insert(AUTHORIZATION, auth_header.clone());
+                beacon_node_http_client_builder =
+                    beacon_node_http_client_builder.default_headers(headers);
+            }
+
             let beacon_node_http_client = beacon_node_http_client_builder
                 // Set default timeout to be the full slot duration.
                 .timeout(slot_duration)
@@ -396,6 +414,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             candidates,
             config.broadcast_topics.clone(),
             context.eth2_config.spec.clone(),
+            context.executor.clone(),
             log.clone(),
         );
 
@@ -403,6 +422,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             proposer_candidates,
             config.broadcast_topics.clone(),
             context.eth2_config.spec.clone(),
+            context.executor.clone(),
             log.clone(),
         );
 
@@ -427,6 +447,22 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
         proposer_nodes.set_slot_clock(slot_clock.clone());
 
         let beacon_nodes = Arc::new(beacon_nodes);
+        let signing_audit_log = config
+            .signing_audit_log
+            .clone()
+            .map(|audit_log_config| {
+                let dir = config.validator_dir.join(SIGNING_AUDIT_LOG_DIR);
+                SigningAuditLog::open_or_create(&dir, audit_log_config, &log).map_err(|e| {
+                    format!(
+                        "Failed to open signing audit log: {:?}.\n\
+                         Check the log with `lighthouse account validator signing-audit-log \
+                         verify`",
+                        e
+                    )
+                })
+            })
+            .transpose()?;
+
         start_fallback_updater_service(context.clone(), beacon_nodes.clone())?;
 
         let proposer_nodes = Arc::new(proposer_nodes);
@@ -446,6 +482,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
         let validator_store = Arc::new(ValidatorStore::new(
             validators,
             slashing_protection,
+            signing_audit_log,
             genesis_validators_root,
             context.eth2_config.spec.clone(),
             doppelganger_service.clone(),
@@ -546,6 +583,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             http_api_listen_addr: None,
             genesis_time,
             beacon_nodes,
+            proposer_nodes,
         })
     }
 
@@ -564,6 +602,8 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
                 task_executor: self.context.executor.clone(),
                 api_secret,
                 validator_store: Some(self.validator_store.clone()),
+                beacon_nodes: Some(self.beacon_nodes.clone()),
+                proposer_nodes: Some(self.proposer_nodes.clone()),
                 validator_dir: Some(self.config.validator_dir.clone()),
                 secrets_dir: Some(self.config.secrets_dir.clone()),
                 graffiti_file: self.config.graffiti_file.clone(),
@@ -849,6 +889,16 @@ pub fn load_pem_certificate<P: AsRef<Path>>(pem_path: P) -> Result<Certificate,
     Certificate::from_pem(&buf).map_err(|e| format!("Unable to parse certificate: {}", e))
 }
 
+/// Loads the bearer token at `token_path` as the value of an `Authorization` header.
+pub fn load_api_token_header<P: AsRef<Path>>(token_path: P) -> Result<HeaderValue, String> {
+    let token = std::fs::read_to_string(&token_path)
+        .map_err(|e| format!("Unable to read API token file: {}", e))?;
+    let mut header = HeaderValue::from_str(&format!("Bearer {}", token.trim()))
+        .map_err(|e| format!("Invalid API token: {}", e))?;
+    header.set_sensitive(true);
+    Ok(header)
+}
+
 // Given the various graffiti control methods, determine the graffiti that will be used for
 // the next block produced by the validator with the given public key.
 pub fn determine_graffiti(
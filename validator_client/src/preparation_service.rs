@@ -339,17 +339,20 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
 
         // Post the proposer preparations to the BN.
         let preparation_data_len = preparation_data.len();
-        let preparation_entries = preparation_data.as_slice();
+        let preparation_entries = Arc::new(preparation_data);
         match self
             .beacon_nodes
             .request(
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
                 ApiTopic::Subscriptions,
-                |beacon_node| async move {
-                    beacon_node
-                        .post_validator_prepare_beacon_proposer(preparation_entries)
-                        .await
+                move |beacon_node| {
+                    let preparation_entries = preparation_entries.clone();
+                    async move {
+                        beacon_node
+                            .post_validator_prepare_beacon_proposer(&preparation_entries)
+                            .await
+                    }
                 },
             )
             .await
@@ -2,12 +2,20 @@
 //!
 //! - Via a local `Keypair`.
 //! - Via a remote signer (Web3Signer)
+//! - Via some other remote signer, through the `RemoteSigner` trait.
+//! - Via a share of the key in a local `Keypair`, combined with partial signatures from peers.
 
 use crate::http_metrics::metrics;
+use distributed::PartialSigningRequest;
+use eth2::lighthouse_vc::types::{
+    PartialSignatureForkInfo, PartialSignatureMessage, PartialSignatureRequest,
+};
 use eth2_keystore::Keystore;
 use lockfile::Lockfile;
 use parking_lot::Mutex;
+use remote_signer::RemoteSigningRequest;
 use reqwest::{header::ACCEPT, Client};
+use signing_audit_log::MessageType as AuditMessageType;
 use std::path::PathBuf;
 use std::sync::Arc;
 use task_executor::TaskExecutor;
@@ -15,8 +23,12 @@ use types::*;
 use url::Url;
 use web3signer::{ForkInfo, SigningRequest, SigningResponse};
 
+pub use distributed::{DistributedPeer, HttpDistributedPeer, PartialSigner};
+pub use remote_signer::{HttpRemoteSigner, RemoteSigner};
 pub use web3signer::Web3SignerObject;
 
+mod distributed;
+mod remote_signer;
 mod web3signer;
 
 #[derive(Debug, PartialEq)]
@@ -27,6 +39,25 @@ pub enum Error {
     },
     Web3SignerRequestFailed(String),
     Web3SignerJsonParsingFailed(String),
+    RemoteSignerRequestFailed(String),
+    RemoteSignerJsonParsingFailed(String),
+    /// The remote signer signed a different signing root than the one requested.
+    RemoteSignerSigningRootMismatch {
+        expected: Hash256,
+        received: Hash256,
+    },
+    /// The remote signer returned a signature that is not valid for the validator's key.
+    RemoteSignerInvalidSignature,
+    /// Too few valid partial signatures were collected for a distributed validator.
+    DistributedInsufficientPartialSignatures {
+        threshold: usize,
+        received: usize,
+        /// The errors from peers, by share index.
+        peer_errors: Vec<(u64, Error)>,
+    },
+    DistributedSigningFailed(String),
+    /// The combined partial signatures are not valid for the distributed validator's key.
+    DistributedInvalidSignature,
     ShuttingDown,
     TokioJoin(String),
     MergeForkNotSupported,
@@ -51,6 +82,112 @@ pub enum SignableMessage<'a, E: EthSpec, Payload: AbstractExecPayload<E> = FullP
 }
 
 impl<'a, E: EthSpec, Payload: AbstractExecPayload<E>> SignableMessage<'a, E, Payload> {
+    /// Maps the message into a Web3Signer type.
+    fn into_web3signer_object(self) -> Result<Web3SignerObject<'a, E, Payload>, Error> {
+        Ok(match self {
+            SignableMessage::RandaoReveal(epoch) => Web3SignerObject::RandaoReveal { epoch },
+            SignableMessage::BeaconBlock(block) => Web3SignerObject::beacon_block(block)?,
+            SignableMessage::AttestationData(a) => Web3SignerObject::Attestation(a),
+            SignableMessage::SignedAggregateAndProof(a) => Web3SignerObject::AggregateAndProof(a),
+            SignableMessage::SelectionProof(slot) => Web3SignerObject::AggregationSlot { slot },
+            SignableMessage::SyncSelectionProof(s) => {
+                Web3SignerObject::SyncAggregatorSelectionData(s)
+            }
+            SignableMessage::SyncCommitteeSignature {
+                beacon_block_root,
+                slot,
+            } => Web3SignerObject::SyncCommitteeMessage {
+                beacon_block_root,
+                slot,
+            },
+            SignableMessage::SignedContributionAndProof(c) => {
+                Web3SignerObject::ContributionAndProof(c)
+            }
+            SignableMessage::ValidatorRegistration(v) => Web3SignerObject::ValidatorRegistration(v),
+            SignableMessage::VoluntaryExit(e) => Web3SignerObject::VoluntaryExit(e),
+        })
+    }
+
+    /// Maps the message into the type sent to the peers of a distributed validator.
+    fn to_partial_signature_message(&self) -> PartialSignatureMessage<E> {
+        match self {
+            SignableMessage::RandaoReveal(epoch) => {
+                PartialSignatureMessage::RandaoReveal { epoch: *epoch }
+            }
+            SignableMessage::BeaconBlock(block) => PartialSignatureMessage::BeaconBlock {
+                block_header: block.block_header(),
+            },
+            SignableMessage::AttestationData(a) => {
+                PartialSignatureMessage::Attestation((*a).clone())
+            }
+            SignableMessage::SignedAggregateAndProof(a) => {
+                let aggregate_and_proof = match a {
+                    AggregateAndProofRef::Base(a) => AggregateAndProof::Base((*a).clone()),
+                    AggregateAndProofRef::Electra(a) => AggregateAndProof::Electra((*a).clone()),
+                };
+                PartialSignatureMessage::AggregateAndProof(aggregate_and_proof)
+            }
+            SignableMessage::SelectionProof(slot) => {
+                PartialSignatureMessage::AggregationSlot { slot: *slot }
+            }
+            SignableMessage::SyncSelectionProof(s) => {
+                PartialSignatureMessage::SyncAggregatorSelectionData((*s).clone())
+            }
+            SignableMessage::SyncCommitteeSignature {
+                beacon_block_root,
+                slot,
+            } => PartialSignatureMessage::SyncCommitteeMessage {
+                beacon_block_root: *beacon_block_root,
+                slot: *slot,
+            },
+            SignableMessage::SignedContributionAndProof(c) => {
+                PartialSignatureMessage::ContributionAndProof((*c).clone())
+            }
+            SignableMessage::ValidatorRegistration(v) => {
+                PartialSignatureMessage::ValidatorRegistration((*v).clone())
+            }
+            SignableMessage::VoluntaryExit(e) => {
+                PartialSignatureMessage::VoluntaryExit((*e).clone())
+            }
+        }
+    }
+
+    /// Returns the type of the message, as recorded in the signing audit log.
+    pub fn audit_message_type(&self) -> AuditMessageType {
+        match self {
+            SignableMessage::RandaoReveal(_) => AuditMessageType::RandaoReveal,
+            SignableMessage::BeaconBlock(_) => AuditMessageType::BeaconBlock,
+            SignableMessage::AttestationData(_) => AuditMessageType::Attestation,
+            SignableMessage::SignedAggregateAndProof(_) => AuditMessageType::AggregateAndProof,
+            SignableMessage::SelectionProof(_) => AuditMessageType::SelectionProof,
+            SignableMessage::SyncSelectionProof(_) => AuditMessageType::SyncSelectionProof,
+            SignableMessage::SyncCommitteeSignature { .. } => {
+                AuditMessageType::SyncCommitteeMessage
+            }
+            SignableMessage::SignedContributionAndProof(_) => {
+                AuditMessageType::ContributionAndProof
+            }
+            SignableMessage::ValidatorRegistration(_) => AuditMessageType::ValidatorRegistration,
+            SignableMessage::VoluntaryExit(_) => AuditMessageType::VoluntaryExit,
+        }
+    }
+
+    /// Returns the slot of the contained message, if it has one.
+    pub fn slot(&self) -> Option<Slot> {
+        match self {
+            SignableMessage::BeaconBlock(b) => Some(b.slot()),
+            SignableMessage::AttestationData(a) => Some(a.slot),
+            SignableMessage::SignedAggregateAndProof(a) => Some(a.aggregate().data().slot),
+            SignableMessage::SelectionProof(slot) => Some(*slot),
+            SignableMessage::SyncSelectionProof(s) => Some(s.slot),
+            SignableMessage::SyncCommitteeSignature { slot, .. } => Some(*slot),
+            SignableMessage::SignedContributionAndProof(c) => Some(c.contribution.slot),
+            SignableMessage::RandaoReveal(_)
+            | SignableMessage::ValidatorRegistration(_)
+            | SignableMessage::VoluntaryExit(_) => None,
+        }
+    }
+
     /// Returns the `SignedRoot` for the contained message.
     ///
     /// The actual `SignedRoot` trait is not used since it also requires a `TreeHash` impl, which is
@@ -74,9 +211,6 @@ impl<'a, E: EthSpec, Payload: AbstractExecPayload<E>> SignableMessage<'a, E, Pay
 }
 
 /// A method used by a validator to sign messages.
-///
-/// Presently there is only a single variant, however we expect more variants to arise (e.g.,
-/// remote signing).
 pub enum SigningMethod {
     /// A validator that is defined by an EIP-2335 keystore on the local filesystem.
     LocalKeystore {
@@ -93,6 +227,23 @@ pub enum SigningMethod {
         http_client: Client,
         voting_public_key: PublicKey,
     },
+    /// A validator that defers to some other remote signer for signing.
+    RemoteSigner {
+        signer: Arc<dyn RemoteSigner>,
+        voting_public_key: PublicKey,
+    },
+    /// A validator whose key is split into threshold shares across several validator clients, one
+    /// of which is defined by an EIP-2335 keystore on the local filesystem.
+    DistributedKeystore {
+        voting_keystore_path: PathBuf,
+        voting_keystore_lockfile: Mutex<Option<Lockfile>>,
+        voting_keystore: Keystore,
+        share_keypair: Arc<Keypair>,
+        share_index: u64,
+        threshold: usize,
+        peers: Vec<DistributedPeer>,
+        voting_public_key: PublicKey,
+    },
 }
 
 /// The additional information used to construct a signature. Mostly used for protection from replay
@@ -125,9 +276,25 @@ impl SigningMethod {
         match self {
             // Slashing protection is ALWAYS required for local keys. DO NOT TURN THIS OFF.
             SigningMethod::LocalKeystore { .. } => true,
+            // Slashing protection is always required for distributed validators, since peers only
+            // refuse messages which are slashable with respect to their own history.
+            SigningMethod::DistributedKeystore { .. } => true,
             // Slashing protection is only required for remote signer keys when the configuration
             // dictates that it is desired.
             SigningMethod::Web3Signer { .. } => enable_web3signer_slashing_protection,
+            // Slashing protection is always required for other remote signers, since they are only
+            // given the signing root of each message.
+            SigningMethod::RemoteSigner { .. } => true,
+        }
+    }
+
+    /// Returns a name for this signing method, for use in metrics and the signing audit log.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SigningMethod::LocalKeystore { .. } => metrics::LOCAL_KEYSTORE,
+            SigningMethod::Web3Signer { .. } => metrics::WEB3SIGNER,
+            SigningMethod::RemoteSigner { .. } => metrics::REMOTE_SIGNER,
+            SigningMethod::DistributedKeystore { .. } => metrics::DISTRIBUTED_KEYSTORE,
         }
     }
 
@@ -190,37 +357,7 @@ impl SigningMethod {
                 let _timer =
                     metrics::start_timer_vec(&metrics::SIGNING_TIMES, &[metrics::WEB3SIGNER]);
 
-                // Map the message into a Web3Signer type.
-                let object = match signable_message {
-                    SignableMessage::RandaoReveal(epoch) => {
-                        Web3SignerObject::RandaoReveal { epoch }
-                    }
-                    SignableMessage::BeaconBlock(block) => Web3SignerObject::beacon_block(block)?,
-                    SignableMessage::AttestationData(a) => Web3SignerObject::Attestation(a),
-                    SignableMessage::SignedAggregateAndProof(a) => {
-                        Web3SignerObject::AggregateAndProof(a)
-                    }
-                    SignableMessage::SelectionProof(slot) => {
-                        Web3SignerObject::AggregationSlot { slot }
-                    }
-                    SignableMessage::SyncSelectionProof(s) => {
-                        Web3SignerObject::SyncAggregatorSelectionData(s)
-                    }
-                    SignableMessage::SyncCommitteeSignature {
-                        beacon_block_root,
-                        slot,
-                    } => Web3SignerObject::SyncCommitteeMessage {
-                        beacon_block_root,
-                        slot,
-                    },
-                    SignableMessage::SignedContributionAndProof(c) => {
-                        Web3SignerObject::ContributionAndProof(c)
-                    }
-                    SignableMessage::ValidatorRegistration(v) => {
-                        Web3SignerObject::ValidatorRegistration(v)
-                    }
-                    SignableMessage::VoluntaryExit(e) => Web3SignerObject::VoluntaryExit(e),
-                };
+                let object = signable_message.into_web3signer_object()?;
 
                 // Determine the Web3Signer message type.
                 let message_type = object.message_type();
@@ -256,6 +393,127 @@ impl SigningMethod {
 
                 Ok(response.signature)
             }
+            SigningMethod::RemoteSigner {
+                signer,
+                voting_public_key,
+            } => {
+                let _timer =
+                    metrics::start_timer_vec(&metrics::SIGNING_TIMES, &[metrics::REMOTE_SIGNER]);
+
+                let request = RemoteSigningRequest {
+                    pubkey: voting_public_key.compress(),
+                    message_type: signable_message.into_web3signer_object()?.message_type(),
+                    fork_info,
+                    signing_root,
+                };
+
+                let response = signer.sign(request).await?;
+
+                // Never trust the remote signer, check that it signed what was requested.
+                if response.signing_root != signing_root {
+                    return Err(Error::RemoteSignerSigningRootMismatch {
+                        expected: signing_root,
+                        received: response.signing_root,
+                    });
+                }
+
+                let voting_public_key = voting_public_key.clone();
+                let signature = response.signature;
+                // Spawn a blocking task to verify the signature. This avoids blocking the core
+                // tokio executor.
+                executor
+                    .spawn_blocking_handle(
+                        move || {
+                            if signature.verify(&voting_public_key, signing_root) {
+                                Ok(signature)
+                            } else {
+                                Err(Error::RemoteSignerInvalidSignature)
+                            }
+                        },
+                        "remote_signer_verify",
+                    )
+                    .ok_or(Error::ShuttingDown)?
+                    .await
+                    .map_err(|e| Error::TokioJoin(e.to_string()))?
+            }
+            SigningMethod::DistributedKeystore {
+                share_keypair,
+                share_index,
+                threshold,
+                peers,
+                voting_public_key,
+                ..
+            } => {
+                let _timer = metrics::start_timer_vec(
+                    &metrics::SIGNING_TIMES,
+                    &[metrics::DISTRIBUTED_KEYSTORE],
+                );
+
+                let pubkey = voting_public_key.compress();
+                let body = serde_json::to_value(PartialSignatureRequest {
+                    pubkey,
+                    fork_info: fork_info.map(|fork_info| PartialSignatureForkInfo {
+                        fork: fork_info.fork,
+                        genesis_validators_root: fork_info.genesis_validators_root,
+                    }),
+                    message: signable_message.to_partial_signature_message(),
+                })
+                .map_err(|e| Error::DistributedSigningFailed(e.to_string()))?;
+                let request = PartialSigningRequest {
+                    pubkey,
+                    signing_root,
+                    body,
+                };
+
+                let share_keypair = share_keypair.clone();
+                // Spawn a blocking task to produce the partial signature. This avoids blocking the
+                // core tokio executor.
+                let own_partial = executor
+                    .spawn_blocking_handle(
+                        move || share_keypair.sk.sign(signing_root),
+                        "distributed_keystore_signer",
+                    )
+                    .ok_or(Error::ShuttingDown)?
+                    .await
+                    .map_err(|e| Error::TokioJoin(e.to_string()))?;
+
+                distributed::sign_distributed(
+                    (*share_index, own_partial),
+                    peers,
+                    *threshold,
+                    voting_public_key,
+                    request,
+                    executor,
+                )
+                .await
+            }
         }
     }
+    /// Return the partial signature of `signing_root` by the share of a distributed validator held
+    /// by this validator client, for one of its peers.
+    ///
+    /// The caller is responsible for computing `signing_root` and checking slashing protection.
+    pub async fn get_partial_signature(
+        &self,
+        signing_root: Hash256,
+        executor: &TaskExecutor,
+    ) -> Result<Signature, Error> {
+        let SigningMethod::DistributedKeystore { share_keypair, .. } = self else {
+            return Err(Error::DistributedSigningFailed(
+                "not a distributed validator".to_string(),
+            ));
+        };
+
+        let share_keypair = share_keypair.clone();
+        // Spawn a blocking task to produce the partial signature. This avoids blocking the core
+        // tokio executor.
+        executor
+            .spawn_blocking_handle(
+                move || share_keypair.sk.sign(signing_root),
+                "distributed_keystore_partial_signer",
+            )
+            .ok_or(Error::ShuttingDown)?
+            .await
+            .map_err(|e| Error::TokioJoin(e.to_string()))
+    }
 }
@@ -0,0 +1,308 @@
+//! Provides signing for distributed validators, whose key is split into BLS threshold shares held
+//! by several validator clients.
+//!
+//! Each validator client signs with its own share and requests partial signatures from its peers
+//! via their HTTP APIs. Peers are sent the full message, which they check against their own
+//! slashing protection before signing. Once `threshold` valid partial signatures have been
+//! collected they are combined into a signature by the validator's key.
+
+use super::remote_signer::RemoteSigningResponse;
+use super::Error;
+use eth2::lighthouse_vc::http_client::ValidatorClientHttpClient;
+use futures::future::BoxFuture;
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::sync::Arc;
+use task_executor::TaskExecutor;
+use types::*;
+
+/// A validator client holding another share of a distributed validator.
+pub struct DistributedPeer {
+    pub share_index: u64,
+    pub share_public_key: PublicKey,
+    pub signer: Arc<dyn PartialSigner>,
+}
+
+/// A request for a partial signature over `signing_root` by the share of the distributed validator
+/// `pubkey` held by a peer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialSigningRequest {
+    pub pubkey: PublicKeyBytes,
+    pub signing_root: Hash256,
+    /// The `PartialSignatureRequest` sent to the peer, encoded as JSON. The peer computes the
+    /// signing root from the message it contains, rather than trusting `signing_root`.
+    pub body: serde_json::Value,
+}
+
+/// A peer which produces partial signatures with its share of a distributed validator.
+///
+/// Implementations don't need to verify their own responses, each of them is checked against the
+/// requested signing root and the share's public key.
+pub trait PartialSigner: Send + Sync {
+    fn sign_partial(
+        &self,
+        request: PartialSigningRequest,
+    ) -> BoxFuture<'_, Result<RemoteSigningResponse, Error>>;
+}
+
+/// A `PartialSigner` served by the HTTP API of a peer validator client.
+pub struct HttpDistributedPeer {
+    client: ValidatorClientHttpClient,
+}
+
+impl HttpDistributedPeer {
+    pub fn new(client: ValidatorClientHttpClient) -> Self {
+        Self { client }
+    }
+}
+
+impl PartialSigner for HttpDistributedPeer {
+    fn sign_partial(
+        &self,
+        request: PartialSigningRequest,
+    ) -> BoxFuture<'_, Result<RemoteSigningResponse, Error>> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .post_lighthouse_distributed_sign(&request.body)
+                .await
+                .map_err(|e| Error::RemoteSignerRequestFailed(e.to_string()))?
+                .data;
+
+            Ok(RemoteSigningResponse {
+                signing_root: response.signing_root,
+                signature: response.signature,
+            })
+        })
+    }
+}
+
+/// Returns a signature by `voting_public_key` over `request.signing_root`, combined from
+/// `own_partial` (the partial signature of the local share) and those of `peers`.
+pub async fn sign_distributed(
+    own_partial: (u64, Signature),
+    peers: &[DistributedPeer],
+    threshold: usize,
+    voting_public_key: &PublicKey,
+    request: PartialSigningRequest,
+    executor: &TaskExecutor,
+) -> Result<Signature, Error> {
+    let signing_root = request.signing_root;
+    let mut partials = vec![own_partial];
+    let mut peer_errors = vec![];
+
+    // Request partial signatures from all peers at once, then use the first valid ones to arrive
+    // so that a single slow or unavailable peer does not delay signing.
+    let mut responses = peers
+        .iter()
+        .map(|peer| {
+            let request = request.clone();
+            async move { (peer, peer.signer.sign_partial(request).await) }
+        })
+        .collect::<FuturesUnordered<_>>();
+
+    while partials.len() < threshold {
+        let Some((peer, result)) = responses.next().await else {
+            break;
+        };
+
+        match verify_partial(peer, signing_root, result, executor).await {
+            Ok(signature) => partials.push((peer.share_index, signature)),
+            Err(e) => peer_errors.push((peer.share_index, e)),
+        }
+    }
+
+    if partials.len() < threshold {
+        return Err(Error::DistributedInsufficientPartialSignatures {
+            threshold,
+            received: partials.len(),
+            peer_errors,
+        });
+    }
+
+    let voting_public_key = voting_public_key.clone();
+    // Spawn a blocking task to combine the partial signatures. This avoids blocking the core
+    // tokio executor.
+    executor
+        .spawn_blocking_handle(
+            move || {
+                let signature = Signature::combine_partials(&partials)
+                    .map_err(|e| Error::DistributedSigningFailed(format!("{:?}", e)))?;
+
+                // Shares which are inconsistent with the validator's key (e.g., due to a wrong
+                // threshold) combine into some unrelated signature.
+                if signature.verify(&voting_public_key, signing_root) {
+                    Ok(signature)
+                } else {
+                    Err(Error::DistributedInvalidSignature)
+                }
+            },
+            "distributed_signer_combine",
+        )
+        .ok_or(Error::ShuttingDown)?
+        .await
+        .map_err(|e| Error::TokioJoin(e.to_string()))?
+}
+
+/// Checks that `result` holds a valid partial signature over `signing_root` by the share of
+/// `peer`.
+async fn verify_partial(
+    peer: &DistributedPeer,
+    signing_root: Hash256,
+    result: Result<RemoteSigningResponse, Error>,
+    executor: &TaskExecutor,
+) -> Result<Signature, Error> {
+    let response = result?;
+
+    if response.signing_root != signing_root {
+        return Err(Error::RemoteSignerSigningRootMismatch {
+            expected: signing_root,
+            received: response.signing_root,
+        });
+    }
+
+    let share_public_key = peer.share_public_key.clone();
+    let signature = response.signature;
+    executor
+        .spawn_blocking_handle(
+            move || {
+                if signature.verify(&share_public_key, signing_root) {
+                    Ok(signature)
+                } else {
+                    Err(Error::RemoteSignerInvalidSignature)
+                }
+            },
+            "distributed_signer_verify",
+        )
+        .ok_or(Error::ShuttingDown)?
+        .await
+        .map_err(|e| Error::TokioJoin(e.to_string()))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signing_method::remote_signer::{MockRemoteSigner, MockRemoteSignerBehaviour};
+    use task_executor::test_utils::TestRuntime;
+
+    struct Cluster {
+        voting_keypair: Keypair,
+        own_share: (u64, Keypair),
+        peers: Vec<DistributedPeer>,
+        peer_signers: Vec<Arc<MockRemoteSigner>>,
+    }
+
+    impl Cluster {
+        fn new(threshold: usize, share_count: usize) -> Self {
+            let voting_keypair = Keypair::random();
+            let mut shares = voting_keypair
+                .sk
+                .split(threshold, share_count)
+                .unwrap()
+                .into_iter()
+                .map(|(index, share)| (index, Keypair::from_components(share.public_key(), share)));
+            let own_share = shares.next().unwrap();
+
+            let (peers, peer_signers) = shares
+                .map(|(share_index, share)| {
+                    let share_public_key = share.pk.clone();
+                    let signer = Arc::new(MockRemoteSigner::with_keypairs([(
+                        voting_keypair.pk.compress(),
+                        share,
+                    )]));
+                    let peer = DistributedPeer {
+                        share_index,
+                        share_public_key,
+                        signer: signer.clone(),
+                    };
+                    (peer, signer)
+                })
+                .unzip();
+
+            Self {
+                voting_keypair,
+                own_share,
+                peers,
+                peer_signers,
+            }
+        }
+
+        fn set_behaviour(&self, peer: usize, behaviour: MockRemoteSignerBehaviour) {
+            self.peer_signers[peer].set_behaviour(behaviour);
+        }
+
+        async fn sign(&self, threshold: usize, signing_root: Hash256) -> Result<Signature, Error> {
+            let runtime = TestRuntime::default();
+            let (share_index, share) = &self.own_share;
+            let request = PartialSigningRequest {
+                pubkey: self.voting_keypair.pk.compress(),
+                signing_root,
+                body: serde_json::Value::Null,
+            };
+
+            sign_distributed(
+                (*share_index, share.sk.sign(signing_root)),
+                &self.peers,
+                threshold,
+                &self.voting_keypair.pk,
+                request,
+                &runtime.task_executor,
+            )
+            .await
+        }
+    }
+
+    #[tokio::test]
+    async fn all_peers_honest() {
+        let cluster = Cluster::new(3, 4);
+        let signing_root = Hash256::repeat_byte(0x01);
+
+        assert_eq!(
+            cluster.sign(3, signing_root).await,
+            Ok(cluster.voting_keypair.sk.sign(signing_root))
+        );
+    }
+
+    #[tokio::test]
+    async fn tolerates_faulty_peers_up_to_threshold() {
+        let cluster = Cluster::new(3, 5);
+        cluster.set_behaviour(0, MockRemoteSignerBehaviour::Unavailable);
+        cluster.set_behaviour(1, MockRemoteSignerBehaviour::WrongKey);
+        let signing_root = Hash256::repeat_byte(0x01);
+
+        assert_eq!(
+            cluster.sign(3, signing_root).await,
+            Ok(cluster.voting_keypair.sk.sign(signing_root))
+        );
+    }
+
+    #[tokio::test]
+    async fn insufficient_partial_signatures() {
+        let cluster = Cluster::new(2, 3);
+        cluster.set_behaviour(0, MockRemoteSignerBehaviour::Unavailable);
+        cluster.set_behaviour(1, MockRemoteSignerBehaviour::WrongSigningRoot);
+
+        let result = cluster.sign(2, Hash256::repeat_byte(0x01)).await;
+        match result {
+            Err(Error::DistributedInsufficientPartialSignatures {
+                threshold,
+                received,
+                peer_errors,
+            }) => {
+                assert_eq!(threshold, 2);
+                assert_eq!(received, 1);
+                assert_eq!(peer_errors.len(), 2);
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn threshold_too_low() {
+        let cluster = Cluster::new(3, 4);
+
+        assert_eq!(
+            cluster.sign(2, Hash256::repeat_byte(0x01)).await,
+            Err(Error::DistributedInvalidSignature)
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//! Provides a pluggable backend for remote signers other than Web3Signer (e.g., a signing service
+//! fronting an HSM).
+//!
+//! Only the signing root of a message is sent to the remote signer, alongside enough context for it
+//! to log or filter requests. Every response is checked to be a valid signature over the requested
+//! signing root before it is used.
+//!
+//! The only transport provided is `HttpRemoteSigner`, which speaks JSON over HTTP(S) with optional
+//! mutual TLS. The wire protocol is documented in the book (`validator-remote-signer.md`). Other
+//! transports (e.g., gRPC) can be supported by implementing `RemoteSigner`.
+
+#[cfg(test)]
+use super::distributed::{PartialSigner, PartialSigningRequest};
+use super::web3signer::{ForkInfo, MessageType};
+use super::Error;
+use futures::future::BoxFuture;
+#[cfg(test)]
+use parking_lot::Mutex;
+use reqwest::{header::ACCEPT, Client};
+use serde::{Deserialize, Serialize};
+#[cfg(test)]
+use std::collections::HashMap;
+use types::*;
+use url::Url;
+
+/// A request for a signature over `signing_root` by the key `pubkey`.
+///
+/// Serialized as the JSON body POSTed by `HttpRemoteSigner`, e.g.
+/// `{"pubkey": "0x..", "type": "ATTESTATION", "fork_info": {..}, "signing_root": "0x.."}`.
+/// `fork_info` is omitted for messages signed independently of the fork.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RemoteSigningRequest {
+    pub pubkey: PublicKeyBytes,
+    #[serde(rename = "type")]
+    pub message_type: MessageType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fork_info: Option<ForkInfo>,
+    pub signing_root: Hash256,
+}
+
+/// The response to a `RemoteSigningRequest`, deserialized by `HttpRemoteSigner` from a JSON body
+/// such as `{"signing_root": "0x..", "signature": "0x.."}`.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RemoteSigningResponse {
+    /// The signing root that was signed, which must match the one requested.
+    pub signing_root: Hash256,
+    pub signature: Signature,
+}
+
+/// A signing backend holding the keys of some validators outside of this process.
+///
+/// Implementations don't need to verify their own responses, `SigningMethod` checks each of them
+/// against the requested signing root and the validator's public key.
+pub trait RemoteSigner: Send + Sync {
+    fn sign(
+        &self,
+        request: RemoteSigningRequest,
+    ) -> BoxFuture<'_, Result<RemoteSigningResponse, Error>>;
+}
+
+/// A `RemoteSigner` served over HTTP(S) with JSON bodies, rather than gRPC.
+///
+/// Each `RemoteSigningRequest` is POSTed to `signing_url`, and any response status other than a
+/// success is treated as a failed request. Mutual TLS is achieved by building `http_client` with a
+/// client identity.
+pub struct HttpRemoteSigner {
+    signing_url: Url,
+    http_client: Client,
+}
+
+impl HttpRemoteSigner {
+    pub fn new(signing_url: Url, http_client: Client) -> Self {
+        Self {
+            signing_url,
+            http_client,
+        }
+    }
+}
+
+impl RemoteSigner for HttpRemoteSigner {
+    fn sign(
+        &self,
+        request: RemoteSigningRequest,
+    ) -> BoxFuture<'_, Result<RemoteSigningResponse, Error>> {
+        Box::pin(async move {
+            self.http_client
+                .post(self.signing_url.clone())
+                .header(ACCEPT, "application/json")
+                .json(&request)
+                .send()
+                .await
+                .map_err(|e| Error::RemoteSignerRequestFailed(e.to_string()))?
+                .error_for_status()
+                .map_err(|e| Error::RemoteSignerRequestFailed(e.to_string()))?
+                .json()
+                .await
+                .map_err(|e| Error::RemoteSignerJsonParsingFailed(e.to_string()))
+        })
+    }
+}
+
+/// Determines how a `MockRemoteSigner` answers requests.
+#[cfg(test)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MockRemoteSignerBehaviour {
+    /// Sign the requested signing root with the requested key.
+    Honest,
+    /// Sign and return a signing root other than the requested one.
+    WrongSigningRoot,
+    /// Sign the requested signing root with a key other than the requested one.
+    WrongKey,
+    /// Fail all requests.
+    Unavailable,
+}
+
+/// An in-process `RemoteSigner` that holds its keys in memory, intended for testing.
+#[cfg(test)]
+pub struct MockRemoteSigner {
+    keypairs: HashMap<PublicKeyBytes, Keypair>,
+    behaviour: Mutex<MockRemoteSignerBehaviour>,
+}
+
+#[cfg(test)]
+impl MockRemoteSigner {
+    pub fn new(keypairs: impl IntoIterator<Item = Keypair>) -> Self {
+        Self::with_keypairs(
+            keypairs
+                .into_iter()
+                .map(|keypair| (keypair.pk.compress(), keypair)),
+        )
+    }
+
+    /// Creates a signer which signs requests for each public key with the associated keypair,
+    /// which need not be the keypair of that public key (e.g., a share of a distributed validator).
+    pub fn with_keypairs(keypairs: impl IntoIterator<Item = (PublicKeyBytes, Keypair)>) -> Self {
+        Self {
+            keypairs: keypairs.into_iter().collect(),
+            behaviour: Mutex::new(MockRemoteSignerBehaviour::Honest),
+        }
+    }
+
+    pub fn set_behaviour(&self, behaviour: MockRemoteSignerBehaviour) {
+        *self.behaviour.lock() = behaviour;
+    }
+
+    fn sign_sync(
+        &self,
+        pubkey: &PublicKeyBytes,
+        signing_root: Hash256,
+    ) -> Result<RemoteSigningResponse, Error> {
+        let keypair = self.keypairs.get(pubkey).ok_or_else(|| {
+            Error::RemoteSignerRequestFailed(format!("unknown public key {:?}", pubkey))
+        })?;
+
+        match *self.behaviour.lock() {
+            MockRemoteSignerBehaviour::Honest => Ok(RemoteSigningResponse {
+                signing_root,
+                signature: keypair.sk.sign(signing_root),
+            }),
+            MockRemoteSignerBehaviour::WrongSigningRoot => {
+                let signing_root = Hash256::repeat_byte(0x42);
+                Ok(RemoteSigningResponse {
+                    signing_root,
+                    signature: keypair.sk.sign(signing_root),
+                })
+            }
+            MockRemoteSignerBehaviour::WrongKey => Ok(RemoteSigningResponse {
+                signing_root,
+                signature: Keypair::random().sk.sign(signing_root),
+            }),
+            MockRemoteSignerBehaviour::Unavailable => Err(Error::RemoteSignerRequestFailed(
+                "remote signer unavailable".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+impl RemoteSigner for MockRemoteSigner {
+    fn sign(
+        &self,
+        request: RemoteSigningRequest,
+    ) -> BoxFuture<'_, Result<RemoteSigningResponse, Error>> {
+        let result = self.sign_sync(&request.pubkey, request.signing_root);
+        Box::pin(async move { result })
+    }
+}
+
+#[cfg(test)]
+impl PartialSigner for MockRemoteSigner {
+    fn sign_partial(
+        &self,
+        request: PartialSigningRequest,
+    ) -> BoxFuture<'_, Result<RemoteSigningResponse, Error>> {
+        let result = self.sign_sync(&request.pubkey, request.signing_root);
+        Box::pin(async move { result })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signing_method::{SignableMessage, SigningMethod};
+    use std::sync::Arc;
+    use task_executor::test_utils::TestRuntime;
+
+    type E = MainnetEthSpec;
+
+    fn remote_signer_method(signer: Arc<MockRemoteSigner>, keypair: &Keypair) -> SigningMethod {
+        SigningMethod::RemoteSigner {
+            signer,
+            voting_public_key: keypair.pk.clone(),
+        }
+    }
+
+    async fn sign_randao(
+        signing_method: &SigningMethod,
+        signing_root: Hash256,
+    ) -> Result<Signature, Error> {
+        let runtime = TestRuntime::default();
+        signing_method
+            .get_signature_from_root::<E, FullPayload<E>>(
+                SignableMessage::RandaoReveal(Epoch::new(1)),
+                signing_root,
+                &runtime.task_executor,
+                None,
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn honest_remote_signer() {
+        let keypair = Keypair::random();
+        let signer = Arc::new(MockRemoteSigner::new([keypair.clone()]));
+        let signing_method = remote_signer_method(signer, &keypair);
+        let signing_root = Hash256::repeat_byte(0x01);
+
+        let signature = sign_randao(&signing_method, signing_root).await.unwrap();
+        assert_eq!(signature, keypair.sk.sign(signing_root));
+        assert!(signing_method.requires_local_slashing_protection(false));
+    }
+
+    #[tokio::test]
+    async fn remote_signer_signing_root_mismatch() {
+        let keypair = Keypair::random();
+        let signer = Arc::new(MockRemoteSigner::new([keypair.clone()]));
+        signer.set_behaviour(MockRemoteSignerBehaviour::WrongSigningRoot);
+        let signing_method = remote_signer_method(signer, &keypair);
+        let signing_root = Hash256::repeat_byte(0x01);
+
+        assert_eq!(
+            sign_randao(&signing_method, signing_root).await,
+            Err(Error::RemoteSignerSigningRootMismatch {
+                expected: signing_root,
+                received: Hash256::repeat_byte(0x42),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn remote_signer_invalid_signature() {
+        let keypair = Keypair::random();
+        let signer = Arc::new(MockRemoteSigner::new([keypair.clone()]));
+        signer.set_behaviour(MockRemoteSignerBehaviour::WrongKey);
+        let signing_method = remote_signer_method(signer, &keypair);
+
+        assert_eq!(
+            sign_randao(&signing_method, Hash256::repeat_byte(0x01)).await,
+            Err(Error::RemoteSignerInvalidSignature)
+        );
+    }
+
+    #[tokio::test]
+    async fn remote_signer_unknown_key() {
+        let keypair = Keypair::random();
+        let signer = Arc::new(MockRemoteSigner::new([]));
+        let signing_method = remote_signer_method(signer, &keypair);
+
+        assert!(matches!(
+            sign_randao(&signing_method, Hash256::repeat_byte(0x01)).await,
+            Err(Error::RemoteSignerRequestFailed(_))
+        ));
+    }
+}
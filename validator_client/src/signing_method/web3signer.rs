@@ -31,7 +31,7 @@ pub enum ForkName {
     Electra,
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct ForkInfo {
     pub fork: Fork,
     pub genesis_validators_root: Hash256,
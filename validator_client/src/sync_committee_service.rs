@@ -185,7 +185,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 |beacon_node| async move {
                     match beacon_node.get_beacon_blocks_root(BlockId::Head).await {
                         Ok(Some(block)) if block.execution_optimistic == Some(false) => {
-                            Ok(block)
+                            Ok((block, beacon_node.to_string()))
                         }
                         Ok(Some(_)) => {
                             Err(format!("To sign sync committee messages for slot {slot} a non-optimistic head block is required"))
@@ -197,8 +197,8 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
             )
             .await;
 
-        let block_root = match response {
-            Ok(block) => block.data.root,
+        let (block_root, beacon_node) = match response {
+            Ok((block, beacon_node)) => (block.data.root, beacon_node),
             Err(errs) => {
                 warn!(
                     log,
@@ -217,7 +217,12 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
         self.inner.context.executor.spawn(
             async move {
                 service
-                    .publish_sync_committee_signatures(slot, block_root, validator_duties)
+                    .publish_sync_committee_signatures(
+                        slot,
+                        block_root,
+                        &beacon_node,
+                        validator_duties,
+                    )
                     .map(|_| ())
                     .await
             },
@@ -249,6 +254,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
         &self,
         slot: Slot,
         beacon_block_root: Hash256,
+        beacon_node: &str,
         validator_duties: Vec<SyncDuty>,
     ) -> Result<(), ()> {
         let log = self.context.log();
@@ -262,6 +268,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                     beacon_block_root,
                     duty.validator_index,
                     &duty.pubkey,
+                    Some(beacon_node),
                 )
                 .await
             {
@@ -292,21 +299,32 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
         });
 
         // Execute all the futures in parallel, collecting any successful results.
-        let committee_signatures = &join_all(signature_futures)
-            .await
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>();
+        //
+        // The signatures are shared with the request to each beacon node, which may outlive this
+        // function.
+        let committee_signatures = Arc::new(
+            join_all(signature_futures)
+                .await
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>(),
+        );
 
         self.beacon_nodes
             .request(
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
                 ApiTopic::SyncCommittee,
-                |beacon_node| async move {
-                    beacon_node
-                        .post_beacon_pool_sync_committee_signatures(committee_signatures)
-                        .await
+                {
+                    let committee_signatures = committee_signatures.clone();
+                    move |beacon_node| {
+                        let committee_signatures = committee_signatures.clone();
+                        async move {
+                            beacon_node
+                                .post_beacon_pool_sync_committee_signatures(&committee_signatures)
+                                .await
+                        }
+                    }
                 },
             )
             .await
@@ -369,7 +387,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
 
         let log = self.context.log();
 
-        let contribution = &self
+        let (contribution, beacon_node) = &self
             .beacon_nodes
             .first_success(
                 RequireSynced::No,
@@ -384,6 +402,9 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                     beacon_node
                         .get_validator_sync_committee_contribution::<E>(&sync_contribution_data)
                         .await
+                        .map(|response| {
+                            response.map(|response| (response.data, beacon_node.to_string()))
+                        })
                 },
             )
             .await
@@ -403,8 +424,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                     "slot" => slot,
                     "beacon_block_root" => ?beacon_block_root,
                 );
-            })?
-            .data;
+            })?;
 
         // Create futures to produce signed contributions.
         let signature_futures = subnet_aggregators.into_iter().map(
@@ -416,6 +436,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                         aggregator_pk,
                         contribution.clone(),
                         selection_proof,
+                        Some(beacon_node.as_str()),
                     )
                     .await
                 {
@@ -581,9 +602,9 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
             "Posting sync subscriptions to BN";
             "count" => subscriptions.len(),
         );
-        let subscriptions_slice = &subscriptions;
+        let subscriptions = Arc::new(subscriptions);
 
-        for subscription in subscriptions_slice {
+        for subscription in subscriptions.iter() {
             debug!(
                 log,
                 "Subscription";
@@ -599,10 +620,13 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
                 ApiTopic::Subscriptions,
-                |beacon_node| async move {
-                    beacon_node
-                        .post_validator_sync_committee_subscriptions(subscriptions_slice)
-                        .await
+                move |beacon_node| {
+                    let subscriptions = subscriptions.clone();
+                    async move {
+                        beacon_node
+                            .post_validator_sync_committee_subscriptions(&subscriptions)
+                            .await
+                    }
                 },
             )
             .await
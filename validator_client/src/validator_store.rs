@@ -6,7 +6,14 @@ use crate::{
     Config,
 };
 use account_utils::validator_definitions::{PasswordStorage, ValidatorDefinition};
+use eth2::lighthouse_vc::types::{
+    PartialSignatureForkInfo, PartialSignatureMessage, PartialSignatureRequest,
+    PartialSignatureResponse,
+};
 use parking_lot::{Mutex, RwLock};
+use signing_audit_log::{
+    Entry as AuditEntry, MessageType as AuditMessageType, Outcome as AuditOutcome, SigningAuditLog,
+};
 use slashing_protection::{
     interchange::Interchange, InterchangeError, NotSafe, Safe, SlashingDatabase,
 };
@@ -36,10 +43,19 @@ pub enum Error {
     UnknownPubkey(PublicKeyBytes),
     Slashable(NotSafe),
     SameData,
-    GreaterThanCurrentSlot { slot: Slot, current_slot: Slot },
-    GreaterThanCurrentEpoch { epoch: Epoch, current_epoch: Epoch },
+    GreaterThanCurrentSlot {
+        slot: Slot,
+        current_slot: Slot,
+    },
+    GreaterThanCurrentEpoch {
+        epoch: Epoch,
+        current_epoch: Epoch,
+    },
     UnableToSignAttestation(AttestationError),
     UnableToSign(SigningError),
+    /// A partial signature was requested for a validator which is not a distributed validator.
+    NotDistributed(PublicKeyBytes),
+    InvalidPartialSignatureRequest(String),
 }
 
 impl From<SigningError> for Error {
@@ -62,6 +78,7 @@ pub struct ValidatorStore<T, E: EthSpec> {
     validators: Arc<RwLock<InitializedValidators>>,
     slashing_protection: SlashingDatabase,
     slashing_protection_last_prune: Arc<Mutex<Epoch>>,
+    signing_audit_log: Option<Arc<SigningAuditLog>>,
     genesis_validators_root: Hash256,
     spec: Arc<ChainSpec>,
     log: Logger,
@@ -84,6 +101,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     pub fn new(
         validators: InitializedValidators,
         slashing_protection: SlashingDatabase,
+        signing_audit_log: Option<SigningAuditLog>,
         genesis_validators_root: Hash256,
         spec: ChainSpec,
         doppelganger_service: Option<Arc<DoppelgangerService>>,
@@ -96,6 +114,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             validators: Arc::new(RwLock::new(validators)),
             slashing_protection,
             slashing_protection_last_prune: Arc::new(Mutex::new(Epoch::new(0))),
+            signing_audit_log: signing_audit_log.map(Arc::new),
             genesis_validators_root,
             spec: Arc::new(spec),
             log,
@@ -131,6 +150,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         self.doppelganger_service.is_some()
     }
 
+    /// Returns the signing audit log, if it is enabled.
+    pub fn signing_audit_log(&self) -> Option<&SigningAuditLog> {
+        self.signing_audit_log.as_deref()
+    }
+
     pub fn initialized_validators(&self) -> Arc<RwLock<InitializedValidators>> {
         self.validators.clone()
     }
@@ -384,6 +408,101 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         }
     }
 
+    /// Returns an entry describing a request to sign `signable_message`, or `None` if the signing
+    /// audit log is disabled.
+    fn signing_audit_entry<Payload: AbstractExecPayload<E>>(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        signing_method: &SigningMethod,
+        signable_message: &SignableMessage<'_, E, Payload>,
+        signing_context: &SigningContext,
+        beacon_node: Option<&str>,
+        outcome: AuditOutcome,
+    ) -> Option<AuditEntry> {
+        self.signing_audit_log.as_ref()?;
+
+        let domain_hash = signing_context.domain_hash(&self.spec);
+        Some(AuditEntry {
+            pubkey: validator_pubkey,
+            message_type: signable_message.audit_message_type(),
+            signing_root: signable_message.signing_root(domain_hash),
+            slot: signable_message.slot(),
+            epoch: Some(signing_context.epoch),
+            signing_method: signing_method.name().into(),
+            beacon_node: beacon_node.map(Into::into),
+            outcome,
+        })
+    }
+
+    /// Append `entry` to the signing audit log.
+    ///
+    /// The append waits for the entry to be synced to disk, so it runs on a blocking thread.
+    /// Failing to write the entry is logged, but does not prevent signing.
+    async fn write_signing_audit_entry(&self, entry: Option<AuditEntry>) {
+        let (Some(signing_audit_log), Some(entry)) = (&self.signing_audit_log, entry) else {
+            return;
+        };
+
+        let signing_audit_log = signing_audit_log.clone();
+        let result = match self.task_executor.spawn_blocking_handle(
+            move || signing_audit_log.append(entry),
+            "signing_audit_log_append",
+        ) {
+            Some(handle) => match handle.await {
+                Ok(result) => result.map(|_| ()).map_err(|e| format!("{:?}", e)),
+                Err(e) => Err(format!("blocking task failed: {:?}", e)),
+            },
+            None => Err("runtime shutting down".to_string()),
+        };
+
+        if let Err(e) = result {
+            error!(
+                self.log,
+                "Failed to write to signing audit log";
+                "error" => e,
+            );
+            metrics::inc_counter(&metrics::SIGNING_AUDIT_LOG_ERRORS_TOTAL);
+        }
+    }
+
+    /// Obtain a signature for `signable_message` from `signing_method`, recording the request in
+    /// the signing audit log.
+    ///
+    /// `beacon_node` identifies the beacon node which provided the message, if any.
+    async fn get_signature<Payload: AbstractExecPayload<E>>(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        signing_method: &SigningMethod,
+        signable_message: SignableMessage<'_, E, Payload>,
+        signing_context: SigningContext,
+        beacon_node: Option<&str>,
+    ) -> Result<Signature, SigningError> {
+        let mut audit_entry = self.signing_audit_entry(
+            validator_pubkey,
+            signing_method,
+            &signable_message,
+            &signing_context,
+            beacon_node,
+            AuditOutcome::Signed,
+        );
+
+        let result = signing_method
+            .get_signature::<E, Payload>(
+                signable_message,
+                signing_context,
+                &self.spec,
+                &self.task_executor,
+            )
+            .await;
+
+        if let (Some(entry), Err(_)) = (&mut audit_entry, &result) {
+            entry.outcome = AuditOutcome::Failed;
+        }
+        self.write_signing_audit_entry(audit_entry).await;
+
+        result
+    }
+
     pub async fn randao_reveal(
         &self,
         validator_pubkey: PublicKeyBytes,
@@ -392,12 +511,13 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
         let signing_context = self.signing_context(Domain::Randao, signing_epoch);
 
-        let signature = signing_method
-            .get_signature::<E, BlindedPayload<E>>(
+        let signature = self
+            .get_signature::<BlindedPayload<E>>(
+                validator_pubkey,
+                &signing_method,
                 SignableMessage::RandaoReveal(signing_epoch),
                 signing_context,
-                &self.spec,
-                &self.task_executor,
+                None,
             )
             .await?;
 
@@ -475,6 +595,14 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .or(self.builder_boost_factor)
     }
 
+    /// Returns the minimum builder bid in Gwei for the given public key, as specified in
+    /// validator_definitions.yml. If `None`, the beacon node applies its own minimum, if any.
+    pub fn get_builder_min_bid_gwei(&self, validator_pubkey: &PublicKeyBytes) -> Option<u64> {
+        self.validators
+            .read()
+            .builder_min_bid_gwei(validator_pubkey)
+    }
+
     /// Returns a `bool` for the given public key that denotes whether this validator should prefer a
     /// builder payload. The priority order for fetching this value is:
     ///
@@ -554,6 +682,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         validator_pubkey: PublicKeyBytes,
         block: BeaconBlock<E, Payload>,
         current_slot: Slot,
+        beacon_node: Option<&str>,
     ) -> Result<SignedBeaconBlock<E, Payload>, Error> {
         // Make sure the block slot is not higher than the current slot to avoid potential attacks.
         if block.slot() > current_slot {
@@ -588,17 +717,30 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             Ok(Safe::Valid)
         };
 
+        if !matches!(slashing_status, Ok(Safe::Valid)) {
+            self.write_signing_audit_entry(self.signing_audit_entry(
+                validator_pubkey,
+                &signing_method,
+                &SignableMessage::BeaconBlock(&block),
+                &signing_context,
+                beacon_node,
+                AuditOutcome::Refused,
+            ))
+            .await;
+        }
+
         match slashing_status {
             // We can safely sign this block without slashing.
             Ok(Safe::Valid) => {
                 metrics::inc_counter_vec(&metrics::SIGNED_BLOCKS_TOTAL, &[metrics::SUCCESS]);
 
-                let signature = signing_method
-                    .get_signature::<E, Payload>(
+                let signature = self
+                    .get_signature::<Payload>(
+                        validator_pubkey,
+                        &signing_method,
                         SignableMessage::BeaconBlock(&block),
                         signing_context,
-                        &self.spec,
-                        &self.task_executor,
+                        beacon_node,
                     )
                     .await?;
                 Ok(SignedBeaconBlock::from_block(block, signature))
@@ -639,6 +781,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         validator_committee_position: usize,
         attestation: &mut Attestation<E>,
         current_epoch: Epoch,
+        beacon_node: Option<&str>,
     ) -> Result<(), Error> {
         // Make sure the target epoch is not higher than the current epoch to avoid potential attacks.
         if attestation.data().target.epoch > current_epoch {
@@ -667,15 +810,28 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             Ok(Safe::Valid)
         };
 
+        if !matches!(slashing_status, Ok(Safe::Valid)) {
+            self.write_signing_audit_entry(self.signing_audit_entry(
+                validator_pubkey,
+                &signing_method,
+                &SignableMessage::<E, BlindedPayload<E>>::AttestationData(attestation.data()),
+                &signing_context,
+                beacon_node,
+                AuditOutcome::Refused,
+            ))
+            .await;
+        }
+
         match slashing_status {
             // We can safely sign this attestation.
             Ok(Safe::Valid) => {
-                let signature = signing_method
-                    .get_signature::<E, BlindedPayload<E>>(
+                let signature = self
+                    .get_signature::<BlindedPayload<E>>(
+                        validator_pubkey,
+                        &signing_method,
                         SignableMessage::AttestationData(attestation.data()),
                         signing_context,
-                        &self.spec,
-                        &self.task_executor,
+                        beacon_node,
                     )
                     .await?;
                 attestation
@@ -735,12 +891,13 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         let signing_context = self.signing_context(Domain::VoluntaryExit, signing_epoch);
         let signing_method = self.doppelganger_bypassed_signing_method(validator_pubkey)?;
 
-        let signature = signing_method
-            .get_signature::<E, BlindedPayload<E>>(
+        let signature = self
+            .get_signature::<BlindedPayload<E>>(
+                validator_pubkey,
+                &signing_method,
                 SignableMessage::VoluntaryExit(&voluntary_exit),
                 signing_context,
-                &self.spec,
-                &self.task_executor,
+                None,
             )
             .await?;
 
@@ -761,14 +918,33 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
 
         let signing_method =
             self.doppelganger_bypassed_signing_method(validator_registration_data.pubkey)?;
-        let signature = signing_method
+        let result = signing_method
             .get_signature_from_root::<E, BlindedPayload<E>>(
                 SignableMessage::ValidatorRegistration(&validator_registration_data),
                 signing_root,
                 &self.task_executor,
                 None,
             )
-            .await?;
+            .await;
+
+        // Registrations are not signed with a `SigningContext`, so are recorded here rather than
+        // via `Self::get_signature`.
+        self.write_signing_audit_entry(self.signing_audit_log.as_ref().map(|_| AuditEntry {
+            pubkey: validator_registration_data.pubkey,
+            message_type: AuditMessageType::ValidatorRegistration,
+            signing_root,
+            slot: None,
+            epoch: None,
+            signing_method: signing_method.name().into(),
+            beacon_node: None,
+            outcome: if result.is_ok() {
+                AuditOutcome::Signed
+            } else {
+                AuditOutcome::Failed
+            },
+        }))
+        .await;
+        let signature = result?;
 
         metrics::inc_counter_vec(
             &metrics::SIGNED_VALIDATOR_REGISTRATIONS_TOTAL,
@@ -791,6 +967,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         aggregator_index: u64,
         aggregate: Attestation<E>,
         selection_proof: SelectionProof,
+        beacon_node: Option<&str>,
     ) -> Result<SignedAggregateAndProof<E>, Error> {
         let signing_epoch = aggregate.data().target.epoch;
         let signing_context = self.signing_context(Domain::AggregateAndProof, signing_epoch);
@@ -799,12 +976,13 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             AggregateAndProof::from_attestation(aggregator_index, aggregate, selection_proof);
 
         let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
-        let signature = signing_method
-            .get_signature::<E, BlindedPayload<E>>(
+        let signature = self
+            .get_signature::<BlindedPayload<E>>(
+                validator_pubkey,
+                &signing_method,
                 SignableMessage::SignedAggregateAndProof(message.to_ref()),
                 signing_context,
-                &self.spec,
-                &self.task_executor,
+                beacon_node,
             )
             .await?;
 
@@ -834,12 +1012,13 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         // be published on the network.
         let signing_method = self.doppelganger_bypassed_signing_method(validator_pubkey)?;
 
-        let signature = signing_method
-            .get_signature::<E, BlindedPayload<E>>(
+        let signature = self
+            .get_signature::<BlindedPayload<E>>(
+                validator_pubkey,
+                &signing_method,
                 SignableMessage::SelectionProof(slot),
                 signing_context,
-                &self.spec,
-                &self.task_executor,
+                None,
             )
             .await
             .map_err(Error::UnableToSign)?;
@@ -873,12 +1052,13 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             subcommittee_index: subnet_id.into(),
         };
 
-        let signature = signing_method
-            .get_signature::<E, BlindedPayload<E>>(
+        let signature = self
+            .get_signature::<BlindedPayload<E>>(
+                *validator_pubkey,
+                &signing_method,
                 SignableMessage::SyncSelectionProof(&message),
                 signing_context,
-                &self.spec,
-                &self.task_executor,
+                None,
             )
             .await
             .map_err(Error::UnableToSign)?;
@@ -892,6 +1072,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         beacon_block_root: Hash256,
         validator_index: u64,
         validator_pubkey: &PublicKeyBytes,
+        beacon_node: Option<&str>,
     ) -> Result<SyncCommitteeMessage, Error> {
         let signing_epoch = slot.epoch(E::slots_per_epoch());
         let signing_context = self.signing_context(Domain::SyncCommittee, signing_epoch);
@@ -899,15 +1080,16 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         // Bypass `with_validator_signing_method`: sync committee messages are not slashable.
         let signing_method = self.doppelganger_bypassed_signing_method(*validator_pubkey)?;
 
-        let signature = signing_method
-            .get_signature::<E, BlindedPayload<E>>(
+        let signature = self
+            .get_signature::<BlindedPayload<E>>(
+                *validator_pubkey,
+                &signing_method,
                 SignableMessage::SyncCommitteeSignature {
                     beacon_block_root,
                     slot,
                 },
                 signing_context,
-                &self.spec,
-                &self.task_executor,
+                beacon_node,
             )
             .await
             .map_err(Error::UnableToSign)?;
@@ -931,6 +1113,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         aggregator_pubkey: PublicKeyBytes,
         contribution: SyncCommitteeContribution<E>,
         selection_proof: SyncSelectionProof,
+        beacon_node: Option<&str>,
     ) -> Result<SignedContributionAndProof<E>, Error> {
         let signing_epoch = contribution.slot.epoch(E::slots_per_epoch());
         let signing_context = self.signing_context(Domain::ContributionAndProof, signing_epoch);
@@ -944,12 +1127,13 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             selection_proof: selection_proof.into(),
         };
 
-        let signature = signing_method
-            .get_signature::<E, BlindedPayload<E>>(
+        let signature = self
+            .get_signature::<BlindedPayload<E>>(
+                aggregator_pubkey,
+                &signing_method,
                 SignableMessage::SignedContributionAndProof(&message),
                 signing_context,
-                &self.spec,
-                &self.task_executor,
+                beacon_node,
             )
             .await
             .map_err(Error::UnableToSign)?;
@@ -962,6 +1146,209 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         Ok(SignedContributionAndProof { message, signature })
     }
 
+    /// Produces a partial signature of `request.message` by this validator client's share of a
+    /// distributed validator, for one of its peers.
+    ///
+    /// The signing root is computed from the message, rather than trusted from the peer, and
+    /// slashable messages are checked against (and recorded in) the local slashing protection
+    /// database before they are signed.
+    pub async fn sign_partial(
+        &self,
+        request: PartialSignatureRequest<E>,
+    ) -> Result<PartialSignatureResponse, Error> {
+        let PartialSignatureRequest {
+            pubkey,
+            fork_info,
+            message,
+        } = request;
+
+        let signing_method = match message {
+            PartialSignatureMessage::RandaoReveal { .. }
+            | PartialSignatureMessage::BeaconBlock { .. }
+            | PartialSignatureMessage::Attestation(_)
+            | PartialSignatureMessage::AggregateAndProof(_) => {
+                self.doppelganger_checked_signing_method(pubkey)?
+            }
+            PartialSignatureMessage::AggregationSlot { .. }
+            | PartialSignatureMessage::SyncCommitteeMessage { .. }
+            | PartialSignatureMessage::SyncAggregatorSelectionData(_)
+            | PartialSignatureMessage::ContributionAndProof(_)
+            | PartialSignatureMessage::VoluntaryExit(_)
+            | PartialSignatureMessage::ValidatorRegistration(_) => {
+                self.doppelganger_bypassed_signing_method(pubkey)?
+            }
+        };
+        if !matches!(*signing_method, SigningMethod::DistributedKeystore { .. }) {
+            return Err(Error::NotDistributed(pubkey));
+        }
+
+        let (domain_hash, signing_epoch) =
+            self.partial_signature_domain(&message, fork_info.as_ref())?;
+        let signing_root = partial_signature_signing_root(&message, domain_hash);
+
+        let mut audit_entry = self.signing_audit_log.as_ref().map(|_| {
+            let (message_type, slot) = partial_signature_audit_type_and_slot(&message);
+            AuditEntry {
+                pubkey,
+                message_type,
+                signing_root,
+                slot,
+                epoch: signing_epoch,
+                signing_method: metrics::DISTRIBUTED_KEYSTORE_PARTIAL.into(),
+                beacon_node: None,
+                outcome: AuditOutcome::Signed,
+            }
+        });
+
+        // Check for slashing conditions.
+        let slashing_status = match &message {
+            PartialSignatureMessage::BeaconBlock { block_header } => {
+                let current_slot = self
+                    .slot_clock
+                    .now_or_genesis()
+                    .unwrap_or_else(|| self.slot_clock.genesis_slot());
+                if block_header.slot > current_slot {
+                    return Err(Error::GreaterThanCurrentSlot {
+                        slot: block_header.slot,
+                        current_slot,
+                    });
+                }
+                self.slashing_protection.check_and_insert_block_proposal(
+                    &pubkey,
+                    block_header,
+                    domain_hash,
+                )
+            }
+            PartialSignatureMessage::Attestation(attestation_data) => {
+                let current_epoch = self
+                    .slot_clock
+                    .now_or_genesis()
+                    .unwrap_or_else(|| self.slot_clock.genesis_slot())
+                    .epoch(E::slots_per_epoch());
+                if attestation_data.target.epoch > current_epoch {
+                    return Err(Error::GreaterThanCurrentEpoch {
+                        epoch: attestation_data.target.epoch,
+                        current_epoch,
+                    });
+                }
+                self.slashing_protection.check_and_insert_attestation(
+                    &pubkey,
+                    attestation_data,
+                    domain_hash,
+                )
+            }
+            _ => Ok(Safe::Valid),
+        };
+
+        if slashing_status.is_err() {
+            if let Some(entry) = &mut audit_entry {
+                entry.outcome = AuditOutcome::Refused;
+            }
+            self.write_signing_audit_entry(audit_entry.clone()).await;
+        }
+
+        match slashing_status {
+            // Every share of a distributed validator signs the same message, so this validator
+            // client may already have signed it.
+            Ok(Safe::Valid) | Ok(Safe::SameData) => (),
+            Err(NotSafe::UnregisteredValidator(pk)) => {
+                warn!(
+                    self.log,
+                    "Not signing partial signature for unregistered validator";
+                    "msg" => "Carefully consider running with --init-slashing-protection (see --help)",
+                    "public_key" => format!("{:?}", pk)
+                );
+                return Err(Error::Slashable(NotSafe::UnregisteredValidator(pk)));
+            }
+            Err(e) => {
+                crit!(
+                    self.log,
+                    "Not signing slashable partial signature";
+                    "public_key" => format!("{:?}", pubkey),
+                    "error" => format!("{:?}", e)
+                );
+                return Err(Error::Slashable(e));
+            }
+        }
+
+        let result = signing_method
+            .get_partial_signature(signing_root, &self.task_executor)
+            .await;
+
+        if let (Some(entry), Err(_)) = (&mut audit_entry, &result) {
+            entry.outcome = AuditOutcome::Failed;
+        }
+        self.write_signing_audit_entry(audit_entry).await;
+        let signature = result?;
+
+        Ok(PartialSignatureResponse {
+            signing_root,
+            signature,
+        })
+    }
+
+    /// Returns the domain `message` is signed with, and the epoch it is signed for (if any),
+    /// checking that `fork_info` matches the fork this validator client would sign it for.
+    fn partial_signature_domain(
+        &self,
+        message: &PartialSignatureMessage<E>,
+        fork_info: Option<&PartialSignatureForkInfo>,
+    ) -> Result<(Hash256, Option<Epoch>), Error> {
+        let slots_per_epoch = E::slots_per_epoch();
+        let (domain, signing_epoch) = match message {
+            PartialSignatureMessage::AggregationSlot { slot } => {
+                (Domain::SelectionProof, slot.epoch(slots_per_epoch))
+            }
+            PartialSignatureMessage::AggregateAndProof(aggregate_and_proof) => (
+                Domain::AggregateAndProof,
+                aggregate_and_proof.aggregate().data().target.epoch,
+            ),
+            PartialSignatureMessage::Attestation(attestation_data) => {
+                (Domain::BeaconAttester, attestation_data.target.epoch)
+            }
+            PartialSignatureMessage::BeaconBlock { block_header } => (
+                Domain::BeaconProposer,
+                block_header.slot.epoch(slots_per_epoch),
+            ),
+            PartialSignatureMessage::RandaoReveal { epoch } => (Domain::Randao, *epoch),
+            PartialSignatureMessage::VoluntaryExit(voluntary_exit) => {
+                (Domain::VoluntaryExit, voluntary_exit.epoch)
+            }
+            PartialSignatureMessage::SyncCommitteeMessage { slot, .. } => {
+                (Domain::SyncCommittee, slot.epoch(slots_per_epoch))
+            }
+            PartialSignatureMessage::SyncAggregatorSelectionData(selection_data) => (
+                Domain::SyncCommitteeSelectionProof,
+                selection_data.slot.epoch(slots_per_epoch),
+            ),
+            PartialSignatureMessage::ContributionAndProof(contribution_and_proof) => (
+                Domain::ContributionAndProof,
+                contribution_and_proof
+                    .contribution
+                    .slot
+                    .epoch(slots_per_epoch),
+            ),
+            // Registrations are signed with the builder domain, which doesn't depend on the fork.
+            PartialSignatureMessage::ValidatorRegistration(_) => {
+                return Ok((self.spec.get_builder_domain(), None));
+            }
+        };
+
+        let signing_context = self.signing_context(domain, signing_epoch);
+        let expected_fork_info = PartialSignatureForkInfo {
+            fork: signing_context.fork,
+            genesis_validators_root: signing_context.genesis_validators_root,
+        };
+        if fork_info != Some(&expected_fork_info) {
+            return Err(Error::InvalidPartialSignatureRequest(format!(
+                "fork_info {:?} does not match {:?}",
+                fork_info, expected_fork_info
+            )));
+        }
+
+        Ok((signing_context.domain_hash(&self.spec), Some(signing_epoch)))
+    }
+
     pub fn import_slashing_protection(
         &self,
         interchange: Interchange,
@@ -1066,3 +1453,58 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         info!(self.log, "Completed pruning of slashing protection DB");
     }
 }
+
+/// Returns the signing root of `message` with respect to `domain`.
+fn partial_signature_signing_root<E: EthSpec>(
+    message: &PartialSignatureMessage<E>,
+    domain: Hash256,
+) -> Hash256 {
+    match message {
+        PartialSignatureMessage::AggregationSlot { slot } => slot.signing_root(domain),
+        PartialSignatureMessage::AggregateAndProof(a) => a.signing_root(domain),
+        PartialSignatureMessage::Attestation(a) => a.signing_root(domain),
+        PartialSignatureMessage::BeaconBlock { block_header } => block_header.signing_root(domain),
+        PartialSignatureMessage::RandaoReveal { epoch } => epoch.signing_root(domain),
+        PartialSignatureMessage::VoluntaryExit(e) => e.signing_root(domain),
+        PartialSignatureMessage::SyncCommitteeMessage {
+            beacon_block_root, ..
+        } => beacon_block_root.signing_root(domain),
+        PartialSignatureMessage::SyncAggregatorSelectionData(s) => s.signing_root(domain),
+        PartialSignatureMessage::ContributionAndProof(c) => c.signing_root(domain),
+        PartialSignatureMessage::ValidatorRegistration(v) => v.signing_root(domain),
+    }
+}
+
+/// Returns the type of `message` and its slot (if any), as recorded in the signing audit log.
+fn partial_signature_audit_type_and_slot<E: EthSpec>(
+    message: &PartialSignatureMessage<E>,
+) -> (AuditMessageType, Option<Slot>) {
+    match message {
+        PartialSignatureMessage::AggregationSlot { slot } => {
+            (AuditMessageType::SelectionProof, Some(*slot))
+        }
+        PartialSignatureMessage::AggregateAndProof(a) => (
+            AuditMessageType::AggregateAndProof,
+            Some(a.aggregate().data().slot),
+        ),
+        PartialSignatureMessage::Attestation(a) => (AuditMessageType::Attestation, Some(a.slot)),
+        PartialSignatureMessage::BeaconBlock { block_header } => {
+            (AuditMessageType::BeaconBlock, Some(block_header.slot))
+        }
+        PartialSignatureMessage::RandaoReveal { .. } => (AuditMessageType::RandaoReveal, None),
+        PartialSignatureMessage::VoluntaryExit(_) => (AuditMessageType::VoluntaryExit, None),
+        PartialSignatureMessage::SyncCommitteeMessage { slot, .. } => {
+            (AuditMessageType::SyncCommitteeMessage, Some(*slot))
+        }
+        PartialSignatureMessage::SyncAggregatorSelectionData(s) => {
+            (AuditMessageType::SyncSelectionProof, Some(s.slot))
+        }
+        PartialSignatureMessage::ContributionAndProof(c) => (
+            AuditMessageType::ContributionAndProof,
+            Some(c.contribution.slot),
+        ),
+        PartialSignatureMessage::ValidatorRegistration(_) => {
+            (AuditMessageType::ValidatorRegistration, None)
+        }
+    }
+}
@@ -141,6 +141,7 @@ impl ValidatorSpecification {
                     gas_limit,
                     builder_proposals,
                     builder_boost_factor,
+                    None,
                     prefer_builder_proposals,
                     None, // Grafitti field is not maintained between validator moves.
                 )
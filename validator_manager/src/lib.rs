@@ -10,6 +10,7 @@ pub mod common;
 pub mod create_validators;
 pub mod import_validators;
 pub mod move_validators;
+pub mod split_keystore;
 
 pub const CMD: &str = "validator_manager";
 
@@ -56,6 +57,7 @@ pub fn cli_app() -> Command {
         .subcommand(create_validators::cli_app())
         .subcommand(import_validators::cli_app())
         .subcommand(move_validators::cli_app())
+        .subcommand(split_keystore::cli_app())
 }
 
 /// Run the account manager, returning an error if the operation did not succeed.
@@ -83,6 +85,9 @@ pub fn run<E: EthSpec>(matches: &ArgMatches, env: Environment<E>) -> Result<(),
                     Some((move_validators::CMD, matches)) => {
                         move_validators::cli_run(matches, dump_config).await
                     }
+                    Some((split_keystore::CMD, matches)) => {
+                        split_keystore::cli_run(matches, dump_config).await
+                    }
                     Some((unknown, _)) => Err(format!(
                         "{} is not a valid {} command. See --help.",
                         unknown, CMD
@@ -0,0 +1,301 @@
+use super::common::*;
+use crate::DumpConfig;
+use account_utils::eth2_keystore::{Keystore, KeystoreBuilder};
+use account_utils::{random_password_string, read_password_from_user, read_password_string};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use types::*;
+
+pub const CMD: &str = "split-keystore";
+pub const KEYSTORE_FLAG: &str = "keystore-path";
+pub const PASSWORD_FILE_FLAG: &str = "password-file";
+pub const THRESHOLD_FLAG: &str = "threshold";
+pub const SHARE_COUNT_FLAG: &str = "share-count";
+pub const OUTPUT_PATH_FLAG: &str = "output-path";
+
+pub const DISTRIBUTED_VALIDATOR_FILENAME: &str = "distributed_validator.json";
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Splits the key in an EIP-2335 keystore into shares, any \"threshold\" of which are \
+                able to sign for the validator. Each share is written to its own keystore, \
+                encrypted with a random password which is written alongside it. The shares are \
+                intended to be imported into distinct validator clients which are configured \
+                as peers of each other. A JSON file describing the shares is also created.",
+        )
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .short('h')
+                .help("Prints help information")
+                .action(ArgAction::HelpLong)
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new(KEYSTORE_FLAG)
+                .long(KEYSTORE_FLAG)
+                .value_name("PATH_TO_KEYSTORE")
+                .help("The path to the EIP-2335 keystore holding the key to be split.")
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(PASSWORD_FILE_FLAG)
+                .long(PASSWORD_FILE_FLAG)
+                .value_name("PATH_TO_PASSWORD_FILE")
+                .help(
+                    "The path to a file containing the password of the keystore. If this flag \
+                    is not provided, the user will be prompted for the password.",
+                )
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(THRESHOLD_FLAG)
+                .long(THRESHOLD_FLAG)
+                .value_name("THRESHOLD")
+                .help("The number of shares required to produce a signature.")
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(SHARE_COUNT_FLAG)
+                .long(SHARE_COUNT_FLAG)
+                .value_name("SHARE_COUNT")
+                .help("The number of shares to create.")
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(OUTPUT_PATH_FLAG)
+                .long(OUTPUT_PATH_FLAG)
+                .value_name("DIRECTORY")
+                .help(
+                    "The path to a directory where the share keystores and passwords will be \
+                    created. The directory will be created if it does not exist.",
+                )
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(STDIN_INPUTS_FLAG)
+                .action(ArgAction::SetTrue)
+                .hide(cfg!(windows))
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty.")
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+}
+
+/// The CLI arguments are parsed into this struct before running the application. This step of
+/// indirection allows for testing the underlying logic without needing to parse CLI arguments.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SplitConfig {
+    pub keystore_path: PathBuf,
+    pub password_file: Option<PathBuf>,
+    pub threshold: usize,
+    pub share_count: usize,
+    pub output_path: PathBuf,
+    pub stdin_inputs: bool,
+}
+
+impl SplitConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        Ok(Self {
+            keystore_path: clap_utils::parse_required(matches, KEYSTORE_FLAG)?,
+            password_file: clap_utils::parse_optional(matches, PASSWORD_FILE_FLAG)?,
+            threshold: clap_utils::parse_required(matches, THRESHOLD_FLAG)?,
+            share_count: clap_utils::parse_required(matches, SHARE_COUNT_FLAG)?,
+            output_path: clap_utils::parse_required(matches, OUTPUT_PATH_FLAG)?,
+            stdin_inputs: cfg!(windows) || matches.get_flag(STDIN_INPUTS_FLAG),
+        })
+    }
+}
+
+/// Describes the shares of a distributed validator, as needed to configure the validator clients
+/// which hold them.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct DistributedValidatorJson {
+    pub voting_public_key: PublicKey,
+    pub threshold: usize,
+    pub shares: Vec<ShareJson>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ShareJson {
+    pub share_index: u64,
+    pub share_public_key: PublicKey,
+    pub keystore_path: PathBuf,
+    pub password_path: PathBuf,
+}
+
+pub async fn cli_run(matches: &ArgMatches, dump_config: DumpConfig) -> Result<(), String> {
+    let config = SplitConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        run(config).await
+    }
+}
+
+async fn run(config: SplitConfig) -> Result<(), String> {
+    let SplitConfig {
+        keystore_path,
+        password_file,
+        threshold,
+        share_count,
+        output_path,
+        stdin_inputs,
+    } = config;
+
+    if !output_path.exists() {
+        fs::create_dir(&output_path)
+            .map_err(|e| format!("Failed to create {:?} directory: {:?}", output_path, e))?;
+    } else if !output_path.is_dir() {
+        return Err(format!("{:?} must be a directory", output_path));
+    }
+
+    let distributed_validator_path = output_path.join(DISTRIBUTED_VALIDATOR_FILENAME);
+    if distributed_validator_path.exists() {
+        return Err(format!(
+            "{:?} already exists, refusing to overwrite",
+            distributed_validator_path
+        ));
+    }
+
+    let keystore = Keystore::from_json_file(&keystore_path)
+        .map_err(|e| format!("Unable to read keystore at {:?}: {:?}", keystore_path, e))?;
+    let password = if let Some(password_file) = &password_file {
+        read_password_string(password_file)?
+    } else {
+        eprintln!("Enter the password for {:?}:", keystore_path);
+        read_password_from_user(stdin_inputs)?
+    };
+    let keypair = keystore
+        .decrypt_keypair(password.as_ref())
+        .map_err(|e| format!("Unable to decrypt keystore: {:?}", e))?;
+
+    let shares = keypair
+        .sk
+        .split(threshold, share_count)
+        .map_err(|e| format!("Unable to split key: {:?}", e))?;
+
+    let mut share_jsons = Vec::with_capacity(shares.len());
+    for (share_index, share) in shares {
+        let share_keypair = Keypair::from_components(share.public_key(), share);
+        let share_password = random_password_string();
+        let share_keystore =
+            KeystoreBuilder::new(&share_keypair, share_password.as_ref(), String::new())
+                .and_then(|builder| builder.build())
+                .map_err(|e| {
+                    format!(
+                        "Unable to build keystore for share {}: {:?}",
+                        share_index, e
+                    )
+                })?;
+
+        let share_keystore_path = output_path.join(format!("share_{}.json", share_index));
+        let share_password_path = output_path.join(format!("share_{}.pass", share_index));
+        write_to_json_file(&share_keystore_path, &share_keystore)?;
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&share_password_path)
+            .and_then(|mut file| file.write_all(share_password.as_ref()))
+            .map_err(|e| format!("Unable to write {:?}: {:?}", share_password_path, e))?;
+
+        share_jsons.push(ShareJson {
+            share_index,
+            share_public_key: share_keypair.pk,
+            keystore_path: share_keystore_path,
+            password_path: share_password_path,
+        });
+    }
+
+    eprintln!(
+        "Split {:?} into {} shares with a threshold of {}",
+        keypair.pk, share_count, threshold
+    );
+
+    write_to_json_file(
+        &distributed_validator_path,
+        &DistributedValidatorJson {
+            voting_public_key: keypair.pk,
+            threshold,
+            shares: share_jsons,
+        },
+    )
+}
+
+// The tests use crypto and are too slow in debug.
+#[cfg(not(debug_assertions))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const PASSWORD: &str = "cats";
+
+    #[tokio::test]
+    async fn split_and_recombine() {
+        let dir = tempdir().unwrap();
+        let keypair = Keypair::random();
+        let keystore = KeystoreBuilder::new(&keypair, PASSWORD.as_bytes(), String::new())
+            .unwrap()
+            .build()
+            .unwrap();
+        let keystore_path = dir.path().join("keystore.json");
+        let password_path = dir.path().join("password");
+        write_to_json_file(&keystore_path, &keystore).unwrap();
+        fs::write(&password_path, PASSWORD).unwrap();
+        let output_path = dir.path().join("shares");
+
+        run(SplitConfig {
+            keystore_path,
+            password_file: Some(password_path),
+            threshold: 2,
+            share_count: 3,
+            output_path: output_path.clone(),
+            stdin_inputs: false,
+        })
+        .await
+        .unwrap();
+
+        let contents =
+            fs::read_to_string(output_path.join(DISTRIBUTED_VALIDATOR_FILENAME)).unwrap();
+        let distributed: DistributedValidatorJson = serde_json::from_str(&contents).unwrap();
+        assert_eq!(distributed.voting_public_key, keypair.pk);
+        assert_eq!(distributed.threshold, 2);
+        assert_eq!(distributed.shares.len(), 3);
+
+        // Any two of the shares sign on behalf of the original key.
+        let message = Hash256::repeat_byte(0x01);
+        let partials = distributed.shares[1..]
+            .iter()
+            .map(|share| {
+                let password = read_password_string(&share.password_path).unwrap();
+                let share_keypair = Keystore::from_json_file(&share.keystore_path)
+                    .unwrap()
+                    .decrypt_keypair(password.as_ref())
+                    .unwrap();
+                assert_eq!(share_keypair.pk, share.share_public_key);
+                (share.share_index, share_keypair.sk.sign(message))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            Signature::combine_partials(&partials).unwrap(),
+            keypair.sk.sign(message)
+        );
+    }
+}